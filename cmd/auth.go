@@ -0,0 +1,114 @@
+package cmd
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+	"slices"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+	"golang.org/x/term"
+)
+
+var authCmd = &cobra.Command{
+	Use:   "auth",
+	Short: "Manage the API keys saved in the OS keyring",
+}
+
+var loginCmd = &cobra.Command{
+	Use:   "login <provider>",
+	Short: "Save the API key of a provider in the OS keyring",
+	Long: `Login asks for the API key of the provider and saves it in the OS keyring:
+the login keychain on macOS, the Secret Service on Linux and the Credential
+Manager on Windows. A saved key is used instead of the environment variables
+and the config files. The key is read from stdin when it isn't a terminal.`,
+	Example: `
+  # Save the Anthropic API key
+  cryoncode auth login anthropic
+
+  # Save a key from a password manager
+  pass show openai | cryoncode auth login openai
+  `,
+	Args:      cobra.ExactArgs(1),
+	ValidArgs: providerNames(),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		provider, err := keyringProvider(args[0])
+		if err != nil {
+			return err
+		}
+		key, err := readAPIKey(provider)
+		if err != nil {
+			return err
+		}
+		if key == "" {
+			return fmt.Errorf("no API key given")
+		}
+		if err := config.SaveAPIKey(provider, key); err != nil {
+			return err
+		}
+		fmt.Printf("Saved the %s API key in the keyring\n", provider)
+		return nil
+	},
+}
+
+var logoutCmd = &cobra.Command{
+	Use:       "logout <provider>",
+	Short:     "Remove the API key of a provider from the OS keyring",
+	Args:      cobra.ExactArgs(1),
+	ValidArgs: providerNames(),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		provider, err := keyringProvider(args[0])
+		if err != nil {
+			return err
+		}
+		if err := config.SaveAPIKey(provider, ""); err != nil {
+			return err
+		}
+		fmt.Printf("Removed the %s API key from the keyring\n", provider)
+		return nil
+	},
+}
+
+func providerNames() []string {
+	names := make([]string, len(config.KeyringProviders))
+	for i, provider := range config.KeyringProviders {
+		names[i] = string(provider)
+	}
+	return names
+}
+
+func keyringProvider(name string) (models.ModelProvider, error) {
+	provider := models.ModelProvider(strings.ToLower(name))
+	if !slices.Contains(config.KeyringProviders, provider) {
+		return "", fmt.Errorf("unknown provider %q, use one of %s", name, strings.Join(providerNames(), ", "))
+	}
+	return provider, nil
+}
+
+// readAPIKey asks for the key without echoing it, or reads the first line of
+// stdin when it's piped
+func readAPIKey(provider models.ModelProvider) (string, error) {
+	fd := int(os.Stdin.Fd())
+	if !term.IsTerminal(fd) {
+		line, err := bufio.NewReader(os.Stdin).ReadString('\n')
+		if err != nil && line == "" {
+			return "", fmt.Errorf("failed to read the API key: %w", err)
+		}
+		return strings.TrimSpace(line), nil
+	}
+	fmt.Fprintf(os.Stderr, "%s API key: ", provider)
+	key, err := term.ReadPassword(fd)
+	fmt.Fprintln(os.Stderr)
+	if err != nil {
+		return "", fmt.Errorf("failed to read the API key: %w", err)
+	}
+	return strings.TrimSpace(string(key)), nil
+}
+
+func init() {
+	authCmd.AddCommand(loginCmd, logoutCmd)
+	rootCmd.AddCommand(authCmd)
+}
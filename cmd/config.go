@@ -0,0 +1,133 @@
+package cmd
+
+import (
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+var configCmd = &cobra.Command{
+	Use:   "config",
+	Short: "Read and change the user config file",
+	Long: `Config reads and changes the settings of the user config file, ~/.cryoncode.json
+or the first one found in the config directories. Keys are the dotted paths of
+the settings, such as tui.theme or agents.coder.model.`,
+}
+
+var configGetCmd = &cobra.Command{
+	Use:   "get <key>",
+	Short: "Print the value of a setting",
+	Args:  cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		values, err := readUserConfig()
+		if err != nil {
+			return err
+		}
+		value, ok := config.GetConfigValue(values, args[0])
+		if !ok {
+			return fmt.Errorf("%s is not set", args[0])
+		}
+		fmt.Println(formatConfigValue(value))
+		return nil
+	},
+}
+
+var configSetCmd = &cobra.Command{
+	Use:   "set <key> <value>",
+	Short: "Change a setting",
+	Long: `Set changes a setting of the user config file. The value is read as JSON, for
+numbers, booleans, lists and objects, unless the setting is a string. The key
+and the value are checked before the file is written.`,
+	Example: `
+  # Change the theme
+  cryoncode config set tui.theme dracula
+
+  # Give the coder more tokens
+  cryoncode config set agents.coder.maxTokens 8000
+
+  # Turn off tools
+  cryoncode config set tools.disabled '["fetch", "web_search"]'
+  `,
+	Args: cobra.ExactArgs(2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		path, err := config.UserConfigFile()
+		if err != nil {
+			return err
+		}
+		return config.SetConfigValue(path, args[0], args[1])
+	},
+}
+
+var configListCmd = &cobra.Command{
+	Use:   "list",
+	Short: "Print the settings of the user config file",
+	Long: `List prints the settings of the user config file. API keys, tokens and other
+secrets are masked, use get to print one.`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		values, err := readUserConfig()
+		if err != nil {
+			return err
+		}
+		keys, flat := config.FlattenConfig(values)
+		for _, key := range keys {
+			value := formatConfigValue(flat[key])
+			if config.IsSecretKey(key) {
+				value = maskSecret(value)
+			}
+			fmt.Printf("%s = %s\n", key, value)
+		}
+		return nil
+	},
+}
+
+var configPathCmd = &cobra.Command{
+	Use:   "path",
+	Short: "Print the path of the user config file",
+	Args:  cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		path, err := config.UserConfigFile()
+		if err != nil {
+			return err
+		}
+		fmt.Println(path)
+		return nil
+	},
+}
+
+func readUserConfig() (map[string]any, error) {
+	path, err := config.UserConfigFile()
+	if err != nil {
+		return nil, err
+	}
+	return config.ReadConfigFile(path)
+}
+
+// formatConfigValue prints strings as they are and the other values as JSON
+func formatConfigValue(value any) string {
+	if s, ok := value.(string); ok {
+		return s
+	}
+	data, err := json.Marshal(value)
+	if err != nil {
+		return fmt.Sprint(value)
+	}
+	return string(data)
+}
+
+// maskSecret hides a secret, references to environment variables are kept
+// since they don't hold the secret itself
+func maskSecret(value string) string {
+	if value == "" || strings.HasPrefix(value, "${") && strings.HasSuffix(value, "}") {
+		return value
+	}
+	return "********"
+}
+
+func init() {
+	configCmd.AddCommand(configGetCmd, configSetCmd, configListCmd, configPathCmd)
+	rootCmd.AddCommand(configCmd)
+}
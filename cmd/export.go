@@ -0,0 +1,144 @@
+package cmd
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/crypt"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/export"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+var exportCmd = &cobra.Command{
+	Use:   "export [session-id]",
+	Short: "Export a session as a Markdown transcript or as JSON",
+	Long: `Export writes the prompts, responses and tool calls of a session as Markdown,
+ready to be pasted into pull requests and issues, or as JSON that the import
+command recreates the session from. Without a session ID the most recently
+active session is exported.`,
+	Example: `
+  # Print the transcript of the last session
+  cryoncode export
+
+  # Write the transcript of a session to a file
+  cryoncode export 0f8fad5b-d9cb-469f-a165-70867728950e -o transcript.md
+
+  # Back up the last session
+  cryoncode export -f json -o session.json
+  `,
+	Args: cobra.MaximumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		output, _ := cmd.Flags().GetString("output")
+		format, _ := cmd.Flags().GetString("format")
+		if format != "markdown" && format != "json" {
+			return fmt.Errorf("invalid format %q, use markdown or json", format)
+		}
+
+		conn, err := openDatabase()
+		if err != nil {
+			return err
+		}
+		defer conn.Close()
+
+		ctx := context.Background()
+		q := db.New(conn)
+		cipher, err := crypt.Load(ctx, q)
+		if err != nil {
+			return err
+		}
+		sessions := session.NewService(q)
+
+		var sessionID string
+		if len(args) > 0 {
+			sessionID = args[0]
+		} else {
+			// Sessions are listed most recently active first
+			list, err := sessions.List(ctx)
+			if err != nil {
+				return err
+			}
+			if len(list) == 0 {
+				return fmt.Errorf("there are no sessions to export")
+			}
+			sessionID = list[0].ID
+		}
+
+		var data []byte
+		if format == "json" {
+			data, err = export.JSON(ctx, q, cipher, sessionID)
+		} else {
+			var transcript string
+			transcript, err = export.SessionMarkdown(ctx, sessions, message.NewService(q, cipher), sessionID)
+			data = []byte(transcript)
+		}
+		if err != nil {
+			return fmt.Errorf("failed to export session %s: %w", sessionID, err)
+		}
+		if output == "" {
+			_, err = os.Stdout.Write(data)
+			return err
+		}
+		return os.WriteFile(output, data, 0o644)
+	},
+}
+
+var importCmd = &cobra.Command{
+	Use:   "import <file>",
+	Short: "Import a session exported as JSON",
+	Long: `Import recreates a session from a file written by export -f json. The session
+and its messages get new IDs, so the same file can be imported more than once.`,
+	Example: `
+  # Restore a session backed up on another machine
+  cryoncode import session.json
+  `,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		data, err := os.ReadFile(args[0])
+		if err != nil {
+			return err
+		}
+
+		conn, err := openDatabase()
+		if err != nil {
+			return err
+		}
+		defer conn.Close()
+
+		cipher, err := crypt.Load(context.Background(), db.New(conn))
+		if err != nil {
+			return err
+		}
+		sessionID, err := export.Import(context.Background(), conn, cipher, data)
+		if err != nil {
+			return fmt.Errorf("failed to import %s: %w", args[0], err)
+		}
+		fmt.Println(sessionID)
+		return nil
+	},
+}
+
+// openDatabase loads the config of the working directory and connects to its
+// database, for the commands that don't start the TUI
+func openDatabase() (*sql.DB, error) {
+	cwd, err := os.Getwd()
+	if err != nil {
+		return nil, fmt.Errorf("failed to get current working directory: %v", err)
+	}
+	if _, err := config.Load(cwd, false); err != nil {
+		return nil, err
+	}
+	return db.Connect(false)
+}
+
+func init() {
+	exportCmd.Flags().StringP("output", "o", "", "File to write the export to instead of the standard output")
+	exportCmd.Flags().StringP("format", "f", "markdown", "Format of the export (markdown, json)")
+	rootCmd.AddCommand(exportCmd)
+	rootCmd.AddCommand(importCmd)
+}
@@ -1,9 +1,13 @@
 package cmd
 
 import (
+	"bufio"
 	"context"
+	"database/sql"
+	"errors"
 	"fmt"
 	"os"
+	"strings"
 	"sync"
 	"time"
 
@@ -17,8 +21,11 @@ import (
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
 	"github.com/zhenbah/cryoncode/internal/tui"
+	"github.com/zhenbah/cryoncode/internal/tui/setup"
+	"github.com/zhenbah/cryoncode/internal/tui/state"
 	"github.com/zhenbah/cryoncode/internal/version"
 	"github.com/spf13/cobra"
+	"golang.org/x/term"
 )
 
 var rootCmd = &cobra.Command{
@@ -61,8 +68,11 @@ to assist developers in writing, debugging, and understanding code directly from
 		debug, _ := cmd.Flags().GetBool("debug")
 		cwd, _ := cmd.Flags().GetString("cwd")
 		prompt, _ := cmd.Flags().GetString("prompt")
+		modelName, _ := cmd.Flags().GetString("model")
+		agentName, _ := cmd.Flags().GetString("agent")
 		outputFormat, _ := cmd.Flags().GetString("output-format")
 		quiet, _ := cmd.Flags().GetBool("quiet")
+		checkDB, _ := cmd.Flags().GetBool("check-db")
 
 		// Validate format option
 		if !format.IsValid(outputFormat) {
@@ -82,13 +92,52 @@ to assist developers in writing, debugging, and understanding code directly from
 			}
 			cwd = c
 		}
+
+		// On the first run, ask for a provider and a model rather than
+		// failing for the lack of them
+		if prompt == "" && term.IsTerminal(int(os.Stdin.Fd())) && config.NeedsSetup(cwd) {
+			if err := setup.Run(); err != nil {
+				return fmt.Errorf("setup failed: %w", err)
+			}
+		}
 		_, err := config.Load(cwd, debug)
 		if err != nil {
 			return err
 		}
+		sessionAgent := config.AgentCoder
+		if agentName != "" {
+			sessionAgent = config.AgentName(strings.ToLower(agentName))
+			if !config.IsSessionAgent(sessionAgent) {
+				return fmt.Errorf("unknown agent %q", agentName)
+			}
+		}
+		if modelName != "" {
+			model, ok := config.ResolveModel(modelName)
+			if !ok {
+				return fmt.Errorf("unknown model %q", modelName)
+			}
+			if err := config.UseAgentModel(sessionAgent, model.ID); err != nil {
+				return err
+			}
+		}
+
+		// One TUI at a time works on a database, a non-interactive prompt
+		// only adds a session of its own
+		if prompt == "" {
+			lock, err := db.LockInstance()
+			if err != nil {
+				return err
+			}
+			defer lock.Release()
+			// The last instance was killed or crashed with the database open
+			checkDB = checkDB || lock.Unclean()
+		}
 
 		// Connect DB, this will also run migrations
-		conn, err := db.Connect()
+		conn, err := db.Connect(checkDB)
+		if errors.Is(err, db.ErrDamaged) && prompt == "" {
+			conn, err = recoverDatabase(err)
+		}
 		if err != nil {
 			return err
 		}
@@ -105,6 +154,12 @@ to assist developers in writing, debugging, and understanding code directly from
 		// Defer shutdown here so it runs for both interactive and non-interactive modes
 		defer app.Shutdown()
 
+		if sessionAgent != config.AgentCoder {
+			if _, err := app.CoderAgent.UseAgent(sessionAgent); err != nil {
+				return err
+			}
+		}
+
 		// Initialize MCP tools early for both modes
 		initMCPTools(ctx, app)
 
@@ -120,11 +175,20 @@ to assist developers in writing, debugging, and understanding code directly from
 		program := tea.NewProgram(
 			tui.New(app),
 			tea.WithAltScreen(),
+			tea.WithMouseCellMotion(),
 		)
 
 		// Setup the subscriptions, this will send services events to the TUI
 		ch, cancelSubs := setupSubscriptions(app, ctx)
 
+		// Apply the changes to the config files without a restart
+		go func() {
+			defer logging.RecoverPanic("config-watcher", nil)
+			if err := config.Watch(ctx); err != nil {
+				logging.Warn("failed to watch the config files", "error", err)
+			}
+		}()
+
 		// Create a context for the TUI message handler
 		tuiCtx, tuiCancel := context.WithCancel(ctx)
 		var tuiWg sync.WaitGroup
@@ -173,6 +237,11 @@ to assist developers in writing, debugging, and understanding code directly from
 		result, err := program.Run()
 		cleanup()
 
+		// The TUI reopens on the same session next time
+		if err := state.Save(config.Get().Data.Directory); err != nil {
+			logging.Warn("failed to save the TUI state", "error", err)
+		}
+
 		if err != nil {
 			logging.Error("TUI error: %v", err)
 			return fmt.Errorf("TUI error: %v", err)
@@ -192,6 +261,25 @@ func attemptTUIRecovery(program *tea.Program) {
 	program.Quit()
 }
 
+// recoverDatabase offers to move a damaged database aside and start with an
+// empty one, the sessions of the damaged one can still be recovered from the
+// copy with the sqlite3 .recover command
+func recoverDatabase(cause error) (*sql.DB, error) {
+	fmt.Fprintf(os.Stderr, "%v\nMove it aside and start with an empty database? [y/N] ", cause)
+	answer, _ := bufio.NewReader(os.Stdin).ReadString('\n')
+	switch strings.ToLower(strings.TrimSpace(answer)) {
+	case "y", "yes":
+	default:
+		return nil, cause
+	}
+	backup, err := db.MoveDamaged()
+	if err != nil {
+		return nil, err
+	}
+	fmt.Fprintf(os.Stderr, "The damaged database was moved to %s\n", backup)
+	return db.Connect(false)
+}
+
 func initMCPTools(ctx context.Context, app *app.App) {
 	go func() {
 		defer logging.RecoverPanic("MCP-goroutine", nil)
@@ -257,6 +345,7 @@ func setupSubscriptions(app *app.App, parentCtx context.Context) (chan tea.Msg,
 	setupSubscriber(ctx, &wg, "messages", app.Messages.Subscribe, ch)
 	setupSubscriber(ctx, &wg, "permissions", app.Permissions.Subscribe, ch)
 	setupSubscriber(ctx, &wg, "coderAgent", app.CoderAgent.Subscribe, ch)
+	setupSubscriber(ctx, &wg, "config", config.Subscribe, ch)
 
 	cleanupFunc := func() {
 		logging.Info("Cancelling all subscriptions")
@@ -294,6 +383,8 @@ func init() {
 	rootCmd.Flags().BoolP("debug", "d", false, "Debug")
 	rootCmd.Flags().StringP("cwd", "c", "", "Current working directory")
 	rootCmd.Flags().StringP("prompt", "p", "", "Prompt to run in non-interactive mode")
+	rootCmd.Flags().StringP("model", "m", "", "Model of the agent for this run, by ID, name or alias")
+	rootCmd.Flags().StringP("agent", "a", "", "Agent running the sessions, the coder or one defined in the config")
 
 	// Add format flag with validation logic
 	rootCmd.Flags().StringP("output-format", "f", format.Text.String(),
@@ -302,6 +393,8 @@ func init() {
 	// Add quiet flag to hide spinner in non-interactive mode
 	rootCmd.Flags().BoolP("quiet", "q", false, "Hide spinner in non-interactive mode")
 
+	rootCmd.Flags().Bool("check-db", false, "Run the full integrity check of the database")
+
 	// Register custom validation for the format flag
 	rootCmd.RegisterFlagCompletionFunc("output-format", func(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
 		return format.SupportedFormats, cobra.ShellCompDirectiveNoFileComp
@@ -51,9 +51,19 @@ func generateSchema() map[string]any {
 		"properties": map[string]any{
 			"directory": map[string]any{
 				"type":        "string",
-				"description": "Directory where application data is stored",
+				"description": "Directory where application data is stored, a relative one is placed at the root of the git repository",
 				"default":     ".cryoncode",
 			},
+			"encrypt": map[string]any{
+				"type":        "boolean",
+				"description": "Encrypt the content of the messages with a key kept in the OS keyring",
+				"default":     false,
+			},
+			"transcripts": map[string]any{
+				"type":        "boolean",
+				"description": "Append every message to a Markdown transcript of its session in the transcripts directory of the data directory, in plain text so not with encrypt",
+				"default":     false,
+			},
 		},
 		"required": []string{"directory"},
 	}
@@ -79,11 +89,13 @@ func generateSchema() map[string]any {
 
 	schema["properties"].(map[string]any)["contextPaths"] = map[string]any{
 		"type":        "array",
-		"description": "Context paths for the application",
+		"description": "Instruction files added to the prompt, relative to the root of the project and to the working directory. A path ending with / adds every file of the directory",
 		"items": map[string]any{
 			"type": "string",
 		},
 		"default": []string{
+			"AGENTS.md",
+			"OPENCODE.md",
 			".github/copilot-instructions.md",
 			".cursorrules",
 			".cursor/rules/",
@@ -93,8 +105,8 @@ func generateSchema() map[string]any {
 			"cryoncode.local.md",
 			"Cryoncode.md",
 			"Cryoncode.local.md",
-			"OPENCODE.md",
-			"OPENCODE.local.md",
+			"CRYONCODE.md",
+			"CRYONCODE.local.md",
 		},
 	}
 
@@ -114,10 +126,40 @@ func generateSchema() map[string]any {
 					"gruvbox",
 					"monokai",
 					"onedark",
+					"solarized",
 					"tokyonight",
 					"tron",
 				},
 			},
+			"editorMaxHeight": map[string]any{
+				"type":        "integer",
+				"description": "Number of lines the input grows to before it scrolls",
+				"default":     10,
+				"minimum":     1,
+			},
+			"images": map[string]any{
+				"type":        "string",
+				"description": "Graphics protocol used to show images in messages, auto detects it from the terminal",
+				"enum":        []string{"auto", "kitty", "iterm2", "sixel", "none"},
+				"default":     "auto",
+			},
+			"inputRatio": map[string]any{
+				"type":        "number",
+				"description": "Part of the chat page height kept for the input until alt+up or alt+down change it",
+				"minimum":     0.05,
+				"maximum":     0.5,
+				"default":     0.1,
+			},
+			"timestamps": map[string]any{
+				"type":        "boolean",
+				"description": "Show the time each message was sent, t toggles it while the messages are focused",
+				"default":     false,
+			},
+			"vimMode": map[string]any{
+				"type":        "boolean",
+				"description": "Enable vim keybindings: normal and insert modes for the input, gg and y for the messages",
+				"default":     false,
+			},
 		},
 	}
 
@@ -134,10 +176,20 @@ func generateSchema() map[string]any {
 					"description": "Command to execute for the MCP server",
 				},
 				"env": map[string]any{
-					"type":        "array",
-					"description": "Environment variables for the MCP server",
-					"items": map[string]any{
-						"type": "string",
+					"description": "Environment variables for the MCP server, by name or as KEY=value strings",
+					"anyOf": []map[string]any{
+						{
+							"type": "object",
+							"additionalProperties": map[string]any{
+								"type": "string",
+							},
+						},
+						{
+							"type": "array",
+							"items": map[string]any{
+								"type": "string",
+							},
+						},
 					},
 				},
 				"args": map[string]any{
@@ -149,9 +201,13 @@ func generateSchema() map[string]any {
 				},
 				"type": map[string]any{
 					"type":        "string",
-					"description": "Type of MCP server",
+					"description": "Type of MCP server, sse when only a url is given and stdio otherwise",
 					"enum":        []string{"stdio", "sse"},
-					"default":     "stdio",
+				},
+				"enabled": map[string]any{
+					"type":        "boolean",
+					"description": "Whether the MCP server is started",
+					"default":     true,
 				},
 				"url": map[string]any{
 					"type":        "string",
@@ -165,7 +221,6 @@ func generateSchema() map[string]any {
 					},
 				},
 			},
-			"required": []string{"command"},
 		},
 	}
 
@@ -219,8 +274,11 @@ func generateSchema() map[string]any {
 			"description": "Agent configuration",
 			"properties": map[string]any{
 				"model": map[string]any{
-					"type":        "string",
-					"description": "Model ID for the agent",
+					"description": "Model ID or alias for the agent",
+					"anyOf": []map[string]any{
+						{"type": "string"},
+						{"type": "string", "description": "Alias from models.aliases"},
+					},
 				},
 				"maxTokens": map[string]any{
 					"type":        "integer",
@@ -232,8 +290,28 @@ func generateSchema() map[string]any {
 					"description": "Reasoning effort for models that support it (OpenAI, Anthropic)",
 					"enum":        []string{"low", "medium", "high"},
 				},
+				"description": map[string]any{
+					"type":        "string",
+					"description": "Description shown when choosing the agent",
+				},
+				"prompt": map[string]any{
+					"type":        "string",
+					"description": "System prompt of the agent, replacing the built-in one",
+				},
+				"tools": map[string]any{
+					"type":        "array",
+					"description": "Tools the agent may use, all of them when empty",
+					"items": map[string]any{
+						"type": "string",
+					},
+				},
+				"temperature": map[string]any{
+					"type":        "number",
+					"description": "Sampling temperature, the default of the provider when unset",
+					"minimum":     0,
+					"maximum":     2,
+				},
 			},
-			"required": []string{"model"},
 		},
 	}
 
@@ -242,7 +320,7 @@ func generateSchema() map[string]any {
 	for modelID := range models.SupportedModels {
 		modelEnum = append(modelEnum, string(modelID))
 	}
-	agentSchema["additionalProperties"].(map[string]any)["properties"].(map[string]any)["model"].(map[string]any)["enum"] = modelEnum
+	agentSchema["additionalProperties"].(map[string]any)["properties"].(map[string]any)["model"].(map[string]any)["anyOf"].([]map[string]any)[0]["enum"] = modelEnum
 
 	// Add specific agent properties
 	agentProperties := map[string]any{}
@@ -271,6 +349,244 @@ func generateSchema() map[string]any {
 		"agent": agentSchema["additionalProperties"],
 	}
 
+	schema["properties"].(map[string]any)["compaction"] = map[string]any{
+		"type":        "object",
+		"description": "When a session is summarized to fit the context window of the model",
+		"properties": map[string]any{
+			"threshold": map[string]any{
+				"type":             "number",
+				"description":      "Part of the context window used before the session is compacted",
+				"exclusiveMinimum": 0,
+				"maximum":          1,
+				"default":          0.95,
+			},
+			"keepTurns": map[string]any{
+				"type":        "integer",
+				"description": "Last turns sent verbatim after the summary instead of being summarized",
+				"minimum":     0,
+				"default":     2,
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["models"] = map[string]any{
+		"type":        "object",
+		"description": "Model settings",
+		"properties": map[string]any{
+			"aliases": map[string]any{
+				"type":        "object",
+				"description": "Short names for models, usable wherever a model is given, e.g. \"fast\": \"gpt-4o-mini\"",
+				"additionalProperties": map[string]any{
+					"type":        "string",
+					"description": "Model ID or name",
+				},
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["permissions"] = map[string]any{
+		"type":        "object",
+		"description": "Rules deciding permission requests without asking, written as tool or tool(pattern). When several match, deny applies over ask over allow",
+		"properties": map[string]any{
+			"allow": map[string]any{
+				"type": "array",
+				"items": map[string]any{
+					"type": "string",
+				},
+				"description": "Rules for requests that run without asking, e.g. \"bash(go test *)\"",
+			},
+			"ask": map[string]any{
+				"type": "array",
+				"items": map[string]any{
+					"type": "string",
+				},
+				"description": "Rules for requests that always ask, even for auto-approved tools",
+			},
+			"deny": map[string]any{
+				"type": "array",
+				"items": map[string]any{
+					"type": "string",
+				},
+				"description": "Rules for requests that are never run, e.g. \"*(**/.env)\"",
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["keybindings"] = map[string]any{
+		"type":        "object",
+		"description": "Keys of the TUI actions by action name, e.g. \"quit\": [\"ctrl+q\"]. An empty list disables the action",
+		"additionalProperties": map[string]any{
+			"type": "array",
+			"items": map[string]any{
+				"type": "string",
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["sandbox"] = map[string]any{
+		"type":        "object",
+		"description": "Container the commands of the agent run in",
+		"properties": map[string]any{
+			"enabled": map[string]any{
+				"type":        "boolean",
+				"description": "Run the commands of the bash and run_tests tools in a container",
+				"default":     false,
+			},
+			"runtime": map[string]any{
+				"type":        "string",
+				"description": "Container runtime",
+				"enum":        []string{"docker", "podman"},
+				"default":     "docker",
+			},
+			"image": map[string]any{
+				"type":        "string",
+				"description": "Image of the container, with the toolchain of the project",
+				"default":     "debian:stable-slim",
+			},
+			"network": map[string]any{
+				"type":        "boolean",
+				"description": "Give the container network access",
+				"default":     false,
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["webSearch"] = map[string]any{
+		"type":        "object",
+		"description": "Search backend of the web_search tool",
+		"properties": map[string]any{
+			"provider": map[string]any{
+				"type":        "string",
+				"description": "Search provider, brave or tavily by default when their API key is set",
+				"enum":        []string{"brave", "tavily", "searxng"},
+			},
+			"url": map[string]any{
+				"type":        "string",
+				"description": "API endpoint of the provider, required for searxng",
+			},
+			"apiKey": map[string]any{
+				"type":        "string",
+				"description": "API key of the provider",
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["tools"] = map[string]any{
+		"type":        "object",
+		"description": "Tools available to the agents",
+		"properties": map[string]any{
+			"timeout": map[string]any{
+				"type":        "integer",
+				"description": "Default timeout of a tool call in seconds, 0 disables it",
+				"minimum":     0,
+				"default":     900,
+			},
+			"timeouts": map[string]any{
+				"type":        "object",
+				"description": "Timeouts in seconds of specific tools, keyed by tool name",
+				"additionalProperties": map[string]any{
+					"type":    "integer",
+					"minimum": 0,
+				},
+			},
+			"maxOutputBytes": map[string]any{
+				"type":        "integer",
+				"description": "Tool output sent to the model is truncated to this size in bytes, 0 disables the limit",
+				"minimum":     0,
+				"default":     30000,
+			},
+			"maxOutputLines": map[string]any{
+				"type":        "integer",
+				"description": "Tool output sent to the model is truncated to this many lines, 0 disables the limit",
+				"minimum":     0,
+				"default":     1000,
+			},
+			"disabled": map[string]any{
+				"type":        "array",
+				"description": "Tools that are not offered to the model",
+				"items": map[string]any{
+					"type": "string",
+				},
+			},
+			"autoApprove": map[string]any{
+				"type":        "array",
+				"description": "Tools that run without asking for permission",
+				"items": map[string]any{
+					"type": "string",
+				},
+			},
+			"custom": map[string]any{
+				"type":        "array",
+				"description": "Tools that run local commands",
+				"items": map[string]any{
+					"type": "object",
+					"properties": map[string]any{
+						"name": map[string]any{
+							"type":        "string",
+							"description": "Name of the tool",
+						},
+						"description": map[string]any{
+							"type":        "string",
+							"description": "Description of the tool for the model",
+						},
+						"parameters": map[string]any{
+							"type":        "object",
+							"description": "JSON schema properties of the tool arguments",
+						},
+						"required": map[string]any{
+							"type":        "array",
+							"description": "Arguments the model must pass",
+							"items": map[string]any{
+								"type": "string",
+							},
+						},
+						"command": map[string]any{
+							"type":        "string",
+							"description": "Command to run, {{name}} placeholders are replaced with the quoted argument",
+						},
+					},
+					"required": []string{"name", "command"},
+				},
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["workspace"] = map[string]any{
+		"type":        "object",
+		"description": "Paths the file system tools are allowed to access",
+		"properties": map[string]any{
+			"root": map[string]any{
+				"type":        "string",
+				"description": "Root of the workspace, the working directory by default",
+			},
+			"restrict": map[string]any{
+				"type":        "boolean",
+				"description": "Refuse access to paths outside of the workspace",
+				"default":     true,
+			},
+			"allowedPaths": map[string]any{
+				"type":        "array",
+				"description": "Paths outside of the workspace the tools may still access",
+				"items": map[string]any{
+					"type": "string",
+				},
+			},
+		},
+	}
+
+	schema["properties"].(map[string]any)["plugins"] = map[string]any{
+		"type":        "object",
+		"description": "WASM plugin tools, loaded from the plugins directory of the data directory",
+		"properties": map[string]any{
+			"maxMemoryMB": map[string]any{
+				"type":        "integer",
+				"description": "Memory a plugin call can use, in MB",
+				"minimum":     1,
+				"default":     256,
+			},
+		},
+	}
+
 	// Add LSP configuration
 	schema["properties"].(map[string]any)["lsp"] = map[string]any{
 		"type":        "object",
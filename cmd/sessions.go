@@ -0,0 +1,103 @@
+package cmd
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+var sessionsCmd = &cobra.Command{
+	Use:   "sessions",
+	Short: "Manage the stored sessions",
+}
+
+var pruneCmd = &cobra.Command{
+	Use:   "prune",
+	Short: "Delete or archive the sessions that haven't been used for a while",
+	Long: `Prune deletes the sessions last active before the given age, with their
+messages and the sessions of their sub-agents, and vacuums the database to give
+the space back. Pinned sessions are kept. With --archive the sessions are
+archived instead, and can be restored from the session list.`,
+	Example: `
+  # Delete the sessions not used in the last 30 days
+  cryoncode sessions prune --older-than 30d
+
+  # List the sessions that would be deleted
+  cryoncode sessions prune --older-than 2w --dry-run
+
+  # Archive them instead
+  cryoncode sessions prune --older-than 30d --archive
+  `,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		olderThan, _ := cmd.Flags().GetString("older-than")
+		archive, _ := cmd.Flags().GetBool("archive")
+		dryRun, _ := cmd.Flags().GetBool("dry-run")
+		age, err := session.ParseAge(olderThan)
+		if err != nil {
+			return err
+		}
+		before := time.Now().Add(-age)
+
+		conn, err := openDatabase()
+		if err != nil {
+			return err
+		}
+		defer conn.Close()
+		if !dryRun {
+			// Don't delete sessions from under an open TUI
+			lock, err := db.LockInstance()
+			if err != nil {
+				return err
+			}
+			defer lock.Release()
+		}
+
+		ctx := context.Background()
+		sessions := session.NewService(db.New(conn))
+
+		var pruned []session.Session
+		if dryRun {
+			list, err := sessions.List(ctx)
+			if err != nil {
+				return err
+			}
+			pruned = session.Stale(list, before)
+		} else {
+			pruned, err = sessions.Prune(ctx, before, archive)
+			if err != nil {
+				return fmt.Errorf("failed to prune sessions: %w", err)
+			}
+		}
+		for _, sess := range pruned {
+			fmt.Printf("%s  %s  %s\n", sess.ID, time.Unix(sess.UpdatedAt, 0).Format(time.DateOnly), sess.Title)
+		}
+
+		switch {
+		case dryRun:
+			fmt.Printf("%d sessions would be pruned\n", len(pruned))
+		case archive:
+			fmt.Printf("Archived %d sessions\n", len(pruned))
+		default:
+			if len(pruned) > 0 {
+				if err := db.Vacuum(ctx, conn); err != nil {
+					return err
+				}
+			}
+			fmt.Printf("Deleted %d sessions\n", len(pruned))
+		}
+		return nil
+	},
+}
+
+func init() {
+	pruneCmd.Flags().String("older-than", "30d", "Prune the sessions last active before this age (e.g. 30d, 2w, 12h)")
+	pruneCmd.Flags().Bool("archive", false, "Archive the sessions instead of deleting them")
+	pruneCmd.Flags().Bool("dry-run", false, "List the sessions that would be pruned without changing anything")
+	sessionsCmd.AddCommand(pruneCmd)
+	rootCmd.AddCommand(sessionsCmd)
+}
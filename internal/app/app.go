@@ -6,19 +6,24 @@ import (
 	"errors"
 	"fmt"
 	"maps"
+	"path/filepath"
 	"sync"
 	"time"
 
 	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/crypt"
 	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/export"
 	"github.com/zhenbah/cryoncode/internal/format"
 	"github.com/zhenbah/cryoncode/internal/history"
 	"github.com/zhenbah/cryoncode/internal/llm/agent"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/permission"
 	"github.com/zhenbah/cryoncode/internal/session"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
 )
 
@@ -26,6 +31,7 @@ type App struct {
 	Sessions    session.Service
 	Messages    message.Service
 	History     history.Service
+	Snapshots   snapshot.Service
 	Permissions permission.Service
 
 	CoderAgent agent.Service
@@ -41,34 +47,47 @@ type App struct {
 
 func New(ctx context.Context, conn *sql.DB) (*App, error) {
 	q := db.New(conn)
+	cipher, err := crypt.Load(ctx, q)
+	if err != nil {
+		return nil, err
+	}
 	sessions := session.NewService(q)
-	messages := message.NewService(q)
+	messages := message.NewService(q, cipher)
 	files := history.NewService(q, conn)
+	snapshots := snapshot.NewService(q, messages, files, filepath.Join(config.Get().Data.Directory, "snapshots"))
 
 	app := &App{
 		Sessions:    sessions,
 		Messages:    messages,
 		History:     files,
-		Permissions: permission.NewPermissionService(),
+		Snapshots:   snapshots,
+		Permissions: permission.NewPermissionService(q),
 		LSPClients:  make(map[string]*lsp.Client),
 	}
 
 	// Initialize theme based on configuration
 	app.initTheme()
 
+	if config.Get().Data.Transcripts {
+		transcripts := export.NewTranscripts(filepath.Join(config.Get().Data.Directory, "transcripts"))
+		go transcripts.Run(ctx, messages)
+	}
+
 	// Initialize LSP clients in the background
 	go app.initLSPClients(ctx)
 
-	var err error
 	app.CoderAgent, err = agent.NewAgent(
 		config.AgentCoder,
 		app.Sessions,
 		app.Messages,
+		app.Permissions,
+		app.Snapshots,
 		agent.CoderAgentTools(
 			app.Permissions,
 			app.Sessions,
 			app.Messages,
 			app.History,
+			app.Snapshots,
 			app.LSPClients,
 		),
 	)
@@ -183,4 +202,7 @@ func (app *App) Shutdown() {
 		}
 		cancel()
 	}
+
+	agent.CloseMcpClients()
+	shell.CloseAllSessions()
 }
@@ -0,0 +1,12 @@
+package app
+
+import (
+	"context"
+
+	"github.com/zhenbah/cryoncode/internal/export"
+)
+
+// ExportMarkdown returns the transcript of the session in Markdown
+func (app *App) ExportMarkdown(ctx context.Context, sessionID string) (string, error) {
+	return export.SessionMarkdown(ctx, app.Sessions, app.Messages, sessionID)
+}
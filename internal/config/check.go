@@ -0,0 +1,266 @@
+package config
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"math"
+	"os"
+	"reflect"
+	"slices"
+	"strings"
+
+	"github.com/pelletier/go-toml/v2"
+	"github.com/spf13/viper"
+)
+
+// checkConfig checks the config files read by Load, and the config they make
+// once merged
+func checkConfig(v *viper.Viper, cfg *Config, workingDir string) error {
+	var problems []string
+	for _, file := range configFiles(v, workingDir) {
+		fileProblems, err := checkConfigFile(file)
+		if err != nil {
+			return err
+		}
+		problems = append(problems, fileProblems...)
+	}
+	problems = append(problems, checkConflicts(cfg)...)
+	return configError(problems)
+}
+
+// checkConfigFile returns the problems of a config file: the keys Config
+// doesn't have, which would be ignored, and the values of the wrong type,
+// each with its path in the file
+func checkConfigFile(path string) ([]string, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	var values map[string]any
+	if isTOML(path) {
+		err = toml.Unmarshal(data, &values)
+	} else {
+		err = json.Unmarshal(data, &values)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to read config %s: %w", path, err)
+	}
+	var problems []string
+	checkValue("", values, reflect.TypeOf(Config{}), &problems)
+	slices.Sort(problems)
+	for i, problem := range problems {
+		problems[i] = path + ": " + problem
+	}
+	return problems, nil
+}
+
+// checkValue checks a value decoded from a config file against the type of
+// the field it's read into. Keys are matched to the fields with configField.
+func checkValue(path string, value any, typ reflect.Type, problems *[]string) {
+	report := func(expected string) {
+		*problems = append(*problems, fmt.Sprintf("%s: expected %s, got %s", keyPath(path), expected, describe(value)))
+	}
+
+	if list, ok := value.([]any); ok && typ == reflect.TypeOf(MCPEnv{}) {
+		// The env of an MCP server may still be a list of KEY=value strings
+		checkValue(path, list, reflect.TypeOf([]string{}), problems)
+		return
+	}
+
+	switch typ.Kind() {
+	case reflect.Interface:
+	case reflect.Pointer:
+		checkValue(path, value, typ.Elem(), problems)
+	case reflect.Struct:
+		table, ok := value.(map[string]any)
+		if !ok {
+			report("an object")
+			return
+		}
+		for key, v := range table {
+			field, ok := configField(typ, key)
+			if !ok {
+				*problems = append(*problems, fmt.Sprintf("%s: unknown key", keyPath(joinPath(path, key))))
+				continue
+			}
+			checkValue(joinPath(path, key), v, field.Type, problems)
+		}
+	case reflect.Map:
+		table, ok := value.(map[string]any)
+		if !ok {
+			report("an object")
+			return
+		}
+		for key, v := range table {
+			checkValue(joinPath(path, key), v, typ.Elem(), problems)
+		}
+	case reflect.Slice:
+		list, ok := value.([]any)
+		if !ok {
+			report("a list")
+			return
+		}
+		for i, v := range list {
+			checkValue(fmt.Sprintf("%s[%d]", path, i), v, typ.Elem(), problems)
+		}
+	case reflect.String:
+		if _, ok := value.(string); !ok {
+			report("a string")
+		}
+	case reflect.Bool:
+		if _, ok := value.(bool); !ok {
+			report("true or false")
+		}
+	case reflect.Int, reflect.Int64:
+		switch v := value.(type) {
+		case int64:
+		case float64:
+			if v != math.Trunc(v) {
+				report("a whole number")
+			}
+		default:
+			report("a number")
+		}
+	case reflect.Float64:
+		switch value.(type) {
+		case int64, float64:
+		default:
+			report("a number")
+		}
+	}
+}
+
+// configField returns the field of a config struct a key is read into. Keys
+// are the json names of the fields, as in the documentation and the schema,
+// or the names of the fields, which viper reads too, both ignoring case.
+func configField(typ reflect.Type, key string) (reflect.StructField, bool) {
+	for i := range typ.NumField() {
+		field := typ.Field(i)
+		if field.Anonymous && field.Type.Kind() == reflect.Struct {
+			if embedded, ok := configField(field.Type, key); ok {
+				return embedded, true
+			}
+			continue
+		}
+		if !field.IsExported() {
+			continue
+		}
+		if strings.EqualFold(jsonName(field), key) || strings.EqualFold(field.Name, key) {
+			return field, true
+		}
+	}
+	return reflect.StructField{}, false
+}
+
+// jsonName returns the name of a field in the config file
+func jsonName(field reflect.StructField) string {
+	name, _, _ := strings.Cut(field.Tag.Get("json"), ",")
+	if name == "" || name == "-" {
+		return field.Name
+	}
+	return name
+}
+
+func joinPath(path, key string) string {
+	if path == "" {
+		return key
+	}
+	return path + "." + key
+}
+
+func keyPath(path string) string {
+	if path == "" {
+		return "the config"
+	}
+	return path
+}
+
+// describe names the type of a decoded value in the terms of the config file
+func describe(value any) string {
+	switch value.(type) {
+	case nil:
+		return "null"
+	case string:
+		return "a string"
+	case bool:
+		return "a boolean"
+	case int64, float64:
+		return "a number"
+	case []any:
+		return "a list"
+	case map[string]any:
+		return "an object"
+	default:
+		return fmt.Sprintf("%T", value)
+	}
+}
+
+// checkConflicts returns the settings of the merged config that can't be used
+// together, they may come from different files
+func checkConflicts(cfg *Config) []string {
+	var problems []string
+	for name, server := range cfg.MCPServers {
+		if !server.IsEnabled() {
+			continue
+		}
+		path := "mcpServers." + name
+		switch server.Type {
+		case MCPStdio:
+			if server.Command == "" {
+				problems = append(problems, path+": a stdio server needs a command")
+			}
+			if server.URL != "" {
+				problems = append(problems, path+": url is only used by sse servers, set type to sse")
+			}
+		case MCPSse:
+			if server.URL == "" {
+				problems = append(problems, path+": an sse server needs a url")
+			}
+			if server.Command != "" {
+				problems = append(problems, path+": command is only used by stdio servers")
+			}
+		default:
+			problems = append(problems, fmt.Sprintf("%s.type: unknown server type %q, expected stdio or sse", path, server.Type))
+		}
+	}
+	if cfg.Data.Encrypt && cfg.Data.Transcripts {
+		// The transcripts are plain text, they would keep a readable copy of
+		// the encrypted messages
+		problems = append(problems, "data.transcripts: transcripts are written in plain text and can't be used with data.encrypt")
+	}
+	for name, agent := range cfg.Agents {
+		if agent.Temperature != nil && (*agent.Temperature < 0 || *agent.Temperature > 2) {
+			problems = append(problems, fmt.Sprintf("agents.%s.temperature: expected a number from 0 to 2", name))
+		}
+	}
+	for list, rules := range map[string][]string{
+		"allow": cfg.Permissions.Allow,
+		"ask":   cfg.Permissions.Ask,
+		"deny":  cfg.Permissions.Deny,
+	} {
+		for i, rule := range rules {
+			if _, _, ok := ParsePermissionRule(rule); !ok {
+				problems = append(problems, fmt.Sprintf("permissions.%s[%d]: invalid rule %q, expected tool or tool(pattern)", list, i, rule))
+			}
+		}
+	}
+	for alias, target := range cfg.Models.Aliases {
+		if _, ok := resolveModel(&Config{}, target); !ok {
+			problems = append(problems, fmt.Sprintf("models.aliases.%s: unknown model %q", alias, target))
+		}
+	}
+	if cfg.WebSearch.Provider == "searxng" && cfg.WebSearch.URL == "" {
+		problems = append(problems, "webSearch.url: the searxng provider needs the url of an instance")
+	}
+	slices.Sort(problems)
+	return problems
+}
+
+// configError reports all the problems of the config at once
+func configError(problems []string) error {
+	if len(problems) == 0 {
+		return nil
+	}
+	return errors.New("invalid config:\n  " + strings.Join(problems, "\n  "))
+}
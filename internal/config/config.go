@@ -2,14 +2,22 @@
 package config
 
 import (
+	"cmp"
 	"encoding/json"
 	"fmt"
 	"log/slog"
+	"maps"
 	"os"
 	"path/filepath"
 	"runtime"
+	"slices"
 	"strings"
+	"sync"
+	"sync/atomic"
+	"time"
 
+	"github.com/bmatcuk/doublestar/v4"
+	"github.com/go-viper/mapstructure/v2"
 	"github.com/zhenbah/cryoncode/internal/llm/models"
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/spf13/viper"
@@ -27,11 +35,12 @@ const (
 // MCPServer defines the configuration for a Model Control Protocol server.
 type MCPServer struct {
 	Command string            `json:"command"`
-	Env     []string          `json:"env"`
+	Env     MCPEnv            `json:"env"`
 	Args    []string          `json:"args"`
 	Type    MCPType           `json:"type"`
 	URL     string            `json:"url"`
 	Headers map[string]string `json:"headers"`
+	Enabled *bool             `json:"enabled,omitempty"` // Started unless set to false
 }
 
 type AgentName string
@@ -47,7 +56,11 @@ const (
 type Agent struct {
 	Model           models.ModelID `json:"model"`
 	MaxTokens       int64          `json:"maxTokens"`
-	ReasoningEffort string         `json:"reasoningEffort"` // For openai models low,medium,heigh
+	ReasoningEffort string         `json:"reasoningEffort"`       // For openai models low,medium,heigh
+	Description     string         `json:"description,omitempty"` // Shown when choosing the agent
+	Prompt          string         `json:"prompt,omitempty"`      // System prompt replacing the built-in one
+	Tools           []string       `json:"tools,omitempty"`       // Tools the agent may use, all of them when empty
+	Temperature     *float64       `json:"temperature,omitempty"` // Sampling temperature, the provider default when unset
 }
 
 // Provider defines configuration for an LLM provider.
@@ -58,7 +71,9 @@ type Provider struct {
 
 // Data defines storage configuration.
 type Data struct {
-	Directory string `json:"directory,omitempty"`
+	Directory   string `json:"directory,omitempty"`
+	Encrypt     bool   `json:"encrypt,omitempty"`     // Encrypt the messages with a key from the OS keyring
+	Transcripts bool   `json:"transcripts,omitempty"` // Append the messages to a Markdown file per session
 }
 
 // LSPConfig defines configuration for Language Server Protocol integration.
@@ -71,7 +86,12 @@ type LSPConfig struct {
 
 // TUIConfig defines the configuration for the Terminal User Interface.
 type TUIConfig struct {
-	Theme string `json:"theme,omitempty"`
+	Theme           string  `json:"theme,omitempty"`
+	EditorMaxHeight int     `json:"editorMaxHeight,omitempty"` // Lines the input grows to before it scrolls
+	VimMode         bool    `json:"vimMode,omitempty"`         // Modal editing of the input and vim keys for the messages
+	Images          string  `json:"images,omitempty"`          // Graphics protocol for images: auto, kitty, iterm2, sixel or none
+	Timestamps      bool    `json:"timestamps,omitempty"`      // Show when each message was sent, t toggles it
+	InputRatio      float64 `json:"inputRatio,omitempty"`      // Part of the height kept for the input until alt+up or alt+down change it
 }
 
 // ShellConfig defines the configuration for the shell used by the bash tool.
@@ -80,6 +100,75 @@ type ShellConfig struct {
 	Args []string `json:"args,omitempty"`
 }
 
+// SandboxConfig defines the container used to isolate commands run by the agent.
+type SandboxConfig struct {
+	Enabled bool   `json:"enabled,omitempty"`
+	Runtime string `json:"runtime,omitempty"` // docker or podman
+	Image   string `json:"image,omitempty"`
+	Network bool   `json:"network,omitempty"`
+}
+
+// WebSearchConfig defines the search backend used by the web_search tool.
+type WebSearchConfig struct {
+	Provider string `json:"provider,omitempty"` // brave, tavily or searxng
+	URL      string `json:"url,omitempty"`
+	APIKey   string `json:"apiKey,omitempty"`
+}
+
+// PluginsConfig defines how WASM plugin tools are executed.
+type PluginsConfig struct {
+	MaxMemoryMB int `json:"maxMemoryMB,omitempty"` // Memory a plugin call can use
+}
+
+// WorkspaceConfig defines which paths the file system tools are allowed to access.
+type WorkspaceConfig struct {
+	Root         string   `json:"root,omitempty"`         // Defaults to the working directory
+	Restrict     bool     `json:"restrict"`               // Refuse access to paths outside of the workspace
+	AllowedPaths []string `json:"allowedPaths,omitempty"` // Paths outside of the workspace the tools may still access
+}
+
+// CustomToolConfig defines a tool that runs a local command.
+type CustomToolConfig struct {
+	Name        string         `json:"name"`
+	Description string         `json:"description"`
+	Parameters  map[string]any `json:"parameters,omitempty"` // JSON schema properties of the tool arguments
+	Required    []string       `json:"required,omitempty"`
+	Command     string         `json:"command"` // {{name}} placeholders are replaced with the quoted argument
+}
+
+// ToolsConfig defines configuration for the tools available to the agents.
+type ToolsConfig struct {
+	Timeout        int                `json:"timeout,omitempty"`        // Default timeout in seconds, 0 disables it
+	Timeouts       map[string]int     `json:"timeouts,omitempty"`       // Per tool timeouts in seconds, keyed by tool name
+	MaxOutputBytes int                `json:"maxOutputBytes,omitempty"` // Tool output sent to the model is truncated to this size
+	MaxOutputLines int                `json:"maxOutputLines,omitempty"` // Tool output sent to the model is truncated to this many lines
+	Disabled       []string           `json:"disabled,omitempty"`       // Tools that are not offered to the model
+	AutoApprove    []string           `json:"autoApprove,omitempty"`    // Tools that run without asking for permission
+	Custom         []CustomToolConfig `json:"custom,omitempty"`         // Tools that run local commands
+}
+
+// PermissionsConfig decides permission requests without asking the user. A
+// rule is a tool name, * for every tool, optionally followed by a command
+// pattern for shell tools or a path glob for the others, as in
+// bash(git push *) or edit(src/**). When rules of several lists match a
+// request the strictest one applies.
+type PermissionsConfig struct {
+	Allow []string `json:"allow,omitempty"` // Run without asking
+	Ask   []string `json:"ask,omitempty"`   // Always ask, even for auto-approved tools
+	Deny  []string `json:"deny,omitempty"`  // Never run, even in non-interactive mode
+}
+
+// CompactionConfig defines when a session is summarized to fit the context window.
+type CompactionConfig struct {
+	Threshold float64 `json:"threshold,omitempty"` // Part of the context window used before the session is compacted
+	KeepTurns int     `json:"keepTurns,omitempty"` // Last turns sent verbatim after the summary instead of summarized
+}
+
+// ModelsConfig defines names for the models.
+type ModelsConfig struct {
+	Aliases map[string]string `json:"aliases,omitempty"` // Short names for model IDs or names, e.g. "fast": "gpt-4o-mini"
+}
+
 // Config is the main configuration structure for the application.
 type Config struct {
 	Data         Data                              `json:"data"`
@@ -88,12 +177,21 @@ type Config struct {
 	Providers    map[models.ModelProvider]Provider `json:"providers,omitempty"`
 	LSP          map[string]LSPConfig              `json:"lsp,omitempty"`
 	Agents       map[AgentName]Agent               `json:"agents,omitempty"`
+	Models       ModelsConfig                      `json:"models,omitempty"`
 	Debug        bool                              `json:"debug,omitempty"`
 	DebugLSP     bool                              `json:"debugLSP,omitempty"`
 	ContextPaths []string                          `json:"contextPaths,omitempty"`
 	TUI          TUIConfig                         `json:"tui"`
 	Shell        ShellConfig                       `json:"shell,omitempty"`
 	AutoCompact  bool                              `json:"autoCompact,omitempty"`
+	Compaction   CompactionConfig                  `json:"compaction,omitempty"`
+	WebSearch    WebSearchConfig                   `json:"webSearch,omitempty"`
+	Sandbox      SandboxConfig                     `json:"sandbox,omitempty"`
+	Tools        ToolsConfig                       `json:"tools,omitempty"`
+	Permissions  PermissionsConfig                 `json:"permissions,omitempty"`
+	Workspace    WorkspaceConfig                   `json:"workspace,omitempty"`
+	Plugins      PluginsConfig                     `json:"plugins,omitempty"`
+	Keybindings  map[string][]string               `json:"keybindings,omitempty"`
 }
 
 // Application constants
@@ -103,9 +201,23 @@ const (
 	appName              = "cryoncode"
 
 	MaxTokensFallbackDefault = 4096
+
+	defaultToolTimeout        = 15 * 60 // seconds
+	defaultToolMaxOutputBytes = 30000
+	defaultToolMaxOutputLines = 1000
+
+	defaultPluginMaxMemoryMB = 256
+
+	defaultEditorMaxHeight = 10
+	defaultInputRatio      = 0.1
+
+	defaultCompactionThreshold = 0.95
+	defaultCompactionKeepTurns = 2
 )
 
 var defaultContextPaths = []string{
+	"AGENTS.md",
+	"OPENCODE.md",
 	".github/copilot-instructions.md",
 	".cursorrules",
 	".cursor/rules/",
@@ -119,43 +231,107 @@ var defaultContextPaths = []string{
 	"CRYONCODE.local.md",
 }
 
-// Global configuration instance
-var cfg *Config
+// active is the global configuration. It's never changed once published, a
+// change publishes a changed copy, so a config returned by Get can be read
+// while the config is reloaded.
+var active atomic.Pointer[Config]
+
+// updating serializes the changes of the config, made by Reload and at runtime
+var updating sync.Mutex
 
 // Load initializes the configuration from environment variables and config files.
 // If debug is true, debug mode is enabled and log level is set to debug.
 // It returns an error if configuration loading fails.
 func Load(workingDir string, debug bool) (*Config, error) {
-	if cfg != nil {
+	if cfg := active.Load(); cfg != nil {
 		return cfg, nil
 	}
+	cfg, err := load(viper.GetViper(), workingDir, debug, true)
+	if err != nil {
+		return cfg, err
+	}
+	loaded = reloadableOf(cfg)
+	active.Store(cfg)
+	return cfg, nil
+}
 
-	cfg = &Config{
+// load reads the config files into a new config with the viper instance,
+// leaving the global config as it is. The logger is only set up for the config
+// the run starts with.
+func load(v *viper.Viper, workingDir string, debug, setupLogs bool) (*Config, error) {
+	cfg := &Config{
 		WorkingDir: workingDir,
 		MCPServers: make(map[string]MCPServer),
 		Providers:  make(map[models.ModelProvider]Provider),
 		LSP:        make(map[string]LSPConfig),
 	}
 
-	configureViper()
-	setDefaults(debug)
+	configureViper(v)
+	setDefaults(v, debug)
 
 	// Read global config
-	if err := readConfig(viper.ReadInConfig()); err != nil {
-		return cfg, err
+	if file, ok := findConfigFile(globalConfigDirs()...); ok {
+		v.SetConfigFile(file)
+		if err := v.ReadInConfig(); err != nil {
+			return cfg, fmt.Errorf("failed to read config: %w", err)
+		}
 	}
 
-	// Load and merge local config
-	mergeLocalConfig(workingDir)
+	// Load and merge the config of the project
+	if err := mergeLocalConfig(v, workingDir); err != nil {
+		return cfg, err
+	}
 
-	setProviderDefaults()
+	setProviderDefaults(v)
 
 	// Apply configuration to the struct
-	if err := viper.Unmarshal(cfg); err != nil {
+	if err := v.Unmarshal(cfg, viper.DecodeHook(mapstructure.ComposeDecodeHookFunc(
+		mapstructure.StringToTimeDurationHookFunc(),
+		mapstructure.StringToSliceHookFunc(","),
+		mcpEnvHook,
+	))); err != nil {
 		return cfg, fmt.Errorf("failed to unmarshal config: %w", err)
 	}
+	if err := restoreMCPEnvNames(cfg, configFiles(v, workingDir)); err != nil {
+		return cfg, err
+	}
+	unsetEnv := expandEnv(cfg)
+
+	applyDefaultValues(cfg)
+	if err := checkConfig(v, cfg, workingDir); err != nil {
+		return cfg, err
+	}
+	cfg.Data.Directory = resolveDataDirectory(workingDir, cfg.Data.Directory)
+	if setupLogs {
+		if err := setupLogging(cfg); err != nil {
+			return cfg, err
+		}
+	}
+
+	for _, name := range unsetEnv {
+		logging.WarnPersist(fmt.Sprintf("The config uses ${%s} but it isn't set", name))
+	}
+
+	// Validate configuration
+	if err := validate(cfg); err != nil {
+		return cfg, fmt.Errorf("config validation failed: %w", err)
+	}
+
+	if cfg.Agents == nil {
+		cfg.Agents = make(map[AgentName]Agent)
+	}
+
+	// Override the max tokens for title agent
+	titleAgent := cfg.Agents[AgentTitle]
+	titleAgent.MaxTokens = 80
+	titleAgent.ReasoningEffort = ""
+	cfg.Agents[AgentTitle] = titleAgent
+	return cfg, nil
+}
 
-	applyDefaultValues()
+// setupLogging sets up the logger of the run with the loaded config
+func setupLogging(cfg *Config) error {
+	logging.PanicDir = cfg.Data.Directory
 	defaultLevel := slog.LevelInfo
 	if cfg.Debug {
 		defaultLevel = slog.LevelDebug
@@ -167,23 +343,23 @@ func Load(workingDir string, debug bool) (*Config, error) {
 		// if file does not exist create it
 		if _, err := os.Stat(loggingFile); os.IsNotExist(err) {
 			if err := os.MkdirAll(cfg.Data.Directory, 0o755); err != nil {
-				return cfg, fmt.Errorf("failed to create directory: %w", err)
+				return fmt.Errorf("failed to create directory: %w", err)
 			}
 			if _, err := os.Create(loggingFile); err != nil {
-				return cfg, fmt.Errorf("failed to create log file: %w", err)
+				return fmt.Errorf("failed to create log file: %w", err)
 			}
 		}
 
 		if _, err := os.Stat(messagesPath); os.IsNotExist(err) {
 			if err := os.MkdirAll(messagesPath, 0o756); err != nil {
-				return cfg, fmt.Errorf("failed to create directory: %w", err)
+				return fmt.Errorf("failed to create directory: %w", err)
 			}
 		}
 		logging.MessageDir = messagesPath
 
 		sloggingFileWriter, err := os.OpenFile(loggingFile, os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0o666)
 		if err != nil {
-			return cfg, fmt.Errorf("failed to open log file: %w", err)
+			return fmt.Errorf("failed to open log file: %w", err)
 		}
 		// Configure logger
 		logger := slog.New(slog.NewTextHandler(sloggingFileWriter, &slog.HandlerOptions{
@@ -197,90 +373,110 @@ func Load(workingDir string, debug bool) (*Config, error) {
 		}))
 		slog.SetDefault(logger)
 	}
-
-	// Validate configuration
-	if err := Validate(); err != nil {
-		return cfg, fmt.Errorf("config validation failed: %w", err)
-	}
-
-	if cfg.Agents == nil {
-		cfg.Agents = make(map[AgentName]Agent)
-	}
-
-	// Override the max tokens for title agent
-	cfg.Agents[AgentTitle] = Agent{
-		Model:     cfg.Agents[AgentTitle].Model,
-		MaxTokens: 80,
-	}
-	return cfg, nil
+	return nil
 }
 
-// configureViper sets up viper's configuration paths and environment variables.
-func configureViper() {
-	viper.SetConfigName(fmt.Sprintf(".%s", appName))
-	viper.SetConfigType("json")
-	viper.AddConfigPath("$HOME")
-	viper.AddConfigPath(fmt.Sprintf("$XDG_CONFIG_HOME/%s", appName))
-	viper.AddConfigPath(fmt.Sprintf("$HOME/.config/%s", appName))
-	viper.SetEnvPrefix(strings.ToUpper(appName))
-	viper.AutomaticEnv()
+// configureViper sets up viper's environment variables. The config files are
+// found by findConfigFile, viper reads them in the format of their extension.
+func configureViper(v *viper.Viper) {
+	v.SetEnvPrefix(strings.ToUpper(appName))
+	v.AutomaticEnv()
 }
 
 // setDefaults configures default values for configuration options.
-func setDefaults(debug bool) {
-	viper.SetDefault("data.directory", defaultDataDirectory)
-	viper.SetDefault("contextPaths", defaultContextPaths)
-	viper.SetDefault("tui.theme", "cryoncode")
-	viper.SetDefault("autoCompact", true)
+func setDefaults(v *viper.Viper, debug bool) {
+	v.SetDefault("data.directory", defaultDataDirectory)
+	v.SetDefault("contextPaths", defaultContextPaths)
+	v.SetDefault("tui.theme", "cryoncode")
+	v.SetDefault("tui.editorMaxHeight", defaultEditorMaxHeight)
+	v.SetDefault("tui.images", "auto")
+	v.SetDefault("tui.inputRatio", defaultInputRatio)
+	v.SetDefault("autoCompact", true)
+	v.SetDefault("compaction.threshold", defaultCompactionThreshold)
+	v.SetDefault("compaction.keepTurns", defaultCompactionKeepTurns)
+	v.SetDefault("tools.timeout", defaultToolTimeout)
+	v.SetDefault("tools.maxOutputBytes", defaultToolMaxOutputBytes)
+	v.SetDefault("tools.maxOutputLines", defaultToolMaxOutputLines)
+	v.SetDefault("plugins.maxMemoryMB", defaultPluginMaxMemoryMB)
+	v.SetDefault("workspace.restrict", true)
 
 	// Set default shell from environment or fallback to /bin/bash
 	shellPath := os.Getenv("SHELL")
 	if shellPath == "" {
 		shellPath = "/bin/bash"
 	}
-	viper.SetDefault("shell.path", shellPath)
-	viper.SetDefault("shell.args", []string{"-l"})
+	v.SetDefault("shell.path", shellPath)
+	v.SetDefault("shell.args", []string{"-l"})
+
+	// Pick up a web search provider from the environment if one is available.
+	// The key is read by the web search tool from the variable of the provider
+	// in use, so it's never sent to another one.
+	if os.Getenv("BRAVE_API_KEY") != "" {
+		v.SetDefault("webSearch.provider", "brave")
+	} else if os.Getenv("TAVILY_API_KEY") != "" {
+		v.SetDefault("webSearch.provider", "tavily")
+	}
 
 	if debug {
-		viper.SetDefault("debug", true)
-		viper.Set("log.level", "debug")
+		v.SetDefault("debug", true)
+		v.Set("log.level", "debug")
 	} else {
-		viper.SetDefault("debug", false)
-		viper.SetDefault("log.level", defaultLogLevel)
+		v.SetDefault("debug", false)
+		v.SetDefault("log.level", defaultLogLevel)
 	}
 }
 
 // setProviderDefaults configures LLM provider defaults based on provider provided by
 // environment variables and configuration file.
-func setProviderDefaults() {
+func setProviderDefaults(v *viper.Viper) {
+	// Keys saved with `cryoncode auth login` take precedence over the
+	// environment and the config files
+	if keys, err := StoredAPIKeys(); err != nil {
+		logging.Debug("Failed to read the API keys from the keyring", "error", err)
+	} else {
+		for provider, key := range keys {
+			v.Set(fmt.Sprintf("providers.%s.apiKey", provider), key)
+		}
+	}
+
+	// Models found at LOCAL_ENDPOINT are used when no other provider is
+	// configured, the defaults set below take precedence
+	if models.LocalDefaultModel != "" {
+		v.SetDefault("providers.local.apiKey", "dummy")
+		v.SetDefault("agents.coder.model", models.LocalDefaultModel)
+		v.SetDefault("agents.summarizer.model", models.LocalDefaultModel)
+		v.SetDefault("agents.task.model", models.LocalDefaultModel)
+		v.SetDefault("agents.title.model", models.LocalDefaultModel)
+	}
+
 	// Set all API keys we can find in the environment
 	// Note: Viper does not default if the json apiKey is ""
 	if apiKey := os.Getenv("ANTHROPIC_API_KEY"); apiKey != "" {
-		viper.SetDefault("providers.anthropic.apiKey", apiKey)
+		v.SetDefault("providers.anthropic.apiKey", apiKey)
 	}
 	if apiKey := os.Getenv("OPENAI_API_KEY"); apiKey != "" {
-		viper.SetDefault("providers.openai.apiKey", apiKey)
+		v.SetDefault("providers.openai.apiKey", apiKey)
 	}
 	if apiKey := os.Getenv("GEMINI_API_KEY"); apiKey != "" {
-		viper.SetDefault("providers.gemini.apiKey", apiKey)
+		v.SetDefault("providers.gemini.apiKey", apiKey)
 	}
 	if apiKey := os.Getenv("GROQ_API_KEY"); apiKey != "" {
-		viper.SetDefault("providers.groq.apiKey", apiKey)
+		v.SetDefault("providers.groq.apiKey", apiKey)
 	}
 	if apiKey := os.Getenv("OPENROUTER_API_KEY"); apiKey != "" {
-		viper.SetDefault("providers.openrouter.apiKey", apiKey)
+		v.SetDefault("providers.openrouter.apiKey", apiKey)
 	}
 	if apiKey := os.Getenv("XAI_API_KEY"); apiKey != "" {
-		viper.SetDefault("providers.xai.apiKey", apiKey)
+		v.SetDefault("providers.xai.apiKey", apiKey)
 	}
 	if apiKey := os.Getenv("AZURE_OPENAI_ENDPOINT"); apiKey != "" {
 		// api-key may be empty when using Entra ID credentials – that's okay
-		viper.SetDefault("providers.azure.apiKey", os.Getenv("AZURE_OPENAI_API_KEY"))
+		v.SetDefault("providers.azure.apiKey", os.Getenv("AZURE_OPENAI_API_KEY"))
 	}
 	if apiKey, err := LoadGitHubToken(); err == nil && apiKey != "" {
-		viper.SetDefault("providers.copilot.apiKey", apiKey)
-		if viper.GetString("providers.copilot.apiKey") == "" {
-			viper.Set("providers.copilot.apiKey", apiKey)
+		v.SetDefault("providers.copilot.apiKey", apiKey)
+		if v.GetString("providers.copilot.apiKey") == "" {
+			v.Set("providers.copilot.apiKey", apiKey)
 		}
 	}
 
@@ -296,92 +492,92 @@ func setProviderDefaults() {
 	// 9. Google Cloud VertexAI
 
 	// copilot configuration
-	if key := viper.GetString("providers.copilot.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.CopilotGPT4o)
-		viper.SetDefault("agents.summarizer.model", models.CopilotGPT4o)
-		viper.SetDefault("agents.task.model", models.CopilotGPT4o)
-		viper.SetDefault("agents.title.model", models.CopilotGPT4o)
+	if key := v.GetString("providers.copilot.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.CopilotGPT4o)
+		v.SetDefault("agents.summarizer.model", models.CopilotGPT4o)
+		v.SetDefault("agents.task.model", models.CopilotGPT4o)
+		v.SetDefault("agents.title.model", models.CopilotGPT4o)
 		return
 	}
 
 	// Anthropic configuration
-	if key := viper.GetString("providers.anthropic.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.Claude4Sonnet)
-		viper.SetDefault("agents.summarizer.model", models.Claude4Sonnet)
-		viper.SetDefault("agents.task.model", models.Claude4Sonnet)
-		viper.SetDefault("agents.title.model", models.Claude4Sonnet)
+	if key := v.GetString("providers.anthropic.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.Claude4Sonnet)
+		v.SetDefault("agents.summarizer.model", models.Claude4Sonnet)
+		v.SetDefault("agents.task.model", models.Claude4Sonnet)
+		v.SetDefault("agents.title.model", models.Claude4Sonnet)
 		return
 	}
 
 	// OpenAI configuration
-	if key := viper.GetString("providers.openai.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.GPT41)
-		viper.SetDefault("agents.summarizer.model", models.GPT41)
-		viper.SetDefault("agents.task.model", models.GPT41Mini)
-		viper.SetDefault("agents.title.model", models.GPT41Mini)
+	if key := v.GetString("providers.openai.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.GPT41)
+		v.SetDefault("agents.summarizer.model", models.GPT41)
+		v.SetDefault("agents.task.model", models.GPT41Mini)
+		v.SetDefault("agents.title.model", models.GPT41Mini)
 		return
 	}
 
 	// Google Gemini configuration
-	if key := viper.GetString("providers.gemini.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.Gemini25)
-		viper.SetDefault("agents.summarizer.model", models.Gemini25)
-		viper.SetDefault("agents.task.model", models.Gemini25Flash)
-		viper.SetDefault("agents.title.model", models.Gemini25Flash)
+	if key := v.GetString("providers.gemini.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.Gemini25)
+		v.SetDefault("agents.summarizer.model", models.Gemini25)
+		v.SetDefault("agents.task.model", models.Gemini25Flash)
+		v.SetDefault("agents.title.model", models.Gemini25Flash)
 		return
 	}
 
 	// Groq configuration
-	if key := viper.GetString("providers.groq.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.QWENQwq)
-		viper.SetDefault("agents.summarizer.model", models.QWENQwq)
-		viper.SetDefault("agents.task.model", models.QWENQwq)
-		viper.SetDefault("agents.title.model", models.QWENQwq)
+	if key := v.GetString("providers.groq.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.QWENQwq)
+		v.SetDefault("agents.summarizer.model", models.QWENQwq)
+		v.SetDefault("agents.task.model", models.QWENQwq)
+		v.SetDefault("agents.title.model", models.QWENQwq)
 		return
 	}
 
 	// OpenRouter configuration
-	if key := viper.GetString("providers.openrouter.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.OpenRouterClaude37Sonnet)
-		viper.SetDefault("agents.summarizer.model", models.OpenRouterClaude37Sonnet)
-		viper.SetDefault("agents.task.model", models.OpenRouterClaude37Sonnet)
-		viper.SetDefault("agents.title.model", models.OpenRouterClaude35Haiku)
+	if key := v.GetString("providers.openrouter.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.OpenRouterClaude37Sonnet)
+		v.SetDefault("agents.summarizer.model", models.OpenRouterClaude37Sonnet)
+		v.SetDefault("agents.task.model", models.OpenRouterClaude37Sonnet)
+		v.SetDefault("agents.title.model", models.OpenRouterClaude35Haiku)
 		return
 	}
 
 	// XAI configuration
-	if key := viper.GetString("providers.xai.apiKey"); strings.TrimSpace(key) != "" {
-		viper.SetDefault("agents.coder.model", models.XAIGrok3Beta)
-		viper.SetDefault("agents.summarizer.model", models.XAIGrok3Beta)
-		viper.SetDefault("agents.task.model", models.XAIGrok3Beta)
-		viper.SetDefault("agents.title.model", models.XAiGrok3MiniFastBeta)
+	if key := v.GetString("providers.xai.apiKey"); strings.TrimSpace(key) != "" {
+		v.SetDefault("agents.coder.model", models.XAIGrok3Beta)
+		v.SetDefault("agents.summarizer.model", models.XAIGrok3Beta)
+		v.SetDefault("agents.task.model", models.XAIGrok3Beta)
+		v.SetDefault("agents.title.model", models.XAiGrok3MiniFastBeta)
 		return
 	}
 
 	// AWS Bedrock configuration
 	if hasAWSCredentials() {
-		viper.SetDefault("agents.coder.model", models.BedrockClaude37Sonnet)
-		viper.SetDefault("agents.summarizer.model", models.BedrockClaude37Sonnet)
-		viper.SetDefault("agents.task.model", models.BedrockClaude37Sonnet)
-		viper.SetDefault("agents.title.model", models.BedrockClaude37Sonnet)
+		v.SetDefault("agents.coder.model", models.BedrockClaude37Sonnet)
+		v.SetDefault("agents.summarizer.model", models.BedrockClaude37Sonnet)
+		v.SetDefault("agents.task.model", models.BedrockClaude37Sonnet)
+		v.SetDefault("agents.title.model", models.BedrockClaude37Sonnet)
 		return
 	}
 
 	// Azure OpenAI configuration
 	if os.Getenv("AZURE_OPENAI_ENDPOINT") != "" {
-		viper.SetDefault("agents.coder.model", models.AzureGPT41)
-		viper.SetDefault("agents.summarizer.model", models.AzureGPT41)
-		viper.SetDefault("agents.task.model", models.AzureGPT41Mini)
-		viper.SetDefault("agents.title.model", models.AzureGPT41Mini)
+		v.SetDefault("agents.coder.model", models.AzureGPT41)
+		v.SetDefault("agents.summarizer.model", models.AzureGPT41)
+		v.SetDefault("agents.task.model", models.AzureGPT41Mini)
+		v.SetDefault("agents.title.model", models.AzureGPT41Mini)
 		return
 	}
 
 	// Google Cloud VertexAI configuration
 	if hasVertexAICredentials() {
-		viper.SetDefault("agents.coder.model", models.VertexAIGemini25)
-		viper.SetDefault("agents.summarizer.model", models.VertexAIGemini25)
-		viper.SetDefault("agents.task.model", models.VertexAIGemini25Flash)
-		viper.SetDefault("agents.title.model", models.VertexAIGemini25Flash)
+		v.SetDefault("agents.coder.model", models.VertexAIGemini25)
+		v.SetDefault("agents.summarizer.model", models.VertexAIGemini25)
+		v.SetDefault("agents.task.model", models.VertexAIGemini25Flash)
+		v.SetDefault("agents.title.model", models.VertexAIGemini25Flash)
 		return
 	}
 }
@@ -433,39 +629,99 @@ func hasCopilotCredentials() bool {
 	return false
 }
 
-// readConfig handles the result of reading a configuration file.
-func readConfig(err error) error {
-	if err == nil {
-		return nil
+// resolveDataDirectory returns where the data is kept. A relative directory is
+// put at the root of the git repository of the working directory, so starting
+// from a subdirectory opens the same sessions. Outside of a repository an
+// existing directory in the working directory is used, otherwise the user
+// data directory.
+func resolveDataDirectory(workingDir, dir string) string {
+	if filepath.IsAbs(dir) {
+		return dir
 	}
+	if root, ok := gitRoot(workingDir); ok {
+		return filepath.Join(root, dir)
+	}
+	local := filepath.Join(workingDir, dir)
+	if _, err := os.Stat(local); err == nil {
+		return local
+	}
+	if userDir := userDataDirectory(); userDir != "" {
+		return userDir
+	}
+	return local
+}
 
-	// It's okay if the config file doesn't exist
-	if _, ok := err.(viper.ConfigFileNotFoundError); ok {
-		return nil
+// gitRoot returns the closest directory from dir up that has a .git entry
+func gitRoot(dir string) (string, bool) {
+	for {
+		if _, err := os.Stat(filepath.Join(dir, ".git")); err == nil {
+			return dir, true
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return "", false
+		}
+		dir = parent
 	}
+}
 
-	return fmt.Errorf("failed to read config: %w", err)
+// userDataDirectory returns $XDG_DATA_HOME/cryoncode, ~/.local/share/cryoncode
+// when it isn't set
+func userDataDirectory() string {
+	if dataHome := os.Getenv("XDG_DATA_HOME"); dataHome != "" {
+		return filepath.Join(dataHome, appName)
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(home, ".local", "share", appName)
 }
 
-// mergeLocalConfig loads and merges configuration from the local directory.
-func mergeLocalConfig(workingDir string) {
-	local := viper.New()
-	local.SetConfigName(fmt.Sprintf(".%s", appName))
-	local.SetConfigType("json")
-	local.AddConfigPath(workingDir)
+// LocalDataDirectory returns the data directory as configured, relative to
+// the working directory. Versions before the data directory was resolved by
+// resolveDataDirectory kept the data there.
+func LocalDataDirectory() string {
+	cfg := Get()
+	if cfg == nil {
+		panic("config not loaded")
+	}
+	dir := viper.GetString("data.directory")
+	if filepath.IsAbs(dir) {
+		return dir
+	}
+	return filepath.Join(cfg.WorkingDir, dir)
+}
 
-	// Merge local config if it exists
-	if err := local.ReadInConfig(); err == nil {
-		viper.MergeConfigMap(local.AllSettings())
+// mergeLocalConfig merges the config files of the project over the user
+// config, the closest one to the working directory last.
+func mergeLocalConfig(v *viper.Viper, workingDir string) error {
+	for _, file := range localConfigFiles(workingDir) {
+		if file == v.ConfigFileUsed() {
+			continue
+		}
+		local := viper.New()
+		local.SetConfigFile(file)
+		if err := local.ReadInConfig(); err != nil {
+			return fmt.Errorf("failed to read config %s: %w", file, err)
+		}
+		if err := v.MergeConfigMap(local.AllSettings()); err != nil {
+			return fmt.Errorf("failed to merge config %s: %w", file, err)
+		}
 	}
+	return nil
 }
 
 // applyDefaultValues sets default values for configuration fields that need processing.
-func applyDefaultValues() {
-	// Set default MCP type if not specified
+func applyDefaultValues(cfg *Config) {
+	// Set default MCP type if not specified, a server with only a url is
+	// reached over SSE
 	for k, v := range cfg.MCPServers {
 		if v.Type == "" {
 			v.Type = MCPStdio
+			if v.URL != "" && v.Command == "" {
+				v.Type = MCPSse
+			}
 			cfg.MCPServers[k] = v
 		}
 	}
@@ -484,7 +740,7 @@ func validateAgent(cfg *Config, name AgentName, agent Agent) error {
 			"configured_model", agent.Model)
 
 		// Set default model based on available providers
-		if setDefaultModelForAgent(name) {
+		if setDefaultModelForAgent(cfg, name) {
 			logging.Info("set default model for agent", "agent", name, "model", cfg.Agents[name].Model)
 		} else {
 			return fmt.Errorf("no valid provider available for agent %s", name)
@@ -506,7 +762,7 @@ func validateAgent(cfg *Config, name AgentName, agent Agent) error {
 				"provider", provider)
 
 			// Set default model based on available providers
-			if setDefaultModelForAgent(name) {
+			if setDefaultModelForAgent(cfg, name) {
 				logging.Info("set default model for agent", "agent", name, "model", cfg.Agents[name].Model)
 			} else {
 				return fmt.Errorf("no valid provider available for agent %s", name)
@@ -526,7 +782,7 @@ func validateAgent(cfg *Config, name AgentName, agent Agent) error {
 			"provider", provider)
 
 		// Set default model based on available providers
-		if setDefaultModelForAgent(name) {
+		if setDefaultModelForAgent(cfg, name) {
 			logging.Info("set default model for agent", "agent", name, "model", cfg.Agents[name].Model)
 		} else {
 			return fmt.Errorf("no valid provider available for agent %s", name)
@@ -605,10 +861,23 @@ func validateAgent(cfg *Config, name AgentName, agent Agent) error {
 	return nil
 }
 
-// Validate checks if the configuration is valid and applies defaults where needed.
-func Validate() error {
-	if cfg == nil {
-		return fmt.Errorf("config not loaded")
+// validate checks if the configuration is valid and applies defaults where needed.
+func validate(cfg *Config) error {
+	// Agents may name their model by alias or name
+	for name, agent := range cfg.Agents {
+		if model, ok := resolveModel(cfg, string(agent.Model)); ok {
+			agent.Model = model.ID
+			cfg.Agents[name] = agent
+		}
+	}
+
+	// User-defined agents run with the model of the coder unless they set one
+	for name, agent := range cfg.Agents {
+		if agent.Model == "" && name != AgentCoder && isSessionAgent(cfg, name) {
+			agent.Model = cfg.Agents[AgentCoder].Model
+			agent.MaxTokens = cmp.Or(agent.MaxTokens, cfg.Agents[AgentCoder].MaxTokens)
+			cfg.Agents[name] = agent
+		}
 	}
 
 	// Validate agent models
@@ -637,6 +906,20 @@ func Validate() error {
 		}
 	}
 
+	// Validate compaction
+	if cfg.Compaction.Threshold <= 0 || cfg.Compaction.Threshold > 1 {
+		logging.Warn("invalid compaction threshold, setting to default",
+			"threshold", cfg.Compaction.Threshold,
+			"default", defaultCompactionThreshold)
+		cfg.Compaction.Threshold = defaultCompactionThreshold
+	}
+	if cfg.Compaction.KeepTurns < 0 {
+		logging.Warn("invalid number of turns kept by compaction, setting to default",
+			"keepTurns", cfg.Compaction.KeepTurns,
+			"default", defaultCompactionKeepTurns)
+		cfg.Compaction.KeepTurns = defaultCompactionKeepTurns
+	}
+
 	return nil
 }
 
@@ -668,7 +951,21 @@ func getProviderAPIKey(provider models.ModelProvider) string {
 }
 
 // setDefaultModelForAgent sets a default model for an agent based on available providers
-func setDefaultModelForAgent(agent AgentName) bool {
+func setDefaultModelForAgent(cfg *Config, agent AgentName) (ok bool) {
+	// A default only picks the model, the prompt and tools given to the
+	// agent are kept
+	defined := cfg.Agents[agent]
+	defer func() {
+		if ok {
+			defaulted := cfg.Agents[agent]
+			defaulted.Description = defined.Description
+			defaulted.Prompt = defined.Prompt
+			defaulted.Tools = defined.Tools
+			defaulted.Temperature = defined.Temperature
+			cfg.Agents[agent] = defaulted
+		}
+	}()
+
 	if hasCopilotCredentials() {
 		maxTokens := int64(5000)
 		if agent == AgentTitle {
@@ -816,117 +1113,270 @@ func setDefaultModelForAgent(agent AgentName) bool {
 	return false
 }
 
-func updateCfgFile(updateCfg func(config *Config)) error {
-	if cfg == nil {
+// updateCfgFile sets dotted keys of the user config file, creating it when
+// there's none. Only the lines of the keys change, the rest of the file is
+// kept as it's written.
+func updateCfgFile(values map[string]any) error {
+	if Get() == nil {
 		return fmt.Errorf("config not loaded")
 	}
 
-	// Get the config file path
 	configFile := viper.ConfigFileUsed()
-	var configData []byte
 	if configFile == "" {
-		homeDir, err := os.UserHomeDir()
-		if err != nil {
-			return fmt.Errorf("failed to get home directory: %w", err)
+		var err error
+		if configFile, err = UserConfigFile(); err != nil {
+			return err
 		}
-		configFile = filepath.Join(homeDir, fmt.Sprintf(".%s.json", appName))
 		logging.Info("config file not found, creating new one", "path", configFile)
-		configData = []byte(`{}`)
-	} else {
-		// Read the existing config file
-		data, err := os.ReadFile(configFile)
-		if err != nil {
-			return fmt.Errorf("failed to read config file: %w", err)
+	}
+
+	for _, key := range slices.Sorted(maps.Keys(values)) {
+		if err := setConfigFileValue(configFile, strings.Split(key, "."), values[key]); err != nil {
+			return err
 		}
-		configData = data
 	}
+	return nil
+}
+
+// Get returns the current configuration.
+// It's safe to call this function multiple times.
+func Get() *Config {
+	return active.Load()
+}
+
+// Update publishes a copy of the config with the change, the config returned
+// by Get before is left as it is. The copy is dropped when the change fails.
+func Update(change func(cfg *Config) error) error {
+	updating.Lock()
+	defer updating.Unlock()
+	cfg := Get()
+	if cfg == nil {
+		return fmt.Errorf("config not loaded")
+	}
+	next := *cfg
+	next.Agents = maps.Clone(cfg.Agents)
+	next.Providers = maps.Clone(cfg.Providers)
+	if err := change(&next); err != nil {
+		return err
+	}
+	active.Store(&next)
+	return nil
+}
 
-	// Parse the JSON
-	var userCfg *Config
-	if err := json.Unmarshal(configData, &userCfg); err != nil {
-		return fmt.Errorf("failed to parse config file: %w", err)
+// ToolTimeout returns the execution timeout for a tool, or zero if the tool
+// should run without a timeout.
+func ToolTimeout(toolName string) time.Duration {
+	cfg := Get()
+	if cfg == nil {
+		return 0
 	}
 
-	updateCfg(userCfg)
+	seconds := cfg.Tools.Timeout
+	// Viper lower cases map keys, so look up both spellings
+	if t, ok := cfg.Tools.Timeouts[toolName]; ok {
+		seconds = t
+	} else if t, ok := cfg.Tools.Timeouts[strings.ToLower(toolName)]; ok {
+		seconds = t
+	}
 
-	// Write the updated config back to file
-	updatedData, err := json.MarshalIndent(userCfg, "", "  ")
-	if err != nil {
-		return fmt.Errorf("failed to marshal config: %w", err)
+	if seconds <= 0 {
+		return 0
 	}
+	return time.Duration(seconds) * time.Second
+}
 
-	if err := os.WriteFile(configFile, updatedData, 0o644); err != nil {
-		return fmt.Errorf("failed to write config file: %w", err)
+// ToolDisabled reports whether the tool is disabled in the configuration.
+func ToolDisabled(toolName string) bool {
+	cfg := Get()
+	if cfg == nil {
+		return false
 	}
+	return slices.ContainsFunc(cfg.Tools.Disabled, func(name string) bool {
+		return strings.EqualFold(name, toolName)
+	})
+}
 
-	return nil
+// ToolAutoApproved reports whether the tool may run without asking the user
+// for permission.
+func ToolAutoApproved(toolName string) bool {
+	cfg := Get()
+	if cfg == nil {
+		return false
+	}
+	return slices.ContainsFunc(cfg.Tools.AutoApprove, func(name string) bool {
+		return strings.EqualFold(name, toolName)
+	})
 }
 
-// Get returns the current configuration.
-// It's safe to call this function multiple times.
-func Get() *Config {
-	return cfg
+// ParsePermissionRule splits a rule of the permissions config into its tool
+// and its pattern, empty when the rule applies to every use of the tool
+func ParsePermissionRule(rule string) (tool, pattern string, ok bool) {
+	tool, rest, hasPattern := strings.Cut(strings.TrimSpace(rule), "(")
+	tool = strings.TrimSpace(tool)
+	if hasPattern {
+		if pattern, ok = strings.CutSuffix(rest, ")"); !ok || strings.TrimSpace(pattern) == "" {
+			return "", "", false
+		}
+	}
+	if tool == "" || !doublestar.ValidatePattern(tool) {
+		return "", "", false
+	}
+	return tool, strings.TrimSpace(pattern), true
+}
+
+// WorkspaceRoot returns the directory the file system tools are restricted to.
+func WorkspaceRoot() string {
+	cfg := Get()
+	if cfg == nil {
+		panic("config not loaded")
+	}
+	if cfg.Workspace.Root == "" {
+		return cfg.WorkingDir
+	}
+	if !filepath.IsAbs(cfg.Workspace.Root) {
+		return filepath.Join(cfg.WorkingDir, cfg.Workspace.Root)
+	}
+	return cfg.Workspace.Root
 }
 
 // WorkingDirectory returns the current working directory from the configuration.
 func WorkingDirectory() string {
+	cfg := Get()
 	if cfg == nil {
 		panic("config not loaded")
 	}
 	return cfg.WorkingDir
 }
 
-func UpdateAgentModel(agentName AgentName, modelID models.ModelID) error {
+// ProjectRoot returns the root of the git repository of the working
+// directory, or the working directory outside of a repository.
+func ProjectRoot() string {
+	cfg := Get()
 	if cfg == nil {
 		panic("config not loaded")
 	}
+	if root, ok := gitRoot(cfg.WorkingDir); ok {
+		return root
+	}
+	return cfg.WorkingDir
+}
 
-	existingAgentCfg := cfg.Agents[agentName]
+func UpdateAgentModel(agentName AgentName, modelID models.ModelID) error {
+	newAgentCfg, err := setAgentModel(agentName, modelID)
+	if err != nil {
+		return err
+	}
 
+	return updateCfgFile(map[string]any{
+		fmt.Sprintf("agents.%s.model", agentName):     string(newAgentCfg.Model),
+		fmt.Sprintf("agents.%s.maxTokens", agentName): newAgentCfg.MaxTokens,
+	})
+}
+
+// UseAgentModel switches the model of an agent for this run only, the config
+// file is left as it is
+func UseAgentModel(agentName AgentName, modelID models.ModelID) error {
+	_, err := setAgentModel(agentName, modelID)
+	return err
+}
+
+func setAgentModel(agentName AgentName, modelID models.ModelID) (Agent, error) {
 	model, ok := models.SupportedModels[modelID]
 	if !ok {
-		return fmt.Errorf("model %s not supported", modelID)
+		return Agent{}, fmt.Errorf("model %s not supported", modelID)
+	}
+
+	var newAgentCfg Agent
+	err := Update(func(cfg *Config) error {
+		newAgentCfg = cfg.Agents[agentName]
+		newAgentCfg.Model = modelID
+		if model.DefaultMaxTokens > 0 {
+			newAgentCfg.MaxTokens = model.DefaultMaxTokens
+		}
+		cfg.Agents[agentName] = newAgentCfg
+		// The copy is dropped on failure, the config is left as it was
+		if err := validateAgent(cfg, agentName, newAgentCfg); err != nil {
+			return fmt.Errorf("failed to update agent model: %w", err)
+		}
+		return nil
+	})
+	if err != nil {
+		return Agent{}, err
 	}
+	return newAgentCfg, nil
+}
+
+// IsSessionAgent reports whether the agent can run sessions: the coder and the
+// agents defined in the config, the other built-in agents only serve them
+func IsSessionAgent(name AgentName) bool {
+	return isSessionAgent(Get(), name)
+}
 
-	maxTokens := existingAgentCfg.MaxTokens
-	if model.DefaultMaxTokens > 0 {
-		maxTokens = model.DefaultMaxTokens
+func isSessionAgent(cfg *Config, name AgentName) bool {
+	switch name {
+	case AgentCoder:
+		return true
+	case AgentSummarizer, AgentTask, AgentTitle:
+		return false
 	}
+	_, ok := cfg.Agents[name]
+	return ok
+}
 
-	newAgentCfg := Agent{
-		Model:           modelID,
-		MaxTokens:       maxTokens,
-		ReasoningEffort: existingAgentCfg.ReasoningEffort,
+// SessionAgents returns the agents that can run sessions, the coder first and
+// then the user-defined agents by name
+func SessionAgents() []AgentName {
+	cfg := Get()
+	if cfg == nil {
+		panic("config not loaded")
+	}
+	var agents []AgentName
+	for name := range cfg.Agents {
+		if name != AgentCoder && isSessionAgent(cfg, name) {
+			agents = append(agents, name)
+		}
 	}
-	cfg.Agents[agentName] = newAgentCfg
+	slices.Sort(agents)
+	return append([]AgentName{AgentCoder}, agents...)
+}
 
-	if err := validateAgent(cfg, agentName, newAgentCfg); err != nil {
-		// revert config update on failure
-		cfg.Agents[agentName] = existingAgentCfg
-		return fmt.Errorf("failed to update agent model: %w", err)
+// ResolveModel returns the supported model a name given by the user stands
+// for: an alias from models.aliases, a model ID or a model name, ignoring case
+func ResolveModel(name string) (models.Model, bool) {
+	cfg := Get()
+	if cfg == nil {
+		panic("config not loaded")
 	}
+	return resolveModel(cfg, name)
+}
 
-	return updateCfgFile(func(config *Config) {
-		if config.Agents == nil {
-			config.Agents = make(map[AgentName]Agent)
+func resolveModel(cfg *Config, name string) (models.Model, bool) {
+	for alias, target := range cfg.Models.Aliases {
+		if strings.EqualFold(alias, name) {
+			name = target
+			break
 		}
-		config.Agents[agentName] = newAgentCfg
-	})
+	}
+	for id, model := range models.SupportedModels {
+		if strings.EqualFold(string(id), name) || strings.EqualFold(model.Name, name) {
+			return model, true
+		}
+	}
+	return models.Model{}, false
 }
 
 // UpdateTheme updates the theme in the configuration and writes it to the config file.
 func UpdateTheme(themeName string) error {
-	if cfg == nil {
-		return fmt.Errorf("config not loaded")
-	}
-
 	// Update the in-memory config
-	cfg.TUI.Theme = themeName
+	if err := Update(func(cfg *Config) error {
+		cfg.TUI.Theme = themeName
+		return nil
+	}); err != nil {
+		return err
+	}
 
 	// Update the file config
-	return updateCfgFile(func(config *Config) {
-		config.TUI.Theme = themeName
-	})
+	return updateCfgFile(map[string]any{"tui.theme": themeName})
 }
 
 // Tries to load Github token from all possible locations
@@ -0,0 +1,368 @@
+package config
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+
+	"github.com/spf13/viper"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+)
+
+func TestResolveDataDirectory(t *testing.T) {
+	dataHome := t.TempDir()
+	t.Setenv("XDG_DATA_HOME", dataHome)
+
+	repo := t.TempDir()
+	sub := filepath.Join(repo, "internal", "db")
+	require.NoError(t, os.MkdirAll(sub, 0o755))
+	require.NoError(t, os.Mkdir(filepath.Join(repo, ".git"), 0o755))
+
+	// The data of a repository is kept at its root
+	assert.Equal(t, filepath.Join(repo, ".cryoncode"), resolveDataDirectory(sub, ".cryoncode"))
+	assert.Equal(t, "/var/cryoncode", resolveDataDirectory(sub, "/var/cryoncode"))
+
+	// Outside of one, an existing directory is kept and the user data
+	// directory is used otherwise
+	dir := t.TempDir()
+	assert.Equal(t, filepath.Join(dataHome, "cryoncode"), resolveDataDirectory(dir, ".cryoncode"))
+	require.NoError(t, os.Mkdir(filepath.Join(dir, ".cryoncode"), 0o755))
+	assert.Equal(t, filepath.Join(dir, ".cryoncode"), resolveDataDirectory(dir, ".cryoncode"))
+}
+
+func TestFindConfigFile(t *testing.T) {
+	home, project := t.TempDir(), t.TempDir()
+	_, ok := findConfigFile(home, project)
+	assert.False(t, ok)
+
+	tomlFile := filepath.Join(project, "cryoncode.toml")
+	require.NoError(t, os.WriteFile(tomlFile, []byte(""), 0o644))
+	file, ok := findConfigFile(home, project)
+	assert.True(t, ok)
+	assert.Equal(t, tomlFile, file)
+
+	// The JSON file comes first in a directory
+	jsonFile := filepath.Join(project, ".cryoncode.json")
+	require.NoError(t, os.WriteFile(jsonFile, []byte("{}"), 0o644))
+	file, _ = findConfigFile(home, project)
+	assert.Equal(t, jsonFile, file)
+}
+
+func TestTOMLConfigFile(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "cryoncode.toml")
+	require.NoError(t, os.WriteFile(path, []byte(`# Themes: cryoncode, dracula, ...
+[tui]
+theme = "dracula"
+inputRatio = 0.4
+
+[agents.coder]
+model = "claude-4-sonnet"
+maxTokens = 5000
+`), 0o644))
+
+	// Only the lines of the keys change, the comments are kept
+	require.NoError(t, setConfigFileValue(path, []string{"tui", "theme"}, "tokyonight"))
+	require.NoError(t, setConfigFileValue(path, []string{"agents", "coder", "reasoningEffort"}, "high"))
+	require.NoError(t, setConfigFileValue(path, []string{"agents", "task", "maxTokens"}, float64(8000)))
+	data, err := os.ReadFile(path)
+	require.NoError(t, err)
+	assert.Equal(t, `# Themes: cryoncode, dracula, ...
+[tui]
+theme = 'tokyonight'
+inputRatio = 0.4
+
+[agents.coder]
+model = "claude-4-sonnet"
+maxTokens = 5000
+reasoningEffort = 'high'
+
+[agents.task]
+maxTokens = 8000
+`, string(data))
+
+	// The key can't be added to a table defined with dotted keys
+	require.NoError(t, os.WriteFile(path, []byte("tui.theme = \"dracula\"\n"), 0o644))
+	assert.ErrorContains(t, setConfigFileValue(path, []string{"tui", "inputRatio"}, 0.5), "change the setting by hand")
+}
+
+func TestLocalConfigFiles(t *testing.T) {
+	repo := t.TempDir()
+	sub := filepath.Join(repo, "services", "api")
+	require.NoError(t, os.MkdirAll(sub, 0o755))
+	require.NoError(t, os.Mkdir(filepath.Join(repo, ".git"), 0o755))
+	assert.Empty(t, localConfigFiles(sub))
+
+	root := filepath.Join(repo, "cryoncode.toml")
+	require.NoError(t, os.WriteFile(root, []byte(""), 0o644))
+	assert.Equal(t, []string{root}, localConfigFiles(sub))
+
+	// The closest file comes last to be merged over the others
+	local := filepath.Join(sub, ".cryoncode.json")
+	require.NoError(t, os.WriteFile(local, []byte("{}"), 0o644))
+	assert.Equal(t, []string{root, local}, localConfigFiles(sub))
+
+	// Outside of a repository only the working directory is looked at
+	dir := t.TempDir()
+	assert.Empty(t, localConfigFiles(dir))
+}
+
+func TestCheckConfigFile(t *testing.T) {
+	path := filepath.Join(t.TempDir(), ".cryoncode.json")
+	require.NoError(t, os.WriteFile(path, []byte(`{
+  "agents": {"coder": {"modle": "gpt-4.1", "maxTokens": "5000"}},
+  "tui": {"theme": "dracula", "editorMaxHeight": 10.5},
+  "tools": {"disabled": "bash"},
+  "lsp": {"go": {"command": "gopls", "options": {"any": true}}},
+  "autocompact": true,
+  "wd": "/project",
+  "workingDir": "/project",
+  "wdir": "/project"
+}`), 0o644))
+
+	problems, err := checkConfigFile(path)
+	require.NoError(t, err)
+	assert.Equal(t, []string{
+		path + ": agents.coder.maxTokens: expected a number, got a string",
+		path + ": agents.coder.modle: unknown key",
+		path + ": tools.disabled: expected a list, got a string",
+		path + ": tui.editorMaxHeight: expected a whole number, got a number",
+		path + ": wdir: unknown key",
+	}, problems)
+}
+
+func TestCheckConflicts(t *testing.T) {
+	problems := checkConflicts(&Config{
+		MCPServers: map[string]MCPServer{
+			"local":  {Type: MCPStdio, Command: "server"},
+			"remote": {Type: MCPSse, Command: "server"},
+		},
+		WebSearch: WebSearchConfig{Provider: "searxng"},
+		Data:      Data{Encrypt: true, Transcripts: true},
+	})
+	assert.Equal(t, []string{
+		"data.transcripts: transcripts are written in plain text and can't be used with data.encrypt",
+		"mcpServers.remote: an sse server needs a url",
+		"mcpServers.remote: command is only used by stdio servers",
+		"webSearch.url: the searxng provider needs the url of an instance",
+	}, problems)
+}
+
+func TestMCPServerConfig(t *testing.T) {
+	var servers map[string]MCPServer
+	require.NoError(t, json.Unmarshal([]byte(`{
+  "object": {"type": "stdio", "command": "server", "env": {"API_TOKEN": "secret", "DEBUG": "1"}},
+  "list": {"type": "stdio", "command": "server", "env": ["DEBUG=1", "API_TOKEN=secret"]},
+  "paused": {"url": "https://mcp.example.com/sse", "command": "server", "enabled": false}
+}`), &servers))
+	assert.Equal(t, []string{"API_TOKEN=secret", "DEBUG=1"}, servers["object"].Env.List())
+	assert.Equal(t, servers["object"].Env, servers["list"].Env)
+	assert.True(t, servers["object"].IsEnabled())
+	assert.False(t, servers["paused"].IsEnabled())
+	assert.Empty(t, checkConflicts(&Config{MCPServers: servers}))
+
+	env, err := mcpEnvHook(reflect.TypeOf([]any{}), reflect.TypeOf(MCPEnv{}), []any{"DEBUG=1"})
+	require.NoError(t, err)
+	assert.Equal(t, MCPEnv{"DEBUG": "1"}, env)
+
+	path := filepath.Join(t.TempDir(), ".cryoncode.json")
+	require.NoError(t, os.WriteFile(path, []byte(`{"mcpServers": {
+  "object": {"command": "server", "env": {"DEBUG": "1"}},
+  "list": {"command": "server", "env": ["DEBUG=1", 2]}
+}}`), 0o644))
+	problems, err := checkConfigFile(path)
+	require.NoError(t, err)
+	assert.Equal(t, []string{path + ": mcpServers.list.env[1]: expected a string, got a number"}, problems)
+}
+
+func TestSetConfigValue(t *testing.T) {
+	path := filepath.Join(t.TempDir(), ".cryoncode.json")
+	require.NoError(t, os.WriteFile(path, []byte(`{"tui": {"editormaxheight": 5}}`), 0o644))
+
+	require.NoError(t, SetConfigValue(path, "tui.theme", "dracula"))
+	require.NoError(t, SetConfigValue(path, "TUI.EditorMaxHeight", "12"))
+	require.NoError(t, SetConfigValue(path, "agents.coder.maxTokens", "8000"))
+	require.NoError(t, SetConfigValue(path, "tools.disabled", `["fetch"]`))
+
+	values, err := ReadConfigFile(path)
+	require.NoError(t, err)
+	assert.Equal(t, map[string]any{
+		"tui":    map[string]any{"theme": "dracula", "editorMaxHeight": float64(12)},
+		"agents": map[string]any{"coder": map[string]any{"maxTokens": float64(8000)}},
+		"tools":  map[string]any{"disabled": []any{"fetch"}},
+	}, values)
+	value, ok := GetConfigValue(values, "Agents.Coder.MaxTokens")
+	assert.True(t, ok)
+	assert.Equal(t, float64(8000), value)
+
+	assert.ErrorContains(t, SetConfigValue(path, "tui.themes", "dracula"), "unknown key tui.themes")
+	assert.ErrorContains(t, SetConfigValue(path, "tui.editorMaxHeight", "tall"), "invalid value")
+	assert.ErrorContains(t, SetConfigValue(path, "tui.vimMode", "1"), "expected true or false")
+
+	// A new file may hold API keys, only the user can read it
+	secret := filepath.Join(t.TempDir(), ".cryoncode.json")
+	require.NoError(t, SetConfigValue(secret, "providers.openai.apiKey", "sk-test"))
+	info, err := os.Stat(secret)
+	require.NoError(t, err)
+	assert.Equal(t, os.FileMode(0o600), info.Mode().Perm())
+}
+
+func TestIsSecretKey(t *testing.T) {
+	assert.True(t, IsSecretKey("providers.openai.apiKey"))
+	assert.True(t, IsSecretKey("webSearch.apiKey"))
+	assert.True(t, IsSecretKey("mcpServers.github.env.GITHUB_TOKEN"))
+	assert.True(t, IsSecretKey("mcpServers.api.headers.Authorization"))
+	assert.False(t, IsSecretKey("agents.coder.model"))
+	assert.False(t, IsSecretKey("tui.theme"))
+}
+
+func TestExpandEnv(t *testing.T) {
+	t.Setenv("TEST_API_KEY", "secret")
+	t.Setenv("TEST_EMPTY", "")
+	cfg := &Config{
+		Providers: map[models.ModelProvider]Provider{
+			models.ProviderOpenAI: {APIKey: "${TEST_API_KEY}"},
+		},
+		Data: Data{Directory: "${TEST_EMPTY:-.cryoncode}"},
+		MCPServers: map[string]MCPServer{
+			"github": {Command: "server", Args: []string{"--token=${TEST_MISSING}", "$1"}},
+		},
+		LSP: map[string]LSPConfig{
+			"go": {Options: map[string]any{"env": []any{"KEY=${TEST_API_KEY}"}}},
+		},
+	}
+
+	assert.Equal(t, []string{"TEST_MISSING"}, expandEnv(cfg))
+	assert.Equal(t, "secret", cfg.Providers[models.ProviderOpenAI].APIKey)
+	assert.Equal(t, ".cryoncode", cfg.Data.Directory)
+	assert.Equal(t, []string{"--token=", "$1"}, cfg.MCPServers["github"].Args)
+	assert.Equal(t, map[string]any{"env": []any{"KEY=secret"}}, cfg.LSP["go"].Options)
+}
+
+func TestResolveModel(t *testing.T) {
+	cfg := &Config{Models: ModelsConfig{Aliases: map[string]string{
+		"fast":  "gpt-4o-mini",
+		"smart": "Claude 4 Sonnet",
+		"gone":  "gpt-1",
+	}}}
+
+	for name, id := range map[string]models.ModelID{
+		"fast":            models.GPT4oMini,
+		"SMART":           models.Claude4Sonnet,
+		"claude-4-sonnet": models.Claude4Sonnet,
+		"claude 4 sonnet": models.Claude4Sonnet,
+	} {
+		model, ok := resolveModel(cfg, name)
+		assert.True(t, ok, name)
+		assert.Equal(t, id, model.ID, name)
+	}
+	_, ok := resolveModel(cfg, "gone")
+	assert.False(t, ok)
+	assert.Equal(t, []string{`models.aliases.gone: unknown model "gpt-1"`}, checkConflicts(cfg))
+}
+
+func TestSessionAgents(t *testing.T) {
+	previous := active.Load()
+	t.Cleanup(func() { active.Store(previous) })
+	active.Store(&Config{Agents: map[AgentName]Agent{
+		AgentCoder:  {Model: models.GPT4oMini},
+		AgentTitle:  {Model: models.GPT4oMini},
+		"reviewer":  {Prompt: "Review the changes"},
+		"architect": {Prompt: "Plan the changes"},
+	}})
+
+	assert.Equal(t, []AgentName{AgentCoder, "architect", "reviewer"}, SessionAgents())
+	assert.True(t, IsSessionAgent("reviewer"))
+	assert.False(t, IsSessionAgent(AgentTitle))
+	assert.False(t, IsSessionAgent("missing"))
+}
+
+func TestReload(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("GITHUB_TOKEN", "")
+	t.Setenv("OPENAI_API_KEY", "test")
+	previous := active.Load()
+	t.Cleanup(func() {
+		active.Store(previous)
+		viper.Reset()
+	})
+	active.Store(nil)
+	viper.Reset()
+
+	project := t.TempDir()
+	path := filepath.Join(project, ".cryoncode.json")
+	require.NoError(t, os.WriteFile(path, []byte(`{
+  "tui": {"theme": "dracula"},
+  "permissions": {"deny": ["bash(rm *)"]}
+}`), 0o644))
+	_, err := Load(project, false)
+	require.NoError(t, err)
+	require.NoError(t, UseAgentModel(AgentCoder, models.GPT4oMini))
+
+	require.NoError(t, os.WriteFile(path, []byte(`{
+  "tui": {"theme": "tokyonight"},
+  "mcpServers": {"files": {"command": "server"}}
+}`), 0o644))
+	before := Get()
+	reloaded, err := Reload()
+	require.NoError(t, err)
+	// The config read before the reload is left as it was
+	assert.Equal(t, "dracula", before.TUI.Theme)
+	assert.NotEmpty(t, before.Permissions.Deny)
+	assert.Equal(t, []string{"tui", "permissions"}, reloaded.Applied)
+	assert.Equal(t, []string{"mcpServers"}, reloaded.Restart)
+	assert.Equal(t, "tokyonight", Get().TUI.Theme)
+	assert.Empty(t, Get().Permissions.Deny)
+	assert.Empty(t, Get().MCPServers)
+	// The files didn't change the coder, so the model picked at runtime stays
+	assert.Equal(t, models.GPT4oMini, Get().Agents[AgentCoder].Model)
+
+	// An invalid file leaves the config as it was
+	require.NoError(t, os.WriteFile(path, []byte(`{"tui": {"theme": 1}}`), 0o644))
+	_, err = Reload()
+	assert.Error(t, err)
+	assert.Equal(t, "tokyonight", Get().TUI.Theme)
+}
+
+func TestWriteSetup(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+	t.Setenv("XDG_CONFIG_HOME", "")
+
+	project := t.TempDir()
+	path, err := WriteSetup(Setup{
+		Provider:      models.ProviderOpenAI,
+		APIKey:        "sk-test",
+		Model:         models.GPT41Mini,
+		DataDirectory: "~/cryoncode-data",
+	})
+	require.NoError(t, err)
+	assert.Equal(t, filepath.Join(home, ".cryoncode.json"), path)
+	assert.False(t, NeedsSetup(project))
+
+	values, err := ReadConfigFile(path)
+	require.NoError(t, err)
+	for key, want := range map[string]any{
+		"providers.openai.apiKey": "sk-test",
+		"agents.coder.model":      string(models.GPT41Mini),
+		"data.directory":          filepath.Join(home, "cryoncode-data"),
+	} {
+		value, ok := GetConfigValue(values, key)
+		assert.True(t, ok, key)
+		assert.Equal(t, want, value, key)
+	}
+
+	// The default data directory isn't written
+	require.NoError(t, os.Remove(path))
+	_, err = WriteSetup(Setup{Provider: models.ProviderOpenAI, APIKey: "sk-test", Model: models.GPT41, DataDirectory: ".cryoncode"})
+	require.NoError(t, err)
+	values, err = ReadConfigFile(path)
+	require.NoError(t, err)
+	_, ok := GetConfigValue(values, "data.directory")
+	assert.False(t, ok)
+}
@@ -0,0 +1,314 @@
+package config
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"reflect"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/pelletier/go-toml/v2"
+)
+
+// UserConfigFile returns the user config file, ~/.cryoncode.json when there
+// is none yet
+func UserConfigFile() (string, error) {
+	if file, ok := findConfigFile(globalConfigDirs()...); ok {
+		return file, nil
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return "", fmt.Errorf("failed to get home directory: %w", err)
+	}
+	return filepath.Join(home, configFileNames[0]), nil
+}
+
+// ReadConfigFile returns the settings of a config file as they're written,
+// none when the file doesn't exist
+func ReadConfigFile(path string) (map[string]any, error) {
+	values := make(map[string]any)
+	data, err := os.ReadFile(path)
+	if errors.Is(err, os.ErrNotExist) {
+		return values, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	if isTOML(path) {
+		err = toml.Unmarshal(data, &values)
+	} else {
+		err = json.Unmarshal(data, &values)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to read config %s: %w", path, err)
+	}
+	return values, nil
+}
+
+// GetConfigValue returns the value of a dotted key of the settings, keys are
+// matched ignoring case
+func GetConfigValue(values map[string]any, key string) (any, bool) {
+	var value any = values
+	for _, part := range strings.Split(key, ".") {
+		table, ok := value.(map[string]any)
+		if !ok {
+			return nil, false
+		}
+		if value, ok = table[tableKey(table, part)]; !ok {
+			return nil, false
+		}
+	}
+	return value, true
+}
+
+// FlattenConfig returns the dotted keys of the settings with their values,
+// sorted by key
+func FlattenConfig(values map[string]any) ([]string, map[string]any) {
+	flat := make(map[string]any)
+	var walk func(prefix string, table map[string]any)
+	walk = func(prefix string, table map[string]any) {
+		for key, value := range table {
+			if nested, ok := value.(map[string]any); ok && len(nested) > 0 {
+				walk(joinPath(prefix, key), nested)
+				continue
+			}
+			flat[joinPath(prefix, key)] = value
+		}
+	}
+	walk("", values)
+	keys := make([]string, 0, len(flat))
+	for key := range flat {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys, flat
+}
+
+// SetConfigValue sets a dotted key of the config file to the value given on
+// the command line, read as JSON unless the setting is a string. The key and
+// the value are checked against Config before the file is written.
+func SetConfigValue(path, key, raw string) error {
+	typ, keys, err := resolveKey(key)
+	if err != nil {
+		return err
+	}
+	var value any = raw
+	if typ.Kind() != reflect.String {
+		if err := json.Unmarshal([]byte(raw), &value); err != nil {
+			return fmt.Errorf("invalid value for %s: %w", key, err)
+		}
+	}
+	var problems []string
+	checkValue(strings.Join(keys, "."), value, typ, &problems)
+	if len(problems) > 0 {
+		return configError(problems)
+	}
+
+	return setConfigFileValue(path, keys, value)
+}
+
+// setConfigFileValue sets a key of a config file, given as its parts, leaving
+// the other settings as they are. A TOML file is edited line by line so it
+// keeps its comments and layout.
+func setConfigFileValue(path string, keys []string, value any) error {
+	data, err := os.ReadFile(path)
+	if err != nil && !errors.Is(err, os.ErrNotExist) {
+		return err
+	}
+	if isTOML(path) {
+		data, err = setTOMLValue(data, keys, value)
+	} else {
+		data, err = setJSONValue(data, keys, value)
+	}
+	if err != nil {
+		return fmt.Errorf("failed to update %s in %s: %w", strings.Join(keys, "."), path, err)
+	}
+	// The file may hold API keys, it's only readable by the user unless it
+	// exists with other permissions already
+	if err := os.MkdirAll(filepath.Dir(path), 0o700); err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0o600)
+}
+
+// setJSONValue sets a key of a JSON document, matching the keys already in it
+// ignoring case
+func setJSONValue(data []byte, keys []string, value any) ([]byte, error) {
+	values := make(map[string]any)
+	if len(bytes.TrimSpace(data)) > 0 {
+		if err := json.Unmarshal(data, &values); err != nil {
+			return nil, err
+		}
+	}
+	table := values
+	for _, part := range keys[:len(keys)-1] {
+		part = tableKey(table, part)
+		nested, ok := table[part].(map[string]any)
+		if !ok {
+			nested = make(map[string]any)
+			table[part] = nested
+		}
+		table = nested
+	}
+	last := keys[len(keys)-1]
+	delete(table, tableKey(table, last))
+	table[last] = value
+	return json.MarshalIndent(values, "", "  ")
+}
+
+// setTOMLValue sets a key of a TOML document by replacing the line of the key
+// in its table, or adding one at the end of the table, or the table at the
+// end of the document. Keys are matched ignoring case.
+func setTOMLValue(data []byte, keys []string, value any) ([]byte, error) {
+	last := keys[len(keys)-1]
+	values := tomlValues(map[string]any{last: value})
+	if len(values) == 0 {
+		return nil, errors.New("TOML has no null value")
+	}
+	var line bytes.Buffer
+	encoder := toml.NewEncoder(&line)
+	encoder.SetTablesInline(true)
+	if err := encoder.Encode(values); err != nil {
+		return nil, err
+	}
+
+	table := keys[:len(keys)-1]
+	lines := strings.SplitAfter(string(data), "\n")
+	// Keys before the first table header are in the root table
+	inTable := len(table) == 0
+	insertAt := -1
+	if inTable {
+		insertAt = 0
+	}
+	for i, l := range lines {
+		trimmed := strings.TrimSpace(l)
+		if strings.HasPrefix(trimmed, "[") {
+			name, _, ok := strings.Cut(strings.TrimPrefix(trimmed, "["), "]")
+			inTable = ok && !strings.HasPrefix(name, "[") && equalTOMLKeys(splitTOMLKey(name), table)
+			if inTable {
+				insertAt = i + 1
+			}
+			continue
+		}
+		if !inTable || trimmed == "" || strings.HasPrefix(trimmed, "#") {
+			continue
+		}
+		if key, _, ok := strings.Cut(trimmed, "="); ok && equalTOMLKeys(splitTOMLKey(key), []string{last}) {
+			indent := l[:len(l)-len(strings.TrimLeft(l, " \t"))]
+			lines[i] = indent + line.String()
+			return checkTOML(strings.Join(lines, ""))
+		}
+		insertAt = i + 1
+	}
+
+	if insertAt >= 0 {
+		if insertAt > 0 && !strings.HasSuffix(lines[insertAt-1], "\n") {
+			lines[insertAt-1] += "\n"
+		}
+		lines = slices.Insert(lines, insertAt, line.String())
+		return checkTOML(strings.Join(lines, ""))
+	}
+	doc := string(data)
+	if doc != "" {
+		doc = strings.TrimRight(doc, "\n") + "\n\n"
+	}
+	header := make([]string, len(table))
+	for i, part := range table {
+		header[i] = tomlKey(part)
+	}
+	return checkTOML(doc + "[" + strings.Join(header, ".") + "]\n" + line.String())
+}
+
+// checkTOML returns the edited TOML document unless it can't be read anymore,
+// which happens when the key is already set in a way the edit doesn't handle,
+// like a dotted key or an inline table
+func checkTOML(doc string) ([]byte, error) {
+	var values map[string]any
+	if err := toml.Unmarshal([]byte(doc), &values); err != nil {
+		return nil, fmt.Errorf("the file can't be edited, change the setting by hand: %w", err)
+	}
+	return []byte(doc), nil
+}
+
+// splitTOMLKey returns the parts of a dotted TOML key without their quotes
+func splitTOMLKey(key string) []string {
+	parts := strings.Split(key, ".")
+	for i, part := range parts {
+		parts[i] = strings.Trim(strings.TrimSpace(part), `"'`)
+	}
+	return parts
+}
+
+func equalTOMLKeys(a, b []string) bool {
+	return slices.EqualFunc(a, b, strings.EqualFold)
+}
+
+// tomlKey quotes a key unless it's a bare key
+func tomlKey(key string) string {
+	for _, r := range key {
+		if !(r >= 'a' && r <= 'z' || r >= 'A' && r <= 'Z' || r >= '0' && r <= '9' || r == '_' || r == '-') {
+			return strconv.Quote(key)
+		}
+	}
+	return key
+}
+
+// IsSecretKey reports whether the setting at a dotted key holds a secret, like
+// an API key or a token, that shouldn't be printed
+func IsSecretKey(key string) bool {
+	last := strings.ToLower(key[strings.LastIndex(key, ".")+1:])
+	for _, suffix := range []string{"apikey", "token", "secret", "password", "authorization"} {
+		if strings.HasSuffix(last, suffix) {
+			return true
+		}
+	}
+	return false
+}
+
+// resolveKey returns the type of the setting at a dotted key and the parts of
+// the key, spelled as in the documentation when they're fields of Config
+func resolveKey(key string) (reflect.Type, []string, error) {
+	parts := strings.Split(key, ".")
+	typ := reflect.TypeOf(Config{})
+	for i, part := range parts {
+		if part == "" {
+			return nil, nil, fmt.Errorf("invalid key %q", key)
+		}
+		switch typ.Kind() {
+		case reflect.Struct:
+			field, ok := configField(typ, part)
+			if !ok {
+				return nil, nil, fmt.Errorf("unknown key %s", strings.Join(parts[:i+1], "."))
+			}
+			if name := jsonName(field); strings.EqualFold(name, part) {
+				parts[i] = name
+			}
+			typ = field.Type
+		case reflect.Map:
+			typ = typ.Elem()
+		default:
+			return nil, nil, fmt.Errorf("%s is not an object", strings.Join(parts[:i], "."))
+		}
+	}
+	return typ, parts, nil
+}
+
+// tableKey returns the key of the table matching the key ignoring case, the
+// key itself when there's none
+func tableKey(table map[string]any, key string) string {
+	if _, ok := table[key]; ok {
+		return key
+	}
+	for k := range table {
+		if strings.EqualFold(k, key) {
+			return k
+		}
+	}
+	return key
+}
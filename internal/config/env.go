@@ -0,0 +1,77 @@
+package config
+
+import (
+	"os"
+	"reflect"
+	"regexp"
+	"slices"
+	"strings"
+)
+
+// envReference matches ${NAME} and ${NAME:-default} in config values. $NAME
+// isn't expanded, so commands keep their shell variables.
+var envReference = regexp.MustCompile(`\$\{([A-Za-z_][A-Za-z0-9_]*)(:-[^}]*)?\}`)
+
+// expandEnv replaces the references to environment variables in the strings
+// of the config, so secrets and machine specific paths can stay out of the
+// config files checked into a project. It returns the variables referenced
+// that aren't set, they're replaced with their default or nothing.
+func expandEnv(cfg *Config) []string {
+	var unset []string
+	expandValue(reflect.ValueOf(cfg).Elem(), &unset)
+	slices.Sort(unset)
+	return slices.Compact(unset)
+}
+
+func expandValue(v reflect.Value, unset *[]string) {
+	switch v.Kind() {
+	case reflect.String:
+		if s := v.String(); strings.Contains(s, "${") {
+			v.SetString(expandString(s, unset))
+		}
+	case reflect.Struct:
+		for i := range v.NumField() {
+			if v.Type().Field(i).IsExported() {
+				expandValue(v.Field(i), unset)
+			}
+		}
+	case reflect.Slice:
+		for i := range v.Len() {
+			expandValue(v.Index(i), unset)
+		}
+	case reflect.Map:
+		// Map values can't be set in place, they're expanded in a copy
+		for _, key := range v.MapKeys() {
+			value := reflect.New(v.Type().Elem()).Elem()
+			value.Set(v.MapIndex(key))
+			expandValue(value, unset)
+			v.SetMapIndex(key, value)
+		}
+	case reflect.Interface, reflect.Pointer:
+		if v.IsNil() {
+			return
+		}
+		if v.Kind() == reflect.Pointer {
+			expandValue(v.Elem(), unset)
+			return
+		}
+		value := reflect.New(v.Elem().Type()).Elem()
+		value.Set(v.Elem())
+		expandValue(value, unset)
+		v.Set(value)
+	}
+}
+
+func expandString(s string, unset *[]string) string {
+	return envReference.ReplaceAllStringFunc(s, func(ref string) string {
+		match := envReference.FindStringSubmatch(ref)
+		if value, ok := os.LookupEnv(match[1]); ok && value != "" {
+			return value
+		}
+		if fallback, ok := strings.CutPrefix(match[2], ":-"); ok {
+			return fallback
+		}
+		*unset = append(*unset, match[1])
+		return ""
+	})
+}
@@ -0,0 +1,121 @@
+package config
+
+import (
+	"fmt"
+	"math"
+	"os"
+	"path/filepath"
+	"slices"
+
+	"github.com/spf13/viper"
+)
+
+// configFileNames are the names of the config file, looked for in this order
+// in each directory. The TOML file takes the same keys as the JSON one and
+// can have comments.
+var configFileNames = []string{
+	fmt.Sprintf(".%s.json", appName),
+	fmt.Sprintf("%s.toml", appName),
+}
+
+// globalConfigDirs returns the directories of the user config file, the first
+// one with a config file is used
+func globalConfigDirs() []string {
+	var dirs []string
+	home, err := os.UserHomeDir()
+	if err == nil {
+		dirs = append(dirs, home)
+	}
+	if configHome := os.Getenv("XDG_CONFIG_HOME"); configHome != "" {
+		dirs = append(dirs, filepath.Join(configHome, appName))
+	}
+	if err == nil {
+		dirs = append(dirs, filepath.Join(home, ".config", appName))
+	}
+	return dirs
+}
+
+// findConfigFile returns the config file of the first directory that has one
+func findConfigFile(dirs ...string) (string, bool) {
+	for _, dir := range dirs {
+		for _, name := range configFileNames {
+			path := filepath.Join(dir, name)
+			if info, err := os.Stat(path); err == nil && !info.IsDir() {
+				return path, true
+			}
+		}
+	}
+	return "", false
+}
+
+// localConfigFiles returns the config files of the project, from the root of
+// the git repository of the working directory down to the working directory,
+// so it works the same when started from a subdirectory and a subdirectory
+// can override the root. Outside of a repository only the working directory
+// has one.
+func localConfigFiles(workingDir string) []string {
+	var files []string
+	for _, dir := range localConfigDirs(workingDir) {
+		if file, ok := findConfigFile(dir); ok {
+			files = append(files, file)
+		}
+	}
+	slices.Reverse(files)
+	return files
+}
+
+// localConfigDirs returns the directories the config files of the project
+// are looked for in, from the working directory up to the root of its git
+// repository
+func localConfigDirs(workingDir string) []string {
+	root, ok := gitRoot(workingDir)
+	if !ok {
+		root = workingDir
+	}
+	var dirs []string
+	for dir := workingDir; ; dir = filepath.Dir(dir) {
+		dirs = append(dirs, dir)
+		if dir == root || filepath.Dir(dir) == dir {
+			break
+		}
+	}
+	return dirs
+}
+
+// configFiles returns the config files read by Load, the user config file
+// first and the ones of the project after it
+func configFiles(v *viper.Viper, workingDir string) []string {
+	var files []string
+	if file := v.ConfigFileUsed(); file != "" {
+		files = append(files, file)
+	}
+	for _, file := range localConfigFiles(workingDir) {
+		if !slices.Contains(files, file) {
+			files = append(files, file)
+		}
+	}
+	return files
+}
+
+// isTOML reports whether the config file is written in TOML
+func isTOML(path string) bool {
+	return filepath.Ext(path) == ".toml"
+}
+
+// tomlValues drops the null values, which TOML doesn't have, and turns the
+// whole numbers JSON decodes as floats back into integers
+func tomlValues(values map[string]any) map[string]any {
+	for key, value := range values {
+		switch v := value.(type) {
+		case nil:
+			delete(values, key)
+		case float64:
+			if v == math.Trunc(v) && math.Abs(v) < 1<<53 {
+				values[key] = int64(v)
+			}
+		case map[string]any:
+			values[key] = tomlValues(v)
+		}
+	}
+	return values
+}
@@ -0,0 +1,69 @@
+package config
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+
+	"github.com/zhenbah/cryoncode/internal/keyring"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+)
+
+const (
+	// apiKeysAccount is the keyring entry of the API keys saved by `cryoncode
+	// auth login`, a JSON object by provider so starting reads the keyring
+	// once
+	apiKeysAccount = "api-keys"
+	apiKeysLabel   = "Cryon code API keys"
+)
+
+// KeyringProviders are the providers whose API key can be saved in the OS
+// keyring
+var KeyringProviders = []models.ModelProvider{
+	models.ProviderAnthropic,
+	models.ProviderOpenAI,
+	models.ProviderGemini,
+	models.ProviderGROQ,
+	models.ProviderOpenRouter,
+	models.ProviderXAI,
+	models.ProviderAzure,
+	models.ProviderCopilot,
+}
+
+// StoredAPIKeys returns the API keys saved in the OS keyring by provider
+func StoredAPIKeys() (map[models.ModelProvider]string, error) {
+	keys := make(map[models.ModelProvider]string)
+	data, err := keyring.Get(apiKeysAccount)
+	if errors.Is(err, keyring.ErrNotFound) {
+		return keys, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	if err := json.Unmarshal([]byte(data), &keys); err != nil {
+		return nil, fmt.Errorf("invalid API keys in the keyring: %w", err)
+	}
+	return keys, nil
+}
+
+// SaveAPIKey saves the API key of the provider in the OS keyring, an empty
+// key removes it
+func SaveAPIKey(provider models.ModelProvider, key string) error {
+	keys, err := StoredAPIKeys()
+	if err != nil {
+		return err
+	}
+	if key == "" {
+		delete(keys, provider)
+	} else {
+		keys[provider] = key
+	}
+	if len(keys) == 0 {
+		return keyring.Delete(apiKeysAccount)
+	}
+	data, err := json.Marshal(keys)
+	if err != nil {
+		return err
+	}
+	return keyring.Set(apiKeysAccount, apiKeysLabel, string(data))
+}
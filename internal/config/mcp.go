@@ -0,0 +1,104 @@
+package config
+
+import (
+	"encoding/json"
+	"reflect"
+	"sort"
+	"strings"
+)
+
+// MCPEnv are the environment variables of an MCP server. They're written as
+// an object, the way other MCP clients take them, or as a list of KEY=value
+// strings.
+type MCPEnv map[string]string
+
+// List returns the variables as KEY=value strings, sorted by name
+func (e MCPEnv) List() []string {
+	list := make([]string, 0, len(e))
+	for name, value := range e {
+		list = append(list, name+"="+value)
+	}
+	sort.Strings(list)
+	return list
+}
+
+func (e *MCPEnv) UnmarshalJSON(data []byte) error {
+	var list []string
+	if err := json.Unmarshal(data, &list); err == nil {
+		*e = envFromList(list)
+		return nil
+	}
+	var env map[string]string
+	if err := json.Unmarshal(data, &env); err != nil {
+		return err
+	}
+	*e = env
+	return nil
+}
+
+// IsEnabled reports whether the server is started, servers are enabled
+// unless they set enabled to false
+func (m MCPServer) IsEnabled() bool {
+	return m.Enabled == nil || *m.Enabled
+}
+
+func envFromList(list []string) MCPEnv {
+	env := make(MCPEnv, len(list))
+	for _, variable := range list {
+		name, value, _ := strings.Cut(variable, "=")
+		env[name] = value
+	}
+	return env
+}
+
+// mcpEnvHook decodes the env of an MCP server written as a list
+func mcpEnvHook(from, to reflect.Type, data any) (any, error) {
+	list, ok := data.([]any)
+	if !ok || to != reflect.TypeOf(MCPEnv{}) {
+		return data, nil
+	}
+	variables := make([]string, 0, len(list))
+	for _, variable := range list {
+		if s, ok := variable.(string); ok {
+			variables = append(variables, s)
+		}
+	}
+	return envFromList(variables), nil
+}
+
+// restoreMCPEnvNames gives the env variables of the MCP servers back the
+// case they're written with in the config files, viper lowercases the keys
+// of objects
+func restoreMCPEnvNames(cfg *Config, files []string) error {
+	names := make(map[string]string)
+	for _, file := range files {
+		values, err := ReadConfigFile(file)
+		if err != nil {
+			return err
+		}
+		servers, _ := GetConfigValue(values, "mcpServers")
+		table, _ := servers.(map[string]any)
+		for _, value := range table {
+			server, _ := value.(map[string]any)
+			variables, _ := server[tableKey(server, "env")].(map[string]any)
+			for name := range variables {
+				names[strings.ToLower(name)] = name
+			}
+		}
+	}
+	for server, m := range cfg.MCPServers {
+		if len(m.Env) == 0 {
+			continue
+		}
+		env := make(MCPEnv, len(m.Env))
+		for name, value := range m.Env {
+			if written, ok := names[name]; ok {
+				name = written
+			}
+			env[name] = value
+		}
+		m.Env = env
+		cfg.MCPServers[server] = m
+	}
+	return nil
+}
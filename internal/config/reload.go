@@ -0,0 +1,194 @@
+package config
+
+import (
+	"context"
+	"fmt"
+	"maps"
+	"path/filepath"
+	"reflect"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/fsnotify/fsnotify"
+	"github.com/spf13/viper"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/pubsub"
+)
+
+// reloadDelay is how long Watch waits for the writes to a config file to
+// settle, editors often save a file in several steps
+const reloadDelay = 300 * time.Millisecond
+
+// Reloaded is published when the config files changed and were read again
+type Reloaded struct {
+	Applied []string // The sections whose changes were applied, e.g. tui or agents.coder
+	Restart []string // The sections whose changes need a restart
+	Err     error    // Why the files couldn't be read, the config is left as it was
+}
+
+var reloads = pubsub.NewBroker[Reloaded]()
+
+// Subscribe returns the reloads of the config made by Watch
+func Subscribe(ctx context.Context) <-chan pubsub.Event[Reloaded] {
+	return reloads.Subscribe(ctx)
+}
+
+// reloadable are the sections of the config that are read when they're used,
+// so changing them doesn't need a restart
+type reloadable struct {
+	TUI         TUIConfig
+	Keybindings map[string][]string
+	Agents      map[AgentName]Agent
+	Models      ModelsConfig
+	Tools       ToolsConfig
+	Permissions PermissionsConfig
+}
+
+// reloadableSections are the keys of the reloadable sections in the config
+var reloadableSections = []string{"tui", "keybindings", "agents", "models", "tools", "permissions"}
+
+// loaded are the reloadable sections as they were read from the config files.
+// A section is only replaced when the files change it, so the changes made at
+// runtime, like the model picked with --model, are kept otherwise.
+var loaded reloadable
+
+func reloadableOf(c *Config) reloadable {
+	return reloadable{
+		TUI:         c.TUI,
+		Keybindings: maps.Clone(c.Keybindings),
+		Agents:      maps.Clone(c.Agents),
+		Models:      ModelsConfig{Aliases: maps.Clone(c.Models.Aliases)},
+		Tools:       c.Tools,
+		Permissions: c.Permissions,
+	}
+}
+
+// Reload reads the config files again and applies the changes to the
+// reloadable sections. The other sections keep their values until a restart.
+// The changes are published as a new config, the one returned by Get before
+// is left as it is.
+func Reload() (Reloaded, error) {
+	updating.Lock()
+	defer updating.Unlock()
+	previous := Get()
+	if previous == nil {
+		return Reloaded{}, fmt.Errorf("config not loaded")
+	}
+	// Read with a viper instance of its own, so the keys removed from the
+	// files lose their value
+	next, err := load(viper.New(), previous.WorkingDir, previous.Debug, false)
+	if err != nil {
+		return Reloaded{}, err
+	}
+
+	var reloaded Reloaded
+	current := *previous
+	fresh := reloadableOf(next)
+	apply := func(name string, differs bool, set func()) {
+		if differs {
+			set()
+			reloaded.Applied = append(reloaded.Applied, name)
+		}
+	}
+	changed := func(previous, fresh, current, next any) bool {
+		return !reflect.DeepEqual(previous, fresh) && !reflect.DeepEqual(current, next)
+	}
+	apply("tui", changed(loaded.TUI, fresh.TUI, current.TUI, next.TUI), func() { current.TUI = next.TUI })
+	apply("keybindings", changed(loaded.Keybindings, fresh.Keybindings, current.Keybindings, next.Keybindings), func() {
+		current.Keybindings = next.Keybindings
+	})
+	names := slices.Collect(maps.Keys(fresh.Agents))
+	for name := range loaded.Agents {
+		if _, ok := fresh.Agents[name]; !ok {
+			names = append(names, name)
+		}
+	}
+	slices.Sort(names)
+	agents := maps.Clone(previous.Agents)
+	for _, name := range names {
+		apply("agents."+string(name), changed(loaded.Agents[name], fresh.Agents[name], current.Agents[name], next.Agents[name]), func() {
+			if agent, ok := next.Agents[name]; ok {
+				agents[name] = agent
+			} else {
+				delete(agents, name)
+			}
+		})
+	}
+	current.Agents = agents
+	apply("models", changed(loaded.Models, fresh.Models, current.Models, next.Models), func() { current.Models = next.Models })
+	if !reflect.DeepEqual(current.Tools.Custom, next.Tools.Custom) {
+		reloaded.Restart = append(reloaded.Restart, "tools.custom")
+	}
+	apply("tools", changed(loaded.Tools, fresh.Tools, current.Tools, next.Tools), func() {
+		tools := next.Tools
+		tools.Custom = current.Tools.Custom
+		current.Tools = tools
+	})
+	apply("permissions", changed(loaded.Permissions, fresh.Permissions, current.Permissions, next.Permissions), func() {
+		current.Permissions = next.Permissions
+	})
+	loaded = fresh
+	active.Store(&current)
+
+	currentValue, nextValue := reflect.ValueOf(&current).Elem(), reflect.ValueOf(next).Elem()
+	for i := range currentValue.NumField() {
+		name, _, _ := strings.Cut(currentValue.Type().Field(i).Tag.Get("json"), ",")
+		if slices.Contains(reloadableSections, name) || name == "wd" {
+			continue
+		}
+		if !reflect.DeepEqual(currentValue.Field(i).Interface(), nextValue.Field(i).Interface()) {
+			reloaded.Restart = append(reloaded.Restart, name)
+		}
+	}
+	return reloaded, nil
+}
+
+// Watch reloads the config when one of its files is written and publishes
+// the result, until the context is done. A config file created meanwhile is
+// picked up as long as its directory exists.
+func Watch(ctx context.Context) error {
+	cfg := Get()
+	if cfg == nil {
+		return fmt.Errorf("config not loaded")
+	}
+	watcher, err := fsnotify.NewWatcher()
+	if err != nil {
+		return fmt.Errorf("failed to watch the config files: %w", err)
+	}
+	defer watcher.Close()
+	for _, dir := range append(globalConfigDirs(), localConfigDirs(cfg.WorkingDir)...) {
+		if err := watcher.Add(dir); err != nil {
+			logging.Debug("not watching config directory", "dir", dir, "error", err)
+		}
+	}
+
+	var pending <-chan time.Time
+	for {
+		select {
+		case <-ctx.Done():
+			return nil
+		case event, ok := <-watcher.Events:
+			if !ok {
+				return nil
+			}
+			if event.Op != fsnotify.Chmod && slices.Contains(configFileNames, filepath.Base(event.Name)) {
+				pending = time.After(reloadDelay)
+			}
+		case err, ok := <-watcher.Errors:
+			if !ok {
+				return nil
+			}
+			logging.Warn("config watcher error", "error", err)
+		case <-pending:
+			pending = nil
+			reloaded, err := Reload()
+			if err != nil {
+				reloaded.Err = err
+			} else if len(reloaded.Applied) == 0 && len(reloaded.Restart) == 0 {
+				continue
+			}
+			reloads.Publish(pubsub.UpdatedEvent, reloaded)
+		}
+	}
+}
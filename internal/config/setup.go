@@ -0,0 +1,95 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+)
+
+// Setup is what the first-run setup asks for
+type Setup struct {
+	Provider      models.ModelProvider
+	APIKey        string
+	KeyInKeyring  bool // Save the key in the OS keyring rather than the config file
+	Model         models.ModelID
+	DataDirectory string
+}
+
+// setupModels are the models the setup offers first for a provider, the ones
+// setProviderDefaults gives the coder
+var setupModels = map[models.ModelProvider]models.ModelID{
+	models.ProviderCopilot:    models.CopilotGPT4o,
+	models.ProviderAnthropic:  models.Claude4Sonnet,
+	models.ProviderOpenAI:     models.GPT41,
+	models.ProviderGemini:     models.Gemini25,
+	models.ProviderGROQ:       models.QWENQwq,
+	models.ProviderOpenRouter: models.OpenRouterClaude37Sonnet,
+	models.ProviderXAI:        models.XAIGrok3Beta,
+	models.ProviderAzure:      models.AzureGPT41,
+}
+
+// SetupModel returns the model the setup offers first for a provider
+func SetupModel(provider models.ModelProvider) models.ModelID {
+	return setupModels[provider]
+}
+
+// NeedsSetup reports whether this is the first run: there's no config file
+// for the working directory and no provider can be used without one
+func NeedsSetup(workingDir string) bool {
+	if _, ok := findConfigFile(globalConfigDirs()...); ok {
+		return false
+	}
+	if len(localConfigFiles(workingDir)) > 0 {
+		return false
+	}
+	if models.LocalDefaultModel != "" || hasCopilotCredentials() || os.Getenv("XAI_API_KEY") != "" ||
+		os.Getenv("AZURE_OPENAI_ENDPOINT") != "" {
+		return false
+	}
+	for provider := range models.ProviderPopularity {
+		if getProviderAPIKey(provider) != "" {
+			return false
+		}
+	}
+	if keys, err := StoredAPIKeys(); err == nil && len(keys) > 0 {
+		return false
+	}
+	return true
+}
+
+// WriteSetup saves the API key of the setup and writes the user config file,
+// returning its path. The other agents get the defaults of the provider.
+func WriteSetup(setup Setup) (string, error) {
+	path, err := UserConfigFile()
+	if err != nil {
+		return "", err
+	}
+	if setup.KeyInKeyring {
+		if err := SaveAPIKey(setup.Provider, setup.APIKey); err != nil {
+			return "", fmt.Errorf("failed to save the API key in the keyring: %w", err)
+		}
+	} else if err := SetConfigValue(path, fmt.Sprintf("providers.%s.apiKey", setup.Provider), setup.APIKey); err != nil {
+		return "", err
+	}
+	if err := SetConfigValue(path, "agents.coder.model", string(setup.Model)); err != nil {
+		return "", err
+	}
+
+	dir := strings.TrimSpace(setup.DataDirectory)
+	if rest, ok := strings.CutPrefix(dir, "~/"); ok {
+		home, err := os.UserHomeDir()
+		if err != nil {
+			return "", fmt.Errorf("failed to get home directory: %w", err)
+		}
+		dir = filepath.Join(home, rest)
+	}
+	if dir != "" && dir != defaultDataDirectory {
+		if err := SetConfigValue(path, "data.directory", dir); err != nil {
+			return "", err
+		}
+	}
+	return path, nil
+}
@@ -0,0 +1,96 @@
+// Package crypt encrypts the content of the messages kept in the database,
+// when data.encrypt is set, with AES-GCM and a key from the OS keyring.
+package crypt
+
+import (
+	"context"
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"encoding/base64"
+	"fmt"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/db"
+)
+
+// prefix marks encrypted values, values stored before encryption was turned
+// on don't have it and are read as they are
+const prefix = "enc:v1:"
+
+// KeySize is the size of the AES-256 key
+const KeySize = 32
+
+// Cipher encrypts and decrypts stored values. A nil Cipher leaves them in
+// plain text.
+type Cipher struct {
+	aead cipher.AEAD
+}
+
+// New returns a Cipher using the key
+func New(key []byte) (*Cipher, error) {
+	if len(key) != KeySize {
+		return nil, fmt.Errorf("invalid encryption key: %d bytes instead of %d", len(key), KeySize)
+	}
+	block, err := aes.NewCipher(key)
+	if err != nil {
+		return nil, err
+	}
+	aead, err := cipher.NewGCM(block)
+	if err != nil {
+		return nil, err
+	}
+	return &Cipher{aead: aead}, nil
+}
+
+// Load returns the Cipher of the messages stored in the database, nil when
+// data.encrypt is off
+func Load(ctx context.Context, q db.Querier) (*Cipher, error) {
+	if !config.Get().Data.Encrypt {
+		return nil, nil
+	}
+	key, err := loadKey(ctx, q)
+	if err != nil {
+		return nil, fmt.Errorf("failed to load the encryption key: %w", err)
+	}
+	return New(key)
+}
+
+// Encrypt returns the value encrypted with a random nonce
+func (c *Cipher) Encrypt(value string) (string, error) {
+	if c == nil {
+		return value, nil
+	}
+	nonce := make([]byte, c.aead.NonceSize())
+	if _, err := rand.Read(nonce); err != nil {
+		return "", err
+	}
+	sealed := c.aead.Seal(nonce, nonce, []byte(value), nil)
+	return prefix + base64.StdEncoding.EncodeToString(sealed), nil
+}
+
+// Decrypt returns the value written by Encrypt, values that aren't encrypted
+// are returned as they are
+func (c *Cipher) Decrypt(stored string) (string, error) {
+	encoded, ok := strings.CutPrefix(stored, prefix)
+	if !ok {
+		return stored, nil
+	}
+	if c == nil {
+		return "", fmt.Errorf("the message is encrypted, set data.encrypt to read it")
+	}
+	sealed, err := base64.StdEncoding.DecodeString(encoded)
+	if err != nil {
+		return "", fmt.Errorf("invalid encrypted value: %w", err)
+	}
+	if len(sealed) < c.aead.NonceSize() {
+		return "", fmt.Errorf("invalid encrypted value: too short")
+	}
+	nonce, ciphertext := sealed[:c.aead.NonceSize()], sealed[c.aead.NonceSize():]
+	value, err := c.aead.Open(nil, nonce, ciphertext, nil)
+	if err != nil {
+		return "", fmt.Errorf("failed to decrypt the message, the encryption key may have changed: %w", err)
+	}
+	return string(value), nil
+}
@@ -0,0 +1,57 @@
+package crypt
+
+import (
+	"bytes"
+	"context"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/db"
+)
+
+func TestCipher(t *testing.T) {
+	c, err := New(bytes.Repeat([]byte{1}, KeySize))
+	require.NoError(t, err)
+
+	encrypted, err := c.Encrypt(`[{"type":"text"}]`)
+	require.NoError(t, err)
+	assert.NotContains(t, encrypted, "text")
+	decrypted, err := c.Decrypt(encrypted)
+	require.NoError(t, err)
+	assert.Equal(t, `[{"type":"text"}]`, decrypted)
+
+	// Values stored before encryption was turned on are read as they are
+	plain, err := c.Decrypt(`[]`)
+	require.NoError(t, err)
+	assert.Equal(t, `[]`, plain)
+
+	other, err := New(bytes.Repeat([]byte{2}, KeySize))
+	require.NoError(t, err)
+	_, err = other.Decrypt(encrypted)
+	assert.Error(t, err)
+
+	var none *Cipher
+	_, err = none.Decrypt(encrypted)
+	assert.Error(t, err)
+
+	_, err = New([]byte("short"))
+	assert.Error(t, err)
+}
+
+type encryptedData struct {
+	db.Querier
+	encrypted int64
+}
+
+func (q encryptedData) HasEncryptedData(context.Context) (int64, error) {
+	return q.encrypted, nil
+}
+
+func TestCheckNoEncryptedData(t *testing.T) {
+	assert.NoError(t, checkNoEncryptedData(context.Background(), encryptedData{}))
+	// A new key would make the messages stored so far unreadable
+	err := checkNoEncryptedData(context.Background(), encryptedData{encrypted: 1})
+	require.Error(t, err)
+	assert.Contains(t, err.Error(), KeyEnv)
+}
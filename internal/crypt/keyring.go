@@ -0,0 +1,70 @@
+package crypt
+
+import (
+	"context"
+	"crypto/rand"
+	"encoding/base64"
+	"errors"
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/keyring"
+)
+
+const (
+	// KeyEnv holds the base64 encoded key, used instead of the OS keyring
+	KeyEnv = "CRYONCODE_ENCRYPTION_KEY"
+
+	keyringAccount = "database"
+	keyringLabel   = "Cryon code message encryption key"
+)
+
+// loadKey returns the key from KeyEnv or the OS keyring, a key is created and
+// saved in the keyring the first time
+func loadKey(ctx context.Context, q db.Querier) ([]byte, error) {
+	if encoded := os.Getenv(KeyEnv); encoded != "" {
+		return decodeKey(encoded)
+	}
+	encoded, err := keyring.Get(keyringAccount)
+	if err == nil {
+		return decodeKey(encoded)
+	}
+	if !errors.Is(err, keyring.ErrNotFound) {
+		return nil, fmt.Errorf("%w, set %s instead", err, KeyEnv)
+	}
+	if err := checkNoEncryptedData(ctx, q); err != nil {
+		return nil, err
+	}
+	key := make([]byte, KeySize)
+	if _, err := rand.Read(key); err != nil {
+		return nil, err
+	}
+	if err := keyring.Set(keyringAccount, keyringLabel, base64.StdEncoding.EncodeToString(key)); err != nil {
+		return nil, fmt.Errorf("%w, set %s instead", err, KeyEnv)
+	}
+	return key, nil
+}
+
+// checkNoEncryptedData returns an error when the database has values
+// encrypted already. A keyring that can't be read may look empty, a new key
+// would then make everything stored so far unreadable.
+func checkNoEncryptedData(ctx context.Context, q db.Querier) error {
+	encrypted, err := q.HasEncryptedData(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to check the database for encrypted messages: %w", err)
+	}
+	if encrypted != 0 {
+		return fmt.Errorf("the keyring has no encryption key but the database has encrypted messages, unlock the keyring or set %s to their key", KeyEnv)
+	}
+	return nil
+}
+
+func decodeKey(encoded string) ([]byte, error) {
+	key, err := base64.StdEncoding.DecodeString(strings.TrimSpace(encoded))
+	if err != nil {
+		return nil, fmt.Errorf("invalid encryption key: %w", err)
+	}
+	return key, nil
+}
@@ -0,0 +1,120 @@
+// Code generated by sqlc. DO NOT EDIT.
+// versions:
+//   sqlc v1.29.0
+// source: blobs.sql
+
+package db
+
+import (
+	"context"
+)
+
+const createBlob = `-- name: CreateBlob :exec
+INSERT INTO blobs (
+    id,
+    data,
+    size,
+    created_at
+) VALUES (
+    ?, ?, ?, strftime('%s', 'now')
+)
+ON CONFLICT (id) DO NOTHING
+`
+
+type CreateBlobParams struct {
+	ID   string `json:"id"`
+	Data []byte `json:"data"`
+	Size int64  `json:"size"`
+}
+
+func (q *Queries) CreateBlob(ctx context.Context, arg CreateBlobParams) error {
+	_, err := q.exec(ctx, q.createBlobStmt, createBlob, arg.ID, arg.Data, arg.Size)
+	return err
+}
+
+const deleteUnusedBlobs = `-- name: DeleteUnusedBlobs :exec
+DELETE FROM blobs
+WHERE id NOT IN (SELECT blob_id FROM message_blobs)
+`
+
+func (q *Queries) DeleteUnusedBlobs(ctx context.Context) error {
+	_, err := q.exec(ctx, q.deleteUnusedBlobsStmt, deleteUnusedBlobs)
+	return err
+}
+
+const getBlob = `-- name: GetBlob :one
+SELECT id, data, size, created_at
+FROM blobs
+WHERE id = ? LIMIT 1
+`
+
+func (q *Queries) GetBlob(ctx context.Context, id string) (Blob, error) {
+	row := q.queryRow(ctx, q.getBlobStmt, getBlob, id)
+	var i Blob
+	err := row.Scan(
+		&i.ID,
+		&i.Data,
+		&i.Size,
+		&i.CreatedAt,
+	)
+	return i, err
+}
+
+const hasEncryptedData = `-- name: HasEncryptedData :one
+SELECT EXISTS (
+    SELECT 1 FROM messages WHERE parts LIKE 'enc:v1:%'
+) OR EXISTS (
+    SELECT 1 FROM blobs WHERE CAST(data AS TEXT) LIKE 'enc:v1:%'
+) AS encrypted
+`
+
+func (q *Queries) HasEncryptedData(ctx context.Context) (int64, error) {
+	row := q.queryRow(ctx, q.hasEncryptedDataStmt, hasEncryptedData)
+	var encrypted int64
+	err := row.Scan(&encrypted)
+	return encrypted, err
+}
+
+const linkMessageBlob = `-- name: LinkMessageBlob :exec
+INSERT OR IGNORE INTO message_blobs (message_id, blob_id)
+VALUES (?, ?)
+`
+
+type LinkMessageBlobParams struct {
+	MessageID string `json:"message_id"`
+	BlobID    string `json:"blob_id"`
+}
+
+func (q *Queries) LinkMessageBlob(ctx context.Context, arg LinkMessageBlobParams) error {
+	_, err := q.exec(ctx, q.linkMessageBlobStmt, linkMessageBlob, arg.MessageID, arg.BlobID)
+	return err
+}
+
+const listMessageBlobs = `-- name: ListMessageBlobs :many
+SELECT blob_id
+FROM message_blobs
+WHERE message_id = ?
+`
+
+func (q *Queries) ListMessageBlobs(ctx context.Context, messageID string) ([]string, error) {
+	rows, err := q.query(ctx, q.listMessageBlobsStmt, listMessageBlobs, messageID)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []string{}
+	for rows.Next() {
+		var blob_id string
+		if err := rows.Scan(&blob_id); err != nil {
+			return nil, err
+		}
+		items = append(items, blob_id)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
@@ -0,0 +1,122 @@
+// Code generated by sqlc. DO NOT EDIT.
+// versions:
+//   sqlc v1.29.0
+// source: checkpoints.sql
+
+package db
+
+import (
+	"context"
+)
+
+const createCheckpoint = `-- name: CreateCheckpoint :exec
+INSERT INTO checkpoints (
+    id,
+    session_id,
+    message_id,
+    created_at
+) VALUES (
+    ?, ?, ?, strftime('%s', 'now')
+)
+`
+
+type CreateCheckpointParams struct {
+	ID        string `json:"id"`
+	SessionID string `json:"session_id"`
+	MessageID string `json:"message_id"`
+}
+
+func (q *Queries) CreateCheckpoint(ctx context.Context, arg CreateCheckpointParams) error {
+	_, err := q.exec(ctx, q.createCheckpointStmt, createCheckpoint, arg.ID, arg.SessionID, arg.MessageID)
+	return err
+}
+
+const linkCheckpointSnapshots = `-- name: LinkCheckpointSnapshots :exec
+INSERT OR IGNORE INTO checkpoint_snapshots (checkpoint_id, snapshot_id)
+SELECT ?, id
+FROM snapshots
+WHERE session_id = ?
+  AND id NOT IN (SELECT snapshot_id FROM checkpoint_snapshots)
+`
+
+type LinkCheckpointSnapshotsParams struct {
+	CheckpointID string `json:"checkpoint_id"`
+	SessionID    string `json:"session_id"`
+}
+
+func (q *Queries) LinkCheckpointSnapshots(ctx context.Context, arg LinkCheckpointSnapshotsParams) error {
+	_, err := q.exec(ctx, q.linkCheckpointSnapshotsStmt, linkCheckpointSnapshots, arg.CheckpointID, arg.SessionID)
+	return err
+}
+
+const listCheckpointFiles = `-- name: ListCheckpointFiles :many
+SELECT cs.checkpoint_id, s.path
+FROM checkpoint_snapshots cs
+JOIN snapshots s ON s.id = cs.snapshot_id
+WHERE s.session_id = ?
+ORDER BY cs.checkpoint_id ASC, s.path ASC
+`
+
+type ListCheckpointFilesRow struct {
+	CheckpointID string `json:"checkpoint_id"`
+	Path         string `json:"path"`
+}
+
+func (q *Queries) ListCheckpointFiles(ctx context.Context, sessionID string) ([]ListCheckpointFilesRow, error) {
+	rows, err := q.query(ctx, q.listCheckpointFilesStmt, listCheckpointFiles, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []ListCheckpointFilesRow{}
+	for rows.Next() {
+		var i ListCheckpointFilesRow
+		if err := rows.Scan(&i.CheckpointID, &i.Path); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+const listCheckpointsBySession = `-- name: ListCheckpointsBySession :many
+SELECT c.id, c.session_id, c.message_id, c.created_at
+FROM checkpoints c
+JOIN messages m ON m.id = c.message_id
+WHERE c.session_id = ? AND m.deleted_at IS NULL
+ORDER BY c.created_at ASC, c.rowid ASC
+`
+
+func (q *Queries) ListCheckpointsBySession(ctx context.Context, sessionID string) ([]Checkpoint, error) {
+	rows, err := q.query(ctx, q.listCheckpointsBySessionStmt, listCheckpointsBySession, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []Checkpoint{}
+	for rows.Next() {
+		var i Checkpoint
+		if err := rows.Scan(
+			&i.ID,
+			&i.SessionID,
+			&i.MessageID,
+			&i.CreatedAt,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
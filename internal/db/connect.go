@@ -1,10 +1,14 @@
 package db
 
 import (
+	"context"
 	"database/sql"
 	"fmt"
+	"net/url"
 	"os"
 	"path/filepath"
+	"strings"
+	"time"
 
 	_ "github.com/ncruces/go-sqlite3/driver"
 	_ "github.com/ncruces/go-sqlite3/embed"
@@ -15,7 +19,49 @@ import (
 	"github.com/pressly/goose/v3"
 )
 
-func Connect() (*sql.DB, error) {
+const (
+	dbName = "cryoncode.db"
+
+	// busyTimeout is how long a connection waits for another one to finish
+	// writing before failing with "database is locked"
+	busyTimeout = 5 * time.Second
+
+	maxOpenConns = 8
+	maxIdleConns = 4
+)
+
+// pragmas are set on every connection of the pool, most of them only apply
+// to the connection they're run on
+var pragmas = []string{
+	"foreign_keys(1)",
+	fmt.Sprintf("busy_timeout(%d)", busyTimeout.Milliseconds()),
+	"journal_mode(wal)",
+	"page_size(4096)",
+	"cache_size(-8000)",
+	"synchronous(normal)",
+}
+
+// dataSourceName returns the URI of the database with the pragmas. Write
+// transactions take the write lock when they begin, so one started while
+// another connection writes waits for it instead of failing when it writes.
+func dataSourceName(path string) string {
+	query := url.Values{"_txlock": {"immediate"}}
+	for _, pragma := range pragmas {
+		query.Add("_pragma", pragma)
+	}
+	if abs, err := filepath.Abs(path); err == nil {
+		path = abs
+	}
+	path = filepath.ToSlash(path)
+	if !strings.HasPrefix(path, "/") {
+		path = "/" + path // C:/ on Windows
+	}
+	return (&url.URL{Scheme: "file", Path: path, RawQuery: query.Encode()}).String()
+}
+
+// Connect opens the database and migrates it. fullCheck runs the full
+// integrity check instead of the quick one, after an unclean shutdown.
+func Connect(fullCheck bool) (*sql.DB, error) {
 	dataDir := config.Get().Data.Directory
 	if dataDir == "" {
 		return nil, fmt.Errorf("data.dir is not set")
@@ -23,34 +69,39 @@ func Connect() (*sql.DB, error) {
 	if err := os.MkdirAll(dataDir, 0o700); err != nil {
 		return nil, fmt.Errorf("failed to create data directory: %w", err)
 	}
-	dbPath := filepath.Join(dataDir, "cryoncode.db")
+	dbPath := filepath.Join(dataDir, dbName)
+	if err := moveLocalDatabase(config.LocalDataDirectory(), dataDir); err != nil {
+		logging.WarnPersist(fmt.Sprintf("Failed to move the database to %s: %v", dataDir, err))
+	}
 	// Open the SQLite database
-	db, err := sql.Open("sqlite3", dbPath)
+	db, err := sql.Open("sqlite3", dataSourceName(dbPath))
 	if err != nil {
 		return nil, fmt.Errorf("failed to open database: %w", err)
 	}
+	db.SetMaxOpenConns(maxOpenConns)
+	db.SetMaxIdleConns(maxIdleConns)
 
 	// Verify connection
 	if err = db.Ping(); err != nil {
 		db.Close()
-		return nil, fmt.Errorf("failed to connect to database: %w", err)
+		return nil, fmt.Errorf("failed to connect to database: %w", damaged(err))
 	}
 
-	// Set pragmas for better performance
-	pragmas := []string{
-		"PRAGMA foreign_keys = ON;",
-		"PRAGMA journal_mode = WAL;",
-		"PRAGMA page_size = 4096;",
-		"PRAGMA cache_size = -8000;",
-		"PRAGMA synchronous = NORMAL;",
+	// Other connections wait on the writer in WAL mode instead of failing,
+	// some file systems don't support it
+	var journalMode string
+	if err := db.QueryRow("PRAGMA journal_mode;").Scan(&journalMode); err != nil {
+		logging.Error("Failed to read the journal mode", "error", err)
+	} else if !strings.EqualFold(journalMode, "wal") {
+		logging.Warn("The database is not in WAL mode, concurrent writes may fail", "journal_mode", journalMode)
 	}
 
-	for _, pragma := range pragmas {
-		if _, err = db.Exec(pragma); err != nil {
-			logging.Error("Failed to set pragma", pragma, err)
-		} else {
-			logging.Debug("Set pragma", "pragma", pragma)
-		}
+	if fullCheck {
+		logging.Info("Running the full integrity check of the database")
+	}
+	if err := checkIntegrity(db, fullCheck); err != nil {
+		db.Close()
+		return nil, err
 	}
 
 	goose.SetBaseFS(FS)
@@ -60,9 +111,79 @@ func Connect() (*sql.DB, error) {
 		return nil, fmt.Errorf("failed to set dialect: %w", err)
 	}
 
+	if err := checkSchemaVersion(db); err != nil {
+		db.Close()
+		return nil, err
+	}
+
 	if err := goose.Up(db, "migrations"); err != nil {
 		logging.Error("Failed to apply migrations", "error", err)
-		return nil, fmt.Errorf("failed to apply migrations: %w", err)
+		db.Close()
+		return nil, fmt.Errorf("failed to apply migrations: %w", damaged(err))
 	}
 	return db, nil
 }
+
+// Vacuum rebuilds the database to give the space of deleted rows back to the
+// file system, and truncates the write-ahead log. Blobs no message uses, left
+// by a message that failed to be saved, are deleted first.
+func Vacuum(ctx context.Context, conn *sql.DB) error {
+	if err := New(conn).DeleteUnusedBlobs(ctx); err != nil {
+		return fmt.Errorf("failed to delete unused blobs: %w", err)
+	}
+	if _, err := conn.ExecContext(ctx, "VACUUM"); err != nil {
+		return fmt.Errorf("failed to vacuum the database: %w", err)
+	}
+	if _, err := conn.ExecContext(ctx, "PRAGMA wal_checkpoint(TRUNCATE)"); err != nil {
+		return fmt.Errorf("failed to checkpoint the database: %w", err)
+	}
+	return nil
+}
+
+// moveLocalDatabase moves the database an older version left in the working
+// directory to the data directory, unless the data directory has one already
+func moveLocalDatabase(localDir, dataDir string) error {
+	if filepath.Clean(localDir) == filepath.Clean(dataDir) {
+		return nil
+	}
+	local := filepath.Join(localDir, dbName)
+	if _, err := os.Stat(local); err != nil {
+		return nil
+	}
+	target := filepath.Join(dataDir, dbName)
+	if _, err := os.Stat(target); err == nil {
+		logging.Warn("Ignoring the database in the working directory", "path", local, "using", target)
+		return nil
+	}
+	// The write-ahead log and shared memory files go with the database
+	for _, suffix := range []string{"", "-wal", "-shm"} {
+		err := os.Rename(local+suffix, target+suffix)
+		if err != nil && !(suffix != "" && os.IsNotExist(err)) {
+			return err
+		}
+	}
+	logging.InfoPersist(fmt.Sprintf("Moved the database from %s to %s", localDir, dataDir))
+	return nil
+}
+
+// checkSchemaVersion refuses a database migrated by a newer version, whose
+// schema the queries of this version may not match
+func checkSchemaVersion(db *sql.DB) error {
+	current, err := goose.GetDBVersion(db)
+	if err != nil {
+		return fmt.Errorf("failed to read the schema version: %w", damaged(err))
+	}
+	migrations, err := goose.CollectMigrations("migrations", 0, goose.MaxVersion)
+	if err != nil {
+		return fmt.Errorf("failed to collect migrations: %w", err)
+	}
+	latest, err := migrations.Last()
+	if err != nil {
+		return fmt.Errorf("failed to collect migrations: %w", err)
+	}
+	if current > latest.Version {
+		return fmt.Errorf("the database schema is at version %d but this version of cryoncode only knows up to %d, update cryoncode to open it", current, latest.Version)
+	}
+	logging.Debug("Database schema version", "version", current, "latest", latest.Version)
+	return nil
+}
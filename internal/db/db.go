@@ -24,21 +24,48 @@ func New(db DBTX) *Queries {
 func Prepare(ctx context.Context, db DBTX) (*Queries, error) {
 	q := Queries{db: db}
 	var err error
+	if q.addSessionTagStmt, err = db.PrepareContext(ctx, addSessionTag); err != nil {
+		return nil, fmt.Errorf("error preparing query AddSessionTag: %w", err)
+	}
+	if q.countMessagesBySessionStmt, err = db.PrepareContext(ctx, countMessagesBySession); err != nil {
+		return nil, fmt.Errorf("error preparing query CountMessagesBySession: %w", err)
+	}
+	if q.createBlobStmt, err = db.PrepareContext(ctx, createBlob); err != nil {
+		return nil, fmt.Errorf("error preparing query CreateBlob: %w", err)
+	}
+	if q.createCheckpointStmt, err = db.PrepareContext(ctx, createCheckpoint); err != nil {
+		return nil, fmt.Errorf("error preparing query CreateCheckpoint: %w", err)
+	}
 	if q.createFileStmt, err = db.PrepareContext(ctx, createFile); err != nil {
 		return nil, fmt.Errorf("error preparing query CreateFile: %w", err)
 	}
 	if q.createMessageStmt, err = db.PrepareContext(ctx, createMessage); err != nil {
 		return nil, fmt.Errorf("error preparing query CreateMessage: %w", err)
 	}
+	if q.createPermissionGrantStmt, err = db.PrepareContext(ctx, createPermissionGrant); err != nil {
+		return nil, fmt.Errorf("error preparing query CreatePermissionGrant: %w", err)
+	}
 	if q.createSessionStmt, err = db.PrepareContext(ctx, createSession); err != nil {
 		return nil, fmt.Errorf("error preparing query CreateSession: %w", err)
 	}
+	if q.createSessionPermissionStmt, err = db.PrepareContext(ctx, createSessionPermission); err != nil {
+		return nil, fmt.Errorf("error preparing query CreateSessionPermission: %w", err)
+	}
+	if q.createSnapshotStmt, err = db.PrepareContext(ctx, createSnapshot); err != nil {
+		return nil, fmt.Errorf("error preparing query CreateSnapshot: %w", err)
+	}
+	if q.deleteChildSessionsStmt, err = db.PrepareContext(ctx, deleteChildSessions); err != nil {
+		return nil, fmt.Errorf("error preparing query DeleteChildSessions: %w", err)
+	}
 	if q.deleteFileStmt, err = db.PrepareContext(ctx, deleteFile); err != nil {
 		return nil, fmt.Errorf("error preparing query DeleteFile: %w", err)
 	}
 	if q.deleteMessageStmt, err = db.PrepareContext(ctx, deleteMessage); err != nil {
 		return nil, fmt.Errorf("error preparing query DeleteMessage: %w", err)
 	}
+	if q.deletePermissionGrantStmt, err = db.PrepareContext(ctx, deletePermissionGrant); err != nil {
+		return nil, fmt.Errorf("error preparing query DeletePermissionGrant: %w", err)
+	}
 	if q.deleteSessionStmt, err = db.PrepareContext(ctx, deleteSession); err != nil {
 		return nil, fmt.Errorf("error preparing query DeleteSession: %w", err)
 	}
@@ -48,6 +75,18 @@ func Prepare(ctx context.Context, db DBTX) (*Queries, error) {
 	if q.deleteSessionMessagesStmt, err = db.PrepareContext(ctx, deleteSessionMessages); err != nil {
 		return nil, fmt.Errorf("error preparing query DeleteSessionMessages: %w", err)
 	}
+	if q.deleteSessionTagStmt, err = db.PrepareContext(ctx, deleteSessionTag); err != nil {
+		return nil, fmt.Errorf("error preparing query DeleteSessionTag: %w", err)
+	}
+	if q.deleteSnapshotStmt, err = db.PrepareContext(ctx, deleteSnapshot); err != nil {
+		return nil, fmt.Errorf("error preparing query DeleteSnapshot: %w", err)
+	}
+	if q.deleteUnusedBlobsStmt, err = db.PrepareContext(ctx, deleteUnusedBlobs); err != nil {
+		return nil, fmt.Errorf("error preparing query DeleteUnusedBlobs: %w", err)
+	}
+	if q.getBlobStmt, err = db.PrepareContext(ctx, getBlob); err != nil {
+		return nil, fmt.Errorf("error preparing query GetBlob: %w", err)
+	}
 	if q.getFileStmt, err = db.PrepareContext(ctx, getFile); err != nil {
 		return nil, fmt.Errorf("error preparing query GetFile: %w", err)
 	}
@@ -60,6 +99,24 @@ func Prepare(ctx context.Context, db DBTX) (*Queries, error) {
 	if q.getSessionByIDStmt, err = db.PrepareContext(ctx, getSessionByID); err != nil {
 		return nil, fmt.Errorf("error preparing query GetSessionByID: %w", err)
 	}
+	if q.hasEncryptedDataStmt, err = db.PrepareContext(ctx, hasEncryptedData); err != nil {
+		return nil, fmt.Errorf("error preparing query HasEncryptedData: %w", err)
+	}
+	if q.importMessageStmt, err = db.PrepareContext(ctx, importMessage); err != nil {
+		return nil, fmt.Errorf("error preparing query ImportMessage: %w", err)
+	}
+	if q.linkCheckpointSnapshotsStmt, err = db.PrepareContext(ctx, linkCheckpointSnapshots); err != nil {
+		return nil, fmt.Errorf("error preparing query LinkCheckpointSnapshots: %w", err)
+	}
+	if q.linkMessageBlobStmt, err = db.PrepareContext(ctx, linkMessageBlob); err != nil {
+		return nil, fmt.Errorf("error preparing query LinkMessageBlob: %w", err)
+	}
+	if q.listCheckpointFilesStmt, err = db.PrepareContext(ctx, listCheckpointFiles); err != nil {
+		return nil, fmt.Errorf("error preparing query ListCheckpointFiles: %w", err)
+	}
+	if q.listCheckpointsBySessionStmt, err = db.PrepareContext(ctx, listCheckpointsBySession); err != nil {
+		return nil, fmt.Errorf("error preparing query ListCheckpointsBySession: %w", err)
+	}
 	if q.listFilesByPathStmt, err = db.PrepareContext(ctx, listFilesByPath); err != nil {
 		return nil, fmt.Errorf("error preparing query ListFilesByPath: %w", err)
 	}
@@ -69,29 +126,94 @@ func Prepare(ctx context.Context, db DBTX) (*Queries, error) {
 	if q.listLatestSessionFilesStmt, err = db.PrepareContext(ctx, listLatestSessionFiles); err != nil {
 		return nil, fmt.Errorf("error preparing query ListLatestSessionFiles: %w", err)
 	}
+	if q.listMessageBlobsStmt, err = db.PrepareContext(ctx, listMessageBlobs); err != nil {
+		return nil, fmt.Errorf("error preparing query ListMessageBlobs: %w", err)
+	}
 	if q.listMessagesBySessionStmt, err = db.PrepareContext(ctx, listMessagesBySession); err != nil {
 		return nil, fmt.Errorf("error preparing query ListMessagesBySession: %w", err)
 	}
+	if q.listMessagesBySessionPageStmt, err = db.PrepareContext(ctx, listMessagesBySessionPage); err != nil {
+		return nil, fmt.Errorf("error preparing query ListMessagesBySessionPage: %w", err)
+	}
 	if q.listNewFilesStmt, err = db.PrepareContext(ctx, listNewFiles); err != nil {
 		return nil, fmt.Errorf("error preparing query ListNewFiles: %w", err)
 	}
+	if q.listPermissionGrantsStmt, err = db.PrepareContext(ctx, listPermissionGrants); err != nil {
+		return nil, fmt.Errorf("error preparing query ListPermissionGrants: %w", err)
+	}
+	if q.listSessionPermissionsStmt, err = db.PrepareContext(ctx, listSessionPermissions); err != nil {
+		return nil, fmt.Errorf("error preparing query ListSessionPermissions: %w", err)
+	}
+	if q.listSessionTagsStmt, err = db.PrepareContext(ctx, listSessionTags); err != nil {
+		return nil, fmt.Errorf("error preparing query ListSessionTags: %w", err)
+	}
 	if q.listSessionsStmt, err = db.PrepareContext(ctx, listSessions); err != nil {
 		return nil, fmt.Errorf("error preparing query ListSessions: %w", err)
 	}
+	if q.listSnapshotsBySessionStmt, err = db.PrepareContext(ctx, listSnapshotsBySession); err != nil {
+		return nil, fmt.Errorf("error preparing query ListSnapshotsBySession: %w", err)
+	}
+	if q.listTagsBySessionStmt, err = db.PrepareContext(ctx, listTagsBySession); err != nil {
+		return nil, fmt.Errorf("error preparing query ListTagsBySession: %w", err)
+	}
+	if q.searchMessagesStmt, err = db.PrepareContext(ctx, searchMessages); err != nil {
+		return nil, fmt.Errorf("error preparing query SearchMessages: %w", err)
+	}
+	if q.searchSessionsStmt, err = db.PrepareContext(ctx, searchSessions); err != nil {
+		return nil, fmt.Errorf("error preparing query SearchSessions: %w", err)
+	}
+	if q.softDeleteMessageStmt, err = db.PrepareContext(ctx, softDeleteMessage); err != nil {
+		return nil, fmt.Errorf("error preparing query SoftDeleteMessage: %w", err)
+	}
 	if q.updateFileStmt, err = db.PrepareContext(ctx, updateFile); err != nil {
 		return nil, fmt.Errorf("error preparing query UpdateFile: %w", err)
 	}
 	if q.updateMessageStmt, err = db.PrepareContext(ctx, updateMessage); err != nil {
 		return nil, fmt.Errorf("error preparing query UpdateMessage: %w", err)
 	}
+	if q.updatePermissionGrantStmt, err = db.PrepareContext(ctx, updatePermissionGrant); err != nil {
+		return nil, fmt.Errorf("error preparing query UpdatePermissionGrant: %w", err)
+	}
 	if q.updateSessionStmt, err = db.PrepareContext(ctx, updateSession); err != nil {
 		return nil, fmt.Errorf("error preparing query UpdateSession: %w", err)
 	}
+	if q.updateSessionArchivedStmt, err = db.PrepareContext(ctx, updateSessionArchived); err != nil {
+		return nil, fmt.Errorf("error preparing query UpdateSessionArchived: %w", err)
+	}
+	if q.updateSessionPinnedStmt, err = db.PrepareContext(ctx, updateSessionPinned); err != nil {
+		return nil, fmt.Errorf("error preparing query UpdateSessionPinned: %w", err)
+	}
+	if q.updateSessionSettingsStmt, err = db.PrepareContext(ctx, updateSessionSettings); err != nil {
+		return nil, fmt.Errorf("error preparing query UpdateSessionSettings: %w", err)
+	}
+	if q.updateSessionTodosStmt, err = db.PrepareContext(ctx, updateSessionTodos); err != nil {
+		return nil, fmt.Errorf("error preparing query UpdateSessionTodos: %w", err)
+	}
 	return &q, nil
 }
 
 func (q *Queries) Close() error {
 	var err error
+	if q.addSessionTagStmt != nil {
+		if cerr := q.addSessionTagStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing addSessionTagStmt: %w", cerr)
+		}
+	}
+	if q.countMessagesBySessionStmt != nil {
+		if cerr := q.countMessagesBySessionStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing countMessagesBySessionStmt: %w", cerr)
+		}
+	}
+	if q.createBlobStmt != nil {
+		if cerr := q.createBlobStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing createBlobStmt: %w", cerr)
+		}
+	}
+	if q.createCheckpointStmt != nil {
+		if cerr := q.createCheckpointStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing createCheckpointStmt: %w", cerr)
+		}
+	}
 	if q.createFileStmt != nil {
 		if cerr := q.createFileStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing createFileStmt: %w", cerr)
@@ -102,11 +224,31 @@ func (q *Queries) Close() error {
 			err = fmt.Errorf("error closing createMessageStmt: %w", cerr)
 		}
 	}
+	if q.createPermissionGrantStmt != nil {
+		if cerr := q.createPermissionGrantStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing createPermissionGrantStmt: %w", cerr)
+		}
+	}
 	if q.createSessionStmt != nil {
 		if cerr := q.createSessionStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing createSessionStmt: %w", cerr)
 		}
 	}
+	if q.createSessionPermissionStmt != nil {
+		if cerr := q.createSessionPermissionStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing createSessionPermissionStmt: %w", cerr)
+		}
+	}
+	if q.createSnapshotStmt != nil {
+		if cerr := q.createSnapshotStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing createSnapshotStmt: %w", cerr)
+		}
+	}
+	if q.deleteChildSessionsStmt != nil {
+		if cerr := q.deleteChildSessionsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing deleteChildSessionsStmt: %w", cerr)
+		}
+	}
 	if q.deleteFileStmt != nil {
 		if cerr := q.deleteFileStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing deleteFileStmt: %w", cerr)
@@ -117,6 +259,11 @@ func (q *Queries) Close() error {
 			err = fmt.Errorf("error closing deleteMessageStmt: %w", cerr)
 		}
 	}
+	if q.deletePermissionGrantStmt != nil {
+		if cerr := q.deletePermissionGrantStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing deletePermissionGrantStmt: %w", cerr)
+		}
+	}
 	if q.deleteSessionStmt != nil {
 		if cerr := q.deleteSessionStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing deleteSessionStmt: %w", cerr)
@@ -132,6 +279,26 @@ func (q *Queries) Close() error {
 			err = fmt.Errorf("error closing deleteSessionMessagesStmt: %w", cerr)
 		}
 	}
+	if q.deleteSessionTagStmt != nil {
+		if cerr := q.deleteSessionTagStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing deleteSessionTagStmt: %w", cerr)
+		}
+	}
+	if q.deleteSnapshotStmt != nil {
+		if cerr := q.deleteSnapshotStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing deleteSnapshotStmt: %w", cerr)
+		}
+	}
+	if q.deleteUnusedBlobsStmt != nil {
+		if cerr := q.deleteUnusedBlobsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing deleteUnusedBlobsStmt: %w", cerr)
+		}
+	}
+	if q.getBlobStmt != nil {
+		if cerr := q.getBlobStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing getBlobStmt: %w", cerr)
+		}
+	}
 	if q.getFileStmt != nil {
 		if cerr := q.getFileStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing getFileStmt: %w", cerr)
@@ -152,6 +319,36 @@ func (q *Queries) Close() error {
 			err = fmt.Errorf("error closing getSessionByIDStmt: %w", cerr)
 		}
 	}
+	if q.hasEncryptedDataStmt != nil {
+		if cerr := q.hasEncryptedDataStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing hasEncryptedDataStmt: %w", cerr)
+		}
+	}
+	if q.importMessageStmt != nil {
+		if cerr := q.importMessageStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing importMessageStmt: %w", cerr)
+		}
+	}
+	if q.linkCheckpointSnapshotsStmt != nil {
+		if cerr := q.linkCheckpointSnapshotsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing linkCheckpointSnapshotsStmt: %w", cerr)
+		}
+	}
+	if q.linkMessageBlobStmt != nil {
+		if cerr := q.linkMessageBlobStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing linkMessageBlobStmt: %w", cerr)
+		}
+	}
+	if q.listCheckpointFilesStmt != nil {
+		if cerr := q.listCheckpointFilesStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listCheckpointFilesStmt: %w", cerr)
+		}
+	}
+	if q.listCheckpointsBySessionStmt != nil {
+		if cerr := q.listCheckpointsBySessionStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listCheckpointsBySessionStmt: %w", cerr)
+		}
+	}
 	if q.listFilesByPathStmt != nil {
 		if cerr := q.listFilesByPathStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing listFilesByPathStmt: %w", cerr)
@@ -167,21 +364,71 @@ func (q *Queries) Close() error {
 			err = fmt.Errorf("error closing listLatestSessionFilesStmt: %w", cerr)
 		}
 	}
+	if q.listMessageBlobsStmt != nil {
+		if cerr := q.listMessageBlobsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listMessageBlobsStmt: %w", cerr)
+		}
+	}
 	if q.listMessagesBySessionStmt != nil {
 		if cerr := q.listMessagesBySessionStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing listMessagesBySessionStmt: %w", cerr)
 		}
 	}
+	if q.listMessagesBySessionPageStmt != nil {
+		if cerr := q.listMessagesBySessionPageStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listMessagesBySessionPageStmt: %w", cerr)
+		}
+	}
 	if q.listNewFilesStmt != nil {
 		if cerr := q.listNewFilesStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing listNewFilesStmt: %w", cerr)
 		}
 	}
+	if q.listPermissionGrantsStmt != nil {
+		if cerr := q.listPermissionGrantsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listPermissionGrantsStmt: %w", cerr)
+		}
+	}
+	if q.listSessionPermissionsStmt != nil {
+		if cerr := q.listSessionPermissionsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listSessionPermissionsStmt: %w", cerr)
+		}
+	}
+	if q.listSessionTagsStmt != nil {
+		if cerr := q.listSessionTagsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listSessionTagsStmt: %w", cerr)
+		}
+	}
 	if q.listSessionsStmt != nil {
 		if cerr := q.listSessionsStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing listSessionsStmt: %w", cerr)
 		}
 	}
+	if q.listSnapshotsBySessionStmt != nil {
+		if cerr := q.listSnapshotsBySessionStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listSnapshotsBySessionStmt: %w", cerr)
+		}
+	}
+	if q.listTagsBySessionStmt != nil {
+		if cerr := q.listTagsBySessionStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing listTagsBySessionStmt: %w", cerr)
+		}
+	}
+	if q.searchMessagesStmt != nil {
+		if cerr := q.searchMessagesStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing searchMessagesStmt: %w", cerr)
+		}
+	}
+	if q.searchSessionsStmt != nil {
+		if cerr := q.searchSessionsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing searchSessionsStmt: %w", cerr)
+		}
+	}
+	if q.softDeleteMessageStmt != nil {
+		if cerr := q.softDeleteMessageStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing softDeleteMessageStmt: %w", cerr)
+		}
+	}
 	if q.updateFileStmt != nil {
 		if cerr := q.updateFileStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing updateFileStmt: %w", cerr)
@@ -192,11 +439,36 @@ func (q *Queries) Close() error {
 			err = fmt.Errorf("error closing updateMessageStmt: %w", cerr)
 		}
 	}
+	if q.updatePermissionGrantStmt != nil {
+		if cerr := q.updatePermissionGrantStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing updatePermissionGrantStmt: %w", cerr)
+		}
+	}
 	if q.updateSessionStmt != nil {
 		if cerr := q.updateSessionStmt.Close(); cerr != nil {
 			err = fmt.Errorf("error closing updateSessionStmt: %w", cerr)
 		}
 	}
+	if q.updateSessionArchivedStmt != nil {
+		if cerr := q.updateSessionArchivedStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing updateSessionArchivedStmt: %w", cerr)
+		}
+	}
+	if q.updateSessionPinnedStmt != nil {
+		if cerr := q.updateSessionPinnedStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing updateSessionPinnedStmt: %w", cerr)
+		}
+	}
+	if q.updateSessionSettingsStmt != nil {
+		if cerr := q.updateSessionSettingsStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing updateSessionSettingsStmt: %w", cerr)
+		}
+	}
+	if q.updateSessionTodosStmt != nil {
+		if cerr := q.updateSessionTodosStmt.Close(); cerr != nil {
+			err = fmt.Errorf("error closing updateSessionTodosStmt: %w", cerr)
+		}
+	}
 	return err
 }
 
@@ -234,55 +506,123 @@ func (q *Queries) queryRow(ctx context.Context, stmt *sql.Stmt, query string, ar
 }
 
 type Queries struct {
-	db                          DBTX
-	tx                          *sql.Tx
-	createFileStmt              *sql.Stmt
-	createMessageStmt           *sql.Stmt
-	createSessionStmt           *sql.Stmt
-	deleteFileStmt              *sql.Stmt
-	deleteMessageStmt           *sql.Stmt
-	deleteSessionStmt           *sql.Stmt
-	deleteSessionFilesStmt      *sql.Stmt
-	deleteSessionMessagesStmt   *sql.Stmt
-	getFileStmt                 *sql.Stmt
-	getFileByPathAndSessionStmt *sql.Stmt
-	getMessageStmt              *sql.Stmt
-	getSessionByIDStmt          *sql.Stmt
-	listFilesByPathStmt         *sql.Stmt
-	listFilesBySessionStmt      *sql.Stmt
-	listLatestSessionFilesStmt  *sql.Stmt
-	listMessagesBySessionStmt   *sql.Stmt
-	listNewFilesStmt            *sql.Stmt
-	listSessionsStmt            *sql.Stmt
-	updateFileStmt              *sql.Stmt
-	updateMessageStmt           *sql.Stmt
-	updateSessionStmt           *sql.Stmt
+	db                            DBTX
+	tx                            *sql.Tx
+	addSessionTagStmt             *sql.Stmt
+	countMessagesBySessionStmt    *sql.Stmt
+	createBlobStmt                *sql.Stmt
+	createCheckpointStmt          *sql.Stmt
+	createFileStmt                *sql.Stmt
+	createMessageStmt             *sql.Stmt
+	createPermissionGrantStmt     *sql.Stmt
+	createSessionStmt             *sql.Stmt
+	createSessionPermissionStmt   *sql.Stmt
+	createSnapshotStmt            *sql.Stmt
+	deleteChildSessionsStmt       *sql.Stmt
+	deleteFileStmt                *sql.Stmt
+	deleteMessageStmt             *sql.Stmt
+	deletePermissionGrantStmt     *sql.Stmt
+	deleteSessionStmt             *sql.Stmt
+	deleteSessionFilesStmt        *sql.Stmt
+	deleteSessionMessagesStmt     *sql.Stmt
+	deleteSessionTagStmt          *sql.Stmt
+	deleteSnapshotStmt            *sql.Stmt
+	deleteUnusedBlobsStmt         *sql.Stmt
+	getBlobStmt                   *sql.Stmt
+	getFileStmt                   *sql.Stmt
+	getFileByPathAndSessionStmt   *sql.Stmt
+	getMessageStmt                *sql.Stmt
+	getSessionByIDStmt            *sql.Stmt
+	hasEncryptedDataStmt          *sql.Stmt
+	importMessageStmt             *sql.Stmt
+	linkCheckpointSnapshotsStmt   *sql.Stmt
+	linkMessageBlobStmt           *sql.Stmt
+	listCheckpointFilesStmt       *sql.Stmt
+	listCheckpointsBySessionStmt  *sql.Stmt
+	listFilesByPathStmt           *sql.Stmt
+	listFilesBySessionStmt        *sql.Stmt
+	listLatestSessionFilesStmt    *sql.Stmt
+	listMessageBlobsStmt          *sql.Stmt
+	listMessagesBySessionStmt     *sql.Stmt
+	listMessagesBySessionPageStmt *sql.Stmt
+	listNewFilesStmt              *sql.Stmt
+	listPermissionGrantsStmt      *sql.Stmt
+	listSessionPermissionsStmt    *sql.Stmt
+	listSessionTagsStmt           *sql.Stmt
+	listSessionsStmt              *sql.Stmt
+	listSnapshotsBySessionStmt    *sql.Stmt
+	listTagsBySessionStmt         *sql.Stmt
+	searchMessagesStmt            *sql.Stmt
+	searchSessionsStmt            *sql.Stmt
+	softDeleteMessageStmt         *sql.Stmt
+	updateFileStmt                *sql.Stmt
+	updateMessageStmt             *sql.Stmt
+	updatePermissionGrantStmt     *sql.Stmt
+	updateSessionStmt             *sql.Stmt
+	updateSessionArchivedStmt     *sql.Stmt
+	updateSessionPinnedStmt       *sql.Stmt
+	updateSessionSettingsStmt     *sql.Stmt
+	updateSessionTodosStmt        *sql.Stmt
 }
 
 func (q *Queries) WithTx(tx *sql.Tx) *Queries {
 	return &Queries{
-		db:                          tx,
-		tx:                          tx,
-		createFileStmt:              q.createFileStmt,
-		createMessageStmt:           q.createMessageStmt,
-		createSessionStmt:           q.createSessionStmt,
-		deleteFileStmt:              q.deleteFileStmt,
-		deleteMessageStmt:           q.deleteMessageStmt,
-		deleteSessionStmt:           q.deleteSessionStmt,
-		deleteSessionFilesStmt:      q.deleteSessionFilesStmt,
-		deleteSessionMessagesStmt:   q.deleteSessionMessagesStmt,
-		getFileStmt:                 q.getFileStmt,
-		getFileByPathAndSessionStmt: q.getFileByPathAndSessionStmt,
-		getMessageStmt:              q.getMessageStmt,
-		getSessionByIDStmt:          q.getSessionByIDStmt,
-		listFilesByPathStmt:         q.listFilesByPathStmt,
-		listFilesBySessionStmt:      q.listFilesBySessionStmt,
-		listLatestSessionFilesStmt:  q.listLatestSessionFilesStmt,
-		listMessagesBySessionStmt:   q.listMessagesBySessionStmt,
-		listNewFilesStmt:            q.listNewFilesStmt,
-		listSessionsStmt:            q.listSessionsStmt,
-		updateFileStmt:              q.updateFileStmt,
-		updateMessageStmt:           q.updateMessageStmt,
-		updateSessionStmt:           q.updateSessionStmt,
+		db:                            tx,
+		tx:                            tx,
+		addSessionTagStmt:             q.addSessionTagStmt,
+		countMessagesBySessionStmt:    q.countMessagesBySessionStmt,
+		createBlobStmt:                q.createBlobStmt,
+		createCheckpointStmt:          q.createCheckpointStmt,
+		createFileStmt:                q.createFileStmt,
+		createMessageStmt:             q.createMessageStmt,
+		createPermissionGrantStmt:     q.createPermissionGrantStmt,
+		createSessionStmt:             q.createSessionStmt,
+		createSessionPermissionStmt:   q.createSessionPermissionStmt,
+		createSnapshotStmt:            q.createSnapshotStmt,
+		deleteChildSessionsStmt:       q.deleteChildSessionsStmt,
+		deleteFileStmt:                q.deleteFileStmt,
+		deleteMessageStmt:             q.deleteMessageStmt,
+		deletePermissionGrantStmt:     q.deletePermissionGrantStmt,
+		deleteSessionStmt:             q.deleteSessionStmt,
+		deleteSessionFilesStmt:        q.deleteSessionFilesStmt,
+		deleteSessionMessagesStmt:     q.deleteSessionMessagesStmt,
+		deleteSessionTagStmt:          q.deleteSessionTagStmt,
+		deleteSnapshotStmt:            q.deleteSnapshotStmt,
+		deleteUnusedBlobsStmt:         q.deleteUnusedBlobsStmt,
+		getBlobStmt:                   q.getBlobStmt,
+		getFileStmt:                   q.getFileStmt,
+		getFileByPathAndSessionStmt:   q.getFileByPathAndSessionStmt,
+		getMessageStmt:                q.getMessageStmt,
+		getSessionByIDStmt:            q.getSessionByIDStmt,
+		hasEncryptedDataStmt:          q.hasEncryptedDataStmt,
+		importMessageStmt:             q.importMessageStmt,
+		linkCheckpointSnapshotsStmt:   q.linkCheckpointSnapshotsStmt,
+		linkMessageBlobStmt:           q.linkMessageBlobStmt,
+		listCheckpointFilesStmt:       q.listCheckpointFilesStmt,
+		listCheckpointsBySessionStmt:  q.listCheckpointsBySessionStmt,
+		listFilesByPathStmt:           q.listFilesByPathStmt,
+		listFilesBySessionStmt:        q.listFilesBySessionStmt,
+		listLatestSessionFilesStmt:    q.listLatestSessionFilesStmt,
+		listMessageBlobsStmt:          q.listMessageBlobsStmt,
+		listMessagesBySessionStmt:     q.listMessagesBySessionStmt,
+		listMessagesBySessionPageStmt: q.listMessagesBySessionPageStmt,
+		listNewFilesStmt:              q.listNewFilesStmt,
+		listPermissionGrantsStmt:      q.listPermissionGrantsStmt,
+		listSessionPermissionsStmt:    q.listSessionPermissionsStmt,
+		listSessionTagsStmt:           q.listSessionTagsStmt,
+		listSessionsStmt:              q.listSessionsStmt,
+		listSnapshotsBySessionStmt:    q.listSnapshotsBySessionStmt,
+		listTagsBySessionStmt:         q.listTagsBySessionStmt,
+		searchMessagesStmt:            q.searchMessagesStmt,
+		searchSessionsStmt:            q.searchSessionsStmt,
+		softDeleteMessageStmt:         q.softDeleteMessageStmt,
+		updateFileStmt:                q.updateFileStmt,
+		updateMessageStmt:             q.updateMessageStmt,
+		updatePermissionGrantStmt:     q.updatePermissionGrantStmt,
+		updateSessionStmt:             q.updateSessionStmt,
+		updateSessionArchivedStmt:     q.updateSessionArchivedStmt,
+		updateSessionPinnedStmt:       q.updateSessionPinnedStmt,
+		updateSessionSettingsStmt:     q.updateSessionSettingsStmt,
+		updateSessionTodosStmt:        q.updateSessionTodosStmt,
 	}
 }
@@ -0,0 +1,81 @@
+package db
+
+import (
+	"database/sql"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/ncruces/go-sqlite3"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+// ErrDamaged is returned by Connect when the database file is corrupt or
+// isn't a SQLite database
+var ErrDamaged = errors.New("the database is damaged")
+
+// maxIntegrityProblems is the number of problems the integrity check reports
+const maxIntegrityProblems = 5
+
+// damaged marks the errors SQLite returns for a damaged file with ErrDamaged
+func damaged(err error) error {
+	if errors.Is(err, sqlite3.CORRUPT) || errors.Is(err, sqlite3.NOTADB) {
+		return fmt.Errorf("%w: %w", ErrDamaged, err)
+	}
+	return err
+}
+
+// checkIntegrity runs SQLite's quick check, which finds damaged pages and
+// records in about the time it takes to read the file. The full check also
+// compares the indexes with their tables, which slows down starts on large
+// databases, so it only runs when asked for.
+func checkIntegrity(db *sql.DB, full bool) error {
+	check := "quick_check"
+	if full {
+		check = "integrity_check"
+	}
+	rows, err := db.Query(fmt.Sprintf("PRAGMA %s(%d)", check, maxIntegrityProblems))
+	if err != nil {
+		return damaged(err)
+	}
+	defer rows.Close()
+	var problems []string
+	for rows.Next() {
+		var problem string
+		if err := rows.Scan(&problem); err != nil {
+			return damaged(err)
+		}
+		if problem != "ok" {
+			problems = append(problems, problem)
+		}
+	}
+	if err := rows.Err(); err != nil {
+		return damaged(err)
+	}
+	if len(problems) > 0 {
+		return fmt.Errorf("%w: %s", ErrDamaged, strings.Join(problems, "; "))
+	}
+	return nil
+}
+
+// MoveDamaged moves the database aside, to be inspected or recovered, so the
+// next Connect creates a new one. It returns the path it was moved to.
+func MoveDamaged() (string, error) {
+	return moveDamaged(filepath.Join(config.Get().Data.Directory, dbName), time.Now())
+}
+
+func moveDamaged(dbPath string, now time.Time) (string, error) {
+	backup := fmt.Sprintf("%s.damaged-%s", dbPath, now.Format("20060102-150405"))
+	// The write-ahead log may hold the last transactions, it goes with the
+	// database
+	for _, suffix := range []string{"", "-wal", "-shm"} {
+		err := os.Rename(dbPath+suffix, backup+suffix)
+		if err != nil && !(suffix != "" && os.IsNotExist(err)) {
+			return "", fmt.Errorf("failed to move the damaged database: %w", err)
+		}
+	}
+	return backup, nil
+}
@@ -0,0 +1,44 @@
+package db
+
+import (
+	"database/sql"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestMoveDamaged(t *testing.T) {
+	dir := t.TempDir()
+	path := filepath.Join(dir, "cryoncode.db")
+	require.NoError(t, os.WriteFile(path, []byte("db"), 0o600))
+	require.NoError(t, os.WriteFile(path+"-wal", []byte("wal"), 0o600))
+	require.NoError(t, os.WriteFile(path+"-shm", []byte("shm"), 0o600))
+
+	backup, err := moveDamaged(path, time.Date(2025, 7, 6, 9, 30, 0, 0, time.UTC))
+	require.NoError(t, err)
+	assert.Equal(t, path+".damaged-20250706-093000", backup)
+	assert.NoFileExists(t, path)
+	assert.NoFileExists(t, path+"-wal")
+	assert.NoFileExists(t, path+"-shm")
+	assert.FileExists(t, backup)
+	assert.FileExists(t, backup+"-wal")
+	assert.FileExists(t, backup+"-shm")
+
+	_, err = moveDamaged(path, time.Now())
+	assert.Error(t, err)
+}
+
+func TestCheckIntegrity(t *testing.T) {
+	conn, err := sql.Open("sqlite3", dataSourceName(filepath.Join(t.TempDir(), dbName)))
+	require.NoError(t, err)
+	defer conn.Close()
+	_, err = conn.Exec("CREATE TABLE t (id INTEGER PRIMARY KEY, name TEXT); CREATE INDEX t_name ON t (name); INSERT INTO t (name) VALUES ('a'), ('b')")
+	require.NoError(t, err)
+
+	assert.NoError(t, checkIntegrity(conn, false))
+	assert.NoError(t, checkIntegrity(conn, true))
+}
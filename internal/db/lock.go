@@ -0,0 +1,81 @@
+package db
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+// ErrInUse is returned by LockInstance when another instance holds the lock
+// of the database
+var ErrInUse = errors.New("the database is in use by another cryoncode instance")
+
+// InstanceLock is held by the instance that has the TUI open on a database.
+// SQLite keeps the writes of several processes apart, but two instances
+// working on the same sessions would still undo each other's changes.
+type InstanceLock struct {
+	file    *os.File
+	unclean bool
+}
+
+// LockInstance takes the lock of the database, next to it in a .lock file. It
+// fails with ErrInUse, without waiting, when another instance holds it.
+func LockInstance() (*InstanceLock, error) {
+	dataDir := config.Get().Data.Directory
+	if dataDir == "" {
+		return nil, fmt.Errorf("data.dir is not set")
+	}
+	if err := os.MkdirAll(dataDir, 0o700); err != nil {
+		return nil, fmt.Errorf("failed to create data directory: %w", err)
+	}
+	return lockPath(filepath.Join(dataDir, dbName) + ".lock")
+}
+
+func lockPath(path string) (*InstanceLock, error) {
+	file, err := os.OpenFile(path, os.O_RDWR|os.O_CREATE, 0o600)
+	if err != nil {
+		return nil, fmt.Errorf("failed to open the lock file: %w", err)
+	}
+	locked, err := tryLock(file)
+	if err != nil {
+		file.Close()
+		return nil, fmt.Errorf("failed to lock %s: %w", path, err)
+	}
+	if !locked {
+		defer file.Close()
+		// The holder writes its process ID in the file
+		if pid, err := io.ReadAll(file); err == nil && len(strings.TrimSpace(string(pid))) > 0 {
+			return nil, fmt.Errorf("%w (pid %s), close it first or set data.directory to another directory", ErrInUse, strings.TrimSpace(string(pid)))
+		}
+		return nil, fmt.Errorf("%w, close it first or set data.directory to another directory", ErrInUse)
+	}
+
+	// Release empties the file, a process ID left in it is the one of an
+	// instance that was killed or crashed
+	pid, err := io.ReadAll(file)
+	unclean := err == nil && len(strings.TrimSpace(string(pid))) > 0
+
+	// The process ID is also shown to the other instances
+	if err := file.Truncate(0); err == nil {
+		file.WriteAt([]byte(strconv.Itoa(os.Getpid())), 0)
+	}
+	return &InstanceLock{file: file, unclean: unclean}, nil
+}
+
+// Unclean reports whether the instance that held the lock before exited
+// without releasing it, with the database possibly left half written
+func (l *InstanceLock) Unclean() bool {
+	return l.unclean
+}
+
+// Release lets another instance take the lock
+func (l *InstanceLock) Release() error {
+	l.file.Truncate(0)
+	return l.file.Close()
+}
@@ -0,0 +1,36 @@
+package db
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestLockPath(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "cryoncode.db.lock")
+
+	lock, err := lockPath(path)
+	require.NoError(t, err)
+	assert.False(t, lock.Unclean())
+
+	_, err = lockPath(path)
+	require.ErrorIs(t, err, ErrInUse)
+	assert.Contains(t, err.Error(), fmt.Sprintf("pid %d", os.Getpid()))
+
+	require.NoError(t, lock.Release())
+	lock, err = lockPath(path)
+	require.NoError(t, err)
+	assert.False(t, lock.Unclean())
+	require.NoError(t, lock.Release())
+
+	// An instance that crashed leaves its process ID in the file
+	require.NoError(t, os.WriteFile(path, []byte("12345"), 0o600))
+	lock, err = lockPath(path)
+	require.NoError(t, err)
+	assert.True(t, lock.Unclean())
+	require.NoError(t, lock.Release())
+}
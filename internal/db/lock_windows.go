@@ -0,0 +1,30 @@
+//go:build windows
+
+package db
+
+import (
+	"errors"
+	"os"
+
+	"golang.org/x/sys/windows"
+)
+
+// tryLock takes an exclusive lock on the file, it returns false when another
+// process holds it. The lock goes away when the file is closed.
+func tryLock(file *os.File) (bool, error) {
+	// Lock a byte past the process ID so the other instances can still read
+	// it
+	overlapped := windows.Overlapped{OffsetHigh: 1}
+	err := windows.LockFileEx(
+		windows.Handle(file.Fd()),
+		windows.LOCKFILE_EXCLUSIVE_LOCK|windows.LOCKFILE_FAIL_IMMEDIATELY,
+		0,
+		1,
+		0,
+		&overlapped,
+	)
+	if errors.Is(err, windows.ERROR_LOCK_VIOLATION) {
+		return false, nil
+	}
+	return err == nil, err
+}
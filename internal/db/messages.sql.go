@@ -10,6 +10,19 @@ import (
 	"database/sql"
 )
 
+const countMessagesBySession = `-- name: CountMessagesBySession :one
+SELECT COUNT(*)
+FROM messages
+WHERE session_id = ? AND deleted_at IS NULL
+`
+
+func (q *Queries) CountMessagesBySession(ctx context.Context, sessionID string) (int64, error) {
+	row := q.queryRow(ctx, q.countMessagesBySessionStmt, countMessagesBySession, sessionID)
+	var count int64
+	err := row.Scan(&count)
+	return count, err
+}
+
 const createMessage = `-- name: CreateMessage :one
 INSERT INTO messages (
     id,
@@ -22,7 +35,7 @@ INSERT INTO messages (
 ) VALUES (
     ?, ?, ?, ?, ?, strftime('%s', 'now'), strftime('%s', 'now')
 )
-RETURNING id, session_id, role, parts, model, created_at, updated_at, finished_at
+RETURNING id, session_id, role, parts, model, created_at, updated_at, finished_at, deleted_at
 `
 
 type CreateMessageParams struct {
@@ -51,6 +64,7 @@ func (q *Queries) CreateMessage(ctx context.Context, arg CreateMessageParams) (M
 		&i.CreatedAt,
 		&i.UpdatedAt,
 		&i.FinishedAt,
+		&i.DeletedAt,
 	)
 	return i, err
 }
@@ -76,7 +90,7 @@ func (q *Queries) DeleteSessionMessages(ctx context.Context, sessionID string) e
 }
 
 const getMessage = `-- name: GetMessage :one
-SELECT id, session_id, role, parts, model, created_at, updated_at, finished_at
+SELECT id, session_id, role, parts, model, created_at, updated_at, finished_at, deleted_at
 FROM messages
 WHERE id = ? LIMIT 1
 `
@@ -93,15 +107,56 @@ func (q *Queries) GetMessage(ctx context.Context, id string) (Message, error) {
 		&i.CreatedAt,
 		&i.UpdatedAt,
 		&i.FinishedAt,
+		&i.DeletedAt,
 	)
 	return i, err
 }
 
+const importMessage = `-- name: ImportMessage :exec
+INSERT INTO messages (
+    id,
+    session_id,
+    role,
+    parts,
+    model,
+    created_at,
+    updated_at,
+    finished_at
+) VALUES (
+    ?, ?, ?, ?, ?, ?, ?, ?
+)
+`
+
+type ImportMessageParams struct {
+	ID         string         `json:"id"`
+	SessionID  string         `json:"session_id"`
+	Role       string         `json:"role"`
+	Parts      string         `json:"parts"`
+	Model      sql.NullString `json:"model"`
+	CreatedAt  int64          `json:"created_at"`
+	UpdatedAt  int64          `json:"updated_at"`
+	FinishedAt sql.NullInt64  `json:"finished_at"`
+}
+
+func (q *Queries) ImportMessage(ctx context.Context, arg ImportMessageParams) error {
+	_, err := q.exec(ctx, q.importMessageStmt, importMessage,
+		arg.ID,
+		arg.SessionID,
+		arg.Role,
+		arg.Parts,
+		arg.Model,
+		arg.CreatedAt,
+		arg.UpdatedAt,
+		arg.FinishedAt,
+	)
+	return err
+}
+
 const listMessagesBySession = `-- name: ListMessagesBySession :many
-SELECT id, session_id, role, parts, model, created_at, updated_at, finished_at
+SELECT id, session_id, role, parts, model, created_at, updated_at, finished_at, deleted_at
 FROM messages
-WHERE session_id = ?
-ORDER BY created_at ASC
+WHERE session_id = ? AND deleted_at IS NULL
+ORDER BY created_at ASC, rowid ASC
 `
 
 func (q *Queries) ListMessagesBySession(ctx context.Context, sessionID string) ([]Message, error) {
@@ -122,6 +177,7 @@ func (q *Queries) ListMessagesBySession(ctx context.Context, sessionID string) (
 			&i.CreatedAt,
 			&i.UpdatedAt,
 			&i.FinishedAt,
+			&i.DeletedAt,
 		); err != nil {
 			return nil, err
 		}
@@ -136,6 +192,126 @@ func (q *Queries) ListMessagesBySession(ctx context.Context, sessionID string) (
 	return items, nil
 }
 
+const listMessagesBySessionPage = `-- name: ListMessagesBySessionPage :many
+SELECT id, session_id, role, parts, model, created_at, updated_at, finished_at, deleted_at
+FROM messages
+WHERE session_id = ? AND deleted_at IS NULL
+ORDER BY created_at ASC, rowid ASC
+LIMIT ? OFFSET ?
+`
+
+type ListMessagesBySessionPageParams struct {
+	SessionID string `json:"session_id"`
+	Limit     int64  `json:"limit"`
+	Offset    int64  `json:"offset"`
+}
+
+func (q *Queries) ListMessagesBySessionPage(ctx context.Context, arg ListMessagesBySessionPageParams) ([]Message, error) {
+	rows, err := q.query(ctx, q.listMessagesBySessionPageStmt, listMessagesBySessionPage,
+		arg.SessionID,
+		arg.Limit,
+		arg.Offset,
+	)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []Message{}
+	for rows.Next() {
+		var i Message
+		if err := rows.Scan(
+			&i.ID,
+			&i.SessionID,
+			&i.Role,
+			&i.Parts,
+			&i.Model,
+			&i.CreatedAt,
+			&i.UpdatedAt,
+			&i.FinishedAt,
+			&i.DeletedAt,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+const searchMessages = `-- name: SearchMessages :many
+SELECT
+    m.id,
+    m.session_id,
+    s.title,
+    CAST(snippet(message_search, 0, '', '', '…', 16) AS TEXT) AS snippet,
+    m.created_at
+FROM message_search
+JOIN messages m ON m.id = message_search.message_id
+JOIN sessions s ON s.id = m.session_id
+WHERE message_search MATCH ?
+    AND s.parent_session_id IS NULL
+ORDER BY rank
+LIMIT ?
+`
+
+type SearchMessagesParams struct {
+	Query      string `json:"query"`
+	MaxResults int64  `json:"max_results"`
+}
+
+type SearchMessagesRow struct {
+	ID        string `json:"id"`
+	SessionID string `json:"session_id"`
+	Title     string `json:"title"`
+	Snippet   string `json:"snippet"`
+	CreatedAt int64  `json:"created_at"`
+}
+
+func (q *Queries) SearchMessages(ctx context.Context, arg SearchMessagesParams) ([]SearchMessagesRow, error) {
+	rows, err := q.query(ctx, q.searchMessagesStmt, searchMessages, arg.Query, arg.MaxResults)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []SearchMessagesRow{}
+	for rows.Next() {
+		var i SearchMessagesRow
+		if err := rows.Scan(
+			&i.ID,
+			&i.SessionID,
+			&i.Title,
+			&i.Snippet,
+			&i.CreatedAt,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+const softDeleteMessage = `-- name: SoftDeleteMessage :exec
+UPDATE messages
+SET deleted_at = strftime('%s', 'now')
+WHERE id = ?
+`
+
+func (q *Queries) SoftDeleteMessage(ctx context.Context, id string) error {
+	_, err := q.exec(ctx, q.softDeleteMessageStmt, softDeleteMessage, id)
+	return err
+}
+
 const updateMessage = `-- name: UpdateMessage :exec
 UPDATE messages
 SET
@@ -0,0 +1,16 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS permission_grants (
+    id TEXT PRIMARY KEY,
+    tool_name TEXT NOT NULL,
+    action TEXT NOT NULL,
+    pattern TEXT NOT NULL,  -- directory for file tools, command prefix for shell tools
+    created_at INTEGER NOT NULL,  -- Unix timestamp in milliseconds
+    UNIQUE(tool_name, action, pattern)
+);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TABLE IF EXISTS permission_grants;
+-- +goose StatementEnd
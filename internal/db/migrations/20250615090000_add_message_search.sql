@@ -0,0 +1,63 @@
+-- +goose Up
+-- +goose StatementBegin
+-- Full-text index over the text parts of the messages
+CREATE VIRTUAL TABLE IF NOT EXISTS message_search USING fts5(
+    content,
+    message_id UNINDEXED,
+    session_id UNINDEXED
+);
+
+INSERT INTO message_search (content, message_id, session_id)
+SELECT
+    COALESCE((
+        SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+        FROM json_each(m.parts) p
+        WHERE json_extract(p.value, '$.type') = 'text'
+    ), ''),
+    m.id,
+    m.session_id
+FROM messages m;
+
+CREATE TRIGGER IF NOT EXISTS insert_message_search
+AFTER INSERT ON messages
+BEGIN
+INSERT INTO message_search (content, message_id, session_id)
+VALUES (
+    COALESCE((
+        SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+        FROM json_each(new.parts) p
+        WHERE json_extract(p.value, '$.type') = 'text'
+    ), ''),
+    new.id,
+    new.session_id
+);
+END;
+
+-- Messages are updated with every chunk while they're streamed, they're only
+-- indexed again once finished
+CREATE TRIGGER IF NOT EXISTS update_message_search
+AFTER UPDATE OF parts ON messages
+WHEN new.finished_at IS NOT NULL
+BEGIN
+UPDATE message_search SET content = COALESCE((
+    SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+    FROM json_each(new.parts) p
+    WHERE json_extract(p.value, '$.type') = 'text'
+), '')
+WHERE message_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS delete_message_search
+AFTER DELETE ON messages
+BEGIN
+DELETE FROM message_search WHERE message_id = old.id;
+END;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TRIGGER IF EXISTS delete_message_search;
+DROP TRIGGER IF EXISTS update_message_search;
+DROP TRIGGER IF EXISTS insert_message_search;
+DROP TABLE IF EXISTS message_search;
+-- +goose StatementEnd
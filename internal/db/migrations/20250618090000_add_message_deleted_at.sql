@@ -0,0 +1,44 @@
+-- +goose Up
+-- +goose StatementBegin
+-- Messages removed by editing an earlier prompt are kept with the time they
+-- were removed, and left out of the session
+ALTER TABLE messages ADD COLUMN deleted_at INTEGER;
+
+CREATE TRIGGER IF NOT EXISTS soft_delete_message
+AFTER UPDATE OF deleted_at ON messages
+WHEN old.deleted_at IS NULL AND new.deleted_at IS NOT NULL
+BEGIN
+UPDATE sessions SET
+    message_count = message_count - 1
+WHERE id = new.session_id;
+DELETE FROM message_search WHERE message_id = new.id;
+END;
+
+-- Removed messages are no longer counted
+DROP TRIGGER IF EXISTS update_session_message_count_on_delete;
+CREATE TRIGGER IF NOT EXISTS update_session_message_count_on_delete
+AFTER DELETE ON messages
+WHEN old.deleted_at IS NULL
+BEGIN
+UPDATE sessions SET
+    message_count = message_count - 1
+WHERE id = old.session_id;
+END;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TRIGGER IF EXISTS soft_delete_message;
+DELETE FROM messages WHERE deleted_at IS NOT NULL;
+
+DROP TRIGGER IF EXISTS update_session_message_count_on_delete;
+CREATE TRIGGER IF NOT EXISTS update_session_message_count_on_delete
+AFTER DELETE ON messages
+BEGIN
+UPDATE sessions SET
+    message_count = message_count - 1
+WHERE id = old.session_id;
+END;
+
+ALTER TABLE messages DROP COLUMN deleted_at;
+-- +goose StatementEnd
@@ -0,0 +1,11 @@
+-- +goose Up
+-- +goose StatementBegin
+-- The first message sent verbatim after the summary, the turns from it on
+-- were left out of the summary
+ALTER TABLE sessions ADD COLUMN kept_message_id TEXT;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+ALTER TABLE sessions DROP COLUMN kept_message_id;
+-- +goose StatementEnd
@@ -0,0 +1,67 @@
+-- +goose Up
+-- +goose StatementBegin
+-- Encrypted parts aren't JSON, json_each fails on them. They're indexed
+-- without text instead.
+DROP TRIGGER IF EXISTS insert_message_search;
+DROP TRIGGER IF EXISTS update_message_search;
+
+CREATE TRIGGER IF NOT EXISTS insert_message_search
+AFTER INSERT ON messages
+BEGIN
+INSERT INTO message_search (content, message_id, session_id)
+VALUES (
+    COALESCE((
+        SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+        FROM json_each(CASE WHEN json_valid(new.parts) THEN new.parts ELSE '[]' END) p
+        WHERE json_extract(p.value, '$.type') = 'text'
+    ), ''),
+    new.id,
+    new.session_id
+);
+END;
+
+CREATE TRIGGER IF NOT EXISTS update_message_search
+AFTER UPDATE OF parts ON messages
+WHEN new.finished_at IS NOT NULL
+BEGIN
+UPDATE message_search SET content = COALESCE((
+    SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+    FROM json_each(CASE WHEN json_valid(new.parts) THEN new.parts ELSE '[]' END) p
+    WHERE json_extract(p.value, '$.type') = 'text'
+), '')
+WHERE message_id = new.id;
+END;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TRIGGER IF EXISTS insert_message_search;
+DROP TRIGGER IF EXISTS update_message_search;
+
+CREATE TRIGGER IF NOT EXISTS insert_message_search
+AFTER INSERT ON messages
+BEGIN
+INSERT INTO message_search (content, message_id, session_id)
+VALUES (
+    COALESCE((
+        SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+        FROM json_each(new.parts) p
+        WHERE json_extract(p.value, '$.type') = 'text'
+    ), ''),
+    new.id,
+    new.session_id
+);
+END;
+
+CREATE TRIGGER IF NOT EXISTS update_message_search
+AFTER UPDATE OF parts ON messages
+WHEN new.finished_at IS NOT NULL
+BEGIN
+UPDATE message_search SET content = COALESCE((
+    SELECT group_concat(json_extract(p.value, '$.data.text'), ' ')
+    FROM json_each(new.parts) p
+    WHERE json_extract(p.value, '$.type') = 'text'
+), '')
+WHERE message_id = new.id;
+END;
+-- +goose StatementEnd
@@ -0,0 +1,13 @@
+-- +goose Up
+-- +goose StatementBegin
+ALTER TABLE sessions ADD COLUMN max_tokens INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sessions ADD COLUMN reasoning_effort TEXT NOT NULL DEFAULT '';
+ALTER TABLE sessions ADD COLUMN system_prompt TEXT NOT NULL DEFAULT '';
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+ALTER TABLE sessions DROP COLUMN system_prompt;
+ALTER TABLE sessions DROP COLUMN reasoning_effort;
+ALTER TABLE sessions DROP COLUMN max_tokens;
+-- +goose StatementEnd
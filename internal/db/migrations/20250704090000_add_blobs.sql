@@ -0,0 +1,34 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS blobs (
+    id TEXT PRIMARY KEY,  -- SHA-256 of the content
+    data BLOB NOT NULL,  -- content, encrypted when data.encrypt is set
+    size INTEGER NOT NULL,  -- size of the content in bytes
+    created_at INTEGER NOT NULL  -- Unix timestamp in milliseconds
+);
+
+CREATE TABLE IF NOT EXISTS message_blobs (
+    message_id TEXT NOT NULL,
+    blob_id TEXT NOT NULL,
+    PRIMARY KEY (message_id, blob_id),
+    FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE,
+    FOREIGN KEY (blob_id) REFERENCES blobs (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_blobs_blob_id ON message_blobs (blob_id);
+
+CREATE TRIGGER IF NOT EXISTS delete_unused_blobs
+AFTER DELETE ON message_blobs
+WHEN NOT EXISTS (SELECT 1 FROM message_blobs WHERE blob_id = old.blob_id)
+BEGIN
+DELETE FROM blobs WHERE id = old.blob_id;
+END;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TRIGGER IF EXISTS delete_unused_blobs;
+DROP INDEX IF EXISTS idx_message_blobs_blob_id;
+DROP TABLE IF EXISTS message_blobs;
+DROP TABLE IF EXISTS blobs;
+-- +goose StatementEnd
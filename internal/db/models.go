@@ -8,6 +8,25 @@ import (
 	"database/sql"
 )
 
+type Blob struct {
+	ID        string `json:"id"`
+	Data      []byte `json:"data"`
+	Size      int64  `json:"size"`
+	CreatedAt int64  `json:"created_at"`
+}
+
+type Checkpoint struct {
+	ID        string `json:"id"`
+	SessionID string `json:"session_id"`
+	MessageID string `json:"message_id"`
+	CreatedAt int64  `json:"created_at"`
+}
+
+type CheckpointSnapshot struct {
+	CheckpointID string `json:"checkpoint_id"`
+	SnapshotID   string `json:"snapshot_id"`
+}
+
 type File struct {
 	ID        string `json:"id"`
 	SessionID string `json:"session_id"`
@@ -27,6 +46,20 @@ type Message struct {
 	CreatedAt  int64          `json:"created_at"`
 	UpdatedAt  int64          `json:"updated_at"`
 	FinishedAt sql.NullInt64  `json:"finished_at"`
+	DeletedAt  sql.NullInt64  `json:"deleted_at"`
+}
+
+type MessageBlob struct {
+	MessageID string `json:"message_id"`
+	BlobID    string `json:"blob_id"`
+}
+
+type PermissionGrant struct {
+	ID        string `json:"id"`
+	ToolName  string `json:"tool_name"`
+	Action    string `json:"action"`
+	Pattern   string `json:"pattern"`
+	CreatedAt int64  `json:"created_at"`
 }
 
 type Session struct {
@@ -40,4 +73,36 @@ type Session struct {
 	UpdatedAt        int64          `json:"updated_at"`
 	CreatedAt        int64          `json:"created_at"`
 	SummaryMessageID sql.NullString `json:"summary_message_id"`
+	Todos            string         `json:"todos"`
+	Model            string         `json:"model"`
+	KeptMessageID    sql.NullString `json:"kept_message_id"`
+	Archived         bool           `json:"archived"`
+	Pinned           bool           `json:"pinned"`
+	MaxTokens        int64          `json:"max_tokens"`
+	ReasoningEffort  string         `json:"reasoning_effort"`
+	SystemPrompt     string         `json:"system_prompt"`
+	Agent            string         `json:"agent"`
+}
+
+type SessionPermission struct {
+	SessionID string `json:"session_id"`
+	ToolName  string `json:"tool_name"`
+	Action    string `json:"action"`
+	Path      string `json:"path"`
+	CreatedAt int64  `json:"created_at"`
+}
+
+type SessionTag struct {
+	SessionID string `json:"session_id"`
+	Tag       string `json:"tag"`
+	CreatedAt int64  `json:"created_at"`
+}
+
+type Snapshot struct {
+	ID        string         `json:"id"`
+	SessionID string         `json:"session_id"`
+	MessageID string         `json:"message_id"`
+	Path      string         `json:"path"`
+	Hash      sql.NullString `json:"hash"`
+	CreatedAt int64          `json:"created_at"`
 }
@@ -0,0 +1,100 @@
+// Code generated by sqlc. DO NOT EDIT.
+// versions:
+//   sqlc v1.29.0
+// source: permission_grants.sql
+
+package db
+
+import (
+	"context"
+)
+
+const createPermissionGrant = `-- name: CreatePermissionGrant :exec
+INSERT OR IGNORE INTO permission_grants (
+    id,
+    tool_name,
+    action,
+    pattern,
+    created_at
+) VALUES (
+    ?, ?, ?, ?, strftime('%s', 'now')
+)
+`
+
+type CreatePermissionGrantParams struct {
+	ID       string `json:"id"`
+	ToolName string `json:"tool_name"`
+	Action   string `json:"action"`
+	Pattern  string `json:"pattern"`
+}
+
+func (q *Queries) CreatePermissionGrant(ctx context.Context, arg CreatePermissionGrantParams) error {
+	_, err := q.exec(ctx, q.createPermissionGrantStmt, createPermissionGrant,
+		arg.ID,
+		arg.ToolName,
+		arg.Action,
+		arg.Pattern,
+	)
+	return err
+}
+
+const deletePermissionGrant = `-- name: DeletePermissionGrant :exec
+DELETE FROM permission_grants
+WHERE id = ?
+`
+
+func (q *Queries) DeletePermissionGrant(ctx context.Context, id string) error {
+	_, err := q.exec(ctx, q.deletePermissionGrantStmt, deletePermissionGrant, id)
+	return err
+}
+
+const listPermissionGrants = `-- name: ListPermissionGrants :many
+SELECT id, tool_name, action, pattern, created_at
+FROM permission_grants
+ORDER BY tool_name ASC, pattern ASC
+`
+
+func (q *Queries) ListPermissionGrants(ctx context.Context) ([]PermissionGrant, error) {
+	rows, err := q.query(ctx, q.listPermissionGrantsStmt, listPermissionGrants)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []PermissionGrant{}
+	for rows.Next() {
+		var i PermissionGrant
+		if err := rows.Scan(
+			&i.ID,
+			&i.ToolName,
+			&i.Action,
+			&i.Pattern,
+			&i.CreatedAt,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+const updatePermissionGrant = `-- name: UpdatePermissionGrant :exec
+UPDATE permission_grants
+SET pattern = ?
+WHERE id = ?
+`
+
+type UpdatePermissionGrantParams struct {
+	Pattern string `json:"pattern"`
+	ID      string `json:"id"`
+}
+
+func (q *Queries) UpdatePermissionGrant(ctx context.Context, arg UpdatePermissionGrantParams) error {
+	_, err := q.exec(ctx, q.updatePermissionGrantStmt, updatePermissionGrant, arg.Pattern, arg.ID)
+	return err
+}
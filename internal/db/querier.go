@@ -6,30 +6,65 @@ package db
 
 import (
 	"context"
+	"database/sql"
 )
 
 type Querier interface {
+	AddSessionTag(ctx context.Context, arg AddSessionTagParams) error
+	CountMessagesBySession(ctx context.Context, sessionID string) (int64, error)
+	CreateBlob(ctx context.Context, arg CreateBlobParams) error
+	CreateCheckpoint(ctx context.Context, arg CreateCheckpointParams) error
 	CreateFile(ctx context.Context, arg CreateFileParams) (File, error)
 	CreateMessage(ctx context.Context, arg CreateMessageParams) (Message, error)
+	CreatePermissionGrant(ctx context.Context, arg CreatePermissionGrantParams) error
 	CreateSession(ctx context.Context, arg CreateSessionParams) (Session, error)
+	CreateSessionPermission(ctx context.Context, arg CreateSessionPermissionParams) error
+	CreateSnapshot(ctx context.Context, arg CreateSnapshotParams) error
+	DeleteChildSessions(ctx context.Context, parentSessionID sql.NullString) error
 	DeleteFile(ctx context.Context, id string) error
 	DeleteMessage(ctx context.Context, id string) error
+	DeletePermissionGrant(ctx context.Context, id string) error
 	DeleteSession(ctx context.Context, id string) error
 	DeleteSessionFiles(ctx context.Context, sessionID string) error
 	DeleteSessionMessages(ctx context.Context, sessionID string) error
+	DeleteSessionTag(ctx context.Context, arg DeleteSessionTagParams) error
+	DeleteSnapshot(ctx context.Context, id string) error
+	DeleteUnusedBlobs(ctx context.Context) error
+	GetBlob(ctx context.Context, id string) (Blob, error)
 	GetFile(ctx context.Context, id string) (File, error)
 	GetFileByPathAndSession(ctx context.Context, arg GetFileByPathAndSessionParams) (File, error)
 	GetMessage(ctx context.Context, id string) (Message, error)
 	GetSessionByID(ctx context.Context, id string) (Session, error)
+	HasEncryptedData(ctx context.Context) (int64, error)
+	ImportMessage(ctx context.Context, arg ImportMessageParams) error
+	LinkCheckpointSnapshots(ctx context.Context, arg LinkCheckpointSnapshotsParams) error
+	LinkMessageBlob(ctx context.Context, arg LinkMessageBlobParams) error
+	ListCheckpointFiles(ctx context.Context, sessionID string) ([]ListCheckpointFilesRow, error)
+	ListCheckpointsBySession(ctx context.Context, sessionID string) ([]Checkpoint, error)
 	ListFilesByPath(ctx context.Context, path string) ([]File, error)
 	ListFilesBySession(ctx context.Context, sessionID string) ([]File, error)
 	ListLatestSessionFiles(ctx context.Context, sessionID string) ([]File, error)
+	ListMessageBlobs(ctx context.Context, messageID string) ([]string, error)
 	ListMessagesBySession(ctx context.Context, sessionID string) ([]Message, error)
+	ListMessagesBySessionPage(ctx context.Context, arg ListMessagesBySessionPageParams) ([]Message, error)
 	ListNewFiles(ctx context.Context) ([]File, error)
+	ListPermissionGrants(ctx context.Context) ([]PermissionGrant, error)
+	ListSessionPermissions(ctx context.Context, sessionID string) ([]SessionPermission, error)
+	ListSessionTags(ctx context.Context) ([]SessionTag, error)
 	ListSessions(ctx context.Context) ([]Session, error)
+	ListSnapshotsBySession(ctx context.Context, sessionID string) ([]Snapshot, error)
+	ListTagsBySession(ctx context.Context, sessionID string) ([]string, error)
+	SearchMessages(ctx context.Context, arg SearchMessagesParams) ([]SearchMessagesRow, error)
+	SearchSessions(ctx context.Context, arg SearchSessionsParams) ([]SearchSessionsRow, error)
+	SoftDeleteMessage(ctx context.Context, id string) error
 	UpdateFile(ctx context.Context, arg UpdateFileParams) (File, error)
 	UpdateMessage(ctx context.Context, arg UpdateMessageParams) error
+	UpdatePermissionGrant(ctx context.Context, arg UpdatePermissionGrantParams) error
 	UpdateSession(ctx context.Context, arg UpdateSessionParams) (Session, error)
+	UpdateSessionArchived(ctx context.Context, arg UpdateSessionArchivedParams) (Session, error)
+	UpdateSessionPinned(ctx context.Context, arg UpdateSessionPinnedParams) (Session, error)
+	UpdateSessionSettings(ctx context.Context, arg UpdateSessionSettingsParams) (Session, error)
+	UpdateSessionTodos(ctx context.Context, arg UpdateSessionTodosParams) (Session, error)
 }
 
 var _ Querier = (*Queries)(nil)
@@ -0,0 +1,75 @@
+// Code generated by sqlc. DO NOT EDIT.
+// versions:
+//   sqlc v1.29.0
+// source: session_permissions.sql
+
+package db
+
+import (
+	"context"
+)
+
+const createSessionPermission = `-- name: CreateSessionPermission :exec
+INSERT OR IGNORE INTO session_permissions (
+    session_id,
+    tool_name,
+    action,
+    path,
+    created_at
+) VALUES (
+    ?, ?, ?, ?, strftime('%s', 'now')
+)
+`
+
+type CreateSessionPermissionParams struct {
+	SessionID string `json:"session_id"`
+	ToolName  string `json:"tool_name"`
+	Action    string `json:"action"`
+	Path      string `json:"path"`
+}
+
+func (q *Queries) CreateSessionPermission(ctx context.Context, arg CreateSessionPermissionParams) error {
+	_, err := q.exec(ctx, q.createSessionPermissionStmt, createSessionPermission,
+		arg.SessionID,
+		arg.ToolName,
+		arg.Action,
+		arg.Path,
+	)
+	return err
+}
+
+const listSessionPermissions = `-- name: ListSessionPermissions :many
+SELECT session_id, tool_name, action, path, created_at
+FROM session_permissions
+WHERE session_id = ?
+ORDER BY tool_name ASC, path ASC
+`
+
+func (q *Queries) ListSessionPermissions(ctx context.Context, sessionID string) ([]SessionPermission, error) {
+	rows, err := q.query(ctx, q.listSessionPermissionsStmt, listSessionPermissions, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []SessionPermission{}
+	for rows.Next() {
+		var i SessionPermission
+		if err := rows.Scan(
+			&i.SessionID,
+			&i.ToolName,
+			&i.Action,
+			&i.Path,
+			&i.CreatedAt,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
@@ -0,0 +1,104 @@
+// Code generated by sqlc. DO NOT EDIT.
+// versions:
+//   sqlc v1.29.0
+// source: session_tags.sql
+
+package db
+
+import (
+	"context"
+)
+
+const addSessionTag = `-- name: AddSessionTag :exec
+INSERT OR IGNORE INTO session_tags (
+    session_id,
+    tag,
+    created_at
+) VALUES (
+    ?, ?, strftime('%s', 'now')
+)
+`
+
+type AddSessionTagParams struct {
+	SessionID string `json:"session_id"`
+	Tag       string `json:"tag"`
+}
+
+func (q *Queries) AddSessionTag(ctx context.Context, arg AddSessionTagParams) error {
+	_, err := q.exec(ctx, q.addSessionTagStmt, addSessionTag, arg.SessionID, arg.Tag)
+	return err
+}
+
+const deleteSessionTag = `-- name: DeleteSessionTag :exec
+DELETE FROM session_tags
+WHERE session_id = ? AND tag = ?
+`
+
+type DeleteSessionTagParams struct {
+	SessionID string `json:"session_id"`
+	Tag       string `json:"tag"`
+}
+
+func (q *Queries) DeleteSessionTag(ctx context.Context, arg DeleteSessionTagParams) error {
+	_, err := q.exec(ctx, q.deleteSessionTagStmt, deleteSessionTag, arg.SessionID, arg.Tag)
+	return err
+}
+
+const listSessionTags = `-- name: ListSessionTags :many
+SELECT session_id, tag, created_at
+FROM session_tags
+ORDER BY tag ASC
+`
+
+func (q *Queries) ListSessionTags(ctx context.Context) ([]SessionTag, error) {
+	rows, err := q.query(ctx, q.listSessionTagsStmt, listSessionTags)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []SessionTag{}
+	for rows.Next() {
+		var i SessionTag
+		if err := rows.Scan(&i.SessionID, &i.Tag, &i.CreatedAt); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+const listTagsBySession = `-- name: ListTagsBySession :many
+SELECT tag
+FROM session_tags
+WHERE session_id = ?
+ORDER BY tag ASC
+`
+
+func (q *Queries) ListTagsBySession(ctx context.Context, sessionID string) ([]string, error) {
+	rows, err := q.query(ctx, q.listTagsBySessionStmt, listTagsBySession, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []string{}
+	for rows.Next() {
+		var tag string
+		if err := rows.Scan(&tag); err != nil {
+			return nil, err
+		}
+		items = append(items, tag)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
@@ -33,7 +33,7 @@ INSERT INTO sessions (
     null,
     strftime('%s', 'now'),
     strftime('%s', 'now')
-) RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id
+) RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
 `
 
 type CreateSessionParams struct {
@@ -68,10 +68,29 @@ func (q *Queries) CreateSession(ctx context.Context, arg CreateSessionParams) (S
 		&i.UpdatedAt,
 		&i.CreatedAt,
 		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
 	)
 	return i, err
 }
 
+const deleteChildSessions = `-- name: DeleteChildSessions :exec
+DELETE FROM sessions
+WHERE parent_session_id = ?
+`
+
+func (q *Queries) DeleteChildSessions(ctx context.Context, parentSessionID sql.NullString) error {
+	_, err := q.exec(ctx, q.deleteChildSessionsStmt, deleteChildSessions, parentSessionID)
+	return err
+}
+
 const deleteSession = `-- name: DeleteSession :exec
 DELETE FROM sessions
 WHERE id = ?
@@ -83,7 +102,7 @@ func (q *Queries) DeleteSession(ctx context.Context, id string) error {
 }
 
 const getSessionByID = `-- name: GetSessionByID :one
-SELECT id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id
+SELECT id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
 FROM sessions
 WHERE id = ? LIMIT 1
 `
@@ -102,15 +121,24 @@ func (q *Queries) GetSessionByID(ctx context.Context, id string) (Session, error
 		&i.UpdatedAt,
 		&i.CreatedAt,
 		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
 	)
 	return i, err
 }
 
 const listSessions = `-- name: ListSessions :many
-SELECT id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id
+SELECT id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
 FROM sessions
 WHERE parent_session_id is NULL
-ORDER BY created_at DESC
+ORDER BY pinned DESC, updated_at DESC, created_at DESC
 `
 
 func (q *Queries) ListSessions(ctx context.Context) ([]Session, error) {
@@ -133,6 +161,85 @@ func (q *Queries) ListSessions(ctx context.Context) ([]Session, error) {
 			&i.UpdatedAt,
 			&i.CreatedAt,
 			&i.SummaryMessageID,
+			&i.Todos,
+			&i.Model,
+			&i.KeptMessageID,
+			&i.Archived,
+			&i.Pinned,
+			&i.MaxTokens,
+			&i.ReasoningEffort,
+			&i.SystemPrompt,
+			&i.Agent,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
+
+const searchSessions = `-- name: SearchSessions :many
+SELECT id, title, updated_at, first_message
+FROM (
+    SELECT
+        s.id,
+        s.title,
+        s.updated_at,
+        CAST(COALESCE((
+            SELECT json_extract(p.value, '$.data.text')
+            FROM messages m, json_each(CASE WHEN json_valid(m.parts) THEN m.parts ELSE '[]' END) p
+            WHERE m.session_id = s.id
+                AND m.role = 'user'
+                AND m.deleted_at IS NULL
+                AND json_extract(p.value, '$.type') = 'text'
+            ORDER BY m.created_at
+            LIMIT 1
+        ), '') AS TEXT) AS first_message
+    FROM sessions s
+    WHERE s.parent_session_id IS NULL
+)
+WHERE title LIKE ? ESCAPE '\' OR first_message LIKE ? ESCAPE '\'
+ORDER BY title LIKE ? ESCAPE '\' DESC, updated_at DESC
+LIMIT ?
+`
+
+type SearchSessionsParams struct {
+	Pattern    string `json:"pattern"`
+	MaxResults int64  `json:"max_results"`
+}
+
+type SearchSessionsRow struct {
+	ID           string `json:"id"`
+	Title        string `json:"title"`
+	UpdatedAt    int64  `json:"updated_at"`
+	FirstMessage string `json:"first_message"`
+}
+
+func (q *Queries) SearchSessions(ctx context.Context, arg SearchSessionsParams) ([]SearchSessionsRow, error) {
+	rows, err := q.query(ctx, q.searchSessionsStmt, searchSessions,
+		arg.Pattern,
+		arg.Pattern,
+		arg.Pattern,
+		arg.MaxResults,
+	)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []SearchSessionsRow{}
+	for rows.Next() {
+		var i SearchSessionsRow
+		if err := rows.Scan(
+			&i.ID,
+			&i.Title,
+			&i.UpdatedAt,
+			&i.FirstMessage,
 		); err != nil {
 			return nil, err
 		}
@@ -154,9 +261,10 @@ SET
     prompt_tokens = ?,
     completion_tokens = ?,
     summary_message_id = ?,
+    kept_message_id = ?,
     cost = ?
 WHERE id = ?
-RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id
+RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
 `
 
 type UpdateSessionParams struct {
@@ -164,6 +272,7 @@ type UpdateSessionParams struct {
 	PromptTokens     int64          `json:"prompt_tokens"`
 	CompletionTokens int64          `json:"completion_tokens"`
 	SummaryMessageID sql.NullString `json:"summary_message_id"`
+	KeptMessageID    sql.NullString `json:"kept_message_id"`
 	Cost             float64        `json:"cost"`
 	ID               string         `json:"id"`
 }
@@ -174,6 +283,7 @@ func (q *Queries) UpdateSession(ctx context.Context, arg UpdateSessionParams) (S
 		arg.PromptTokens,
 		arg.CompletionTokens,
 		arg.SummaryMessageID,
+		arg.KeptMessageID,
 		arg.Cost,
 		arg.ID,
 	)
@@ -189,6 +299,190 @@ func (q *Queries) UpdateSession(ctx context.Context, arg UpdateSessionParams) (S
 		&i.UpdatedAt,
 		&i.CreatedAt,
 		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
+	)
+	return i, err
+}
+
+const updateSessionArchived = `-- name: UpdateSessionArchived :one
+UPDATE sessions
+SET
+    archived = ?
+WHERE id = ?
+RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
+`
+
+type UpdateSessionArchivedParams struct {
+	Archived bool   `json:"archived"`
+	ID       string `json:"id"`
+}
+
+func (q *Queries) UpdateSessionArchived(ctx context.Context, arg UpdateSessionArchivedParams) (Session, error) {
+	row := q.queryRow(ctx, q.updateSessionArchivedStmt, updateSessionArchived, arg.Archived, arg.ID)
+	var i Session
+	err := row.Scan(
+		&i.ID,
+		&i.ParentSessionID,
+		&i.Title,
+		&i.MessageCount,
+		&i.PromptTokens,
+		&i.CompletionTokens,
+		&i.Cost,
+		&i.UpdatedAt,
+		&i.CreatedAt,
+		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
+	)
+	return i, err
+}
+
+const updateSessionPinned = `-- name: UpdateSessionPinned :one
+UPDATE sessions
+SET
+    pinned = ?
+WHERE id = ?
+RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
+`
+
+type UpdateSessionPinnedParams struct {
+	Pinned bool   `json:"pinned"`
+	ID     string `json:"id"`
+}
+
+func (q *Queries) UpdateSessionPinned(ctx context.Context, arg UpdateSessionPinnedParams) (Session, error) {
+	row := q.queryRow(ctx, q.updateSessionPinnedStmt, updateSessionPinned, arg.Pinned, arg.ID)
+	var i Session
+	err := row.Scan(
+		&i.ID,
+		&i.ParentSessionID,
+		&i.Title,
+		&i.MessageCount,
+		&i.PromptTokens,
+		&i.CompletionTokens,
+		&i.Cost,
+		&i.UpdatedAt,
+		&i.CreatedAt,
+		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
+	)
+	return i, err
+}
+
+const updateSessionSettings = `-- name: UpdateSessionSettings :one
+UPDATE sessions
+SET
+    model = ?,
+    max_tokens = ?,
+    reasoning_effort = ?,
+    system_prompt = ?,
+    agent = ?
+WHERE id = ?
+RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
+`
+
+type UpdateSessionSettingsParams struct {
+	Model           string `json:"model"`
+	MaxTokens       int64  `json:"max_tokens"`
+	ReasoningEffort string `json:"reasoning_effort"`
+	SystemPrompt    string `json:"system_prompt"`
+	Agent           string `json:"agent"`
+	ID              string `json:"id"`
+}
+
+func (q *Queries) UpdateSessionSettings(ctx context.Context, arg UpdateSessionSettingsParams) (Session, error) {
+	row := q.queryRow(ctx, q.updateSessionSettingsStmt, updateSessionSettings,
+		arg.Model,
+		arg.MaxTokens,
+		arg.ReasoningEffort,
+		arg.SystemPrompt,
+		arg.Agent,
+		arg.ID,
+	)
+	var i Session
+	err := row.Scan(
+		&i.ID,
+		&i.ParentSessionID,
+		&i.Title,
+		&i.MessageCount,
+		&i.PromptTokens,
+		&i.CompletionTokens,
+		&i.Cost,
+		&i.UpdatedAt,
+		&i.CreatedAt,
+		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
+	)
+	return i, err
+}
+
+const updateSessionTodos = `-- name: UpdateSessionTodos :one
+UPDATE sessions
+SET
+    todos = ?
+WHERE id = ?
+RETURNING id, parent_session_id, title, message_count, prompt_tokens, completion_tokens, cost, updated_at, created_at, summary_message_id, todos, model, kept_message_id, archived, pinned, max_tokens, reasoning_effort, system_prompt, agent
+`
+
+type UpdateSessionTodosParams struct {
+	Todos string `json:"todos"`
+	ID    string `json:"id"`
+}
+
+func (q *Queries) UpdateSessionTodos(ctx context.Context, arg UpdateSessionTodosParams) (Session, error) {
+	row := q.queryRow(ctx, q.updateSessionTodosStmt, updateSessionTodos, arg.Todos, arg.ID)
+	var i Session
+	err := row.Scan(
+		&i.ID,
+		&i.ParentSessionID,
+		&i.Title,
+		&i.MessageCount,
+		&i.PromptTokens,
+		&i.CompletionTokens,
+		&i.Cost,
+		&i.UpdatedAt,
+		&i.CreatedAt,
+		&i.SummaryMessageID,
+		&i.Todos,
+		&i.Model,
+		&i.KeptMessageID,
+		&i.Archived,
+		&i.Pinned,
+		&i.MaxTokens,
+		&i.ReasoningEffort,
+		&i.SystemPrompt,
+		&i.Agent,
 	)
 	return i, err
 }
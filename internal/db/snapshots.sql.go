@@ -0,0 +1,90 @@
+// Code generated by sqlc. DO NOT EDIT.
+// versions:
+//   sqlc v1.29.0
+// source: snapshots.sql
+
+package db
+
+import (
+	"context"
+	"database/sql"
+)
+
+const createSnapshot = `-- name: CreateSnapshot :exec
+INSERT OR IGNORE INTO snapshots (
+    id,
+    session_id,
+    message_id,
+    path,
+    hash,
+    created_at
+) VALUES (
+    ?, ?, ?, ?, ?, strftime('%s', 'now')
+)
+`
+
+type CreateSnapshotParams struct {
+	ID        string         `json:"id"`
+	SessionID string         `json:"session_id"`
+	MessageID string         `json:"message_id"`
+	Path      string         `json:"path"`
+	Hash      sql.NullString `json:"hash"`
+}
+
+func (q *Queries) CreateSnapshot(ctx context.Context, arg CreateSnapshotParams) error {
+	_, err := q.exec(ctx, q.createSnapshotStmt, createSnapshot,
+		arg.ID,
+		arg.SessionID,
+		arg.MessageID,
+		arg.Path,
+		arg.Hash,
+	)
+	return err
+}
+
+const deleteSnapshot = `-- name: DeleteSnapshot :exec
+DELETE FROM snapshots
+WHERE id = ?
+`
+
+func (q *Queries) DeleteSnapshot(ctx context.Context, id string) error {
+	_, err := q.exec(ctx, q.deleteSnapshotStmt, deleteSnapshot, id)
+	return err
+}
+
+const listSnapshotsBySession = `-- name: ListSnapshotsBySession :many
+SELECT id, session_id, message_id, path, hash, created_at
+FROM snapshots
+WHERE session_id = ?
+ORDER BY created_at ASC
+`
+
+func (q *Queries) ListSnapshotsBySession(ctx context.Context, sessionID string) ([]Snapshot, error) {
+	rows, err := q.query(ctx, q.listSnapshotsBySessionStmt, listSnapshotsBySession, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	items := []Snapshot{}
+	for rows.Next() {
+		var i Snapshot
+		if err := rows.Scan(
+			&i.ID,
+			&i.SessionID,
+			&i.MessageID,
+			&i.Path,
+			&i.Hash,
+			&i.CreatedAt,
+		); err != nil {
+			return nil, err
+		}
+		items = append(items, i)
+	}
+	if err := rows.Close(); err != nil {
+		return nil, err
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
+	}
+	return items, nil
+}
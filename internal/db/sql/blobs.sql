@@ -0,0 +1,35 @@
+-- name: CreateBlob :exec
+INSERT INTO blobs (
+    id,
+    data,
+    size,
+    created_at
+) VALUES (
+    ?, ?, ?, strftime('%s', 'now')
+)
+ON CONFLICT (id) DO NOTHING;
+
+-- name: GetBlob :one
+SELECT *
+FROM blobs
+WHERE id = ? LIMIT 1;
+
+-- name: LinkMessageBlob :exec
+INSERT OR IGNORE INTO message_blobs (message_id, blob_id)
+VALUES (?, ?);
+
+-- name: ListMessageBlobs :many
+SELECT blob_id
+FROM message_blobs
+WHERE message_id = ?;
+
+-- name: DeleteUnusedBlobs :exec
+DELETE FROM blobs
+WHERE id NOT IN (SELECT blob_id FROM message_blobs);
+
+-- name: HasEncryptedData :one
+SELECT EXISTS (
+    SELECT 1 FROM messages WHERE parts LIKE 'enc:v1:%'
+) OR EXISTS (
+    SELECT 1 FROM blobs WHERE CAST(data AS TEXT) LIKE 'enc:v1:%'
+) AS encrypted;
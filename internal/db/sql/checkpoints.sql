@@ -0,0 +1,30 @@
+-- name: CreateCheckpoint :exec
+INSERT INTO checkpoints (
+    id,
+    session_id,
+    message_id,
+    created_at
+) VALUES (
+    ?, ?, ?, strftime('%s', 'now')
+);
+
+-- name: LinkCheckpointSnapshots :exec
+INSERT OR IGNORE INTO checkpoint_snapshots (checkpoint_id, snapshot_id)
+SELECT ?, id
+FROM snapshots
+WHERE session_id = ?
+  AND id NOT IN (SELECT snapshot_id FROM checkpoint_snapshots);
+
+-- name: ListCheckpointsBySession :many
+SELECT c.*
+FROM checkpoints c
+JOIN messages m ON m.id = c.message_id
+WHERE c.session_id = ? AND m.deleted_at IS NULL
+ORDER BY c.created_at ASC, c.rowid ASC;
+
+-- name: ListCheckpointFiles :many
+SELECT cs.checkpoint_id, s.path
+FROM checkpoint_snapshots cs
+JOIN snapshots s ON s.id = cs.snapshot_id
+WHERE s.session_id = ?
+ORDER BY cs.checkpoint_id ASC, s.path ASC;
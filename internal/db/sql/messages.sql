@@ -6,8 +6,35 @@ WHERE id = ? LIMIT 1;
 -- name: ListMessagesBySession :many
 SELECT *
 FROM messages
-WHERE session_id = ?
-ORDER BY created_at ASC;
+WHERE session_id = ? AND deleted_at IS NULL
+ORDER BY created_at ASC, rowid ASC;
+
+-- name: ListMessagesBySessionPage :many
+SELECT *
+FROM messages
+WHERE session_id = ? AND deleted_at IS NULL
+ORDER BY created_at ASC, rowid ASC
+LIMIT ? OFFSET ?;
+
+-- name: CountMessagesBySession :one
+SELECT COUNT(*)
+FROM messages
+WHERE session_id = ? AND deleted_at IS NULL;
+
+-- name: SearchMessages :many
+SELECT
+    m.id,
+    m.session_id,
+    s.title,
+    CAST(snippet(message_search, 0, '', '', '…', 16) AS TEXT) AS snippet,
+    m.created_at
+FROM message_search
+JOIN messages m ON m.id = message_search.message_id
+JOIN sessions s ON s.id = m.session_id
+WHERE message_search MATCH sqlc.arg(query)
+    AND s.parent_session_id IS NULL
+ORDER BY rank
+LIMIT sqlc.arg(max_results);
 
 -- name: CreateMessage :one
 INSERT INTO messages (
@@ -23,6 +50,20 @@ INSERT INTO messages (
 )
 RETURNING *;
 
+-- name: ImportMessage :exec
+INSERT INTO messages (
+    id,
+    session_id,
+    role,
+    parts,
+    model,
+    created_at,
+    updated_at,
+    finished_at
+) VALUES (
+    ?, ?, ?, ?, ?, ?, ?, ?
+);
+
 -- name: UpdateMessage :exec
 UPDATE messages
 SET
@@ -31,6 +72,10 @@ SET
     updated_at = strftime('%s', 'now')
 WHERE id = ?;
 
+-- name: SoftDeleteMessage :exec
+UPDATE messages
+SET deleted_at = strftime('%s', 'now')
+WHERE id = ?;
 
 -- name: DeleteMessage :exec
 DELETE FROM messages
@@ -0,0 +1,24 @@
+-- name: CreatePermissionGrant :exec
+INSERT OR IGNORE INTO permission_grants (
+    id,
+    tool_name,
+    action,
+    pattern,
+    created_at
+) VALUES (
+    ?, ?, ?, ?, strftime('%s', 'now')
+);
+
+-- name: ListPermissionGrants :many
+SELECT *
+FROM permission_grants
+ORDER BY tool_name ASC, pattern ASC;
+
+-- name: UpdatePermissionGrant :exec
+UPDATE permission_grants
+SET pattern = ?
+WHERE id = ?;
+
+-- name: DeletePermissionGrant :exec
+DELETE FROM permission_grants
+WHERE id = ?;
@@ -0,0 +1,16 @@
+-- name: CreateSessionPermission :exec
+INSERT OR IGNORE INTO session_permissions (
+    session_id,
+    tool_name,
+    action,
+    path,
+    created_at
+) VALUES (
+    ?, ?, ?, ?, strftime('%s', 'now')
+);
+
+-- name: ListSessionPermissions :many
+SELECT *
+FROM session_permissions
+WHERE session_id = ?
+ORDER BY tool_name ASC, path ASC;
@@ -0,0 +1,23 @@
+-- name: AddSessionTag :exec
+INSERT OR IGNORE INTO session_tags (
+    session_id,
+    tag,
+    created_at
+) VALUES (
+    ?, ?, strftime('%s', 'now')
+);
+
+-- name: ListSessionTags :many
+SELECT *
+FROM session_tags
+ORDER BY tag ASC;
+
+-- name: ListTagsBySession :many
+SELECT tag
+FROM session_tags
+WHERE session_id = ?
+ORDER BY tag ASC;
+
+-- name: DeleteSessionTag :exec
+DELETE FROM session_tags
+WHERE session_id = ? AND tag = ?;
@@ -32,7 +32,31 @@ WHERE id = ? LIMIT 1;
 SELECT *
 FROM sessions
 WHERE parent_session_id is NULL
-ORDER BY created_at DESC;
+ORDER BY pinned DESC, updated_at DESC, created_at DESC;
+
+-- name: SearchSessions :many
+SELECT id, title, updated_at, first_message
+FROM (
+    SELECT
+        s.id,
+        s.title,
+        s.updated_at,
+        CAST(COALESCE((
+            SELECT json_extract(p.value, '$.data.text')
+            FROM messages m, json_each(CASE WHEN json_valid(m.parts) THEN m.parts ELSE '[]' END) p
+            WHERE m.session_id = s.id
+                AND m.role = 'user'
+                AND m.deleted_at IS NULL
+                AND json_extract(p.value, '$.type') = 'text'
+            ORDER BY m.created_at
+            LIMIT 1
+        ), '') AS TEXT) AS first_message
+    FROM sessions s
+    WHERE s.parent_session_id IS NULL
+)
+WHERE title LIKE sqlc.arg(pattern) ESCAPE '\' OR first_message LIKE sqlc.arg(pattern) ESCAPE '\'
+ORDER BY title LIKE sqlc.arg(pattern) ESCAPE '\' DESC, updated_at DESC
+LIMIT sqlc.arg(max_results);
 
 -- name: UpdateSession :one
 UPDATE sessions
@@ -41,11 +65,48 @@ SET
     prompt_tokens = ?,
     completion_tokens = ?,
     summary_message_id = ?,
+    kept_message_id = ?,
     cost = ?
 WHERE id = ?
 RETURNING *;
 
+-- name: UpdateSessionSettings :one
+UPDATE sessions
+SET
+    model = ?,
+    max_tokens = ?,
+    reasoning_effort = ?,
+    system_prompt = ?,
+    agent = ?
+WHERE id = ?
+RETURNING *;
+
+-- name: UpdateSessionArchived :one
+UPDATE sessions
+SET
+    archived = ?
+WHERE id = ?
+RETURNING *;
+
+-- name: UpdateSessionPinned :one
+UPDATE sessions
+SET
+    pinned = ?
+WHERE id = ?
+RETURNING *;
+
+-- name: UpdateSessionTodos :one
+UPDATE sessions
+SET
+    todos = ?
+WHERE id = ?
+RETURNING *;
+
 
 -- name: DeleteSession :exec
 DELETE FROM sessions
 WHERE id = ?;
+
+-- name: DeleteChildSessions :exec
+DELETE FROM sessions
+WHERE parent_session_id = ?;
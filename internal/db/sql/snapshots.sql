@@ -0,0 +1,21 @@
+-- name: CreateSnapshot :exec
+INSERT OR IGNORE INTO snapshots (
+    id,
+    session_id,
+    message_id,
+    path,
+    hash,
+    created_at
+) VALUES (
+    ?, ?, ?, ?, ?, strftime('%s', 'now')
+);
+
+-- name: ListSnapshotsBySession :many
+SELECT *
+FROM snapshots
+WHERE session_id = ?
+ORDER BY created_at ASC;
+
+-- name: DeleteSnapshot :exec
+DELETE FROM snapshots
+WHERE id = ?;
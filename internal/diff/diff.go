@@ -17,6 +17,7 @@ import (
 	"github.com/charmbracelet/x/ansi"
 	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
 	"github.com/sergi/go-diff/diffmatchpatch"
 )
 
@@ -187,7 +188,7 @@ func ParseUnifiedDiff(diff string) (DiffResult, error) {
 					OldLineNo: 0,
 					NewLineNo: newLine,
 					Kind:      LineAdded,
-					Content:   line[1:],
+					Content:   util.ExpandTabs(line[1:]),
 				})
 				newLine++
 			case '-':
@@ -195,7 +196,7 @@ func ParseUnifiedDiff(diff string) (DiffResult, error) {
 					OldLineNo: oldLine,
 					NewLineNo: 0,
 					Kind:      LineRemoved,
-					Content:   line[1:],
+					Content:   util.ExpandTabs(line[1:]),
 				})
 				oldLine++
 			default:
@@ -203,7 +204,7 @@ func ParseUnifiedDiff(diff string) (DiffResult, error) {
 					OldLineNo: oldLine,
 					NewLineNo: newLine,
 					Kind:      LineContext,
-					Content:   line,
+					Content:   line[:1] + util.ExpandTabs(line[1:]),
 				})
 				oldLine++
 				newLine++
@@ -0,0 +1,249 @@
+package export
+
+import (
+	"context"
+	"database/sql"
+	"encoding/json"
+	"fmt"
+
+	"github.com/google/uuid"
+	"github.com/zhenbah/cryoncode/internal/crypt"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+// jsonVersion is the version of the JSON format, increased when it changes in
+// a way older versions can't import
+const jsonVersion = 1
+
+type sessionExport struct {
+	Version  int               `json:"version"`
+	Session  exportedSession   `json:"session"`
+	Messages []exportedMessage `json:"messages"`
+}
+
+type exportedSession struct {
+	ID               string          `json:"id"`
+	Title            string          `json:"title"`
+	PromptTokens     int64           `json:"prompt_tokens"`
+	CompletionTokens int64           `json:"completion_tokens"`
+	Cost             float64         `json:"cost"`
+	SummaryMessageID string          `json:"summary_message_id,omitempty"`
+	KeptMessageID    string          `json:"kept_message_id,omitempty"`
+	Todos            json.RawMessage `json:"todos,omitempty"`
+	Model            string          `json:"model,omitempty"`
+	MaxTokens        int64           `json:"max_tokens,omitempty"`
+	ReasoningEffort  string          `json:"reasoning_effort,omitempty"`
+	SystemPrompt     string          `json:"system_prompt,omitempty"`
+	Agent            string          `json:"agent,omitempty"`
+	Tags             []string        `json:"tags,omitempty"`
+	Archived         bool            `json:"archived,omitempty"`
+	Pinned           bool            `json:"pinned,omitempty"`
+	CreatedAt        int64           `json:"created_at"`
+	UpdatedAt        int64           `json:"updated_at"`
+}
+
+type exportedMessage struct {
+	ID         string          `json:"id"`
+	Role       string          `json:"role"`
+	Parts      json.RawMessage `json:"parts"`
+	Model      string          `json:"model,omitempty"`
+	CreatedAt  int64           `json:"created_at"`
+	UpdatedAt  int64           `json:"updated_at"`
+	FinishedAt int64           `json:"finished_at,omitempty"`
+}
+
+// JSON returns the session and its messages as they're stored, with the parts
+// of the messages kept as is so nothing is lost. Encrypted parts are
+// decrypted with the cipher.
+func JSON(ctx context.Context, q db.Querier, cipher *crypt.Cipher, sessionID string) ([]byte, error) {
+	sess, err := q.GetSessionByID(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	messages, err := q.ListMessagesBySession(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+
+	out := sessionExport{
+		Version: jsonVersion,
+		Session: exportedSession{
+			ID:               sess.ID,
+			Title:            sess.Title,
+			PromptTokens:     sess.PromptTokens,
+			CompletionTokens: sess.CompletionTokens,
+			Cost:             sess.Cost,
+			SummaryMessageID: sess.SummaryMessageID.String,
+			KeptMessageID:    sess.KeptMessageID.String,
+			Model:            sess.Model,
+			MaxTokens:        sess.MaxTokens,
+			ReasoningEffort:  sess.ReasoningEffort,
+			SystemPrompt:     sess.SystemPrompt,
+			Agent:            sess.Agent,
+			Archived:         sess.Archived,
+			Pinned:           sess.Pinned,
+			CreatedAt:        sess.CreatedAt,
+			UpdatedAt:        sess.UpdatedAt,
+		},
+		Messages: make([]exportedMessage, len(messages)),
+	}
+	if sess.Todos != "" {
+		out.Session.Todos = json.RawMessage(sess.Todos)
+	}
+	if out.Session.Tags, err = q.ListTagsBySession(ctx, sessionID); err != nil {
+		return nil, err
+	}
+	for i, msg := range messages {
+		parts, err := cipher.Decrypt(msg.Parts)
+		if err != nil {
+			return nil, err
+		}
+		if parts, err = message.InlineBlobs(ctx, q, cipher, parts); err != nil {
+			return nil, err
+		}
+		out.Messages[i] = exportedMessage{
+			ID:         msg.ID,
+			Role:       msg.Role,
+			Parts:      json.RawMessage(parts),
+			Model:      msg.Model.String,
+			CreatedAt:  msg.CreatedAt,
+			UpdatedAt:  msg.UpdatedAt,
+			FinishedAt: msg.FinishedAt.Int64,
+		}
+	}
+	return json.MarshalIndent(out, "", "  ")
+}
+
+// Import recreates a session exported by JSON and returns its ID. The session
+// and its messages get new IDs, so a session can be imported more than once.
+// The parts of the messages are encrypted with the cipher when it's set.
+func Import(ctx context.Context, conn *sql.DB, cipher *crypt.Cipher, data []byte) (string, error) {
+	in, err := decodeExport(data)
+	if err != nil {
+		return "", err
+	}
+
+	tx, err := conn.BeginTx(ctx, nil)
+	if err != nil {
+		return "", err
+	}
+	defer tx.Rollback()
+	q := db.New(conn).WithTx(tx)
+
+	sessionID := uuid.New().String()
+	if _, err := q.CreateSession(ctx, db.CreateSessionParams{
+		ID:               sessionID,
+		Title:            in.Session.Title,
+		PromptTokens:     in.Session.PromptTokens,
+		CompletionTokens: in.Session.CompletionTokens,
+		Cost:             in.Session.Cost,
+	}); err != nil {
+		return "", fmt.Errorf("failed to create the session: %w", err)
+	}
+
+	messageIDs := make(map[string]string, len(in.Messages))
+	for _, msg := range in.Messages {
+		messageIDs[msg.ID] = uuid.New().String()
+		parts, blobs, err := message.StoreBlobs(ctx, q, cipher, string(msg.Parts))
+		if err != nil {
+			return "", fmt.Errorf("failed to import message %s: %w", msg.ID, err)
+		}
+		if parts, err = cipher.Encrypt(parts); err != nil {
+			return "", err
+		}
+		if err := q.ImportMessage(ctx, db.ImportMessageParams{
+			ID:         messageIDs[msg.ID],
+			SessionID:  sessionID,
+			Role:       msg.Role,
+			Parts:      parts,
+			Model:      sql.NullString{String: msg.Model, Valid: msg.Model != ""},
+			CreatedAt:  msg.CreatedAt,
+			UpdatedAt:  msg.UpdatedAt,
+			FinishedAt: sql.NullInt64{Int64: msg.FinishedAt, Valid: msg.FinishedAt != 0},
+		}); err != nil {
+			return "", fmt.Errorf("failed to import message %s: %w", msg.ID, err)
+		}
+		if err := message.LinkBlobs(ctx, q, messageIDs[msg.ID], blobs); err != nil {
+			return "", fmt.Errorf("failed to import message %s: %w", msg.ID, err)
+		}
+	}
+
+	summaryID, ok := messageIDs[in.Session.SummaryMessageID]
+	keptID, kept := messageIDs[in.Session.KeptMessageID]
+	if _, err := q.UpdateSession(ctx, db.UpdateSessionParams{
+		ID:               sessionID,
+		Title:            in.Session.Title,
+		PromptTokens:     in.Session.PromptTokens,
+		CompletionTokens: in.Session.CompletionTokens,
+		SummaryMessageID: sql.NullString{String: summaryID, Valid: ok},
+		KeptMessageID:    sql.NullString{String: keptID, Valid: ok && kept},
+		Cost:             in.Session.Cost,
+	}); err != nil {
+		return "", fmt.Errorf("failed to update the session: %w", err)
+	}
+	if len(in.Session.Todos) > 0 {
+		if _, err := q.UpdateSessionTodos(ctx, db.UpdateSessionTodosParams{
+			ID:    sessionID,
+			Todos: string(in.Session.Todos),
+		}); err != nil {
+			return "", fmt.Errorf("failed to update the session: %w", err)
+		}
+	}
+	for _, tag := range in.Session.Tags {
+		if err := q.AddSessionTag(ctx, db.AddSessionTagParams{
+			SessionID: sessionID,
+			Tag:       tag,
+		}); err != nil {
+			return "", fmt.Errorf("failed to tag the session: %w", err)
+		}
+	}
+	if in.Session.Archived {
+		if _, err := q.UpdateSessionArchived(ctx, db.UpdateSessionArchivedParams{
+			ID:       sessionID,
+			Archived: true,
+		}); err != nil {
+			return "", fmt.Errorf("failed to update the session: %w", err)
+		}
+	}
+	if in.Session.Pinned {
+		if _, err := q.UpdateSessionPinned(ctx, db.UpdateSessionPinnedParams{
+			ID:     sessionID,
+			Pinned: true,
+		}); err != nil {
+			return "", fmt.Errorf("failed to update the session: %w", err)
+		}
+	}
+	if in.Session.Model != "" {
+		if _, err := q.UpdateSessionSettings(ctx, db.UpdateSessionSettingsParams{
+			ID:              sessionID,
+			Model:           in.Session.Model,
+			MaxTokens:       in.Session.MaxTokens,
+			ReasoningEffort: in.Session.ReasoningEffort,
+			SystemPrompt:    in.Session.SystemPrompt,
+			Agent:           in.Session.Agent,
+		}); err != nil {
+			return "", fmt.Errorf("failed to update the session: %w", err)
+		}
+	}
+
+	if err := tx.Commit(); err != nil {
+		return "", err
+	}
+	return sessionID, nil
+}
+
+// decodeExport reads an export, refusing one written in a newer format
+func decodeExport(data []byte) (sessionExport, error) {
+	var in sessionExport
+	if err := json.Unmarshal(data, &in); err != nil {
+		return sessionExport{}, fmt.Errorf("invalid session export: %w", err)
+	}
+	if in.Version == 0 {
+		return sessionExport{}, fmt.Errorf("invalid session export: missing version")
+	}
+	if in.Version > jsonVersion {
+		return sessionExport{}, fmt.Errorf("the session export has version %d, this version of cryoncode imports up to %d", in.Version, jsonVersion)
+	}
+	return in, nil
+}
@@ -0,0 +1,29 @@
+package export
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestDecodeExport(t *testing.T) {
+	in, err := decodeExport([]byte(`{
+		"version": 1,
+		"session": {"id": "s", "title": "Fix the login", "todos": [{"content": "test"}]},
+		"messages": [{"id": "m", "role": "user", "parts": [{"type": "text", "data": {"text": "hi"}}], "created_at": 10}]
+	}`))
+	require.NoError(t, err)
+	assert.Equal(t, "Fix the login", in.Session.Title)
+	assert.JSONEq(t, `[{"content": "test"}]`, string(in.Session.Todos))
+	require.Len(t, in.Messages, 1)
+	// The parts are kept as they were exported
+	assert.JSONEq(t, `[{"type": "text", "data": {"text": "hi"}}]`, string(in.Messages[0].Parts))
+
+	_, err = decodeExport([]byte(`{"version": 2, "session": {}}`))
+	assert.ErrorContains(t, err, "version 2")
+	_, err = decodeExport([]byte(`{"session": {}}`))
+	assert.Error(t, err)
+	_, err = decodeExport([]byte(`[`))
+	assert.Error(t, err)
+}
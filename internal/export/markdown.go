@@ -0,0 +1,114 @@
+// Package export writes sessions out of the database, to be read or shared
+// outside of the TUI.
+package export
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+// SessionMarkdown loads the session and its messages and returns the
+// transcript in Markdown
+func SessionMarkdown(ctx context.Context, sessions session.Service, messages message.Service, sessionID string) (string, error) {
+	sess, err := sessions.Get(ctx, sessionID)
+	if err != nil {
+		return "", err
+	}
+	msgs, err := messages.List(ctx, sessionID)
+	if err != nil {
+		return "", err
+	}
+	return Markdown(sess, msgs), nil
+}
+
+// Markdown returns the transcript of the session: the prompts and responses
+// as they were written, and the tool calls with their input and output in
+// fenced code blocks.
+func Markdown(sess session.Session, messages []message.Message) string {
+	results := make(map[string]message.ToolResult)
+	for _, msg := range messages {
+		for _, result := range msg.ToolResults() {
+			results[result.ToolCallID] = result
+		}
+	}
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "# %s\n", sess.Title)
+	for _, msg := range messages {
+		switch msg.Role {
+		case message.User:
+			b.WriteString("\n## User\n")
+			writeText(&b, msg.Content().String())
+			for _, attachment := range msg.BinaryContent() {
+				fmt.Fprintf(&b, "\nAttached `%s`\n", attachment.Path)
+			}
+		case message.Assistant:
+			text := msg.Content().String()
+			calls := msg.ToolCalls()
+			if strings.TrimSpace(text) == "" && len(calls) == 0 {
+				continue
+			}
+			b.WriteString("\n## Assistant\n")
+			writeText(&b, text)
+			for _, call := range calls {
+				fmt.Fprintf(&b, "\n**Tool: %s**\n\n", call.Name)
+				b.WriteString(fence(indentJSON(call.Input), "json"))
+				result, ok := results[call.ID]
+				if !ok {
+					continue
+				}
+				output := result.Content
+				if result.FullContent != "" {
+					output = result.FullContent
+				}
+				if result.IsError {
+					b.WriteString("\nError:\n")
+				}
+				b.WriteString("\n")
+				b.WriteString(fence(output, ""))
+			}
+		}
+	}
+	return b.String()
+}
+
+func writeText(b *strings.Builder, text string) {
+	text = strings.TrimSpace(text)
+	if text == "" {
+		return
+	}
+	b.WriteString("\n")
+	b.WriteString(text)
+	b.WriteString("\n")
+}
+
+// fence wraps the content in a code block whose fence is longer than any run
+// of backticks in the content
+func fence(content, lang string) string {
+	longest, run := 0, 0
+	for _, r := range content {
+		if r == '`' {
+			run++
+			longest = max(longest, run)
+		} else {
+			run = 0
+		}
+	}
+	marker := strings.Repeat("`", max(3, longest+1))
+	return marker + lang + "\n" + strings.TrimRight(content, "\n") + "\n" + marker + "\n"
+}
+
+// indentJSON indents the tool input, it's kept as is when it isn't valid JSON
+func indentJSON(input string) string {
+	var out bytes.Buffer
+	if err := json.Indent(&out, []byte(input), "", "  "); err != nil {
+		return input
+	}
+	return out.String()
+}
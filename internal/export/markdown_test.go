@@ -0,0 +1,80 @@
+package export
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+func TestMarkdown(t *testing.T) {
+	messages := []message.Message{
+		{
+			Role:  message.User,
+			Parts: []message.ContentPart{message.TextContent{Text: "List the files"}},
+		},
+		{
+			Role: message.Assistant,
+			Parts: []message.ContentPart{
+				message.TextContent{Text: "Listing them."},
+				message.ToolCall{ID: "call", Name: "ls", Input: `{"path":"."}`},
+			},
+		},
+		{
+			Role: message.Tool,
+			Parts: []message.ContentPart{
+				message.ToolResult{ToolCallID: "call", Content: "go.mod\nmain.go"},
+			},
+		},
+		{
+			Role:  message.Assistant,
+			Parts: []message.ContentPart{message.TextContent{Text: "There are two files."}},
+		},
+	}
+
+	expected := "# Files\n" +
+		"\n## User\n\nList the files\n" +
+		"\n## Assistant\n\nListing them.\n" +
+		"\n**Tool: ls**\n\n```json\n{\n  \"path\": \".\"\n}\n```\n" +
+		"\n```\ngo.mod\nmain.go\n```\n" +
+		"\n## Assistant\n\nThere are two files.\n"
+	assert.Equal(t, expected, Markdown(session.Session{Title: "Files"}, messages))
+}
+
+func TestFence(t *testing.T) {
+	assert.Equal(t, "```go\nx\n```\n", fence("x\n", "go"))
+	assert.Equal(t, "````\n```\ny\n```\n````\n", fence("```\ny\n```", ""))
+}
+
+func TestTranscriptEntry(t *testing.T) {
+	at := time.Unix(1700000000, 0).Format(time.DateTime)
+
+	prompt := message.Message{
+		Role:      message.User,
+		Parts:     []message.ContentPart{message.TextContent{Text: "List the files"}},
+		CreatedAt: 1700000000,
+	}
+	assert.Equal(t, "\n## User ("+at+")\n\nList the files\n", transcriptEntry(prompt))
+
+	response := message.Message{
+		Role: message.Assistant,
+		Parts: []message.ContentPart{
+			message.ToolCall{ID: "call", Name: "ls", Input: `{}`},
+		},
+		CreatedAt: 1700000000,
+	}
+	assert.Equal(t, "\n## Assistant ("+at+")\n\n**Tool: ls**\n\n```json\n{}\n```\n", transcriptEntry(response))
+
+	results := message.Message{
+		Role: message.Tool,
+		Parts: []message.ContentPart{
+			message.ToolResult{ToolCallID: "call", Name: "ls", Content: "go.mod", IsError: true},
+		},
+	}
+	assert.Equal(t, "\n**Result: ls**\n\nError:\n\n```\ngo.mod\n```\n", transcriptEntry(results))
+
+	// Empty responses aren't written
+	assert.Equal(t, "", transcriptEntry(message.Message{Role: message.Assistant}))
+}
@@ -0,0 +1,119 @@
+package export
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/pubsub"
+)
+
+// Transcripts appends the messages of each session to a Markdown file named
+// after the session as they're written, so the transcript survives the loss
+// of the database or a crash in the middle of a turn.
+type Transcripts struct {
+	dir string
+	// written holds the responses already appended, a response can still be
+	// updated after it finished
+	written map[string]bool
+}
+
+// NewTranscripts creates the logger writing the transcripts in dir
+func NewTranscripts(dir string) *Transcripts {
+	return &Transcripts{
+		dir:     dir,
+		written: make(map[string]bool),
+	}
+}
+
+// Run appends the messages published by the service until the context is
+// done. User prompts and tool results are written when they're created, the
+// responses once they're finished.
+func (t *Transcripts) Run(ctx context.Context, messages message.Service) {
+	events := messages.Subscribe(ctx)
+	for event := range events {
+		msg := event.Payload
+		switch event.Type {
+		case pubsub.CreatedEvent:
+			if msg.Role == message.Assistant {
+				continue
+			}
+		case pubsub.UpdatedEvent:
+			if msg.Role != message.Assistant || !msg.IsFinished() || t.written[msg.ID] {
+				continue
+			}
+			t.written[msg.ID] = true
+		default:
+			continue
+		}
+		if err := t.append(msg); err != nil {
+			logging.Warn("Failed to write the transcript", "session_id", msg.SessionID, "error", err)
+		}
+	}
+}
+
+func (t *Transcripts) append(msg message.Message) error {
+	entry := transcriptEntry(msg)
+	if entry == "" {
+		return nil
+	}
+	if err := os.MkdirAll(t.dir, 0o755); err != nil {
+		return err
+	}
+	f, err := os.OpenFile(filepath.Join(t.dir, msg.SessionID+".md"), os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0o600)
+	if err != nil {
+		return err
+	}
+	if _, err := f.WriteString(entry); err != nil {
+		f.Close()
+		return err
+	}
+	return f.Close()
+}
+
+// transcriptEntry returns the section of the transcript for a message, with
+// the time it was written. Tool results are written under the calls they
+// answer, since the calls were written with the response before them.
+func transcriptEntry(msg message.Message) string {
+	var b strings.Builder
+	at := time.Unix(msg.CreatedAt, 0).Format(time.DateTime)
+	switch msg.Role {
+	case message.User:
+		fmt.Fprintf(&b, "\n## User (%s)\n", at)
+		writeText(&b, msg.Content().String())
+		for _, attachment := range msg.BinaryContent() {
+			fmt.Fprintf(&b, "\nAttached `%s`\n", attachment.Path)
+		}
+	case message.Assistant:
+		text := msg.Content().String()
+		calls := msg.ToolCalls()
+		if strings.TrimSpace(text) == "" && len(calls) == 0 {
+			return ""
+		}
+		fmt.Fprintf(&b, "\n## Assistant (%s)\n", at)
+		writeText(&b, text)
+		for _, call := range calls {
+			fmt.Fprintf(&b, "\n**Tool: %s**\n\n", call.Name)
+			b.WriteString(fence(indentJSON(call.Input), "json"))
+		}
+	case message.Tool:
+		for _, result := range msg.ToolResults() {
+			output := result.Content
+			if result.FullContent != "" {
+				output = result.FullContent
+			}
+			fmt.Fprintf(&b, "\n**Result: %s**\n", result.Name)
+			if result.IsError {
+				b.WriteString("\nError:\n")
+			}
+			b.WriteString("\n")
+			b.WriteString(fence(output, ""))
+		}
+	}
+	return b.String()
+}
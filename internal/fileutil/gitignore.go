@@ -0,0 +1,119 @@
+package fileutil
+
+import (
+	"bufio"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/bmatcuk/doublestar/v4"
+)
+
+type gitignoreRule struct {
+	base     string // directory of the .gitignore file the rule comes from
+	pattern  string
+	negate   bool
+	dirOnly  bool
+	anchored bool
+}
+
+// GitIgnore matches paths against the rules of .gitignore files. It supports
+// comments, negation, directory only patterns, anchored patterns and the **
+// wildcard, which covers the patterns commonly found in projects.
+type GitIgnore struct {
+	rules []gitignoreRule
+}
+
+// NewGitIgnore loads the .gitignore files of dir and of its parent directories
+// up to the root of the git repository containing it.
+func NewGitIgnore(dir string) *GitIgnore {
+	g := &GitIgnore{}
+
+	var dirs []string
+	for current := filepath.Clean(dir); ; {
+		dirs = append(dirs, current)
+		if _, err := os.Stat(filepath.Join(current, ".git")); err == nil {
+			break
+		}
+		parent := filepath.Dir(current)
+		if parent == current {
+			// Not in a git repository, only use the rules of dir itself
+			dirs = dirs[:1]
+			break
+		}
+		current = parent
+	}
+
+	// Rules of deeper directories take precedence, so load them last
+	for i := len(dirs) - 1; i >= 0; i-- {
+		g.AddFile(dirs[i])
+	}
+	return g
+}
+
+// AddFile loads the rules of the .gitignore file in dir, if there is one.
+func (g *GitIgnore) AddFile(dir string) {
+	file, err := os.Open(filepath.Join(dir, ".gitignore"))
+	if err != nil {
+		return
+	}
+	defer file.Close()
+
+	scanner := bufio.NewScanner(file)
+	for scanner.Scan() {
+		line := strings.TrimRight(scanner.Text(), " \t\r")
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+
+		rule := gitignoreRule{base: dir}
+		if strings.HasPrefix(line, "!") {
+			rule.negate = true
+			line = line[1:]
+		}
+		line = strings.TrimPrefix(line, "\\")
+		if strings.HasSuffix(line, "/") {
+			rule.dirOnly = true
+			line = strings.TrimSuffix(line, "/")
+		}
+		if strings.HasPrefix(line, "/") {
+			rule.anchored = true
+			line = strings.TrimPrefix(line, "/")
+		} else if strings.Contains(line, "/") {
+			// A slash in the middle makes the pattern relative to the .gitignore file
+			rule.anchored = true
+		}
+		if line == "" {
+			continue
+		}
+		rule.pattern = line
+		g.rules = append(g.rules, rule)
+	}
+}
+
+// Match reports whether the absolute path is ignored. Like git, the last
+// matching rule wins so negated patterns can re-include paths.
+func (g *GitIgnore) Match(path string, isDir bool) bool {
+	ignored := false
+	for _, rule := range g.rules {
+		if rule.dirOnly && !isDir {
+			continue
+		}
+		rel, err := filepath.Rel(rule.base, path)
+		if err != nil || rel == "." || rel == ".." || strings.HasPrefix(rel, ".."+string(filepath.Separator)) {
+			continue
+		}
+		rel = filepath.ToSlash(rel)
+
+		var matched bool
+		if rule.anchored {
+			matched, _ = doublestar.Match(rule.pattern, rel)
+		} else {
+			matched, _ = doublestar.Match(rule.pattern, filepath.Base(path))
+		}
+		if matched {
+			ignored = !rule.negate
+		}
+	}
+	return ignored
+}
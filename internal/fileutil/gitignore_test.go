@@ -0,0 +1,50 @@
+package fileutil
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestGitIgnore(t *testing.T) {
+	root := t.TempDir()
+	require.NoError(t, os.Mkdir(filepath.Join(root, ".git"), 0o755))
+	require.NoError(t, os.MkdirAll(filepath.Join(root, "sub"), 0o755))
+	require.NoError(t, os.WriteFile(filepath.Join(root, ".gitignore"), []byte(`# build output
+target/
+*.log
+!keep.log
+/root-only.txt
+docs/**/*.tmp
+`), 0o644))
+	require.NoError(t, os.WriteFile(filepath.Join(root, "sub", ".gitignore"), []byte("generated.go\n"), 0o644))
+
+	g := NewGitIgnore(filepath.Join(root, "sub"))
+
+	testCases := []struct {
+		path     string
+		isDir    bool
+		expected bool
+	}{
+		{path: "target", isDir: true, expected: true},
+		{path: "sub/target", isDir: true, expected: true},
+		{path: "target", isDir: false, expected: false},
+		{path: "sub/debug.log", expected: true},
+		{path: "sub/keep.log", expected: false},
+		{path: "root-only.txt", expected: true},
+		{path: "sub/root-only.txt", expected: false},
+		{path: "docs/a/b/file.tmp", expected: true},
+		{path: "sub/generated.go", expected: true},
+		{path: "generated.go", expected: false},
+		{path: "sub/main.go", expected: false},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.path, func(t *testing.T) {
+			assert.Equal(t, tc.expected, g.Match(filepath.Join(root, tc.path), tc.isDir))
+		})
+	}
+}
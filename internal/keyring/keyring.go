@@ -0,0 +1,31 @@
+// Package keyring keeps secrets in the OS keyring: the login keychain on
+// macOS, the Secret Service of Linux desktops through secret-tool, and the
+// Credential Manager on Windows.
+package keyring
+
+import "errors"
+
+// service groups the secrets of cryoncode in the keyring, each one is saved
+// under an account of the service
+const service = "cryoncode"
+
+// ErrNotFound is returned by Get when the keyring has no secret for the
+// account
+var ErrNotFound = errors.New("no secret in the keyring")
+
+// Get returns the secret saved for the account
+func Get(account string) (string, error) {
+	return get(account)
+}
+
+// Set saves the secret for the account, replacing the one saved before. The
+// label is shown by the keyring managers.
+func Set(account, label, secret string) error {
+	return set(account, label, secret)
+}
+
+// Delete removes the secret of the account, it's not an error if there is
+// none
+func Delete(account string) error {
+	return remove(account)
+}
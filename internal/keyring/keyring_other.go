@@ -0,0 +1,82 @@
+//go:build !windows
+
+package keyring
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+func get(account string) (string, error) {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		cmd = exec.Command("security", "find-generic-password", "-s", service, "-a", account, "-w")
+	case "linux", "freebsd", "openbsd", "netbsd":
+		cmd = exec.Command("secret-tool", "lookup", "service", service, "account", account)
+	default:
+		return "", fmt.Errorf("the OS keyring is not supported on %s", runtime.GOOS)
+	}
+	var stderr bytes.Buffer
+	cmd.Stderr = &stderr
+	out, err := cmd.Output()
+	var exitErr *exec.ExitError
+	switch {
+	case errors.As(err, &exitErr) && secretMissing(exitErr.ExitCode(), stderr.String()):
+		return "", ErrNotFound
+	case err != nil:
+		return "", fmt.Errorf("failed to read from the keyring: %w: %s", err, strings.TrimSpace(stderr.String()))
+	}
+	return strings.TrimSpace(string(out)), nil
+}
+
+// secretMissing tells a lookup of a secret that doesn't exist from a keyring
+// that can't be read, which must not lead to a new secret replacing the one
+// in use
+func secretMissing(exitCode int, stderr string) bool {
+	if runtime.GOOS == "darwin" {
+		return exitCode == 44 // errSecItemNotFound
+	}
+	return exitCode == 1 && strings.TrimSpace(stderr) == ""
+}
+
+func set(account, label, secret string) error {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		// Without a value -w asks for the secret, twice, so it isn't in the
+		// arguments any user can read from the process list
+		cmd = exec.Command("security", "add-generic-password", "-U", "-s", service, "-a", account, "-l", label, "-w")
+		cmd.Stdin = strings.NewReader(secret + "\n" + secret + "\n")
+	default:
+		cmd = exec.Command("secret-tool", "store", "--label="+label, "service", service, "account", account)
+		cmd.Stdin = strings.NewReader(secret)
+	}
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to save in the keyring: %w: %s", err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+func remove(account string) error {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		cmd = exec.Command("security", "delete-generic-password", "-s", service, "-a", account)
+	default:
+		cmd = exec.Command("secret-tool", "clear", "service", service, "account", account)
+	}
+	out, err := cmd.CombinedOutput()
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) && secretMissing(exitErr.ExitCode(), "") {
+		return nil
+	}
+	if err != nil {
+		return fmt.Errorf("failed to delete from the keyring: %w: %s", err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
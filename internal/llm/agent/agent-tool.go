@@ -4,6 +4,8 @@ import (
 	"context"
 	"encoding/json"
 	"fmt"
+	"slices"
+	"strings"
 
 	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/llm/tools"
@@ -23,18 +25,26 @@ const (
 )
 
 type AgentParams struct {
-	Prompt string `json:"prompt"`
+	Prompt string   `json:"prompt"`
+	Tools  []string `json:"tools,omitempty"`
 }
 
 func (b *agentTool) Info() tools.ToolInfo {
 	return tools.ToolInfo{
 		Name:        AgentToolName,
-		Description: "Launch a new agent that has access to the following tools: GlobTool, GrepTool, LS, View. When you are searching for a keyword or file and are not confident that you will find the right match on the first try, use the Agent tool to perform the search for you. For example:\n\n- If you are searching for a keyword like \"config\" or \"logger\", or for questions like \"which file does X?\", the Agent tool is strongly recommended\n- If you want to read a specific file path, use the View or GlobTool tool instead of the Agent tool, to find the match more quickly\n- If you are searching for a specific class definition like \"class Foo\", use the GlobTool tool instead, to find the match more quickly\n\nUsage notes:\n1. Launch multiple agents concurrently whenever possible, to maximize performance; to do that, use a single message with multiple tool uses\n2. When the agent is done, it will return a single message back to you. The result returned by the agent is not visible to the user. To show the user the result, you should send a text message back to the user with a concise summary of the result.\n3. Each agent invocation is stateless. You will not be able to send additional messages to the agent, nor will the agent be able to communicate with you outside of its final report. Therefore, your prompt should contain a highly detailed task description for the agent to perform autonomously and you should specify exactly what information the agent should return back to you in its final and only message to you.\n4. The agent's outputs should generally be trusted\n5. IMPORTANT: The agent can not use Bash, Replace, Edit, so can not modify files. If you want to use these tools, use them directly instead of going through the agent.",
+		Description: "Launch a new agent that has access to the following tools: GlobTool, GrepTool, LS, View. When you are searching for a keyword or file and are not confident that you will find the right match on the first try, use the Agent tool to perform the search for you. For example:\n\n- If you are searching for a keyword like \"config\" or \"logger\", or for questions like \"which file does X?\", the Agent tool is strongly recommended\n- If you want to read a specific file path, use the View or GlobTool tool instead of the Agent tool, to find the match more quickly\n- If you are searching for a specific class definition like \"class Foo\", use the GlobTool tool instead, to find the match more quickly\n\nUsage notes:\n1. Launch multiple agents concurrently whenever possible, to maximize performance; to do that, use a single message with multiple tool uses\n2. When the agent is done, it will return a single message back to you. The result returned by the agent is not visible to the user. To show the user the result, you should send a text message back to the user with a concise summary of the result.\n3. Each agent invocation is stateless. You will not be able to send additional messages to the agent, nor will the agent be able to communicate with you outside of its final report. Therefore, your prompt should contain a highly detailed task description for the agent to perform autonomously and you should specify exactly what information the agent should return back to you in its final and only message to you.\n4. The agent's outputs should generally be trusted\n5. IMPORTANT: The agent can not use Bash, Replace, Edit, so can not modify files. If you want to use these tools, use them directly instead of going through the agent.\n6. Pass tools to let the agent use only some of its tools, for instance only grep and view for a search.",
 		Parameters: map[string]any{
 			"prompt": map[string]any{
 				"type":        "string",
 				"description": "The task for the agent to perform",
 			},
+			"tools": map[string]any{
+				"type":        "array",
+				"description": fmt.Sprintf("The tools the agent may use, defaults to all of: %s", strings.Join(toolNames(TaskAgentTools(b.lspClients)), ", ")),
+				"items": map[string]any{
+					"type": "string",
+				},
+			},
 		},
 		Required: []string{"prompt"},
 	}
@@ -49,51 +59,100 @@ func (b *agentTool) Run(ctx context.Context, call tools.ToolCall) (tools.ToolRes
 		return tools.NewTextErrorResponse("prompt is required"), nil
 	}
 
+	agentTools, err := restrictTools(TaskAgentTools(b.lspClients), params.Tools)
+	if err != nil {
+		return tools.NewTextErrorResponse(err.Error()), nil
+	}
+
 	sessionID, messageID := tools.GetContextValues(ctx)
 	if sessionID == "" || messageID == "" {
 		return tools.ToolResponse{}, fmt.Errorf("session_id and message_id are required")
 	}
 
-	agent, err := NewAgent(config.AgentTask, b.sessions, b.messages, TaskAgentTools(b.lspClients))
+	response, err := runSubAgent(ctx, b.sessions, b.messages, call.ID, sessionID, "New Agent Session", params.Prompt, agentTools)
+	if err != nil {
+		return tools.ToolResponse{}, err
+	}
+	if response.Role != message.Assistant {
+		return tools.NewTextErrorResponse("no response"), nil
+	}
+	return tools.NewTextResponse(response.Content().String()), nil
+}
+
+// runSubAgent runs the prompt with a task agent in a child session of the
+// parent session and returns its final message. The cost of the child session
+// is added to the parent session.
+func runSubAgent(
+	ctx context.Context,
+	sessions session.Service,
+	messages message.Service,
+	toolCallID, parentSessionID, title, prompt string,
+	agentTools []tools.BaseTool,
+) (message.Message, error) {
+	agent, err := NewAgent(config.AgentTask, sessions, messages, nil, nil, agentTools)
 	if err != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error creating agent: %s", err)
+		return message.Message{}, fmt.Errorf("error creating agent: %s", err)
 	}
 
-	session, err := b.sessions.CreateTaskSession(ctx, call.ID, sessionID, "New Agent Session")
+	session, err := sessions.CreateTaskSession(ctx, toolCallID, parentSessionID, title)
 	if err != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error creating session: %s", err)
+		return message.Message{}, fmt.Errorf("error creating session: %s", err)
 	}
 
-	done, err := agent.Run(ctx, session.ID, params.Prompt)
+	done, err := agent.Run(ctx, session.ID, prompt)
 	if err != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error generating agent: %s", err)
+		return message.Message{}, fmt.Errorf("error generating agent: %s", err)
 	}
 	result := <-done
 	if result.Error != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error generating agent: %s", result.Error)
+		return message.Message{}, fmt.Errorf("error generating agent: %s", result.Error)
 	}
 
-	response := result.Message
-	if response.Role != message.Assistant {
-		return tools.NewTextErrorResponse("no response"), nil
-	}
-
-	updatedSession, err := b.sessions.Get(ctx, session.ID)
+	updatedSession, err := sessions.Get(ctx, session.ID)
 	if err != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error getting session: %s", err)
+		return message.Message{}, fmt.Errorf("error getting session: %s", err)
 	}
-	parentSession, err := b.sessions.Get(ctx, sessionID)
+	parentSession, err := sessions.Get(ctx, parentSessionID)
 	if err != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error getting parent session: %s", err)
+		return message.Message{}, fmt.Errorf("error getting parent session: %s", err)
 	}
 
 	parentSession.Cost += updatedSession.Cost
 
-	_, err = b.sessions.Save(ctx, parentSession)
+	_, err = sessions.Save(ctx, parentSession)
 	if err != nil {
-		return tools.ToolResponse{}, fmt.Errorf("error saving parent session: %s", err)
+		return message.Message{}, fmt.Errorf("error saving parent session: %s", err)
 	}
-	return tools.NewTextResponse(response.Content().String()), nil
+	return result.Message, nil
+}
+
+// restrictTools returns the tools with the given names, or all of them when no
+// names are given.
+func restrictTools(available []tools.BaseTool, names []string) ([]tools.BaseTool, error) {
+	if len(names) == 0 {
+		return available, nil
+	}
+	var restricted []tools.BaseTool
+	for _, name := range names {
+		i := slices.IndexFunc(available, func(tool tools.BaseTool) bool {
+			return tool.Info().Name == name
+		})
+		if i < 0 {
+			return nil, fmt.Errorf("tool %q is not available to the agent, use any of: %s", name, strings.Join(toolNames(available), ", "))
+		}
+		if !slices.Contains(restricted, available[i]) {
+			restricted = append(restricted, available[i])
+		}
+	}
+	return restricted, nil
+}
+
+func toolNames(agentTools []tools.BaseTool) []string {
+	names := make([]string, 0, len(agentTools))
+	for _, tool := range agentTools {
+		names = append(names, tool.Info().Name)
+	}
+	return names
 }
 
 func NewAgentTool(
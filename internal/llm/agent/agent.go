@@ -4,6 +4,7 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"slices"
 	"strings"
 	"sync"
 	"time"
@@ -18,6 +19,7 @@ import (
 	"github.com/zhenbah/cryoncode/internal/permission"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
 	"github.com/zhenbah/cryoncode/internal/session"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
 )
 
 // Common errors
@@ -50,19 +52,39 @@ type Service interface {
 	Model() models.Model
 	Run(ctx context.Context, sessionID string, content string, attachments ...message.Attachment) (<-chan AgentEvent, error)
 	Cancel(sessionID string)
+	// CancelAll cancels the requests of every session
+	CancelAll()
 	IsSessionBusy(sessionID string) bool
 	IsBusy() bool
 	Update(agentName config.AgentName, modelID models.ModelID) (models.Model, error)
+	// Name returns the agent in use
+	Name() config.AgentName
+	// UseAgent switches to another agent that can run sessions, with its
+	// configured settings
+	UseAgent(agentName config.AgentName) (models.Model, error)
+	// Settings returns the settings the agent runs with
+	Settings() session.Settings
+	// UseSettings switches to the agent and settings recorded for a session,
+	// the configured ones are used for those that weren't recorded
+	UseSettings(settings session.Settings) (models.Model, error)
+	// ReloadConfig registers the tools again and recreates the providers of
+	// the titles and summaries once the config was reloaded
+	ReloadConfig() error
 	Summarize(ctx context.Context, sessionID string) error
 }
 
 type agent struct {
 	*pubsub.Broker[AgentEvent]
-	sessions session.Service
-	messages message.Service
-
-	tools    []tools.BaseTool
+	name        config.AgentName
+	sessions    session.Service
+	messages    message.Service
+	permissions permission.Service
+	snapshots   snapshot.Service
+
+	allTools []tools.BaseTool // the tools the agent's allowlist picks from
+	tools    *tools.Registry
 	provider provider.Provider
+	settings session.Settings // the settings the provider was created with
 
 	titleProvider     provider.Provider
 	summarizeProvider provider.Provider
@@ -74,9 +96,15 @@ func NewAgent(
 	agentName config.AgentName,
 	sessions session.Service,
 	messages message.Service,
+	permissions permission.Service,
+	snapshots snapshot.Service,
 	agentTools []tools.BaseTool,
 ) (Service, error) {
-	agentProvider, err := createAgentProvider(agentName)
+	settings, err := agentSettings(agentName, session.Settings{})
+	if err != nil {
+		return nil, err
+	}
+	agentProvider, err := newAgentProvider(agentName, settings)
 	if err != nil {
 		return nil, err
 	}
@@ -98,10 +126,15 @@ func NewAgent(
 
 	agent := &agent{
 		Broker:            pubsub.NewBroker[AgentEvent](),
+		name:              agentName,
 		provider:          agentProvider,
+		settings:          settings,
 		messages:          messages,
 		sessions:          sessions,
-		tools:             agentTools,
+		permissions:       permissions,
+		snapshots:         snapshots,
+		allTools:          agentTools,
+		tools:             newToolRegistry(agentTools, config.Get().Agents[agentName].Tools),
 		titleProvider:     titleProvider,
 		summarizeProvider: summarizeProvider,
 		activeRequests:    sync.Map{},
@@ -132,6 +165,13 @@ func (a *agent) Cancel(sessionID string) {
 	}
 }
 
+func (a *agent) CancelAll() {
+	a.activeRequests.Range(func(key, _ interface{}) bool {
+		a.Cancel(key.(string))
+		return true
+	})
+}
+
 func (a *agent) IsBusy() bool {
 	busy := false
 	a.activeRequests.Range(func(key, value interface{}) bool {
@@ -158,10 +198,14 @@ func (a *agent) generateTitle(ctx context.Context, sessionID string, content str
 	if a.titleProvider == nil {
 		return nil
 	}
-	session, err := a.sessions.Get(ctx, sessionID)
+	sess, err := a.sessions.Get(ctx, sessionID)
 	if err != nil {
 		return err
 	}
+	if sess.Title != session.DefaultTitle {
+		// Keep titles given by the user
+		return nil
+	}
 	ctx = context.WithValue(ctx, tools.SessionIDContextKey, sessionID)
 	parts := []message.ContentPart{message.TextContent{Text: content}}
 	response, err := a.titleProvider.SendMessages(
@@ -183,8 +227,8 @@ func (a *agent) generateTitle(ctx context.Context, sessionID string, content str
 		return nil
 	}
 
-	session.Title = title
-	_, err = a.sessions.Save(ctx, session)
+	sess.Title = title
+	_, err = a.sessions.Save(ctx, sess)
 	return err
 }
 
@@ -197,7 +241,10 @@ func (a *agent) err(err error) AgentEvent {
 
 func (a *agent) Run(ctx context.Context, sessionID string, content string, attachments ...message.Attachment) (<-chan AgentEvent, error) {
 	if !a.provider.Model().SupportsAttachments && attachments != nil {
-		attachments = nil
+		// Text files are sent as text, so every model can take them
+		attachments = slices.DeleteFunc(attachments, func(attachment message.Attachment) bool {
+			return !strings.HasPrefix(attachment.MimeType, "text/")
+		})
 	}
 	events := make(chan AgentEvent)
 	if a.IsSessionBusy(sessionID) {
@@ -252,17 +299,12 @@ func (a *agent) processGeneration(ctx context.Context, sessionID, content string
 	if err != nil {
 		return a.err(fmt.Errorf("failed to get session: %w", err))
 	}
-	if session.SummaryMessageID != "" {
-		summaryMsgInex := -1
-		for i, msg := range msgs {
-			if msg.ID == session.SummaryMessageID {
-				summaryMsgInex = i
-				break
-			}
-		}
-		if summaryMsgInex != -1 {
-			msgs = msgs[summaryMsgInex:]
-			msgs[0].Role = message.User
+	msgs = historyAfterSummary(msgs, session.SummaryMessageID, session.KeptMessageID)
+	if session.Model == "" {
+		// Record the settings so the session carries on with them when it's
+		// opened again, whatever the config says by then
+		if _, err := a.sessions.SetSettings(ctx, sessionID, a.settings); err != nil {
+			return a.err(fmt.Errorf("failed to save the session settings: %w", err))
 		}
 	}
 
@@ -270,6 +312,14 @@ func (a *agent) processGeneration(ctx context.Context, sessionID, content string
 	if err != nil {
 		return a.err(fmt.Errorf("failed to create user message: %w", err))
 	}
+	if a.snapshots != nil {
+		// A turn that failed or was cancelled may have changed files too
+		defer func() {
+			if err := a.snapshots.Checkpoint(context.Background(), sessionID, userMsg.ID); err != nil {
+				logging.ErrorPersist(fmt.Sprintf("failed to record checkpoint: %v", err))
+			}
+		}()
+	}
 	// Append the new user message to the conversation history.
 	msgHistory := append(msgs, userMsg)
 
@@ -321,7 +371,7 @@ func (a *agent) createUserMessage(ctx context.Context, sessionID, content string
 
 func (a *agent) streamAndHandleEvents(ctx context.Context, sessionID string, msgHistory []message.Message) (message.Message, *message.Message, error) {
 	ctx = context.WithValue(ctx, tools.SessionIDContextKey, sessionID)
-	eventChan := a.provider.StreamResponse(ctx, msgHistory, a.tools)
+	eventChan := a.provider.StreamResponse(ctx, msgHistory, a.tools.Tools())
 
 	assistantMsg, err := a.messages.Create(ctx, sessionID, message.CreateMessageParams{
 		Role:  message.Assistant,
@@ -364,22 +414,10 @@ func (a *agent) streamAndHandleEvents(ctx context.Context, sessionID string, msg
 			goto out
 		default:
 			// Continue processing
-			var tool tools.BaseTool
-			for _, availableTool := range a.tools {
-				if availableTool.Info().Name == toolCall.Name {
-					tool = availableTool
-					break
-				}
-				// Monkey patch for Copilot Sonnet-4 tool repetition obfuscation
-				// if strings.HasPrefix(toolCall.Name, availableTool.Info().Name) &&
-				// 	strings.HasPrefix(toolCall.Name, availableTool.Info().Name+availableTool.Info().Name) {
-				// 	tool = availableTool
-				// 	break
-				// }
-			}
+			tool, ok := a.tools.Get(toolCall.Name)
 
 			// Tool not found
-			if tool == nil {
+			if !ok {
 				toolResults[i] = message.ToolResult{
 					ToolCallID: toolCall.ID,
 					Content:    fmt.Sprintf("Tool not found: %s", toolCall.Name),
@@ -387,7 +425,15 @@ func (a *agent) streamAndHandleEvents(ctx context.Context, sessionID string, msg
 				}
 				continue
 			}
-			toolResult, toolErr := tool.Run(ctx, tools.ToolCall{
+			if deniedByPolicy(toolCall.Name, toolCall.Input) {
+				toolResults[i] = message.ToolResult{
+					ToolCallID: toolCall.ID,
+					Content:    "Permission denied by the permissions config",
+					IsError:    true,
+				}
+				continue
+			}
+			toolResult, toolErr := a.runTool(ctx, sessionID, tool, tools.ToolCall{
 				ID:    toolCall.ID,
 				Name:  toolCall.Name,
 				Input: toolCall.Input,
@@ -416,6 +462,16 @@ func (a *agent) streamAndHandleEvents(ctx context.Context, sessionID string, msg
 				Metadata:   toolResult.Metadata,
 				IsError:    toolResult.IsError,
 			}
+			// view reads a window of the file already and tells the model
+			// where to go on, cutting out its middle would lose lines
+			if toolCall.Name == tools.ViewToolName {
+				continue
+			}
+			toolsCfg := config.Get().Tools
+			if truncated, ok := tools.TruncateForModel(toolResult.Content, toolsCfg.MaxOutputBytes, toolsCfg.MaxOutputLines); ok {
+				toolResults[i].Content = truncated
+				toolResults[i].FullContent = toolResult.Content
+			}
 		}
 	}
 out:
@@ -437,6 +493,52 @@ out:
 	return assistantMsg, &msg, err
 }
 
+// runTool runs a tool call and enforces the timeout configured for the tool.
+// Time spent waiting for the user to answer a permission request doesn't count
+// towards the timeout.
+func (a *agent) runTool(ctx context.Context, sessionID string, tool tools.BaseTool, call tools.ToolCall) (tools.ToolResponse, error) {
+	timeout := config.ToolTimeout(call.Name)
+	if timeout <= 0 {
+		return tool.Run(ctx, call)
+	}
+
+	toolCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	type toolRunResult struct {
+		response tools.ToolResponse
+		err      error
+	}
+	resultCh := make(chan toolRunResult, 1)
+	go func() {
+		response, err := tool.Run(toolCtx, call)
+		resultCh <- toolRunResult{response: response, err: err}
+	}()
+
+	ticker := time.NewTicker(100 * time.Millisecond)
+	defer ticker.Stop()
+
+	var elapsed time.Duration
+	last := time.Now()
+	for {
+		select {
+		case result := <-resultCh:
+			return result.response, result.err
+		case <-ctx.Done():
+			return tools.NewTextErrorResponse("Tool execution canceled by user"), nil
+		case now := <-ticker.C:
+			if a.permissions == nil || !a.permissions.HasPendingRequest(sessionID) {
+				elapsed += now.Sub(last)
+			}
+			last = now
+			if elapsed >= timeout {
+				logging.Warn("Tool execution timed out", "tool", call.Name, "timeout", timeout)
+				return tools.NewTextErrorResponse(fmt.Sprintf("Tool %s timed out after %s and was stopped. Try a narrower operation or a shorter command.", call.Name, timeout)), nil
+			}
+		}
+	}
+}
+
 func (a *agent) finishMessage(ctx context.Context, msg *message.Message, finishReson message.FinishReason) {
 	msg.AddFinish(finishReson)
 	_ = a.messages.Update(ctx, *msg)
@@ -522,16 +624,96 @@ func (a *agent) Update(agentName config.AgentName, modelID models.ModelID) (mode
 		return models.Model{}, fmt.Errorf("failed to update config: %w", err)
 	}
 
-	provider, err := createAgentProvider(agentName)
+	settings, err := agentSettings(agentName, session.Settings{})
+	if err != nil {
+		return models.Model{}, err
+	}
+	provider, err := newAgentProvider(agentName, settings)
 	if err != nil {
 		return models.Model{}, fmt.Errorf("failed to create provider for model %s: %w", modelID, err)
 	}
 
 	a.provider = provider
+	a.settings = settings
+
+	return a.provider.Model(), nil
+}
+
+func (a *agent) Settings() session.Settings {
+	return a.settings
+}
+
+func (a *agent) Name() config.AgentName {
+	return a.name
+}
+
+func (a *agent) UseAgent(agentName config.AgentName) (models.Model, error) {
+	if !config.IsSessionAgent(agentName) {
+		return models.Model{}, fmt.Errorf("unknown agent %s", agentName)
+	}
+	return a.UseSettings(session.Settings{Agent: string(agentName)})
+}
+
+func (a *agent) UseSettings(recorded session.Settings) (models.Model, error) {
+	agentName := a.name
+	switch {
+	case recorded.Agent != "":
+		agentName = config.AgentName(recorded.Agent)
+	case recorded.Model != "":
+		// Sessions run by the coder don't record it
+		agentName = config.AgentCoder
+	}
+	if !config.IsSessionAgent(agentName) {
+		return models.Model{}, fmt.Errorf("agent %s is not defined", agentName)
+	}
+	settings, err := agentSettings(agentName, recorded)
+	if err != nil {
+		return models.Model{}, err
+	}
+	if agentName == a.name && settings == a.settings {
+		return a.provider.Model(), nil
+	}
+	if a.IsBusy() {
+		return models.Model{}, fmt.Errorf("cannot change model while processing requests")
+	}
+
+	provider, err := newAgentProvider(agentName, settings)
+	if err != nil {
+		return models.Model{}, fmt.Errorf("failed to create provider for model %s: %w", settings.Model, err)
+	}
+
+	if agentName != a.name {
+		a.name = agentName
+		a.tools = newToolRegistry(a.allTools, config.Get().Agents[agentName].Tools)
+	}
+	a.provider = provider
+	a.settings = settings
 
 	return a.provider.Model(), nil
 }
 
+func (a *agent) ReloadConfig() error {
+	if a.IsBusy() {
+		return fmt.Errorf("cannot reload the tools while processing requests")
+	}
+	if a.titleProvider != nil {
+		titleProvider, err := createAgentProvider(config.AgentTitle)
+		if err != nil {
+			return err
+		}
+		a.titleProvider = titleProvider
+	}
+	if a.summarizeProvider != nil {
+		summarizeProvider, err := createAgentProvider(config.AgentSummarizer)
+		if err != nil {
+			return err
+		}
+		a.summarizeProvider = summarizeProvider
+	}
+	a.tools = newToolRegistry(a.allTools, config.Get().Agents[a.name].Tools)
+	return nil
+}
+
 func (a *agent) Summarize(ctx context.Context, sessionID string) error {
 	if a.summarizeProvider == nil {
 		return fmt.Errorf("summarize provider not available")
@@ -557,6 +739,16 @@ func (a *agent) Summarize(ctx context.Context, sessionID string) error {
 		}
 
 		a.Publish(pubsub.CreatedEvent, event)
+		current, err := a.sessions.Get(summarizeCtx, sessionID)
+		if err != nil {
+			event = AgentEvent{
+				Type:  AgentEventTypeError,
+				Error: fmt.Errorf("failed to get session: %w", err),
+				Done:  true,
+			}
+			a.Publish(pubsub.CreatedEvent, event)
+			return
+		}
 		// Get all messages from the session
 		msgs, err := a.messages.List(summarizeCtx, sessionID)
 		if err != nil {
@@ -586,6 +778,16 @@ func (a *agent) Summarize(ctx context.Context, sessionID string) error {
 		}
 		a.Publish(pubsub.CreatedEvent, event)
 
+		// The last turns are sent as they are after the summary, the rest of
+		// what the model sees now is summarized
+		history := historyAfterSummary(msgs, current.SummaryMessageID, current.KeptMessageID)
+		keep := keptTurnsStart(history, config.Get().Compaction.KeepTurns)
+		keptMessageID := ""
+		if keep < len(history) {
+			keptMessageID = history[keep].ID
+		}
+		msgs = history[:keep]
+
 		// Add a system message to guide the summarization
 		summarizePrompt := "Provide a detailed but concise summary of our conversation above. Focus on information that would be helpful for continuing the conversation, including what we did, what we're doing, which files we're working on, and what we're going to do next."
 
@@ -633,7 +835,7 @@ func (a *agent) Summarize(ctx context.Context, sessionID string) error {
 		}
 		event = AgentEvent{
 			Type:     AgentEventTypeSummarize,
-			Progress: "Creating new session...",
+			Progress: "Saving summary...",
 		}
 
 		a.Publish(pubsub.CreatedEvent, event)
@@ -671,6 +873,7 @@ func (a *agent) Summarize(ctx context.Context, sessionID string) error {
 			return
 		}
 		oldSession.SummaryMessageID = msg.ID
+		oldSession.KeptMessageID = keptMessageID
 		oldSession.CompletionTokens = response.Usage.OutputTokens
 		oldSession.PromptTokens = 0
 		model := a.summarizeProvider.Model()
@@ -704,41 +907,79 @@ func (a *agent) Summarize(ctx context.Context, sessionID string) error {
 }
 
 func createAgentProvider(agentName config.AgentName) (provider.Provider, error) {
-	cfg := config.Get()
-	agentConfig, ok := cfg.Agents[agentName]
+	settings, err := agentSettings(agentName, session.Settings{})
+	if err != nil {
+		return nil, err
+	}
+	return newAgentProvider(agentName, settings)
+}
+
+// agentSettings returns the settings recorded for a session, with the ones
+// that weren't recorded taken from the config of the agent
+func agentSettings(agentName config.AgentName, recorded session.Settings) (session.Settings, error) {
+	agentConfig, ok := config.Get().Agents[agentName]
 	if !ok {
-		return nil, fmt.Errorf("agent %s not found", agentName)
+		return session.Settings{}, fmt.Errorf("agent %s not found", agentName)
+	}
+	settings := recorded
+	if settings.Model == "" {
+		settings.Model = agentConfig.Model
+	}
+	model, ok := models.SupportedModels[settings.Model]
+	if !ok {
+		return session.Settings{}, fmt.Errorf("model %s not supported", settings.Model)
+	}
+	if settings.MaxTokens == 0 {
+		settings.MaxTokens = model.DefaultMaxTokens
+		// The configured max tokens are meant for the configured model
+		if agentConfig.MaxTokens > 0 && settings.Model == agentConfig.Model {
+			settings.MaxTokens = agentConfig.MaxTokens
+		}
 	}
-	model, ok := models.SupportedModels[agentConfig.Model]
+	if settings.ReasoningEffort == "" {
+		settings.ReasoningEffort = agentConfig.ReasoningEffort
+	}
+	if settings.SystemPrompt == "" {
+		settings.SystemPrompt = prompt.GetAgentPrompt(agentName, model.Provider)
+	}
+	settings.Agent = ""
+	if agentName != config.AgentCoder && config.IsSessionAgent(agentName) {
+		settings.Agent = string(agentName)
+	}
+	return settings, nil
+}
+
+func newAgentProvider(agentName config.AgentName, settings session.Settings) (provider.Provider, error) {
+	model, ok := models.SupportedModels[settings.Model]
 	if !ok {
-		return nil, fmt.Errorf("model %s not supported", agentConfig.Model)
+		return nil, fmt.Errorf("model %s not supported", settings.Model)
 	}
 
-	providerCfg, ok := cfg.Providers[model.Provider]
+	agentCfg := config.Get().Agents[agentName]
+	providerCfg, ok := config.Get().Providers[model.Provider]
 	if !ok {
 		return nil, fmt.Errorf("provider %s not supported", model.Provider)
 	}
 	if providerCfg.Disabled {
 		return nil, fmt.Errorf("provider %s is not enabled", model.Provider)
 	}
-	maxTokens := model.DefaultMaxTokens
-	if agentConfig.MaxTokens > 0 {
-		maxTokens = agentConfig.MaxTokens
-	}
 	opts := []provider.ProviderClientOption{
 		provider.WithAPIKey(providerCfg.APIKey),
 		provider.WithModel(model),
-		provider.WithSystemMessage(prompt.GetAgentPrompt(agentName, model.Provider)),
-		provider.WithMaxTokens(maxTokens),
+		provider.WithSystemMessage(settings.SystemPrompt),
+		provider.WithMaxTokens(settings.MaxTokens),
+	}
+	if agentCfg.Temperature != nil {
+		opts = append(opts, provider.WithTemperature(*agentCfg.Temperature))
 	}
 	if model.Provider == models.ProviderOpenAI || model.Provider == models.ProviderLocal && model.CanReason {
 		opts = append(
 			opts,
 			provider.WithOpenAIOptions(
-				provider.WithReasoningEffort(agentConfig.ReasoningEffort),
+				provider.WithReasoningEffort(settings.ReasoningEffort),
 			),
 		)
-	} else if model.Provider == models.ProviderAnthropic && model.CanReason && agentName == config.AgentCoder {
+	} else if model.Provider == models.ProviderAnthropic && model.CanReason && config.IsSessionAgent(agentName) {
 		opts = append(
 			opts,
 			provider.WithAnthropicOptions(
@@ -756,3 +997,36 @@ func createAgentProvider(agentName config.AgentName) (provider.Provider, error)
 
 	return agentProvider, nil
 }
+
+// historyAfterSummary returns the messages sent to the model once the session
+// was summarized: the summary, as a prompt, followed by the turns left out of
+// it and the messages sent since. Without a summary all the messages are sent.
+func historyAfterSummary(msgs []message.Message, summaryID, keptID string) []message.Message {
+	summaryIdx := slices.IndexFunc(msgs, func(msg message.Message) bool { return msg.ID == summaryID })
+	if summaryID == "" || summaryIdx < 0 {
+		return msgs
+	}
+	summary := msgs[summaryIdx]
+	summary.Role = message.User
+	history := []message.Message{summary}
+	keptIdx := slices.IndexFunc(msgs, func(msg message.Message) bool { return msg.ID == keptID })
+	if keptID != "" && keptIdx >= 0 && keptIdx < summaryIdx {
+		history = append(history, msgs[keptIdx:summaryIdx]...)
+	}
+	return append(history, msgs[summaryIdx+1:]...)
+}
+
+// keptTurnsStart returns where the last turns that are kept out of a summary
+// of the history start, each turn starting with a prompt. The first message
+// is always summarized, so fewer turns are kept when there aren't enough.
+func keptTurnsStart(history []message.Message, turns int) int {
+	start := len(history)
+	kept := 0
+	for i := len(history) - 1; i > 0 && kept < turns; i-- {
+		if history[i].Role == message.User {
+			start = i
+			kept++
+		}
+	}
+	return start
+}
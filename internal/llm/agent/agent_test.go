@@ -0,0 +1,84 @@
+package agent
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/llm/tools"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+func ids(msgs []message.Message) []string {
+	out := make([]string, len(msgs))
+	for i, msg := range msgs {
+		out[i] = msg.ID
+	}
+	return out
+}
+
+func TestHistoryAfterSummary(t *testing.T) {
+	msgs := []message.Message{
+		{ID: "1", Role: message.User},
+		{ID: "2", Role: message.Assistant},
+		{ID: "3", Role: message.User},
+		{ID: "4", Role: message.Assistant},
+		{ID: "summary", Role: message.Assistant},
+		{ID: "5", Role: message.User},
+	}
+
+	assert.Equal(t, ids(msgs), ids(historyAfterSummary(msgs, "", "")))
+	assert.Equal(t, []string{"summary", "5"}, ids(historyAfterSummary(msgs, "summary", "")))
+	assert.Equal(t, []string{"summary", "3", "4", "5"}, ids(historyAfterSummary(msgs, "summary", "3")))
+	assert.Equal(t, ids(msgs), ids(historyAfterSummary(msgs, "deleted", "3")))
+
+	history := historyAfterSummary(msgs, "summary", "3")
+	assert.Equal(t, message.User, history[0].Role)
+	assert.Equal(t, message.Assistant, msgs[4].Role)
+}
+
+func TestKeptTurnsStart(t *testing.T) {
+	history := []message.Message{
+		{ID: "1", Role: message.User},
+		{ID: "2", Role: message.Assistant},
+		{ID: "3", Role: message.User},
+		{ID: "4", Role: message.Assistant},
+		{ID: "5", Role: message.Tool},
+		{ID: "6", Role: message.User},
+		{ID: "7", Role: message.Assistant},
+	}
+
+	assert.Equal(t, 7, keptTurnsStart(history, 0))
+	assert.Equal(t, 5, keptTurnsStart(history, 1))
+	assert.Equal(t, 2, keptTurnsStart(history, 2))
+	assert.Equal(t, 2, keptTurnsStart(history, 5))
+	assert.Equal(t, 0, keptTurnsStart(nil, 2))
+}
+
+func TestNewToolRegistryAllowlist(t *testing.T) {
+	agentTools := []tools.BaseTool{tools.NewGlobTool(), tools.NewGrepTool(), tools.NewLsTool()}
+	names := func(registry *tools.Registry) []string {
+		var out []string
+		for _, tool := range registry.Tools() {
+			out = append(out, tool.Info().Name)
+		}
+		return out
+	}
+
+	assert.ElementsMatch(t, []string{tools.GlobToolName, tools.GrepToolName, tools.LSToolName}, names(newToolRegistry(agentTools, nil)))
+	assert.ElementsMatch(t, []string{tools.GlobToolName, tools.LSToolName}, names(newToolRegistry(agentTools, []string{tools.LSToolName, tools.GlobToolName, "missing"})))
+}
+
+func TestRestrictTools(t *testing.T) {
+	agentTools := []tools.BaseTool{tools.NewGlobTool(), tools.NewGrepTool(), tools.NewLsTool()}
+
+	restricted, err := restrictTools(agentTools, nil)
+	assert.NoError(t, err)
+	assert.Equal(t, toolNames(agentTools), toolNames(restricted))
+
+	restricted, err = restrictTools(agentTools, []string{tools.GrepToolName, tools.GlobToolName, tools.GrepToolName})
+	assert.NoError(t, err)
+	assert.Equal(t, []string{tools.GrepToolName, tools.GlobToolName}, toolNames(restricted))
+
+	_, err = restrictTools(agentTools, []string{tools.BashToolName})
+	assert.ErrorContains(t, err, "not available to the agent")
+}
@@ -3,7 +3,13 @@ package agent
 import (
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
+	"maps"
+	"slices"
+	"strings"
+	"sync"
+	"time"
 
 	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/llm/tools"
@@ -29,6 +35,7 @@ type MCPClient interface {
 	) (*mcp.InitializeResult, error)
 	ListTools(ctx context.Context, request mcp.ListToolsRequest) (*mcp.ListToolsResult, error)
 	CallTool(ctx context.Context, request mcp.CallToolRequest) (*mcp.CallToolResult, error)
+	Ping(ctx context.Context) error
 	Close() error
 }
 
@@ -45,42 +52,188 @@ func (b *mcpTool) Info() tools.ToolInfo {
 	}
 }
 
-func runTool(ctx context.Context, c MCPClient, toolName string, input string) (tools.ToolResponse, error) {
-	defer c.Close()
+// mcpPingTimeout is how long a server has to answer a ping after a failed
+// call before it is considered gone and restarted
+const mcpPingTimeout = 5 * time.Second
+
+var errMcpServerClosed = errors.New("mcp server closed")
+
+// mcpServer is the client of a configured server. Stdio servers are started
+// once, by the first call that needs them, and reused for every tool call
+// instead of spawning a new process each time.
+type mcpServer struct {
+	start  sync.Once
+	mu     sync.Mutex
+	closed bool
+	client MCPClient
+	err    error
+}
+
+// mcpServers holds the servers started or being started. The mutex only
+// guards the map, a server starting doesn't hold up the calls to the others.
+var (
+	mcpServers      = make(map[string]*mcpServer)
+	mcpServersMutex sync.Mutex
+)
+
+// newMcpClient starts the client of the server. The event stream of an SSE
+// client lasts as long as the context it's started with, so it gets one that
+// outlives the call starting it and is only stopped by Close.
+func newMcpClient(m config.MCPServer) (MCPClient, error) {
+	switch m.Type {
+	case config.MCPStdio:
+		return client.NewStdioMCPClient(
+			m.Command,
+			m.Env.List(),
+			m.Args...,
+		)
+	case config.MCPSse:
+		c, err := client.NewSSEMCPClient(
+			m.URL,
+			client.WithHeaders(m.Headers),
+		)
+		if err != nil {
+			return nil, err
+		}
+		if err := c.Start(context.Background()); err != nil {
+			c.Close()
+			return nil, err
+		}
+		return c, nil
+	}
+	return nil, fmt.Errorf("invalid mcp type: %s", m.Type)
+}
+
+// startMcpClient starts the server and performs the initialize handshake, the
+// context only bounds the handshake
+func startMcpClient(ctx context.Context, m config.MCPServer) (MCPClient, error) {
+	c, err := newMcpClient(m)
+	if err != nil {
+		return nil, fmt.Errorf("error creating mcp client: %w", err)
+	}
+
 	initRequest := mcp.InitializeRequest{}
 	initRequest.Params.ProtocolVersion = mcp.LATEST_PROTOCOL_VERSION
 	initRequest.Params.ClientInfo = mcp.Implementation{
 		Name:    "Cryoncode",
 		Version: version.Version,
 	}
+	if _, err := c.Initialize(ctx, initRequest); err != nil {
+		c.Close()
+		return nil, fmt.Errorf("error initializing mcp client: %w", err)
+	}
+	return c, nil
+}
 
-	_, err := c.Initialize(ctx, initRequest)
-	if err != nil {
-		return tools.NewTextErrorResponse(err.Error()), nil
+// getMcpServer returns the running server, starting it if needed. The calls
+// made while it starts wait for it, a server that failed to start is started
+// again by the next call.
+func getMcpServer(ctx context.Context, name string, m config.MCPServer) (*mcpServer, error) {
+	mcpServersMutex.Lock()
+	server, ok := mcpServers[name]
+	if !ok {
+		server = &mcpServer{}
+		mcpServers[name] = server
+	}
+	mcpServersMutex.Unlock()
+
+	server.start.Do(func() {
+		c, err := startMcpClient(ctx, m)
+		server.mu.Lock()
+		defer server.mu.Unlock()
+		// Closed while it was starting
+		if err == nil && server.closed {
+			c.Close()
+			c, err = nil, errMcpServerClosed
+		}
+		server.client, server.err = c, err
+	})
+	if server.err != nil {
+		stopMcpServer(name, server)
+		return nil, server.err
+	}
+	return server, nil
+}
+
+// close stops the server. A server still starting is stopped once it has.
+func (s *mcpServer) close() error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.closed {
+		return nil
+	}
+	s.closed = true
+	if s.client == nil {
+		return nil
+	}
+	return s.client.Close()
+}
+
+// stopMcpServer stops the server, it is started again on the next call. A
+// server that already replaced it is left running.
+func stopMcpServer(name string, server *mcpServer) {
+	mcpServersMutex.Lock()
+	if mcpServers[name] == server {
+		delete(mcpServers, name)
+	}
+	mcpServersMutex.Unlock()
+
+	if err := server.close(); err != nil {
+		logging.Error("error closing mcp client", "name", name, "error", err)
+	}
+}
+
+// mcpServerAlive reports whether the server still answers. A failed call to a
+// server that does was refused by the server, it doesn't need a restart.
+func mcpServerAlive(c MCPClient) bool {
+	ctx, cancel := context.WithTimeout(context.Background(), mcpPingTimeout)
+	defer cancel()
+	return c.Ping(ctx) == nil
+}
+
+// CloseMcpClients stops all running MCP servers and forgets their tools, so
+// they are listed again by the next call to GetMcpTools.
+func CloseMcpClients() {
+	resetMcpTools()
+
+	mcpServersMutex.Lock()
+	servers := mcpServers
+	mcpServers = make(map[string]*mcpServer)
+	mcpServersMutex.Unlock()
+
+	for name, server := range servers {
+		if err := server.close(); err != nil {
+			logging.Error("error closing mcp client", "name", name, "error", err)
+		}
 	}
+}
 
+func runTool(ctx context.Context, c MCPClient, toolName string, input string) (tools.ToolResponse, error) {
 	toolRequest := mcp.CallToolRequest{}
 	toolRequest.Params.Name = toolName
 	var args map[string]any
-	if err = json.Unmarshal([]byte(input), &args); err != nil {
+	if err := json.Unmarshal([]byte(input), &args); err != nil {
 		return tools.NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
 	}
 	toolRequest.Params.Arguments = args
 	result, err := c.CallTool(ctx, toolRequest)
 	if err != nil {
-		return tools.NewTextErrorResponse(err.Error()), nil
+		return tools.ToolResponse{}, err
 	}
 
-	output := ""
+	output := make([]string, 0, len(result.Content))
 	for _, v := range result.Content {
 		if v, ok := v.(mcp.TextContent); ok {
-			output = v.Text
+			output = append(output, v.Text)
 		} else {
-			output = fmt.Sprintf("%v", v)
+			output = append(output, fmt.Sprintf("%v", v))
 		}
 	}
 
-	return tools.NewTextResponse(output), nil
+	if result.IsError {
+		return tools.NewTextErrorResponse(strings.Join(output, "\n")), nil
+	}
+	return tools.NewTextResponse(strings.Join(output, "\n")), nil
 }
 
 func (b *mcpTool) Run(ctx context.Context, params tools.ToolCall) (tools.ToolResponse, error) {
@@ -100,32 +253,24 @@ func (b *mcpTool) Run(ctx context.Context, params tools.ToolCall) (tools.ToolRes
 		},
 	)
 	if !p {
-		return tools.NewTextErrorResponse("permission denied"), nil
+		return tools.ToolResponse{}, permission.ErrorPermissionDenied
 	}
 
-	switch b.mcpConfig.Type {
-	case config.MCPStdio:
-		c, err := client.NewStdioMCPClient(
-			b.mcpConfig.Command,
-			b.mcpConfig.Env,
-			b.mcpConfig.Args...,
-		)
-		if err != nil {
-			return tools.NewTextErrorResponse(err.Error()), nil
-		}
-		return runTool(ctx, c, b.tool.Name, params.Input)
-	case config.MCPSse:
-		c, err := client.NewSSEMCPClient(
-			b.mcpConfig.URL,
-			client.WithHeaders(b.mcpConfig.Headers),
-		)
-		if err != nil {
-			return tools.NewTextErrorResponse(err.Error()), nil
+	server, err := getMcpServer(ctx, b.mcpName, b.mcpConfig)
+	if err != nil {
+		return tools.NewTextErrorResponse(err.Error()), nil
+	}
+	response, err := runTool(ctx, server.client, b.tool.Name, params.Input)
+	if err != nil {
+		// The server may have exited or the connection broken, it is only
+		// restarted on the next call when it doesn't answer anymore
+		if ctx.Err() == nil && !mcpServerAlive(server.client) {
+			logging.Error("mcp server stopped responding, restarting it", "name", b.mcpName, "error", err)
+			stopMcpServer(b.mcpName, server)
 		}
-		return runTool(ctx, c, b.tool.Name, params.Input)
+		return tools.NewTextErrorResponse(err.Error()), nil
 	}
-
-	return tools.NewTextErrorResponse("invalid mcp type"), nil
+	return response, nil
 }
 
 func NewMcpTool(name string, tool mcp.Tool, permissions permission.Service, mcpConfig config.MCPServer) tools.BaseTool {
@@ -137,65 +282,65 @@ func NewMcpTool(name string, tool mcp.Tool, permissions permission.Service, mcpC
 	}
 }
 
-var mcpTools []tools.BaseTool
-
-func getTools(ctx context.Context, name string, m config.MCPServer, permissions permission.Service, c MCPClient) []tools.BaseTool {
-	var stdioTools []tools.BaseTool
-	initRequest := mcp.InitializeRequest{}
-	initRequest.Params.ProtocolVersion = mcp.LATEST_PROTOCOL_VERSION
-	initRequest.Params.ClientInfo = mcp.Implementation{
-		Name:    "Cryoncode",
-		Version: version.Version,
-	}
+// mcpTools are the tools of each server, listed the first time the server is
+// started. The servers that failed to start are tried again by the next call
+// to GetMcpTools.
+var (
+	mcpTools      = make(map[string][]tools.BaseTool)
+	mcpToolsMutex sync.Mutex
+)
 
-	_, err := c.Initialize(ctx, initRequest)
+func getTools(ctx context.Context, name string, m config.MCPServer, permissions permission.Service) ([]tools.BaseTool, error) {
+	server, err := getMcpServer(ctx, name, m)
 	if err != nil {
-		logging.Error("error initializing mcp client", "error", err)
-		return stdioTools
+		return nil, fmt.Errorf("failed to start MCP server %s: %w", name, err)
 	}
 	toolsRequest := mcp.ListToolsRequest{}
-	tools, err := c.ListTools(ctx, toolsRequest)
+	result, err := server.client.ListTools(ctx, toolsRequest)
 	if err != nil {
-		logging.Error("error listing tools", "error", err)
-		return stdioTools
+		stopMcpServer(name, server)
+		return nil, fmt.Errorf("failed to list the tools of MCP server %s: %w", name, err)
 	}
-	for _, t := range tools.Tools {
-		stdioTools = append(stdioTools, NewMcpTool(name, t, permissions, m))
+	serverTools := make([]tools.BaseTool, 0, len(result.Tools))
+	for _, t := range result.Tools {
+		serverTools = append(serverTools, NewMcpTool(name, t, permissions, m))
 	}
-	defer c.Close()
-	return stdioTools
+	return serverTools, nil
 }
 
+// GetMcpTools returns the tools of the enabled servers, starting the ones
+// whose tools aren't known yet
 func GetMcpTools(ctx context.Context, permissions permission.Service) []tools.BaseTool {
-	if len(mcpTools) > 0 {
-		return mcpTools
-	}
-	for name, m := range config.Get().MCPServers {
-		switch m.Type {
-		case config.MCPStdio:
-			c, err := client.NewStdioMCPClient(
-				m.Command,
-				m.Env,
-				m.Args...,
-			)
-			if err != nil {
-				logging.Error("error creating mcp client", "error", err)
-				continue
-			}
-
-			mcpTools = append(mcpTools, getTools(ctx, name, m, permissions, c)...)
-		case config.MCPSse:
-			c, err := client.NewSSEMCPClient(
-				m.URL,
-				client.WithHeaders(m.Headers),
-			)
+	servers := config.Get().MCPServers
+	var allTools []tools.BaseTool
+	for _, name := range slices.Sorted(maps.Keys(servers)) {
+		m := servers[name]
+		if !m.IsEnabled() {
+			continue
+		}
+		mcpToolsMutex.Lock()
+		serverTools, ok := mcpTools[name]
+		mcpToolsMutex.Unlock()
+		if !ok {
+			var err error
+			serverTools, err = getTools(ctx, name, m, permissions)
 			if err != nil {
-				logging.Error("error creating mcp client", "error", err)
+				logging.ErrorPersist(err.Error())
 				continue
 			}
-			mcpTools = append(mcpTools, getTools(ctx, name, m, permissions, c)...)
+			mcpToolsMutex.Lock()
+			mcpTools[name] = serverTools
+			mcpToolsMutex.Unlock()
 		}
+		allTools = append(allTools, serverTools...)
 	}
+	return allTools
+}
+
+// resetMcpTools forgets the listed tools
+func resetMcpTools() {
+	mcpToolsMutex.Lock()
+	defer mcpToolsMutex.Unlock()
 
-	return mcpTools
+	clear(mcpTools)
 }
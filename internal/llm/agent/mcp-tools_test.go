@@ -0,0 +1,91 @@
+package agent
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/mark3labs/mcp-go/mcp"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools"
+)
+
+type fakeMcpClient struct {
+	MCPClient
+	callErr error
+	pingErr error
+	closed  bool
+}
+
+func (c *fakeMcpClient) CallTool(context.Context, mcp.CallToolRequest) (*mcp.CallToolResult, error) {
+	return nil, c.callErr
+}
+
+func (c *fakeMcpClient) Ping(context.Context) error {
+	return c.pingErr
+}
+
+func (c *fakeMcpClient) Close() error {
+	c.closed = true
+	return nil
+}
+
+// runningMcpServer registers a started server with the client
+func runningMcpServer(t *testing.T, name string, c MCPClient) *mcpServer {
+	server := &mcpServer{client: c}
+	server.start.Do(func() {})
+	mcpServersMutex.Lock()
+	mcpServers[name] = server
+	mcpServersMutex.Unlock()
+	t.Cleanup(CloseMcpClients)
+	return server
+}
+
+func TestMcpToolRestart(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+
+	ctx := context.WithValue(context.Background(), tools.SessionIDContextKey, "session")
+	ctx = context.WithValue(ctx, tools.MessageIDContextKey, "message")
+	tool := NewMcpTool("server", mcp.Tool{Name: "search"}, allowAll{}, config.MCPServer{})
+
+	// An error returned by a server that still answers is the error of the
+	// call, the server keeps running
+	refused := &fakeMcpClient{callErr: errors.New("invalid params")}
+	server := runningMcpServer(t, "server", refused)
+	response, err := tool.Run(ctx, tools.ToolCall{Input: `{}`})
+	require.NoError(t, err)
+	assert.True(t, response.IsError)
+	assert.False(t, refused.closed)
+	assert.Same(t, server, mcpServers["server"])
+
+	// A server that doesn't answer anymore is stopped and started again by
+	// the next call
+	gone := &fakeMcpClient{callErr: errors.New("broken pipe"), pingErr: errors.New("broken pipe")}
+	runningMcpServer(t, "server", gone)
+	response, err = tool.Run(ctx, tools.ToolCall{Input: `{}`})
+	require.NoError(t, err)
+	assert.True(t, response.IsError)
+	assert.True(t, gone.closed)
+	assert.NotContains(t, mcpServers, "server")
+}
+
+func TestCloseMcpClients(t *testing.T) {
+	c := &fakeMcpClient{}
+	runningMcpServer(t, "server", c)
+	mcpToolsMutex.Lock()
+	mcpTools["server"] = []tools.BaseTool{NewMcpTool("server", mcp.Tool{Name: "search"}, allowAll{}, config.MCPServer{})}
+	mcpToolsMutex.Unlock()
+
+	CloseMcpClients()
+	assert.True(t, c.closed)
+	assert.Empty(t, mcpServers)
+	// The tools are listed again when the servers are started again
+	assert.Empty(t, mcpTools)
+}
@@ -0,0 +1,273 @@
+package agent
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"sync"
+
+	"github.com/tetratelabs/wazero"
+	"github.com/tetratelabs/wazero/api"
+	"github.com/tetratelabs/wazero/imports/wasi_snapshot_preview1"
+	"github.com/tetratelabs/wazero/sys"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+// PluginManifest describes the tools of a WASM plugin. It is read from a JSON
+// file next to the module with the same base name, e.g. jq.wasm and jq.json.
+type PluginManifest struct {
+	Tools []PluginToolManifest `json:"tools"`
+}
+
+// PluginToolManifest describes a tool of a plugin and the function of the
+// module it calls.
+type PluginToolManifest struct {
+	Name        string         `json:"name"`
+	Description string         `json:"description"`
+	Parameters  map[string]any `json:"parameters"`
+	Required    []string       `json:"required"`
+	// Export is the function called with the tool input. Without it the
+	// module is run as a WASI command with the input on stdin.
+	Export string `json:"export,omitempty"`
+}
+
+// wasmPageSize is the size of a page of WebAssembly memory
+const wasmPageSize = 64 * 1024
+
+// plugin is a compiled module, instantiated again for every call so calls
+// don't share any state
+type plugin struct {
+	path     string
+	runtime  wazero.Runtime
+	compiled wazero.CompiledModule
+}
+
+type pluginTool struct {
+	manifest    PluginToolManifest
+	plugin      *plugin
+	permissions permission.Service
+}
+
+func (p *pluginTool) Info() tools.ToolInfo {
+	required := p.manifest.Required
+	if required == nil {
+		required = make([]string, 0)
+	}
+	return tools.ToolInfo{
+		Name:        p.manifest.Name,
+		Description: p.manifest.Description,
+		Parameters:  p.manifest.Parameters,
+		Required:    required,
+	}
+}
+
+// Run calls the function of the module with the tool input and returns its
+// output to the model. The module runs in the embedded runtime, without access
+// to the file system or the network.
+func (p *pluginTool) Run(ctx context.Context, params tools.ToolCall) (tools.ToolResponse, error) {
+	sessionID, messageID := tools.GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return tools.ToolResponse{}, fmt.Errorf("session ID and message ID are required for running a plugin")
+	}
+	export := p.manifest.Export
+	if export == "" {
+		export = "_start"
+	}
+	ok := p.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        config.WorkingDirectory(),
+			ToolName:    p.manifest.Name,
+			Action:      "execute",
+			Description: fmt.Sprintf("execute plugin %s with the following parameters: %s", p.manifest.Name, params.Input),
+			Command:     filepath.Base(p.plugin.path) + " " + export,
+			Params:      params.Input,
+		},
+	)
+	if !ok {
+		return tools.ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	var stderr bytes.Buffer
+	output, err := p.plugin.call(ctx, p.manifest.Export, params.Input, &stderr)
+	if err != nil {
+		if ctx.Err() != nil {
+			return tools.ToolResponse{}, ctx.Err()
+		}
+		return tools.NewTextErrorResponse(fmt.Sprintf("plugin %s failed: %s\n%s", p.manifest.Name, err, strings.TrimSpace(stderr.String()))), nil
+	}
+	return tools.NewTextResponse(output), nil
+}
+
+// call runs the export with the input. An export gets the input in the memory
+// of the module, at the address returned by its alloc export, as a pointer and
+// a length, and returns the address of its output in the high 32 bits of the
+// result and its length in the low ones. Without an export the module is run
+// as a WASI command, reading the input on stdin and writing to stdout.
+func (p *plugin) call(ctx context.Context, export, input string, stderr *bytes.Buffer) (string, error) {
+	var stdout bytes.Buffer
+	moduleConfig := wazero.NewModuleConfig().
+		WithName("").
+		WithArgs(filepath.Base(p.path)).
+		WithStdout(&stdout).
+		WithStderr(stderr)
+
+	if export == "" {
+		mod, err := p.runtime.InstantiateModule(ctx, p.compiled, moduleConfig.WithStdin(strings.NewReader(input)))
+		if mod != nil {
+			defer mod.Close(ctx)
+		}
+		// A command exits with proc_exit, which is only a failure with
+		// another code than 0
+		var exitErr *sys.ExitError
+		if err != nil && (!errors.As(err, &exitErr) || exitErr.ExitCode() != 0) {
+			return "", err
+		}
+		return stdout.String(), nil
+	}
+
+	mod, err := p.runtime.InstantiateModule(ctx, p.compiled, moduleConfig.WithStartFunctions("_initialize"))
+	if err != nil {
+		return "", err
+	}
+	defer mod.Close(ctx)
+
+	results, err := mod.ExportedFunction("alloc").Call(ctx, uint64(len(input)))
+	if err != nil {
+		return "", fmt.Errorf("alloc failed: %w", err)
+	}
+	ptr := uint32(results[0])
+	if !mod.Memory().Write(ptr, []byte(input)) {
+		return "", fmt.Errorf("alloc returned %d, out of the memory of the module", ptr)
+	}
+	results, err = mod.ExportedFunction(export).Call(ctx, uint64(ptr), uint64(len(input)))
+	if err != nil {
+		return "", err
+	}
+	output, ok := mod.Memory().Read(uint32(results[0]>>32), uint32(results[0]))
+	if !ok {
+		return "", fmt.Errorf("%s returned an output out of the memory of the module", export)
+	}
+	// Copied, the memory is released with the module
+	return string(output), nil
+}
+
+// checkExports reports why the module can't run the tool, if it can't
+func (p *plugin) checkExports(manifest PluginToolManifest) error {
+	functions := p.compiled.ExportedFunctions()
+	if manifest.Export == "" {
+		if _, ok := functions["_start"]; !ok {
+			return fmt.Errorf("the module exports no _start function to run as a command, set export in the manifest")
+		}
+		return nil
+	}
+	if len(p.compiled.ExportedMemories()) == 0 {
+		return fmt.Errorf("the module exports no memory")
+	}
+	alloc, ok := functions["alloc"]
+	if !ok || !slices.Equal(alloc.ParamTypes(), []api.ValueType{api.ValueTypeI32}) || !slices.Equal(alloc.ResultTypes(), []api.ValueType{api.ValueTypeI32}) {
+		return fmt.Errorf("the module exports no alloc function taking and returning an i32")
+	}
+	function, ok := functions[manifest.Export]
+	if !ok {
+		return fmt.Errorf("the module exports no %s function", manifest.Export)
+	}
+	if !slices.Equal(function.ParamTypes(), []api.ValueType{api.ValueTypeI32, api.ValueTypeI32}) || !slices.Equal(function.ResultTypes(), []api.ValueType{api.ValueTypeI64}) {
+		return fmt.Errorf("%s must take a pointer and a length as i32 and return an i64", manifest.Export)
+	}
+	return nil
+}
+
+var (
+	pluginRuntimeOnce sync.Once
+	pluginRuntime     wazero.Runtime
+)
+
+// getPluginRuntime returns the runtime the plugins share. The modules get the
+// WASI functions, but no directory and no environment variables.
+func getPluginRuntime() wazero.Runtime {
+	pluginRuntimeOnce.Do(func() {
+		pluginRuntime = newPluginRuntime(context.Background(), config.Get().Plugins.MaxMemoryMB)
+	})
+	return pluginRuntime
+}
+
+func newPluginRuntime(ctx context.Context, maxMemoryMB int) wazero.Runtime {
+	runtimeConfig := wazero.NewRuntimeConfig().WithCloseOnContextDone(true)
+	if maxMemoryMB > 0 {
+		runtimeConfig = runtimeConfig.WithMemoryLimitPages(uint32(maxMemoryMB * 1024 * 1024 / wasmPageSize))
+	}
+	runtime := wazero.NewRuntimeWithConfig(ctx, runtimeConfig)
+	wasi_snapshot_preview1.MustInstantiate(ctx, runtime)
+	return runtime
+}
+
+// GetPluginTools discovers the WASM plugins in the plugins directory of the
+// data directory.
+func GetPluginTools(permissions permission.Service) []tools.BaseTool {
+	pluginsDir := filepath.Join(config.Get().Data.Directory, "plugins")
+	modules, err := filepath.Glob(filepath.Join(pluginsDir, "*.wasm"))
+	if err != nil || len(modules) == 0 {
+		return nil
+	}
+	return loadPluginTools(context.Background(), getPluginRuntime(), modules, permissions)
+}
+
+func loadPluginTools(ctx context.Context, runtime wazero.Runtime, modules []string, permissions permission.Service) []tools.BaseTool {
+	var pluginTools []tools.BaseTool
+	for _, modulePath := range modules {
+		manifestPath := strings.TrimSuffix(modulePath, ".wasm") + ".json"
+		data, err := os.ReadFile(manifestPath)
+		if err != nil {
+			logging.Warn("skipping plugin without manifest", "plugin", modulePath, "error", err)
+			continue
+		}
+		var manifest PluginManifest
+		if err := json.Unmarshal(data, &manifest); err != nil {
+			logging.Warn("skipping plugin with invalid manifest", "plugin", modulePath, "error", err)
+			continue
+		}
+		code, err := os.ReadFile(modulePath)
+		if err != nil {
+			logging.Warn("skipping unreadable plugin", "plugin", modulePath, "error", err)
+			continue
+		}
+		compiled, err := runtime.CompileModule(ctx, code)
+		if err != nil {
+			logging.Warn("skipping invalid plugin", "plugin", modulePath, "error", err)
+			continue
+		}
+		p := &plugin{path: modulePath, runtime: runtime, compiled: compiled}
+
+		for _, toolManifest := range manifest.Tools {
+			if toolManifest.Name == "" {
+				toolManifest.Name = toolManifest.Export
+			}
+			if toolManifest.Name == "" {
+				toolManifest.Name = strings.TrimSuffix(filepath.Base(modulePath), ".wasm")
+			}
+			if err := p.checkExports(toolManifest); err != nil {
+				logging.Warn("skipping plugin tool", "plugin", modulePath, "tool", toolManifest.Name, "error", err)
+				continue
+			}
+			if toolManifest.Parameters == nil {
+				toolManifest.Parameters = make(map[string]any)
+			}
+			pluginTools = append(pluginTools, &pluginTool{
+				manifest:    toolManifest,
+				plugin:      p,
+				permissions: permissions,
+			})
+		}
+	}
+	return pluginTools
+}
@@ -0,0 +1,79 @@
+package agent
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+// stripModule exports a strip function returning its input without the first
+// and the last byte:
+//
+//	(module
+//	  (memory (export "memory") 1)
+//	  (func (export "alloc") (param i32) (result i32)
+//	    i32.const 1024)
+//	  (func (export "strip") (param i32 i32) (result i64)
+//	    local.get 0  i32.const 1  i32.add  i64.extend_i32_u
+//	    i64.const 32  i64.shl
+//	    local.get 1  i32.const 2  i32.sub  i64.extend_i32_u
+//	    i64.or))
+var stripModule = []byte{
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x0c, 0x02, 0x60,
+	0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7e, 0x03, 0x03,
+	0x02, 0x00, 0x01, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x1a, 0x03, 0x06,
+	0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x05, 0x61, 0x6c, 0x6c,
+	0x6f, 0x63, 0x00, 0x00, 0x05, 0x73, 0x74, 0x72, 0x69, 0x70, 0x00, 0x01,
+	0x0a, 0x1a, 0x02, 0x05, 0x00, 0x41, 0x80, 0x08, 0x0b, 0x12, 0x00, 0x20,
+	0x00, 0x41, 0x01, 0x6a, 0xad, 0x42, 0x20, 0x86, 0x20, 0x01, 0x41, 0x02,
+	0x6b, 0xad, 0x84, 0x0b,
+}
+
+type allowAll struct {
+	permission.Service
+}
+
+func (allowAll) Request(permission.CreatePermissionRequest) bool {
+	return true
+}
+
+func TestPluginTools(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+
+	dir := t.TempDir()
+	module := filepath.Join(dir, "text.wasm")
+	require.NoError(t, os.WriteFile(module, stripModule, 0o644))
+	require.NoError(t, os.WriteFile(filepath.Join(dir, "text.json"), []byte(`{"tools": [
+		{"name": "strip", "export": "strip", "description": "Strips the braces", "parameters": {"text": {"type": "string"}}},
+		{"name": "missing", "export": "missing"},
+		{"name": "command"}
+	]}`), 0o644))
+
+	ctx := context.Background()
+	runtime := newPluginRuntime(ctx, 1)
+	defer runtime.Close(ctx)
+
+	// The tools whose function the module doesn't export are skipped
+	pluginTools := loadPluginTools(ctx, runtime, []string{module}, allowAll{})
+	require.Len(t, pluginTools, 1)
+	assert.Equal(t, "strip", pluginTools[0].Info().Name)
+
+	ctx = context.WithValue(ctx, tools.SessionIDContextKey, "session")
+	ctx = context.WithValue(ctx, tools.MessageIDContextKey, "message")
+	response, err := pluginTools[0].Run(ctx, tools.ToolCall{Input: `{"text":"hi"}`})
+	require.NoError(t, err)
+	assert.False(t, response.IsError)
+	assert.Equal(t, `"text":"hi"`, response.Content)
+}
@@ -1,14 +1,23 @@
 package agent
 
 import (
+	"cmp"
 	"context"
+	"encoding/json"
+	"io/fs"
+	"path/filepath"
+	"slices"
 
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/diff"
 	"github.com/zhenbah/cryoncode/internal/history"
 	"github.com/zhenbah/cryoncode/internal/llm/tools"
+	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/permission"
 	"github.com/zhenbah/cryoncode/internal/session"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
 )
 
 func CoderAgentTools(
@@ -16,36 +25,167 @@ func CoderAgentTools(
 	sessions session.Service,
 	messages message.Service,
 	history history.Service,
+	snapshots snapshot.Service,
 	lspClients map[string]*lsp.Client,
 ) []tools.BaseTool {
 	ctx := context.Background()
 	otherTools := GetMcpTools(ctx, permissions)
-	if len(lspClients) > 0 {
+	otherTools = append(otherTools, GetPluginTools(permissions)...)
+	otherTools = append(otherTools, customTools(permissions)...)
+	if hasLSPConfigured() {
 		otherTools = append(otherTools, tools.NewDiagnosticsTool(lspClients))
 	}
+	if config.Get().WebSearch.Provider != "" {
+		otherTools = append(otherTools, tools.NewWebSearchTool(permissions))
+	}
 	return append(
 		[]tools.BaseTool{
 			tools.NewBashTool(permissions),
-			tools.NewEditTool(lspClients, permissions, history),
+			tools.NewShellSessionTool(permissions),
+			tools.NewEditTool(lspClients, permissions, history, snapshots),
 			tools.NewFetchTool(permissions),
+			tools.NewDownloadTool(permissions, snapshots),
+			tools.NewDocsTool(permissions),
+			tools.NewEnvInfoTool(permissions),
 			tools.NewGlobTool(),
 			tools.NewGrepTool(),
 			tools.NewLsTool(),
 			tools.NewSourcegraphTool(),
+			tools.NewSymbolsTool(lspClients),
 			tools.NewViewTool(lspClients),
-			tools.NewPatchTool(lspClients, permissions, history),
-			tools.NewWriteTool(lspClients, permissions, history),
+			tools.NewPatchTool(lspClients, permissions, history, snapshots),
+			tools.NewWriteTool(lspClients, permissions, history, snapshots),
+			tools.NewMultiEditTool(lspClients, permissions, history, snapshots),
+			tools.NewNotebookReadTool(),
+			tools.NewNotebookEditTool(permissions, history, snapshots),
+			tools.NewMkdirTool(permissions),
+			tools.NewRmTool(permissions, history, snapshots),
+			tools.NewMvTool(permissions, snapshots),
+			tools.NewCpTool(permissions, snapshots),
+			tools.NewRunTestsTool(permissions),
+			tools.NewTodoTool(sessions),
+			tools.NewCopyToClipboardTool(permissions),
 			NewAgentTool(sessions, messages, lspClients),
 		}, otherTools...,
 	)
 }
 
 func TaskAgentTools(lspClients map[string]*lsp.Client) []tools.BaseTool {
-	return []tools.BaseTool{
+	taskTools := []tools.BaseTool{
 		tools.NewGlobTool(),
 		tools.NewGrepTool(),
 		tools.NewLsTool(),
 		tools.NewSourcegraphTool(),
+		tools.NewSymbolsTool(lspClients),
 		tools.NewViewTool(lspClients),
+		tools.NewNotebookReadTool(),
+	}
+	if hasLSPConfigured() {
+		taskTools = append(taskTools, tools.NewDiagnosticsTool(lspClients))
+	}
+	return taskTools
+}
+
+// customTools creates the command tools defined in the config.
+func customTools(permissions permission.Service) []tools.BaseTool {
+	var custom []tools.BaseTool
+	for _, toolCfg := range config.Get().Tools.Custom {
+		if toolCfg.Name == "" || toolCfg.Command == "" {
+			logging.Warn("skipping custom tool without a name or command", "name", toolCfg.Name)
+			continue
+		}
+		custom = append(custom, tools.NewCustomTool(toolCfg, permissions))
+	}
+	return custom
+}
+
+// hasLSPConfigured reports whether any language server is enabled in the config.
+// LSP clients are started in the background, so the clients map is usually still
+// empty when the tools are built and can't be used to decide this.
+func hasLSPConfigured() bool {
+	for _, lspCfg := range config.Get().LSP {
+		if !lspCfg.Disabled {
+			return true
+		}
+	}
+	return false
+}
+
+// newToolRegistry registers the tools of an agent, leaving out the tools
+// disabled in the config so they are never offered to the model, and the ones
+// the agent isn't allowed to use when its config lists them.
+func newToolRegistry(agentTools []tools.BaseTool, allowed []string) *tools.Registry {
+	registry := tools.NewRegistry()
+	for _, tool := range agentTools {
+		name := tool.Info().Name
+		if config.ToolDisabled(name) || len(allowed) > 0 && !slices.Contains(allowed, name) {
+			continue
+		}
+		if err := registry.Register(tool); err != nil {
+			logging.Warn("skipping tool", "error", err)
+		}
+	}
+	for _, name := range allowed {
+		if _, ok := registry.Get(name); !ok && !config.ToolDisabled(name) {
+			logging.Warn("unknown tool allowed for the agent", "tool", name)
+		}
+	}
+	return registry
+}
+
+// deniedByPolicy reports whether the permissions config denies a tool call.
+// It's checked before running any tool, so deny rules also apply to the tools
+// that never ask for permission, like view or grep. Every path of the call is
+// checked, the tools scanning a directory leave the denied paths out of their
+// results.
+func deniedByPolicy(toolName, input string) bool {
+	var params struct {
+		FilePath    string `json:"file_path"`
+		Path        string `json:"path"`
+		Source      string `json:"source"`
+		Destination string `json:"destination"`
+		PatchText   string `json:"patch_text"`
+		Command     string `json:"command"`
+	}
+	_ = json.Unmarshal([]byte(input), &params)
+	if permission.Decide(toolName, "", params.Command) == permission.Deny {
+		return true
+	}
+	paths := []string{params.FilePath, params.Path, params.Source, params.Destination}
+	if params.PatchText != "" {
+		paths = append(paths, diff.IdentifyFilesNeeded(params.PatchText)...)
+		paths = append(paths, diff.IdentifyFilesAdded(params.PatchText)...)
+	}
+	for _, path := range paths {
+		if path != "" && permission.Decide(toolName, path, "") == permission.Deny {
+			return true
+		}
+	}
+	// A directory moved, copied or removed takes the denied paths inside it
+	// along
+	switch toolName {
+	case tools.MvToolName, tools.CpToolName, tools.RmToolName:
+		return containsDenied(toolName, cmp.Or(params.Source, params.Path))
+	}
+	return false
+}
+
+// containsDenied reports whether the permissions config denies the tool a
+// path inside the directory
+func containsDenied(toolName, dir string) bool {
+	if dir == "" || !permission.DeniesWithin(toolName, dir) {
+		return false
+	}
+	if !filepath.IsAbs(dir) {
+		dir = filepath.Join(config.WorkingDirectory(), dir)
 	}
+	denied := false
+	_ = filepath.WalkDir(dir, func(path string, _ fs.DirEntry, err error) error {
+		if err == nil && permission.Decide(toolName, path, "") == permission.Deny {
+			denied = true
+			return filepath.SkipAll
+		}
+		return nil
+	})
+	return denied
 }
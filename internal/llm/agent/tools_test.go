@@ -0,0 +1,44 @@
+package agent
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+func TestDeniedByPolicy(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	project := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(project, ".cryoncode.json"), []byte(`{"permissions": {"deny": ["*(secrets/**)", "bash(rm -rf *)"]}}`), 0o600))
+	require.NoError(t, os.MkdirAll(filepath.Join(project, "data", "secrets"), 0o755))
+	require.NoError(t, os.MkdirAll(filepath.Join(project, "secrets"), 0o755))
+	_, err := config.Load(project, false)
+	require.NoError(t, err)
+
+	assert.True(t, deniedByPolicy("view", `{"file_path": "secrets/key.pem"}`))
+	assert.True(t, deniedByPolicy("bash", `{"command": "make && rm -rf /"}`))
+	assert.False(t, deniedByPolicy("view", `{"file_path": "README.md"}`))
+
+	t.Run("checks both paths of a move or a copy", func(t *testing.T) {
+		assert.True(t, deniedByPolicy("mv", `{"source": "secrets/key.pem", "destination": "key.pem"}`))
+		assert.True(t, deniedByPolicy("cp", `{"source": "key.pem", "destination": "secrets/key.pem"}`))
+		assert.False(t, deniedByPolicy("cp", `{"source": "key.pem", "destination": "backup/key.pem"}`))
+	})
+
+	t.Run("checks the files of a patch", func(t *testing.T) {
+		assert.True(t, deniedByPolicy("patch", `{"patch_text": "*** Begin Patch\n*** Add File: secrets/key.pem\n+key\n*** End Patch"}`))
+	})
+
+	t.Run("checks what a moved or removed directory contains", func(t *testing.T) {
+		assert.True(t, deniedByPolicy("rm", `{"path": ".", "recursive": true}`))
+		assert.True(t, deniedByPolicy("mv", `{"source": ".", "destination": "../elsewhere"}`))
+		assert.False(t, deniedByPolicy("rm", `{"path": "data", "recursive": true}`))
+	})
+}
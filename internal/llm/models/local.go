@@ -11,7 +11,6 @@ import (
 	"unicode"
 
 	"github.com/zhenbah/cryoncode/internal/logging"
-	"github.com/spf13/viper"
 )
 
 const (
@@ -21,6 +20,10 @@ const (
 	lmStudioBetaModelsPath = "api/v0/models"
 )
 
+// LocalDefaultModel is the model found at LOCAL_ENDPOINT the agents use when
+// no other provider is configured, the first loaded one or else the first one
+var LocalDefaultModel ModelID
+
 func init() {
 	if endpoint := os.Getenv("LOCAL_ENDPOINT"); endpoint != "" {
 		localEndpoint, err := url.Parse(endpoint)
@@ -52,7 +55,6 @@ func init() {
 
 		loadLocalModels(models)
 
-		viper.SetDefault("providers.local.apiKey", "dummy")
 		ProviderPopularity[ProviderLocal] = 0
 	}
 }
@@ -129,10 +131,7 @@ func loadLocalModels(models []localModel) {
 		SupportedModels[model.ID] = model
 
 		if i == 0 || m.State == "loaded" {
-			viper.SetDefault("agents.coder.model", model.ID)
-			viper.SetDefault("agents.summarizer.model", model.ID)
-			viper.SetDefault("agents.task.model", model.ID)
-			viper.SetDefault("agents.title.model", model.ID)
+			LocalDefaultModel = model.ID
 		}
 	}
 }
@@ -26,8 +26,11 @@ func GetAgentPrompt(agentName config.AgentName, provider models.ModelProvider) s
 	default:
 		basePrompt = "You are a helpful assistant"
 	}
+	if agentCfg, ok := config.Get().Agents[agentName]; ok && agentCfg.Prompt != "" {
+		basePrompt = agentCfg.Prompt
+	}
 
-	if agentName == config.AgentCoder || agentName == config.AgentTask {
+	if agentName == config.AgentTask || config.IsSessionAgent(agentName) {
 		// Add context from project-specific instruction files if they exist
 		contextContent := getContextFromPaths()
 		logging.Debug("Context content", "Context", contextContent)
@@ -38,23 +41,23 @@ func GetAgentPrompt(agentName config.AgentName, provider models.ModelProvider) s
 	return basePrompt
 }
 
-var (
-	onceContext    sync.Once
-	contextContent string
-)
-
+// getContextFromPaths reads the context files of the root of the project,
+// then the ones of the working directory when it's below the root. They're
+// read each time a prompt is built, so a file written by /init is used by
+// the next sessions.
 func getContextFromPaths() string {
-	onceContext.Do(func() {
-		var (
-			cfg          = config.Get()
-			workDir      = cfg.WorkingDir
-			contextPaths = cfg.ContextPaths
-		)
-
-		contextContent = processContextPaths(workDir, contextPaths)
-	})
-
-	return contextContent
+	cfg := config.Get()
+	dirs := []string{config.ProjectRoot()}
+	if cfg.WorkingDir != dirs[0] {
+		dirs = append(dirs, cfg.WorkingDir)
+	}
+	var contexts []string
+	for _, dir := range dirs {
+		if context := processContextPaths(dir, cfg.ContextPaths); context != "" {
+			contexts = append(contexts, context)
+		}
+	}
+	return strings.Join(contexts, "\n")
 }
 
 func processContextPaths(workDir string, paths []string) string {
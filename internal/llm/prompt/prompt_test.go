@@ -4,6 +4,7 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"strings"
 	"testing"
 
 	"github.com/zhenbah/cryoncode/internal/config"
@@ -19,12 +20,14 @@ func TestGetContextFromPaths(t *testing.T) {
 	if err != nil {
 		t.Fatalf("Failed to load config: %v", err)
 	}
-	cfg := config.Get()
-	cfg.WorkingDir = tmpDir
-	cfg.ContextPaths = []string{
-		"file.txt",
-		"directory/",
-	}
+	require.NoError(t, config.Update(func(cfg *config.Config) error {
+		cfg.WorkingDir = tmpDir
+		cfg.ContextPaths = []string{
+			"file.txt",
+			"directory/",
+		}
+		return nil
+	}))
 	testFiles := []string{
 		"file.txt",
 		"directory/file_a.txt",
@@ -39,6 +42,26 @@ func TestGetContextFromPaths(t *testing.T) {
 	assert.Equal(t, expectedContext, context)
 }
 
+func TestGetContextFromProjectRoot(t *testing.T) {
+	root := t.TempDir()
+	createTestFiles(t, root, []string{".git/", "AGENTS.md", "internal/AGENTS.md", "internal/OPENCODE.md"})
+	_, err := config.Load(root, false)
+	require.NoError(t, err)
+	require.NoError(t, config.Update(func(cfg *config.Config) error {
+		cfg.WorkingDir = filepath.Join(root, "internal")
+		cfg.ContextPaths = []string{"AGENTS.md", "OPENCODE.md"}
+		return nil
+	}))
+
+	context := getContextFromPaths()
+	rootContext := fmt.Sprintf("# From:%s/AGENTS.md\nAGENTS.md: test content", root)
+	assert.Contains(t, context, rootContext)
+	assert.Contains(t, context, fmt.Sprintf("# From:%s/internal/OPENCODE.md\ninternal/OPENCODE.md: test content", root))
+	// The files of the working directory come after the ones of the root
+	assert.True(t, strings.HasPrefix(context, rootContext))
+	assert.Contains(t, context, "internal/AGENTS.md: test content")
+}
+
 func createTestFiles(t *testing.T, tmpDir string, testFiles []string) {
 	t.Helper()
 	for _, path := range testFiles {
@@ -74,6 +74,10 @@ func (a *anthropicClient) convertMessages(messages []message.Message) (anthropic
 			var contentBlocks []anthropic.ContentBlockParamUnion
 			contentBlocks = append(contentBlocks, content)
 			for _, binaryContent := range msg.BinaryContent() {
+				if binaryContent.IsText() {
+					contentBlocks = append(contentBlocks, anthropic.NewTextBlock(binaryContent.Text()))
+					continue
+				}
 				base64Image := binaryContent.String(models.ProviderAnthropic)
 				imageBlock := anthropic.NewImageBlockBase64(binaryContent.MIMEType, base64Image)
 				contentBlocks = append(contentBlocks, imageBlock)
@@ -165,6 +169,9 @@ func (a *anthropicClient) preparedMessages(messages []anthropic.MessageParam, to
 	isUser := lastMessage.Role == anthropic.MessageParamRoleUser
 	messageContent := ""
 	temperature := anthropic.Float(0)
+	if a.providerOptions.temperature != nil {
+		temperature = anthropic.Float(*a.providerOptions.temperature)
+	}
 	if isUser {
 		for _, m := range lastMessage.Content {
 			if m.OfText != nil && m.OfText.Text != "" {
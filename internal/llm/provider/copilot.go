@@ -198,6 +198,11 @@ func (c *copilotClient) convertMessages(messages []message.Message) (copilotMess
 			content = append(content, openai.ChatCompletionContentPartUnionParam{OfText: &textBlock})
 
 			for _, binaryContent := range msg.BinaryContent() {
+				if binaryContent.IsText() {
+					fileBlock := openai.ChatCompletionContentPartTextParam{Text: binaryContent.Text()}
+					content = append(content, openai.ChatCompletionContentPartUnionParam{OfText: &fileBlock})
+					continue
+				}
 				imageURL := openai.ChatCompletionContentPartImageImageURLParam{URL: binaryContent.String(models.ProviderCopilot)}
 				imageBlock := openai.ChatCompletionContentPartImageParam{ImageURL: imageURL}
 				content = append(content, openai.ChatCompletionContentPartUnionParam{OfImageURL: &imageBlock})
@@ -301,6 +306,10 @@ func (c *copilotClient) preparedParams(messages []openai.ChatCompletionMessagePa
 		}
 	} else {
 		params.MaxTokens = openai.Int(c.providerOptions.maxTokens)
+		// Reasoning models only take their default temperature
+		if c.providerOptions.temperature != nil {
+			params.Temperature = openai.Float(*c.providerOptions.temperature)
+		}
 	}
 
 	return params
@@ -58,6 +58,10 @@ func (g *geminiClient) convertMessages(messages []message.Message) []*genai.Cont
 			var parts []*genai.Part
 			parts = append(parts, &genai.Part{Text: msg.Content().String()})
 			for _, binaryContent := range msg.BinaryContent() {
+				if binaryContent.IsText() {
+					parts = append(parts, &genai.Part{Text: binaryContent.Text()})
+					continue
+				}
 				imageFormat := strings.Split(binaryContent.MIMEType, "/")
 				parts = append(parts, &genai.Part{InlineData: &genai.Blob{
 					MIMEType: imageFormat[1],
@@ -183,6 +187,10 @@ func (g *geminiClient) send(ctx context.Context, messages []message.Message, too
 			Parts: []*genai.Part{{Text: g.providerOptions.systemMessage}},
 		},
 	}
+	if g.providerOptions.temperature != nil {
+		temperature := float32(*g.providerOptions.temperature)
+		config.Temperature = &temperature
+	}
 	if len(tools) > 0 {
 		config.Tools = g.convertTools(tools)
 	}
@@ -271,6 +279,10 @@ func (g *geminiClient) stream(ctx context.Context, messages []message.Message, t
 			Parts: []*genai.Part{{Text: g.providerOptions.systemMessage}},
 		},
 	}
+	if g.providerOptions.temperature != nil {
+		temperature := float32(*g.providerOptions.temperature)
+		config.Temperature = &temperature
+	}
 	if len(tools) > 0 {
 		config.Tools = g.convertTools(tools)
 	}
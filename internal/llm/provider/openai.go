@@ -76,6 +76,11 @@ func (o *openaiClient) convertMessages(messages []message.Message) (openaiMessag
 			textBlock := openai.ChatCompletionContentPartTextParam{Text: msg.Content().String()}
 			content = append(content, openai.ChatCompletionContentPartUnionParam{OfText: &textBlock})
 			for _, binaryContent := range msg.BinaryContent() {
+				if binaryContent.IsText() {
+					fileBlock := openai.ChatCompletionContentPartTextParam{Text: binaryContent.Text()}
+					content = append(content, openai.ChatCompletionContentPartUnionParam{OfText: &fileBlock})
+					continue
+				}
 				imageURL := openai.ChatCompletionContentPartImageImageURLParam{URL: binaryContent.String(models.ProviderOpenAI)}
 				imageBlock := openai.ChatCompletionContentPartImageParam{ImageURL: imageURL}
 
@@ -180,6 +185,10 @@ func (o *openaiClient) preparedParams(messages []openai.ChatCompletionMessagePar
 		}
 	} else {
 		params.MaxTokens = openai.Int(o.providerOptions.maxTokens)
+		// Reasoning models only take their default temperature
+		if o.providerOptions.temperature != nil {
+			params.Temperature = openai.Float(*o.providerOptions.temperature)
+		}
 	}
 
 	return params
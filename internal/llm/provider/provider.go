@@ -63,6 +63,7 @@ type providerClientOptions struct {
 	model         models.Model
 	maxTokens     int64
 	systemMessage string
+	temperature   *float64 // the default of the provider when nil
 
 	anthropicOptions []AnthropicOption
 	openaiOptions    []OpenAIOption
@@ -216,6 +217,12 @@ func WithSystemMessage(systemMessage string) ProviderClientOption {
 	}
 }
 
+func WithTemperature(temperature float64) ProviderClientOption {
+	return func(options *providerClientOptions) {
+		options.temperature = &temperature
+	}
+}
+
 func WithAnthropicOptions(anthropicOptions ...AnthropicOption) ProviderClientOption {
 	return func(options *providerClientOptions) {
 		options.anthropicOptions = anthropicOptions
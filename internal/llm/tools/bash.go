@@ -25,6 +25,7 @@ type BashPermissionsParams struct {
 type BashResponseMetadata struct {
 	StartTime int64 `json:"start_time"`
 	EndTime   int64 `json:"end_time"`
+	ExitCode  int   `json:"exit_code"`
 }
 type bashTool struct {
 	permissions permission.Service
@@ -36,6 +37,10 @@ const (
 	DefaultTimeout  = 1 * 60 * 1000  // 1 minutes in milliseconds
 	MaxTimeout      = 10 * 60 * 1000 // 10 minutes in milliseconds
 	MaxOutputLength = 30000
+
+	// maxStoredOutputLength bounds the output kept in memory and stored with
+	// the message, the agent truncates it further before sending it to the model.
+	maxStoredOutputLength = 1024 * 1024
 )
 
 var bannedCommands = []string{
@@ -56,6 +61,10 @@ var safeReadOnlyCommands = []string{
 
 func bashDescription() string {
 	bannedCommandsStr := strings.Join(bannedCommands, ", ")
+	outputLimit := MaxOutputLength
+	if cfg := config.Get(); cfg != nil && cfg.Tools.MaxOutputBytes > 0 {
+		outputLimit = cfg.Tools.MaxOutputBytes
+	}
 	return fmt.Sprintf(`Executes a given bash command in a persistent shell session with optional timeout, ensuring proper handling and security measures.
 
 Before executing the command, please follow these steps:
@@ -200,7 +209,7 @@ EOF
 
 Important:
 - Return an empty response - the user will see the gh output directly
-- Never update git config`, bannedCommandsStr, MaxOutputLength)
+- Never update git config`, bannedCommandsStr, outputLimit)
 }
 
 func NewBashTool(permission permission.Service) BaseTool {
@@ -243,24 +252,11 @@ func (b *bashTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 		return NewTextErrorResponse("missing command"), nil
 	}
 
-	baseCmd := strings.Fields(params.Command)[0]
-	for _, banned := range bannedCommands {
-		if strings.EqualFold(baseCmd, banned) {
-			return NewTextErrorResponse(fmt.Sprintf("command '%s' is not allowed", baseCmd)), nil
-		}
+	if baseCmd, banned := isBannedCommand(params.Command); banned {
+		return NewTextErrorResponse(fmt.Sprintf("command '%s' is not allowed", baseCmd)), nil
 	}
 
-	isSafeReadOnly := false
-	cmdLower := strings.ToLower(params.Command)
-
-	for _, safe := range safeReadOnlyCommands {
-		if strings.HasPrefix(cmdLower, strings.ToLower(safe)) {
-			if len(cmdLower) == len(safe) || cmdLower[len(safe)] == ' ' || cmdLower[len(safe)] == '-' {
-				isSafeReadOnly = true
-				break
-			}
-		}
-	}
+	isSafeReadOnly := isSafeReadOnlyCommand(params.Command)
 
 	sessionID, messageID := GetContextValues(ctx)
 	if sessionID == "" || messageID == "" {
@@ -274,6 +270,7 @@ func (b *bashTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 				ToolName:    BashToolName,
 				Action:      "execute",
 				Description: fmt.Sprintf("Execute command: %s", params.Command),
+				Command:     params.Command,
 				Params: BashPermissionsParams{
 					Command: params.Command,
 				},
@@ -283,9 +280,13 @@ func (b *bashTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 			return ToolResponse{}, permission.ErrorPermissionDenied
 		}
 	}
+	command := params.Command
+	if shell.SandboxEnabled() {
+		command = shell.SandboxCommand(command)
+	}
 	startTime := time.Now()
-	shell := shell.GetPersistentShell(config.WorkingDirectory())
-	stdout, stderr, exitCode, interrupted, err := shell.Exec(ctx, params.Command, params.Timeout)
+	sh := shell.GetPersistentShell(config.WorkingDirectory())
+	stdout, stderr, exitCode, interrupted, err := sh.Exec(ctx, command, params.Timeout)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("error executing command: %w", err)
 	}
@@ -319,6 +320,7 @@ func (b *bashTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 	metadata := BashResponseMetadata{
 		StartTime: startTime.UnixMilli(),
 		EndTime:   time.Now().UnixMilli(),
+		ExitCode:  exitCode,
 	}
 	if stdout == "" {
 		return WithResponseMetadata(NewTextResponse("no output"), metadata), nil
@@ -326,12 +328,41 @@ func (b *bashTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 	return WithResponseMetadata(NewTextResponse(stdout), metadata), nil
 }
 
+// isBannedCommand reports whether the command starts with a banned command and
+// returns that command.
+func isBannedCommand(command string) (string, bool) {
+	fields := strings.Fields(command)
+	if len(fields) == 0 {
+		return "", false
+	}
+	for _, banned := range bannedCommands {
+		if strings.EqualFold(fields[0], banned) {
+			return fields[0], true
+		}
+	}
+	return "", false
+}
+
+// isSafeReadOnlyCommand reports whether the command only reads, so it can run
+// without asking for permission.
+func isSafeReadOnlyCommand(command string) bool {
+	cmdLower := strings.ToLower(command)
+	for _, safe := range safeReadOnlyCommands {
+		if strings.HasPrefix(cmdLower, strings.ToLower(safe)) {
+			if len(cmdLower) == len(safe) || cmdLower[len(safe)] == ' ' || cmdLower[len(safe)] == '-' {
+				return true
+			}
+		}
+	}
+	return false
+}
+
 func truncateOutput(content string) string {
-	if len(content) <= MaxOutputLength {
+	if len(content) <= maxStoredOutputLength {
 		return content
 	}
 
-	halfLength := MaxOutputLength / 2
+	halfLength := maxStoredOutputLength / 2
 	start := content[:halfLength]
 	end := content[len(content)-halfLength:]
 
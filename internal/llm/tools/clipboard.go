@@ -0,0 +1,128 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/atotto/clipboard"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type CopyToClipboardParams struct {
+	Text string `json:"text"`
+}
+
+type CopyToClipboardPermissionsParams struct {
+	Text string `json:"text"`
+}
+
+type CopyToClipboardResponseMetadata struct {
+	Lines int `json:"lines"`
+	Bytes int `json:"bytes"`
+}
+
+type copyToClipboardTool struct {
+	permissions permission.Service
+}
+
+const (
+	CopyToClipboardToolName    = "copy_to_clipboard"
+	copyToClipboardDescription = `Copies text to the system clipboard of the user, replacing what is on it.
+
+WHEN TO USE THIS TOOL:
+- Use when the user asks you to copy something, such as a command, a snippet or a message
+- Use to hand over text the user wants to paste somewhere else, e.g. a commit message or a reply
+
+HOW TO USE:
+- Provide the exact text to copy
+- Copy only the text itself, without surrounding markdown fences or explanations
+
+FEATURES:
+- Works with the native clipboard on macOS and Windows
+- Uses xclip, xsel or wl-copy on Linux
+
+LIMITATIONS:
+- Needs a clipboard, so it fails in headless environments such as SSH sessions or containers
+- Replaces the current content of the clipboard
+- Asks the user for permission, showing the text
+- Only plain text is supported
+
+TIPS:
+- Tell the user what you copied, they can't see the clipboard in the chat`
+)
+
+func NewCopyToClipboardTool(permissions permission.Service) BaseTool {
+	return &copyToClipboardTool{
+		permissions: permissions,
+	}
+}
+
+func (c *copyToClipboardTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        CopyToClipboardToolName,
+		Description: copyToClipboardDescription,
+		Parameters: map[string]any{
+			"text": map[string]any{
+				"type":        "string",
+				"description": "The text to copy to the clipboard",
+			},
+		},
+		Required: []string{"text"},
+	}
+}
+
+func (c *copyToClipboardTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params CopyToClipboardParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+	if params.Text == "" {
+		return NewTextErrorResponse("text is required"), nil
+	}
+
+	if clipboard.Unsupported {
+		return NewTextErrorResponse("no clipboard is available on this system"), nil
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for copying to the clipboard")
+	}
+	// The user pastes the text somewhere, maybe in a terminal, they see it
+	// before it replaces what they copied
+	fence := "```"
+	for strings.Contains(params.Text, fence) {
+		fence += "`"
+	}
+	p := c.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        config.WorkingDirectory(),
+			ToolName:    CopyToClipboardToolName,
+			Action:      "copy",
+			Description: fmt.Sprintf("Copy to the clipboard:\n\n%s\n%s\n%s", fence, params.Text, fence),
+			Params: CopyToClipboardPermissionsParams{
+				Text: params.Text,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	if err := clipboard.WriteAll(params.Text); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("failed to copy to the clipboard: %s", err)), nil
+	}
+
+	lines := strings.Count(params.Text, "\n") + 1
+	return WithResponseMetadata(
+		NewTextResponse(fmt.Sprintf("Copied %d lines (%d bytes) to the clipboard", lines, len(params.Text))),
+		CopyToClipboardResponseMetadata{
+			Lines: lines,
+			Bytes: len(params.Text),
+		},
+	), nil
+}
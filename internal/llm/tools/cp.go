@@ -0,0 +1,242 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+)
+
+type CpParams struct {
+	Source      string `json:"source"`
+	Destination string `json:"destination"`
+	Overwrite   bool   `json:"overwrite,omitempty"`
+}
+
+type CpPermissionsParams struct {
+	Source      string `json:"source"`
+	Destination string `json:"destination"`
+	Overwrite   bool   `json:"overwrite"`
+}
+
+type cpTool struct {
+	permissions permission.Service
+	snapshots   snapshot.Service
+}
+
+const (
+	CpToolName    = "cp"
+	cpDescription = `Copy tool that copies a file or directory to a new location.
+
+WHEN TO USE THIS TOOL:
+- Use when you need a duplicate of an existing file as a starting point
+- Helpful for copying fixtures, templates or configuration files
+
+HOW TO USE:
+- Provide the source path and the destination path
+- Directories are copied recursively
+- Set overwrite to true to replace existing files at the destination
+
+FEATURES:
+- Works for both files and directories
+- Creates missing parent directories of the destination
+- Preserves file permissions
+
+LIMITATIONS:
+- Will not overwrite existing files unless overwrite is true
+- Symbolic links are skipped when copying directories
+
+TIPS:
+- Use the View tool to inspect the copy before modifying it
+- Prefer the mv tool when you don't need to keep the original`
+)
+
+func NewCpTool(permissions permission.Service, snapshots snapshot.Service) BaseTool {
+	return &cpTool{
+		permissions: permissions,
+		snapshots:   snapshots,
+	}
+}
+
+func (c *cpTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        CpToolName,
+		Description: cpDescription,
+		Parameters: map[string]any{
+			"source": map[string]any{
+				"type":        "string",
+				"description": "The path of the file or directory to copy",
+			},
+			"destination": map[string]any{
+				"type":        "string",
+				"description": "The path to copy the file or directory to",
+			},
+			"overwrite": map[string]any{
+				"type":        "boolean",
+				"description": "Replace existing files at the destination (default: false)",
+			},
+		},
+		Required: []string{"source", "destination"},
+	}
+}
+
+func (c *cpTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params CpParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.Source == "" || params.Destination == "" {
+		return NewTextErrorResponse("source and destination are required"), nil
+	}
+
+	rootDir := config.WorkingDirectory()
+	source := params.Source
+	if !filepath.IsAbs(source) {
+		source = filepath.Join(rootDir, source)
+	}
+	destination := params.Destination
+	if !filepath.IsAbs(destination) {
+		destination = filepath.Join(rootDir, destination)
+	}
+
+	if err := checkWorkspacePath(source); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+	if err := checkWritePath(destination); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	sourceInfo, err := os.Stat(source)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return NewTextErrorResponse(fmt.Sprintf("Source not found: %s", source)), nil
+		}
+		return ToolResponse{}, fmt.Errorf("error checking source: %w", err)
+	}
+
+	if sourceInfo.IsDir() && strings.HasPrefix(destination+string(filepath.Separator), source+string(filepath.Separator)) {
+		return NewTextErrorResponse("Cannot copy a directory into itself"), nil
+	}
+
+	if destInfo, err := os.Stat(destination); err == nil {
+		if !params.Overwrite {
+			return NewTextErrorResponse(fmt.Sprintf("Destination already exists: %s. Set overwrite to true to replace it.", destination)), nil
+		}
+		if destInfo.IsDir() != sourceInfo.IsDir() {
+			return NewTextErrorResponse(fmt.Sprintf("Cannot overwrite %s: source and destination types differ", destination)), nil
+		}
+	} else if !os.IsNotExist(err) {
+		return ToolResponse{}, fmt.Errorf("error checking destination: %w", err)
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session_id and message_id are required")
+	}
+
+	permissionPath := filepath.Dir(destination)
+	if strings.HasPrefix(destination, rootDir) {
+		permissionPath = rootDir
+	}
+	p := c.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        permissionPath,
+			ToolName:    CpToolName,
+			Action:      "copy",
+			Description: fmt.Sprintf("Copy %s to %s", source, destination),
+			Params: CpPermissionsParams{
+				Source:      source,
+				Destination: destination,
+				Overwrite:   params.Overwrite,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	if !sourceInfo.IsDir() {
+		if err := c.snapshots.Take(ctx, sessionID, messageID, destination); err != nil {
+			return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+		}
+		if err := copyFile(source, destination, sourceInfo.Mode()); err != nil {
+			return ToolResponse{}, fmt.Errorf("error copying file: %w", err)
+		}
+		recordFileWrite(destination)
+		return NewTextResponse(fmt.Sprintf("Copied %s to %s", source, destination)), nil
+	}
+
+	copied := 0
+	err = filepath.WalkDir(source, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(source, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(destination, rel)
+
+		if d.IsDir() {
+			return os.MkdirAll(target, 0o755)
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		if _, err := os.Stat(target); err == nil && !params.Overwrite {
+			return nil
+		}
+		if err := c.snapshots.Take(ctx, sessionID, messageID, target); err != nil {
+			return err
+		}
+		if err := copyFile(path, target, info.Mode()); err != nil {
+			return err
+		}
+		recordFileWrite(target)
+		copied++
+		return nil
+	})
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("error copying directory: %w", err)
+	}
+
+	return NewTextResponse(fmt.Sprintf("Copied %d files from %s to %s", copied, source, destination)), nil
+}
+
+func copyFile(src, dst string, mode os.FileMode) error {
+	if err := os.MkdirAll(filepath.Dir(dst), 0o755); err != nil {
+		return err
+	}
+
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+
+	out, err := os.OpenFile(dst, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, mode.Perm())
+	if err != nil {
+		return err
+	}
+
+	if _, err := io.Copy(out, in); err != nil {
+		out.Close()
+		return err
+	}
+	return out.Close()
+}
@@ -0,0 +1,152 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type CustomPermissionsParams struct {
+	Command string `json:"command"`
+}
+
+type CustomResponseMetadata struct {
+	Command   string `json:"command"`
+	ExitCode  int    `json:"exit_code"`
+	StartTime int64  `json:"start_time"`
+	EndTime   int64  `json:"end_time"`
+}
+
+type customTool struct {
+	cfg         config.CustomToolConfig
+	permissions permission.Service
+}
+
+var customToolPlaceholderRegex = regexp.MustCompile(`\{\{\s*(\w+)\s*\}\}`)
+
+// NewCustomTool creates a tool from the configuration that runs a local
+// command with the arguments of the model substituted into its template.
+func NewCustomTool(cfg config.CustomToolConfig, permissions permission.Service) BaseTool {
+	return &customTool{
+		cfg:         cfg,
+		permissions: permissions,
+	}
+}
+
+func (c *customTool) Info() ToolInfo {
+	parameters := c.cfg.Parameters
+	if parameters == nil {
+		parameters = make(map[string]any)
+	}
+	required := c.cfg.Required
+	if required == nil {
+		required = make([]string, 0)
+	}
+	return ToolInfo{
+		Name:        c.cfg.Name,
+		Description: c.cfg.Description,
+		Parameters:  parameters,
+		Required:    required,
+	}
+}
+
+func (c *customTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var args map[string]any
+	if err := json.Unmarshal([]byte(call.Input), &args); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+	for _, name := range c.cfg.Required {
+		if _, ok := args[name]; !ok {
+			return NewTextErrorResponse(fmt.Sprintf("%s is required", name)), nil
+		}
+	}
+
+	command := expandCommandTemplate(c.cfg.Command, args)
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for running a custom tool")
+	}
+
+	workDir := config.WorkingDirectory()
+	p := c.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        workDir,
+			ToolName:    c.cfg.Name,
+			Action:      "execute",
+			Description: fmt.Sprintf("Execute command: %s", command),
+			Command:     command,
+			Params: CustomPermissionsParams{
+				Command: command,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	execCommand := command
+	if shell.SandboxEnabled() {
+		execCommand = shell.SandboxCommand(command)
+	}
+	startTime := time.Now()
+	sh := shell.GetPersistentShell(workDir)
+	stdout, stderr, exitCode, interrupted, err := sh.Exec(ctx, fmt.Sprintf("(cd %s && %s)", shellQuote(workDir), execCommand), MaxTimeout)
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("error executing command: %w", err)
+	}
+
+	output := truncateOutput(stdout)
+	if stderr != "" {
+		if output != "" {
+			output += "\n"
+		}
+		output += truncateOutput(stderr)
+	}
+
+	metadata := CustomResponseMetadata{
+		Command:   command,
+		ExitCode:  exitCode,
+		StartTime: startTime.UnixMilli(),
+		EndTime:   time.Now().UnixMilli(),
+	}
+	if interrupted {
+		return WithResponseMetadata(NewTextErrorResponse(output+"\nCommand was aborted before completion"), metadata), nil
+	}
+	if exitCode != 0 {
+		return WithResponseMetadata(NewTextErrorResponse(fmt.Sprintf("%s\nExit code %d", output, exitCode)), metadata), nil
+	}
+	if output == "" {
+		output = "no output"
+	}
+	return WithResponseMetadata(NewTextResponse(output), metadata), nil
+}
+
+// expandCommandTemplate replaces the {{name}} placeholders of the template with
+// the shell quoted value of the argument. Missing arguments are replaced with
+// an empty string.
+func expandCommandTemplate(template string, args map[string]any) string {
+	return customToolPlaceholderRegex.ReplaceAllStringFunc(template, func(placeholder string) string {
+		name := customToolPlaceholderRegex.FindStringSubmatch(placeholder)[1]
+		value, ok := args[name]
+		if !ok || value == nil {
+			return "''"
+		}
+		switch v := value.(type) {
+		case string:
+			return shellQuote(v)
+		case float64, bool:
+			return shellQuote(fmt.Sprint(v))
+		default:
+			encoded, _ := json.Marshal(v)
+			return shellQuote(string(encoded))
+		}
+	})
+}
@@ -5,10 +5,12 @@ import (
 	"encoding/json"
 	"fmt"
 	"maps"
+	"path/filepath"
 	"sort"
 	"strings"
 	"time"
 
+	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/lsp/protocol"
 )
@@ -58,7 +60,7 @@ func (b *diagnosticsTool) Info() ToolInfo {
 		Parameters: map[string]any{
 			"file_path": map[string]any{
 				"type":        "string",
-				"description": "The path to the file to get diagnostics for (leave w empty for project diagnostics)",
+				"description": "The path to the file to get diagnostics for (leave empty for project diagnostics)",
 			},
 		},
 		Required: []string{},
@@ -74,15 +76,25 @@ func (b *diagnosticsTool) Run(ctx context.Context, call ToolCall) (ToolResponse,
 	lsps := b.lspClients
 
 	if len(lsps) == 0 {
-		return NewTextErrorResponse("no LSP clients available"), nil
+		return NewTextErrorResponse("no LSP clients available, the language servers may still be starting"), nil
 	}
 
-	if params.FilePath != "" {
-		notifyLspOpenFile(ctx, params.FilePath, lsps)
-		waitForLspDiagnostics(ctx, params.FilePath, lsps)
+	filePath := params.FilePath
+	if filePath != "" {
+		if !filepath.IsAbs(filePath) {
+			filePath = filepath.Join(config.WorkingDirectory(), filePath)
+		}
+		if err := checkWorkspacePath(filePath); err != nil {
+			return NewTextErrorResponse(err.Error()), nil
+		}
+		notifyLspOpenFile(ctx, filePath, lsps)
+		waitForLspDiagnostics(ctx, filePath, lsps)
 	}
 
-	output := getDiagnostics(params.FilePath, lsps)
+	output := getDiagnostics(filePath, lsps)
+	if output == "" {
+		output = "No diagnostics reported"
+	}
 
 	return NewTextResponse(output), nil
 }
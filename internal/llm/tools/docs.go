@@ -0,0 +1,270 @@
+package tools
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"os/exec"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type DocsParams struct {
+	Name   string `json:"name"`
+	Source string `json:"source"`
+}
+
+type DocsPermissionsParams struct {
+	Command string `json:"command"`
+}
+
+type DocsResponseMetadata struct {
+	Source  string `json:"source"`
+	Command string `json:"command"`
+}
+
+type docsTool struct {
+	permissions permission.Service
+}
+
+const (
+	DocsToolName    = "docs"
+	docsDescription = `Looks up the documentation of a command line tool or a code item, such as a man page, the --help output of a command or the documentation of a Go package or symbol.
+
+WHEN TO USE THIS TOOL:
+- Use before running a command whose flags or subcommands you are not sure about
+- Use to check the documented API of a Go package, type or function, or of a Python module
+- Prefer this over guessing flags or function signatures from memory
+
+HOW TO USE:
+- Provide the name of the command, e.g. "tar" or "git commit", or of the item, e.g. "net/http.Client" or "json.dumps"
+- Optionally choose the source: "man", "help", "go" or "python"
+- Without a source, Go items are looked up with go doc, commands with man and then with --help
+
+FEATURES:
+- Reads man pages as plain text
+- Runs "<command> --help" for commands without a man page
+- Uses go doc for Go packages and symbols and pydoc for Python modules and functions
+
+LIMITATIONS:
+- Only documentation installed on this machine is available
+- Running --help, go doc or pydoc executes code of the command or the project, so it asks for permission first and runs in the sandbox when it is enabled
+- Long pages are truncated
+
+TIPS:
+- Look up subcommands directly, e.g. "git rebase" or "docker compose up"
+- Use the fetch tool for documentation that is only available online`
+)
+
+const (
+	docsTimeout   = 15 * time.Second
+	maxDocsLength = 100 * 1024
+)
+
+// docsNameRegex restricts the names to what commands and code items look like.
+// No word may start with a dash, so a name can't smuggle in flags.
+var docsNameRegex = regexp.MustCompile(`^[\w@.+:/][\w@.+:/-]*( [\w@.+:/][\w@.+:/-]*)*$`)
+
+func NewDocsTool(permissions permission.Service) BaseTool {
+	return &docsTool{
+		permissions: permissions,
+	}
+}
+
+func (d *docsTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        DocsToolName,
+		Description: docsDescription,
+		Parameters: map[string]any{
+			"name": map[string]any{
+				"type":        "string",
+				"description": "The command, subcommand or code item to look up, e.g. \"git commit\" or \"net/http.Client\"",
+			},
+			"source": map[string]any{
+				"type":        "string",
+				"description": "Where to look the documentation up",
+				"enum":        []string{"man", "help", "go", "python"},
+			},
+		},
+		Required: []string{"name"},
+	}
+}
+
+func (d *docsTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params DocsParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	name := strings.Join(strings.Fields(params.Name), " ")
+	if name == "" {
+		return NewTextErrorResponse("name is required"), nil
+	}
+	if !docsNameRegex.MatchString(name) {
+		return NewTextErrorResponse(fmt.Sprintf("invalid name: %s", params.Name)), nil
+	}
+
+	var sources []string
+	switch params.Source {
+	case "":
+		sources = defaultDocsSources(name)
+	case "man", "help", "go", "python":
+		sources = []string{params.Source}
+	default:
+		return NewTextErrorResponse(fmt.Sprintf("unsupported source: %s. Use man, help, go or python", params.Source)), nil
+	}
+
+	var failures []string
+	for _, source := range sources {
+		args := docsCommand(source, name)
+		// Only man reads a page, the other sources run the command or import
+		// the code of the project, e.g. pydoc puts the working directory on
+		// the module path
+		runsCode := source != "man"
+		sandboxed := runsCode && shell.SandboxEnabled()
+		if _, err := exec.LookPath(args[0]); err != nil && !sandboxed {
+			failures = append(failures, fmt.Sprintf("%s: %s is not installed", source, args[0]))
+			continue
+		}
+
+		command := strings.Join(args, " ")
+		if runsCode {
+			ok, err := d.requestPermission(ctx, command)
+			if err != nil {
+				return ToolResponse{}, err
+			}
+			if !ok {
+				return ToolResponse{}, permission.ErrorPermissionDenied
+			}
+		}
+
+		output, err := runDocsCommand(ctx, args, sandboxed)
+		if ctx.Err() != nil {
+			return ToolResponse{}, ctx.Err()
+		}
+		if err != nil || strings.TrimSpace(output) == "" {
+			failures = append(failures, fmt.Sprintf("%s: no documentation found", source))
+			continue
+		}
+
+		if len(output) > maxDocsLength {
+			output = output[:maxDocsLength] + "\n\n[Documentation truncated]"
+		}
+		return WithResponseMetadata(
+			NewTextResponse(output),
+			DocsResponseMetadata{
+				Source:  source,
+				Command: command,
+			},
+		), nil
+	}
+
+	return NewTextErrorResponse(fmt.Sprintf("no documentation found for %s\n%s", name, strings.Join(failures, "\n"))), nil
+}
+
+func (d *docsTool) requestPermission(ctx context.Context, command string) (bool, error) {
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return false, fmt.Errorf("session ID and message ID are required for running a command")
+	}
+	return d.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        config.WorkingDirectory(),
+			ToolName:    DocsToolName,
+			Action:      "execute",
+			Description: fmt.Sprintf("Execute command: %s", command),
+			Command:     command,
+			Params: DocsPermissionsParams{
+				Command: command,
+			},
+		},
+	), nil
+}
+
+// defaultDocsSources guesses where the documentation of the name is. Names with
+// a dot or a slash look like Go items, anything else like a command.
+func defaultDocsSources(name string) []string {
+	if !strings.Contains(name, " ") && strings.ContainsAny(name, "./") {
+		return []string{"go", "python"}
+	}
+	return []string{"man", "help"}
+}
+
+// docsCommand returns the command line that prints the documentation of the
+// name from the source.
+func docsCommand(source, name string) []string {
+	parts := strings.Fields(name)
+	switch source {
+	case "man":
+		// Subcommands usually have their own page, e.g. git-commit
+		return []string{"man", strings.Join(parts, "-")}
+	case "go":
+		return append([]string{"go", "doc"}, parts...)
+	case "python":
+		return []string{"python3", "-m", "pydoc", name}
+	default:
+		return append(parts, "--help")
+	}
+}
+
+// runDocsCommand runs the command in the working directory, in a container of
+// the sandbox when sandboxed is set, the way the bash tool runs commands
+func runDocsCommand(ctx context.Context, args []string, sandboxed bool) (string, error) {
+	ctx, cancel := context.WithTimeout(ctx, docsTimeout)
+	defer cancel()
+
+	var stdout, stderr bytes.Buffer
+	cmd := exec.CommandContext(ctx, args[0], args[1:]...)
+	if sandboxed {
+		quoted := make([]string, len(args))
+		for i, arg := range args {
+			quoted[i] = shellQuote(arg)
+		}
+		cmd = exec.CommandContext(ctx, "sh", "-c", shell.SandboxCommand(strings.Join(quoted, " ")))
+	}
+	cmd.Dir = config.WorkingDirectory()
+	cmd.Stdout = &stdout
+	cmd.Stderr = &stderr
+	// Plain text without a pager or terminal formatting
+	cmd.Env = append(cmd.Environ(), "MANPAGER=cat", "PAGER=cat", "MANWIDTH=100", "MAN_KEEP_FORMATTING=0", "NO_COLOR=1")
+	err := cmd.Run()
+
+	output := stdout.String()
+	if output == "" {
+		// Many commands print their help to stderr
+		output = stderr.String()
+	}
+	if args[0] == "man" {
+		output = stripOverstrike(output)
+	}
+	if err != nil && args[len(args)-1] != "--help" {
+		return "", err
+	}
+	// Commands often exit with an error status after printing their help
+	return output, nil
+}
+
+// stripOverstrike removes the backspace sequences man uses for bold and
+// underlined text.
+func stripOverstrike(s string) string {
+	if !strings.Contains(s, "\b") {
+		return s
+	}
+	var b strings.Builder
+	runes := []rune(s)
+	for i := 0; i < len(runes); i++ {
+		if i+1 < len(runes) && runes[i+1] == '\b' {
+			i++ // Skip the overstruck character and the backspace
+			continue
+		}
+		b.WriteRune(runes[i])
+	}
+	return b.String()
+}
@@ -0,0 +1,66 @@
+package tools
+
+import (
+	"context"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+// recordedPermissions denies every request and keeps them
+type recordedPermissions struct {
+	permission.Service
+	requests []permission.CreatePermissionRequest
+}
+
+func (p *recordedPermissions) Request(request permission.CreatePermissionRequest) bool {
+	p.requests = append(p.requests, request)
+	return false
+}
+
+func toolContext() context.Context {
+	ctx := context.WithValue(context.Background(), SessionIDContextKey, "session")
+	return context.WithValue(ctx, MessageIDContextKey, "message")
+}
+
+func TestDocsCommand(t *testing.T) {
+	assert.Equal(t, []string{"man", "git-commit"}, docsCommand("man", "git commit"))
+	assert.Equal(t, []string{"git", "commit", "--help"}, docsCommand("help", "git commit"))
+	assert.Equal(t, []string{"go", "doc", "net/http.Client"}, docsCommand("go", "net/http.Client"))
+	assert.Equal(t, []string{"python3", "-m", "pydoc", "json.dumps"}, docsCommand("python", "json.dumps"))
+
+	assert.Equal(t, []string{"man", "help"}, defaultDocsSources("git commit"))
+	assert.Equal(t, []string{"go", "python"}, defaultDocsSources("strings.Builder"))
+}
+
+func TestDocsNameRegex(t *testing.T) {
+	assert.True(t, docsNameRegex.MatchString("docker compose up"))
+	assert.True(t, docsNameRegex.MatchString("net/http.Client"))
+	assert.False(t, docsNameRegex.MatchString("rm -rf /"))
+	assert.False(t, docsNameRegex.MatchString("ls; rm x"))
+	assert.False(t, docsNameRegex.MatchString("--version"))
+}
+
+func TestStripOverstrike(t *testing.T) {
+	assert.Equal(t, "NAME", stripOverstrike("N\bNA\bAM\bME\bE"))
+	assert.Equal(t, "_x", stripOverstrike("_\b_x"))
+}
+
+func TestDocsAsksBeforeRunningCode(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+
+	// Every source but man runs a command, it doesn't without permission
+	permissions := &recordedPermissions{}
+	_, err = NewDocsTool(permissions).Run(toolContext(), ToolCall{Input: `{"name": "strings.Builder", "source": "go"}`})
+	require.ErrorIs(t, err, permission.ErrorPermissionDenied)
+	require.Len(t, permissions.requests, 1)
+	assert.Equal(t, "go doc strings.Builder", permissions.requests[0].Command)
+}
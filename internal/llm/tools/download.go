@@ -0,0 +1,273 @@
+package tools
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+)
+
+type DownloadParams struct {
+	URL       string `json:"url"`
+	FilePath  string `json:"file_path"`
+	SHA256    string `json:"sha256,omitempty"`
+	MaxSizeMB int    `json:"max_size_mb,omitempty"`
+	Timeout   int    `json:"timeout,omitempty"`
+}
+
+type DownloadPermissionsParams struct {
+	URL      string `json:"url"`
+	FilePath string `json:"file_path"`
+}
+
+type DownloadResponseMetadata struct {
+	URL         string `json:"url"`
+	FilePath    string `json:"file_path"`
+	Size        int64  `json:"size"`
+	SHA256      string `json:"sha256"`
+	ContentType string `json:"content_type"`
+}
+
+type downloadTool struct {
+	permissions permission.Service
+	snapshots   snapshot.Service
+}
+
+const (
+	DownloadToolName    = "download"
+	downloadDescription = `Downloads a file from a URL and saves it to a local path, reporting its size and SHA-256 checksum.
+
+WHEN TO USE THIS TOOL:
+- Use to save sample data, archives, release artifacts or other files to disk
+- Use instead of the Fetch tool when you need the file itself rather than its content in the conversation
+
+HOW TO USE:
+- Provide the URL and the path to save the file to
+- Optionally provide the expected SHA-256 checksum, the download is rejected if it doesn't match
+- Optionally raise the size limit with max_size_mb or the timeout in seconds
+
+FEATURES:
+- Streams the file to disk, the content is not added to the conversation
+- Creates missing parent directories
+- Reports the size, content type and SHA-256 checksum of the downloaded file
+- Replacing an existing file can be undone like other file changes
+
+LIMITATIONS:
+- Only supports HTTP and HTTPS URLs without authentication
+- Files are limited to 100MB by default and 1GB at most
+- The timeout defaults to 5 minutes and is at most 30 minutes
+
+TIPS:
+- Verify release artifacts with the checksum published next to them
+- Use the Bash tool to extract archives after downloading them`
+)
+
+const (
+	defaultDownloadMaxSizeMB = 100
+	maxDownloadMaxSizeMB     = 1024
+	defaultDownloadTimeout   = 5 * 60
+	maxDownloadTimeout       = 30 * 60
+)
+
+func NewDownloadTool(permissions permission.Service, snapshots snapshot.Service) BaseTool {
+	return &downloadTool{
+		permissions: permissions,
+		snapshots:   snapshots,
+	}
+}
+
+func (d *downloadTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        DownloadToolName,
+		Description: downloadDescription,
+		Parameters: map[string]any{
+			"url": map[string]any{
+				"type":        "string",
+				"description": "The URL to download",
+			},
+			"file_path": map[string]any{
+				"type":        "string",
+				"description": "The path to save the file to",
+			},
+			"sha256": map[string]any{
+				"type":        "string",
+				"description": "The expected SHA-256 checksum of the file in hex",
+			},
+			"max_size_mb": map[string]any{
+				"type":        "number",
+				"description": "The maximum size of the file in MB (default 100, max 1024)",
+			},
+			"timeout": map[string]any{
+				"type":        "number",
+				"description": "Optional timeout in seconds (default 300, max 1800)",
+			},
+		},
+		Required: []string{"url", "file_path"},
+	}
+}
+
+func (d *downloadTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params DownloadParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.URL == "" {
+		return NewTextErrorResponse("url is required"), nil
+	}
+	if !strings.HasPrefix(params.URL, "http://") && !strings.HasPrefix(params.URL, "https://") {
+		return NewTextErrorResponse("URL must start with http:// or https://"), nil
+	}
+	if params.FilePath == "" {
+		return NewTextErrorResponse("file_path is required"), nil
+	}
+	expectedHash := strings.ToLower(strings.TrimSpace(params.SHA256))
+	if expectedHash != "" {
+		if decoded, err := hex.DecodeString(expectedHash); err != nil || len(decoded) != sha256.Size {
+			return NewTextErrorResponse("sha256 must be a hex encoded SHA-256 checksum"), nil
+		}
+	}
+
+	maxSizeMB := params.MaxSizeMB
+	if maxSizeMB <= 0 {
+		maxSizeMB = defaultDownloadMaxSizeMB
+	}
+	maxSizeMB = min(maxSizeMB, maxDownloadMaxSizeMB)
+	maxSize := int64(maxSizeMB) * 1024 * 1024
+
+	timeout := params.Timeout
+	if timeout <= 0 {
+		timeout = defaultDownloadTimeout
+	}
+	timeout = min(timeout, maxDownloadTimeout)
+
+	filePath := params.FilePath
+	if !filepath.IsAbs(filePath) {
+		filePath = filepath.Join(config.WorkingDirectory(), filePath)
+	}
+
+	if err := checkWritePath(filePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+	if info, err := os.Stat(filePath); err == nil && info.IsDir() {
+		return NewTextErrorResponse(fmt.Sprintf("path is a directory, not a file: %s", filePath)), nil
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for downloading a file")
+	}
+
+	p := d.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        config.WorkingDirectory(),
+			ToolName:    DownloadToolName,
+			Action:      "download",
+			Description: fmt.Sprintf("Download %s to %s", params.URL, filePath),
+			Params: DownloadPermissionsParams{
+				URL:      params.URL,
+				FilePath: filePath,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	client := &http.Client{
+		Timeout: time.Duration(timeout) * time.Second,
+	}
+	req, err := http.NewRequestWithContext(ctx, "GET", params.URL, nil)
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("User-Agent", "cryoncode/1.0")
+
+	resp, err := client.Do(req)
+	if err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("failed to download URL: %s", err)), nil
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return NewTextErrorResponse(fmt.Sprintf("Request failed with status code: %d", resp.StatusCode)), nil
+	}
+	if resp.ContentLength > maxSize {
+		return NewTextErrorResponse(fmt.Sprintf("file is too large: %d bytes, the limit is %dMB", resp.ContentLength, maxSizeMB)), nil
+	}
+
+	if err := os.MkdirAll(filepath.Dir(filePath), 0o755); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to create parent directories: %w", err)
+	}
+
+	// Download next to the destination so a failed download never leaves a
+	// partial file behind and the rename doesn't cross file systems
+	tmp, err := os.CreateTemp(filepath.Dir(filePath), "."+filepath.Base(filePath)+".download-*")
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to create file: %w", err)
+	}
+	defer os.Remove(tmp.Name())
+
+	hash := sha256.New()
+	size, err := io.Copy(io.MultiWriter(tmp, hash), io.LimitReader(resp.Body, maxSize+1))
+	if closeErr := tmp.Close(); err == nil {
+		err = closeErr
+	}
+	if err != nil {
+		if ctx.Err() != nil {
+			return ToolResponse{}, ctx.Err()
+		}
+		return NewTextErrorResponse(fmt.Sprintf("failed to download URL: %s", err)), nil
+	}
+	if size > maxSize {
+		return NewTextErrorResponse(fmt.Sprintf("file is larger than the limit of %dMB, raise max_size_mb to download it", maxSizeMB)), nil
+	}
+
+	checksum := hex.EncodeToString(hash.Sum(nil))
+	if expectedHash != "" && checksum != expectedHash {
+		return NewTextErrorResponse(fmt.Sprintf("checksum mismatch: expected %s, got %s. The file was not saved", expectedHash, checksum)), nil
+	}
+
+	if err := d.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+	if err := os.Chmod(tmp.Name(), 0o644); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
+	}
+	if err := os.Rename(tmp.Name(), filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
+	}
+	recordFileWrite(filePath)
+
+	contentType := resp.Header.Get("Content-Type")
+	result := fmt.Sprintf("Downloaded %s to %s\nSize: %d bytes\nSHA-256: %s", params.URL, filePath, size, checksum)
+	if contentType != "" {
+		result += fmt.Sprintf("\nContent-Type: %s", contentType)
+	}
+	if expectedHash != "" {
+		result += "\nThe checksum matches the expected one"
+	}
+
+	return WithResponseMetadata(
+		NewTextResponse(result),
+		DownloadResponseMetadata{
+			URL:         params.URL,
+			FilePath:    filePath,
+			Size:        size,
+			SHA256:      checksum,
+			ContentType: contentType,
+		},
+	), nil
+}
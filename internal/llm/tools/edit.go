@@ -15,6 +15,7 @@ import (
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
 )
 
 type EditParams struct {
@@ -24,8 +25,10 @@ type EditParams struct {
 }
 
 type EditPermissionsParams struct {
-	FilePath string `json:"file_path"`
-	Diff     string `json:"diff"`
+	FilePath  string `json:"file_path"`
+	Diff      string `json:"diff"`
+	Additions int    `json:"additions"`
+	Removals  int    `json:"removals"`
 }
 
 type EditResponseMetadata struct {
@@ -38,6 +41,7 @@ type editTool struct {
 	lspClients  map[string]*lsp.Client
 	permissions permission.Service
 	files       history.Service
+	snapshots   snapshot.Service
 }
 
 const (
@@ -91,11 +95,12 @@ When making edits:
 Remember: when making multiple file edits in a row to the same file, you should prefer to send all edits in a single message with multiple calls to this tool, rather than multiple messages with a single call each.`
 )
 
-func NewEditTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service) BaseTool {
+func NewEditTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service, snapshots snapshot.Service) BaseTool {
 	return &editTool{
 		lspClients:  lspClients,
 		permissions: permissions,
 		files:       files,
+		snapshots:   snapshots,
 	}
 }
 
@@ -136,6 +141,10 @@ func (e *editTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 		params.FilePath = filepath.Join(wd, params.FilePath)
 	}
 
+	if err := checkWritePath(params.FilePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
 	var response ToolResponse
 	var err error
 
@@ -209,8 +218,10 @@ func (e *editTool) createNewFile(ctx context.Context, filePath, content string)
 			Action:      "write",
 			Description: fmt.Sprintf("Create file %s", filePath),
 			Params: EditPermissionsParams{
-				FilePath: filePath,
-				Diff:     diff,
+				FilePath:  filePath,
+				Diff:      diff,
+				Additions: additions,
+				Removals:  removals,
 			},
 		},
 	)
@@ -218,6 +229,10 @@ func (e *editTool) createNewFile(ctx context.Context, filePath, content string)
 		return ToolResponse{}, permission.ErrorPermissionDenied
 	}
 
+	if err := e.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
 	err = os.WriteFile(filePath, []byte(content), 0o644)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
@@ -320,8 +335,10 @@ func (e *editTool) deleteContent(ctx context.Context, filePath, oldString string
 			Action:      "write",
 			Description: fmt.Sprintf("Delete content from file %s", filePath),
 			Params: EditPermissionsParams{
-				FilePath: filePath,
-				Diff:     diff,
+				FilePath:  filePath,
+				Diff:      diff,
+				Additions: additions,
+				Removals:  removals,
 			},
 		},
 	)
@@ -329,6 +346,10 @@ func (e *editTool) deleteContent(ctx context.Context, filePath, oldString string
 		return ToolResponse{}, permission.ErrorPermissionDenied
 	}
 
+	if err := e.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
 	err = os.WriteFile(filePath, []byte(newContent), 0o644)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
@@ -440,8 +461,10 @@ func (e *editTool) replaceContent(ctx context.Context, filePath, oldString, newS
 			Action:      "write",
 			Description: fmt.Sprintf("Replace content in file %s", filePath),
 			Params: EditPermissionsParams{
-				FilePath: filePath,
-				Diff:     diff,
+				FilePath:  filePath,
+				Diff:      diff,
+				Additions: additions,
+				Removals:  removals,
 			},
 		},
 	)
@@ -449,6 +472,10 @@ func (e *editTool) replaceContent(ctx context.Context, filePath, oldString, newS
 		return ToolResponse{}, permission.ErrorPermissionDenied
 	}
 
+	if err := e.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
 	err = os.WriteFile(filePath, []byte(newContent), 0o644)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
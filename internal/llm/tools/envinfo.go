@@ -0,0 +1,354 @@
+package tools
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"regexp"
+	"runtime"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type EnvInfoParams struct {
+	Tools []string `json:"tools"`
+}
+
+type ToolchainVersion struct {
+	Name    string `json:"name"`
+	Version string `json:"version"`
+}
+
+type EnvInfoResponseMetadata struct {
+	OS         string             `json:"os"`
+	Arch       string             `json:"arch"`
+	Shell      string             `json:"shell"`
+	Toolchains []ToolchainVersion `json:"toolchains"`
+	GitBranch  string             `json:"git_branch"`
+}
+
+type EnvInfoPermissionsParams struct {
+	Command string `json:"command"`
+}
+
+type envInfoTool struct {
+	permissions permission.Service
+}
+
+const (
+	EnvInfoToolName    = "env_info"
+	envInfoDescription = `Reports the environment you are working in: operating system, architecture, shell, the versions of installed toolchains and the state of the git repository.
+
+WHEN TO USE THIS TOOL:
+- Use before running platform specific commands, e.g. to know whether sed is GNU or BSD or which package manager is available
+- Use to check which language toolchains and versions are installed before building or testing
+- Use instead of asking the user about their environment
+
+HOW TO USE:
+- Call it without parameters to get the common information
+- Optionally list extra tools to report the version of, e.g. ["terraform", "kubectl"]
+
+FEATURES:
+- Shows the OS with its version, the CPU architecture and the shell used by the bash tool
+- Checks go, rustc, cargo, node, npm, python3, java, ruby, gcc, clang, make and docker
+- Shows the current git branch, commit, upstream status and the number of changed files
+
+LIMITATIONS:
+- Extra tools are asked for their version with --version, which runs them, so it asks for permission first and runs in the sandbox when it is enabled
+- Tools that don't support --version report nothing
+- Tools that are not on the PATH are listed as not installed
+
+TIPS:
+- The information rarely changes during a session, there is no need to call it more than once`
+)
+
+const envInfoCommandTimeout = 5 * time.Second
+
+// defaultToolchains are the tools whose version is always reported, with the
+// arguments that print it.
+var defaultToolchains = []struct {
+	name string
+	args []string
+}{
+	{"go", []string{"version"}},
+	{"rustc", []string{"--version"}},
+	{"cargo", []string{"--version"}},
+	{"node", []string{"--version"}},
+	{"npm", []string{"--version"}},
+	{"python3", []string{"--version"}},
+	{"java", []string{"-version"}},
+	{"ruby", []string{"--version"}},
+	{"gcc", []string{"--version"}},
+	{"clang", []string{"--version"}},
+	{"make", []string{"--version"}},
+	{"docker", []string{"--version"}},
+}
+
+var envToolNameRegex = regexp.MustCompile(`^[\w.+][\w.+-]*$`)
+
+func NewEnvInfoTool(permissions permission.Service) BaseTool {
+	return &envInfoTool{
+		permissions: permissions,
+	}
+}
+
+func (e *envInfoTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        EnvInfoToolName,
+		Description: envInfoDescription,
+		Parameters: map[string]any{
+			"tools": map[string]any{
+				"type":        "array",
+				"description": "Extra tools to report the version of",
+				"items": map[string]any{
+					"type": "string",
+				},
+			},
+		},
+		Required: []string{},
+	}
+}
+
+func (e *envInfoTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params EnvInfoParams
+	if call.Input != "" {
+		if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+			return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+		}
+	}
+
+	type versionCommand struct {
+		name  string
+		args  []string
+		extra bool
+	}
+	commands := make([]versionCommand, 0, len(defaultToolchains)+len(params.Tools))
+	for _, toolchain := range defaultToolchains {
+		commands = append(commands, versionCommand{toolchain.name, toolchain.args, false})
+	}
+	for _, name := range params.Tools {
+		if !envToolNameRegex.MatchString(name) {
+			return NewTextErrorResponse(fmt.Sprintf("invalid tool name: %s", name)), nil
+		}
+		commands = append(commands, versionCommand{name, []string{"--version"}, true})
+	}
+
+	// The extra tools can be any program, running them with --version
+	// executes them
+	for _, command := range commands {
+		if !command.extra {
+			continue
+		}
+		ok, err := e.requestPermission(ctx, command.name+" --version")
+		if err != nil {
+			return ToolResponse{}, err
+		}
+		if !ok {
+			return ToolResponse{}, permission.ErrorPermissionDenied
+		}
+	}
+
+	versions := make([]string, len(commands))
+	var wg sync.WaitGroup
+	for i, command := range commands {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			versions[i] = commandVersion(ctx, command.name, command.args, command.extra && shell.SandboxEnabled())
+		}()
+	}
+	wg.Wait()
+
+	metadata := EnvInfoResponseMetadata{
+		OS:    osDescription(),
+		Arch:  runtime.GOARCH,
+		Shell: shellPath(),
+	}
+
+	var output strings.Builder
+	fmt.Fprintf(&output, "OS: %s\n", metadata.OS)
+	fmt.Fprintf(&output, "Architecture: %s\n", metadata.Arch)
+	fmt.Fprintf(&output, "Shell: %s\n", metadata.Shell)
+	fmt.Fprintf(&output, "Working directory: %s\n", config.WorkingDirectory())
+	if cfg := config.Get(); cfg != nil && cfg.Sandbox.Enabled {
+		fmt.Fprintf(&output, "Sandbox: commands run in a %s container from the %s image, the versions of the toolchains below are from the host, those of the extra tools from the container\n", cfg.Sandbox.Runtime, cfg.Sandbox.Image)
+	}
+
+	output.WriteString("\nToolchains:\n")
+	var missing []string
+	for i, command := range commands {
+		if versions[i] == "" {
+			missing = append(missing, command.name)
+			continue
+		}
+		fmt.Fprintf(&output, "- %s: %s\n", command.name, versions[i])
+		metadata.Toolchains = append(metadata.Toolchains, ToolchainVersion{Name: command.name, Version: versions[i]})
+	}
+	if len(missing) > 0 {
+		fmt.Fprintf(&output, "Not installed: %s\n", strings.Join(missing, ", "))
+	}
+
+	output.WriteString("\nGit:\n")
+	branch, gitState := gitStatus(ctx)
+	metadata.GitBranch = branch
+	output.WriteString(gitState)
+
+	return WithResponseMetadata(
+		NewTextResponse(strings.TrimSpace(output.String())),
+		metadata,
+	), nil
+}
+
+func (e *envInfoTool) requestPermission(ctx context.Context, command string) (bool, error) {
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return false, fmt.Errorf("session ID and message ID are required for running a command")
+	}
+	return e.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        config.WorkingDirectory(),
+			ToolName:    EnvInfoToolName,
+			Action:      "execute",
+			Description: fmt.Sprintf("Execute command: %s", command),
+			Command:     command,
+			Params: EnvInfoPermissionsParams{
+				Command: command,
+			},
+		},
+	), nil
+}
+
+// commandVersion returns the first line of the version output of the command,
+// or an empty string when it is not installed. A sandboxed command runs in a
+// container of the sandbox, the way the bash tool runs commands.
+func commandVersion(ctx context.Context, name string, args []string, sandboxed bool) string {
+	var output string
+	var err error
+	if sandboxed {
+		quoted := []string{shellQuote(name)}
+		for _, arg := range args {
+			quoted = append(quoted, shellQuote(arg))
+		}
+		output, err = runEnvCommand(ctx, "sh", "-c", shell.SandboxCommand(strings.Join(quoted, " ")))
+		// The shell of the container exits with 127 for a command it can't find
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) && exitErr.ExitCode() == 127 {
+			return ""
+		}
+	} else {
+		if _, err := exec.LookPath(name); err != nil {
+			return ""
+		}
+		output, err = runEnvCommand(ctx, name, args...)
+	}
+	if err != nil && output == "" {
+		return "installed, version unknown"
+	}
+	line, _, _ := strings.Cut(strings.TrimSpace(output), "\n")
+	if line == "" {
+		return "installed, version unknown"
+	}
+	return strings.TrimSpace(line)
+}
+
+// gitStatus describes the git repository of the working directory and returns
+// its current branch.
+func gitStatus(ctx context.Context) (string, string) {
+	if _, err := exec.LookPath("git"); err != nil {
+		return "", "git is not installed\n"
+	}
+	output, err := runEnvCommand(ctx, "git", "status", "--porcelain", "--branch")
+	if err != nil {
+		return "", "Not a git repository\n"
+	}
+
+	var b strings.Builder
+	branch := ""
+	changed := 0
+	scanner := bufio.NewScanner(strings.NewReader(output))
+	for scanner.Scan() {
+		line := scanner.Text()
+		if header, ok := strings.CutPrefix(line, "## "); ok {
+			// e.g. "main...origin/main [ahead 1]" or "No commits yet on main"
+			branch, _, _ = strings.Cut(header, "...")
+			branch, _, _ = strings.Cut(branch, " [")
+			fmt.Fprintf(&b, "Branch: %s\n", header)
+			continue
+		}
+		if line != "" {
+			changed++
+		}
+	}
+	if commit, err := runEnvCommand(ctx, "git", "log", "-1", "--format=%h %s"); err == nil && commit != "" {
+		fmt.Fprintf(&b, "Last commit: %s\n", strings.TrimSpace(commit))
+	}
+	if changed == 0 {
+		b.WriteString("Working tree clean\n")
+	} else {
+		fmt.Fprintf(&b, "Changed files: %d\n", changed)
+	}
+	return branch, b.String()
+}
+
+func runEnvCommand(ctx context.Context, name string, args ...string) (string, error) {
+	ctx, cancel := context.WithTimeout(ctx, envInfoCommandTimeout)
+	defer cancel()
+
+	var stdout, stderr bytes.Buffer
+	cmd := exec.CommandContext(ctx, name, args...)
+	cmd.Dir = config.WorkingDirectory()
+	cmd.Stdout = &stdout
+	cmd.Stderr = &stderr
+	err := cmd.Run()
+
+	output := stdout.String()
+	if strings.TrimSpace(output) == "" {
+		// Some tools, like java, print their version to stderr
+		output = stderr.String()
+	}
+	return output, err
+}
+
+// osDescription returns the name of the operating system with its version when
+// it can be found.
+func osDescription() string {
+	switch runtime.GOOS {
+	case "linux":
+		data, err := os.ReadFile("/etc/os-release")
+		if err != nil {
+			return "linux"
+		}
+		for _, line := range strings.Split(string(data), "\n") {
+			if name, ok := strings.CutPrefix(line, "PRETTY_NAME="); ok {
+				return fmt.Sprintf("linux (%s)", strings.Trim(name, `"`))
+			}
+		}
+	case "darwin":
+		if version, err := exec.Command("sw_vers", "-productVersion").Output(); err == nil {
+			return fmt.Sprintf("darwin (macOS %s)", strings.TrimSpace(string(version)))
+		}
+	}
+	return runtime.GOOS
+}
+
+// shellPath returns the shell the bash tool runs commands with.
+func shellPath() string {
+	if cfg := config.Get(); cfg != nil && cfg.Shell.Path != "" {
+		return cfg.Shell.Path
+	}
+	if shell := os.Getenv("SHELL"); shell != "" {
+		return shell
+	}
+	return "/bin/bash"
+}
@@ -0,0 +1,25 @@
+package tools
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+func TestEnvInfoAsksForExtraTools(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+
+	permissions := &recordedPermissions{}
+	_, err = NewEnvInfoTool(permissions).Run(toolContext(), ToolCall{Input: `{"tools": ["terraform"]}`})
+	require.ErrorIs(t, err, permission.ErrorPermissionDenied)
+	require.Len(t, permissions.requests, 1)
+	assert.Equal(t, "terraform --version", permissions.requests[0].Command)
+}
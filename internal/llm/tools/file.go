@@ -1,8 +1,16 @@
 package tools
 
 import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
 	"sync"
 	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
+	"github.com/zhenbah/cryoncode/internal/permission"
 )
 
 // File record to track when files were read/written
@@ -51,3 +59,118 @@ func recordFileWrite(path string) {
 	record.writeTime = time.Now()
 	fileRecords[path] = record
 }
+
+// isOutsideSandbox reports whether the sandbox is enabled and the given absolute
+// path is outside of the working directory, in which case it must not be modified.
+// Symlinks are resolved first so they cannot be used to escape it.
+func isOutsideSandbox(path string) bool {
+	if !shell.SandboxEnabled() {
+		return false
+	}
+	return !isWithinDir(canonicalPath(config.WorkingDirectory()), canonicalPath(path))
+}
+
+// checkWritePath returns an error if the given absolute path may not be
+// changed: it's outside of the workspace while it's restricted, or outside of
+// the working directory while the sandbox is enabled. Every tool that creates,
+// changes or removes files checks their paths with it.
+func checkWritePath(path string) error {
+	if err := checkWorkspacePath(path); err != nil {
+		return err
+	}
+	if isOutsideSandbox(path) {
+		return fmt.Errorf("sandbox is enabled, cannot change files outside of the working directory: %s", path)
+	}
+	return nil
+}
+
+// deniedPaths returns whether the permissions config denies the tool a path
+// found under root. Deny rules are checked against the paths of a call before
+// running it, this keeps the tools scanning a parent of a denied path from
+// listing it or its content.
+func deniedPaths(toolName, root string) func(path string) bool {
+	if !permission.DeniesWithin(toolName, root) {
+		return func(string) bool { return false }
+	}
+	return func(path string) bool {
+		return permission.Decide(toolName, path, "") == permission.Deny
+	}
+}
+
+// checkWorkspacePath returns an error if workspace restriction is enabled and
+// the given absolute path is outside of the workspace root and of the allowed
+// paths. Symlinks are resolved first so they cannot be used to escape it.
+func checkWorkspacePath(path string) error {
+	cfg := config.Get()
+	if cfg == nil || !cfg.Workspace.Restrict {
+		return nil
+	}
+	if _, inside := ResolveWorkspacePath(path); inside {
+		return nil
+	}
+	return fmt.Errorf("access denied: %s is outside of the workspace %s. Ask the user to add it to workspace.allowedPaths if access is needed", path, config.WorkspaceRoot())
+}
+
+// ResolveWorkspacePath returns the given absolute path with its symlinks
+// resolved, and whether it is inside the workspace root or one of the allowed
+// paths, whether or not the restriction is enabled.
+func ResolveWorkspacePath(path string) (string, bool) {
+	resolved := canonicalPath(path)
+	cfg := config.Get()
+	if cfg == nil {
+		return resolved, false
+	}
+
+	root := config.WorkspaceRoot()
+	if isWithinDir(canonicalPath(root), resolved) {
+		return resolved, true
+	}
+	for _, allowed := range cfg.Workspace.AllowedPaths {
+		allowed = expandHome(allowed)
+		if !filepath.IsAbs(allowed) {
+			allowed = filepath.Join(root, allowed)
+		}
+		if isWithinDir(canonicalPath(allowed), resolved) {
+			return resolved, true
+		}
+	}
+	return resolved, false
+}
+
+// canonicalPath cleans the path and resolves symlinks in its longest existing
+// prefix, so paths of files that don't exist yet can be checked as well.
+func canonicalPath(path string) string {
+	path = filepath.Clean(path)
+	existing := path
+	rest := ""
+	for {
+		if resolved, err := filepath.EvalSymlinks(existing); err == nil {
+			return filepath.Join(resolved, rest)
+		}
+		parent := filepath.Dir(existing)
+		if parent == existing {
+			return path
+		}
+		rest = filepath.Join(filepath.Base(existing), rest)
+		existing = parent
+	}
+}
+
+func isWithinDir(dir, path string) bool {
+	rel, err := filepath.Rel(dir, path)
+	if err != nil {
+		return false
+	}
+	return rel != ".." && !strings.HasPrefix(rel, ".."+string(filepath.Separator))
+}
+
+func expandHome(path string) string {
+	if path != "~" && !strings.HasPrefix(path, "~/") {
+		return path
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return path
+	}
+	return filepath.Join(home, strings.TrimPrefix(path, "~"))
+}
@@ -0,0 +1,63 @@
+package tools
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+func TestIsWithinDir(t *testing.T) {
+	assert.True(t, isWithinDir("/workspace", "/workspace"))
+	assert.True(t, isWithinDir("/workspace", "/workspace/src/main.go"))
+	assert.True(t, isWithinDir("/workspace", "/workspace/..data"))
+	assert.False(t, isWithinDir("/workspace", "/workspace-other/file"))
+	assert.False(t, isWithinDir("/workspace", "/etc/passwd"))
+	assert.False(t, isWithinDir("/workspace", "/workspace/../etc/passwd"))
+}
+
+func TestCanonicalPathResolvesSymlinks(t *testing.T) {
+	workspace := t.TempDir()
+	outside := t.TempDir()
+	require.NoError(t, os.Symlink(outside, filepath.Join(workspace, "link")))
+
+	resolvedWorkspace := canonicalPath(workspace)
+	resolvedOutside := canonicalPath(outside)
+
+	// Files that don't exist yet are resolved through their existing parent
+	path := canonicalPath(filepath.Join(workspace, "link", "new", "file.txt"))
+	assert.Equal(t, filepath.Join(resolvedOutside, "new", "file.txt"), path)
+	assert.False(t, isWithinDir(resolvedWorkspace, path))
+}
+
+func TestCheckWritePathInSandbox(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	workDir := t.TempDir()
+	outside := t.TempDir()
+	require.NoError(t, os.Symlink(outside, filepath.Join(workDir, "link")))
+	_, err := config.Load(workDir, false)
+	require.NoError(t, err)
+	require.NoError(t, config.Update(func(cfg *config.Config) error {
+		cfg.WorkingDir = workDir
+		cfg.Workspace.Restrict = false
+		cfg.Sandbox.Enabled = true
+		return nil
+	}))
+	t.Cleanup(func() {
+		_ = config.Update(func(cfg *config.Config) error {
+			cfg.Sandbox.Enabled = false
+			return nil
+		})
+	})
+
+	assert.NoError(t, checkWritePath(filepath.Join(workDir, "file.txt")))
+	assert.Error(t, checkWritePath(filepath.Join(outside, "file.txt")))
+	// A symlink in the working directory doesn't lead out of the sandbox
+	assert.Error(t, checkWritePath(filepath.Join(workDir, "link", "file.txt")))
+}
@@ -7,6 +7,7 @@ import (
 	"fmt"
 	"os/exec"
 	"path/filepath"
+	"slices"
 	"sort"
 	"strings"
 
@@ -100,12 +101,19 @@ func (g *globTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 	searchPath := params.Path
 	if searchPath == "" {
 		searchPath = config.WorkingDirectory()
+	} else if !filepath.IsAbs(searchPath) {
+		searchPath = filepath.Join(config.WorkingDirectory(), searchPath)
 	}
 
-	files, truncated, err := globFiles(params.Pattern, searchPath, 100)
+	if err := checkWorkspacePath(searchPath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	files, truncated, err := globFiles(ctx, params.Pattern, searchPath, 100)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("error finding files: %w", err)
 	}
+	files = slices.DeleteFunc(files, deniedPaths(GlobToolName, searchPath))
 
 	var output string
 	if len(files) == 0 {
@@ -126,14 +134,18 @@ func (g *globTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 	), nil
 }
 
-func globFiles(pattern, searchPath string, limit int) ([]string, bool, error) {
-	cmdRg := fileutil.GetRgCmd(pattern)
-	if cmdRg != nil {
-		cmdRg.Dir = searchPath
-		matches, err := runRipgrep(cmdRg, searchPath, limit)
+func globFiles(ctx context.Context, pattern, searchPath string, limit int) ([]string, bool, error) {
+	if cmdRg := fileutil.GetRgCmd(pattern); cmdRg != nil {
+		// Rebuild the command so ripgrep is killed when the tool call is canceled
+		cmd := exec.CommandContext(ctx, cmdRg.Path, cmdRg.Args[1:]...)
+		cmd.Dir = searchPath
+		matches, err := runRipgrep(cmd, searchPath, limit)
 		if err == nil {
 			return matches, len(matches) >= limit && limit > 0, nil
 		}
+		if ctx.Err() != nil {
+			return nil, false, ctx.Err()
+		}
 		logging.Warn(fmt.Sprintf("Ripgrep execution failed: %v. Falling back to doublestar.", err))
 	}
 
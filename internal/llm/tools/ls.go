@@ -9,11 +9,14 @@ import (
 	"strings"
 
 	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/fileutil"
 )
 
 type LSParams struct {
-	Path   string   `json:"path"`
-	Ignore []string `json:"ignore"`
+	Path      string   `json:"path"`
+	Ignore    []string `json:"ignore"`
+	Recursive *bool    `json:"recursive,omitempty"`
+	MaxDepth  int      `json:"max_depth,omitempty"`
 }
 
 type TreeNode struct {
@@ -43,19 +46,20 @@ WHEN TO USE THIS TOOL:
 HOW TO USE:
 - Provide a path to list (defaults to current working directory)
 - Optionally specify glob patterns to ignore
+- Optionally set max_depth to limit how deep the tree goes, or recursive to false to only list the directory itself
 - Results are displayed in a tree structure
 
 FEATURES:
 - Displays a hierarchical view of files and directories
 - Automatically skips hidden files/directories (starting with '.')
-- Skips common system directories like __pycache__
+- Skips files ignored by .gitignore and common build directories like node_modules and target
 - Can filter out files matching specific patterns
 
 LIMITATIONS:
 - Results are limited to 1000 files
 - Very large directories will be truncated
 - Does not show file sizes or permissions
-- Cannot recursively list all directories in a large project
+- Cannot recursively list all directories in a large project, use max_depth to get an overview instead
 
 TIPS:
 - Use Glob tool for finding files by name patterns instead of browsing
@@ -83,6 +87,14 @@ func (l *lsTool) Info() ToolInfo {
 					"type": "string",
 				},
 			},
+			"recursive": map[string]any{
+				"type":        "boolean",
+				"description": "List subdirectories recursively (defaults to true)",
+			},
+			"max_depth": map[string]any{
+				"type":        "integer",
+				"description": "Maximum depth of subdirectories to list when recursive, 0 means no limit",
+			},
 		},
 		Required: []string{"path"},
 	}
@@ -103,11 +115,20 @@ func (l *lsTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
 		searchPath = filepath.Join(config.WorkingDirectory(), searchPath)
 	}
 
+	if err := checkWorkspacePath(searchPath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
 	if _, err := os.Stat(searchPath); os.IsNotExist(err) {
 		return NewTextErrorResponse(fmt.Sprintf("path does not exist: %s", searchPath)), nil
 	}
 
-	files, truncated, err := listDirectory(searchPath, params.Ignore, MaxLSFiles)
+	maxDepth := params.MaxDepth
+	if params.Recursive != nil && !*params.Recursive {
+		maxDepth = 1
+	}
+
+	files, truncated, err := listDirectory(ctx, searchPath, params.Ignore, deniedPaths(LSToolName, searchPath), maxDepth, MaxLSFiles)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("error listing directory: %w", err)
 	}
@@ -128,22 +149,39 @@ func (l *lsTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
 	), nil
 }
 
-func listDirectory(initialPath string, ignorePatterns []string, limit int) ([]string, bool, error) {
+// listDirectory walks the directory, skipping ignored and denied files. A
+// maxDepth of 1 only lists the entries of the directory itself, 0 means no
+// limit.
+func listDirectory(ctx context.Context, initialPath string, ignorePatterns []string, denied func(string) bool, maxDepth int, limit int) ([]string, bool, error) {
 	var results []string
 	truncated := false
+	gitignore := fileutil.NewGitIgnore(initialPath)
 
 	err := filepath.Walk(initialPath, func(path string, info os.FileInfo, err error) error {
+		// Stop walking large trees as soon as the tool call is canceled
+		if ctxErr := ctx.Err(); ctxErr != nil {
+			return ctxErr
+		}
 		if err != nil {
 			return nil // Skip files we don't have permission to access
 		}
 
-		if shouldSkip(path, ignorePatterns) {
+		if path != initialPath && (shouldSkip(path, ignorePatterns) || gitignore.Match(path, info.IsDir()) || denied(path)) {
 			if info.IsDir() {
 				return filepath.SkipDir
 			}
 			return nil
 		}
 
+		if info.IsDir() && path != initialPath {
+			gitignore.AddFile(path)
+		}
+
+		depth := 0
+		if rel, err := filepath.Rel(initialPath, path); err == nil && rel != "." {
+			depth = len(strings.Split(rel, string(filepath.Separator)))
+		}
+
 		if path != initialPath {
 			if info.IsDir() {
 				path = path + string(filepath.Separator)
@@ -151,6 +189,10 @@ func listDirectory(initialPath string, ignorePatterns []string, limit int) ([]st
 			results = append(results, path)
 		}
 
+		if info.IsDir() && maxDepth > 0 && depth >= maxDepth {
+			return filepath.SkipDir
+		}
+
 		if len(results) >= limit {
 			truncated = true
 			return filepath.SkipAll
@@ -12,6 +12,8 @@ import (
 	"github.com/stretchr/testify/require"
 )
 
+func noneDenied(string) bool { return false }
+
 func TestLsTool_Info(t *testing.T) {
 	tool := NewLsTool()
 	info := tool.Info()
@@ -402,7 +404,7 @@ func TestListDirectory(t *testing.T) {
 	}
 
 	t.Run("lists files with no limit", func(t *testing.T) {
-		files, truncated, err := listDirectory(tempDir, []string{}, 1000)
+		files, truncated, err := listDirectory(context.Background(), tempDir, []string{}, noneDenied, 0, 1000)
 		require.NoError(t, err)
 		assert.False(t, truncated)
 		
@@ -428,14 +430,37 @@ func TestListDirectory(t *testing.T) {
 	})
 
 	t.Run("respects limit and returns truncated flag", func(t *testing.T) {
-		files, truncated, err := listDirectory(tempDir, []string{}, 2)
+		files, truncated, err := listDirectory(context.Background(), tempDir, []string{}, noneDenied, 0, 2)
 		require.NoError(t, err)
 		assert.True(t, truncated)
 		assert.Len(t, files, 2)
 	})
 
+	t.Run("respects max depth", func(t *testing.T) {
+		files, _, err := listDirectory(context.Background(), tempDir, []string{}, noneDenied, 1, 1000)
+		require.NoError(t, err)
+
+		for _, file := range files {
+			rel, err := filepath.Rel(tempDir, file)
+			require.NoError(t, err)
+			assert.NotContains(t, rel, string(filepath.Separator), "Found nested path: %s", file)
+		}
+	})
+
+	t.Run("respects gitignore", func(t *testing.T) {
+		require.NoError(t, os.WriteFile(filepath.Join(tempDir, ".gitignore"), []byte("dir2/\n"), 0644))
+		defer os.Remove(filepath.Join(tempDir, ".gitignore"))
+
+		files, _, err := listDirectory(context.Background(), tempDir, []string{}, noneDenied, 0, 1000)
+		require.NoError(t, err)
+
+		for _, file := range files {
+			assert.NotContains(t, file, "dir2", "Found ignored path: %s", file)
+		}
+	})
+
 	t.Run("respects ignore patterns", func(t *testing.T) {
-		files, truncated, err := listDirectory(tempDir, []string{"*.txt"}, 1000)
+		files, truncated, err := listDirectory(context.Background(), tempDir, []string{"*.txt"}, noneDenied, 0, 1000)
 		require.NoError(t, err)
 		assert.False(t, truncated)
 		
@@ -454,4 +479,15 @@ func TestListDirectory(t *testing.T) {
 		}
 		assert.True(t, containsDir)
 	})
+
+	t.Run("skips denied paths", func(t *testing.T) {
+		denied := func(path string) bool { return path == filepath.Join(tempDir, "dir1") }
+		files, _, err := listDirectory(context.Background(), tempDir, []string{}, denied, 0, 1000)
+		require.NoError(t, err)
+
+		for _, file := range files {
+			assert.NotContains(t, file, "dir1", "Found denied path: %s", file)
+		}
+		assert.Contains(t, files, filepath.Join(tempDir, "file1.txt"))
+	})
 }
\ No newline at end of file
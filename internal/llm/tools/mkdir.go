@@ -0,0 +1,129 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type MkdirParams struct {
+	Path string `json:"path"`
+}
+
+type MkdirPermissionsParams struct {
+	Path string `json:"path"`
+}
+
+type mkdirTool struct {
+	permissions permission.Service
+}
+
+const (
+	MkdirToolName    = "mkdir"
+	mkdirDescription = `Directory creation tool that creates a new directory, including any missing parent directories.
+
+WHEN TO USE THIS TOOL:
+- Use when you need to create an empty directory
+- Helpful for scaffolding a new package or module layout
+
+HOW TO USE:
+- Provide the path of the directory to create
+- Missing parent directories are created as well
+
+FEATURES:
+- Succeeds without changes if the directory already exists
+- Reports an error if a file already exists at the path
+
+LIMITATIONS:
+- Cannot set custom permissions (directories are created with mode 0755)
+
+TIPS:
+- You don't need this tool before writing a file; the Write tool creates parent directories automatically
+- Use the LS tool to verify the location before creating directories`
+)
+
+func NewMkdirTool(permissions permission.Service) BaseTool {
+	return &mkdirTool{
+		permissions: permissions,
+	}
+}
+
+func (m *mkdirTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        MkdirToolName,
+		Description: mkdirDescription,
+		Parameters: map[string]any{
+			"path": map[string]any{
+				"type":        "string",
+				"description": "The path of the directory to create",
+			},
+		},
+		Required: []string{"path"},
+	}
+}
+
+func (m *mkdirTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params MkdirParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.Path == "" {
+		return NewTextErrorResponse("path is required"), nil
+	}
+
+	dirPath := params.Path
+	if !filepath.IsAbs(dirPath) {
+		dirPath = filepath.Join(config.WorkingDirectory(), dirPath)
+	}
+
+	if err := checkWritePath(dirPath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	info, err := os.Stat(dirPath)
+	if err == nil {
+		if !info.IsDir() {
+			return NewTextErrorResponse(fmt.Sprintf("A file already exists at this path: %s", dirPath)), nil
+		}
+		return NewTextResponse(fmt.Sprintf("Directory already exists: %s", dirPath)), nil
+	} else if !os.IsNotExist(err) {
+		return ToolResponse{}, fmt.Errorf("error checking directory: %w", err)
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session_id and message_id are required")
+	}
+
+	rootDir := config.WorkingDirectory()
+	permissionPath := filepath.Dir(dirPath)
+	if strings.HasPrefix(dirPath, rootDir) {
+		permissionPath = rootDir
+	}
+	p := m.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        permissionPath,
+			ToolName:    MkdirToolName,
+			Action:      "create",
+			Description: fmt.Sprintf("Create directory %s", dirPath),
+			Params:      MkdirPermissionsParams{Path: dirPath},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	if err := os.MkdirAll(dirPath, 0o755); err != nil {
+		return ToolResponse{}, fmt.Errorf("error creating directory: %w", err)
+	}
+
+	return NewTextResponse(fmt.Sprintf("Directory created: %s", dirPath)), nil
+}
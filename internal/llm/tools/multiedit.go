@@ -0,0 +1,301 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/diff"
+	"github.com/zhenbah/cryoncode/internal/history"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/lsp"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+)
+
+type MultiEditOperation struct {
+	OldString  string `json:"old_string"`
+	NewString  string `json:"new_string"`
+	ReplaceAll bool   `json:"replace_all,omitempty"`
+}
+
+type MultiEditParams struct {
+	FilePath string               `json:"file_path"`
+	Edits    []MultiEditOperation `json:"edits"`
+}
+
+type MultiEditResponseMetadata struct {
+	Diff      string `json:"diff"`
+	Additions int    `json:"additions"`
+	Removals  int    `json:"removals"`
+	Edits     int    `json:"edits"`
+}
+
+type multiEditTool struct {
+	lspClients  map[string]*lsp.Client
+	permissions permission.Service
+	files       history.Service
+	snapshots   snapshot.Service
+}
+
+const (
+	MultiEditToolName    = "multi_edit"
+	multiEditDescription = `Applies several search and replace edits to a single file in one operation. The edits are applied in order and either all of them succeed or the file is left unchanged.
+
+WHEN TO USE THIS TOOL:
+- Use when you need to make several changes to the same file, such as renaming a symbol or updating related code blocks
+- Prefer this over multiple calls to the Edit tool for the same file
+
+HOW TO USE:
+- Read the file with the View tool first
+- Provide the absolute file path and a list of edits
+- Each edit has an old_string to find and a new_string to replace it with
+- Set replace_all on an edit to replace every occurrence of its old_string instead of exactly one
+- To create a new file, use a single first edit with an empty old_string and the file content as new_string
+
+FEATURES:
+- Edits are applied in the given order, so later edits see the result of earlier ones
+- Atomic: if any edit fails, no change is written to the file
+- Shows a single diff of all the changes for approval
+
+LIMITATIONS:
+- Only edits one file per call
+- Without replace_all, each old_string must match exactly one location in the file at the time it is applied
+
+TIPS:
+- Include enough surrounding context in old_string to make each match unique
+- Make sure earlier edits don't change the text later edits are looking for`
+)
+
+func NewMultiEditTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service, snapshots snapshot.Service) BaseTool {
+	return &multiEditTool{
+		lspClients:  lspClients,
+		permissions: permissions,
+		files:       files,
+		snapshots:   snapshots,
+	}
+}
+
+func (m *multiEditTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        MultiEditToolName,
+		Description: multiEditDescription,
+		Parameters: map[string]any{
+			"file_path": map[string]any{
+				"type":        "string",
+				"description": "The absolute path to the file to modify",
+			},
+			"edits": map[string]any{
+				"type":        "array",
+				"description": "The edits to apply in order",
+				"items": map[string]any{
+					"type": "object",
+					"properties": map[string]any{
+						"old_string": map[string]any{
+							"type":        "string",
+							"description": "The text to replace",
+						},
+						"new_string": map[string]any{
+							"type":        "string",
+							"description": "The text to replace it with",
+						},
+						"replace_all": map[string]any{
+							"type":        "boolean",
+							"description": "Replace all occurrences of old_string (default false)",
+						},
+					},
+					"required": []string{"old_string", "new_string"},
+				},
+			},
+		},
+		Required: []string{"file_path", "edits"},
+	}
+}
+
+func (m *multiEditTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params MultiEditParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse("invalid parameters"), nil
+	}
+
+	if params.FilePath == "" {
+		return NewTextErrorResponse("file_path is required"), nil
+	}
+	if len(params.Edits) == 0 {
+		return NewTextErrorResponse("at least one edit is required"), nil
+	}
+
+	filePath := params.FilePath
+	if !filepath.IsAbs(filePath) {
+		filePath = filepath.Join(config.WorkingDirectory(), filePath)
+	}
+
+	if err := checkWritePath(filePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	oldContent := ""
+	isNewFile := false
+	fileInfo, err := os.Stat(filePath)
+	if err != nil {
+		if !os.IsNotExist(err) {
+			return ToolResponse{}, fmt.Errorf("failed to access file: %w", err)
+		}
+		if params.Edits[0].OldString != "" {
+			return NewTextErrorResponse(fmt.Sprintf("file not found: %s", filePath)), nil
+		}
+		isNewFile = true
+	} else {
+		if fileInfo.IsDir() {
+			return NewTextErrorResponse(fmt.Sprintf("path is a directory, not a file: %s", filePath)), nil
+		}
+
+		if getLastReadTime(filePath).IsZero() {
+			return NewTextErrorResponse("you must read the file before editing it. Use the View tool first"), nil
+		}
+
+		modTime := fileInfo.ModTime()
+		lastRead := getLastReadTime(filePath)
+		if modTime.After(lastRead) {
+			return NewTextErrorResponse(
+				fmt.Sprintf("file %s has been modified since it was last read (mod time: %s, last read: %s)",
+					filePath, modTime.Format(time.RFC3339), lastRead.Format(time.RFC3339),
+				)), nil
+		}
+
+		content, err := os.ReadFile(filePath)
+		if err != nil {
+			return ToolResponse{}, fmt.Errorf("failed to read file: %w", err)
+		}
+		oldContent = string(content)
+	}
+
+	newContent, err := applyEdits(oldContent, params.Edits, isNewFile)
+	if err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+	if !isNewFile && newContent == oldContent {
+		return NewTextErrorResponse("new content is the same as old content. No changes made."), nil
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for editing a file")
+	}
+
+	diff, additions, removals := diff.GenerateDiff(
+		oldContent,
+		newContent,
+		filePath,
+	)
+	rootDir := config.WorkingDirectory()
+	permissionPath := filepath.Dir(filePath)
+	if strings.HasPrefix(filePath, rootDir) {
+		permissionPath = rootDir
+	}
+	p := m.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        permissionPath,
+			ToolName:    MultiEditToolName,
+			Action:      "write",
+			Description: fmt.Sprintf("Apply %d edits to file %s", len(params.Edits), filePath),
+			Params: EditPermissionsParams{
+				FilePath:  filePath,
+				Diff:      diff,
+				Additions: additions,
+				Removals:  removals,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	if err := m.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
+	if isNewFile {
+		if err := os.MkdirAll(filepath.Dir(filePath), 0o755); err != nil {
+			return ToolResponse{}, fmt.Errorf("failed to create parent directories: %w", err)
+		}
+	}
+	if err := os.WriteFile(filePath, []byte(newContent), 0o644); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
+	}
+
+	// Check if file exists in history
+	file, err := m.files.GetByPathAndSession(ctx, filePath, sessionID)
+	if err != nil {
+		_, err = m.files.Create(ctx, sessionID, filePath, oldContent)
+		if err != nil {
+			// Log error but don't fail the operation
+			return ToolResponse{}, fmt.Errorf("error creating file history: %w", err)
+		}
+	}
+	if file.Content != oldContent {
+		// User Manually changed the content store an intermediate version
+		_, err = m.files.CreateVersion(ctx, sessionID, filePath, oldContent)
+		if err != nil {
+			logging.Debug("Error creating file history version", "error", err)
+		}
+	}
+	// Store the new version
+	_, err = m.files.CreateVersion(ctx, sessionID, filePath, newContent)
+	if err != nil {
+		logging.Debug("Error creating file history version", "error", err)
+	}
+
+	recordFileWrite(filePath)
+	recordFileRead(filePath)
+
+	waitForLspDiagnostics(ctx, filePath, m.lspClients)
+	text := fmt.Sprintf("<result>\nApplied %d edits to file: %s\n</result>\n", len(params.Edits), filePath)
+	text += getDiagnostics(filePath, m.lspClients)
+
+	return WithResponseMetadata(
+		NewTextResponse(text),
+		MultiEditResponseMetadata{
+			Diff:      diff,
+			Additions: additions,
+			Removals:  removals,
+			Edits:     len(params.Edits),
+		},
+	), nil
+}
+
+// applyEdits applies the edits in order to the content and returns the result.
+// It fails on the first edit that can't be applied.
+func applyEdits(content string, edits []MultiEditOperation, isNewFile bool) (string, error) {
+	for i, edit := range edits {
+		if edit.OldString == "" {
+			if i == 0 && isNewFile {
+				content = edit.NewString
+				continue
+			}
+			return "", fmt.Errorf("edit %d: old_string is required, an empty old_string is only allowed in the first edit to create a new file", i+1)
+		}
+		if edit.OldString == edit.NewString {
+			return "", fmt.Errorf("edit %d: old_string and new_string are the same", i+1)
+		}
+
+		count := strings.Count(content, edit.OldString)
+		switch {
+		case count == 0:
+			return "", fmt.Errorf("edit %d: old_string not found in file. Make sure it matches exactly, including whitespace and line breaks, and that earlier edits did not change it", i+1)
+		case edit.ReplaceAll:
+			content = strings.ReplaceAll(content, edit.OldString, edit.NewString)
+		case count > 1:
+			return "", fmt.Errorf("edit %d: old_string appears %d times in the file. Provide more context to make it unique or set replace_all", i+1, count)
+		default:
+			content = strings.Replace(content, edit.OldString, edit.NewString, 1)
+		}
+	}
+	return content, nil
+}
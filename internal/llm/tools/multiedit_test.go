@@ -0,0 +1,51 @@
+package tools
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestApplyEdits(t *testing.T) {
+	t.Run("applies edits in order", func(t *testing.T) {
+		content, err := applyEdits("foo bar baz", []MultiEditOperation{
+			{OldString: "foo", NewString: "qux"},
+			{OldString: "qux bar", NewString: "done"},
+		}, false)
+		require.NoError(t, err)
+		assert.Equal(t, "done baz", content)
+	})
+
+	t.Run("replaces all occurrences", func(t *testing.T) {
+		content, err := applyEdits("a a a", []MultiEditOperation{
+			{OldString: "a", NewString: "b", ReplaceAll: true},
+		}, false)
+		require.NoError(t, err)
+		assert.Equal(t, "b b b", content)
+	})
+
+	t.Run("fails on ambiguous match", func(t *testing.T) {
+		_, err := applyEdits("a a", []MultiEditOperation{
+			{OldString: "a", NewString: "b"},
+		}, false)
+		assert.ErrorContains(t, err, "edit 1: old_string appears 2 times")
+	})
+
+	t.Run("fails when a later edit does not match", func(t *testing.T) {
+		_, err := applyEdits("foo", []MultiEditOperation{
+			{OldString: "foo", NewString: "bar"},
+			{OldString: "foo", NewString: "baz"},
+		}, false)
+		assert.ErrorContains(t, err, "edit 2: old_string not found")
+	})
+
+	t.Run("creates a new file", func(t *testing.T) {
+		content, err := applyEdits("", []MultiEditOperation{
+			{OldString: "", NewString: "package main\n"},
+			{OldString: "main", NewString: "tools"},
+		}, true)
+		require.NoError(t, err)
+		assert.Equal(t, "package tools\n", content)
+	})
+}
@@ -0,0 +1,194 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+)
+
+type MvParams struct {
+	Source      string `json:"source"`
+	Destination string `json:"destination"`
+	Overwrite   bool   `json:"overwrite,omitempty"`
+}
+
+type MvPermissionsParams struct {
+	Source      string `json:"source"`
+	Destination string `json:"destination"`
+	Overwrite   bool   `json:"overwrite"`
+}
+
+type mvTool struct {
+	permissions permission.Service
+	snapshots   snapshot.Service
+}
+
+const (
+	MvToolName    = "mv"
+	mvDescription = `Move tool that moves or renames a file or directory.
+
+WHEN TO USE THIS TOOL:
+- Use when you need to rename a file or directory
+- Helpful for reorganizing the project layout without rewriting file contents
+
+HOW TO USE:
+- Provide the source path and the destination path
+- Set overwrite to true to replace an existing file at the destination
+
+FEATURES:
+- Works for both files and directories
+- Creates missing parent directories of the destination
+- Preserves file contents and permissions
+
+LIMITATIONS:
+- Will not overwrite an existing destination unless overwrite is true
+- Cannot overwrite an existing directory
+
+TIPS:
+- Remember to update imports and references to the moved file
+- Use the Grep tool to find references to the old path`
+)
+
+func NewMvTool(permissions permission.Service, snapshots snapshot.Service) BaseTool {
+	return &mvTool{
+		permissions: permissions,
+		snapshots:   snapshots,
+	}
+}
+
+func (m *mvTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        MvToolName,
+		Description: mvDescription,
+		Parameters: map[string]any{
+			"source": map[string]any{
+				"type":        "string",
+				"description": "The path of the file or directory to move",
+			},
+			"destination": map[string]any{
+				"type":        "string",
+				"description": "The new path for the file or directory",
+			},
+			"overwrite": map[string]any{
+				"type":        "boolean",
+				"description": "Replace an existing file at the destination (default: false)",
+			},
+		},
+		Required: []string{"source", "destination"},
+	}
+}
+
+func (m *mvTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params MvParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.Source == "" || params.Destination == "" {
+		return NewTextErrorResponse("source and destination are required"), nil
+	}
+
+	rootDir := config.WorkingDirectory()
+	source := params.Source
+	if !filepath.IsAbs(source) {
+		source = filepath.Join(rootDir, source)
+	}
+	destination := params.Destination
+	if !filepath.IsAbs(destination) {
+		destination = filepath.Join(rootDir, destination)
+	}
+
+	for _, path := range []string{source, destination} {
+		if err := checkWritePath(path); err != nil {
+			return NewTextErrorResponse(err.Error()), nil
+		}
+	}
+
+	if _, err := os.Stat(source); err != nil {
+		if os.IsNotExist(err) {
+			return NewTextErrorResponse(fmt.Sprintf("Source not found: %s", source)), nil
+		}
+		return ToolResponse{}, fmt.Errorf("error checking source: %w", err)
+	}
+
+	if destInfo, err := os.Stat(destination); err == nil {
+		if destInfo.IsDir() {
+			return NewTextErrorResponse(fmt.Sprintf("Destination is an existing directory: %s", destination)), nil
+		}
+		if !params.Overwrite {
+			return NewTextErrorResponse(fmt.Sprintf("Destination already exists: %s. Set overwrite to true to replace it.", destination)), nil
+		}
+	} else if !os.IsNotExist(err) {
+		return ToolResponse{}, fmt.Errorf("error checking destination: %w", err)
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session_id and message_id are required")
+	}
+
+	permissionPath := filepath.Dir(destination)
+	if strings.HasPrefix(source, rootDir) && strings.HasPrefix(destination, rootDir) {
+		permissionPath = rootDir
+	}
+	p := m.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        permissionPath,
+			ToolName:    MvToolName,
+			Action:      "move",
+			Description: fmt.Sprintf("Move %s to %s", source, destination),
+			Params: MvPermissionsParams{
+				Source:      source,
+				Destination: destination,
+				Overwrite:   params.Overwrite,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	// Both ends are snapshotted, so a revert moves the files back
+	err := filepath.WalkDir(source, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		rel, err := filepath.Rel(source, path)
+		if err != nil {
+			return err
+		}
+		for _, file := range []string{path, filepath.Join(destination, rel)} {
+			if err := m.snapshots.Take(ctx, sessionID, messageID, file); err != nil {
+				return err
+			}
+		}
+		return nil
+	})
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(destination), 0o755); err != nil {
+		return ToolResponse{}, fmt.Errorf("error creating directory: %w", err)
+	}
+
+	if err := os.Rename(source, destination); err != nil {
+		return ToolResponse{}, fmt.Errorf("error moving file: %w", err)
+	}
+
+	recordFileWrite(destination)
+
+	return NewTextResponse(fmt.Sprintf("Moved %s to %s", source, destination)), nil
+}
@@ -0,0 +1,352 @@
+package tools
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/charmbracelet/x/ansi"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+type NotebookReadParams struct {
+	FilePath string `json:"file_path"`
+	CellID   string `json:"cell_id"`
+}
+
+type NotebookReadResponseMetadata struct {
+	FilePath string `json:"file_path"`
+	Cells    int    `json:"cells"`
+	Language string `json:"language"`
+}
+
+type notebookReadTool struct{}
+
+const (
+	NotebookReadToolName    = "notebook_read"
+	notebookReadDescription = `Reads a Jupyter notebook (.ipynb) and shows its cells with their outputs.
+
+WHEN TO USE THIS TOOL:
+- Use instead of the View tool for .ipynb files, which are JSON documents that are hard to read raw
+- Use before editing a notebook with the notebook_edit tool
+
+HOW TO USE:
+- Provide the path to the notebook
+- Optionally provide a cell_id to read a single cell, either the id of the cell or its index
+
+FEATURES:
+- Shows the source of each cell with its index, id and type
+- Shows the text outputs of code cells, including streams, results and errors
+- Lists rich outputs such as images or HTML by their type
+
+LIMITATIONS:
+- Images and other binary outputs are not shown
+- Long outputs are truncated
+
+TIPS:
+- Use the index or id shown for a cell to edit it with the notebook_edit tool`
+)
+
+const (
+	maxNotebookOutputLength = 10 * 1024
+)
+
+// notebook is a parsed Jupyter notebook. The document is kept as generic JSON
+// so fields this package doesn't know about survive an edit.
+type notebook struct {
+	doc   map[string]any
+	cells []map[string]any
+}
+
+func NewNotebookReadTool() BaseTool {
+	return &notebookReadTool{}
+}
+
+func (n *notebookReadTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        NotebookReadToolName,
+		Description: notebookReadDescription,
+		Parameters: map[string]any{
+			"file_path": map[string]any{
+				"type":        "string",
+				"description": "The path to the notebook to read",
+			},
+			"cell_id": map[string]any{
+				"type":        "string",
+				"description": "The id or the 0-based index of a single cell to read",
+			},
+		},
+		Required: []string{"file_path"},
+	}
+}
+
+func (n *notebookReadTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params NotebookReadParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.FilePath == "" {
+		return NewTextErrorResponse("file_path is required"), nil
+	}
+
+	filePath := params.FilePath
+	if !filepath.IsAbs(filePath) {
+		filePath = filepath.Join(config.WorkingDirectory(), filePath)
+	}
+
+	if err := checkWorkspacePath(filePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+	if filepath.Ext(filePath) != ".ipynb" {
+		return NewTextErrorResponse(fmt.Sprintf("not a Jupyter notebook: %s", filePath)), nil
+	}
+
+	data, err := os.ReadFile(filePath)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return NewTextErrorResponse(fmt.Sprintf("file not found: %s", filePath)), nil
+		}
+		return ToolResponse{}, fmt.Errorf("error reading file: %w", err)
+	}
+
+	nb, err := parseNotebook(data)
+	if err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	start, end := 0, len(nb.cells)
+	if params.CellID != "" {
+		idx, ok := nb.findCell(params.CellID)
+		if !ok {
+			return NewTextErrorResponse(fmt.Sprintf("cell not found: %s", params.CellID)), nil
+		}
+		start, end = idx, idx+1
+	}
+
+	var output strings.Builder
+	for i := start; i < end; i++ {
+		output.WriteString(formatCell(i, nb.cells[i]))
+		if output.Len() > MaxReadSize {
+			fmt.Fprintf(&output, "\n[Notebook truncated after cell %d of %d, use cell_id to read the other cells]\n", i, len(nb.cells))
+			break
+		}
+	}
+	if len(nb.cells) == 0 {
+		output.WriteString("The notebook has no cells")
+	}
+
+	recordFileRead(filePath)
+
+	return WithResponseMetadata(
+		NewTextResponse(output.String()),
+		NotebookReadResponseMetadata{
+			FilePath: filePath,
+			Cells:    len(nb.cells),
+			Language: nb.language(),
+		},
+	), nil
+}
+
+func parseNotebook(data []byte) (*notebook, error) {
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	// Keep numbers as they are written, e.g. execution counts stay integers
+	decoder.UseNumber()
+
+	var doc map[string]any
+	if err := decoder.Decode(&doc); err != nil {
+		return nil, fmt.Errorf("invalid notebook, it is not valid JSON: %w", err)
+	}
+	rawCells, ok := doc["cells"].([]any)
+	if !ok {
+		return nil, fmt.Errorf("invalid notebook, it has no cells list")
+	}
+
+	nb := &notebook{doc: doc}
+	for i, rawCell := range rawCells {
+		cell, ok := rawCell.(map[string]any)
+		if !ok {
+			return nil, fmt.Errorf("invalid notebook, cell %d is not an object", i)
+		}
+		nb.cells = append(nb.cells, cell)
+	}
+	return nb, nil
+}
+
+// marshal writes the notebook the way Jupyter does, with sorted keys, one
+// space indentation and a trailing newline.
+func (nb *notebook) marshal() ([]byte, error) {
+	cells := make([]any, len(nb.cells))
+	for i, cell := range nb.cells {
+		cells[i] = cell
+	}
+	nb.doc["cells"] = cells
+
+	var buf bytes.Buffer
+	encoder := json.NewEncoder(&buf)
+	encoder.SetEscapeHTML(false)
+	encoder.SetIndent("", " ")
+	if err := encoder.Encode(nb.doc); err != nil {
+		return nil, err
+	}
+	return buf.Bytes(), nil
+}
+
+// findCell returns the index of the cell with the id, falling back to reading
+// the id as a 0-based index.
+func (nb *notebook) findCell(id string) (int, bool) {
+	for i, cell := range nb.cells {
+		if cellID, _ := cell["id"].(string); cellID != "" && cellID == id {
+			return i, true
+		}
+	}
+	idx, err := strconv.Atoi(id)
+	if err != nil || idx < 0 || idx >= len(nb.cells) {
+		return 0, false
+	}
+	return idx, true
+}
+
+// usesCellIDs reports whether the cells of the notebook need an id, which is
+// the case from nbformat 4.5 on.
+func (nb *notebook) usesCellIDs() bool {
+	for _, cell := range nb.cells {
+		if _, ok := cell["id"]; ok {
+			return true
+		}
+	}
+	major, _ := nb.doc["nbformat"].(json.Number)
+	minor, _ := nb.doc["nbformat_minor"].(json.Number)
+	majorVersion, _ := major.Int64()
+	minorVersion, _ := minor.Int64()
+	return majorVersion > 4 || (majorVersion == 4 && minorVersion >= 5)
+}
+
+func (nb *notebook) language() string {
+	metadata, _ := nb.doc["metadata"].(map[string]any)
+	if info, ok := metadata["language_info"].(map[string]any); ok {
+		if name, ok := info["name"].(string); ok {
+			return name
+		}
+	}
+	if spec, ok := metadata["kernelspec"].(map[string]any); ok {
+		if language, ok := spec["language"].(string); ok {
+			return language
+		}
+	}
+	return ""
+}
+
+// multilineString reads a notebook text field, which is either a string or a
+// list of lines.
+func multilineString(value any) string {
+	switch v := value.(type) {
+	case string:
+		return v
+	case []any:
+		var b strings.Builder
+		for _, line := range v {
+			if s, ok := line.(string); ok {
+				b.WriteString(s)
+			}
+		}
+		return b.String()
+	}
+	return ""
+}
+
+// splitSource splits the source into lines that keep their line break, the
+// way Jupyter stores it.
+func splitSource(source string) []any {
+	lines := []any{}
+	for _, line := range strings.SplitAfter(source, "\n") {
+		if line != "" {
+			lines = append(lines, line)
+		}
+	}
+	return lines
+}
+
+func formatCell(idx int, cell map[string]any) string {
+	cellType, _ := cell["cell_type"].(string)
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "<cell index=\"%d\"", idx)
+	if id, ok := cell["id"].(string); ok {
+		fmt.Fprintf(&b, " id=\"%s\"", id)
+	}
+	fmt.Fprintf(&b, " type=\"%s\"", cellType)
+	if count, ok := cell["execution_count"].(json.Number); ok {
+		fmt.Fprintf(&b, " execution_count=\"%s\"", count)
+	}
+	b.WriteString(">\n")
+	source := multilineString(cell["source"])
+	b.WriteString(source)
+	if source != "" && !strings.HasSuffix(source, "\n") {
+		b.WriteString("\n")
+	}
+	b.WriteString("</cell>\n")
+
+	if outputs := formatOutputs(cell); outputs != "" {
+		fmt.Fprintf(&b, "<outputs index=\"%d\">\n%s\n</outputs>\n", idx, outputs)
+	}
+	return b.String()
+}
+
+func formatOutputs(cell map[string]any) string {
+	outputs, _ := cell["outputs"].([]any)
+	var parts []string
+	for _, rawOutput := range outputs {
+		output, ok := rawOutput.(map[string]any)
+		if !ok {
+			continue
+		}
+		switch output["output_type"] {
+		case "stream":
+			parts = append(parts, multilineString(output["text"]))
+		case "execute_result", "display_data":
+			data, _ := output["data"].(map[string]any)
+			if text, ok := data["text/plain"]; ok {
+				parts = append(parts, multilineString(text))
+			}
+			var mimeTypes []string
+			for mimeType := range data {
+				if mimeType != "text/plain" {
+					mimeTypes = append(mimeTypes, mimeType)
+				}
+			}
+			sort.Strings(mimeTypes)
+			for _, mimeType := range mimeTypes {
+				parts = append(parts, fmt.Sprintf("[%s output]", mimeType))
+			}
+		case "error":
+			ename, _ := output["ename"].(string)
+			evalue, _ := output["evalue"].(string)
+			parts = append(parts, fmt.Sprintf("%s: %s", ename, evalue))
+			if traceback, ok := output["traceback"].([]any); ok {
+				var lines []string
+				for _, line := range traceback {
+					if s, ok := line.(string); ok {
+						lines = append(lines, ansi.Strip(s))
+					}
+				}
+				parts = append(parts, strings.Join(lines, "\n"))
+			}
+		}
+	}
+
+	for i, part := range parts {
+		parts[i] = strings.TrimRight(part, "\n")
+	}
+	text := strings.Join(parts, "\n")
+	if len(text) > maxNotebookOutputLength {
+		text = text[:maxNotebookOutputLength] + "\n[Output truncated]"
+	}
+	return text
+}
@@ -0,0 +1,342 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/google/uuid"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/diff"
+	"github.com/zhenbah/cryoncode/internal/history"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+)
+
+type NotebookEditParams struct {
+	FilePath  string `json:"file_path"`
+	CellID    string `json:"cell_id"`
+	NewSource string `json:"new_source"`
+	CellType  string `json:"cell_type"`
+	EditMode  string `json:"edit_mode"`
+}
+
+type NotebookEditResponseMetadata struct {
+	Diff      string `json:"diff"`
+	Additions int    `json:"additions"`
+	Removals  int    `json:"removals"`
+	EditMode  string `json:"edit_mode"`
+	CellIndex int    `json:"cell_index"`
+}
+
+type notebookEditTool struct {
+	permissions permission.Service
+	files       history.Service
+	snapshots   snapshot.Service
+}
+
+const (
+	NotebookEditToolName    = "notebook_edit"
+	notebookEditDescription = `Edits a cell of a Jupyter notebook (.ipynb): replaces its source, inserts a new cell or deletes a cell, keeping the notebook valid.
+
+WHEN TO USE THIS TOOL:
+- Use to change notebooks instead of the Edit or Write tools, which work on the raw JSON and easily corrupt it
+
+HOW TO USE:
+- Read the notebook with the notebook_read tool first
+- Choose the edit_mode: "replace" (default), "insert" or "delete"
+- replace: give the cell_id of the cell and its new_source, optionally a new cell_type
+- insert: give the new_source and the cell_type of the new cell, it is inserted after the cell with cell_id, or at the start without a cell_id
+- delete: give the cell_id of the cell to remove
+
+FEATURES:
+- cell_id is the id of the cell or its 0-based index as shown by notebook_read
+- Replacing the source of a code cell clears its outputs and execution count, since they no longer match
+- New cells get an id when the notebook format uses them
+- Other cells, outputs and notebook metadata are kept as they are
+
+LIMITATIONS:
+- Edits one cell per call
+- Can't run cells, so new outputs have to come from running the notebook
+
+TIPS:
+- Write new_source as plain code or markdown, without JSON escaping
+- Read the notebook again after inserting or deleting cells, the indexes of the following cells change`
+)
+
+func NewNotebookEditTool(permissions permission.Service, files history.Service, snapshots snapshot.Service) BaseTool {
+	return &notebookEditTool{
+		permissions: permissions,
+		files:       files,
+		snapshots:   snapshots,
+	}
+}
+
+func (n *notebookEditTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        NotebookEditToolName,
+		Description: notebookEditDescription,
+		Parameters: map[string]any{
+			"file_path": map[string]any{
+				"type":        "string",
+				"description": "The path to the notebook to edit",
+			},
+			"cell_id": map[string]any{
+				"type":        "string",
+				"description": "The id or the 0-based index of the cell to edit, or of the cell to insert after",
+			},
+			"new_source": map[string]any{
+				"type":        "string",
+				"description": "The new source of the cell",
+			},
+			"cell_type": map[string]any{
+				"type":        "string",
+				"description": "The type of the cell, required when inserting",
+				"enum":        []string{"code", "markdown"},
+			},
+			"edit_mode": map[string]any{
+				"type":        "string",
+				"description": "The kind of edit, defaults to replace",
+				"enum":        []string{"replace", "insert", "delete"},
+			},
+		},
+		Required: []string{"file_path"},
+	}
+}
+
+func (n *notebookEditTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params NotebookEditParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.FilePath == "" {
+		return NewTextErrorResponse("file_path is required"), nil
+	}
+	if params.EditMode == "" {
+		params.EditMode = "replace"
+	}
+	if params.CellType != "" && params.CellType != "code" && params.CellType != "markdown" {
+		return NewTextErrorResponse(fmt.Sprintf("invalid cell_type: %s. Use code or markdown", params.CellType)), nil
+	}
+
+	filePath := params.FilePath
+	if !filepath.IsAbs(filePath) {
+		filePath = filepath.Join(config.WorkingDirectory(), filePath)
+	}
+
+	if err := checkWritePath(filePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+	if filepath.Ext(filePath) != ".ipynb" {
+		return NewTextErrorResponse(fmt.Sprintf("not a Jupyter notebook: %s", filePath)), nil
+	}
+
+	fileInfo, err := os.Stat(filePath)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return NewTextErrorResponse(fmt.Sprintf("file not found: %s", filePath)), nil
+		}
+		return ToolResponse{}, fmt.Errorf("failed to access file: %w", err)
+	}
+	lastRead := getLastReadTime(filePath)
+	if lastRead.IsZero() {
+		return NewTextErrorResponse("you must read the notebook before editing it. Use the notebook_read tool first"), nil
+	}
+	if modTime := fileInfo.ModTime(); modTime.After(lastRead) {
+		return NewTextErrorResponse(
+			fmt.Sprintf("notebook %s has been modified since it was last read (mod time: %s, last read: %s)",
+				filePath, modTime.Format(time.RFC3339), lastRead.Format(time.RFC3339),
+			)), nil
+	}
+
+	data, err := os.ReadFile(filePath)
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to read file: %w", err)
+	}
+	nb, err := parseNotebook(data)
+	if err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	cellIndex, oldSource, newSource, err := nb.edit(params)
+	if err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	newContent, err := nb.marshal()
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to write notebook: %w", err)
+	}
+	oldContent := string(data)
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for editing a notebook")
+	}
+
+	// Show the change to the cell source rather than to the JSON document
+	diff, additions, removals := diff.GenerateDiff(
+		oldSource,
+		newSource,
+		filePath,
+	)
+	rootDir := config.WorkingDirectory()
+	permissionPath := filepath.Dir(filePath)
+	if strings.HasPrefix(filePath, rootDir) {
+		permissionPath = rootDir
+	}
+	p := n.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        permissionPath,
+			ToolName:    NotebookEditToolName,
+			Action:      "write",
+			Description: fmt.Sprintf("%s cell %d of notebook %s", strings.ToUpper(params.EditMode[:1])+params.EditMode[1:], cellIndex, filePath),
+			Params: EditPermissionsParams{
+				FilePath:  filePath,
+				Diff:      diff,
+				Additions: additions,
+				Removals:  removals,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	if err := n.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
+	if err := os.WriteFile(filePath, newContent, fileInfo.Mode().Perm()); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to write file: %w", err)
+	}
+
+	// Check if file exists in history
+	file, err := n.files.GetByPathAndSession(ctx, filePath, sessionID)
+	if err != nil {
+		_, err = n.files.Create(ctx, sessionID, filePath, oldContent)
+		if err != nil {
+			return ToolResponse{}, fmt.Errorf("error creating file history: %w", err)
+		}
+	}
+	if file.Content != oldContent {
+		// User Manually changed the content store an intermediate version
+		_, err = n.files.CreateVersion(ctx, sessionID, filePath, oldContent)
+		if err != nil {
+			logging.Debug("Error creating file history version", "error", err)
+		}
+	}
+	// Store the new version
+	_, err = n.files.CreateVersion(ctx, sessionID, filePath, string(newContent))
+	if err != nil {
+		logging.Debug("Error creating file history version", "error", err)
+	}
+
+	recordFileWrite(filePath)
+	recordFileRead(filePath)
+
+	var result string
+	switch params.EditMode {
+	case "insert":
+		result = fmt.Sprintf("Inserted cell %d in notebook: %s", cellIndex, filePath)
+	case "delete":
+		result = fmt.Sprintf("Deleted cell %d from notebook: %s", cellIndex, filePath)
+	default:
+		result = fmt.Sprintf("Replaced the source of cell %d in notebook: %s", cellIndex, filePath)
+	}
+
+	return WithResponseMetadata(
+		NewTextResponse(fmt.Sprintf("<result>\n%s\n</result>\n", result)),
+		NotebookEditResponseMetadata{
+			Diff:      diff,
+			Additions: additions,
+			Removals:  removals,
+			EditMode:  params.EditMode,
+			CellIndex: cellIndex,
+		},
+	), nil
+}
+
+// edit applies the edit to the cells of the notebook. It returns the index of
+// the edited cell with its source before and after the edit.
+func (nb *notebook) edit(params NotebookEditParams) (int, string, string, error) {
+	switch params.EditMode {
+	case "replace":
+		if params.CellID == "" {
+			return 0, "", "", fmt.Errorf("cell_id is required to replace a cell")
+		}
+		idx, ok := nb.findCell(params.CellID)
+		if !ok {
+			return 0, "", "", fmt.Errorf("cell not found: %s", params.CellID)
+		}
+		cell := nb.cells[idx]
+		oldSource := multilineString(cell["source"])
+		cellType, _ := cell["cell_type"].(string)
+		if params.CellType != "" {
+			cellType = params.CellType
+		}
+		if oldSource == params.NewSource && cellType == cell["cell_type"] {
+			return 0, "", "", fmt.Errorf("new source is the same as the current source of the cell. No changes made")
+		}
+		cell["cell_type"] = cellType
+		cell["source"] = splitSource(params.NewSource)
+		resetCellOutputs(cell)
+		return idx, oldSource, params.NewSource, nil
+
+	case "insert":
+		if params.CellType == "" {
+			return 0, "", "", fmt.Errorf("cell_type is required to insert a cell")
+		}
+		idx := 0
+		if params.CellID != "" {
+			after, ok := nb.findCell(params.CellID)
+			if !ok {
+				return 0, "", "", fmt.Errorf("cell not found: %s", params.CellID)
+			}
+			idx = after + 1
+		}
+		cell := map[string]any{
+			"cell_type": params.CellType,
+			"metadata":  map[string]any{},
+			"source":    splitSource(params.NewSource),
+		}
+		if nb.usesCellIDs() {
+			cell["id"] = strings.ReplaceAll(uuid.New().String(), "-", "")[:8]
+		}
+		resetCellOutputs(cell)
+		nb.cells = append(nb.cells[:idx], append([]map[string]any{cell}, nb.cells[idx:]...)...)
+		return idx, "", params.NewSource, nil
+
+	case "delete":
+		if params.CellID == "" {
+			return 0, "", "", fmt.Errorf("cell_id is required to delete a cell")
+		}
+		idx, ok := nb.findCell(params.CellID)
+		if !ok {
+			return 0, "", "", fmt.Errorf("cell not found: %s", params.CellID)
+		}
+		oldSource := multilineString(nb.cells[idx]["source"])
+		nb.cells = append(nb.cells[:idx], nb.cells[idx+1:]...)
+		return idx, oldSource, "", nil
+	}
+	return 0, "", "", fmt.Errorf("invalid edit_mode: %s. Use replace, insert or delete", params.EditMode)
+}
+
+// resetCellOutputs clears the outputs of a code cell and removes the fields
+// other cell types must not have.
+func resetCellOutputs(cell map[string]any) {
+	if cell["cell_type"] == "code" {
+		cell["outputs"] = []any{}
+		cell["execution_count"] = nil
+		return
+	}
+	delete(cell, "outputs")
+	delete(cell, "execution_count")
+}
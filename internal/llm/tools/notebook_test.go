@@ -0,0 +1,162 @@
+package tools
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+const testNotebook = `{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "id": "intro",
+   "metadata": {},
+   "source": [
+    "# Title\n"
+   ]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 3,
+   "id": "calc",
+   "metadata": {
+    "tags": ["keep"]
+   },
+   "outputs": [
+    {
+     "name": "stdout",
+     "output_type": "stream",
+     "text": ["4\n"]
+    },
+    {
+     "data": {
+      "image/png": "iVBORw0KGgo=",
+      "text/plain": ["<Figure>"]
+     },
+     "metadata": {},
+     "output_type": "display_data"
+    }
+   ],
+   "source": "print(2 + 2)"
+  }
+ ],
+ "metadata": {
+  "language_info": {"name": "python"}
+ },
+ "nbformat": 4,
+ "nbformat_minor": 5
+}`
+
+func TestParseNotebook(t *testing.T) {
+	nb, err := parseNotebook([]byte(testNotebook))
+	require.NoError(t, err)
+	require.Len(t, nb.cells, 2)
+	assert.Equal(t, "python", nb.language())
+	assert.True(t, nb.usesCellIDs())
+
+	idx, ok := nb.findCell("calc")
+	assert.True(t, ok)
+	assert.Equal(t, 1, idx)
+	idx, ok = nb.findCell("0")
+	assert.True(t, ok)
+	assert.Equal(t, 0, idx)
+	_, ok = nb.findCell("missing")
+	assert.False(t, ok)
+
+	_, err = parseNotebook([]byte(`{"metadata": {}}`))
+	assert.Error(t, err)
+}
+
+func TestFormatCell(t *testing.T) {
+	nb, err := parseNotebook([]byte(testNotebook))
+	require.NoError(t, err)
+
+	assert.Equal(t,
+		"<cell index=\"1\" id=\"calc\" type=\"code\" execution_count=\"3\">\nprint(2 + 2)\n</cell>\n"+
+			"<outputs index=\"1\">\n4\n<Figure>\n[image/png output]\n</outputs>\n",
+		formatCell(1, nb.cells[1]),
+	)
+}
+
+func TestNotebookEdit(t *testing.T) {
+	t.Run("replace clears the outputs", func(t *testing.T) {
+		nb, err := parseNotebook([]byte(testNotebook))
+		require.NoError(t, err)
+
+		idx, oldSource, newSource, err := nb.edit(NotebookEditParams{CellID: "calc", NewSource: "x = 1\nprint(x)", EditMode: "replace"})
+		require.NoError(t, err)
+		assert.Equal(t, 1, idx)
+		assert.Equal(t, "print(2 + 2)", oldSource)
+		assert.Equal(t, "x = 1\nprint(x)", newSource)
+		assert.Equal(t, []any{"x = 1\n", "print(x)"}, nb.cells[1]["source"])
+		assert.Equal(t, []any{}, nb.cells[1]["outputs"])
+		assert.Nil(t, nb.cells[1]["execution_count"])
+		assert.NotNil(t, nb.cells[1]["metadata"])
+	})
+
+	t.Run("replace with another type drops code fields", func(t *testing.T) {
+		nb, err := parseNotebook([]byte(testNotebook))
+		require.NoError(t, err)
+
+		_, _, _, err = nb.edit(NotebookEditParams{CellID: "1", NewSource: "Some text", CellType: "markdown", EditMode: "replace"})
+		require.NoError(t, err)
+		assert.NotContains(t, nb.cells[1], "outputs")
+		assert.NotContains(t, nb.cells[1], "execution_count")
+	})
+
+	t.Run("insert adds a cell with an id", func(t *testing.T) {
+		nb, err := parseNotebook([]byte(testNotebook))
+		require.NoError(t, err)
+
+		idx, _, _, err := nb.edit(NotebookEditParams{CellID: "intro", NewSource: "import os\n", CellType: "code", EditMode: "insert"})
+		require.NoError(t, err)
+		assert.Equal(t, 1, idx)
+		require.Len(t, nb.cells, 3)
+		assert.Len(t, nb.cells[1]["id"], 8)
+		assert.Equal(t, "calc", nb.cells[2]["id"])
+	})
+
+	t.Run("insert requires a cell type", func(t *testing.T) {
+		nb, err := parseNotebook([]byte(testNotebook))
+		require.NoError(t, err)
+
+		_, _, _, err = nb.edit(NotebookEditParams{NewSource: "x", EditMode: "insert"})
+		assert.Error(t, err)
+	})
+
+	t.Run("delete removes the cell", func(t *testing.T) {
+		nb, err := parseNotebook([]byte(testNotebook))
+		require.NoError(t, err)
+
+		_, oldSource, _, err := nb.edit(NotebookEditParams{CellID: "intro", EditMode: "delete"})
+		require.NoError(t, err)
+		assert.Equal(t, "# Title\n", oldSource)
+		require.Len(t, nb.cells, 1)
+		assert.Equal(t, "calc", nb.cells[0]["id"])
+	})
+}
+
+func TestNotebookMarshal(t *testing.T) {
+	nb, err := parseNotebook([]byte(`{"cells": [{"cell_type": "markdown", "metadata": {}, "source": ["a < b"]}], "metadata": {}, "nbformat": 4, "nbformat_minor": 4}`))
+	require.NoError(t, err)
+
+	data, err := nb.marshal()
+	require.NoError(t, err)
+	assert.Equal(t, `{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": [
+    "a < b"
+   ]
+  }
+ ],
+ "metadata": {},
+ "nbformat": 4,
+ "nbformat_minor": 4
+}
+`, string(data))
+}
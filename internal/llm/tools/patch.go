@@ -14,6 +14,7 @@ import (
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
 )
 
 type PatchParams struct {
@@ -30,6 +31,7 @@ type patchTool struct {
 	lspClients  map[string]*lsp.Client
 	permissions permission.Service
 	files       history.Service
+	snapshots   snapshot.Service
 }
 
 const (
@@ -64,11 +66,12 @@ CRITICAL REQUIREMENTS FOR USING THIS TOOL:
 The tool will apply all changes in a single atomic operation.`
 )
 
-func NewPatchTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service) BaseTool {
+func NewPatchTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service, snapshots snapshot.Service) BaseTool {
 	return &patchTool{
 		lspClients:  lspClients,
 		permissions: permissions,
 		files:       files,
+		snapshots:   snapshots,
 	}
 }
 
@@ -105,6 +108,10 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 			absPath = filepath.Join(wd, absPath)
 		}
 
+		if err := checkWritePath(absPath); err != nil {
+			return NewTextErrorResponse(err.Error()), nil
+		}
+
 		if getLastReadTime(absPath).IsZero() {
 			return NewTextErrorResponse(fmt.Sprintf("you must read the file %s before patching it. Use the FileRead tool first", filePath)), nil
 		}
@@ -140,6 +147,10 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 			absPath = filepath.Join(wd, absPath)
 		}
 
+		if err := checkWritePath(absPath); err != nil {
+			return NewTextErrorResponse(err.Error()), nil
+		}
+
 		_, err := os.Stat(absPath)
 		if err == nil {
 			return NewTextErrorResponse(fmt.Sprintf("file already exists and cannot be added: %s", absPath)), nil
@@ -191,7 +202,7 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 		switch change.Type {
 		case diff.ActionAdd:
 			dir := filepath.Dir(path)
-			patchDiff, _, _ := diff.GenerateDiff("", *change.NewContent, path)
+			patchDiff, additions, removals := diff.GenerateDiff("", *change.NewContent, path)
 			p := p.permissions.Request(
 				permission.CreatePermissionRequest{
 					SessionID:   sessionID,
@@ -200,8 +211,10 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 					Action:      "create",
 					Description: fmt.Sprintf("Create file %s", path),
 					Params: EditPermissionsParams{
-						FilePath: path,
-						Diff:     patchDiff,
+						FilePath:  path,
+						Diff:      patchDiff,
+						Additions: additions,
+						Removals:  removals,
 					},
 				},
 			)
@@ -217,7 +230,7 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 			if change.NewContent != nil {
 				newContent = *change.NewContent
 			}
-			patchDiff, _, _ := diff.GenerateDiff(currentContent, newContent, path)
+			patchDiff, additions, removals := diff.GenerateDiff(currentContent, newContent, path)
 			dir := filepath.Dir(path)
 			p := p.permissions.Request(
 				permission.CreatePermissionRequest{
@@ -227,8 +240,10 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 					Action:      "update",
 					Description: fmt.Sprintf("Update file %s", path),
 					Params: EditPermissionsParams{
-						FilePath: path,
-						Diff:     patchDiff,
+						FilePath:  path,
+						Diff:      patchDiff,
+						Additions: additions,
+						Removals:  removals,
 					},
 				},
 			)
@@ -237,7 +252,7 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 			}
 		case diff.ActionDelete:
 			dir := filepath.Dir(path)
-			patchDiff, _, _ := diff.GenerateDiff(*change.OldContent, "", path)
+			patchDiff, additions, removals := diff.GenerateDiff(*change.OldContent, "", path)
 			p := p.permissions.Request(
 				permission.CreatePermissionRequest{
 					SessionID:   sessionID,
@@ -246,8 +261,10 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 					Action:      "delete",
 					Description: fmt.Sprintf("Delete file %s", path),
 					Params: EditPermissionsParams{
-						FilePath: path,
-						Diff:     patchDiff,
+						FilePath:  path,
+						Diff:      patchDiff,
+						Additions: additions,
+						Removals:  removals,
 					},
 				},
 			)
@@ -257,6 +274,17 @@ func (p *patchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 		}
 	}
 
+	// Snapshot the files before changing them so the changes can be reverted
+	for path := range commit.Changes {
+		absPath := path
+		if !filepath.IsAbs(absPath) {
+			absPath = filepath.Join(config.WorkingDirectory(), absPath)
+		}
+		if err := p.snapshots.Take(ctx, sessionID, messageID, absPath); err != nil {
+			return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+		}
+	}
+
 	// Apply the changes to the filesystem
 	err = diff.ApplyCommit(commit, func(path string, content string) error {
 		absPath := path
@@ -0,0 +1,40 @@
+package tools
+
+import "fmt"
+
+// Registry holds the tools available to an agent. The tool definitions sent to
+// the model and the dispatch of the tool calls it makes both go through it, so
+// built-in, MCP, plugin and custom tools share the same path.
+type Registry struct {
+	tools  []BaseTool
+	byName map[string]BaseTool
+}
+
+func NewRegistry() *Registry {
+	return &Registry{
+		byName: make(map[string]BaseTool),
+	}
+}
+
+// Register adds the tool to the registry. It fails if another tool with the
+// same name is already registered.
+func (r *Registry) Register(tool BaseTool) error {
+	name := tool.Info().Name
+	if _, ok := r.byName[name]; ok {
+		return fmt.Errorf("tool %s is already registered", name)
+	}
+	r.tools = append(r.tools, tool)
+	r.byName[name] = tool
+	return nil
+}
+
+// Get returns the tool with the given name.
+func (r *Registry) Get(name string) (BaseTool, bool) {
+	tool, ok := r.byName[name]
+	return tool, ok
+}
+
+// Tools returns the registered tools in registration order.
+func (r *Registry) Tools() []BaseTool {
+	return r.tools
+}
@@ -0,0 +1,38 @@
+package tools
+
+import (
+	"context"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+type fakeTool struct {
+	name string
+}
+
+func (f *fakeTool) Info() ToolInfo {
+	return ToolInfo{Name: f.name}
+}
+
+func (f *fakeTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	return NewTextResponse(f.name), nil
+}
+
+func TestRegistry(t *testing.T) {
+	registry := NewRegistry()
+	require.NoError(t, registry.Register(&fakeTool{name: "first"}))
+	require.NoError(t, registry.Register(&fakeTool{name: "second"}))
+	assert.Error(t, registry.Register(&fakeTool{name: "first"}))
+
+	tool, ok := registry.Get("second")
+	require.True(t, ok)
+	assert.Equal(t, "second", tool.Info().Name)
+
+	_, ok = registry.Get("missing")
+	assert.False(t, ok)
+
+	assert.Len(t, registry.Tools(), 2)
+	assert.Equal(t, "first", registry.Tools()[0].Info().Name)
+}
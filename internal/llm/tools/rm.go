@@ -0,0 +1,210 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/history"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+)
+
+type RmParams struct {
+	Path      string `json:"path"`
+	Recursive bool   `json:"recursive,omitempty"`
+}
+
+type RmPermissionsParams struct {
+	Path      string `json:"path"`
+	Recursive bool   `json:"recursive"`
+	IsDir     bool   `json:"is_dir"`
+}
+
+type rmTool struct {
+	permissions permission.Service
+	files       history.Service
+	snapshots   snapshot.Service
+}
+
+const (
+	RmToolName    = "rm"
+	rmDescription = `File deletion tool that removes a file or directory from the filesystem.
+
+WHEN TO USE THIS TOOL:
+- Use when you need to delete a file that is no longer needed
+- Helpful for cleaning up generated files or removing obsolete code
+
+HOW TO USE:
+- Provide the path of the file or directory to delete
+- Set recursive to true to delete a non-empty directory and all of its contents
+
+FEATURES:
+- Deleted files are recorded in the session file history
+- Reverting the message brings the deleted files back
+- Refuses to delete the working directory itself
+
+LIMITATIONS:
+- Non-empty directories require recursive to be true
+- Symbolic links and empty directories are not brought back by a revert
+
+TIPS:
+- Use the LS tool to double check what a directory contains before deleting it recursively
+- Prefer the mv tool when you want to rename rather than delete`
+)
+
+func NewRmTool(permissions permission.Service, files history.Service, snapshots snapshot.Service) BaseTool {
+	return &rmTool{
+		permissions: permissions,
+		files:       files,
+		snapshots:   snapshots,
+	}
+}
+
+func (r *rmTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        RmToolName,
+		Description: rmDescription,
+		Parameters: map[string]any{
+			"path": map[string]any{
+				"type":        "string",
+				"description": "The path of the file or directory to delete",
+			},
+			"recursive": map[string]any{
+				"type":        "boolean",
+				"description": "Delete a directory and all of its contents (default: false)",
+			},
+		},
+		Required: []string{"path"},
+	}
+}
+
+func (r *rmTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params RmParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.Path == "" {
+		return NewTextErrorResponse("path is required"), nil
+	}
+
+	rootDir := config.WorkingDirectory()
+	targetPath := params.Path
+	if !filepath.IsAbs(targetPath) {
+		targetPath = filepath.Join(rootDir, targetPath)
+	}
+	targetPath = filepath.Clean(targetPath)
+
+	if err := checkWritePath(targetPath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	if targetPath == filepath.Clean(rootDir) || targetPath == string(filepath.Separator) {
+		return NewTextErrorResponse(fmt.Sprintf("Refusing to delete %s", targetPath)), nil
+	}
+
+	info, err := os.Stat(targetPath)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return NewTextErrorResponse(fmt.Sprintf("Path not found: %s", targetPath)), nil
+		}
+		return ToolResponse{}, fmt.Errorf("error checking path: %w", err)
+	}
+
+	if info.IsDir() && !params.Recursive {
+		entries, err := os.ReadDir(targetPath)
+		if err != nil {
+			return ToolResponse{}, fmt.Errorf("error reading directory: %w", err)
+		}
+		if len(entries) > 0 {
+			return NewTextErrorResponse(fmt.Sprintf("Directory is not empty: %s. Set recursive to true to delete it and its contents.", targetPath)), nil
+		}
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session_id and message_id are required")
+	}
+
+	permissionPath := filepath.Dir(targetPath)
+	if strings.HasPrefix(targetPath, rootDir) {
+		permissionPath = rootDir
+	}
+	description := fmt.Sprintf("Delete file %s", targetPath)
+	if info.IsDir() {
+		description = fmt.Sprintf("Delete directory %s", targetPath)
+		if params.Recursive {
+			description += " and all of its contents"
+		}
+	}
+	p := r.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        permissionPath,
+			ToolName:    RmToolName,
+			Action:      "delete",
+			Description: description,
+			Params: RmPermissionsParams{
+				Path:      targetPath,
+				Recursive: params.Recursive,
+				IsDir:     info.IsDir(),
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	if err := snapshotFiles(ctx, r.snapshots, sessionID, messageID, targetPath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
+	var oldContent []byte
+	if !info.IsDir() {
+		oldContent, _ = os.ReadFile(targetPath)
+	}
+
+	if params.Recursive {
+		err = os.RemoveAll(targetPath)
+	} else {
+		err = os.Remove(targetPath)
+	}
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("error deleting path: %w", err)
+	}
+
+	if !info.IsDir() {
+		// Record the deletion as an empty version so the change shows up in the session history
+		if _, err := r.files.GetByPathAndSession(ctx, targetPath, sessionID); err != nil {
+			if _, err := r.files.Create(ctx, sessionID, targetPath, string(oldContent)); err != nil {
+				logging.Debug("Error creating file history", "error", err)
+			}
+		}
+		if _, err := r.files.CreateVersion(ctx, sessionID, targetPath, ""); err != nil {
+			logging.Debug("Error creating file history version", "error", err)
+		}
+	}
+
+	return NewTextResponse(fmt.Sprintf("Deleted: %s", targetPath)), nil
+}
+
+// snapshotFiles takes a snapshot of the file, or of every file under the
+// directory, so reverting the message brings them back
+func snapshotFiles(ctx context.Context, snapshots snapshot.Service, sessionID, messageID, path string) error {
+	return filepath.WalkDir(path, func(file string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		return snapshots.Take(ctx, sessionID, messageID, file)
+	})
+}
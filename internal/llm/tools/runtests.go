@@ -0,0 +1,343 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type RunTestsParams struct {
+	Framework string `json:"framework,omitempty"`
+	Path      string `json:"path,omitempty"`
+	Filter    string `json:"filter,omitempty"`
+	Timeout   int    `json:"timeout,omitempty"`
+}
+
+type RunTestsPermissionsParams struct {
+	Framework string `json:"framework"`
+	Command   string `json:"command"`
+}
+
+type RunTestsResponseMetadata struct {
+	Framework string `json:"framework"`
+	Command   string `json:"command"`
+	Passed    int    `json:"passed"`
+	Failed    int    `json:"failed"`
+	Skipped   int    `json:"skipped"`
+	ExitCode  int    `json:"exit_code"`
+	StartTime int64  `json:"start_time"`
+	EndTime   int64  `json:"end_time"`
+}
+
+type runTestsTool struct {
+	permissions permission.Service
+}
+
+const (
+	RunTestsToolName    = "run_tests"
+	runTestsDescription = `Runs the project's test suite and returns a structured pass/fail summary followed by the test output.
+
+WHEN TO USE THIS TOOL:
+- Use after making changes to verify that nothing is broken
+- Helpful for checking whether a specific failing test has been fixed
+- Prefer this over running test commands through the Bash tool
+
+HOW TO USE:
+- Call without parameters to detect the project type and run the whole test suite
+- Optionally provide a path to run the tests of a sub-directory or package
+- Optionally provide a filter to only run tests whose name matches it
+- Optionally force the framework when the project uses several (go, cargo, npm, pytest)
+
+FEATURES:
+- Detects the project type from go.mod, Cargo.toml, package.json or Python project files
+- Reports the number of passed, failed and skipped tests
+- Output is truncated to keep the most relevant parts
+
+LIMITATIONS:
+- Only supports go, cargo, npm and pytest projects
+- Counts are parsed from the test runner output and may be incomplete for custom reporters
+- Default timeout is 5 minutes (max 10 minutes)
+
+TIPS:
+- Run a focused subset with filter while iterating, then the full suite before finishing
+- Read the failure output carefully before changing code`
+)
+
+const (
+	TestFrameworkGo     = "go"
+	TestFrameworkCargo  = "cargo"
+	TestFrameworkNpm    = "npm"
+	TestFrameworkPytest = "pytest"
+
+	defaultTestTimeout = 5 * 60 * 1000
+)
+
+var (
+	goTestPassRegex      = regexp.MustCompile(`(?m)^\s*--- PASS: `)
+	goTestFailRegex      = regexp.MustCompile(`(?m)^\s*--- FAIL: `)
+	goTestSkipRegex      = regexp.MustCompile(`(?m)^\s*--- SKIP: `)
+	cargoTestResultRegex = regexp.MustCompile(`test result: \w+\. (\d+) passed; (\d+) failed; (\d+) ignored`)
+	pytestSummaryRegex   = regexp.MustCompile(`(\d+) (passed|failed|skipped|error|errors)`)
+	jestSummaryRegex     = regexp.MustCompile(`(?m)^Tests:\s+(.*)$`)
+	jestCountRegex       = regexp.MustCompile(`(\d+) (passed|failed|skipped|todo)`)
+)
+
+func NewRunTestsTool(permissions permission.Service) BaseTool {
+	return &runTestsTool{
+		permissions: permissions,
+	}
+}
+
+func (r *runTestsTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        RunTestsToolName,
+		Description: runTestsDescription,
+		Parameters: map[string]any{
+			"framework": map[string]any{
+				"type":        "string",
+				"description": "The test framework to use; detected from the project files when omitted",
+				"enum":        []string{TestFrameworkGo, TestFrameworkCargo, TestFrameworkNpm, TestFrameworkPytest},
+			},
+			"path": map[string]any{
+				"type":        "string",
+				"description": "Optional directory or package to run the tests for",
+			},
+			"filter": map[string]any{
+				"type":        "string",
+				"description": "Optional test name pattern to only run matching tests",
+			},
+			"timeout": map[string]any{
+				"type":        "number",
+				"description": "Optional timeout in milliseconds (max 600000)",
+			},
+		},
+		Required: []string{},
+	}
+}
+
+func (r *runTestsTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params RunTestsParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.Timeout > MaxTimeout {
+		params.Timeout = MaxTimeout
+	} else if params.Timeout <= 0 {
+		params.Timeout = defaultTestTimeout
+	}
+
+	workDir := config.WorkingDirectory()
+	framework := params.Framework
+	if framework == "" {
+		framework = detectTestFramework(workDir)
+		if framework == "" {
+			return NewTextErrorResponse("Could not detect the project type. Specify the framework parameter (go, cargo, npm or pytest)."), nil
+		}
+	}
+
+	command, err := buildTestCommand(framework, params.Path, params.Filter)
+	if err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for running tests")
+	}
+
+	p := r.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        workDir,
+			ToolName:    RunTestsToolName,
+			Action:      "execute",
+			Description: fmt.Sprintf("Run tests: %s", command),
+			Command:     command,
+			Params: RunTestsPermissionsParams{
+				Framework: framework,
+				Command:   command,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	execCommand := command
+	if shell.SandboxEnabled() {
+		execCommand = shell.SandboxCommand(command)
+	}
+	startTime := time.Now()
+	sh := shell.GetPersistentShell(workDir)
+	stdout, stderr, exitCode, interrupted, err := sh.Exec(ctx, fmt.Sprintf("(cd %s && %s)", shellQuote(workDir), execCommand), params.Timeout)
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("error running tests: %w", err)
+	}
+
+	output := stdout
+	if stderr != "" {
+		if output != "" {
+			output += "\n"
+		}
+		output += stderr
+	}
+
+	summary := parseTestSummary(framework, output)
+	metadata := RunTestsResponseMetadata{
+		Framework: framework,
+		Command:   command,
+		Passed:    summary.passed,
+		Failed:    summary.failed,
+		Skipped:   summary.skipped,
+		ExitCode:  exitCode,
+		StartTime: startTime.UnixMilli(),
+		EndTime:   time.Now().UnixMilli(),
+	}
+
+	status := "PASSED"
+	if interrupted {
+		status = "TIMED OUT"
+	} else if exitCode != 0 {
+		status = "FAILED"
+	}
+
+	result := fmt.Sprintf("<summary>\nStatus: %s\nCommand: %s\nPassed: %d\nFailed: %d\nSkipped: %d\nExit code: %d\n</summary>\n\n<output>\n%s\n</output>",
+		status, command, summary.passed, summary.failed, summary.skipped, exitCode, truncateOutput(output))
+
+	return WithResponseMetadata(NewTextResponse(result), metadata), nil
+}
+
+// detectTestFramework looks for well known project files in the working directory.
+func detectTestFramework(dir string) string {
+	exists := func(name string) bool {
+		_, err := os.Stat(filepath.Join(dir, name))
+		return err == nil
+	}
+
+	switch {
+	case exists("go.mod"):
+		return TestFrameworkGo
+	case exists("Cargo.toml"):
+		return TestFrameworkCargo
+	case exists("package.json"):
+		return TestFrameworkNpm
+	case exists("pytest.ini"), exists("pyproject.toml"), exists("setup.py"), exists("setup.cfg"), exists("tox.ini"):
+		return TestFrameworkPytest
+	}
+	return ""
+}
+
+func buildTestCommand(framework, path, filter string) (string, error) {
+	switch framework {
+	case TestFrameworkGo:
+		pkg := "./..."
+		if path != "" {
+			pkg = strings.TrimSuffix(path, "/") + "/..."
+			if !filepath.IsAbs(path) && !strings.HasPrefix(path, "./") {
+				pkg = "./" + pkg
+			}
+		}
+		cmd := "go test -v " + shellQuote(pkg)
+		if filter != "" {
+			cmd += " -run " + shellQuote(filter)
+		}
+		return cmd, nil
+	case TestFrameworkCargo:
+		cmd := "cargo test"
+		if path != "" {
+			cmd += " --manifest-path " + shellQuote(filepath.Join(path, "Cargo.toml"))
+		}
+		if filter != "" {
+			cmd += " " + shellQuote(filter)
+		}
+		return cmd, nil
+	case TestFrameworkNpm:
+		cmd := "npm test"
+		if path != "" {
+			cmd += " --prefix " + shellQuote(path)
+		}
+		if filter != "" {
+			cmd += " -- -t " + shellQuote(filter)
+		}
+		return cmd, nil
+	case TestFrameworkPytest:
+		cmd := "python -m pytest"
+		if path != "" {
+			cmd += " " + shellQuote(path)
+		}
+		if filter != "" {
+			cmd += " -k " + shellQuote(filter)
+		}
+		return cmd, nil
+	}
+	return "", fmt.Errorf("unsupported test framework: %s", framework)
+}
+
+type testSummary struct {
+	passed  int
+	failed  int
+	skipped int
+}
+
+func parseTestSummary(framework, output string) testSummary {
+	var summary testSummary
+	switch framework {
+	case TestFrameworkGo:
+		summary.passed = len(goTestPassRegex.FindAllString(output, -1))
+		summary.failed = len(goTestFailRegex.FindAllString(output, -1))
+		summary.skipped = len(goTestSkipRegex.FindAllString(output, -1))
+	case TestFrameworkCargo:
+		for _, match := range cargoTestResultRegex.FindAllStringSubmatch(output, -1) {
+			summary.passed += atoi(match[1])
+			summary.failed += atoi(match[2])
+			summary.skipped += atoi(match[3])
+		}
+	case TestFrameworkPytest:
+		lines := strings.Split(strings.TrimSpace(output), "\n")
+		last := lines[len(lines)-1]
+		for _, match := range pytestSummaryRegex.FindAllStringSubmatch(last, -1) {
+			switch match[2] {
+			case "passed":
+				summary.passed = atoi(match[1])
+			case "failed", "error", "errors":
+				summary.failed += atoi(match[1])
+			case "skipped":
+				summary.skipped = atoi(match[1])
+			}
+		}
+	case TestFrameworkNpm:
+		if line := jestSummaryRegex.FindStringSubmatch(output); line != nil {
+			for _, match := range jestCountRegex.FindAllStringSubmatch(line[1], -1) {
+				switch match[2] {
+				case "passed":
+					summary.passed = atoi(match[1])
+				case "failed":
+					summary.failed = atoi(match[1])
+				case "skipped", "todo":
+					summary.skipped += atoi(match[1])
+				}
+			}
+		}
+	}
+	return summary
+}
+
+func atoi(s string) int {
+	n, _ := strconv.Atoi(s)
+	return n
+}
+
+func shellQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
@@ -0,0 +1,62 @@
+package tools
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestDetectTestFramework(t *testing.T) {
+	testCases := []struct {
+		file     string
+		expected string
+	}{
+		{file: "go.mod", expected: TestFrameworkGo},
+		{file: "Cargo.toml", expected: TestFrameworkCargo},
+		{file: "package.json", expected: TestFrameworkNpm},
+		{file: "pyproject.toml", expected: TestFrameworkPytest},
+		{file: "README.md", expected: ""},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.file, func(t *testing.T) {
+			dir := t.TempDir()
+			require.NoError(t, os.WriteFile(filepath.Join(dir, tc.file), []byte(""), 0o644))
+			assert.Equal(t, tc.expected, detectTestFramework(dir))
+		})
+	}
+}
+
+func TestBuildTestCommand(t *testing.T) {
+	cmd, err := buildTestCommand(TestFrameworkGo, "internal/tools", "TestLs")
+	require.NoError(t, err)
+	assert.Equal(t, "go test -v './internal/tools/...' -run 'TestLs'", cmd)
+
+	cmd, err = buildTestCommand(TestFrameworkPytest, "", "test_parse")
+	require.NoError(t, err)
+	assert.Equal(t, "python -m pytest -k 'test_parse'", cmd)
+
+	_, err = buildTestCommand("maven", "", "")
+	assert.Error(t, err)
+}
+
+func TestParseTestSummary(t *testing.T) {
+	goOutput := "=== RUN   TestA\n--- PASS: TestA (0.00s)\n=== RUN   TestB\n--- FAIL: TestB (0.00s)\n    --- SKIP: TestB/sub (0.00s)\nFAIL"
+	summary := parseTestSummary(TestFrameworkGo, goOutput)
+	assert.Equal(t, testSummary{passed: 1, failed: 1, skipped: 1}, summary)
+
+	cargoOutput := "test result: ok. 3 passed; 0 failed; 1 ignored; 0 measured\ntest result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured"
+	summary = parseTestSummary(TestFrameworkCargo, cargoOutput)
+	assert.Equal(t, testSummary{passed: 5, failed: 1, skipped: 1}, summary)
+
+	pytestOutput := "collected 6 items\n\n==== 1 failed, 4 passed, 1 skipped in 0.12s ===="
+	summary = parseTestSummary(TestFrameworkPytest, pytestOutput)
+	assert.Equal(t, testSummary{passed: 4, failed: 1, skipped: 1}, summary)
+
+	jestOutput := "Test Suites: 1 failed, 1 total\nTests:       2 failed, 10 passed, 12 total"
+	summary = parseTestSummary(TestFrameworkNpm, jestOutput)
+	assert.Equal(t, testSummary{passed: 10, failed: 2}, summary)
+}
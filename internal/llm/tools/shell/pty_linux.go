@@ -0,0 +1,71 @@
+//go:build linux
+
+package shell
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"syscall"
+
+	"golang.org/x/sys/unix"
+)
+
+// startTerminal starts the command attached to a new pseudo terminal and
+// returns its master side.
+func startTerminal(cmd *exec.Cmd, cols, rows int) (terminal, bool, error) {
+	master, slave, err := openPTY()
+	if err != nil {
+		return nil, false, err
+	}
+	defer slave.Close()
+
+	if err := unix.IoctlSetWinsize(int(master.Fd()), unix.TIOCSWINSZ, &unix.Winsize{
+		Row: uint16(rows),
+		Col: uint16(cols),
+	}); err != nil {
+		master.Close()
+		return nil, false, fmt.Errorf("failed to set terminal size: %w", err)
+	}
+
+	cmd.Stdin = slave
+	cmd.Stdout = slave
+	cmd.Stderr = slave
+	// Make the terminal the controlling terminal of a new session, so job
+	// control and Ctrl+C work like in a real terminal
+	cmd.SysProcAttr = &syscall.SysProcAttr{
+		Setsid:  true,
+		Setctty: true,
+	}
+	if err := cmd.Start(); err != nil {
+		master.Close()
+		return nil, false, err
+	}
+	return master, true, nil
+}
+
+func openPTY() (*os.File, *os.File, error) {
+	master, err := os.OpenFile("/dev/ptmx", os.O_RDWR|syscall.O_NOCTTY|syscall.O_CLOEXEC, 0)
+	if err != nil {
+		return nil, nil, fmt.Errorf("failed to open pseudo terminal: %w", err)
+	}
+
+	fd := int(master.Fd())
+	// Unlock the slave side and find its number, like unlockpt and ptsname
+	if err := unix.IoctlSetPointerInt(fd, unix.TIOCSPTLCK, 0); err != nil {
+		master.Close()
+		return nil, nil, fmt.Errorf("failed to unlock pseudo terminal: %w", err)
+	}
+	n, err := unix.IoctlGetInt(fd, unix.TIOCGPTN)
+	if err != nil {
+		master.Close()
+		return nil, nil, fmt.Errorf("failed to get pseudo terminal number: %w", err)
+	}
+
+	slave, err := os.OpenFile(fmt.Sprintf("/dev/pts/%d", n), os.O_RDWR|syscall.O_NOCTTY, 0)
+	if err != nil {
+		master.Close()
+		return nil, nil, fmt.Errorf("failed to open pseudo terminal: %w", err)
+	}
+	return master, slave, nil
+}
@@ -0,0 +1,52 @@
+//go:build !linux
+
+package shell
+
+import (
+	"io"
+	"os"
+	"os/exec"
+)
+
+// pipeTerminal connects to a command through pipes where pseudo terminals are
+// not supported. Stdout and stderr share one pipe so the output keeps its order.
+type pipeTerminal struct {
+	output *os.File
+	input  io.WriteCloser
+}
+
+func (p *pipeTerminal) Read(b []byte) (int, error) {
+	return p.output.Read(b)
+}
+
+func (p *pipeTerminal) Write(b []byte) (int, error) {
+	return p.input.Write(b)
+}
+
+func (p *pipeTerminal) Close() error {
+	p.input.Close()
+	return p.output.Close()
+}
+
+// startTerminal starts the command with pipes for its input and output.
+func startTerminal(cmd *exec.Cmd, cols, rows int) (terminal, bool, error) {
+	input, err := cmd.StdinPipe()
+	if err != nil {
+		return nil, false, err
+	}
+	output, writer, err := os.Pipe()
+	if err != nil {
+		return nil, false, err
+	}
+	cmd.Stdout = writer
+	cmd.Stderr = writer
+	if err := cmd.Start(); err != nil {
+		output.Close()
+		writer.Close()
+		return nil, false, err
+	}
+	// The child has its own copy of the write end, closing ours lets reads end
+	// when it exits
+	writer.Close()
+	return &pipeTerminal{output: output, input: input}, false, nil
+}
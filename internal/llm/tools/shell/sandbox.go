@@ -0,0 +1,48 @@
+package shell
+
+import (
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+const (
+	defaultSandboxRuntime = "docker"
+	defaultSandboxImage   = "debian:stable-slim"
+)
+
+// SandboxEnabled reports whether model driven commands should run inside the sandbox.
+func SandboxEnabled() bool {
+	cfg := config.Get()
+	return cfg != nil && cfg.Sandbox.Enabled
+}
+
+// SandboxCommand wraps a command so it runs in a throwaway container that only
+// has access to the working directory. The working directory is mounted at the
+// same path so file paths in the output match the host.
+func SandboxCommand(command string) string {
+	sandboxCfg := config.Get().Sandbox
+
+	runtime := sandboxCfg.Runtime
+	if runtime == "" {
+		runtime = defaultSandboxRuntime
+	}
+	image := sandboxCfg.Image
+	if image == "" {
+		image = defaultSandboxImage
+	}
+
+	workDir := config.WorkingDirectory()
+	args := []string{
+		runtime, "run", "--rm", "-i",
+		"--user", `"$(id -u):$(id -g)"`,
+		"-v", shellQuote(workDir + ":" + workDir),
+		"-w", `"$PWD"`,
+	}
+	if !sandboxCfg.Network {
+		args = append(args, "--network", "none")
+	}
+	args = append(args, image, "sh", "-c", shellQuote(command))
+
+	return strings.Join(args, " ")
+}
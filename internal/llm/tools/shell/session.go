@@ -0,0 +1,321 @@
+package shell
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/charmbracelet/x/ansi"
+)
+
+const (
+	// MaxSessions is the number of shell sessions that can run at the same time
+	MaxSessions = 10
+
+	// maxSessionBuffer bounds the unread output kept for a session, older
+	// output is dropped first
+	maxSessionBuffer = 1024 * 1024
+
+	// outputSettleTime is how long the output has to be quiet before a read
+	// returns, so a read doesn't stop in the middle of a burst of output
+	outputSettleTime = 300 * time.Millisecond
+
+	sessionCols = 200
+	sessionRows = 50
+)
+
+// terminal is the connection to the process of a session, either the master
+// side of a pseudo terminal or a pair of pipes.
+type terminal interface {
+	io.ReadWriteCloser
+}
+
+// Session is a long lived interactive shell. Unlike the persistent shell used
+// by the bash tool, commands are sent to it as input and its output is read
+// as it comes, so it suits interactive programs, REPLs and long running
+// processes.
+type Session struct {
+	ID        string
+	Shell     string
+	StartedAt time.Time
+	// PTY reports whether the session runs in a pseudo terminal
+	PTY bool
+
+	cmd  *exec.Cmd
+	term terminal
+
+	mu       sync.Mutex
+	output   []byte
+	dropped  int
+	exitCode int
+
+	updated chan struct{}
+	done    chan struct{}
+}
+
+var (
+	sessionsMu sync.Mutex
+	sessions   = map[string]*Session{}
+)
+
+// StartSession starts a new shell session with the id in the directory.
+func StartSession(id, cwd string) (*Session, error) {
+	sessionsMu.Lock()
+	defer sessionsMu.Unlock()
+
+	if s, ok := sessions[id]; ok {
+		if s.Alive() {
+			return nil, fmt.Errorf("shell session %s is already running", id)
+		}
+		s.close()
+	}
+	running := 0
+	for _, s := range sessions {
+		if s.Alive() {
+			running++
+		}
+	}
+	if running >= MaxSessions {
+		return nil, fmt.Errorf("too many shell sessions, close one first (limit %d)", MaxSessions)
+	}
+
+	shellPath, shellArgs := configuredShell()
+	cmd := exec.Command(shellPath, shellArgs...)
+	cmd.Dir = cwd
+	cmd.Env = append(os.Environ(),
+		"TERM=dumb",
+		"NO_COLOR=1",
+		"PAGER=cat",
+		"GIT_PAGER=cat",
+		"GIT_EDITOR=true",
+	)
+
+	term, isPTY, err := startTerminal(cmd, sessionCols, sessionRows)
+	if err != nil {
+		return nil, fmt.Errorf("failed to start shell: %w", err)
+	}
+
+	s := &Session{
+		ID:        id,
+		Shell:     shellPath,
+		StartedAt: time.Now(),
+		PTY:       isPTY,
+		cmd:       cmd,
+		term:      term,
+		updated:   make(chan struct{}, 1),
+		done:      make(chan struct{}),
+	}
+	go s.readOutput()
+	sessions[id] = s
+	return s, nil
+}
+
+// GetSession returns the session with the id.
+func GetSession(id string) (*Session, bool) {
+	sessionsMu.Lock()
+	defer sessionsMu.Unlock()
+	s, ok := sessions[id]
+	return s, ok
+}
+
+// ListSessions returns the sessions ordered by start time.
+func ListSessions() []*Session {
+	sessionsMu.Lock()
+	defer sessionsMu.Unlock()
+
+	list := make([]*Session, 0, len(sessions))
+	for _, s := range sessions {
+		list = append(list, s)
+	}
+	sort.Slice(list, func(i, j int) bool {
+		return list[i].StartedAt.Before(list[j].StartedAt)
+	})
+	return list
+}
+
+// CloseSession stops the session with the id and forgets it.
+func CloseSession(id string) error {
+	sessionsMu.Lock()
+	s, ok := sessions[id]
+	delete(sessions, id)
+	sessionsMu.Unlock()
+
+	if !ok {
+		return fmt.Errorf("shell session not found: %s", id)
+	}
+	s.close()
+	return nil
+}
+
+// CloseAllSessions stops every session, it is called on shutdown.
+func CloseAllSessions() {
+	sessionsMu.Lock()
+	list := make([]*Session, 0, len(sessions))
+	for id, s := range sessions {
+		list = append(list, s)
+		delete(sessions, id)
+	}
+	sessionsMu.Unlock()
+
+	for _, s := range list {
+		s.close()
+	}
+}
+
+// Alive reports whether the shell of the session is still running.
+func (s *Session) Alive() bool {
+	select {
+	case <-s.done:
+		return false
+	default:
+		return true
+	}
+}
+
+// ExitCode returns the exit code of the shell once it has stopped.
+func (s *Session) ExitCode() int {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.exitCode
+}
+
+// Send writes the input to the shell. A line break is added when missing so
+// the input is run as a command.
+func (s *Session) Send(input string) error {
+	if !s.Alive() {
+		return errors.New("shell session has exited")
+	}
+	if !strings.HasSuffix(input, "\n") {
+		input += "\n"
+	}
+	_, err := s.term.Write([]byte(input))
+	return err
+}
+
+// Interrupt stops the command running in the foreground of the session, like
+// pressing Ctrl+C.
+func (s *Session) Interrupt() error {
+	if !s.Alive() {
+		return errors.New("shell session has exited")
+	}
+	if s.PTY {
+		// The terminal turns the character into SIGINT for the foreground job
+		_, err := s.term.Write([]byte{0x03})
+		return err
+	}
+	return s.cmd.Process.Signal(os.Interrupt)
+}
+
+// Read waits for output of the session and returns the output produced since
+// the last read. It returns once the output has been quiet for a moment, the
+// shell exits or the wait time is over.
+func (s *Session) Read(ctx context.Context, wait time.Duration) string {
+	timeout := time.NewTimer(wait)
+	defer timeout.Stop()
+	idle := time.NewTimer(outputSettleTime)
+	defer idle.Stop()
+
+loop:
+	for {
+		select {
+		case <-ctx.Done():
+			break loop
+		case <-timeout.C:
+			break loop
+		case <-s.done:
+			break loop
+		case <-s.updated:
+			idle.Reset(outputSettleTime)
+		case <-idle.C:
+			if s.hasOutput() {
+				break loop
+			}
+		}
+	}
+	return s.takeOutput()
+}
+
+func (s *Session) hasOutput() bool {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return len(s.output) > 0
+}
+
+func (s *Session) takeOutput() string {
+	s.mu.Lock()
+	output, dropped := s.output, s.dropped
+	s.output, s.dropped = nil, 0
+	s.mu.Unlock()
+
+	text := CleanTerminalOutput(string(output))
+	if dropped > 0 {
+		text = fmt.Sprintf("[%d bytes of earlier output dropped]\n%s", dropped, text)
+	}
+	return text
+}
+
+func (s *Session) readOutput() {
+	buf := make([]byte, 32*1024)
+	for {
+		n, err := s.term.Read(buf)
+		if n > 0 {
+			s.mu.Lock()
+			s.output = append(s.output, buf[:n]...)
+			if over := len(s.output) - maxSessionBuffer; over > 0 {
+				s.output = s.output[over:]
+				s.dropped += over
+			}
+			s.mu.Unlock()
+
+			select {
+			case s.updated <- struct{}{}:
+			default:
+			}
+		}
+		if err != nil {
+			// The master side of a pseudo terminal fails with EIO once the
+			// shell has exited, pipes return EOF
+			break
+		}
+	}
+
+	err := s.cmd.Wait()
+	s.mu.Lock()
+	if exitErr, ok := err.(*exec.ExitError); ok {
+		s.exitCode = exitErr.ExitCode()
+	}
+	s.mu.Unlock()
+	close(s.done)
+}
+
+func (s *Session) close() {
+	if s.Alive() && s.cmd.Process != nil {
+		s.cmd.Process.Kill()
+	}
+	s.term.Close()
+}
+
+// CleanTerminalOutput removes escape sequences and carriage returns from the
+// output of a terminal. Lines redrawn with a carriage return, like progress
+// bars, keep only their last state.
+func CleanTerminalOutput(output string) string {
+	output = ansi.Strip(output)
+	output = strings.ReplaceAll(output, "\r\n", "\n")
+
+	lines := strings.Split(output, "\n")
+	for i, line := range lines {
+		line = strings.TrimRight(line, "\r")
+		if idx := strings.LastIndex(line, "\r"); idx >= 0 {
+			line = line[idx+1:]
+		}
+		lines[i] = line
+	}
+	return strings.Join(lines, "\n")
+}
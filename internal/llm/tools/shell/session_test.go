@@ -0,0 +1,37 @@
+package shell
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestCleanTerminalOutput(t *testing.T) {
+	tests := []struct {
+		name   string
+		output string
+		want   string
+	}{
+		{
+			name:   "converts line endings",
+			output: "one\r\ntwo\r\n",
+			want:   "one\ntwo\n",
+		},
+		{
+			name:   "removes escape sequences",
+			output: "\x1b[1;32mok\x1b[0m done",
+			want:   "ok done",
+		},
+		{
+			name:   "keeps the last state of redrawn lines",
+			output: "10%\r50%\r100%\r\nfinished",
+			want:   "100%\nfinished",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			assert.Equal(t, tt.want, CleanTerminalOutput(tt.output))
+		})
+	}
+}
@@ -59,30 +59,7 @@ func GetPersistentShell(workingDir string) *PersistentShell {
 }
 
 func newPersistentShell(cwd string) *PersistentShell {
-	// Get shell configuration from config
-	cfg := config.Get()
-	
-	// Default to environment variable if config is not set or nil
-	var shellPath string
-	var shellArgs []string
-	
-	if cfg != nil {
-		shellPath = cfg.Shell.Path
-		shellArgs = cfg.Shell.Args
-	}
-	
-	if shellPath == "" {
-		shellPath = os.Getenv("SHELL")
-		if shellPath == "" {
-			shellPath = "/bin/bash"
-		}
-	}
-	
-	// Default shell args
-	if len(shellArgs) == 0 {
-		shellArgs = []string{"-l"}
-	}
-
+	shellPath, shellArgs := configuredShell()
 	cmd := exec.Command(shellPath, shellArgs...)
 	cmd.Dir = cwd
 
@@ -129,6 +106,27 @@ func newPersistentShell(cwd string) *PersistentShell {
 	return shell
 }
 
+// configuredShell returns the shell from the config, falling back to $SHELL
+// started as a login shell.
+func configuredShell() (string, []string) {
+	var shellPath string
+	var shellArgs []string
+	if cfg := config.Get(); cfg != nil {
+		shellPath = cfg.Shell.Path
+		shellArgs = cfg.Shell.Args
+	}
+	if shellPath == "" {
+		shellPath = os.Getenv("SHELL")
+		if shellPath == "" {
+			shellPath = "/bin/bash"
+		}
+	}
+	if len(shellArgs) == 0 {
+		shellArgs = []string{"-l"}
+	}
+	return shellPath, shellArgs
+}
+
 func (s *PersistentShell) processCommands() {
 	for cmd := range s.commandQueue {
 		result := s.execCommand(cmd.command, cmd.timeout, cmd.ctx)
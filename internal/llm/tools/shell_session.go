@@ -0,0 +1,229 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/tools/shell"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type ShellSessionParams struct {
+	Action    string `json:"action"`
+	SessionID string `json:"session_id"`
+	Input     string `json:"input"`
+	Wait      int    `json:"wait"`
+}
+
+type ShellSessionResponseMetadata struct {
+	SessionID string `json:"session_id"`
+	Alive     bool   `json:"alive"`
+	ExitCode  int    `json:"exit_code"`
+}
+
+type shellSessionTool struct {
+	permissions permission.Service
+}
+
+const (
+	ShellSessionToolName    = "shell_session"
+	shellSessionDescription = `Runs long lived interactive shell sessions. A session keeps its shell running between calls, so its working directory, environment variables, activated virtualenvs and background processes stay as they are, and you read its output as it comes.
+
+WHEN TO USE THIS TOOL:
+- Use for processes that keep running, such as dev servers, file watchers or test runners in watch mode
+- Use for interactive programs and REPLs, such as python, node, psql or a debugger
+- Use when commands depend on shell state set up by earlier commands, like a sourced script
+- Prefer the Bash tool for ordinary one-off commands
+
+HOW TO USE:
+- start: starts a session with the given session_id ("default" when empty) and returns its first output
+- send: sends input to the session, a line break is added when missing, and returns the output that follows
+- read: returns the output produced since the last call without sending anything
+- interrupt: stops the command running in the foreground, like Ctrl+C
+- list: lists the sessions
+- close: stops the session
+- wait sets how long to wait for output in milliseconds, the call returns earlier once the output is quiet
+
+FEATURES:
+- Sessions run in a pseudo terminal on Linux, so programs behave as in a real terminal
+- Escape sequences are removed from the output and progress bars show their last state
+- Several sessions can run at the same time, up to 10
+
+LIMITATIONS:
+- Not available when the sandbox is enabled
+- Only output produced since the last call is returned, older output is gone
+- Full screen programs such as vim, less or top don't work, use their non-interactive options instead
+- The same commands as in the Bash tool are banned, and sent input asks for permission like Bash commands
+
+TIPS:
+- Increase wait for commands that take a while to print their result, or call read again later
+- Use interrupt to stop a server before starting it again with other options
+- Close sessions you no longer need`
+)
+
+const (
+	defaultShellSessionID   = "default"
+	defaultShellSessionWait = 2 * 1000
+	maxShellSessionWait     = 2 * 60 * 1000
+)
+
+var shellSessionIDRegex = regexp.MustCompile(`^[\w.-]{1,64}$`)
+
+func NewShellSessionTool(permissions permission.Service) BaseTool {
+	return &shellSessionTool{
+		permissions: permissions,
+	}
+}
+
+func (s *shellSessionTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        ShellSessionToolName,
+		Description: shellSessionDescription,
+		Parameters: map[string]any{
+			"action": map[string]any{
+				"type":        "string",
+				"description": "What to do with the session",
+				"enum":        []string{"start", "send", "read", "interrupt", "list", "close"},
+			},
+			"session_id": map[string]any{
+				"type":        "string",
+				"description": "The name of the session, defaults to \"default\"",
+			},
+			"input": map[string]any{
+				"type":        "string",
+				"description": "The input to send, required for send",
+			},
+			"wait": map[string]any{
+				"type":        "number",
+				"description": "How long to wait for output in milliseconds (default 2000, max 120000)",
+			},
+		},
+		Required: []string{"action"},
+	}
+}
+
+func (s *shellSessionTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params ShellSessionParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if params.SessionID == "" {
+		params.SessionID = defaultShellSessionID
+	}
+	if !shellSessionIDRegex.MatchString(params.SessionID) {
+		return NewTextErrorResponse("session_id may only contain letters, digits, '.', '_' and '-'"), nil
+	}
+	wait := time.Duration(defaultShellSessionWait) * time.Millisecond
+	if params.Wait > 0 {
+		wait = time.Duration(min(params.Wait, maxShellSessionWait)) * time.Millisecond
+	}
+
+	switch params.Action {
+	case "list":
+		return s.list(), nil
+	case "start":
+		if shell.SandboxEnabled() {
+			return NewTextErrorResponse("shell sessions are not available when the sandbox is enabled, use the Bash tool"), nil
+		}
+		session, err := shell.StartSession(params.SessionID, config.WorkingDirectory())
+		if err != nil {
+			return NewTextErrorResponse(err.Error()), nil
+		}
+		return sessionOutput(session, session.Read(ctx, wait)), nil
+	}
+
+	session, ok := shell.GetSession(params.SessionID)
+	if !ok {
+		return NewTextErrorResponse(fmt.Sprintf("shell session not found: %s. Start it first", params.SessionID)), nil
+	}
+
+	switch params.Action {
+	case "send":
+		if params.Input == "" {
+			return NewTextErrorResponse("input is required"), nil
+		}
+		if baseCmd, banned := isBannedCommand(params.Input); banned {
+			return NewTextErrorResponse(fmt.Sprintf("command '%s' is not allowed", baseCmd)), nil
+		}
+		if !isSafeReadOnlyCommand(params.Input) {
+			sessionID, messageID := GetContextValues(ctx)
+			if sessionID == "" || messageID == "" {
+				return ToolResponse{}, fmt.Errorf("session ID and message ID are required for sending input to a shell session")
+			}
+			p := s.permissions.Request(
+				permission.CreatePermissionRequest{
+					SessionID:   sessionID,
+					Path:        config.WorkingDirectory(),
+					ToolName:    ShellSessionToolName,
+					Action:      "execute",
+					Description: fmt.Sprintf("Send to shell session %s: %s", params.SessionID, params.Input),
+					Command:     params.Input,
+					Params: BashPermissionsParams{
+						Command: params.Input,
+					},
+				},
+			)
+			if !p {
+				return ToolResponse{}, permission.ErrorPermissionDenied
+			}
+		}
+		if err := session.Send(params.Input); err != nil {
+			return NewTextErrorResponse(fmt.Sprintf("failed to send input: %s", err)), nil
+		}
+		return sessionOutput(session, session.Read(ctx, wait)), nil
+	case "read":
+		return sessionOutput(session, session.Read(ctx, wait)), nil
+	case "interrupt":
+		if err := session.Interrupt(); err != nil {
+			return NewTextErrorResponse(fmt.Sprintf("failed to interrupt: %s", err)), nil
+		}
+		return sessionOutput(session, session.Read(ctx, wait)), nil
+	case "close":
+		if err := shell.CloseSession(params.SessionID); err != nil {
+			return NewTextErrorResponse(err.Error()), nil
+		}
+		return NewTextResponse(fmt.Sprintf("Closed shell session %s", params.SessionID)), nil
+	}
+	return NewTextErrorResponse(fmt.Sprintf("invalid action: %s. Use start, send, read, interrupt, list or close", params.Action)), nil
+}
+
+func (s *shellSessionTool) list() ToolResponse {
+	sessions := shell.ListSessions()
+	if len(sessions) == 0 {
+		return NewTextResponse("No shell sessions")
+	}
+	var b strings.Builder
+	for _, session := range sessions {
+		status := "running"
+		if !session.Alive() {
+			status = fmt.Sprintf("exited with code %d", session.ExitCode())
+		}
+		fmt.Fprintf(&b, "%s: %s, %s, started %s ago\n", session.ID, session.Shell, status, time.Since(session.StartedAt).Round(time.Second))
+	}
+	return NewTextResponse(strings.TrimSuffix(b.String(), "\n"))
+}
+
+func sessionOutput(session *shell.Session, output string) ToolResponse {
+	output = truncateOutput(strings.TrimRight(output, "\n"))
+	alive := session.Alive()
+	if output == "" {
+		output = "no new output"
+	}
+	if !alive {
+		output += fmt.Sprintf("\n\nThe shell session has exited with code %d", session.ExitCode())
+	}
+	return WithResponseMetadata(
+		NewTextResponse(output),
+		ShellSessionResponseMetadata{
+			SessionID: session.ID,
+			Alive:     alive,
+			ExitCode:  session.ExitCode(),
+		},
+	)
+}
@@ -0,0 +1,486 @@
+package tools
+
+import (
+	"bufio"
+	"context"
+	"encoding/json"
+	"fmt"
+	"go/ast"
+	"go/parser"
+	"go/token"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/fileutil"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/lsp"
+	"github.com/zhenbah/cryoncode/internal/lsp/protocol"
+)
+
+type SymbolsParams struct {
+	Query string `json:"query"`
+	Path  string `json:"path"`
+	Kind  string `json:"kind"`
+}
+
+type SymbolsResponseMetadata struct {
+	NumberOfSymbols int    `json:"number_of_symbols"`
+	Truncated       bool   `json:"truncated"`
+	Source          string `json:"source"`
+}
+
+type symbolMatch struct {
+	name      string
+	kind      string
+	container string
+	path      string
+	line      int
+}
+
+type symbolsTool struct {
+	lspClients map[string]*lsp.Client
+}
+
+const (
+	SymbolsToolName    = "symbols"
+	symbolsDescription = `Finds the definitions of functions, methods, types, classes and other symbols by name, returning their kind, file and line.
+
+WHEN TO USE THIS TOOL:
+- Use when you need to find where a function, type or class is defined
+- Prefer this over Grep when looking for a definition rather than every use of a name
+- Helpful for navigating an unfamiliar codebase structurally
+
+HOW TO USE:
+- Provide a query with the symbol name or a part of it
+- Optionally restrict the results to a directory with path
+- Optionally filter by kind, e.g. "function", "method", "struct", "class", "interface"
+
+FEATURES:
+- Uses the configured language servers when they are running, which understand the code like the compiler does
+- Without them, parses Go files with the Go parser, returning functions, methods with their receiver type, types, constants and variables
+- Falls back to matching common definition forms for Rust, Python, JavaScript/TypeScript, Java, C/C++ and Ruby, and Go files that don't parse, line by line with regular expressions
+- Respects .gitignore files and skips hidden and dependency directories in the fallback
+
+LIMITATIONS:
+- Results are limited to 100 symbols
+- The fallback doesn't parse the code: it misses definitions split over several lines or made by macros, and can match definitions inside comments or strings
+- Language servers may only index files they have opened or loaded
+
+TIPS:
+- Use the View tool with the returned line as offset to read the definition
+- Use Grep to find the uses of a symbol once you know where it is defined`
+)
+
+const maxSymbolResults = 100
+
+// symbolDefinitionPatterns match definitions in the fallback scanner. The first
+// submatch of each pattern is the symbol name.
+var symbolDefinitionPatterns = map[string][]struct {
+	kind    string
+	pattern *regexp.Regexp
+}{
+	".go": {
+		{"Method", regexp.MustCompile(`^func\s+\([^)]*\)\s+(\w+)`)},
+		{"Function", regexp.MustCompile(`^func\s+(\w+)`)},
+		{"Struct", regexp.MustCompile(`^(?:type\s+|\s+)(\w+)\s+struct\b`)},
+		{"Interface", regexp.MustCompile(`^(?:type\s+|\s+)(\w+)\s+interface\b`)},
+		{"Class", regexp.MustCompile(`^type\s+(\w+)\s`)},
+	},
+	".rs": {
+		{"Function", regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+|async\s+|unsafe\s+|extern\s+"[^"]*"\s+)*fn\s+(\w+)`)},
+		{"Struct", regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+(\w+)`)},
+		{"Enum", regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?enum\s+(\w+)`)},
+		{"Interface", regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?trait\s+(\w+)`)},
+		{"Class", regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?type\s+(\w+)`)},
+		{"Module", regexp.MustCompile(`^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)`)},
+	},
+	".py": {
+		{"Function", regexp.MustCompile(`^\s*(?:async\s+)?def\s+(\w+)`)},
+		{"Class", regexp.MustCompile(`^\s*class\s+(\w+)`)},
+	},
+	".js": {
+		{"Function", regexp.MustCompile(`^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*(\w+)`)},
+		{"Class", regexp.MustCompile(`^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+(\w+)`)},
+		{"Function", regexp.MustCompile(`^\s*(?:export\s+)?(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s+)?(?:function|\([^)]*\)\s*=>|\w+\s*=>)`)},
+		{"Interface", regexp.MustCompile(`^\s*(?:export\s+)?interface\s+(\w+)`)},
+		{"Class", regexp.MustCompile(`^\s*(?:export\s+)?type\s+(\w+)\s*(?:<[^>]*>)?\s*=`)},
+		{"Enum", regexp.MustCompile(`^\s*(?:export\s+)?(?:const\s+)?enum\s+(\w+)`)},
+	},
+	".java": {
+		{"Class", regexp.MustCompile(`^\s*(?:(?:public|private|protected|abstract|final|static)\s+)*class\s+(\w+)`)},
+		{"Interface", regexp.MustCompile(`^\s*(?:(?:public|private|protected|abstract|static)\s+)*interface\s+(\w+)`)},
+		{"Enum", regexp.MustCompile(`^\s*(?:(?:public|private|protected|static)\s+)*enum\s+(\w+)`)},
+		{"Method", regexp.MustCompile(`^\s*(?:(?:public|private|protected|abstract|final|static|synchronized)\s+)+[\w<>\[\],\s]+\s+(\w+)\s*\(`)},
+	},
+	".c": {
+		{"Struct", regexp.MustCompile(`^\s*(?:typedef\s+)?struct\s+(\w+)\s*\{`)},
+		{"Class", regexp.MustCompile(`^\s*class\s+(\w+)`)},
+		{"Function", regexp.MustCompile(`^[A-Za-z_][\w\s\*&:<>,]*?\b(\w+)\s*\([^;]*$`)},
+	},
+	".rb": {
+		{"Method", regexp.MustCompile(`^\s*def\s+(?:self\.)?(\w+[?!=]?)`)},
+		{"Class", regexp.MustCompile(`^\s*class\s+(\w+)`)},
+		{"Module", regexp.MustCompile(`^\s*module\s+(\w+)`)},
+	},
+}
+
+// symbolPatternAliases maps extensions to the patterns of a similar language.
+var symbolPatternAliases = map[string]string{
+	".jsx": ".js",
+	".ts":  ".js",
+	".tsx": ".js",
+	".mjs": ".js",
+	".cjs": ".js",
+	".h":   ".c",
+	".cc":  ".c",
+	".cpp": ".c",
+	".hpp": ".c",
+	".cxx": ".c",
+	".hh":  ".c",
+}
+
+func NewSymbolsTool(lspClients map[string]*lsp.Client) BaseTool {
+	return &symbolsTool{
+		lspClients: lspClients,
+	}
+}
+
+func (s *symbolsTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        SymbolsToolName,
+		Description: symbolsDescription,
+		Parameters: map[string]any{
+			"query": map[string]any{
+				"type":        "string",
+				"description": "The symbol name or part of it to search for",
+			},
+			"path": map[string]any{
+				"type":        "string",
+				"description": "The directory to search in. Defaults to the current working directory.",
+			},
+			"kind": map[string]any{
+				"type":        "string",
+				"description": "Only return symbols of this kind, e.g. function, method, struct, class, interface, enum",
+			},
+		},
+		Required: []string{"query"},
+	}
+}
+
+func (s *symbolsTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params SymbolsParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if strings.TrimSpace(params.Query) == "" {
+		return NewTextErrorResponse("query is required"), nil
+	}
+
+	searchPath := params.Path
+	if searchPath == "" {
+		searchPath = config.WorkingDirectory()
+	} else if !filepath.IsAbs(searchPath) {
+		searchPath = filepath.Join(config.WorkingDirectory(), searchPath)
+	}
+
+	if err := checkWorkspacePath(searchPath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	source := "lsp"
+	symbols := s.lspSymbols(ctx, params.Query, searchPath)
+	if len(symbols) == 0 {
+		source = "scan"
+		var err error
+		symbols, err = scanSymbols(ctx, params.Query, searchPath)
+		if err != nil {
+			return ToolResponse{}, fmt.Errorf("error searching symbols: %w", err)
+		}
+	}
+
+	symbols = filterSymbolsByKind(symbols, params.Kind)
+	denied := deniedPaths(SymbolsToolName, searchPath)
+	symbols = slices.DeleteFunc(symbols, func(symbol symbolMatch) bool {
+		return denied(symbol.path)
+	})
+	sort.Slice(symbols, func(i, j int) bool {
+		if symbols[i].path != symbols[j].path {
+			return symbols[i].path < symbols[j].path
+		}
+		return symbols[i].line < symbols[j].line
+	})
+
+	truncated := len(symbols) > maxSymbolResults
+	if truncated {
+		symbols = symbols[:maxSymbolResults]
+	}
+
+	var output strings.Builder
+	if len(symbols) == 0 {
+		output.WriteString("No symbols found")
+	} else {
+		fmt.Fprintf(&output, "Found %d symbols\n", len(symbols))
+		for _, symbol := range symbols {
+			fmt.Fprintf(&output, "%s:%d %s %s", symbol.path, symbol.line, symbol.kind, symbol.name)
+			if symbol.container != "" {
+				fmt.Fprintf(&output, " (in %s)", symbol.container)
+			}
+			output.WriteString("\n")
+		}
+		if truncated {
+			output.WriteString("\n(Results are truncated. Consider using a more specific query, path or kind.)")
+		}
+	}
+
+	return WithResponseMetadata(
+		NewTextResponse(output.String()),
+		SymbolsResponseMetadata{
+			NumberOfSymbols: len(symbols),
+			Truncated:       truncated,
+			Source:          source,
+		},
+	), nil
+}
+
+// lspSymbols asks the running language servers for the workspace symbols
+// matching the query under the search path.
+func (s *symbolsTool) lspSymbols(ctx context.Context, query, searchPath string) []symbolMatch {
+	var symbols []symbolMatch
+	seen := make(map[string]bool)
+	for name, client := range s.lspClients {
+		callCtx, cancel := context.WithTimeout(ctx, 10*time.Second)
+		result, err := client.Symbol(callCtx, protocol.WorkspaceSymbolParams{Query: query})
+		cancel()
+		if err != nil {
+			logging.Debug("workspace symbol request failed", "lsp", name, "error", err)
+			continue
+		}
+		results, err := result.Results()
+		if err != nil {
+			logging.Debug("invalid workspace symbol response", "lsp", name, "error", err)
+			continue
+		}
+
+		for _, r := range results {
+			location := r.GetLocation()
+			path := location.URI.Path()
+			if !isWithinDir(searchPath, path) {
+				continue
+			}
+			symbol := symbolMatch{
+				name: r.GetName(),
+				path: path,
+				line: int(location.Range.Start.Line) + 1,
+			}
+			switch v := r.(type) {
+			case *protocol.SymbolInformation:
+				symbol.kind = protocol.TableKindMap[v.Kind]
+				symbol.container = v.ContainerName
+			case *protocol.WorkspaceSymbol:
+				symbol.kind = protocol.TableKindMap[v.Kind]
+				symbol.container = v.ContainerName
+			}
+
+			key := fmt.Sprintf("%s:%d:%s", symbol.path, symbol.line, symbol.name)
+			if seen[key] {
+				continue
+			}
+			seen[key] = true
+			symbols = append(symbols, symbol)
+		}
+	}
+	return symbols
+}
+
+// scanSymbols walks the files under the root and matches common definition
+// forms whose name contains the query, ignoring case.
+func scanSymbols(ctx context.Context, query, root string) ([]symbolMatch, error) {
+	query = strings.ToLower(query)
+	ignore := fileutil.NewGitIgnore(root)
+
+	var symbols []symbolMatch
+	err := filepath.WalkDir(root, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return nil // Skip entries we can't access
+		}
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+
+		if d.IsDir() {
+			if path != root && (fileutil.SkipHidden(path) || ignore.Match(path, true)) {
+				return filepath.SkipDir
+			}
+			if path != root {
+				ignore.AddFile(path)
+			}
+			return nil
+		}
+		if fileutil.SkipHidden(path) || ignore.Match(path, false) {
+			return nil
+		}
+
+		ext := strings.ToLower(filepath.Ext(path))
+		if alias, ok := symbolPatternAliases[ext]; ok {
+			ext = alias
+		}
+		if _, ok := symbolDefinitionPatterns[ext]; !ok {
+			return nil
+		}
+
+		// Stop scanning well past the limit, the results are truncated anyway
+		if len(symbols) > maxSymbolResults*2 {
+			return filepath.SkipAll
+		}
+		if ext == ".go" {
+			if goSymbols, ok := parseGoSymbols(path, query); ok {
+				symbols = append(symbols, goSymbols...)
+				return nil
+			}
+		}
+		symbols = append(symbols, scanFileSymbols(path, ext, query)...)
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	return symbols, nil
+}
+
+func scanFileSymbols(path, ext, query string) []symbolMatch {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil
+	}
+	defer file.Close()
+
+	var symbols []symbolMatch
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 1024*1024)
+	lineNum := 0
+	for scanner.Scan() {
+		lineNum++
+		line := scanner.Text()
+		if !strings.Contains(strings.ToLower(line), query) {
+			continue
+		}
+		for _, def := range symbolDefinitionPatterns[ext] {
+			m := def.pattern.FindStringSubmatch(line)
+			if m == nil {
+				continue
+			}
+			// Only the first matching pattern counts, so order the patterns
+			// from the most to the least specific
+			if strings.Contains(strings.ToLower(m[1]), query) {
+				symbols = append(symbols, symbolMatch{
+					name: m[1],
+					kind: def.kind,
+					path: path,
+					line: lineNum,
+				})
+			}
+			break
+		}
+	}
+	return symbols
+}
+
+// parseGoSymbols returns the top level definitions of a Go file whose name
+// contains the query, false when the file doesn't parse
+func parseGoSymbols(path, query string) ([]symbolMatch, bool) {
+	fset := token.NewFileSet()
+	file, err := parser.ParseFile(fset, path, nil, parser.SkipObjectResolution)
+	if err != nil {
+		return nil, false
+	}
+
+	var symbols []symbolMatch
+	add := func(name *ast.Ident, kind, container string) {
+		if strings.Contains(strings.ToLower(name.Name), query) {
+			symbols = append(symbols, symbolMatch{
+				name:      name.Name,
+				kind:      kind,
+				container: container,
+				path:      path,
+				line:      fset.Position(name.Pos()).Line,
+			})
+		}
+	}
+	for _, decl := range file.Decls {
+		switch decl := decl.(type) {
+		case *ast.FuncDecl:
+			if decl.Recv != nil && len(decl.Recv.List) > 0 {
+				add(decl.Name, "Method", receiverTypeName(decl.Recv.List[0].Type))
+			} else {
+				add(decl.Name, "Function", "")
+			}
+		case *ast.GenDecl:
+			for _, spec := range decl.Specs {
+				switch spec := spec.(type) {
+				case *ast.TypeSpec:
+					switch spec.Type.(type) {
+					case *ast.StructType:
+						add(spec.Name, "Struct", "")
+					case *ast.InterfaceType:
+						add(spec.Name, "Interface", "")
+					default:
+						add(spec.Name, "Class", "")
+					}
+				case *ast.ValueSpec:
+					kind := "Variable"
+					if decl.Tok == token.CONST {
+						kind = "Constant"
+					}
+					for _, name := range spec.Names {
+						if name.Name != "_" {
+							add(name, kind, "")
+						}
+					}
+				}
+			}
+		}
+	}
+	return symbols, true
+}
+
+// receiverTypeName returns the name of the type of a method receiver, without
+// the pointer and the type parameters
+func receiverTypeName(expr ast.Expr) string {
+	for {
+		switch e := expr.(type) {
+		case *ast.StarExpr:
+			expr = e.X
+		case *ast.ParenExpr:
+			expr = e.X
+		case *ast.IndexExpr:
+			expr = e.X
+		case *ast.IndexListExpr:
+			expr = e.X
+		case *ast.Ident:
+			return e.Name
+		default:
+			return ""
+		}
+	}
+}
+
+func filterSymbolsByKind(symbols []symbolMatch, kind string) []symbolMatch {
+	if kind == "" {
+		return symbols
+	}
+	filtered := symbols[:0]
+	for _, symbol := range symbols {
+		if strings.EqualFold(symbol.kind, kind) {
+			filtered = append(filtered, symbol)
+		}
+	}
+	return filtered
+}
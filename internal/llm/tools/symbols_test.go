@@ -0,0 +1,86 @@
+package tools
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestScanSymbols(t *testing.T) {
+	dir := t.TempDir()
+	files := map[string]string{
+		"server.go": "package main\n\ntype Server struct {\n}\n\nfunc (s *Server) Start() error {\n\treturn nil\n}\n\nfunc NewServer() *Server {\n\treturn &Server{}\n}\n",
+		"app.py":    "class ServerApp:\n    def start_server(self):\n        pass\n",
+		"notes.txt": "func ServerNotes()\n",
+	}
+	for name, content := range files {
+		require.NoError(t, os.WriteFile(filepath.Join(dir, name), []byte(content), 0o644))
+	}
+
+	symbols, err := scanSymbols(context.Background(), "server", dir)
+	require.NoError(t, err)
+
+	found := make(map[string]string)
+	for _, symbol := range symbols {
+		found[symbol.name] = symbol.kind
+	}
+	assert.Equal(t, map[string]string{
+		"Server":       "Struct",
+		"NewServer":    "Function",
+		"ServerApp":    "Class",
+		"start_server": "Function",
+	}, found)
+
+	functions := filterSymbolsByKind(symbols, "function")
+	assert.Len(t, functions, 2)
+}
+
+func TestParseGoSymbols(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "cache.go")
+	require.NoError(t, os.WriteFile(path, []byte(`package cache
+
+const (
+	cacheSize = 100
+	_         = iota
+)
+
+type (
+	Cache[K comparable, V any] struct {
+		entries map[K]V
+	}
+	CacheLoader interface {
+		Load(key string) (any, error)
+	}
+)
+
+func (c *Cache[K, V]) ClearCache(
+	keys ...K,
+) {
+}
+
+// NewCache isn't a definition in a comment
+var defaultCache = "func fakeCache()"
+`), 0o644))
+
+	symbols, ok := parseGoSymbols(path, "cache")
+	require.True(t, ok)
+	assert.Equal(t, []symbolMatch{
+		{name: "cacheSize", kind: "Constant", path: path, line: 4},
+		{name: "Cache", kind: "Struct", path: path, line: 9},
+		{name: "CacheLoader", kind: "Interface", path: path, line: 12},
+		{name: "ClearCache", kind: "Method", container: "Cache", path: path, line: 17},
+		{name: "defaultCache", kind: "Variable", path: path, line: 23},
+	}, symbols)
+
+	// A file that doesn't parse goes through the regular expressions
+	require.NoError(t, os.WriteFile(path, []byte("package cache
+
+func NewCache( {
+"), 0o644))
+	_, ok = parseGoSymbols(path, "cache")
+	assert.False(t, ok)
+}
@@ -0,0 +1,162 @@
+package tools
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+type TodoParams struct {
+	Todos []session.Todo `json:"todos"`
+}
+
+type TodoResponseMetadata struct {
+	Todos     []session.Todo `json:"todos"`
+	Completed int            `json:"completed"`
+}
+
+type todoTool struct {
+	sessions session.Service
+}
+
+const (
+	TodoToolName    = "todo"
+	todoDescription = `Creates and updates the task list for the current session. The list is shown to the user as the current plan, so they can follow your progress.
+
+WHEN TO USE THIS TOOL:
+- Use for tasks that need three or more distinct steps or touch several files
+- Use when the user gives you a list of things to do
+- Update the list as soon as you start or finish a task, not in batches at the end
+
+HOW TO USE:
+- Always send the complete list, it replaces the previous one
+- Each task has a content describing it and a status: pending, in_progress or completed
+- Keep exactly one task in_progress while you are working
+- Send an empty list to clear the plan
+
+FEATURES:
+- The plan is stored with the session and survives restarts
+- The user sees the plan and the status of each task in the sidebar
+
+LIMITATIONS:
+- One list per session
+- Not needed for simple or single step tasks
+
+TIPS:
+- Write tasks as short, specific actions, e.g. "Add retry to the HTTP client"
+- Mark a task completed only when it is fully done, including tests passing
+- Add new tasks to the list when you discover more work along the way`
+)
+
+func NewTodoTool(sessions session.Service) BaseTool {
+	return &todoTool{
+		sessions: sessions,
+	}
+}
+
+func (t *todoTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        TodoToolName,
+		Description: todoDescription,
+		Parameters: map[string]any{
+			"todos": map[string]any{
+				"type":        "array",
+				"description": "The complete, updated task list",
+				"items": map[string]any{
+					"type": "object",
+					"properties": map[string]any{
+						"content": map[string]any{
+							"type":        "string",
+							"description": "What the task is",
+						},
+						"status": map[string]any{
+							"type":        "string",
+							"description": "The status of the task",
+							"enum":        []string{string(session.TodoPending), string(session.TodoInProgress), string(session.TodoCompleted)},
+						},
+					},
+					"required": []string{"content", "status"},
+				},
+			},
+		},
+		Required: []string{"todos"},
+	}
+}
+
+func (t *todoTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params TodoParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("error parsing parameters: %s", err)), nil
+	}
+
+	if err := validateTodos(params.Todos); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
+	sessionID, _ := GetContextValues(ctx)
+	if sessionID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID is required for updating the task list")
+	}
+
+	if _, err := t.sessions.SetTodos(ctx, sessionID, params.Todos); err != nil {
+		return ToolResponse{}, fmt.Errorf("error saving the task list: %w", err)
+	}
+
+	completed := 0
+	for _, todo := range params.Todos {
+		if todo.Status == session.TodoCompleted {
+			completed++
+		}
+	}
+
+	output := "Task list cleared"
+	if len(params.Todos) > 0 {
+		output = fmt.Sprintf("Task list updated (%d/%d completed)\n%s", completed, len(params.Todos), FormatTodos(params.Todos))
+	}
+	return WithResponseMetadata(
+		NewTextResponse(output),
+		TodoResponseMetadata{
+			Todos:     params.Todos,
+			Completed: completed,
+		},
+	), nil
+}
+
+func validateTodos(todos []session.Todo) error {
+	inProgress := 0
+	for i, todo := range todos {
+		if strings.TrimSpace(todo.Content) == "" {
+			return fmt.Errorf("task %d: content is required", i+1)
+		}
+		switch todo.Status {
+		case session.TodoPending, session.TodoCompleted:
+		case session.TodoInProgress:
+			inProgress++
+		default:
+			return fmt.Errorf("task %d: invalid status %q, use pending, in_progress or completed", i+1, todo.Status)
+		}
+	}
+	if inProgress > 1 {
+		return fmt.Errorf("only one task can be in_progress at a time, found %d", inProgress)
+	}
+	return nil
+}
+
+// FormatTodos renders the task list as a checklist.
+func FormatTodos(todos []session.Todo) string {
+	var b strings.Builder
+	for _, todo := range todos {
+		marker := "[ ]"
+		switch todo.Status {
+		case session.TodoInProgress:
+			marker = "[~]"
+		case session.TodoCompleted:
+			marker = "[x]"
+		}
+		fmt.Fprintf(&b, "%s %s\n", marker, todo.Content)
+	}
+	return strings.TrimSuffix(b.String(), "\n")
+}
@@ -0,0 +1,51 @@
+package tools
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+func TestValidateTodos(t *testing.T) {
+	t.Run("accepts a valid list", func(t *testing.T) {
+		err := validateTodos([]session.Todo{
+			{Content: "Write the parser", Status: session.TodoCompleted},
+			{Content: "Add tests", Status: session.TodoInProgress},
+			{Content: "Update docs", Status: session.TodoPending},
+		})
+		assert.NoError(t, err)
+	})
+
+	t.Run("accepts an empty list", func(t *testing.T) {
+		assert.NoError(t, validateTodos(nil))
+	})
+
+	t.Run("rejects empty content", func(t *testing.T) {
+		err := validateTodos([]session.Todo{{Content: "  ", Status: session.TodoPending}})
+		assert.Error(t, err)
+	})
+
+	t.Run("rejects unknown status", func(t *testing.T) {
+		err := validateTodos([]session.Todo{{Content: "Add tests", Status: "done"}})
+		assert.Error(t, err)
+	})
+
+	t.Run("rejects several tasks in progress", func(t *testing.T) {
+		err := validateTodos([]session.Todo{
+			{Content: "Add tests", Status: session.TodoInProgress},
+			{Content: "Update docs", Status: session.TodoInProgress},
+		})
+		assert.Error(t, err)
+	})
+}
+
+func TestFormatTodos(t *testing.T) {
+	output := FormatTodos([]session.Todo{
+		{Content: "Write the parser", Status: session.TodoCompleted},
+		{Content: "Add tests", Status: session.TodoInProgress},
+		{Content: "Update docs", Status: session.TodoPending},
+	})
+	assert.Equal(t, "[x] Write the parser\n[~] Add tests\n[ ] Update docs", output)
+}
@@ -3,6 +3,8 @@ package tools
 import (
 	"context"
 	"encoding/json"
+	"fmt"
+	"strings"
 )
 
 type ToolInfo struct {
@@ -82,3 +84,53 @@ func GetContextValues(ctx context.Context) (string, string) {
 	}
 	return sessionID.(string), messageID.(string)
 }
+
+// TruncateForModel shortens tool output that exceeds the given byte or line
+// limits, keeping the beginning and the end of the output. It reports whether
+// the content was truncated. A limit of zero or less disables that check.
+func TruncateForModel(content string, maxBytes, maxLines int) (string, bool) {
+	lines := strings.Split(content, "\n")
+	if (maxBytes <= 0 || len(content) <= maxBytes) && (maxLines <= 0 || len(lines) <= maxLines) {
+		return content, false
+	}
+
+	byteBudget := len(content)
+	if maxBytes > 0 {
+		byteBudget = maxBytes
+	}
+	lineBudget := len(lines)
+	if maxLines > 0 {
+		lineBudget = maxLines
+	}
+
+	head := make([]string, 0)
+	headBytes := 0
+	for _, line := range lines {
+		if len(head) >= lineBudget/2 || headBytes+len(line)+1 > byteBudget/2 {
+			break
+		}
+		head = append(head, line)
+		headBytes += len(line) + 1
+	}
+
+	tail := make([]string, 0)
+	tailBytes := 0
+	for i := len(lines) - 1; i >= len(head); i-- {
+		line := lines[i]
+		if len(tail) >= lineBudget/2 || tailBytes+len(line)+1 > byteBudget/2 {
+			break
+		}
+		tail = append([]string{line}, tail...)
+		tailBytes += len(line) + 1
+	}
+
+	// A single huge line doesn't fit the budget, fall back to cutting bytes
+	if len(head) == 0 && len(tail) == 0 {
+		cut := strings.ToValidUTF8(content[:byteBudget/2], "")
+		return fmt.Sprintf("%s\n\n... [%d bytes truncated] ...", cut, len(content)-len(cut)), true
+	}
+
+	omitted := len(lines) - len(head) - len(tail)
+	return fmt.Sprintf("%s\n\n... [%d lines truncated, the full output was stored but is not shown to you] ...\n\n%s",
+		strings.Join(head, "\n"), omitted, strings.Join(tail, "\n")), true
+}
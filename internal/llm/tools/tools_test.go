@@ -0,0 +1,55 @@
+package tools
+
+import (
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestTruncateForModel(t *testing.T) {
+	t.Run("keeps short output unchanged", func(t *testing.T) {
+		content, truncated := TruncateForModel("hello\nworld", 100, 10)
+		assert.False(t, truncated)
+		assert.Equal(t, "hello\nworld", content)
+	})
+
+	t.Run("truncates on line limit keeping head and tail", func(t *testing.T) {
+		lines := make([]string, 100)
+		for i := range lines {
+			lines[i] = fmt.Sprintf("line %d", i)
+		}
+		content, truncated := TruncateForModel(strings.Join(lines, "\n"), 0, 10)
+		assert.True(t, truncated)
+		assert.True(t, strings.HasPrefix(content, "line 0\n"))
+		assert.True(t, strings.HasSuffix(content, "\nline 99"))
+		assert.Contains(t, content, "[90 lines truncated")
+	})
+
+	t.Run("truncates on byte limit", func(t *testing.T) {
+		lines := make([]string, 100)
+		for i := range lines {
+			lines[i] = strings.Repeat("x", 99)
+		}
+		content, truncated := TruncateForModel(strings.Join(lines, "\n"), 1000, 0)
+		assert.True(t, truncated)
+		assert.Less(t, len(content), 1200)
+	})
+
+	t.Run("truncates a single long line", func(t *testing.T) {
+		content, truncated := TruncateForModel(strings.Repeat("x", 5000), 1000, 0)
+		assert.True(t, truncated)
+		assert.Contains(t, content, "[4500 bytes truncated]")
+	})
+}
+
+func TestExpandCommandTemplate(t *testing.T) {
+	args := map[string]any{
+		"name":  "it's",
+		"count": float64(3),
+		"tags":  []any{"a", "b"},
+	}
+	command := expandCommandTemplate("run {{name}} -n {{ count }} --tags {{tags}} {{missing}}", args)
+	assert.Equal(t, `run 'it'\''s' -n '3' --tags '["a","b"]' ''`, command)
+}
@@ -9,6 +9,7 @@ import (
 	"os"
 	"path/filepath"
 	"strings"
+	"unicode/utf8"
 
 	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/logging"
@@ -50,15 +51,15 @@ HOW TO USE:
 FEATURES:
 - Displays file contents with line numbers for easy reference
 - Can read from any position in a file using the offset parameter
-- Handles large files by limiting the number of lines read
+- Handles large files by limiting the number of lines read, read them in chunks with offset and limit
 - Automatically truncates very long lines for better display
 - Suggests similar file names when the requested file isn't found
 
 LIMITATIONS:
-- Maximum file size is 250KB
+- At most 250KB of content is returned per call
 - Default reading limit is 2000 lines
 - Lines longer than 2000 characters are truncated
-- Cannot display binary files or images
+- Cannot display binary files or images, they are rejected
 - Images can be identified but not displayed
 
 TIPS:
@@ -113,6 +114,10 @@ func (v *viewTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 		filePath = filepath.Join(config.WorkingDirectory(), filePath)
 	}
 
+	if err := checkWorkspacePath(filePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
 	// Check if file exists
 	fileInfo, err := os.Stat(filePath)
 	if err != nil {
@@ -150,12 +155,6 @@ func (v *viewTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 		return NewTextErrorResponse(fmt.Sprintf("Path is a directory, not a file: %s", filePath)), nil
 	}
 
-	// Check file size
-	if fileInfo.Size() > MaxReadSize {
-		return NewTextErrorResponse(fmt.Sprintf("File is too large (%d bytes). Maximum size is %d bytes",
-			fileInfo.Size(), MaxReadSize)), nil
-	}
-
 	// Set default limit if not provided
 	if params.Limit <= 0 {
 		params.Limit = DefaultReadLimit
@@ -168,11 +167,22 @@ func (v *viewTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 		return NewTextErrorResponse(fmt.Sprintf("This is an image file of type: %s\nUse a different tool to process images", imageType)), nil
 	}
 
+	isBinary, err := isBinaryFile(filePath)
+	if err != nil {
+		return ToolResponse{}, fmt.Errorf("error reading file: %w", err)
+	}
+	if isBinary {
+		return NewTextErrorResponse(fmt.Sprintf("Cannot display binary file: %s", filePath)), nil
+	}
+
 	// Read the file content
-	content, lineCount, err := readTextFile(filePath, params.Offset, params.Limit)
+	content, lineCount, more, err := readTextFile(filePath, params.Offset, params.Limit)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("error reading file: %w", err)
 	}
+	if params.Offset > 0 && params.Offset >= lineCount {
+		return NewTextErrorResponse(fmt.Sprintf("Offset %d is beyond the end of the file, which has %d lines", params.Offset, lineCount)), nil
+	}
 
 	notifyLspOpenFile(ctx, filePath, v.lspClients)
 	output := "<file>\n"
@@ -180,9 +190,8 @@ func (v *viewTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error)
 	output += addLineNumbers(content, params.Offset+1)
 
 	// Add a note if the content was truncated
-	if lineCount > params.Offset+len(strings.Split(content, "\n")) {
-		output += fmt.Sprintf("\n\n(File has more lines. Use 'offset' parameter to read beyond line %d)",
-			params.Offset+len(strings.Split(content, "\n")))
+	if more {
+		output += fmt.Sprintf("\n\n(File has more lines. Use 'offset' parameter to read beyond line %d)", lineCount)
 	}
 	output += "\n</file>\n"
 	output += getDiagnostics(filePath, v.lspClients)
@@ -221,54 +230,67 @@ func addLineNumbers(content string, startLine int) string {
 	return strings.Join(result, "\n")
 }
 
-func readTextFile(filePath string, offset, limit int) (string, int, error) {
+// readTextFile reads up to limit lines from the offset. lineCount is the
+// number of the last line read, and more reports whether the file goes on
+// after it. Lines after the window aren't read.
+func readTextFile(filePath string, offset, limit int) (content string, lineCount int, more bool, err error) {
 	file, err := os.Open(filePath)
 	if err != nil {
-		return "", 0, err
+		return "", 0, false, err
 	}
 	defer file.Close()
 
-	lineCount := 0
-
 	scanner := NewLineScanner(file)
-	if offset > 0 {
-		for lineCount < offset && scanner.Scan() {
-			lineCount++
-		}
-		if err = scanner.Err(); err != nil {
-			return "", 0, err
-		}
-	}
-
-	if offset == 0 {
-		_, err = file.Seek(0, io.SeekStart)
-		if err != nil {
-			return "", 0, err
-		}
+	for lineCount < offset && scanner.Scan() {
+		lineCount++
 	}
 
 	var lines []string
-	lineCount = offset
+	readSize := 0
 
-	for scanner.Scan() && len(lines) < limit {
+	// Stop at the line limit or once enough content was read, whichever comes first
+	for len(lines) < limit && readSize < MaxReadSize && scanner.Scan() {
 		lineCount++
 		lineText := scanner.Text()
-		if len(lineText) > MaxLineLength {
-			lineText = lineText[:MaxLineLength] + "..."
+		if scanner.Truncated() {
+			lineText += "..."
 		}
 		lines = append(lines, lineText)
+		readSize += len(lineText) + 1
 	}
+	more = lineCount >= offset && scanner.Scan()
 
-	// Continue scanning to get total line count
-	for scanner.Scan() {
-		lineCount++
+	if err := scanner.Err(); err != nil {
+		return "", 0, false, err
 	}
 
-	if err := scanner.Err(); err != nil {
-		return "", 0, err
+	return strings.Join(lines, "\n"), lineCount, more, nil
+}
+
+// isBinaryFile reports whether the file looks like a binary file, based on
+// its first few kilobytes containing NUL bytes or invalid UTF-8.
+func isBinaryFile(filePath string) (bool, error) {
+	file, err := os.Open(filePath)
+	if err != nil {
+		return false, err
 	}
+	defer file.Close()
+
+	buf := make([]byte, 8*1024)
+	n, err := io.ReadFull(file, buf)
+	if err != nil && err != io.EOF && err != io.ErrUnexpectedEOF {
+		return false, err
+	}
+	buf = buf[:n]
 
-	return strings.Join(lines, "\n"), lineCount, nil
+	if strings.ContainsRune(string(buf), 0) {
+		return true, nil
+	}
+	// The buffer may end in the middle of a multi-byte character
+	for i := 0; i < utf8.UTFMax && len(buf) > 0 && !utf8.Valid(buf); i++ {
+		buf = buf[:len(buf)-1]
+	}
+	return !utf8.Valid(buf), nil
 }
 
 func isImageFile(filePath string) (bool, string) {
@@ -291,24 +313,58 @@ func isImageFile(filePath string) (bool, string) {
 	}
 }
 
+// LineScanner reads a file line by line like bufio.Scanner, without its limit
+// on the length of a line: only the first MaxLineLength bytes of a line are
+// kept.
 type LineScanner struct {
-	scanner *bufio.Scanner
+	reader    *bufio.Reader
+	line      []byte
+	truncated bool
+	err       error
 }
 
 func NewLineScanner(r io.Reader) *LineScanner {
 	return &LineScanner{
-		scanner: bufio.NewScanner(r),
+		reader: bufio.NewReader(r),
 	}
 }
 
 func (s *LineScanner) Scan() bool {
-	return s.scanner.Scan()
+	if s.err != nil {
+		return false
+	}
+	s.line = s.line[:0]
+	s.truncated = false
+	for {
+		chunk, isPrefix, err := s.reader.ReadLine()
+		if err != nil {
+			s.err = err
+			return false
+		}
+		room := MaxLineLength - len(s.line)
+		if len(chunk) > room {
+			chunk = chunk[:room]
+			s.truncated = true
+		}
+		s.line = append(s.line, chunk...)
+		if !isPrefix {
+			return true
+		}
+	}
 }
 
 func (s *LineScanner) Text() string {
-	return s.scanner.Text()
+	return string(s.line)
+}
+
+// Truncated reports whether the line was longer than MaxLineLength
+func (s *LineScanner) Truncated() bool {
+	return s.truncated
 }
 
 func (s *LineScanner) Err() error {
-	return s.scanner.Err()
+	if s.err == io.EOF {
+		return nil
+	}
+	return s.err
 }
@@ -0,0 +1,78 @@
+package tools
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestIsBinaryFile(t *testing.T) {
+	dir := t.TempDir()
+
+	testCases := []struct {
+		name     string
+		content  []byte
+		expected bool
+	}{
+		{name: "text.go", content: []byte("package main\n"), expected: false},
+		{name: "unicode.txt", content: []byte("héllo wörld ✓\n"), expected: false},
+		{name: "empty.txt", content: []byte{}, expected: false},
+		{name: "nul.bin", content: []byte{'a', 0, 'b'}, expected: true},
+		{name: "latin1.bin", content: []byte{0xff, 0xfe, 0xfd, 0x80, 0x81}, expected: true},
+	}
+
+	for _, tc := range testCases {
+		t.Run(tc.name, func(t *testing.T) {
+			path := filepath.Join(dir, tc.name)
+			require.NoError(t, os.WriteFile(path, tc.content, 0o644))
+			isBinary, err := isBinaryFile(path)
+			require.NoError(t, err)
+			assert.Equal(t, tc.expected, isBinary)
+		})
+	}
+}
+
+func TestReadTextFileRange(t *testing.T) {
+	lines := make([]string, 100)
+	for i := range lines {
+		lines[i] = "line"
+	}
+	path := filepath.Join(t.TempDir(), "file.txt")
+	require.NoError(t, os.WriteFile(path, []byte(strings.Join(lines, "\n")), 0o644))
+
+	content, lineCount, more, err := readTextFile(path, 10, 5)
+	require.NoError(t, err)
+	assert.Equal(t, 15, lineCount)
+	assert.True(t, more)
+	assert.Len(t, strings.Split(content, "\n"), 5)
+
+	_, lineCount, more, err = readTextFile(path, 95, 10)
+	require.NoError(t, err)
+	assert.Equal(t, 100, lineCount)
+	assert.False(t, more)
+
+	_, lineCount, _, err = readTextFile(path, 200, 10)
+	require.NoError(t, err)
+	assert.Equal(t, 100, lineCount)
+}
+
+func TestReadTextFileLongLine(t *testing.T) {
+	// Longer than the 64KB bufio.Scanner allows for a line
+	long := strings.Repeat("x", 100*1024)
+	path := filepath.Join(t.TempDir(), "minified.js")
+	require.NoError(t, os.WriteFile(path, []byte("first\n"+long+"\r\nlast"), 0o644))
+
+	content, lineCount, more, err := readTextFile(path, 0, 10)
+	require.NoError(t, err)
+	assert.Equal(t, 3, lineCount)
+	assert.False(t, more)
+	lines := strings.Split(content, "\n")
+	require.Len(t, lines, 3)
+	assert.Equal(t, "first", lines[0])
+	assert.Equal(t, long[:MaxLineLength]+"...", lines[1])
+	assert.Equal(t, "last", lines[2])
+}
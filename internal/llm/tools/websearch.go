@@ -0,0 +1,353 @@
+package tools
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/permission"
+)
+
+type WebSearchParams struct {
+	Query string `json:"query"`
+	Count int    `json:"count,omitempty"`
+}
+
+type WebSearchPermissionsParams struct {
+	Query    string `json:"query"`
+	Provider string `json:"provider"`
+}
+
+type WebSearchResponseMetadata struct {
+	Provider        string `json:"provider"`
+	NumberOfResults int    `json:"number_of_results"`
+}
+
+type webSearchResult struct {
+	Title   string
+	URL     string
+	Snippet string
+}
+
+type webSearchTool struct {
+	client      *http.Client
+	permissions permission.Service
+}
+
+const (
+	WebSearchToolName        = "web_search"
+	webSearchToolDescription = `Searches the web using the configured search provider and returns a list of results.
+
+WHEN TO USE THIS TOOL:
+- Use when you need up-to-date information that is not available in the codebase
+- Helpful for researching unfamiliar libraries, APIs, error messages or release notes
+- Useful for finding the right documentation page before fetching it
+
+HOW TO USE:
+- Provide a concise search query
+- Optionally specify the number of results to return (default: 5, max: 20)
+- Use the fetch tool on the most relevant result URLs to read the full content
+
+FEATURES:
+- Returns the title, URL and a short snippet for each result
+- Works with Brave Search, Tavily or a self-hosted SearXNG instance
+
+LIMITATIONS:
+- Only available when a search provider is configured
+- Snippets are short; they are not a substitute for reading the page
+- Results depend on the search provider and may be incomplete
+
+TIPS:
+- Include library names and versions in the query for more precise results
+- Prefer official documentation over blog posts when both are returned
+- Follow up with the fetch tool to read the pages you need`
+)
+
+const (
+	WebSearchProviderBrave   = "brave"
+	WebSearchProviderTavily  = "tavily"
+	WebSearchProviderSearXNG = "searxng"
+
+	defaultWebSearchCount = 5
+	maxWebSearchCount     = 20
+)
+
+func NewWebSearchTool(permissions permission.Service) BaseTool {
+	return &webSearchTool{
+		client: &http.Client{
+			Timeout: 30 * time.Second,
+		},
+		permissions: permissions,
+	}
+}
+
+func (t *webSearchTool) Info() ToolInfo {
+	return ToolInfo{
+		Name:        WebSearchToolName,
+		Description: webSearchToolDescription,
+		Parameters: map[string]any{
+			"query": map[string]any{
+				"type":        "string",
+				"description": "The search query",
+			},
+			"count": map[string]any{
+				"type":        "number",
+				"description": "Optional number of results to return (default: 5, max: 20)",
+			},
+		},
+		Required: []string{"query"},
+	}
+}
+
+func (t *webSearchTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error) {
+	var params WebSearchParams
+	if err := json.Unmarshal([]byte(call.Input), &params); err != nil {
+		return NewTextErrorResponse("Failed to parse web_search parameters: " + err.Error()), nil
+	}
+
+	if strings.TrimSpace(params.Query) == "" {
+		return NewTextErrorResponse("query parameter is required"), nil
+	}
+
+	if params.Count <= 0 {
+		params.Count = defaultWebSearchCount
+	}
+	if params.Count > maxWebSearchCount {
+		params.Count = maxWebSearchCount
+	}
+
+	searchCfg := config.Get().WebSearch
+	if searchCfg.Provider == "" {
+		return NewTextErrorResponse("No web search provider configured. Set webSearch.provider in the config file."), nil
+	}
+
+	sessionID, messageID := GetContextValues(ctx)
+	if sessionID == "" || messageID == "" {
+		return ToolResponse{}, fmt.Errorf("session ID and message ID are required for web search")
+	}
+
+	p := t.permissions.Request(
+		permission.CreatePermissionRequest{
+			SessionID:   sessionID,
+			Path:        config.WorkingDirectory(),
+			ToolName:    WebSearchToolName,
+			Action:      "search",
+			Description: fmt.Sprintf("Search the web for: %s", params.Query),
+			Params: WebSearchPermissionsParams{
+				Query:    params.Query,
+				Provider: searchCfg.Provider,
+			},
+		},
+	)
+	if !p {
+		return ToolResponse{}, permission.ErrorPermissionDenied
+	}
+
+	var (
+		results []webSearchResult
+		err     error
+	)
+	switch strings.ToLower(searchCfg.Provider) {
+	case WebSearchProviderBrave:
+		results, err = t.searchBrave(ctx, searchCfg, params)
+	case WebSearchProviderTavily:
+		results, err = t.searchTavily(ctx, searchCfg, params)
+	case WebSearchProviderSearXNG:
+		results, err = t.searchSearXNG(ctx, searchCfg, params)
+	default:
+		return NewTextErrorResponse(fmt.Sprintf("Unsupported web search provider: %s", searchCfg.Provider)), nil
+	}
+	if err != nil {
+		return NewTextErrorResponse(fmt.Sprintf("Web search failed: %s", err)), nil
+	}
+
+	if len(results) > params.Count {
+		results = results[:params.Count]
+	}
+
+	metadata := WebSearchResponseMetadata{
+		Provider:        searchCfg.Provider,
+		NumberOfResults: len(results),
+	}
+
+	if len(results) == 0 {
+		return WithResponseMetadata(NewTextResponse("No results found"), metadata), nil
+	}
+
+	return WithResponseMetadata(NewTextResponse(formatWebSearchResults(results)), metadata), nil
+}
+
+func (t *webSearchTool) searchBrave(ctx context.Context, cfg config.WebSearchConfig, params WebSearchParams) ([]webSearchResult, error) {
+	endpoint := cfg.URL
+	if endpoint == "" {
+		endpoint = "https://api.search.brave.com/res/v1/web/search"
+	}
+	apiKey := webSearchAPIKey(cfg, "BRAVE_API_KEY")
+	if apiKey == "" {
+		return nil, fmt.Errorf("brave search requires an API key")
+	}
+
+	query := url.Values{}
+	query.Set("q", params.Query)
+	query.Set("count", fmt.Sprintf("%d", params.Count))
+
+	req, err := http.NewRequestWithContext(ctx, "GET", endpoint+"?"+query.Encode(), nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Accept", "application/json")
+	req.Header.Set("X-Subscription-Token", apiKey)
+
+	var result struct {
+		Web struct {
+			Results []struct {
+				Title       string `json:"title"`
+				URL         string `json:"url"`
+				Description string `json:"description"`
+			} `json:"results"`
+		} `json:"web"`
+	}
+	if err := t.doJSON(req, &result); err != nil {
+		return nil, err
+	}
+
+	results := make([]webSearchResult, 0, len(result.Web.Results))
+	for _, r := range result.Web.Results {
+		results = append(results, webSearchResult{Title: r.Title, URL: r.URL, Snippet: r.Description})
+	}
+	return results, nil
+}
+
+func (t *webSearchTool) searchTavily(ctx context.Context, cfg config.WebSearchConfig, params WebSearchParams) ([]webSearchResult, error) {
+	endpoint := cfg.URL
+	if endpoint == "" {
+		endpoint = "https://api.tavily.com/search"
+	}
+	apiKey := webSearchAPIKey(cfg, "TAVILY_API_KEY")
+	if apiKey == "" {
+		return nil, fmt.Errorf("tavily search requires an API key")
+	}
+
+	body, err := json.Marshal(map[string]any{
+		"api_key":     apiKey,
+		"query":       params.Query,
+		"max_results": params.Count,
+	})
+	if err != nil {
+		return nil, fmt.Errorf("failed to marshal request: %w", err)
+	}
+
+	req, err := http.NewRequestWithContext(ctx, "POST", endpoint, bytes.NewBuffer(body))
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Content-Type", "application/json")
+
+	var result struct {
+		Results []struct {
+			Title   string `json:"title"`
+			URL     string `json:"url"`
+			Content string `json:"content"`
+		} `json:"results"`
+	}
+	if err := t.doJSON(req, &result); err != nil {
+		return nil, err
+	}
+
+	results := make([]webSearchResult, 0, len(result.Results))
+	for _, r := range result.Results {
+		results = append(results, webSearchResult{Title: r.Title, URL: r.URL, Snippet: r.Content})
+	}
+	return results, nil
+}
+
+// webSearchAPIKey returns the key of the config, or the one in the environment
+// variable of the provider. Only the provider the variable belongs to gets it.
+func webSearchAPIKey(cfg config.WebSearchConfig, envVar string) string {
+	if cfg.APIKey != "" {
+		return cfg.APIKey
+	}
+	return os.Getenv(envVar)
+}
+
+func (t *webSearchTool) searchSearXNG(ctx context.Context, cfg config.WebSearchConfig, params WebSearchParams) ([]webSearchResult, error) {
+	if cfg.URL == "" {
+		return nil, fmt.Errorf("searxng requires the instance URL to be configured")
+	}
+
+	query := url.Values{}
+	query.Set("q", params.Query)
+	query.Set("format", "json")
+
+	endpoint := strings.TrimSuffix(cfg.URL, "/")
+	if !strings.HasSuffix(endpoint, "/search") {
+		endpoint += "/search"
+	}
+
+	req, err := http.NewRequestWithContext(ctx, "GET", endpoint+"?"+query.Encode(), nil)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create request: %w", err)
+	}
+	req.Header.Set("Accept", "application/json")
+	if cfg.APIKey != "" {
+		req.Header.Set("Authorization", "Bearer "+cfg.APIKey)
+	}
+
+	var result struct {
+		Results []struct {
+			Title   string `json:"title"`
+			URL     string `json:"url"`
+			Content string `json:"content"`
+		} `json:"results"`
+	}
+	if err := t.doJSON(req, &result); err != nil {
+		return nil, err
+	}
+
+	results := make([]webSearchResult, 0, len(result.Results))
+	for _, r := range result.Results {
+		results = append(results, webSearchResult{Title: r.Title, URL: r.URL, Snippet: r.Content})
+	}
+	return results, nil
+}
+
+func (t *webSearchTool) doJSON(req *http.Request, v any) error {
+	req.Header.Set("User-Agent", "cryoncode/1.0")
+
+	resp, err := t.client.Do(req)
+	if err != nil {
+		return fmt.Errorf("failed to perform search: %w", err)
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 1024))
+		return fmt.Errorf("request failed with status code %d: %s", resp.StatusCode, strings.TrimSpace(string(body)))
+	}
+
+	if err := json.NewDecoder(io.LimitReader(resp.Body, 5*1024*1024)).Decode(v); err != nil {
+		return fmt.Errorf("failed to parse response: %w", err)
+	}
+	return nil
+}
+
+func formatWebSearchResults(results []webSearchResult) string {
+	var sb strings.Builder
+	for i, r := range results {
+		sb.WriteString(fmt.Sprintf("%d. %s\n", i+1, r.Title))
+		sb.WriteString(fmt.Sprintf("   URL: %s\n", r.URL))
+		if snippet := strings.Join(strings.Fields(r.Snippet), " "); snippet != "" {
+			sb.WriteString(fmt.Sprintf("   %s\n", snippet))
+		}
+		sb.WriteString("\n")
+	}
+	return strings.TrimRight(sb.String(), "\n")
+}
@@ -0,0 +1,18 @@
+package tools
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+func TestWebSearchAPIKey(t *testing.T) {
+	t.Setenv("BRAVE_API_KEY", "brave-key")
+	t.Setenv("TAVILY_API_KEY", "")
+
+	assert.Equal(t, "brave-key", webSearchAPIKey(config.WebSearchConfig{Provider: "brave"}, "BRAVE_API_KEY"))
+	// The Brave key is never given to another provider
+	assert.Empty(t, webSearchAPIKey(config.WebSearchConfig{Provider: "tavily"}, "TAVILY_API_KEY"))
+	assert.Equal(t, "own-key", webSearchAPIKey(config.WebSearchConfig{Provider: "tavily", APIKey: "own-key"}, "TAVILY_API_KEY"))
+}
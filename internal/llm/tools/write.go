@@ -15,6 +15,7 @@ import (
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
 )
 
 type WriteParams struct {
@@ -23,14 +24,17 @@ type WriteParams struct {
 }
 
 type WritePermissionsParams struct {
-	FilePath string `json:"file_path"`
-	Diff     string `json:"diff"`
+	FilePath  string `json:"file_path"`
+	Diff      string `json:"diff"`
+	Additions int    `json:"additions"`
+	Removals  int    `json:"removals"`
 }
 
 type writeTool struct {
 	lspClients  map[string]*lsp.Client
 	permissions permission.Service
 	files       history.Service
+	snapshots   snapshot.Service
 }
 
 type WriteResponseMetadata struct {
@@ -71,11 +75,12 @@ TIPS:
 - Always include descriptive comments when making changes to existing code`
 )
 
-func NewWriteTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service) BaseTool {
+func NewWriteTool(lspClients map[string]*lsp.Client, permissions permission.Service, files history.Service, snapshots snapshot.Service) BaseTool {
 	return &writeTool{
 		lspClients:  lspClients,
 		permissions: permissions,
 		files:       files,
+		snapshots:   snapshots,
 	}
 }
 
@@ -116,6 +121,10 @@ func (w *writeTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 		filePath = filepath.Join(config.WorkingDirectory(), filePath)
 	}
 
+	if err := checkWritePath(filePath); err != nil {
+		return NewTextErrorResponse(err.Error()), nil
+	}
+
 	fileInfo, err := os.Stat(filePath)
 	if err == nil {
 		if fileInfo.IsDir() {
@@ -174,8 +183,10 @@ func (w *writeTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 			Action:      "write",
 			Description: fmt.Sprintf("Create file %s", filePath),
 			Params: WritePermissionsParams{
-				FilePath: filePath,
-				Diff:     diff,
+				FilePath:  filePath,
+				Diff:      diff,
+				Additions: additions,
+				Removals:  removals,
 			},
 		},
 	)
@@ -183,6 +194,10 @@ func (w *writeTool) Run(ctx context.Context, call ToolCall) (ToolResponse, error
 		return ToolResponse{}, permission.ErrorPermissionDenied
 	}
 
+	if err := w.snapshots.Take(ctx, sessionID, messageID, filePath); err != nil {
+		return ToolResponse{}, fmt.Errorf("failed to snapshot file: %w", err)
+	}
+
 	err = os.WriteFile(filePath, []byte(params.Content), 0o644)
 	if err != nil {
 		return ToolResponse{}, fmt.Errorf("error writing file: %w", err)
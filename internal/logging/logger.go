@@ -1,11 +1,11 @@
 package logging
 
 import (
+	"encoding/json"
 	"fmt"
 	"log/slog"
 	"os"
-	// "path/filepath"
-	"encoding/json"
+	"path/filepath"
 	"runtime"
 	"runtime/debug"
 	"sync"
@@ -61,6 +61,10 @@ func ErrorPersist(msg string, args ...any) {
 	slog.Error(msg, args...)
 }
 
+// PanicDir is the directory panic logs are written to, the current directory
+// when it's empty
+var PanicDir string
+
 // RecoverPanic is a common function to handle panics gracefully.
 // It logs the error, creates a panic log file with stack trace,
 // and executes an optional cleanup function before returning.
@@ -72,6 +76,9 @@ func RecoverPanic(name string, cleanup func()) {
 		// Create a timestamped panic log file
 		timestamp := time.Now().Format("20060102-150405")
 		filename := fmt.Sprintf("cryoncode-panic-%s-%s.log", name, timestamp)
+		if PanicDir != "" {
+			filename = filepath.Join(PanicDir, filename)
+		}
 
 		file, err := os.Create(filename)
 		if err != nil {
@@ -0,0 +1,182 @@
+package message
+
+import (
+	"context"
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"slices"
+
+	"github.com/zhenbah/cryoncode/internal/crypt"
+	"github.com/zhenbah/cryoncode/internal/db"
+)
+
+// blobThreshold is the size from which the data of an attachment or the full
+// output of a tool is kept in the blobs table instead of in the parts of its
+// message, which are read and written whole
+const blobThreshold = 64 * 1024
+
+// blobID returns the ID of the blob holding the data, its SHA-256, so the same
+// content is only stored once
+func blobID(data []byte) string {
+	sum := sha256.Sum256(data)
+	return hex.EncodeToString(sum[:])
+}
+
+// externalize returns a copy of the parts with the large content replaced by
+// the ID of its blob, and the content of the blobs by ID
+func externalize(parts []ContentPart) ([]ContentPart, map[string][]byte) {
+	parts = slices.Clone(parts)
+	blobs := make(map[string][]byte)
+	for i, part := range parts {
+		switch p := part.(type) {
+		case BinaryContent:
+			if len(p.Data) >= blobThreshold {
+				p.Blob = blobID(p.Data)
+				blobs[p.Blob] = p.Data
+				p.Data = nil
+				parts[i] = p
+			}
+		case ToolResult:
+			if len(p.FullContent) >= blobThreshold {
+				data := []byte(p.FullContent)
+				p.FullContentBlob = blobID(data)
+				blobs[p.FullContentBlob] = data
+				p.FullContent = ""
+				parts[i] = p
+			}
+		}
+	}
+	return parts, blobs
+}
+
+// storeBlobs writes the large content of the parts to blobs, and returns the
+// parts to store in the message and the IDs of the blobs to link to it
+func storeBlobs(ctx context.Context, q db.Querier, cipher *crypt.Cipher, parts []ContentPart) ([]ContentPart, []string, error) {
+	parts, blobs := externalize(parts)
+	ids := make([]string, 0, len(blobs))
+	for id, data := range blobs {
+		stored, err := cipher.Encrypt(string(data))
+		if err != nil {
+			return nil, nil, err
+		}
+		if err := q.CreateBlob(ctx, db.CreateBlobParams{
+			ID:   id,
+			Data: []byte(stored),
+			Size: int64(len(data)),
+		}); err != nil {
+			return nil, nil, fmt.Errorf("failed to store blob %s: %w", id, err)
+		}
+		ids = append(ids, id)
+	}
+	return parts, ids, nil
+}
+
+// linkBlobs records that the message uses the blobs, a blob is deleted with
+// the last message using it
+func linkBlobs(ctx context.Context, q db.Querier, messageID string, ids []string) error {
+	for _, id := range ids {
+		if err := q.LinkMessageBlob(ctx, db.LinkMessageBlobParams{
+			MessageID: messageID,
+			BlobID:    id,
+		}); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// loadBlobs puts the content kept in blobs back in the parts
+func loadBlobs(ctx context.Context, q db.Querier, cipher *crypt.Cipher, parts []ContentPart) error {
+	for i, part := range parts {
+		switch p := part.(type) {
+		case BinaryContent:
+			if p.Blob == "" {
+				continue
+			}
+			data, err := readBlob(ctx, q, cipher, p.Blob)
+			if err != nil {
+				return err
+			}
+			p.Data = []byte(data)
+			parts[i] = p
+		case ToolResult:
+			if p.FullContentBlob == "" {
+				continue
+			}
+			data, err := readBlob(ctx, q, cipher, p.FullContentBlob)
+			if err != nil {
+				return err
+			}
+			p.FullContent = data
+			parts[i] = p
+		}
+	}
+	return nil
+}
+
+func readBlob(ctx context.Context, q db.Querier, cipher *crypt.Cipher, id string) (string, error) {
+	blob, err := q.GetBlob(ctx, id)
+	if err != nil {
+		return "", fmt.Errorf("failed to read blob %s: %w", id, err)
+	}
+	return cipher.Decrypt(string(blob.Data))
+}
+
+// InlineBlobs returns the stored parts of a message with the content kept in
+// blobs put back in them, so they can be read without the database
+func InlineBlobs(ctx context.Context, q db.Querier, cipher *crypt.Cipher, partsJSON string) (string, error) {
+	parts, err := unmarshallParts([]byte(partsJSON))
+	if err != nil {
+		return "", err
+	}
+	if !hasBlobs(parts) {
+		return partsJSON, nil
+	}
+	if err := loadBlobs(ctx, q, cipher, parts); err != nil {
+		return "", err
+	}
+	data, err := marshallParts(parts)
+	return string(data), err
+}
+
+// StoreBlobs moves the large content of the parts of a message to blobs and
+// returns the parts to store, and the blobs to link to the message with
+// LinkBlobs once it's created
+func StoreBlobs(ctx context.Context, q db.Querier, cipher *crypt.Cipher, partsJSON string) (string, []string, error) {
+	parts, err := unmarshallParts([]byte(partsJSON))
+	if err != nil {
+		return "", nil, err
+	}
+	parts, ids, err := storeBlobs(ctx, q, cipher, parts)
+	if err != nil {
+		return "", nil, err
+	}
+	if len(ids) == 0 {
+		return partsJSON, nil, nil
+	}
+	data, err := marshallParts(parts)
+	return string(data), ids, err
+}
+
+// LinkBlobs links the blobs returned by StoreBlobs to the message
+func LinkBlobs(ctx context.Context, q db.Querier, messageID string, ids []string) error {
+	return linkBlobs(ctx, q, messageID, ids)
+}
+
+// hasBlobs reports whether some content of the parts is kept in blobs
+func hasBlobs(parts []ContentPart) bool {
+	for _, part := range parts {
+		switch p := part.(type) {
+		case BinaryContent:
+			if p.Blob != "" {
+				return true
+			}
+		case ToolResult:
+			if p.FullContentBlob != "" {
+				return true
+			}
+		}
+	}
+	return false
+}
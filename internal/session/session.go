@@ -3,12 +3,40 @@ package session
 import (
 	"context"
 	"database/sql"
+	"encoding/json"
+	"fmt"
+	"slices"
+	"strconv"
+	"strings"
+	"time"
+	"unicode"
 
 	"github.com/google/uuid"
 	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
 )
 
+// DefaultTitle is the title of sessions the user didn't name, it is replaced
+// by a title generated from the first message.
+const DefaultTitle = "New Session"
+
+type TodoStatus string
+
+const (
+	TodoPending    TodoStatus = "pending"
+	TodoInProgress TodoStatus = "in_progress"
+	TodoCompleted  TodoStatus = "completed"
+)
+
+// Todo is a task of the plan the agent keeps for a session.
+type Todo struct {
+	Content string     `json:"content"`
+	Status  TodoStatus `json:"status"`
+}
+
 type Session struct {
 	ID               string
 	ParentSessionID  string
@@ -17,20 +45,78 @@ type Session struct {
 	PromptTokens     int64
 	CompletionTokens int64
 	SummaryMessageID string
+	KeptMessageID    string // first message of the turns sent verbatim after the summary
 	Cost             float64
+	Todos            []Todo
+	Model            models.ModelID // empty to use the configured model
+	MaxTokens        int64          // zero to use the configured max tokens
+	ReasoningEffort  string
+	SystemPrompt     string // empty to use the prompt of the agent
+	Agent            string // empty for the coder, or the agent in use when it wasn't run yet
+	Tags             []string
+	Archived         bool // hidden from the session list
+	Pinned           bool // listed before the other sessions
 	CreatedAt        int64
 	UpdatedAt        int64
 }
 
+// Settings are the settings of the agent a session was run with. The
+// provider is the one of the model.
+type Settings struct {
+	Model           models.ModelID
+	MaxTokens       int64
+	ReasoningEffort string
+	SystemPrompt    string
+	Agent           string
+}
+
+// Settings returns the settings recorded for the session, empty when it
+// hasn't been run yet
+func (s Session) Settings() Settings {
+	return Settings{
+		Model:           s.Model,
+		MaxTokens:       s.MaxTokens,
+		ReasoningEffort: s.ReasoningEffort,
+		SystemPrompt:    s.SystemPrompt,
+		Agent:           s.Agent,
+	}
+}
+
+// HasTag reports whether the session is tagged with the tag
+func (s Session) HasTag(tag string) bool {
+	return slices.Contains(s.Tags, tag)
+}
+
+// SearchResult is a session found by Search, with the first message sent in it
+type SearchResult struct {
+	ID           string
+	Title        string
+	FirstMessage string
+	UpdatedAt    int64
+}
+
 type Service interface {
 	pubsub.Suscriber[Session]
 	Create(ctx context.Context, title string) (Session, error)
 	CreateTitleSession(ctx context.Context, parentSessionID string) (Session, error)
 	CreateTaskSession(ctx context.Context, toolCallID, parentSessionID, title string) (Session, error)
+	// Fork creates a session with the messages of the session up to the
+	// response to the prompt messageID
+	Fork(ctx context.Context, id, messageID string) (Session, error)
 	Get(ctx context.Context, id string) (Session, error)
 	List(ctx context.Context) ([]Session, error)
+	Search(ctx context.Context, query string, limit int) ([]SearchResult, error)
 	Save(ctx context.Context, session Session) (Session, error)
+	SetTodos(ctx context.Context, id string, todos []Todo) (Session, error)
+	SetSettings(ctx context.Context, id string, settings Settings) (Session, error)
+	AddTag(ctx context.Context, id, tag string) (Session, error)
+	RemoveTag(ctx context.Context, id, tag string) (Session, error)
+	SetArchived(ctx context.Context, id string, archived bool) (Session, error)
+	SetPinned(ctx context.Context, id string, pinned bool) (Session, error)
 	Delete(ctx context.Context, id string) error
+	// Prune deletes the sessions last active before the time, or archives
+	// them, and returns them. Pinned sessions are kept.
+	Prune(ctx context.Context, before time.Time, archive bool) ([]Session, error)
 }
 
 type service struct {
@@ -79,11 +165,145 @@ func (s *service) CreateTitleSession(ctx context.Context, parentSessionID string
 	return session, nil
 }
 
+func (s *service) Fork(ctx context.Context, id, messageID string) (Session, error) {
+	original, err := s.Get(ctx, id)
+	if err != nil {
+		return Session{}, err
+	}
+	messages, err := s.q.ListMessagesBySession(ctx, id)
+	if err != nil {
+		return Session{}, err
+	}
+	end, ok := forkEnd(messages, messageID)
+	if !ok {
+		return Session{}, fmt.Errorf("message %s is not a prompt of session %s", messageID, id)
+	}
+
+	dbSession, err := s.q.CreateSession(ctx, db.CreateSessionParams{
+		ID:    uuid.New().String(),
+		Title: original.Title + " (fork)",
+	})
+	if err != nil {
+		return Session{}, err
+	}
+	fork, err := s.copyMessages(ctx, original, dbSession.ID, messages[:end])
+	if err == nil {
+		err = s.copyTags(ctx, original, fork.ID)
+	}
+	if err != nil {
+		// Don't leave a session with part of the history behind
+		if deleteErr := s.q.DeleteSession(ctx, dbSession.ID); deleteErr != nil {
+			logging.Warn("failed to delete the incomplete fork", "session_id", dbSession.ID, "error", deleteErr)
+		}
+		return Session{}, err
+	}
+	session, err := s.withTags(ctx, fork)
+	if err != nil {
+		return Session{}, err
+	}
+	s.Publish(pubsub.CreatedEvent, session)
+	return session, nil
+}
+
+// copyTags tags the fork with the tags of the original session
+func (s *service) copyTags(ctx context.Context, original Session, forkID string) error {
+	for _, tag := range original.Tags {
+		if err := s.q.AddSessionTag(ctx, db.AddSessionTagParams{
+			SessionID: forkID,
+			Tag:       tag,
+		}); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// copyMessages copies the messages to the fork under new IDs, with the
+// summary and model of the original session
+func (s *service) copyMessages(ctx context.Context, original Session, forkID string, messages []db.Message) (db.Session, error) {
+	messageIDs := make(map[string]string, len(messages))
+	for _, msg := range messages {
+		messageIDs[msg.ID] = uuid.New().String()
+		if err := s.q.ImportMessage(ctx, db.ImportMessageParams{
+			ID:         messageIDs[msg.ID],
+			SessionID:  forkID,
+			Role:       msg.Role,
+			Parts:      msg.Parts,
+			Model:      msg.Model,
+			CreatedAt:  msg.CreatedAt,
+			UpdatedAt:  msg.UpdatedAt,
+			FinishedAt: msg.FinishedAt,
+		}); err != nil {
+			return db.Session{}, err
+		}
+		// The copy uses the blobs of the original, they must outlive it
+		blobs, err := s.q.ListMessageBlobs(ctx, msg.ID)
+		if err != nil {
+			return db.Session{}, err
+		}
+		if err := message.LinkBlobs(ctx, s.q, messageIDs[msg.ID], blobs); err != nil {
+			return db.Session{}, err
+		}
+	}
+
+	// The summary is kept when it was written before the fork point, the
+	// tokens and cost are counted again from the next response
+	summaryID, ok := messageIDs[original.SummaryMessageID]
+	keptID, kept := messageIDs[original.KeptMessageID]
+	fork, err := s.q.UpdateSession(ctx, db.UpdateSessionParams{
+		ID:               forkID,
+		Title:            original.Title + " (fork)",
+		SummaryMessageID: sql.NullString{String: summaryID, Valid: ok},
+		KeptMessageID:    sql.NullString{String: keptID, Valid: ok && kept},
+	})
+	if err != nil {
+		return db.Session{}, err
+	}
+	if original.Model != "" {
+		return s.q.UpdateSessionSettings(ctx, db.UpdateSessionSettingsParams{
+			ID:              forkID,
+			Model:           string(original.Model),
+			MaxTokens:       original.MaxTokens,
+			ReasoningEffort: original.ReasoningEffort,
+			SystemPrompt:    original.SystemPrompt,
+			Agent:           original.Agent,
+		})
+	}
+	return fork, nil
+}
+
+// forkEnd returns the number of messages kept by a fork at the prompt: the
+// prompt and the messages up to the next prompt
+func forkEnd(messages []db.Message, promptID string) (int, bool) {
+	start := -1
+	for i, msg := range messages {
+		switch {
+		case msg.ID == promptID:
+			if msg.Role != string(message.User) {
+				return 0, false
+			}
+			start = i
+		case start >= 0 && msg.Role == string(message.User):
+			return i, true
+		}
+	}
+	if start < 0 {
+		return 0, false
+	}
+	return len(messages), true
+}
+
 func (s *service) Delete(ctx context.Context, id string) error {
 	session, err := s.Get(ctx, id)
 	if err != nil {
 		return err
 	}
+	// Sessions of sub-agents and title generation go with their parent, the
+	// messages and files of each session are removed by the foreign keys
+	err = s.q.DeleteChildSessions(ctx, sql.NullString{String: session.ID, Valid: true})
+	if err != nil {
+		return err
+	}
 	err = s.q.DeleteSession(ctx, session.ID)
 	if err != nil {
 		return err
@@ -92,12 +312,66 @@ func (s *service) Delete(ctx context.Context, id string) error {
 	return nil
 }
 
+func (s *service) Prune(ctx context.Context, before time.Time, archive bool) ([]Session, error) {
+	sessions, err := s.List(ctx)
+	if err != nil {
+		return nil, err
+	}
+	var pruned []Session
+	for _, session := range Stale(sessions, before) {
+		if archive {
+			if session.Archived {
+				continue
+			}
+			_, err = s.SetArchived(ctx, session.ID, true)
+		} else {
+			err = s.Delete(ctx, session.ID)
+		}
+		if err != nil {
+			return pruned, err
+		}
+		pruned = append(pruned, session)
+	}
+	return pruned, nil
+}
+
+// Stale returns the sessions last active before the time, except the pinned
+// ones
+func Stale(sessions []Session, before time.Time) []Session {
+	var stale []Session
+	for _, session := range sessions {
+		if !session.Pinned && session.UpdatedAt < before.Unix() {
+			stale = append(stale, session)
+		}
+	}
+	return stale
+}
+
+// ParseAge parses an age such as 30d, 2w or 12h. Days and weeks are added to
+// the units of time.ParseDuration.
+func ParseAge(age string) (time.Duration, error) {
+	for suffix, unit := range map[string]time.Duration{"d": 24 * time.Hour, "w": 7 * 24 * time.Hour} {
+		if n, ok := strings.CutSuffix(age, suffix); ok {
+			count, err := strconv.Atoi(n)
+			if err != nil || count < 0 {
+				return 0, fmt.Errorf("invalid age %q, use a number of days such as 30d", age)
+			}
+			return time.Duration(count) * unit, nil
+		}
+	}
+	d, err := time.ParseDuration(age)
+	if err != nil || d < 0 {
+		return 0, fmt.Errorf("invalid age %q, use a number of days such as 30d", age)
+	}
+	return d, nil
+}
+
 func (s *service) Get(ctx context.Context, id string) (Session, error) {
 	dbSession, err := s.q.GetSessionByID(ctx, id)
 	if err != nil {
 		return Session{}, err
 	}
-	return s.fromDBItem(dbSession), nil
+	return s.withTags(ctx, dbSession)
 }
 
 func (s *service) Save(ctx context.Context, session Session) (Session, error) {
@@ -110,29 +384,232 @@ func (s *service) Save(ctx context.Context, session Session) (Session, error) {
 			String: session.SummaryMessageID,
 			Valid:  session.SummaryMessageID != "",
 		},
+		KeptMessageID: sql.NullString{
+			String: session.KeptMessageID,
+			Valid:  session.KeptMessageID != "",
+		},
 		Cost: session.Cost,
 	})
 	if err != nil {
 		return Session{}, err
 	}
-	session = s.fromDBItem(dbSession)
+	session, err = s.withTags(ctx, dbSession)
+	if err != nil {
+		return Session{}, err
+	}
 	s.Publish(pubsub.UpdatedEvent, session)
 	return session, nil
 }
 
+// SetTodos replaces the todo list of the session. It is saved separately from
+// the other fields so concurrent saves of the session don't overwrite it.
+func (s *service) SetTodos(ctx context.Context, id string, todos []Todo) (Session, error) {
+	if todos == nil {
+		todos = []Todo{}
+	}
+	data, err := json.Marshal(todos)
+	if err != nil {
+		return Session{}, err
+	}
+	dbSession, err := s.q.UpdateSessionTodos(ctx, db.UpdateSessionTodosParams{
+		ID:    id,
+		Todos: string(data),
+	})
+	if err != nil {
+		return Session{}, err
+	}
+	session, err := s.withTags(ctx, dbSession)
+	if err != nil {
+		return Session{}, err
+	}
+	s.Publish(pubsub.UpdatedEvent, session)
+	return session, nil
+}
+
+// SetSettings records the settings the session is run with, to switch back
+// to them when the session is opened again.
+func (s *service) SetSettings(ctx context.Context, id string, settings Settings) (Session, error) {
+	dbSession, err := s.q.UpdateSessionSettings(ctx, db.UpdateSessionSettingsParams{
+		ID:              id,
+		Model:           string(settings.Model),
+		MaxTokens:       settings.MaxTokens,
+		ReasoningEffort: settings.ReasoningEffort,
+		SystemPrompt:    settings.SystemPrompt,
+		Agent:           settings.Agent,
+	})
+	if err != nil {
+		return Session{}, err
+	}
+	session, err := s.withTags(ctx, dbSession)
+	if err != nil {
+		return Session{}, err
+	}
+	s.Publish(pubsub.UpdatedEvent, session)
+	return session, nil
+}
+
+// SetArchived archives the session, or brings it back to the session list
+func (s *service) SetArchived(ctx context.Context, id string, archived bool) (Session, error) {
+	dbSession, err := s.q.UpdateSessionArchived(ctx, db.UpdateSessionArchivedParams{
+		ID:       id,
+		Archived: archived,
+	})
+	if err != nil {
+		return Session{}, err
+	}
+	session, err := s.withTags(ctx, dbSession)
+	if err != nil {
+		return Session{}, err
+	}
+	s.Publish(pubsub.UpdatedEvent, session)
+	return session, nil
+}
+
+// SetPinned pins the session to the top of the session list, or unpins it
+func (s *service) SetPinned(ctx context.Context, id string, pinned bool) (Session, error) {
+	dbSession, err := s.q.UpdateSessionPinned(ctx, db.UpdateSessionPinnedParams{
+		ID:     id,
+		Pinned: pinned,
+	})
+	if err != nil {
+		return Session{}, err
+	}
+	session, err := s.withTags(ctx, dbSession)
+	if err != nil {
+		return Session{}, err
+	}
+	s.Publish(pubsub.UpdatedEvent, session)
+	return session, nil
+}
+
+// AddTag tags the session, the tag is normalized by NormalizeTag
+func (s *service) AddTag(ctx context.Context, id, tag string) (Session, error) {
+	tag = NormalizeTag(tag)
+	if tag == "" {
+		return Session{}, fmt.Errorf("empty tag")
+	}
+	if err := s.q.AddSessionTag(ctx, db.AddSessionTagParams{
+		SessionID: id,
+		Tag:       tag,
+	}); err != nil {
+		return Session{}, err
+	}
+	return s.publishTags(ctx, id)
+}
+
+// RemoveTag removes the tag from the session
+func (s *service) RemoveTag(ctx context.Context, id, tag string) (Session, error) {
+	if err := s.q.DeleteSessionTag(ctx, db.DeleteSessionTagParams{
+		SessionID: id,
+		Tag:       NormalizeTag(tag),
+	}); err != nil {
+		return Session{}, err
+	}
+	return s.publishTags(ctx, id)
+}
+
+func (s *service) publishTags(ctx context.Context, id string) (Session, error) {
+	session, err := s.Get(ctx, id)
+	if err != nil {
+		return Session{}, err
+	}
+	s.Publish(pubsub.UpdatedEvent, session)
+	return session, nil
+}
+
+// NormalizeTag returns the tag as it's stored: lowercase, without a leading
+// #, and with dashes in place of whitespace
+func NormalizeTag(tag string) string {
+	tag = strings.TrimPrefix(strings.TrimSpace(tag), "#")
+	return strings.ToLower(strings.Join(strings.Fields(tag), "-"))
+}
+
+// List returns the sessions started by the user, archived ones included, the
+// pinned sessions first and then the most recently active
 func (s *service) List(ctx context.Context) ([]Session, error) {
 	dbSessions, err := s.q.ListSessions(ctx)
 	if err != nil {
 		return nil, err
 	}
+	dbTags, err := s.q.ListSessionTags(ctx)
+	if err != nil {
+		return nil, err
+	}
+	tags := make(map[string][]string)
+	for _, t := range dbTags {
+		tags[t.SessionID] = append(tags[t.SessionID], t.Tag)
+	}
 	sessions := make([]Session, len(dbSessions))
 	for i, dbSession := range dbSessions {
 		sessions[i] = s.fromDBItem(dbSession)
+		sessions[i].Tags = tags[dbSession.ID]
 	}
 	return sessions, nil
 }
 
+// Search returns the sessions whose title or first message contains the
+// characters of the query in order, sessions matching by title first and the
+// most recent first otherwise.
+func (s *service) Search(ctx context.Context, query string, limit int) ([]SearchResult, error) {
+	rows, err := s.q.SearchSessions(ctx, db.SearchSessionsParams{
+		Pattern:    fuzzyPattern(query),
+		MaxResults: int64(limit),
+	})
+	if err != nil {
+		return nil, err
+	}
+	results := make([]SearchResult, len(rows))
+	for i, row := range rows {
+		results[i] = SearchResult{
+			ID:           row.ID,
+			Title:        row.Title,
+			FirstMessage: row.FirstMessage,
+			UpdatedAt:    row.UpdatedAt,
+		}
+	}
+	return results, nil
+}
+
+// fuzzyPattern turns the query into a LIKE pattern matching text that has its
+// characters in order with anything between them, e.g. "%f%i%x%" for "fix".
+// Whitespace is ignored and the wildcards of LIKE are escaped with a
+// backslash.
+func fuzzyPattern(query string) string {
+	var b strings.Builder
+	b.WriteByte('%')
+	for _, r := range query {
+		if unicode.IsSpace(r) {
+			continue
+		}
+		if r == '%' || r == '_' || r == '\\' {
+			b.WriteByte('\\')
+		}
+		b.WriteRune(r)
+		b.WriteByte('%')
+	}
+	return b.String()
+}
+
+// withTags converts the session and loads its tags
+func (s *service) withTags(ctx context.Context, item db.Session) (Session, error) {
+	session := s.fromDBItem(item)
+	tags, err := s.q.ListTagsBySession(ctx, item.ID)
+	if err != nil {
+		return Session{}, err
+	}
+	if len(tags) > 0 {
+		session.Tags = tags
+	}
+	return session, nil
+}
+
 func (s service) fromDBItem(item db.Session) Session {
+	var todos []Todo
+	if item.Todos != "" {
+		if err := json.Unmarshal([]byte(item.Todos), &todos); err != nil {
+			logging.Warn("invalid session todos", "session_id", item.ID, "error", err)
+		}
+	}
 	return Session{
 		ID:               item.ID,
 		ParentSessionID:  item.ParentSessionID.String,
@@ -141,7 +618,16 @@ func (s service) fromDBItem(item db.Session) Session {
 		PromptTokens:     item.PromptTokens,
 		CompletionTokens: item.CompletionTokens,
 		SummaryMessageID: item.SummaryMessageID.String,
+		KeptMessageID:    item.KeptMessageID.String,
 		Cost:             item.Cost,
+		Todos:            todos,
+		Model:            models.ModelID(item.Model),
+		MaxTokens:        item.MaxTokens,
+		ReasoningEffort:  item.ReasoningEffort,
+		SystemPrompt:     item.SystemPrompt,
+		Agent:            item.Agent,
+		Archived:         item.Archived,
+		Pinned:           item.Pinned,
 		CreatedAt:        item.CreatedAt,
 		UpdatedAt:        item.UpdatedAt,
 	}
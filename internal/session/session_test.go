@@ -0,0 +1,126 @@
+package session
+
+import (
+	"context"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+func TestFuzzyPattern(t *testing.T) {
+	assert.Equal(t, "%", fuzzyPattern(""))
+	assert.Equal(t, "%f%i%x%", fuzzyPattern("fix"))
+	assert.Equal(t, "%f%i%x%b%u%g%", fuzzyPattern(" fix  bug "))
+	assert.Equal(t, `%1%0%0%\%%a%\_%b%\\%`, fuzzyPattern(`100%a_b\`))
+	assert.Equal(t, "%é%t%é%", fuzzyPattern("été"))
+}
+
+func TestNormalizeTag(t *testing.T) {
+	assert.Equal(t, "api", NormalizeTag("api"))
+	assert.Equal(t, "api", NormalizeTag(" #API "))
+	assert.Equal(t, "release-notes", NormalizeTag("Release  notes"))
+	assert.Equal(t, "", NormalizeTag("#"))
+}
+
+func TestForkEnd(t *testing.T) {
+	messages := []db.Message{
+		{ID: "1", Role: "user"},
+		{ID: "2", Role: "assistant"},
+		{ID: "3", Role: "tool"},
+		{ID: "4", Role: "assistant"},
+		{ID: "5", Role: "user"},
+		{ID: "6", Role: "assistant"},
+	}
+
+	end, ok := forkEnd(messages, "1")
+	assert.True(t, ok)
+	assert.Equal(t, 4, end)
+
+	end, ok = forkEnd(messages, "5")
+	assert.True(t, ok)
+	assert.Equal(t, 6, end)
+
+	_, ok = forkEnd(messages, "2")
+	assert.False(t, ok)
+	_, ok = forkEnd(messages, "7")
+	assert.False(t, ok)
+}
+
+func TestForkKeepsBlobs(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+	t.Setenv("OPENAI_API_KEY", "test")
+	_, err := config.Load(t.TempDir(), false)
+	require.NoError(t, err)
+	conn, err := db.Connect(false)
+	require.NoError(t, err)
+	defer conn.Close()
+
+	ctx := context.Background()
+	q := db.New(conn)
+	sessions := NewService(q)
+	messages := message.NewService(q, nil)
+
+	original, err := sessions.Create(ctx, "original")
+	require.NoError(t, err)
+	prompt, err := messages.Create(ctx, original.ID, message.CreateMessageParams{
+		Role:  message.User,
+		Parts: []message.ContentPart{message.TextContent{Text: "build it"}},
+	})
+	require.NoError(t, err)
+	// Large enough to be kept in a blob
+	output := strings.Repeat("output\n", 20*1024)
+	_, err = messages.Create(ctx, original.ID, message.CreateMessageParams{
+		Role:  message.Tool,
+		Parts: []message.ContentPart{message.ToolResult{ToolCallID: "call", Name: "bash", Content: "output", FullContent: output}},
+	})
+	require.NoError(t, err)
+
+	fork, err := sessions.Fork(ctx, original.ID, prompt.ID)
+	require.NoError(t, err)
+	require.NoError(t, sessions.Delete(ctx, original.ID))
+
+	forked, err := messages.List(ctx, fork.ID)
+	require.NoError(t, err)
+	require.Len(t, forked, 2)
+	require.Len(t, forked[1].ToolResults(), 1)
+	assert.Equal(t, output, forked[1].ToolResults()[0].FullContent)
+}
+
+func TestParseAge(t *testing.T) {
+	for age, expected := range map[string]time.Duration{
+		"30d": 30 * 24 * time.Hour,
+		"2w":  14 * 24 * time.Hour,
+		"12h": 12 * time.Hour,
+		"0d":  0,
+	} {
+		d, err := ParseAge(age)
+		require.NoError(t, err, age)
+		assert.Equal(t, expected, d, age)
+	}
+	for _, age := range []string{"", "30", "d", "-1d", "1.5d", "-2h"} {
+		_, err := ParseAge(age)
+		assert.Error(t, err, age)
+	}
+}
+
+func TestStale(t *testing.T) {
+	now := time.Unix(1_000_000, 0)
+	sessions := []Session{
+		{ID: "old", UpdatedAt: now.Unix() - 100},
+		{ID: "pinned", UpdatedAt: now.Unix() - 100, Pinned: true},
+		{ID: "recent", UpdatedAt: now.Unix()},
+	}
+
+	stale := Stale(sessions, now.Add(-time.Minute))
+	require.Len(t, stale, 1)
+	assert.Equal(t, "old", stale[0].ID)
+	assert.Empty(t, Stale(sessions, now.Add(-time.Hour)))
+}
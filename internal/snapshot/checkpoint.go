@@ -0,0 +1,118 @@
+package snapshot
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/google/uuid"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+// Checkpoint is the state of a session at the end of a turn: the
+// conversation up to the responses to the prompt, and the files as the turn
+// left them.
+type Checkpoint struct {
+	ID        string
+	SessionID string
+	// MessageID is the prompt that started the turn
+	MessageID string
+	// Files are the paths changed during the turn
+	Files     []string
+	CreatedAt int64
+}
+
+func (s *service) Checkpoint(ctx context.Context, sessionID, promptID string) error {
+	id := uuid.New().String()
+	if err := s.q.CreateCheckpoint(ctx, db.CreateCheckpointParams{
+		ID:        id,
+		SessionID: sessionID,
+		MessageID: promptID,
+	}); err != nil {
+		return err
+	}
+	return s.q.LinkCheckpointSnapshots(ctx, db.LinkCheckpointSnapshotsParams{
+		CheckpointID: id,
+		SessionID:    sessionID,
+	})
+}
+
+func (s *service) ListCheckpoints(ctx context.Context, sessionID string) ([]Checkpoint, error) {
+	dbCheckpoints, err := s.q.ListCheckpointsBySession(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	files, err := s.q.ListCheckpointFiles(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	paths := make(map[string][]string)
+	for _, file := range files {
+		// A file changed more than once in a turn has a snapshot for each
+		// message that changed it
+		if p := paths[file.CheckpointID]; len(p) == 0 || p[len(p)-1] != file.Path {
+			paths[file.CheckpointID] = append(p, file.Path)
+		}
+	}
+
+	checkpoints := make([]Checkpoint, len(dbCheckpoints))
+	for i, c := range dbCheckpoints {
+		checkpoints[i] = Checkpoint{
+			ID:        c.ID,
+			SessionID: c.SessionID,
+			MessageID: c.MessageID,
+			Files:     paths[c.ID],
+			CreatedAt: c.CreatedAt,
+		}
+	}
+	return checkpoints, nil
+}
+
+func (s *service) RestoreCheckpoint(ctx context.Context, sessionID, checkpointID string) ([]string, error) {
+	checkpoints, err := s.ListCheckpoints(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	var promptID string
+	for _, c := range checkpoints {
+		if c.ID == checkpointID {
+			promptID = c.MessageID
+		}
+	}
+	if promptID == "" {
+		return nil, fmt.Errorf("checkpoint %s not found in session", checkpointID)
+	}
+
+	messages, err := s.messages.List(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	nextID, ok := nextPrompt(messages, promptID)
+	if !ok {
+		// Nothing happened since the checkpoint
+		return nil, nil
+	}
+
+	// The files go back first, the messages are kept if that fails so the
+	// restore can be tried again
+	paths, err := s.RevertSince(ctx, sessionID, nextID)
+	if err != nil {
+		return paths, err
+	}
+	return paths, s.messages.Truncate(ctx, sessionID, nextID)
+}
+
+// nextPrompt returns the ID of the first prompt after the given one, which
+// starts the turns to undo to go back to the end of its turn
+func nextPrompt(messages []message.Message, promptID string) (string, bool) {
+	found := false
+	for _, msg := range messages {
+		switch {
+		case msg.ID == promptID:
+			found = true
+		case found && msg.Role == message.User:
+			return msg.ID, true
+		}
+	}
+	return "", false
+}
@@ -0,0 +1,28 @@
+package snapshot
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+func TestNextPrompt(t *testing.T) {
+	messages := []message.Message{
+		{ID: "1", Role: message.User},
+		{ID: "2", Role: message.Assistant},
+		{ID: "3", Role: message.Tool},
+		{ID: "4", Role: message.Assistant},
+		{ID: "5", Role: message.User},
+		{ID: "6", Role: message.Assistant},
+	}
+
+	id, ok := nextPrompt(messages, "1")
+	assert.True(t, ok)
+	assert.Equal(t, "5", id)
+
+	_, ok = nextPrompt(messages, "5")
+	assert.False(t, ok, "nothing follows the last turn")
+	_, ok = nextPrompt(messages, "7")
+	assert.False(t, ok)
+}
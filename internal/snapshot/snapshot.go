@@ -0,0 +1,335 @@
+package snapshot
+
+import (
+	"bytes"
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/google/uuid"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/diff"
+	"github.com/zhenbah/cryoncode/internal/history"
+	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+// Snapshot records the content a file had before a message of a session
+// changed it.
+type Snapshot struct {
+	ID        string
+	SessionID string
+	MessageID string
+	Path      string
+	// Hash of the content in the store, empty when the file didn't exist
+	Hash      string
+	CreatedAt int64
+}
+
+// ChangedFile is a file the agent created or modified during a session,
+// compared with its content before the first change.
+type ChangedFile struct {
+	Path      string
+	Created   bool
+	Deleted   bool
+	Additions int
+	Removals  int
+}
+
+type Service interface {
+	// Take saves the current content of the file before the message changes
+	// it. Only the first snapshot of a file is kept for each message.
+	Take(ctx context.Context, sessionID, messageID, path string) error
+	ListBySession(ctx context.Context, sessionID string) ([]Snapshot, error)
+	// RevertSince restores every file changed by the message or any later
+	// message of the session to its content before the message, and returns
+	// the restored paths.
+	RevertSince(ctx context.Context, sessionID, messageID string) ([]string, error)
+	// ChangedFiles lists the files of the session that differ from their
+	// content before the agent first changed them.
+	ChangedFiles(ctx context.Context, sessionID string) ([]ChangedFile, error)
+	// RevertFile restores a single file to its content before the agent first
+	// changed it in the session.
+	RevertFile(ctx context.Context, sessionID, path string) error
+	// Checkpoint records the end of the turn started by the prompt, with the
+	// snapshots taken since the previous checkpoint.
+	Checkpoint(ctx context.Context, sessionID, promptID string) error
+	// ListCheckpoints returns the checkpoints of the session, oldest first.
+	// Checkpoints of prompts removed from the session are left out.
+	ListCheckpoints(ctx context.Context, sessionID string) ([]Checkpoint, error)
+	// RestoreCheckpoint rewinds the session to the checkpoint: the files
+	// changed after it are restored and the messages after its turn are
+	// removed. It returns the restored paths.
+	RestoreCheckpoint(ctx context.Context, sessionID, checkpointID string) ([]string, error)
+}
+
+type service struct {
+	q        db.Querier
+	messages message.Service
+	files    history.Service
+	store    *store
+}
+
+// NewService creates a snapshot service that keeps the file contents in dir.
+func NewService(q db.Querier, messages message.Service, files history.Service, dir string) Service {
+	return &service{
+		q:        q,
+		messages: messages,
+		files:    files,
+		store:    newStore(dir),
+	}
+}
+
+func (s *service) Take(ctx context.Context, sessionID, messageID, path string) error {
+	var hash sql.NullString
+	content, err := os.ReadFile(path)
+	switch {
+	case err == nil:
+		h, err := s.store.put(content)
+		if err != nil {
+			return err
+		}
+		hash = sql.NullString{String: h, Valid: true}
+	case !os.IsNotExist(err):
+		return fmt.Errorf("failed to read file for snapshot: %w", err)
+	}
+
+	return s.q.CreateSnapshot(ctx, db.CreateSnapshotParams{
+		ID:        uuid.New().String(),
+		SessionID: sessionID,
+		MessageID: messageID,
+		Path:      path,
+		Hash:      hash,
+	})
+}
+
+func (s *service) ListBySession(ctx context.Context, sessionID string) ([]Snapshot, error) {
+	dbSnapshots, err := s.q.ListSnapshotsBySession(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	snapshots := make([]Snapshot, len(dbSnapshots))
+	for i, dbSnapshot := range dbSnapshots {
+		snapshots[i] = s.fromDBItem(dbSnapshot)
+	}
+	return snapshots, nil
+}
+
+func (s *service) RevertSince(ctx context.Context, sessionID, messageID string) ([]string, error) {
+	messages, err := s.messages.List(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	start := -1
+	for i, msg := range messages {
+		if msg.ID == messageID {
+			start = i
+			break
+		}
+	}
+	if start == -1 {
+		return nil, fmt.Errorf("message %s not found in session", messageID)
+	}
+	messageIDs := make([]string, 0, len(messages)-start)
+	for _, msg := range messages[start:] {
+		messageIDs = append(messageIDs, msg.ID)
+	}
+
+	snapshots, err := s.ListBySession(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+	restore, undone := selectSnapshots(snapshots, messageIDs)
+
+	var errs []error
+	restored := make(map[string]bool, len(restore))
+	for _, snapshot := range restore {
+		if err := s.restore(ctx, snapshot); err != nil {
+			errs = append(errs, err)
+			continue
+		}
+		restored[snapshot.Path] = true
+	}
+
+	// Drop the snapshots that were undone so a later revert doesn't restore
+	// them again, but keep the ones of files that failed to restore
+	for _, snapshot := range undone {
+		if !restored[snapshot.Path] {
+			continue
+		}
+		if err := s.q.DeleteSnapshot(ctx, snapshot.ID); err != nil {
+			logging.Warn("failed to delete snapshot", "id", snapshot.ID, "error", err)
+		}
+	}
+
+	paths := make([]string, 0, len(restored))
+	for path := range restored {
+		paths = append(paths, path)
+	}
+	sort.Strings(paths)
+	return paths, errors.Join(errs...)
+}
+
+func (s *service) ChangedFiles(ctx context.Context, sessionID string) ([]ChangedFile, error) {
+	snapshots, err := s.ListBySession(ctx, sessionID)
+	if err != nil {
+		return nil, err
+	}
+
+	var changed []ChangedFile
+	for _, snapshot := range firstSnapshots(snapshots) {
+		var original []byte
+		if snapshot.Hash != "" {
+			original, err = s.store.get(snapshot.Hash)
+			if err != nil {
+				logging.Warn("failed to read snapshot", "path", snapshot.Path, "error", err)
+				continue
+			}
+		}
+		current, err := os.ReadFile(snapshot.Path)
+		exists := err == nil
+		if err != nil && !os.IsNotExist(err) {
+			logging.Warn("failed to read changed file", "path", snapshot.Path, "error", err)
+			continue
+		}
+
+		// Skip files that are back to their original state
+		if exists == (snapshot.Hash != "") && bytes.Equal(original, current) {
+			continue
+		}
+		_, additions, removals := diff.GenerateDiff(string(original), string(current), snapshot.Path)
+		changed = append(changed, ChangedFile{
+			Path:      snapshot.Path,
+			Created:   snapshot.Hash == "",
+			Deleted:   !exists,
+			Additions: additions,
+			Removals:  removals,
+		})
+	}
+	return changed, nil
+}
+
+func (s *service) RevertFile(ctx context.Context, sessionID, path string) error {
+	snapshots, err := s.ListBySession(ctx, sessionID)
+	if err != nil {
+		return err
+	}
+
+	var fileSnapshots []Snapshot
+	for _, snapshot := range snapshots {
+		if snapshot.Path == path {
+			fileSnapshots = append(fileSnapshots, snapshot)
+		}
+	}
+	if len(fileSnapshots) == 0 {
+		return fmt.Errorf("no changes recorded for %s", path)
+	}
+
+	if err := s.restore(ctx, fileSnapshots[0]); err != nil {
+		return err
+	}
+	for _, snapshot := range fileSnapshots {
+		if err := s.q.DeleteSnapshot(ctx, snapshot.ID); err != nil {
+			logging.Warn("failed to delete snapshot", "id", snapshot.ID, "error", err)
+		}
+	}
+	return nil
+}
+
+// restore writes the content of the snapshot back to its file, or removes the
+// file if it didn't exist when the snapshot was taken.
+func (s *service) restore(ctx context.Context, snapshot Snapshot) error {
+	var content []byte
+	if snapshot.Hash == "" {
+		if err := os.Remove(snapshot.Path); err != nil && !os.IsNotExist(err) {
+			return fmt.Errorf("failed to remove %s: %w", snapshot.Path, err)
+		}
+	} else {
+		var err error
+		content, err = s.store.get(snapshot.Hash)
+		if err != nil {
+			return fmt.Errorf("failed to restore %s: %w", snapshot.Path, err)
+		}
+		mode := os.FileMode(0o644)
+		if info, err := os.Stat(snapshot.Path); err == nil {
+			mode = info.Mode().Perm()
+		}
+		if err := os.MkdirAll(filepath.Dir(snapshot.Path), 0o755); err != nil {
+			return fmt.Errorf("failed to restore %s: %w", snapshot.Path, err)
+		}
+		if err := os.WriteFile(snapshot.Path, content, mode); err != nil {
+			return fmt.Errorf("failed to restore %s: %w", snapshot.Path, err)
+		}
+	}
+
+	// Keep the file history of the session in sync with the restored content
+	if _, err := s.files.GetByPathAndSession(ctx, snapshot.Path, snapshot.SessionID); err == nil {
+		if _, err := s.files.CreateVersion(ctx, snapshot.SessionID, snapshot.Path, string(content)); err != nil {
+			logging.Warn("failed to record reverted file version", "path", snapshot.Path, "error", err)
+		}
+	}
+	return nil
+}
+
+// selectSnapshots picks the snapshots taken by the given messages, which are in
+// conversation order. It returns the earliest snapshot of each file, which
+// holds the content to restore, and all the snapshots that get undone.
+func selectSnapshots(snapshots []Snapshot, messageIDs []string) (restore []Snapshot, undone []Snapshot) {
+	order := make(map[string]int, len(messageIDs))
+	for i, id := range messageIDs {
+		order[id] = i
+	}
+
+	earliest := make(map[string]Snapshot)
+	for _, snapshot := range snapshots {
+		pos, ok := order[snapshot.MessageID]
+		if !ok {
+			continue
+		}
+		undone = append(undone, snapshot)
+		if current, ok := earliest[snapshot.Path]; !ok || pos < order[current.MessageID] {
+			earliest[snapshot.Path] = snapshot
+		}
+	}
+
+	for _, snapshot := range earliest {
+		restore = append(restore, snapshot)
+	}
+	sort.Slice(restore, func(i, j int) bool {
+		return restore[i].Path < restore[j].Path
+	})
+	return restore, undone
+}
+
+// firstSnapshots returns the first snapshot of each file, sorted by path. The
+// snapshots must be in the order they were taken.
+func firstSnapshots(snapshots []Snapshot) []Snapshot {
+	seen := make(map[string]bool)
+	var first []Snapshot
+	for _, snapshot := range snapshots {
+		if seen[snapshot.Path] {
+			continue
+		}
+		seen[snapshot.Path] = true
+		first = append(first, snapshot)
+	}
+	sort.Slice(first, func(i, j int) bool {
+		return first[i].Path < first[j].Path
+	})
+	return first
+}
+
+func (s *service) fromDBItem(item db.Snapshot) Snapshot {
+	return Snapshot{
+		ID:        item.ID,
+		SessionID: item.SessionID,
+		MessageID: item.MessageID,
+		Path:      item.Path,
+		Hash:      item.Hash.String,
+		CreatedAt: item.CreatedAt,
+	}
+}
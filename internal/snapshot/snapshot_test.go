@@ -0,0 +1,124 @@
+package snapshot
+
+import (
+	"context"
+	"database/sql"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/db"
+	"github.com/zhenbah/cryoncode/internal/history"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+func TestStore(t *testing.T) {
+	s := newStore(t.TempDir())
+
+	hash, err := s.put([]byte("hello"))
+	require.NoError(t, err)
+
+	again, err := s.put([]byte("hello"))
+	require.NoError(t, err)
+	assert.Equal(t, hash, again)
+
+	content, err := s.get(hash)
+	require.NoError(t, err)
+	assert.Equal(t, "hello", string(content))
+
+	_, err = s.get("not-a-hash")
+	assert.Error(t, err)
+}
+
+func TestSelectSnapshots(t *testing.T) {
+	snapshots := []Snapshot{
+		{ID: "1", MessageID: "m1", Path: "/a.go", Hash: "a0"},
+		{ID: "2", MessageID: "m2", Path: "/a.go", Hash: "a1"},
+		{ID: "3", MessageID: "m3", Path: "/b.go", Hash: ""},
+		{ID: "4", MessageID: "m3", Path: "/a.go", Hash: "a2"},
+	}
+
+	restore, undone := selectSnapshots(snapshots, []string{"m2", "m3"})
+
+	require.Len(t, restore, 2)
+	assert.Equal(t, "2", restore[0].ID, "should restore the content from before the first undone message")
+	assert.Equal(t, "3", restore[1].ID)
+	assert.Len(t, undone, 3)
+
+	restore, undone = selectSnapshots(snapshots, []string{"m4"})
+	assert.Empty(t, restore)
+	assert.Empty(t, undone)
+}
+
+type snapshotRows struct {
+	db.Querier
+	rows []db.Snapshot
+}
+
+func (q *snapshotRows) CreateSnapshot(_ context.Context, arg db.CreateSnapshotParams) error {
+	q.rows = append(q.rows, db.Snapshot{
+		ID:        arg.ID,
+		SessionID: arg.SessionID,
+		MessageID: arg.MessageID,
+		Path:      arg.Path,
+		Hash:      arg.Hash,
+	})
+	return nil
+}
+
+func (q *snapshotRows) ListSnapshotsBySession(context.Context, string) ([]db.Snapshot, error) {
+	return q.rows, nil
+}
+
+func (q *snapshotRows) DeleteSnapshot(_ context.Context, id string) error {
+	q.rows = slices.DeleteFunc(q.rows, func(row db.Snapshot) bool { return row.ID == id })
+	return nil
+}
+
+type sessionMessages struct {
+	message.Service
+	messages []message.Message
+}
+
+func (m sessionMessages) List(context.Context, string) ([]message.Message, error) {
+	return m.messages, nil
+}
+
+type noFileHistory struct {
+	history.Service
+}
+
+func (noFileHistory) GetByPathAndSession(context.Context, string, string) (history.File, error) {
+	return history.File{}, sql.ErrNoRows
+}
+
+func TestRevertSinceDeletedFile(t *testing.T) {
+	ctx := context.Background()
+	dir := t.TempDir()
+	deleted := filepath.Join(dir, "pkg", "old.go")
+	created := filepath.Join(dir, "new.go")
+	require.NoError(t, os.MkdirAll(filepath.Dir(deleted), 0o755))
+	require.NoError(t, os.WriteFile(deleted, []byte("package pkg"), 0o644))
+
+	q := &snapshotRows{}
+	s := NewService(q, sessionMessages{messages: []message.Message{{ID: "m1"}, {ID: "m2"}}}, noFileHistory{}, t.TempDir())
+
+	// m2 deletes the directory and creates a file, like rm and write do
+	require.NoError(t, s.Take(ctx, "session", "m2", deleted))
+	require.NoError(t, os.RemoveAll(filepath.Dir(deleted)))
+	require.NoError(t, s.Take(ctx, "session", "m2", created))
+	require.NoError(t, os.WriteFile(created, []byte("package main"), 0o644))
+
+	paths, err := s.RevertSince(ctx, "session", "m2")
+	require.NoError(t, err)
+	assert.Equal(t, []string{created, deleted}, paths)
+
+	content, err := os.ReadFile(deleted)
+	require.NoError(t, err)
+	assert.Equal(t, "package pkg", string(content))
+	assert.NoFileExists(t, created)
+	assert.Empty(t, q.rows)
+}
@@ -0,0 +1,71 @@
+package snapshot
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// store is a content addressed store for file contents. Each object is saved
+// under the SHA-256 hash of its content, so identical contents are only
+// stored once no matter how many snapshots refer to them.
+type store struct {
+	dir string
+}
+
+func newStore(dir string) *store {
+	return &store{dir: dir}
+}
+
+func (s *store) objectPath(hash string) string {
+	return filepath.Join(s.dir, hash[:2], hash)
+}
+
+// put saves the content and returns its hash.
+func (s *store) put(content []byte) (string, error) {
+	sum := sha256.Sum256(content)
+	hash := hex.EncodeToString(sum[:])
+
+	path := s.objectPath(hash)
+	if _, err := os.Stat(path); err == nil {
+		return hash, nil
+	}
+
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return "", fmt.Errorf("failed to create snapshot directory: %w", err)
+	}
+	// Write to a temporary file first so a partially written object is
+	// never mistaken for a complete one
+	tmp, err := os.CreateTemp(filepath.Dir(path), hash+".tmp")
+	if err != nil {
+		return "", fmt.Errorf("failed to create snapshot object: %w", err)
+	}
+	if _, err := tmp.Write(content); err != nil {
+		tmp.Close()
+		os.Remove(tmp.Name())
+		return "", fmt.Errorf("failed to write snapshot object: %w", err)
+	}
+	if err := tmp.Close(); err != nil {
+		os.Remove(tmp.Name())
+		return "", fmt.Errorf("failed to write snapshot object: %w", err)
+	}
+	if err := os.Rename(tmp.Name(), path); err != nil {
+		os.Remove(tmp.Name())
+		return "", fmt.Errorf("failed to save snapshot object: %w", err)
+	}
+	return hash, nil
+}
+
+// get returns the content saved under the hash.
+func (s *store) get(hash string) ([]byte, error) {
+	if len(hash) != sha256.Size*2 {
+		return nil, fmt.Errorf("invalid snapshot hash: %q", hash)
+	}
+	content, err := os.ReadFile(s.objectPath(hash))
+	if err != nil {
+		return nil, fmt.Errorf("failed to read snapshot object: %w", err)
+	}
+	return content, nil
+}
@@ -17,14 +17,44 @@ import (
 type SendMsg struct {
 	Text        string
 	Attachments []message.Attachment
+	// Replaces is the prompt that is removed with the messages after it
+	// before the message is sent
+	Replaces string
+}
+
+// EditPromptMsg loads an earlier prompt into the editor, sending it replaces
+// the prompt and the messages after it
+type EditPromptMsg struct {
+	Prompt message.Message
 }
 
 type SessionSelectedMsg = session.Session
 
 type SessionClearedMsg struct{}
 
+// ToggleSidebarMsg shows or hides the session sidebar
+type ToggleSidebarMsg struct{}
+
+// ShowSessionsMsg opens the dialog to switch sessions
+type ShowSessionsMsg struct{}
+
+// ScrollToMessageMsg scrolls the messages to the one with the ID
+type ScrollToMessageMsg struct {
+	ID string
+}
+
 type EditorFocusMsg bool
 
+// EditorInsertModeMsg is sent when the editor switches between the insert
+// and normal modes of the vim keybindings.
+type EditorInsertModeMsg bool
+
+// EditorResizeMsg is sent when the editor needs a different number of lines
+// for its content.
+type EditorResizeMsg struct {
+	Height int
+}
+
 func header(width int) string {
 	return lipgloss.JoinVertical(
 		lipgloss.Top,
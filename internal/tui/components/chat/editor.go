@@ -2,8 +2,10 @@ package chat
 
 import (
 	"fmt"
+	"net/http"
 	"os"
 	"os/exec"
+	"path/filepath"
 	"slices"
 	"strings"
 	"unicode"
@@ -13,6 +15,7 @@ import (
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
 	"github.com/zhenbah/cryoncode/internal/app"
+	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/logging"
 	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/session"
@@ -31,11 +34,26 @@ type editorCmp struct {
 	textarea    textarea.Model
 	attachments []message.Attachment
 	deleteMode  bool
+	// contentHeight is the number of lines last requested for the content
+	contentHeight int
+	// normalMode is set while keys are vim commands instead of text
+	normalMode bool
+	// pendingKey is the first key of a two key vim command, e.g. dd
+	pendingKey string
+	history    *promptHistory
+	edits      editHistory
+	// editing is the earlier prompt the text replaces when it's sent
+	editing string
 }
 
 type EditorKeyMaps struct {
-	Send       key.Binding
-	OpenEditor key.Binding
+	Send            key.Binding
+	Newline         key.Binding
+	OpenEditor      key.Binding
+	HistoryPrevious key.Binding
+	HistoryNext     key.Binding
+	Undo            key.Binding
+	Redo            key.Binding
 }
 
 type bluredEditorKeyMaps struct {
@@ -54,10 +72,40 @@ var editorMaps = EditorKeyMaps{
 		key.WithKeys("enter", "ctrl+s"),
 		key.WithHelp("enter", "send message"),
 	),
+	Newline: key.NewBinding(
+		key.WithKeys("alt+enter", "ctrl+j"),
+		key.WithHelp("alt+enter", "new line"),
+	),
 	OpenEditor: key.NewBinding(
 		key.WithKeys("ctrl+e"),
 		key.WithHelp("ctrl+e", "open editor"),
 	),
+	HistoryPrevious: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous prompt"),
+	),
+	HistoryNext: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next prompt"),
+	),
+	Undo: key.NewBinding(
+		key.WithKeys("ctrl+z"),
+		key.WithHelp("ctrl+z", "undo"),
+	),
+	Redo: key.NewBinding(
+		key.WithKeys("ctrl+y"),
+		key.WithHelp("ctrl+y", "redo"),
+	),
+}
+
+func init() {
+	layout.RegisterAction("send", &editorMaps.Send)
+	layout.RegisterAction("newline", &editorMaps.Newline)
+	layout.RegisterAction("open_editor", &editorMaps.OpenEditor)
+	layout.RegisterAction("history_previous", &editorMaps.HistoryPrevious)
+	layout.RegisterAction("history_next", &editorMaps.HistoryNext)
+	layout.RegisterAction("undo", &editorMaps.Undo)
+	layout.RegisterAction("redo", &editorMaps.Redo)
 }
 
 var DeleteKeyMaps = DeleteAttachmentKeyMaps{
@@ -77,42 +125,93 @@ var DeleteKeyMaps = DeleteAttachmentKeyMaps{
 
 const (
 	maxAttachments = 5
+	// maxMentionedFileSize is the largest file attached when mentioned with @
+	maxMentionedFileSize = 100 * 1024
 )
 
+// externalEditorMsg carries the message written in the external editor
+type externalEditorMsg struct {
+	text string
+}
+
+// openEditor suspends the TUI to edit the message in $VISUAL or $EDITOR. The
+// text comes back into the editor when it exits, to be reviewed and sent.
 func (m *editorCmp) openEditor() tea.Cmd {
-	editor := os.Getenv("EDITOR")
+	editor := os.Getenv("VISUAL")
+	if editor == "" {
+		editor = os.Getenv("EDITOR")
+	}
 	if editor == "" {
 		editor = "nvim"
 	}
+	// Allow editors that need arguments, like "code --wait"
+	args := strings.Fields(editor)
 
 	tmpfile, err := os.CreateTemp("", "msg_*.md")
 	if err != nil {
 		return util.ReportError(err)
 	}
+	_, err = tmpfile.WriteString(m.textarea.Value())
 	tmpfile.Close()
-	c := exec.Command(editor, tmpfile.Name()) //nolint:gosec
+	if err != nil {
+		os.Remove(tmpfile.Name())
+		return util.ReportError(err)
+	}
+	c := exec.Command(args[0], append(args[1:], tmpfile.Name())...) //nolint:gosec
 	c.Stdin = os.Stdin
 	c.Stdout = os.Stdout
 	c.Stderr = os.Stderr
 	return tea.ExecProcess(c, func(err error) tea.Msg {
+		defer os.Remove(tmpfile.Name())
 		if err != nil {
-			return util.ReportError(err)
+			return util.InfoMsg{Type: util.InfoTypeError, Msg: fmt.Sprintf("%s exited with an error, the message was not changed: %v", args[0], err)}
 		}
 		content, err := os.ReadFile(tmpfile.Name())
 		if err != nil {
-			return util.ReportError(err)
+			return util.InfoMsg{Type: util.InfoTypeError, Msg: err.Error()}
 		}
-		if len(content) == 0 {
-			return util.ReportWarn("Message is empty")
-		}
-		os.Remove(tmpfile.Name())
-		attachments := m.attachments
-		m.attachments = nil
-		return SendMsg{
-			Text:        string(content),
-			Attachments: attachments,
+		return externalEditorMsg{text: strings.TrimRight(string(content), "\n")}
+	})
+}
+
+// attachMentionedFile attaches a text file picked with @, so that its content
+// is sent with the message. Folders, larger files and other kinds of files are
+// only mentioned by path, the agent can read them with its tools.
+func (m *editorCmp) attachMentionedFile(path string) tea.Cmd {
+	if !filepath.IsAbs(path) {
+		path = filepath.Join(config.WorkingDirectory(), path)
+	}
+	info, err := os.Stat(path)
+	if err != nil || info.IsDir() {
+		return nil
+	}
+	if info.Size() > maxMentionedFileSize {
+		return util.ReportWarn(fmt.Sprintf("%s is larger than %dKB, only its path is sent", filepath.Base(path), maxMentionedFileSize/1024))
+	}
+	for _, attachment := range m.attachments {
+		if attachment.FilePath == path {
+			return nil
 		}
+	}
+	if len(m.attachments) >= maxAttachments {
+		return util.ReportWarn(fmt.Sprintf("cannot add more than %d attachments, only the path is sent", maxAttachments))
+	}
+
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return util.ReportError(err)
+	}
+	mimeType := http.DetectContentType(content[:min(512, len(content))])
+	if !strings.HasPrefix(mimeType, "text/") {
+		return nil
+	}
+	m.attachments = append(m.attachments, message.Attachment{
+		FilePath: path,
+		FileName: filepath.Base(path),
+		MimeType: mimeType,
+		Content:  content,
 	})
+	return nil
 }
 
 func (m *editorCmp) Init() tea.Cmd {
@@ -120,15 +219,26 @@ func (m *editorCmp) Init() tea.Cmd {
 }
 
 func (m *editorCmp) send() tea.Cmd {
+	text := m.textarea.Value()
+	command, value, isCommand := ParseSlashCommand(text)
+	if isCommand {
+		// Commands don't use the attachments, they stay for the next message
+		m.history.Add(text)
+		m.textarea.Reset()
+		return util.CmdHandler(command)
+	}
+
 	if m.app.CoderAgent.IsSessionBusy(m.session.ID) {
 		return util.ReportWarn("Agent is working, please wait...")
 	}
 
-	value := m.textarea.Value()
+	m.history.Add(text)
 	m.textarea.Reset()
 	attachments := m.attachments
+	replaces := m.editing
 
 	m.attachments = nil
+	m.editing = ""
 	if value == "" {
 		return nil
 	}
@@ -136,11 +246,100 @@ func (m *editorCmp) send() tea.Cmd {
 		util.CmdHandler(SendMsg{
 			Text:        value,
 			Attachments: attachments,
+			Replaces:    replaces,
 		}),
 	)
 }
 
+// recallPrompt returns the prompt from history for Up in an empty editor, and
+// for Up and Down while a recalled prompt is shown and left unchanged. Within
+// a prompt of several lines they only move to the next prompt from its first
+// and last line.
+func (m *editorCmp) recallPrompt(msg tea.KeyMsg) (string, bool) {
+	value := m.textarea.Value()
+	browsing := m.history.Browsing(value)
+	switch {
+	case key.Matches(msg, editorMaps.HistoryPrevious):
+		if value == "" && !browsing {
+			m.history.Reset()
+			return m.history.Previous()
+		}
+		if browsing && m.textarea.Line() == 0 {
+			return m.history.Previous()
+		}
+	case key.Matches(msg, editorMaps.HistoryNext):
+		if browsing && m.textarea.Line() == m.textarea.LineCount()-1 {
+			return m.history.Next()
+		}
+	}
+	return "", false
+}
+
 func (m *editorCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	keyMsg, isKey := msg.(tea.KeyMsg)
+	if isKey && m.textarea.Focused() && !keyMsg.Paste {
+		switch {
+		case key.Matches(keyMsg, editorMaps.Undo):
+			if snapshot, ok := m.edits.Undo(m.snapshot()); ok {
+				m.restore(snapshot)
+			}
+			return m, m.resize()
+		case key.Matches(keyMsg, editorMaps.Redo):
+			if snapshot, ok := m.edits.Redo(m.snapshot()); ok {
+				m.restore(snapshot)
+			}
+			return m, m.resize()
+		}
+	}
+
+	before := m.snapshot()
+	_, cmd := m.update(msg)
+	// Typing a word is undone at once, anything else one change at a time
+	typing := isKey && !keyMsg.Paste && keyMsg.Type == tea.KeyRunes &&
+		len(keyMsg.Runes) == 1 && !unicode.IsSpace(keyMsg.Runes[0])
+	m.edits.Record(before, m.snapshot(), typing)
+	// Clearing the edited prompt cancels the edit
+	if m.editing != "" && m.textarea.Value() == "" {
+		m.editing = ""
+	}
+	return m, tea.Batch(cmd, m.resize())
+}
+
+// snapshot returns the content of the input and the cursor position
+func (m *editorCmp) snapshot() editSnapshot {
+	info := m.textarea.LineInfo()
+	return editSnapshot{
+		value: m.textarea.Value(),
+		row:   m.textarea.Line(),
+		col:   info.StartColumn + info.ColumnOffset,
+	}
+}
+
+// restore puts back the content and cursor position of a snapshot
+func (m *editorCmp) restore(snapshot editSnapshot) {
+	m.textarea.SetValue(snapshot.value)
+	// SetValue leaves the cursor at the end of the input
+	for m.textarea.Line() > snapshot.row {
+		m.textarea.CursorUp()
+	}
+	m.textarea.SetCursor(snapshot.col)
+}
+
+// resize asks for more room when the content has more lines than fit, up to
+// the configured maximum, and gives the room back when lines are removed.
+func (m *editorCmp) resize() tea.Cmd {
+	height := min(m.textarea.LineCount(), max(config.Get().TUI.EditorMaxHeight, 1))
+	if len(m.attachments) > 0 {
+		height++
+	}
+	if height == m.contentHeight {
+		return nil
+	}
+	m.contentHeight = height
+	return util.CmdHandler(EditorResizeMsg{Height: height})
+}
+
+func (m *editorCmp) update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	var cmd tea.Cmd
 	switch msg := msg.(type) {
 	case dialog.ThemeChangedMsg:
@@ -150,19 +349,48 @@ func (m *editorCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		modifiedValue := strings.Replace(existingValue, msg.SearchString, msg.CompletionValue, 1)
 
 		m.textarea.SetValue(modifiedValue)
+		return m, m.attachMentionedFile(msg.CompletionValue)
+	case externalEditorMsg:
+		m.textarea.SetValue(msg.text)
+		m.textarea.CursorEnd()
+		return m, nil
+	case EditPromptMsg:
+		m.textarea.SetValue(msg.Prompt.Content().String())
+		m.textarea.CursorEnd()
+		m.attachments = msg.Prompt.Attachments()
+		m.editing = msg.Prompt.ID
 		return m, nil
 	case SessionSelectedMsg:
 		if msg.ID != m.session.ID {
 			m.session = msg
+			m.editing = ""
+		}
+		return m, nil
+	case EditorFocusMsg:
+		m.deleteMode = false
+		if msg {
+			cmd = m.textarea.Focus()
+			if config.Get().TUI.VimMode {
+				cmd = tea.Batch(cmd, m.setInsertMode(true))
+			}
+			return m, cmd
 		}
+		m.textarea.Blur()
 		return m, nil
 	case dialog.AttachmentAddedMsg:
 		if len(m.attachments) >= maxAttachments {
-			logging.ErrorPersist(fmt.Sprintf("cannot add more than %d images", maxAttachments))
+			logging.ErrorPersist(fmt.Sprintf("cannot add more than %d attachments", maxAttachments))
 			return m, cmd
 		}
 		m.attachments = append(m.attachments, msg.Attachment)
 	case tea.KeyMsg:
+		// Pasted text is inserted as is, none of its characters or line
+		// breaks are taken for key bindings, in any mode
+		if msg.Paste {
+			m.deleteMode = false
+			m.textarea, cmd = m.textarea.Update(msg)
+			return m, cmd
+		}
 		if key.Matches(msg, DeleteKeyMaps.AttachmentDeleteMode) {
 			m.deleteMode = true
 			return m, nil
@@ -184,20 +412,37 @@ func (m *editorCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				return m, nil
 			}
 		}
+		if m.normalMode && m.textarea.Focused() {
+			if cmd, handled := m.updateNormalMode(msg); handled {
+				return m, cmd
+			}
+		}
 		if key.Matches(msg, messageKeys.PageUp) || key.Matches(msg, messageKeys.PageDown) ||
 			key.Matches(msg, messageKeys.HalfPageUp) || key.Matches(msg, messageKeys.HalfPageDown) {
 			return m, nil
 		}
 		if key.Matches(msg, editorMaps.OpenEditor) {
-			if m.app.CoderAgent.IsSessionBusy(m.session.ID) {
-				return m, util.ReportWarn("Agent is working, please wait...")
-			}
 			return m, m.openEditor()
 		}
 		if key.Matches(msg, DeleteKeyMaps.Escape) {
+			if !m.deleteMode && !m.normalMode && m.textarea.Focused() && config.Get().TUI.VimMode {
+				return m, m.setInsertMode(false)
+			}
 			m.deleteMode = false
 			return m, nil
 		}
+		if m.textarea.Focused() && !m.deleteMode {
+			if prompt, ok := m.recallPrompt(msg); ok {
+				m.textarea.SetValue(prompt)
+				m.textarea.CursorEnd()
+				return m, nil
+			}
+		}
+		if m.textarea.Focused() && key.Matches(msg, editorMaps.Newline) {
+			// The textarea inserts a line break at the cursor on a plain Enter
+			m.textarea, cmd = m.textarea.Update(tea.KeyMsg{Type: tea.KeyEnter})
+			return m, cmd
+		}
 		// Hanlde Enter key
 		if m.textarea.Focused() && key.Matches(msg, editorMaps.Send) {
 			value := m.textarea.Value()
@@ -224,6 +469,12 @@ func (m *editorCmp) View() string {
 		Padding(0, 0, 0, 1).
 		Bold(true).
 		Foreground(t.Primary())
+	if m.normalMode {
+		style = style.Foreground(t.Accent())
+	}
+	if m.editing != "" {
+		style = style.Foreground(t.Warning())
+	}
 
 	if len(m.attachments) == 0 {
 		return lipgloss.JoinHorizontal(lipgloss.Top, style.Render(">"), m.textarea.View())
@@ -257,12 +508,7 @@ func (m *editorCmp) attachmentsContent() string {
 		Background(t.TextMuted()).
 		Foreground(t.Text())
 	for i, attachment := range m.attachments {
-		var filename string
-		if len(attachment.FileName) > 10 {
-			filename = fmt.Sprintf(" %s %s...", styles.DocumentIcon, attachment.FileName[0:7])
-		} else {
-			filename = fmt.Sprintf(" %s %s", styles.DocumentIcon, attachment.FileName)
-		}
+		filename := fmt.Sprintf(" %s %s", styles.DocumentIcon, util.Truncate(attachment.FileName, 10))
 		if m.deleteMode {
 			filename = fmt.Sprintf("%d%s", i, filename)
 		}
@@ -276,6 +522,10 @@ func (m *editorCmp) BindingKeys() []key.Binding {
 	bindings := []key.Binding{}
 	bindings = append(bindings, layout.KeyMapToSlice(editorMaps)...)
 	bindings = append(bindings, layout.KeyMapToSlice(DeleteKeyMaps)...)
+	bindings = append(bindings, slashCommandBindings()...)
+	if config.Get().TUI.VimMode {
+		bindings = append(bindings, layout.KeyMapToSlice(vimKeyMaps)...)
+	}
 	return bindings
 }
 
@@ -298,6 +548,8 @@ func CreateTextArea(existing *textarea.Model) textarea.Model {
 	ta.Prompt = " "
 	ta.ShowLineNumbers = false
 	ta.CharLimit = -1
+	// Don't cut long pasted snippets, the editor scrolls instead
+	ta.MaxHeight = 0
 
 	if existing != nil {
 		ta.SetValue(existing.Value())
@@ -314,5 +566,6 @@ func NewEditorCmp(app *app.App) tea.Model {
 	return &editorCmp{
 		app:      app,
 		textarea: ta,
+		history:  newPromptHistory(config.Get().Data.Directory),
 	}
 }
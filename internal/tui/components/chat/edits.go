@@ -0,0 +1,61 @@
+package chat
+
+// maxEdits is the number of changes to the input that can be undone
+const maxEdits = 100
+
+// editSnapshot is the content of the input and where its cursor was
+type editSnapshot struct {
+	value string
+	row   int
+	col   int
+}
+
+// editHistory is the undo and redo stacks of the input. Typing a word is one
+// change, pastes, deletions and replacing the whole input are each their own.
+type editHistory struct {
+	undo []editSnapshot
+	redo []editSnapshot
+	// typing is set when the last change was a typed character, the next
+	// one is then part of the same change
+	typing bool
+}
+
+// Record adds the input as it was before a change. Changes that only move
+// the cursor aren't recorded.
+func (h *editHistory) Record(before, after editSnapshot, typing bool) {
+	if before.value == after.value {
+		return
+	}
+	if !typing || !h.typing {
+		h.undo = append(h.undo, before)
+		if len(h.undo) > maxEdits {
+			h.undo = h.undo[len(h.undo)-maxEdits:]
+		}
+	}
+	h.typing = typing
+	h.redo = nil
+}
+
+// Undo returns the input before the last change, current is kept to redo it
+func (h *editHistory) Undo(current editSnapshot) (editSnapshot, bool) {
+	if len(h.undo) == 0 {
+		return editSnapshot{}, false
+	}
+	previous := h.undo[len(h.undo)-1]
+	h.undo = h.undo[:len(h.undo)-1]
+	h.redo = append(h.redo, current)
+	h.typing = false
+	return previous, true
+}
+
+// Redo returns the input after the last undone change
+func (h *editHistory) Redo(current editSnapshot) (editSnapshot, bool) {
+	if len(h.redo) == 0 {
+		return editSnapshot{}, false
+	}
+	next := h.redo[len(h.redo)-1]
+	h.redo = h.redo[:len(h.redo)-1]
+	h.undo = append(h.undo, current)
+	h.typing = false
+	return next, true
+}
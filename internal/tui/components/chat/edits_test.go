@@ -0,0 +1,63 @@
+package chat
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestEditHistoryGroupsTyping(t *testing.T) {
+	h := &editHistory{}
+	h.Record(editSnapshot{value: ""}, editSnapshot{value: "h"}, true)
+	h.Record(editSnapshot{value: "h"}, editSnapshot{value: "hi"}, true)
+	h.Record(editSnapshot{value: "hi"}, editSnapshot{value: "hi "}, false)
+	h.Record(editSnapshot{value: "hi "}, editSnapshot{value: "hi y"}, true)
+
+	previous, ok := h.Undo(editSnapshot{value: "hi y"})
+	assert.True(t, ok)
+	assert.Equal(t, "hi ", previous.value)
+	previous, ok = h.Undo(previous)
+	assert.True(t, ok)
+	assert.Equal(t, "hi", previous.value)
+	previous, ok = h.Undo(previous)
+	assert.True(t, ok)
+	assert.Equal(t, "", previous.value)
+	_, ok = h.Undo(previous)
+	assert.False(t, ok)
+}
+
+func TestEditHistoryRedo(t *testing.T) {
+	h := &editHistory{}
+	h.Record(editSnapshot{value: "a prompt"}, editSnapshot{value: ""}, false)
+
+	previous, ok := h.Undo(editSnapshot{value: ""})
+	assert.True(t, ok)
+	assert.Equal(t, "a prompt", previous.value)
+
+	next, ok := h.Redo(previous)
+	assert.True(t, ok)
+	assert.Equal(t, "", next.value)
+	_, ok = h.Redo(next)
+	assert.False(t, ok)
+
+	// A new change drops what could be redone
+	h.Undo(next)
+	h.Record(editSnapshot{value: "a prompt"}, editSnapshot{value: "a prompt!"}, true)
+	_, ok = h.Redo(editSnapshot{value: "a prompt!"})
+	assert.False(t, ok)
+}
+
+func TestEditHistoryIgnoresCursorMoves(t *testing.T) {
+	h := &editHistory{}
+	h.Record(editSnapshot{value: "text", col: 4}, editSnapshot{value: "text", col: 0}, false)
+	_, ok := h.Undo(editSnapshot{value: "text"})
+	assert.False(t, ok)
+}
+
+func TestEditHistoryLimit(t *testing.T) {
+	h := &editHistory{}
+	for i := range maxEdits + 10 {
+		h.Record(editSnapshot{value: string(rune('a' + i%26))}, editSnapshot{value: "changed"}, false)
+	}
+	assert.Len(t, h.undo, maxEdits)
+}
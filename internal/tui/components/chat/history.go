@@ -0,0 +1,143 @@
+package chat
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+
+	"github.com/zhenbah/cryoncode/internal/logging"
+)
+
+const (
+	historyFilename = "prompt_history"
+	// maxHistory is the number of prompts kept, older ones are dropped
+	maxHistory = 500
+)
+
+// promptHistory keeps the prompts sent in every session, oldest first, so
+// they can be recalled in the editor like a shell history. Each prompt is
+// stored as a JSON string on its own line, prompts can span several lines.
+type promptHistory struct {
+	path    string
+	entries []string
+	// index is the entry shown in the editor, len(entries) when none is
+	index int
+}
+
+func newPromptHistory(dataDir string) *promptHistory {
+	h := &promptHistory{path: filepath.Join(dataDir, historyFilename)}
+	h.load()
+	return h
+}
+
+func (h *promptHistory) load() {
+	file, err := os.Open(h.path)
+	if err != nil {
+		if !os.IsNotExist(err) {
+			logging.Warn("failed to read the prompt history", "error", err)
+		}
+		return
+	}
+	defer file.Close()
+
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 10*1024*1024)
+	for scanner.Scan() {
+		var prompt string
+		if err := json.Unmarshal(scanner.Bytes(), &prompt); err != nil || prompt == "" {
+			continue
+		}
+		h.entries = append(h.entries, prompt)
+	}
+	if len(h.entries) > maxHistory {
+		h.entries = h.entries[len(h.entries)-maxHistory:]
+		h.rewrite()
+	}
+	h.index = len(h.entries)
+}
+
+// Add records a sent prompt, a prompt sent twice in a row is kept once
+func (h *promptHistory) Add(prompt string) {
+	defer h.Reset()
+	if prompt == "" || (len(h.entries) > 0 && h.entries[len(h.entries)-1] == prompt) {
+		return
+	}
+	h.entries = append(h.entries, prompt)
+	if len(h.entries) > maxHistory {
+		h.entries = h.entries[len(h.entries)-maxHistory:]
+		h.rewrite()
+	} else {
+		h.append(prompt)
+	}
+}
+
+// Browsing reports whether the editor shows the value recalled from history
+func (h *promptHistory) Browsing(value string) bool {
+	return h.index < len(h.entries) && h.entries[h.index] == value
+}
+
+// Previous returns the prompt sent before the one shown, false at the oldest
+func (h *promptHistory) Previous() (string, bool) {
+	if h.index == 0 {
+		return "", false
+	}
+	h.index--
+	return h.entries[h.index], true
+}
+
+// Next returns the prompt sent after the one shown, or an empty prompt past
+// the latest one
+func (h *promptHistory) Next() (string, bool) {
+	if h.index >= len(h.entries) {
+		return "", false
+	}
+	h.index++
+	if h.index == len(h.entries) {
+		return "", true
+	}
+	return h.entries[h.index], true
+}
+
+// Reset stops browsing, the next Previous returns the latest prompt
+func (h *promptHistory) Reset() {
+	h.index = len(h.entries)
+}
+
+func (h *promptHistory) append(prompt string) {
+	line, err := json.Marshal(prompt)
+	if err != nil {
+		return
+	}
+	if err := os.MkdirAll(filepath.Dir(h.path), 0o755); err != nil {
+		logging.Warn("failed to save the prompt history", "error", err)
+		return
+	}
+	file, err := os.OpenFile(h.path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0o600)
+	if err != nil {
+		logging.Warn("failed to save the prompt history", "error", err)
+		return
+	}
+	defer file.Close()
+	if _, err := file.Write(append(line, '\n')); err != nil {
+		logging.Warn("failed to save the prompt history", "error", err)
+	}
+}
+
+func (h *promptHistory) rewrite() {
+	var data []byte
+	for _, prompt := range h.entries {
+		line, err := json.Marshal(prompt)
+		if err != nil {
+			continue
+		}
+		data = append(append(data, line...), '\n')
+	}
+	if err := os.MkdirAll(filepath.Dir(h.path), 0o755); err != nil {
+		logging.Warn("failed to save the prompt history", "error", err)
+		return
+	}
+	if err := os.WriteFile(h.path, data, 0o600); err != nil {
+		logging.Warn("failed to save the prompt history", "error", err)
+	}
+}
@@ -0,0 +1,61 @@
+package chat
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestPromptHistory(t *testing.T) {
+	t.Run("recalls prompts from the latest", func(t *testing.T) {
+		h := newPromptHistory(t.TempDir())
+		h.Add("first")
+		h.Add("second\nline")
+
+		prompt, ok := h.Previous()
+		assert.True(t, ok)
+		assert.Equal(t, "second\nline", prompt)
+		assert.True(t, h.Browsing(prompt))
+		prompt, _ = h.Previous()
+		assert.Equal(t, "first", prompt)
+		_, ok = h.Previous()
+		assert.False(t, ok)
+
+		prompt, _ = h.Next()
+		assert.Equal(t, "second\nline", prompt)
+		prompt, ok = h.Next()
+		assert.True(t, ok)
+		assert.Equal(t, "", prompt)
+		_, ok = h.Next()
+		assert.False(t, ok)
+	})
+
+	t.Run("keeps a repeated prompt once", func(t *testing.T) {
+		h := newPromptHistory(t.TempDir())
+		h.Add("same")
+		h.Add("same")
+		h.Add("")
+		assert.Equal(t, []string{"same"}, h.entries)
+	})
+
+	t.Run("is shared through the data directory", func(t *testing.T) {
+		dir := t.TempDir()
+		newPromptHistory(dir).Add("multi\nline")
+		h := newPromptHistory(dir)
+		assert.Equal(t, []string{"multi\nline"}, h.entries)
+		prompt, _ := h.Previous()
+		assert.Equal(t, "multi\nline", prompt)
+	})
+
+	t.Run("drops the oldest prompts", func(t *testing.T) {
+		dir := t.TempDir()
+		h := newPromptHistory(dir)
+		for i := range maxHistory + 1 {
+			h.Add(fmt.Sprintf("prompt %d", i))
+		}
+		assert.Len(t, h.entries, maxHistory)
+		assert.Equal(t, "prompt 1", h.entries[0])
+		assert.Equal(t, h.entries, newPromptHistory(dir).entries)
+	})
+}
@@ -4,22 +4,36 @@ import (
 	"context"
 	"fmt"
 	"math"
+	"slices"
+	"strings"
+	"time"
 
 	"github.com/charmbracelet/bubbles/key"
 	"github.com/charmbracelet/bubbles/spinner"
 	"github.com/charmbracelet/bubbles/viewport"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
+	zone "github.com/lrstanley/bubblezone"
 	"github.com/zhenbah/cryoncode/internal/app"
+	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
 	"github.com/zhenbah/cryoncode/internal/session"
 	"github.com/zhenbah/cryoncode/internal/tui/components/dialog"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/state"
 	"github.com/zhenbah/cryoncode/internal/tui/styles"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
 	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
 
+// messagesZone marks the visible messages to find what the mouse points at
+const messagesZone = "messages"
+
+// messagesPageSize is the number of messages loaded when a session is opened
+// and each time the top of the loaded ones is reached
+const messagesPageSize = 50
+
 type cacheItem struct {
 	width   int
 	content []uiMessage
@@ -36,6 +50,29 @@ type messagesCmp struct {
 	spinner       spinner.Model
 	rendering     bool
 	attachments   viewport.Model
+	// expandToolOutput shows the full tool output instead of a short preview
+	expandToolOutput bool
+	// expandedTools are the tool calls opened from their one line summary
+	expandedTools map[string]bool
+	// selectedTool is the tool call that space expands or collapses
+	selectedTool string
+	// showTimestamps shows the time each message was sent
+	showTimestamps bool
+	// restoreScroll is the number of lines to scroll up from the bottom once
+	// the messages are rendered, to reopen a session where it was left
+	restoreScroll int
+	// hasOlder is set while older messages of the session aren't loaded yet
+	hasOlder bool
+	// scrollTo is the message to scroll to once the messages are rendered
+	scrollTo string
+	// editorFocused is false while keys are used to scroll the messages
+	editorFocused bool
+	// unseenMessages is set when messages arrive while scrolled up
+	unseenMessages bool
+	// editorNormalMode is set while the editor is in vim normal mode
+	editorNormalMode bool
+	// pendingKey is the first key of a two key vim command, e.g. gg
+	pendingKey string
 }
 type renderFinishedMsg struct{}
 
@@ -44,6 +81,19 @@ type MessageKeys struct {
 	PageUp       key.Binding
 	HalfPageUp   key.Binding
 	HalfPageDown key.Binding
+	LineUp       key.Binding
+	LineDown     key.Binding
+	Top          key.Binding
+	Bottom       key.Binding
+	Yank         key.Binding
+
+	ToggleToolOutput key.Binding
+	PreviousTool     key.Binding
+	NextTool         key.Binding
+	ExpandTool       key.Binding
+	ToggleTimestamps key.Binding
+	PreviousPrompt   key.Binding
+	NextPrompt       key.Binding
 }
 
 var messageKeys = MessageKeys{
@@ -63,6 +113,73 @@ var messageKeys = MessageKeys{
 		key.WithKeys("ctrl+d", "ctrl+d"),
 		key.WithHelp("ctrl+d", "½ page down"),
 	),
+	LineUp: key.NewBinding(
+		key.WithKeys("k", "up"),
+		key.WithHelp("k/↑", "scroll up (editor not focused)"),
+	),
+	LineDown: key.NewBinding(
+		key.WithKeys("j", "down"),
+		key.WithHelp("j/↓", "scroll down (editor not focused)"),
+	),
+	Top: key.NewBinding(
+		key.WithKeys("g", "home"),
+		key.WithHelp("g", "go to the first message (editor not focused)"),
+	),
+	Bottom: key.NewBinding(
+		key.WithKeys("G", "end"),
+		key.WithHelp("G", "go to the last message (editor not focused)"),
+	),
+	Yank: key.NewBinding(
+		key.WithKeys("y"),
+		key.WithHelp("y", "copy the message at the top (vim mode, editor not focused)"),
+	),
+	ToggleToolOutput: key.NewBinding(
+		key.WithKeys("ctrl+x"),
+		key.WithHelp("ctrl+x", "expand tool output"),
+	),
+	PreviousTool: key.NewBinding(
+		key.WithKeys("["),
+		key.WithHelp("[", "select the previous tool call (editor not focused)"),
+	),
+	NextTool: key.NewBinding(
+		key.WithKeys("]"),
+		key.WithHelp("]", "select the next tool call (editor not focused)"),
+	),
+	ExpandTool: key.NewBinding(
+		key.WithKeys(" "),
+		key.WithHelp("space", "expand or collapse the selected tool call (editor not focused)"),
+	),
+	ToggleTimestamps: key.NewBinding(
+		key.WithKeys("t"),
+		key.WithHelp("t", "show or hide message timestamps (editor not focused)"),
+	),
+	PreviousPrompt: key.NewBinding(
+		key.WithKeys("{"),
+		key.WithHelp("{", "jump to the previous prompt (editor not focused)"),
+	),
+	NextPrompt: key.NewBinding(
+		key.WithKeys("}"),
+		key.WithHelp("}", "jump to the next prompt (editor not focused)"),
+	),
+}
+
+func init() {
+	layout.RegisterAction("page_up", &messageKeys.PageUp)
+	layout.RegisterAction("page_down", &messageKeys.PageDown)
+	layout.RegisterAction("half_page_up", &messageKeys.HalfPageUp)
+	layout.RegisterAction("half_page_down", &messageKeys.HalfPageDown)
+	layout.RegisterAction("line_up", &messageKeys.LineUp)
+	layout.RegisterAction("line_down", &messageKeys.LineDown)
+	layout.RegisterAction("top", &messageKeys.Top)
+	layout.RegisterAction("bottom", &messageKeys.Bottom)
+	layout.RegisterAction("yank", &messageKeys.Yank)
+	layout.RegisterAction("toggle_tool_output", &messageKeys.ToggleToolOutput)
+	layout.RegisterAction("previous_tool", &messageKeys.PreviousTool)
+	layout.RegisterAction("next_tool", &messageKeys.NextTool)
+	layout.RegisterAction("expand_tool", &messageKeys.ExpandTool)
+	layout.RegisterAction("toggle_timestamps", &messageKeys.ToggleTimestamps)
+	layout.RegisterAction("previous_prompt", &messageKeys.PreviousPrompt)
+	layout.RegisterAction("next_prompt", &messageKeys.NextPrompt)
 }
 
 func (m *messagesCmp) Init() tea.Cmd {
@@ -86,19 +203,87 @@ func (m *messagesCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		m.messages = make([]message.Message, 0)
 		m.currentMsgID = ""
 		m.rendering = false
+		m.hasOlder = false
+		m.saveScroll()
 		return m, nil
-
+	case EditorFocusMsg:
+		m.editorFocused = bool(msg)
+	case EditorInsertModeMsg:
+		m.editorNormalMode = !bool(msg)
+
+	case tea.MouseMsg:
+		if msg.Action == tea.MouseActionRelease && msg.Button == tea.MouseButtonLeft {
+			m.clickTool(msg)
+		}
+		u, cmd := m.viewport.Update(msg)
+		m.viewport = u
+		cmds = append(cmds, cmd)
 	case tea.KeyMsg:
+		vimMode := config.Get().TUI.VimMode
+		pendingKey := m.pendingKey
+		m.pendingKey = ""
 		if key.Matches(msg, messageKeys.PageUp) || key.Matches(msg, messageKeys.PageDown) ||
-			key.Matches(msg, messageKeys.HalfPageUp) || key.Matches(msg, messageKeys.HalfPageDown) {
+			key.Matches(msg, messageKeys.HalfPageUp) || key.Matches(msg, messageKeys.HalfPageDown) ||
+			(!m.editorFocused && key.Matches(msg, messageKeys.LineUp, messageKeys.LineDown)) {
 			u, cmd := m.viewport.Update(msg)
 			m.viewport = u
 			cmds = append(cmds, cmd)
 		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.Top) {
+			// With vim keybindings the first message is gg
+			if vimMode && msg.String() == "g" && pendingKey != "g" {
+				m.pendingKey = "g"
+			} else {
+				m.viewport.GotoTop()
+			}
+		}
+		if !m.editorFocused && vimMode && key.Matches(msg, messageKeys.Yank) {
+			cmds = append(cmds, m.yank())
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.Bottom) {
+			m.viewport.GotoBottom()
+		}
+		if key.Matches(msg, messageKeys.ToggleToolOutput) {
+			m.expandToolOutput = !m.expandToolOutput
+			m.rerender()
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.PreviousTool) {
+			m.selectTool(-1)
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.NextTool) {
+			m.selectTool(1)
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.ExpandTool) && m.selectedTool != "" {
+			m.expandedTools[m.selectedTool] = !m.expandedTools[m.selectedTool]
+			m.renderTool(m.selectedTool)
+			m.scrollToTool(m.selectedTool)
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.ToggleTimestamps) {
+			m.showTimestamps = !m.showTimestamps
+			m.rerender()
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.PreviousPrompt) {
+			m.jumpToPrompt(-1)
+		}
+		if !m.editorFocused && key.Matches(msg, messageKeys.NextPrompt) {
+			m.jumpToPrompt(1)
+		}
 
 	case renderFinishedMsg:
 		m.rendering = false
 		m.viewport.GotoBottom()
+		if m.restoreScroll > 0 {
+			m.viewport.SetYOffset(m.viewport.YOffset - m.restoreScroll)
+			m.restoreScroll = 0
+		}
+		if m.scrollTo != "" {
+			cmds = append(cmds, m.scrollToMessage())
+		}
+	case ScrollToMessageMsg:
+		m.scrollTo = msg.ID
+		if !m.rendering {
+			cmds = append(cmds, m.scrollToMessage())
+		}
 	case pubsub.Event[session.Session]:
 		if msg.Type == pubsub.UpdatedEvent && msg.Payload.ID == m.session.ID {
 			m.session = msg.Payload
@@ -150,17 +335,53 @@ func (m *messagesCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 					break
 				}
 			}
+		} else if msg.Type == pubsub.DeletedEvent && msg.Payload.SessionID == m.session.ID {
+			// Messages removed by editing an earlier prompt
+			for i, v := range m.messages {
+				if v.ID == msg.Payload.ID {
+					m.messages = slices.Delete(m.messages, i, i+1)
+					delete(m.cachedContent, msg.Payload.ID)
+					if len(m.messages) > 0 {
+						m.currentMsgID = m.messages[len(m.messages)-1].ID
+						delete(m.cachedContent, m.currentMsgID)
+					} else {
+						m.currentMsgID = ""
+					}
+					m.renderView()
+					break
+				}
+			}
 		}
 		if needsRerender {
+			// Only follow new output when already at the bottom, so reading
+			// earlier messages isn't interrupted. Sending a message always
+			// jumps to the bottom.
+			atBottom := m.viewport.AtBottom() || msg.Payload.Role == message.User
 			m.renderView()
 			if len(m.messages) > 0 {
 				if (msg.Type == pubsub.CreatedEvent) ||
 					(msg.Type == pubsub.UpdatedEvent && msg.Payload.ID == m.messages[len(m.messages)-1].ID) {
-					m.viewport.GotoBottom()
+					if atBottom {
+						m.viewport.GotoBottom()
+					} else {
+						m.unseenMessages = true
+					}
 				}
 			}
 		}
 	}
+	if m.viewport.AtBottom() {
+		m.unseenMessages = false
+	}
+	switch msg.(type) {
+	case tea.KeyMsg, tea.MouseMsg:
+		if m.hasOlder && !m.rendering && m.viewport.AtTop() {
+			cmds = append(cmds, m.loadOlder())
+		}
+	}
+	if !m.rendering {
+		m.saveScroll()
+	}
 
 	spinner, cmd := m.spinner.Update(msg)
 	m.spinner = spinner
@@ -202,6 +423,7 @@ func (m *messagesCmp) renderView() {
 			userMsg := renderUserMessage(
 				msg,
 				msg.ID == m.currentMsgID,
+				m.showTimestamps,
 				m.width,
 				pos,
 			)
@@ -225,6 +447,12 @@ func (m *messagesCmp) renderView() {
 				m.app.Messages,
 				m.currentMsgID,
 				isSummary,
+				toolView{
+					expandAll: m.expandToolOutput,
+					expanded:  m.expandedTools,
+					selected:  m.selectedTool,
+				},
+				m.showTimestamps,
 				m.width,
 				pos,
 			)
@@ -302,7 +530,7 @@ func (m *messagesCmp) View() string {
 		Render(
 			lipgloss.JoinVertical(
 				lipgloss.Top,
-				m.viewport.View(),
+				zone.Mark(messagesZone, m.viewport.View()),
 				m.working(),
 				m.help(),
 			),
@@ -361,11 +589,25 @@ func (m *messagesCmp) working() string {
 			task = "Generating..."
 		}
 		if task != "" {
-			text += baseStyle.
-				Width(m.width).
-				Foreground(t.Primary()).
-				Bold(true).
-				Render(fmt.Sprintf("%s %s ", m.spinner.View(), task))
+			// The spinner ticks keep the elapsed time current
+			elapsed := ""
+			for i := len(m.messages) - 1; i >= 0; i-- {
+				if m.messages[i].Role == message.User {
+					elapsed = time.Since(time.Unix(m.messages[i].CreatedAt, 0)).Round(time.Second).String()
+					break
+				}
+			}
+			text += lipgloss.JoinHorizontal(
+				lipgloss.Left,
+				baseStyle.
+					Foreground(t.Primary()).
+					Bold(true).
+					Render(fmt.Sprintf("%s %s ", m.spinner.View(), task)),
+				baseStyle.
+					Width(max(m.width-lipgloss.Width(m.spinner.View())-len(task)-2, 0)).
+					Foreground(t.TextMuted()).
+					Render(elapsed),
+			)
 		}
 	}
 	return text
@@ -376,6 +618,9 @@ func (m *messagesCmp) help() string {
 	baseStyle := styles.BaseStyle()
 
 	text := ""
+	if config.Get().TUI.VimMode && (m.editorNormalMode || !m.editorFocused) {
+		text += baseStyle.Foreground(t.Accent()).Bold(true).Render("-- NORMAL -- ")
+	}
 
 	if m.app.CoderAgent.IsBusy() {
 		text += lipgloss.JoinHorizontal(
@@ -395,11 +640,75 @@ func (m *messagesCmp) help() string {
 			baseStyle.Foreground(t.TextMuted()).Bold(true).Render(" and enter to add a new line"),
 		)
 	}
+	if indicator := m.scrollIndicator(); indicator != "" {
+		if gap := m.width - lipgloss.Width(text) - lipgloss.Width(indicator); gap > 0 {
+			text = lipgloss.JoinHorizontal(lipgloss.Left, text, baseStyle.Render(strings.Repeat(" ", gap)), indicator)
+		}
+	}
 	return baseStyle.
 		Width(m.width).
 		Render(text)
 }
 
+// yank copies the text of the message at the top of the view, or of the last
+// message when scrolled to the bottom.
+func (m *messagesCmp) yank() tea.Cmd {
+	if len(m.uiMessages) == 0 {
+		return nil
+	}
+	selected := m.uiMessages[len(m.uiMessages)-1]
+	if !m.viewport.AtBottom() {
+		offset := 0
+		for _, uiMsg := range m.uiMessages {
+			offset += uiMsg.height + 1 // + 1 for spacing
+			if offset > m.viewport.YOffset {
+				selected = uiMsg
+				break
+			}
+		}
+	}
+
+	text := ""
+	for _, msg := range m.messages {
+		if selected.messageType != toolMessageType {
+			if msg.ID == selected.ID {
+				text = msg.Content().String()
+				break
+			}
+			continue
+		}
+		for _, result := range msg.ToolResults() {
+			if result.ToolCallID == selected.ID {
+				text = result.Content
+				if result.FullContent != "" {
+					text = result.FullContent
+				}
+			}
+		}
+	}
+	if strings.TrimSpace(text) == "" {
+		return util.ReportWarn("Nothing to copy in this message")
+	}
+	return util.CmdHandler(dialog.CopySelectedMsg{Text: text})
+}
+
+// scrollIndicator shows how far the messages are scrolled when not at the
+// bottom.
+func (m *messagesCmp) scrollIndicator() string {
+	if len(m.messages) == 0 || m.viewport.AtBottom() {
+		return ""
+	}
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	indicator := fmt.Sprintf("%.f%%", m.viewport.ScrollPercent()*100)
+	if m.unseenMessages {
+		return baseStyle.Foreground(t.Primary()).Bold(true).Render("↓ new messages ") +
+			baseStyle.Foreground(t.TextMuted()).Render(indicator)
+	}
+	return baseStyle.Foreground(t.TextMuted()).Render(indicator)
+}
+
 func (m *messagesCmp) initialScreen() string {
 	baseStyle := styles.BaseStyle()
 
@@ -413,6 +722,159 @@ func (m *messagesCmp) initialScreen() string {
 	)
 }
 
+// selectTool moves the selection to the previous or next tool call. Without
+// a selection the last tool call is selected going back, and the first one
+// in view going forward.
+func (m *messagesCmp) selectTool(direction int) {
+	var toolIDs []string
+	for _, uiMsg := range m.uiMessages {
+		if uiMsg.messageType == toolMessageType {
+			toolIDs = append(toolIDs, uiMsg.ID)
+		}
+	}
+	if len(toolIDs) == 0 {
+		return
+	}
+
+	var selected string
+	idx := slices.Index(toolIDs, m.selectedTool)
+	switch {
+	case idx >= 0:
+		selected = toolIDs[max(0, min(idx+direction, len(toolIDs)-1))]
+	case direction < 0:
+		selected = toolIDs[len(toolIDs)-1]
+	default:
+		selected = toolIDs[len(toolIDs)-1]
+		for _, id := range toolIDs {
+			if m.toolOffset(id) >= m.viewport.YOffset {
+				selected = id
+				break
+			}
+		}
+	}
+
+	previous := m.selectedTool
+	m.selectedTool = selected
+	if previous != "" && previous != selected {
+		m.renderTool(previous)
+	}
+	m.renderTool(selected)
+	m.scrollToTool(selected)
+}
+
+// jumpToPrompt scrolls the previous or next prompt to the top of the messages
+func (m *messagesCmp) jumpToPrompt(direction int) {
+	var offsets []int
+	offset := 0
+	for _, uiMsg := range m.uiMessages {
+		if uiMsg.messageType == userMessageType {
+			offsets = append(offsets, offset)
+		}
+		offset += uiMsg.height + 1
+	}
+	if target, ok := adjacentOffset(offsets, m.viewport.YOffset, direction); ok {
+		m.viewport.SetYOffset(target)
+	}
+}
+
+// adjacentOffset returns the last offset above the current one, or the first
+// one below it, of the sorted offsets
+func adjacentOffset(offsets []int, current int, direction int) (int, bool) {
+	if direction < 0 {
+		for i := len(offsets) - 1; i >= 0; i-- {
+			if offsets[i] < current {
+				return offsets[i], true
+			}
+		}
+		return 0, false
+	}
+	for _, offset := range offsets {
+		if offset > current {
+			return offset, true
+		}
+	}
+	return 0, false
+}
+
+// clickTool selects and expands or collapses the tool call under the mouse
+func (m *messagesCmp) clickTool(msg tea.MouseMsg) {
+	z := zone.Get(messagesZone)
+	if z == nil || !z.InBounds(msg) {
+		return
+	}
+	_, y := z.Pos(msg)
+	toolCallID, ok := toolAtLine(m.uiMessages, m.viewport.YOffset+y)
+	if !ok {
+		return
+	}
+	previous := m.selectedTool
+	m.selectedTool = toolCallID
+	m.expandedTools[toolCallID] = !m.expandedTools[toolCallID]
+	if previous != "" && previous != toolCallID {
+		m.renderTool(previous)
+	}
+	m.renderTool(toolCallID)
+}
+
+// toolAtLine returns the tool call shown on the line of the messages
+func toolAtLine(uiMessages []uiMessage, line int) (string, bool) {
+	offset := 0
+	for _, uiMsg := range uiMessages {
+		if line < offset {
+			break
+		}
+		if line < offset+uiMsg.height {
+			if uiMsg.messageType != toolMessageType {
+				return "", false
+			}
+			return uiMsg.ID, true
+		}
+		offset += uiMsg.height + 1
+	}
+	return "", false
+}
+
+// renderTool renders again the message with the tool call
+func (m *messagesCmp) renderTool(toolCallID string) {
+	for _, msg := range m.messages {
+		for _, call := range msg.ToolCalls() {
+			if call.ID == toolCallID {
+				delete(m.cachedContent, msg.ID)
+			}
+		}
+	}
+	m.renderView()
+}
+
+// toolOffset returns the line of the messages the tool call starts at
+func (m *messagesCmp) toolOffset(toolCallID string) int {
+	offset := 0
+	for _, uiMsg := range m.uiMessages {
+		if uiMsg.ID == toolCallID {
+			return offset
+		}
+		offset += uiMsg.height + 1
+	}
+	return offset
+}
+
+// scrollToTool scrolls the least needed to show the tool call
+func (m *messagesCmp) scrollToTool(toolCallID string) {
+	top := m.toolOffset(toolCallID)
+	height := 1
+	for _, uiMsg := range m.uiMessages {
+		if uiMsg.ID == toolCallID {
+			height = uiMsg.height
+		}
+	}
+	switch {
+	case top < m.viewport.YOffset:
+		m.viewport.SetYOffset(top)
+	case top+height > m.viewport.YOffset+m.viewport.Height:
+		m.viewport.SetYOffset(min(top, top+height-m.viewport.Height))
+	}
+}
+
 func (m *messagesCmp) rerender() {
 	for _, msg := range m.messages {
 		delete(m.cachedContent, msg.ID)
@@ -443,15 +905,20 @@ func (m *messagesCmp) SetSession(session session.Session) tea.Cmd {
 		return nil
 	}
 	m.session = session
-	messages, err := m.app.Messages.List(context.Background(), session.ID)
+	messages, hasOlder, err := m.app.Messages.ListRecent(context.Background(), session.ID, 0, messagesPageSize)
 	if err != nil {
 		return util.ReportError(err)
 	}
 	m.messages = messages
+	m.hasOlder = hasOlder
 	if len(m.messages) > 0 {
 		m.currentMsgID = m.messages[len(m.messages)-1].ID
 	}
 	delete(m.cachedContent, m.currentMsgID)
+	// The session open when the TUI was closed is scrolled back to where it was
+	if fromBottom, ok := state.TakeScroll(session.ID); ok {
+		m.restoreScroll = fromBottom
+	}
 	m.rendering = true
 	return func() tea.Msg {
 		m.renderView()
@@ -459,12 +926,76 @@ func (m *messagesCmp) SetSession(session session.Session) tea.Cmd {
 	}
 }
 
+// loadOlder adds the page of messages before the loaded ones, keeping the
+// same messages in view
+func (m *messagesCmp) loadOlder() tea.Cmd {
+	older, hasOlder, err := m.app.Messages.ListRecent(context.Background(), m.session.ID, len(m.messages), messagesPageSize)
+	if err != nil {
+		return util.ReportError(err)
+	}
+	m.hasOlder = hasOlder
+	if len(older) == 0 {
+		return nil
+	}
+	lines := m.viewport.TotalLineCount()
+	m.messages = append(older, m.messages...)
+	m.renderView()
+	m.viewport.SetYOffset(m.viewport.YOffset + m.viewport.TotalLineCount() - lines)
+	return nil
+}
+
+// scrollToMessage scrolls the messages to put scrollTo at the top, loading older
+// messages until it's found
+func (m *messagesCmp) scrollToMessage() tea.Cmd {
+	id := m.scrollTo
+	m.scrollTo = ""
+	for !slices.ContainsFunc(m.messages, func(msg message.Message) bool { return msg.ID == id }) {
+		if !m.hasOlder {
+			return util.ReportWarn("The message is no longer in the session")
+		}
+		if cmd := m.loadOlder(); cmd != nil {
+			return cmd
+		}
+	}
+	offset := 0
+	for _, uiMsg := range m.uiMessages {
+		if uiMsg.ID == id {
+			m.viewport.SetYOffset(offset)
+			break
+		}
+		offset += uiMsg.height + 1
+	}
+	return nil
+}
+
+// saveScroll keeps the session and its scroll position, they're restored
+// when the TUI is started again
+func (m *messagesCmp) saveScroll() {
+	fromBottom := max(m.viewport.TotalLineCount()-m.viewport.Height-m.viewport.YOffset, 0)
+	state.Update(func(s *state.State) {
+		s.SessionID = m.session.ID
+		s.ScrollFromBottom = fromBottom
+	})
+}
+
 func (m *messagesCmp) BindingKeys() []key.Binding {
 	return []key.Binding{
 		m.viewport.KeyMap.PageDown,
 		m.viewport.KeyMap.PageUp,
 		m.viewport.KeyMap.HalfPageUp,
 		m.viewport.KeyMap.HalfPageDown,
+		messageKeys.LineUp,
+		messageKeys.LineDown,
+		messageKeys.Top,
+		messageKeys.Bottom,
+		messageKeys.Yank,
+		messageKeys.ToggleToolOutput,
+		messageKeys.PreviousTool,
+		messageKeys.NextTool,
+		messageKeys.ExpandTool,
+		messageKeys.ToggleTimestamps,
+		messageKeys.PreviousPrompt,
+		messageKeys.NextPrompt,
 	}
 }
 
@@ -477,11 +1008,16 @@ func NewMessagesCmp(app *app.App) tea.Model {
 	vp.KeyMap.PageDown = messageKeys.PageDown
 	vp.KeyMap.HalfPageUp = messageKeys.HalfPageUp
 	vp.KeyMap.HalfPageDown = messageKeys.HalfPageDown
+	vp.KeyMap.Up = messageKeys.LineUp
+	vp.KeyMap.Down = messageKeys.LineDown
 	return &messagesCmp{
-		app:           app,
-		cachedContent: make(map[string]cacheItem),
-		viewport:      vp,
-		spinner:       s,
-		attachments:   attachmets,
+		app:            app,
+		cachedContent:  make(map[string]cacheItem),
+		expandedTools:  make(map[string]bool),
+		viewport:       vp,
+		spinner:        s,
+		attachments:    attachmets,
+		editorFocused:  true,
+		showTimestamps: config.Get().TUI.Timestamps,
 	}
 }
@@ -0,0 +1,54 @@
+package chat
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestAdjacentOffset(t *testing.T) {
+	offsets := []int{0, 20, 45}
+
+	offset, ok := adjacentOffset(offsets, 30, -1)
+	assert.True(t, ok)
+	assert.Equal(t, 20, offset)
+
+	// A prompt at the top of the view isn't jumped to again
+	offset, ok = adjacentOffset(offsets, 20, -1)
+	assert.True(t, ok)
+	assert.Equal(t, 0, offset)
+
+	offset, ok = adjacentOffset(offsets, 20, 1)
+	assert.True(t, ok)
+	assert.Equal(t, 45, offset)
+
+	_, ok = adjacentOffset(offsets, 0, -1)
+	assert.False(t, ok)
+	_, ok = adjacentOffset(offsets, 45, 1)
+	assert.False(t, ok)
+	_, ok = adjacentOffset(nil, 10, 1)
+	assert.False(t, ok)
+}
+
+func TestToolAtLine(t *testing.T) {
+	uiMessages := []uiMessage{
+		{ID: "user", messageType: userMessageType, height: 3},
+		{ID: "assistant", messageType: assistantMessageType, height: 2},
+		{ID: "tool", messageType: toolMessageType, height: 4},
+	}
+
+	id, ok := toolAtLine(uiMessages, 7)
+	assert.True(t, ok)
+	assert.Equal(t, "tool", id)
+	id, ok = toolAtLine(uiMessages, 10)
+	assert.True(t, ok)
+	assert.Equal(t, "tool", id)
+
+	_, ok = toolAtLine(uiMessages, 1)
+	assert.False(t, ok)
+	// The blank line between the messages belongs to none of them
+	_, ok = toolAtLine(uiMessages, 6)
+	assert.False(t, ok)
+	_, ok = toolAtLine(uiMessages, 11)
+	assert.False(t, ok)
+}
@@ -16,8 +16,10 @@ import (
 	"github.com/zhenbah/cryoncode/internal/llm/models"
 	"github.com/zhenbah/cryoncode/internal/llm/tools"
 	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/tui/graphics"
 	"github.com/zhenbah/cryoncode/internal/tui/styles"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
 
 type uiMessageType int
@@ -27,7 +29,8 @@ const (
 	assistantMessageType
 	toolMessageType
 
-	maxResultHeight = 10
+	maxResultHeight         = 10
+	maxExpandedResultHeight = 500
 )
 
 type uiMessage struct {
@@ -40,7 +43,7 @@ type uiMessage struct {
 
 func toMarkdown(content string, focused bool, width int) string {
 	r := styles.GetMarkdownRenderer(width)
-	rendered, _ := r.Render(content)
+	rendered, _ := r.Render(styles.TagCodeBlockLanguages(util.ExpandTabs(content)))
 	return rendered
 }
 
@@ -54,8 +57,12 @@ func renderMessage(msg string, isUser bool, isFocused bool, width int, info ...s
 		BorderForeground(t.Primary()).
 		BorderStyle(lipgloss.ThickBorder())
 
+	// The gutter tells who wrote the message: a half block for the user
+	// and a thick line for the assistant
 	if isUser {
-		style = style.BorderForeground(t.Secondary())
+		style = style.
+			BorderForeground(t.Secondary()).
+			BorderStyle(lipgloss.OuterHalfBlockBorder())
 	}
 
 	// Apply markdown formatting and handle background color
@@ -79,7 +86,7 @@ func renderMessage(msg string, isUser bool, isFocused bool, width int, info ...s
 	return rendered
 }
 
-func renderUserMessage(msg message.Message, isFocused bool, width int, position int) uiMessage {
+func renderUserMessage(msg message.Message, isFocused bool, showTimestamp bool, width int, position int) uiMessage {
 	var styledAttachments []string
 	t := theme.CurrentTheme()
 	attachmentStyles := styles.BaseStyle().
@@ -88,21 +95,32 @@ func renderUserMessage(msg message.Message, isFocused bool, width int, position
 		Foreground(t.Text())
 	for _, attachment := range msg.BinaryContent() {
 		file := filepath.Base(attachment.Path)
-		var filename string
-		if len(file) > 10 {
-			filename = fmt.Sprintf(" %s %s...", styles.DocumentIcon, file[0:7])
-		} else {
-			filename = fmt.Sprintf(" %s %s", styles.DocumentIcon, file)
-		}
+		filename := fmt.Sprintf(" %s %s", styles.DocumentIcon, util.Truncate(file, 10))
 		styledAttachments = append(styledAttachments, attachmentStyles.Render(filename))
 	}
-	content := ""
+	var info []string
+	if showTimestamp {
+		info = append(info, styles.BaseStyle().
+			Width(width-1).
+			Foreground(t.TextMuted()).
+			Render(" "+formatMessageTime(msg.CreatedAt)),
+		)
+	}
 	if len(styledAttachments) > 0 {
-		attachmentContent := styles.BaseStyle().Width(width).Render(lipgloss.JoinHorizontal(lipgloss.Left, styledAttachments...))
-		content = renderMessage(msg.Content().String(), true, isFocused, width, attachmentContent)
-	} else {
-		content = renderMessage(msg.Content().String(), true, isFocused, width)
+		info = append(info, styles.BaseStyle().Width(width).Render(lipgloss.JoinHorizontal(lipgloss.Left, styledAttachments...)))
+	}
+	// Images are drawn below the attachments when the terminal supports it,
+	// the attachment names are all that is shown otherwise
+	protocol := graphics.Detect(config.Get().TUI.Images)
+	for _, attachment := range msg.BinaryContent() {
+		if !strings.HasPrefix(attachment.MIMEType, "image/") {
+			continue
+		}
+		if lines, ok := graphics.Render(protocol, attachment.Data, width-4, graphics.MaxRows); ok {
+			info = append(info, " "+strings.Join(lines, "\n "))
+		}
 	}
+	content := renderMessage(msg.Content().String(), true, isFocused, width, info...)
 	userMsg := uiMessage{
 		ID:          msg.ID,
 		messageType: userMessageType,
@@ -113,6 +131,17 @@ func renderUserMessage(msg message.Message, isFocused bool, width int, position
 	return userMsg
 }
 
+// toolView is how the tool calls of the messages are shown
+type toolView struct {
+	// expandAll shows the full output of every tool call
+	expandAll bool
+	// expanded are the tool calls shown with a preview of their output, the
+	// others are collapsed to a one line summary
+	expanded map[string]bool
+	// selected is the tool call picked to be expanded or collapsed
+	selected string
+}
+
 // Returns multiple uiMessages because of the tool calls
 func renderAssistantMessage(
 	msg message.Message,
@@ -121,6 +150,8 @@ func renderAssistantMessage(
 	messagesService message.Service, // We need this to get the task tool messages
 	focusedUIMessageId string,
 	isSummary bool,
+	view toolView,
+	showTimestamp bool,
 	width int,
 	position int,
 ) []uiMessage {
@@ -136,34 +167,32 @@ func renderAssistantMessage(
 	baseStyle := styles.BaseStyle()
 
 	// Add finish info if available
+	var status string
 	if finished {
 		switch finishData.Reason {
 		case message.FinishReasonEndTurn:
-			took := formatTimestampDiff(msg.CreatedAt, finishData.Time)
-			info = append(info, baseStyle.
-				Width(width-1).
-				Foreground(t.TextMuted()).
-				Render(fmt.Sprintf(" %s (%s)", models.SupportedModels[msg.Model].Name, took)),
-			)
+			status = formatTimestampDiff(msg.CreatedAt, finishData.Time)
 		case message.FinishReasonCanceled:
-			info = append(info, baseStyle.
-				Width(width-1).
-				Foreground(t.TextMuted()).
-				Render(fmt.Sprintf(" %s (%s)", models.SupportedModels[msg.Model].Name, "canceled")),
-			)
+			status = "canceled"
 		case message.FinishReasonError:
-			info = append(info, baseStyle.
-				Width(width-1).
-				Foreground(t.TextMuted()).
-				Render(fmt.Sprintf(" %s (%s)", models.SupportedModels[msg.Model].Name, "error")),
-			)
+			status = "error"
 		case message.FinishReasonPermissionDenied:
-			info = append(info, baseStyle.
-				Width(width-1).
-				Foreground(t.TextMuted()).
-				Render(fmt.Sprintf(" %s (%s)", models.SupportedModels[msg.Model].Name, "permission denied")),
-			)
+			status = "permission denied"
+		}
+	} else if content != "" {
+		// Shown while the response is still streaming in
+		status = "writing..."
+	}
+	if status != "" {
+		modelInfo := fmt.Sprintf("%s (%s)", models.SupportedModels[msg.Model].Name, status)
+		if showTimestamp {
+			modelInfo = formatMessageTime(msg.CreatedAt) + " · " + modelInfo
 		}
+		info = append(info, baseStyle.
+			Width(width-1).
+			Foreground(t.TextMuted()).
+			Render(" "+modelInfo),
+		)
 	}
 	if content != "" || (finished && finishData.Reason == message.FinishReasonEndTurn) {
 		if content == "" {
@@ -195,6 +224,7 @@ func renderAssistantMessage(
 			messagesService,
 			focusedUIMessageId,
 			false,
+			view,
 			width,
 			i+1,
 		)
@@ -224,12 +254,32 @@ func toolName(name string) string {
 		return "Bash"
 	case tools.EditToolName:
 		return "Edit"
+	case tools.MultiEditToolName:
+		return "Multi-Edit"
+	case tools.NotebookReadToolName:
+		return "Read Notebook"
+	case tools.NotebookEditToolName:
+		return "Edit Notebook"
 	case tools.FetchToolName:
 		return "Fetch"
+	case tools.DownloadToolName:
+		return "Download"
+	case tools.ShellSessionToolName:
+		return "Shell"
 	case tools.GlobToolName:
 		return "Glob"
 	case tools.GrepToolName:
 		return "Grep"
+	case tools.SymbolsToolName:
+		return "Symbols"
+	case tools.DocsToolName:
+		return "Docs"
+	case tools.EnvInfoToolName:
+		return "Environment"
+	case tools.TodoToolName:
+		return "Plan"
+	case tools.CopyToClipboardToolName:
+		return "Copy"
 	case tools.LSToolName:
 		return "List"
 	case tools.SourcegraphToolName:
@@ -240,6 +290,20 @@ func toolName(name string) string {
 		return "Write"
 	case tools.PatchToolName:
 		return "Patch"
+	case tools.WebSearchToolName:
+		return "Web Search"
+	case tools.MkdirToolName:
+		return "Mkdir"
+	case tools.RmToolName:
+		return "Delete"
+	case tools.MvToolName:
+		return "Move"
+	case tools.CpToolName:
+		return "Copy"
+	case tools.DiagnosticsToolName:
+		return "Diagnostics"
+	case tools.RunTestsToolName:
+		return "Tests"
 	}
 	return name
 }
@@ -252,12 +316,32 @@ func getToolAction(name string) string {
 		return "Building command..."
 	case tools.EditToolName:
 		return "Preparing edit..."
+	case tools.MultiEditToolName:
+		return "Preparing edits..."
+	case tools.NotebookReadToolName:
+		return "Reading notebook..."
+	case tools.NotebookEditToolName:
+		return "Preparing notebook edit..."
 	case tools.FetchToolName:
 		return "Writing fetch..."
+	case tools.DownloadToolName:
+		return "Preparing download..."
+	case tools.ShellSessionToolName:
+		return "Using shell session..."
 	case tools.GlobToolName:
 		return "Finding files..."
 	case tools.GrepToolName:
 		return "Searching content..."
+	case tools.SymbolsToolName:
+		return "Finding symbols..."
+	case tools.DocsToolName:
+		return "Looking up documentation..."
+	case tools.EnvInfoToolName:
+		return "Inspecting environment..."
+	case tools.TodoToolName:
+		return "Updating plan..."
+	case tools.CopyToClipboardToolName:
+		return "Copying to clipboard..."
 	case tools.LSToolName:
 		return "Listing directory..."
 	case tools.SourcegraphToolName:
@@ -268,6 +352,20 @@ func getToolAction(name string) string {
 		return "Preparing write..."
 	case tools.PatchToolName:
 		return "Preparing patch..."
+	case tools.WebSearchToolName:
+		return "Searching the web..."
+	case tools.MkdirToolName:
+		return "Creating directory..."
+	case tools.RmToolName:
+		return "Deleting..."
+	case tools.MvToolName:
+		return "Moving..."
+	case tools.CpToolName:
+		return "Copying..."
+	case tools.DiagnosticsToolName:
+		return "Checking diagnostics..."
+	case tools.RunTestsToolName:
+		return "Running tests..."
 	}
 	return "Working..."
 }
@@ -277,10 +375,7 @@ func renderParams(paramsWidth int, params ...string) string {
 	if len(params) == 0 {
 		return ""
 	}
-	mainParam := params[0]
-	if len(mainParam) > paramsWidth {
-		mainParam = mainParam[:paramsWidth-3] + "..."
-	}
+	mainParam := util.Truncate(params[0], paramsWidth)
 
 	if len(params) == 1 {
 		return mainParam
@@ -350,6 +445,36 @@ func renderToolParams(paramWidth int, toolCall message.ToolCall) string {
 		json.Unmarshal([]byte(toolCall.Input), &params)
 		filePath := removeWorkingDirPrefix(params.FilePath)
 		return renderParams(paramWidth, filePath)
+	case tools.MultiEditToolName:
+		var params tools.MultiEditParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		filePath := removeWorkingDirPrefix(params.FilePath)
+		return renderParams(paramWidth, filePath, "edits", fmt.Sprintf("%d", len(params.Edits)))
+	case tools.NotebookReadToolName:
+		var params tools.NotebookReadParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		filePath := removeWorkingDirPrefix(params.FilePath)
+		toolParams := []string{
+			filePath,
+		}
+		if params.CellID != "" {
+			toolParams = append(toolParams, "cell", params.CellID)
+		}
+		return renderParams(paramWidth, toolParams...)
+	case tools.NotebookEditToolName:
+		var params tools.NotebookEditParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		filePath := removeWorkingDirPrefix(params.FilePath)
+		toolParams := []string{
+			filePath,
+		}
+		if params.EditMode != "" {
+			toolParams = append(toolParams, "mode", params.EditMode)
+		}
+		if params.CellID != "" {
+			toolParams = append(toolParams, "cell", params.CellID)
+		}
+		return renderParams(paramWidth, toolParams...)
 	case tools.FetchToolName:
 		var params tools.FetchParams
 		json.Unmarshal([]byte(toolCall.Input), &params)
@@ -364,6 +489,17 @@ func renderToolParams(paramWidth int, toolCall message.ToolCall) string {
 			toolParams = append(toolParams, "timeout", (time.Duration(params.Timeout) * time.Second).String())
 		}
 		return renderParams(paramWidth, toolParams...)
+	case tools.DownloadToolName:
+		var params tools.DownloadParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, params.URL, "to", removeWorkingDirPrefix(params.FilePath))
+	case tools.ShellSessionToolName:
+		var params tools.ShellSessionParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		if params.Input != "" {
+			return renderParams(paramWidth, strings.ReplaceAll(params.Input, "\n", " "), "session", params.SessionID)
+		}
+		return renderParams(paramWidth, params.Action, "session", params.SessionID)
 	case tools.GlobToolName:
 		var params tools.GlobParams
 		json.Unmarshal([]byte(toolCall.Input), &params)
@@ -392,6 +528,41 @@ func renderToolParams(paramWidth int, toolCall message.ToolCall) string {
 			toolParams = append(toolParams, "literal", "true")
 		}
 		return renderParams(paramWidth, toolParams...)
+	case tools.SymbolsToolName:
+		var params tools.SymbolsParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		toolParams := []string{
+			params.Query,
+		}
+		if params.Kind != "" {
+			toolParams = append(toolParams, "kind", params.Kind)
+		}
+		if params.Path != "" {
+			toolParams = append(toolParams, "path", params.Path)
+		}
+		return renderParams(paramWidth, toolParams...)
+	case tools.DocsToolName:
+		var params tools.DocsParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		toolParams := []string{
+			params.Name,
+		}
+		if params.Source != "" {
+			toolParams = append(toolParams, "source", params.Source)
+		}
+		return renderParams(paramWidth, toolParams...)
+	case tools.EnvInfoToolName:
+		var params tools.EnvInfoParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, strings.Join(params.Tools, ", "))
+	case tools.CopyToClipboardToolName:
+		var params tools.CopyToClipboardParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, strings.ReplaceAll(params.Text, "\n", " "))
+	case tools.TodoToolName:
+		var params tools.TodoParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, fmt.Sprintf("%d tasks", len(params.Todos)))
 	case tools.LSToolName:
 		var params tools.LSParams
 		json.Unmarshal([]byte(toolCall.Input), &params)
@@ -423,6 +594,62 @@ func renderToolParams(paramWidth int, toolCall message.ToolCall) string {
 		json.Unmarshal([]byte(toolCall.Input), &params)
 		filePath := removeWorkingDirPrefix(params.FilePath)
 		return renderParams(paramWidth, filePath)
+	case tools.WebSearchToolName:
+		var params tools.WebSearchParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		toolParams := []string{
+			params.Query,
+		}
+		if params.Count != 0 {
+			toolParams = append(toolParams, "count", fmt.Sprintf("%d", params.Count))
+		}
+		return renderParams(paramWidth, toolParams...)
+	case tools.MkdirToolName:
+		var params tools.MkdirParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, removeWorkingDirPrefix(params.Path))
+	case tools.RmToolName:
+		var params tools.RmParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		toolParams := []string{
+			removeWorkingDirPrefix(params.Path),
+		}
+		if params.Recursive {
+			toolParams = append(toolParams, "recursive", "true")
+		}
+		return renderParams(paramWidth, toolParams...)
+	case tools.MvToolName:
+		var params tools.MvParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, fmt.Sprintf("%s → %s", removeWorkingDirPrefix(params.Source), removeWorkingDirPrefix(params.Destination)))
+	case tools.CpToolName:
+		var params tools.CpParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		return renderParams(paramWidth, fmt.Sprintf("%s → %s", removeWorkingDirPrefix(params.Source), removeWorkingDirPrefix(params.Destination)))
+	case tools.DiagnosticsToolName:
+		var params tools.DiagnosticsParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		if params.FilePath == "" {
+			return renderParams(paramWidth, "project")
+		}
+		return renderParams(paramWidth, removeWorkingDirPrefix(params.FilePath))
+	case tools.RunTestsToolName:
+		var params tools.RunTestsParams
+		json.Unmarshal([]byte(toolCall.Input), &params)
+		path := params.Path
+		if path == "" {
+			path = "."
+		}
+		toolParams := []string{
+			path,
+		}
+		if params.Framework != "" {
+			toolParams = append(toolParams, "framework", params.Framework)
+		}
+		if params.Filter != "" {
+			toolParams = append(toolParams, "filter", params.Filter)
+		}
+		return renderParams(paramWidth, toolParams...)
 	default:
 		input := strings.ReplaceAll(toolCall.Input, "\n", " ")
 		params = renderParams(paramWidth, input)
@@ -438,10 +665,18 @@ func truncateHeight(content string, height int) string {
 	return content
 }
 
-func renderToolResponse(toolCall message.ToolCall, response message.ToolResult, width int) string {
+func renderToolResponse(toolCall message.ToolCall, response message.ToolResult, expanded bool, width int) string {
 	t := theme.CurrentTheme()
 	baseStyle := styles.BaseStyle()
 
+	resultHeight := maxResultHeight
+	if expanded {
+		resultHeight = maxExpandedResultHeight
+		if response.FullContent != "" {
+			response.Content = response.FullContent
+		}
+	}
+
 	if response.IsError {
 		errContent := fmt.Sprintf("Error: %s", strings.ReplaceAll(response.Content, "\n", " "))
 		errContent = ansi.Truncate(errContent, width-1, "...")
@@ -451,7 +686,7 @@ func renderToolResponse(toolCall message.ToolCall, response message.ToolResult,
 			Render(errContent)
 	}
 
-	resultContent := truncateHeight(response.Content, maxResultHeight)
+	resultContent := truncateHeight(util.ExpandTabs(response.Content), resultHeight)
 	switch toolCall.Name {
 	case agent.AgentToolName:
 		return styles.ForceReplaceBackgroundWithLipgloss(
@@ -464,10 +699,10 @@ func renderToolResponse(toolCall message.ToolCall, response message.ToolResult,
 			toMarkdown(resultContent, true, width),
 			t.Background(),
 		)
-	case tools.EditToolName:
+	case tools.EditToolName, tools.MultiEditToolName, tools.NotebookEditToolName:
 		metadata := tools.EditResponseMetadata{}
 		json.Unmarshal([]byte(response.Metadata), &metadata)
-		truncDiff := truncateHeight(metadata.Diff, maxResultHeight)
+		truncDiff := truncateHeight(metadata.Diff, resultHeight)
 		formattedDiff, _ := diff.FormatDiff(truncDiff, diff.WithTotalWidth(width))
 		return formattedDiff
 	case tools.FetchToolName:
@@ -485,14 +720,54 @@ func renderToolResponse(toolCall message.ToolCall, response message.ToolResult,
 			toMarkdown(resultContent, true, width),
 			t.Background(),
 		)
+	case tools.DownloadToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.ShellSessionToolName:
+		resultContent = fmt.Sprintf("```bash\n%s\n```", resultContent)
+		return styles.ForceReplaceBackgroundWithLipgloss(
+			toMarkdown(resultContent, true, width),
+			t.Background(),
+		)
 	case tools.GlobToolName:
 		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
 	case tools.GrepToolName:
 		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.SymbolsToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.DocsToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.CopyToClipboardToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.NotebookReadToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.EnvInfoToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.TodoToolName:
+		metadata := tools.TodoResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		if len(metadata.Todos) == 0 {
+			return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+		}
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(tools.FormatTodos(metadata.Todos))
 	case tools.LSToolName:
 		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
 	case tools.SourcegraphToolName:
 		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.WebSearchToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.MkdirToolName, tools.RmToolName, tools.MvToolName, tools.CpToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(resultContent)
+	case tools.DiagnosticsToolName:
+		return baseStyle.Width(width).Foreground(t.TextMuted()).Render(strings.TrimSpace(resultContent))
+	case tools.RunTestsToolName:
+		metadata := tools.RunTestsResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		summary := fmt.Sprintf("%d passed, %d failed, %d skipped (exit code %d)", metadata.Passed, metadata.Failed, metadata.Skipped, metadata.ExitCode)
+		color := t.Success()
+		if metadata.ExitCode != 0 {
+			color = t.Error()
+		}
+		return baseStyle.Width(width).Foreground(color).Render(summary)
 	case tools.ViewToolName:
 		metadata := tools.ViewResponseMetadata{}
 		json.Unmarshal([]byte(response.Metadata), &metadata)
@@ -502,7 +777,7 @@ func renderToolResponse(toolCall message.ToolCall, response message.ToolResult,
 		} else {
 			ext = strings.ToLower(ext[1:])
 		}
-		resultContent = fmt.Sprintf("```%s\n%s\n```", ext, truncateHeight(metadata.Content, maxResultHeight))
+		resultContent = fmt.Sprintf("```%s\n%s\n```", ext, truncateHeight(util.ExpandTabs(metadata.Content), resultHeight))
 		return styles.ForceReplaceBackgroundWithLipgloss(
 			toMarkdown(resultContent, true, width),
 			t.Background(),
@@ -518,7 +793,7 @@ func renderToolResponse(toolCall message.ToolCall, response message.ToolResult,
 		} else {
 			ext = strings.ToLower(ext[1:])
 		}
-		resultContent = fmt.Sprintf("```%s\n%s\n```", ext, truncateHeight(params.Content, maxResultHeight))
+		resultContent = fmt.Sprintf("```%s\n%s\n```", ext, truncateHeight(util.ExpandTabs(params.Content), resultHeight))
 		return styles.ForceReplaceBackgroundWithLipgloss(
 			toMarkdown(resultContent, true, width),
 			t.Background(),
@@ -538,6 +813,7 @@ func renderToolMessage(
 	messagesService message.Service,
 	focusedUIMessageId string,
 	nested bool,
+	view toolView,
 	width int,
 	position int,
 ) uiMessage {
@@ -551,9 +827,13 @@ func renderToolMessage(
 	style := baseStyle.
 		Width(width - 1).
 		BorderLeft(true).
-		BorderStyle(lipgloss.ThickBorder()).
+		BorderStyle(lipgloss.NormalBorder()).
 		PaddingLeft(1).
 		BorderForeground(t.TextMuted())
+	if toolCall.ID == view.selected {
+		style = style.BorderForeground(t.Primary())
+	}
+	expanded := view.expandAll
 
 	response := findToolResponse(toolCall.ID, allMessages)
 	toolNameText := baseStyle.Foreground(t.TextMuted()).
@@ -570,6 +850,7 @@ func renderToolMessage(
 
 		content := style.Render(lipgloss.JoinHorizontal(lipgloss.Left, toolNameText, progressText))
 		toolMsg := uiMessage{
+			ID:          toolCall.ID,
 			messageType: toolMessageType,
 			position:    position,
 			height:      lipgloss.Height(content),
@@ -578,11 +859,29 @@ func renderToolMessage(
 		return toolMsg
 	}
 
+	if !nested && response != nil && !view.expandAll && !view.expanded[toolCall.ID] {
+		content := style.Render(renderCollapsedTool(toolCall, *response, toolNameText, width-2))
+		return uiMessage{
+			ID:          toolCall.ID,
+			messageType: toolMessageType,
+			position:    position,
+			height:      lipgloss.Height(content),
+			content:     content,
+		}
+	}
+
 	params := renderToolParams(width-2-lipgloss.Width(toolNameText), toolCall)
 	responseContent := ""
 	if response != nil {
-		responseContent = renderToolResponse(toolCall, *response, width-2)
+		responseContent = renderToolResponse(toolCall, *response, expanded, width-2)
 		responseContent = strings.TrimSuffix(responseContent, "\n")
+		if response.FullContent != "" && !expanded && !response.IsError {
+			hint := baseStyle.
+				Width(width - 2).
+				Foreground(t.TextMuted()).
+				Render(fmt.Sprintf("Output truncated for the model, press %s to show the full output", messageKeys.ToggleToolOutput.Help().Key))
+			responseContent = lipgloss.JoinVertical(lipgloss.Left, responseContent, hint)
+		}
 	} else {
 		responseContent = baseStyle.
 			Italic(true).
@@ -617,7 +916,7 @@ func renderToolMessage(
 			toolCalls = append(toolCalls, v.ToolCalls()...)
 		}
 		for _, call := range toolCalls {
-			rendered := renderToolMessage(call, []message.Message{}, messagesService, focusedUIMessageId, true, width, 0)
+			rendered := renderToolMessage(call, []message.Message{}, messagesService, focusedUIMessageId, true, view, width, 0)
 			parts = append(parts, rendered.content)
 		}
 	}
@@ -638,6 +937,7 @@ func renderToolMessage(
 		)
 	}
 	toolMsg := uiMessage{
+		ID:          toolCall.ID,
 		messageType: toolMessageType,
 		position:    position,
 		height:      lipgloss.Height(content),
@@ -646,7 +946,132 @@ func renderToolMessage(
 	return toolMsg
 }
 
+// renderCollapsedTool renders the tool call on a single line, its parameters
+// followed by a summary of the result
+func renderCollapsedTool(toolCall message.ToolCall, response message.ToolResult, toolNameText string, width int) string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	summary := "→ " + toolSummary(toolCall, response)
+	summaryColor := t.TextMuted()
+	if response.IsError {
+		summaryColor = t.Error()
+	}
+	// The summary is kept whole, the parameters take the room left
+	summary = ansi.Truncate(summary, max(width-lipgloss.Width(toolNameText)-10, 10), "...")
+	paramsWidth := width - lipgloss.Width(toolNameText) - lipgloss.Width(summary) - 1
+	params := ""
+	if paramsWidth > 3 {
+		params = ansi.Truncate(renderToolParams(paramsWidth, toolCall), paramsWidth, "...")
+	}
+	return lipgloss.JoinHorizontal(
+		lipgloss.Left,
+		toolNameText,
+		baseStyle.Foreground(t.TextMuted()).Render(params+" "),
+		baseStyle.Foreground(summaryColor).Render(summary),
+	)
+}
+
+// toolSummary describes the result of a tool call in a few words, e.g.
+// "42 entries" for ls or "exit 0, 120 lines" for bash
+func toolSummary(toolCall message.ToolCall, response message.ToolResult) string {
+	if response.IsError {
+		return "error: " + firstLine(response.Content)
+	}
+	content := response.Content
+	if response.FullContent != "" {
+		content = response.FullContent
+	}
+	switch toolCall.Name {
+	case tools.BashToolName:
+		metadata := tools.BashResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		if content == "no output" {
+			return fmt.Sprintf("exit %d, no output", metadata.ExitCode)
+		}
+		return fmt.Sprintf("exit %d, %s", metadata.ExitCode, plural(countLines(content), "line"))
+	case tools.LSToolName:
+		metadata := tools.LSResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return plural(metadata.NumberOfFiles, "entry") + truncatedMark(metadata.Truncated)
+	case tools.GlobToolName:
+		metadata := tools.GlobResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return plural(metadata.NumberOfFiles, "file") + truncatedMark(metadata.Truncated)
+	case tools.GrepToolName:
+		metadata := tools.GrepResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return plural(metadata.NumberOfMatches, "match") + truncatedMark(metadata.Truncated)
+	case tools.ViewToolName:
+		metadata := tools.ViewResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return plural(countLines(metadata.Content), "line")
+	case tools.EditToolName, tools.MultiEditToolName, tools.NotebookEditToolName, tools.WriteToolName:
+		metadata := tools.EditResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return fmt.Sprintf("+%d -%d", metadata.Additions, metadata.Removals)
+	case tools.RunTestsToolName:
+		metadata := tools.RunTestsResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return fmt.Sprintf("%d passed, %d failed", metadata.Passed, metadata.Failed)
+	case tools.TodoToolName:
+		metadata := tools.TodoResponseMetadata{}
+		json.Unmarshal([]byte(response.Metadata), &metadata)
+		return plural(len(metadata.Todos), "todo")
+	}
+	if strings.TrimSpace(content) == "" {
+		return "no output"
+	}
+	return plural(countLines(content), "line")
+}
+
+func countLines(content string) int {
+	content = strings.TrimRight(content, "\n")
+	if content == "" {
+		return 0
+	}
+	return strings.Count(content, "\n") + 1
+}
+
+func firstLine(content string) string {
+	line, _, _ := strings.Cut(strings.TrimSpace(content), "\n")
+	return line
+}
+
+func plural(n int, word string) string {
+	if n == 1 {
+		return "1 " + word
+	}
+	switch {
+	case strings.HasSuffix(word, "ch"):
+		word += "es"
+	case strings.HasSuffix(word, "y"):
+		word = strings.TrimSuffix(word, "y") + "ies"
+	default:
+		word += "s"
+	}
+	return fmt.Sprintf("%d %s", n, word)
+}
+
+func truncatedMark(truncated bool) string {
+	if truncated {
+		return "+"
+	}
+	return ""
+}
+
 // Helper function to format the time difference between two Unix timestamps
+// formatMessageTime returns the time of day a message was sent, with the
+// date for messages from before today
+func formatMessageTime(createdAt int64) string {
+	sent := time.Unix(createdAt, 0)
+	now := time.Now()
+	if sent.Year() == now.Year() && sent.YearDay() == now.YearDay() {
+		return sent.Format("15:04")
+	}
+	return sent.Format("Jan 2 15:04")
+}
+
 func formatTimestampDiff(start, end int64) string {
 	diffSeconds := float64(end-start) / 1000.0 // Convert to seconds
 	if diffSeconds < 1 {
@@ -0,0 +1,80 @@
+package chat
+
+import (
+	"testing"
+	"time"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/llm/tools"
+	"github.com/zhenbah/cryoncode/internal/message"
+)
+
+func TestToolSummary(t *testing.T) {
+	tests := []struct {
+		name     string
+		call     message.ToolCall
+		response message.ToolResult
+		want     string
+	}{
+		{
+			name:     "ls counts the entries",
+			call:     message.ToolCall{Name: tools.LSToolName},
+			response: message.ToolResult{Metadata: `{"number_of_files":42}`},
+			want:     "42 entries",
+		},
+		{
+			name:     "bash shows the exit code and the lines",
+			call:     message.ToolCall{Name: tools.BashToolName},
+			response: message.ToolResult{Content: "a\nb\nc\n", Metadata: `{"exit_code":0}`},
+			want:     "exit 0, 3 lines",
+		},
+		{
+			name: "bash counts the full output",
+			call: message.ToolCall{Name: tools.BashToolName},
+			response: message.ToolResult{
+				Content:     "a",
+				FullContent: "a\nb",
+				Metadata:    `{"exit_code":1}`,
+			},
+			want: "exit 1, 2 lines",
+		},
+		{
+			name:     "grep marks truncated results",
+			call:     message.ToolCall{Name: tools.GrepToolName},
+			response: message.ToolResult{Metadata: `{"number_of_matches":1,"truncated":true}`},
+			want:     "1 match+",
+		},
+		{
+			name:     "edits show the changed lines",
+			call:     message.ToolCall{Name: tools.EditToolName},
+			response: message.ToolResult{Metadata: `{"additions":3,"removals":1}`},
+			want:     "+3 -1",
+		},
+		{
+			name:     "errors show their first line",
+			call:     message.ToolCall{Name: tools.ViewToolName},
+			response: message.ToolResult{Content: "file not found\ndetails", IsError: true},
+			want:     "error: file not found",
+		},
+		{
+			name:     "other tools count the lines",
+			call:     message.ToolCall{Name: tools.FetchToolName},
+			response: message.ToolResult{Content: "one line"},
+			want:     "1 line",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			assert.Equal(t, tt.want, toolSummary(tt.call, tt.response))
+		})
+	}
+}
+
+func TestFormatMessageTime(t *testing.T) {
+	now := time.Now()
+	today := time.Date(now.Year(), now.Month(), now.Day(), 9, 5, 0, 0, time.Local)
+	assert.Equal(t, "09:05", formatMessageTime(today.Unix()))
+
+	earlier := time.Date(2024, time.March, 7, 18, 30, 0, 0, time.Local)
+	assert.Equal(t, "Mar 7 18:30", formatMessageTime(earlier.Unix()))
+}
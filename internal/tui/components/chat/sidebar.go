@@ -8,6 +8,8 @@ import (
 
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
+	"github.com/charmbracelet/x/ansi"
+	zone "github.com/lrstanley/bubblezone"
 	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/diff"
 	"github.com/zhenbah/cryoncode/internal/history"
@@ -15,8 +17,12 @@ import (
 	"github.com/zhenbah/cryoncode/internal/session"
 	"github.com/zhenbah/cryoncode/internal/tui/styles"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
 
+// sidebarSessionZone marks the session in the sidebar so it can be clicked
+const sidebarSessionZone = "sidebar-session"
+
 type sidebarCmp struct {
 	width, height int
 	session       session.Session
@@ -64,6 +70,13 @@ func (m *sidebarCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				m.session = msg.Payload
 			}
 		}
+	case tea.MouseMsg:
+		if msg.Action != tea.MouseActionRelease || msg.Button != tea.MouseButtonLeft {
+			return m, nil
+		}
+		if z := zone.Get(sidebarSessionZone); z != nil && z.InBounds(msg) {
+			return m, util.CmdHandler(ShowSessionsMsg{})
+		}
 	case pubsub.Event[history.File]:
 		if msg.Payload.SessionID == m.session.ID {
 			// Process the individual file change instead of reloading all files
@@ -84,6 +97,21 @@ func (m *sidebarCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 func (m *sidebarCmp) View() string {
 	baseStyle := styles.BaseStyle()
 
+	sections := []string{
+		header(m.width),
+		" ",
+		zone.Mark(sidebarSessionZone, m.sessionSection()),
+	}
+	if len(m.session.Todos) > 0 {
+		sections = append(sections, " ", m.todosSection())
+	}
+	sections = append(sections,
+		" ",
+		lspsConfigured(m.width),
+		" ",
+		m.modifiedFiles(),
+	)
+
 	return baseStyle.
 		Width(m.width).
 		PaddingLeft(4).
@@ -92,13 +120,7 @@ func (m *sidebarCmp) View() string {
 		Render(
 			lipgloss.JoinVertical(
 				lipgloss.Top,
-				header(m.width),
-				" ",
-				m.sessionSection(),
-				" ",
-				lspsConfigured(m.width),
-				" ",
-				m.modifiedFiles(),
+				sections...,
 			),
 		)
 }
@@ -124,6 +146,60 @@ func (m *sidebarCmp) sessionSection() string {
 	)
 }
 
+// todosSection renders the task list the agent keeps for the session
+func (m *sidebarCmp) todosSection() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	completed := 0
+	for _, todo := range m.session.Todos {
+		if todo.Status == session.TodoCompleted {
+			completed++
+		}
+	}
+
+	title := baseStyle.
+		Width(m.width).
+		Foreground(t.Primary()).
+		Bold(true).
+		Render(fmt.Sprintf("Plan (%d/%d):", completed, len(m.session.Todos)))
+
+	var todoViews []string
+	for _, todo := range m.session.Todos {
+		var item string
+		switch todo.Status {
+		case session.TodoCompleted:
+			item = baseStyle.
+				Foreground(t.TextMuted()).
+				Strikethrough(true).
+				Render("✓ " + todo.Content)
+		case session.TodoInProgress:
+			item = baseStyle.
+				Foreground(t.Primary()).
+				Bold(true).
+				Render("→ " + todo.Content)
+		default:
+			item = baseStyle.
+				Foreground(t.Text()).
+				Render("○ " + todo.Content)
+		}
+		todoViews = append(todoViews, baseStyle.Width(m.width).Render(ansi.Truncate(item, m.width, "…")))
+	}
+
+	return baseStyle.
+		Width(m.width).
+		Render(
+			lipgloss.JoinVertical(
+				lipgloss.Top,
+				title,
+				lipgloss.JoinVertical(
+					lipgloss.Left,
+					todoViews...,
+				),
+			),
+		)
+}
+
 func (m *sidebarCmp) modifiedFile(filePath string, additions, removals int) string {
 	t := theme.CurrentTheme()
 	baseStyle := styles.BaseStyle()
@@ -0,0 +1,88 @@
+package chat
+
+import (
+	"strings"
+	"unicode"
+
+	"github.com/charmbracelet/bubbles/key"
+)
+
+// SlashCommand is a command typed into the editor instead of a message.
+type SlashCommand struct {
+	Name        string
+	Usage       string
+	Description string
+}
+
+// SlashCommands are the commands the editor recognizes. Text starting with a
+// slash that isn't one of them is sent as a message.
+var SlashCommands = []SlashCommand{
+	{Name: "new", Usage: "/new [title]", Description: "start a new session"},
+	{Name: "clear", Usage: "/clear", Description: "clear the conversation and start over"},
+	{Name: "model", Usage: "/model [name]", Description: "switch the model, or choose one from a list"},
+	{Name: "agent", Usage: "/agent [name]", Description: "switch the agent running the session, or list the agents"},
+	{Name: "compact", Usage: "/compact", Description: "summarize the older turns of the session"},
+	{Name: "sessions", Usage: "/sessions [tag]", Description: "switch to another session, optionally one with the tag"},
+	{Name: "tag", Usage: "/tag [tags]", Description: "tag the session, -tag removes a tag"},
+	{Name: "prune", Usage: "/prune [age]", Description: "archive the sessions inactive for 30d or the given age"},
+	{Name: "search", Usage: "/search [words]", Description: "find words in the messages of all sessions"},
+	{Name: "undo", Usage: "/undo", Description: "revert file changes since a chosen prompt"},
+	{Name: "checkpoints", Usage: "/checkpoints", Description: "rewind the conversation and files to an earlier turn"},
+	{Name: "fork", Usage: "/fork", Description: "continue a copy of the session from a chosen prompt"},
+	{Name: "retry", Usage: "/retry [model]", Description: "send the last prompt again, optionally to another model"},
+	{Name: "edit", Usage: "/edit", Description: "edit a prompt and run the session again from it"},
+	{Name: "delete", Usage: "/delete", Description: "delete a prompt or response from the session"},
+	{Name: "export", Usage: "/export [file]", Description: "copy the session as Markdown, or write it to a file"},
+	{Name: "init", Usage: "/init", Description: "ask the model to write an AGENTS.md for the project"},
+	{Name: "help", Usage: "/help", Description: "show the keyboard shortcuts and commands"},
+}
+
+// SlashCommandMsg is sent when a slash command is submitted in the editor.
+type SlashCommandMsg struct {
+	Name string
+	Args string
+}
+
+// ParseSlashCommand returns the command when the text is one of the
+// SlashCommands. Otherwise it returns the text to send as a message. A double
+// slash in front of a command is sent as a single one, so that a message can
+// still start with the name of a command.
+func ParseSlashCommand(text string) (SlashCommandMsg, string, bool) {
+	trimmed := strings.TrimSpace(text)
+	if strings.HasPrefix(trimmed, "//") {
+		if _, _, ok := ParseSlashCommand(trimmed[1:]); ok {
+			return SlashCommandMsg{}, trimmed[1:], false
+		}
+		return SlashCommandMsg{}, text, false
+	}
+	if !strings.HasPrefix(trimmed, "/") {
+		return SlashCommandMsg{}, text, false
+	}
+
+	name, args := trimmed[1:], ""
+	if i := strings.IndexFunc(name, unicode.IsSpace); i >= 0 {
+		name, args = name[:i], name[i:]
+	}
+	for _, cmd := range SlashCommands {
+		if strings.EqualFold(cmd.Name, name) {
+			return SlashCommandMsg{
+				Name: cmd.Name,
+				Args: strings.TrimSpace(args),
+			}, "", true
+		}
+	}
+	return SlashCommandMsg{}, text, false
+}
+
+// slashCommandBindings lists the commands in the help dialog. Their keys are
+// never pressed as such, they only keep the entries apart.
+func slashCommandBindings() []key.Binding {
+	bindings := make([]key.Binding, 0, len(SlashCommands))
+	for _, cmd := range SlashCommands {
+		bindings = append(bindings, key.NewBinding(
+			key.WithKeys("/"+cmd.Name),
+			key.WithHelp(cmd.Usage, cmd.Description),
+		))
+	}
+	return bindings
+}
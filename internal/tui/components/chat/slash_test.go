@@ -0,0 +1,65 @@
+package chat
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestParseSlashCommand(t *testing.T) {
+	tests := []struct {
+		name   string
+		text   string
+		want   SlashCommandMsg
+		wantOk bool
+		send   string
+	}{
+		{
+			name:   "command without arguments",
+			text:   "/compact",
+			want:   SlashCommandMsg{Name: "compact"},
+			wantOk: true,
+		},
+		{
+			name:   "command with arguments",
+			text:   "  /model  claude-3.7-sonnet \n",
+			want:   SlashCommandMsg{Name: "model", Args: "claude-3.7-sonnet"},
+			wantOk: true,
+		},
+		{
+			name:   "command names ignore case",
+			text:   "/New Release notes",
+			want:   SlashCommandMsg{Name: "new", Args: "Release notes"},
+			wantOk: true,
+		},
+		{
+			name: "unknown commands are messages",
+			text: "/usr/bin is missing",
+			send: "/usr/bin is missing",
+		},
+		{
+			name: "plain messages are left as they are",
+			text: "what does /help print?",
+			send: "what does /help print?",
+		},
+		{
+			name: "double slash escapes a command",
+			text: "//help me with this",
+			send: "/help me with this",
+		},
+		{
+			name: "double slashes without a command are left as they are",
+			text: "// TODO: explain this",
+			send: "// TODO: explain this",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, send, ok := ParseSlashCommand(tt.text)
+			assert.Equal(t, tt.wantOk, ok)
+			assert.Equal(t, tt.want, got)
+			assert.Equal(t, tt.send, send)
+		})
+	}
+}
@@ -0,0 +1,201 @@
+package chat
+
+import (
+	"slices"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// VimKeyMaps are the normal mode keys of the editor when the vim keybindings
+// are enabled.
+type VimKeyMaps struct {
+	Left            key.Binding
+	Right           key.Binding
+	Up              key.Binding
+	Down            key.Binding
+	WordForward     key.Binding
+	WordBackward    key.Binding
+	LineStart       key.Binding
+	LineEnd         key.Binding
+	DeleteCharacter key.Binding
+	DeleteLine      key.Binding
+	Insert          key.Binding
+	InsertLineStart key.Binding
+	Append          key.Binding
+	AppendLineEnd   key.Binding
+	OpenLine        key.Binding
+}
+
+var vimKeyMaps = VimKeyMaps{
+	Left: key.NewBinding(
+		key.WithKeys("h"),
+		key.WithHelp("h", "cursor left (normal mode)"),
+	),
+	Right: key.NewBinding(
+		key.WithKeys("l"),
+		key.WithHelp("l", "cursor right (normal mode)"),
+	),
+	Up: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "cursor up (normal mode)"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "cursor down (normal mode)"),
+	),
+	WordForward: key.NewBinding(
+		key.WithKeys("w"),
+		key.WithHelp("w", "next word (normal mode)"),
+	),
+	WordBackward: key.NewBinding(
+		key.WithKeys("b"),
+		key.WithHelp("b", "previous word (normal mode)"),
+	),
+	LineStart: key.NewBinding(
+		key.WithKeys("0"),
+		key.WithHelp("0", "start of line (normal mode)"),
+	),
+	LineEnd: key.NewBinding(
+		key.WithKeys("$"),
+		key.WithHelp("$", "end of line (normal mode)"),
+	),
+	DeleteCharacter: key.NewBinding(
+		key.WithKeys("x"),
+		key.WithHelp("x", "delete character (normal mode)"),
+	),
+	DeleteLine: key.NewBinding(
+		key.WithKeys("d"),
+		key.WithHelp("dd", "delete line (normal mode)"),
+	),
+	Insert: key.NewBinding(
+		key.WithKeys("i"),
+		key.WithHelp("i", "insert before cursor (normal mode)"),
+	),
+	InsertLineStart: key.NewBinding(
+		key.WithKeys("I"),
+		key.WithHelp("I", "insert at start of line (normal mode)"),
+	),
+	Append: key.NewBinding(
+		key.WithKeys("a"),
+		key.WithHelp("a", "insert after cursor (normal mode)"),
+	),
+	AppendLineEnd: key.NewBinding(
+		key.WithKeys("A"),
+		key.WithHelp("A", "insert at end of line (normal mode)"),
+	),
+	OpenLine: key.NewBinding(
+		key.WithKeys("o"),
+		key.WithHelp("o", "insert on a new line (normal mode)"),
+	),
+}
+
+// setInsertMode switches between the insert and normal modes of the vim
+// keybindings.
+func (m *editorCmp) setInsertMode(insert bool) tea.Cmd {
+	m.normalMode = !insert
+	m.pendingKey = ""
+	return util.CmdHandler(EditorInsertModeMsg(insert))
+}
+
+// updateNormalMode handles a key press in normal mode. Keys it doesn't know
+// are reported as not handled, so that e.g. enter still sends the message.
+func (m *editorCmp) updateNormalMode(msg tea.KeyMsg) (tea.Cmd, bool) {
+	pendingKey := m.pendingKey
+	m.pendingKey = ""
+
+	line, column := m.cursorLine()
+	switch {
+	case key.Matches(msg, vimKeyMaps.Insert):
+		return m.setInsertMode(true), true
+	case key.Matches(msg, vimKeyMaps.InsertLineStart):
+		m.textarea.CursorStart()
+		return m.setInsertMode(true), true
+	case key.Matches(msg, vimKeyMaps.Append):
+		if column < len(line) {
+			m.moveCursor(tea.KeyMsg{Type: tea.KeyRight})
+		}
+		return m.setInsertMode(true), true
+	case key.Matches(msg, vimKeyMaps.AppendLineEnd):
+		m.textarea.CursorEnd()
+		return m.setInsertMode(true), true
+	case key.Matches(msg, vimKeyMaps.OpenLine):
+		m.textarea.CursorEnd()
+		m.textarea.InsertRune('\n')
+		return m.setInsertMode(true), true
+	case key.Matches(msg, vimKeyMaps.Left):
+		if column > 0 {
+			m.moveCursor(tea.KeyMsg{Type: tea.KeyLeft})
+		}
+	case key.Matches(msg, vimKeyMaps.Right):
+		if column < len(line)-1 {
+			m.moveCursor(tea.KeyMsg{Type: tea.KeyRight})
+		}
+	case key.Matches(msg, vimKeyMaps.Up):
+		m.textarea.CursorUp()
+	case key.Matches(msg, vimKeyMaps.Down):
+		m.textarea.CursorDown()
+	case key.Matches(msg, vimKeyMaps.WordForward):
+		m.moveCursor(tea.KeyMsg{Type: tea.KeyRight, Alt: true})
+	case key.Matches(msg, vimKeyMaps.WordBackward):
+		m.moveCursor(tea.KeyMsg{Type: tea.KeyLeft, Alt: true})
+	case key.Matches(msg, vimKeyMaps.LineStart):
+		m.textarea.CursorStart()
+	case key.Matches(msg, vimKeyMaps.LineEnd):
+		m.textarea.CursorEnd()
+	case key.Matches(msg, vimKeyMaps.DeleteCharacter):
+		// Unlike the delete key, x never joins the next line
+		if column < len(line) {
+			m.moveCursor(tea.KeyMsg{Type: tea.KeyDelete})
+		}
+	case key.Matches(msg, vimKeyMaps.DeleteLine):
+		if pendingKey == "d" {
+			m.deleteLine()
+		} else {
+			m.pendingKey = "d"
+		}
+	default:
+		// Typing doesn't change the text outside of insert mode
+		return nil, msg.Type == tea.KeyRunes || msg.Type == tea.KeySpace || msg.Type == tea.KeyBackspace
+	}
+	return nil, true
+}
+
+// moveCursor lets the textarea handle one of its own keys, used for the
+// motions it has no method for.
+func (m *editorCmp) moveCursor(msg tea.KeyMsg) {
+	m.textarea, _ = m.textarea.Update(msg)
+}
+
+// cursorLine returns the line under the cursor and the column of the cursor
+// in it.
+func (m *editorCmp) cursorLine() ([]rune, int) {
+	lines := strings.Split(m.textarea.Value(), "\n")
+	row := m.textarea.Line()
+	if row >= len(lines) {
+		return nil, 0
+	}
+	info := m.textarea.LineInfo()
+	return []rune(lines[row]), info.StartColumn + info.ColumnOffset
+}
+
+// deleteLine removes the line under the cursor and moves the cursor to the
+// start of the line that takes its place.
+func (m *editorCmp) deleteLine() {
+	lines := strings.Split(m.textarea.Value(), "\n")
+	row := m.textarea.Line()
+	if row >= len(lines) {
+		return
+	}
+	lines = slices.Delete(lines, row, row+1)
+	m.textarea.SetValue(strings.Join(lines, "\n"))
+
+	// SetValue leaves the cursor at the end of the input
+	row = min(row, max(len(lines)-1, 0))
+	for m.textarea.Line() > row {
+		m.textarea.CursorUp()
+	}
+	m.textarea.CursorStart()
+}
@@ -2,12 +2,14 @@ package core
 
 import (
 	"fmt"
+	"os"
+	"path/filepath"
 	"strings"
-	"time"
 
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
 	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/agent"
 	"github.com/zhenbah/cryoncode/internal/llm/models"
 	"github.com/zhenbah/cryoncode/internal/lsp"
 	"github.com/zhenbah/cryoncode/internal/lsp/protocol"
@@ -16,7 +18,6 @@ import (
 	"github.com/zhenbah/cryoncode/internal/tui/components/chat"
 	"github.com/zhenbah/cryoncode/internal/tui/styles"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
-	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
 
 type StatusCmp interface {
@@ -24,20 +25,12 @@ type StatusCmp interface {
 }
 
 type statusCmp struct {
-	info       util.InfoMsg
 	width      int
-	messageTTL time.Duration
 	lspClients map[string]*lsp.Client
+	agent      agent.Service
 	session    session.Session
 }
 
-// clearMessageCmd is a command that clears status messages after a timeout
-func (m statusCmp) clearMessageCmd(ttl time.Duration) tea.Cmd {
-	return tea.Tick(ttl, func(time.Time) tea.Msg {
-		return util.ClearStatusMsg{}
-	})
-}
-
 func (m statusCmp) Init() tea.Cmd {
 	return nil
 }
@@ -57,15 +50,6 @@ func (m statusCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				m.session = msg.Payload
 			}
 		}
-	case util.InfoMsg:
-		m.info = msg
-		ttl := msg.TTL
-		if ttl == 0 {
-			ttl = m.messageTTL
-		}
-		return m, m.clearMessageCmd(ttl)
-	case util.ClearStatusMsg:
-		m.info = util.InfoMsg{}
 	}
 	return m, nil
 }
@@ -139,46 +123,58 @@ func (m statusCmp) View() string {
 		status += tokensStyle.Render(tokens)
 	}
 
+	if m.session.ID != "" && m.agent.IsSessionBusy(m.session.ID) {
+		working := styles.Padded().
+			Background(t.Primary()).
+			Foreground(t.Background()).
+			Render(fmt.Sprintf("%s working", styles.SpinnerIcon))
+		tokenInfoWidth += lipgloss.Width(working)
+		status += working
+	}
+
 	diagnostics := styles.Padded().
 		Background(t.BackgroundDarker()).
 		Render(m.projectDiagnostics())
 
 	availableWidht := max(0, m.width-lipgloss.Width(helpWidget)-lipgloss.Width(m.model())-lipgloss.Width(diagnostics)-tokenInfoWidth)
 
-	if m.info.Msg != "" {
-		infoStyle := styles.Padded().
-			Foreground(t.Background()).
-			Width(availableWidht)
-
-		switch m.info.Type {
-		case util.InfoTypeInfo:
-			infoStyle = infoStyle.Background(t.Info())
-		case util.InfoTypeWarn:
-			infoStyle = infoStyle.Background(t.Warning())
-		case util.InfoTypeError:
-			infoStyle = infoStyle.Background(t.Error())
-		}
-
-		infoWidth := availableWidht - 10
-		// Truncate message if it's longer than available width
-		msg := m.info.Msg
-		if len(msg) > infoWidth && infoWidth > 0 {
-			msg = msg[:infoWidth] + "..."
-		}
-		status += infoStyle.Render(msg)
-	} else {
-		status += styles.Padded().
-			Foreground(t.Text()).
-			Background(t.BackgroundSecondary()).
-			Width(availableWidht).
-			Render("")
+	// The working directory is left out when the terminal is too narrow
+	workingDir := styles.Padded().
+		Background(t.BackgroundDarker()).
+		Foreground(t.TextMuted()).
+		Render(shortWorkingDirectory())
+	if availableWidht-lipgloss.Width(workingDir) < 30 {
+		workingDir = ""
 	}
+	availableWidht -= lipgloss.Width(workingDir)
+
+	status += styles.Padded().
+		Foreground(t.Text()).
+		Background(t.BackgroundSecondary()).
+		Width(availableWidht).
+		Render("")
 
 	status += diagnostics
+	status += workingDir
 	status += m.model()
 	return status
 }
 
+// shortWorkingDirectory returns the working directory with the home directory
+// shortened to ~
+func shortWorkingDirectory() string {
+	cwd := config.WorkingDirectory()
+	if home, err := os.UserHomeDir(); err == nil && home != "" {
+		if rel, err := filepath.Rel(home, cwd); err == nil && !strings.HasPrefix(rel, "..") {
+			if rel == "." {
+				return "~"
+			}
+			return filepath.Join("~", rel)
+		}
+	}
+	return cwd
+}
+
 func (m *statusCmp) projectDiagnostics() string {
 	t := theme.CurrentTheme()
 
@@ -283,11 +279,11 @@ func (m statusCmp) model() string {
 		Render(model.Name)
 }
 
-func NewStatusCmp(lspClients map[string]*lsp.Client) StatusCmp {
+func NewStatusCmp(lspClients map[string]*lsp.Client, coderAgent agent.Service) StatusCmp {
 	helpWidget = getHelpWidget()
 
 	return &statusCmp{
-		messageTTL: 10 * time.Second,
 		lspClients: lspClients,
+		agent:      coderAgent,
 	}
 }
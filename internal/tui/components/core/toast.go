@@ -0,0 +1,141 @@
+package core
+
+import (
+	"time"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+const (
+	// maxToasts is the number of notifications shown at once, older ones are
+	// dismissed early to make room
+	maxToasts = 4
+
+	infoToastTTL  = 4 * time.Second
+	errorToastTTL = 10 * time.Second
+)
+
+type ToastsCmp interface {
+	tea.Model
+	// Empty reports whether there is no notification to show
+	Empty() bool
+}
+
+type toast struct {
+	id   int
+	info util.InfoMsg
+}
+
+type dismissToastMsg struct {
+	id int
+}
+
+// toastsCmp shows notifications stacked in a corner of the screen until they
+// expire, so that several of them can be read even when they arrive together.
+type toastsCmp struct {
+	toasts []toast
+	nextID int
+	width  int
+}
+
+func (m *toastsCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (m *toastsCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.WindowSizeMsg:
+		m.width = msg.Width
+	case util.InfoMsg:
+		if msg.Msg == "" {
+			return m, nil
+		}
+		// The same notification again only moves it to the bottom
+		m.remove(func(t toast) bool {
+			return t.info.Type == msg.Type && t.info.Msg == msg.Msg
+		})
+		if len(m.toasts) >= maxToasts {
+			m.toasts = m.toasts[len(m.toasts)-maxToasts+1:]
+		}
+		m.nextID++
+		id := m.nextID
+		m.toasts = append(m.toasts, toast{id: id, info: msg})
+		return m, tea.Tick(toastTTL(msg), func(time.Time) tea.Msg {
+			return dismissToastMsg{id: id}
+		})
+	case dismissToastMsg:
+		m.remove(func(t toast) bool {
+			return t.id == msg.id
+		})
+	}
+	return m, nil
+}
+
+func (m *toastsCmp) remove(match func(toast) bool) {
+	toasts := m.toasts[:0]
+	for _, t := range m.toasts {
+		if !match(t) {
+			toasts = append(toasts, t)
+		}
+	}
+	m.toasts = toasts
+}
+
+// toastTTL returns how long the notification stays, errors and warnings stay
+// longer so they can be read
+func toastTTL(info util.InfoMsg) time.Duration {
+	switch {
+	case info.TTL > 0:
+		return info.TTL
+	case info.Type == util.InfoTypeInfo:
+		return infoToastTTL
+	default:
+		return errorToastTTL
+	}
+}
+
+func (m *toastsCmp) Empty() bool {
+	return len(m.toasts) == 0
+}
+
+func (m *toastsCmp) View() string {
+	if len(m.toasts) == 0 {
+		return ""
+	}
+	t := theme.CurrentTheme()
+	width := max(20, min(60, m.width/3))
+
+	views := make([]string, 0, len(m.toasts))
+	for _, toast := range m.toasts {
+		icon, color := styles.InfoIcon, t.Info()
+		switch toast.info.Type {
+		case util.InfoTypeWarn:
+			icon, color = styles.WarningIcon, t.Warning()
+		case util.InfoTypeError:
+			icon, color = styles.ErrorIcon, t.Error()
+		}
+		text := toast.info.Msg
+		if icon != "" {
+			text = icon + " " + text
+		}
+		views = append(views, lipgloss.NewStyle().
+			Width(width).
+			MaxHeight(6).
+			Padding(0, 1).
+			Background(t.BackgroundSecondary()).
+			Foreground(t.Text()).
+			Border(lipgloss.ThickBorder(), false, false, false, true).
+			BorderForeground(color).
+			BorderBackground(t.BackgroundSecondary()).
+			Render(text))
+	}
+	return lipgloss.JoinVertical(lipgloss.Right, views...)
+}
+
+func NewToastsCmp() ToastsCmp {
+	return &toastsCmp{}
+}
@@ -0,0 +1,49 @@
+package core
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+func TestToasts(t *testing.T) {
+	messages := func(m *toastsCmp) []string {
+		var msgs []string
+		for _, toast := range m.toasts {
+			msgs = append(msgs, toast.info.Msg)
+		}
+		return msgs
+	}
+
+	t.Run("keeps the latest notifications", func(t *testing.T) {
+		m := &toastsCmp{}
+		for i := range maxToasts + 2 {
+			m.Update(util.InfoMsg{Msg: fmt.Sprintf("toast %d", i)})
+		}
+		assert.Equal(t, []string{"toast 2", "toast 3", "toast 4", "toast 5"}, messages(m))
+	})
+
+	t.Run("shows a repeated notification once", func(t *testing.T) {
+		m := &toastsCmp{}
+		m.Update(util.InfoMsg{Type: util.InfoTypeError, Msg: "failed"})
+		m.Update(util.InfoMsg{Msg: "saved"})
+		m.Update(util.InfoMsg{Type: util.InfoTypeError, Msg: "failed"})
+		assert.Equal(t, []string{"saved", "failed"}, messages(m))
+	})
+
+	t.Run("dismisses expired notifications", func(t *testing.T) {
+		m := &toastsCmp{}
+		m.Update(util.InfoMsg{Msg: "first"})
+		m.Update(util.InfoMsg{Msg: "second"})
+		m.Update(dismissToastMsg{id: m.toasts[0].id})
+		assert.Equal(t, []string{"second"}, messages(m))
+		assert.False(t, m.Empty())
+	})
+
+	t.Run("keeps errors on screen longer", func(t *testing.T) {
+		assert.Greater(t, toastTTL(util.InfoMsg{Type: util.InfoTypeError}), toastTTL(util.InfoMsg{}))
+		assert.Equal(t, errorToastTTL, toastTTL(util.InfoMsg{Type: util.InfoTypeWarn}))
+	})
+}
@@ -0,0 +1,210 @@
+package dialog
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/charmbracelet/x/ansi"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// RevertFileMsg is sent when a changed file is selected to be reverted
+type RevertFileMsg struct {
+	Path string
+}
+
+// CloseChangesDialogMsg is sent when the changed files dialog is closed
+type CloseChangesDialogMsg struct{}
+
+// ChangesDialog interface for the dialog listing the files changed in the
+// session
+type ChangesDialog interface {
+	tea.Model
+	layout.Bindings
+	SetFiles(files []snapshot.ChangedFile)
+}
+
+type changesDialogCmp struct {
+	files       []snapshot.ChangedFile
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type changesKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Revert key.Binding
+	Escape key.Binding
+	J      key.Binding
+	K      key.Binding
+}
+
+var changesKeys = changesKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous file"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next file"),
+	),
+	Revert: key.NewBinding(
+		key.WithKeys("r"),
+		key.WithHelp("r", "revert file"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+	J: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "next file"),
+	),
+	K: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "previous file"),
+	),
+}
+
+func (c *changesDialogCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (c *changesDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, changesKeys.Up) || key.Matches(msg, changesKeys.K):
+			if c.selectedIdx > 0 {
+				c.selectedIdx--
+			}
+			return c, nil
+		case key.Matches(msg, changesKeys.Down) || key.Matches(msg, changesKeys.J):
+			if c.selectedIdx < len(c.files)-1 {
+				c.selectedIdx++
+			}
+			return c, nil
+		case key.Matches(msg, changesKeys.Revert):
+			if len(c.files) > 0 {
+				return c, util.CmdHandler(RevertFileMsg{
+					Path: c.files[c.selectedIdx].Path,
+				})
+			}
+		case key.Matches(msg, changesKeys.Escape):
+			return c, util.CmdHandler(CloseChangesDialogMsg{})
+		}
+	case tea.WindowSizeMsg:
+		c.width = msg.Width
+		c.height = msg.Height
+	}
+	return c, nil
+}
+
+func (c *changesDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	if len(c.files) == 0 {
+		return baseStyle.Padding(1, 2).
+			Border(lipgloss.RoundedBorder()).
+			BorderBackground(t.Background()).
+			BorderForeground(t.TextMuted()).
+			Width(40).
+			Render("No files changed")
+	}
+
+	maxWidth := max(40, min(80, c.width-15))
+	maxVisibleFiles := min(10, len(c.files))
+
+	// Keep the selected file visible, centering it when possible
+	startIdx := 0
+	if len(c.files) > maxVisibleFiles {
+		halfVisible := maxVisibleFiles / 2
+		if c.selectedIdx >= halfVisible && c.selectedIdx < len(c.files)-halfVisible {
+			startIdx = c.selectedIdx - halfVisible
+		} else if c.selectedIdx >= len(c.files)-halfVisible {
+			startIdx = len(c.files) - maxVisibleFiles
+		}
+	}
+	endIdx := min(startIdx+maxVisibleFiles, len(c.files))
+
+	fileItems := make([]string, 0, maxVisibleFiles)
+	for i := startIdx; i < endIdx; i++ {
+		file := c.files[i]
+		stats := fmt.Sprintf("+%d -%d", file.Additions, file.Removals)
+		switch {
+		case file.Created && !file.Deleted:
+			stats = "new " + stats
+		case file.Deleted && !file.Created:
+			stats = "deleted"
+		}
+
+		path := strings.TrimPrefix(strings.TrimPrefix(file.Path, config.WorkingDirectory()), "/")
+		pathWidth := maxWidth - lipgloss.Width(stats) - 3
+		if width := ansi.StringWidth(path); pathWidth > 1 && width > pathWidth {
+			path = ansi.TruncateLeft(path, width-pathWidth+1, "…")
+		}
+
+		itemStyle := baseStyle.Width(maxWidth)
+		statsStyle := baseStyle.Foreground(t.TextMuted())
+		if i == c.selectedIdx {
+			itemStyle = itemStyle.
+				Background(t.Primary()).
+				Foreground(t.Background()).
+				Bold(true)
+			statsStyle = statsStyle.
+				Background(t.Primary()).
+				Foreground(t.Background())
+		}
+		line := path + strings.Repeat(" ", max(1, maxWidth-2-lipgloss.Width(path)-len(stats))) + statsStyle.Render(stats)
+		fileItems = append(fileItems, itemStyle.Padding(0, 1).Render(line))
+	}
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Files Changed")
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, fileItems...)),
+		baseStyle.Width(maxWidth).Render(""),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (c *changesDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(changesKeys)
+}
+
+// SetFiles sets the changed files, keeping the selection in range
+func (c *changesDialogCmp) SetFiles(files []snapshot.ChangedFile) {
+	c.files = files
+	c.selectedIdx = max(0, min(c.selectedIdx, len(files)-1))
+}
+
+// NewChangesDialogCmp creates a new changed files dialog
+func NewChangesDialogCmp() ChangesDialog {
+	return &changesDialogCmp{
+		files: []snapshot.ChangedFile{},
+	}
+}
@@ -0,0 +1,220 @@
+package dialog
+
+import (
+	"fmt"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/snapshot"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// RestoreCheckpointMsg is sent when the checkpoint to go back to is selected
+type RestoreCheckpointMsg struct {
+	CheckpointID string
+}
+
+// CloseCheckpointDialogMsg is sent when the checkpoint dialog is closed
+type CloseCheckpointDialogMsg struct{}
+
+// CheckpointDialog interface for the dialog that picks the checkpoint to
+// rewind the session to
+type CheckpointDialog interface {
+	tea.Model
+	layout.Bindings
+	SetCheckpoints(checkpoints []snapshot.Checkpoint, messages []message.Message)
+}
+
+// checkpointItem is a checkpoint with the prompt of its turn
+type checkpointItem struct {
+	checkpoint snapshot.Checkpoint
+	prompt     message.Message
+}
+
+type checkpointDialogCmp struct {
+	items       []checkpointItem
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type checkpointKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+	J      key.Binding
+	K      key.Binding
+}
+
+var checkpointKeys = checkpointKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous checkpoint"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next checkpoint"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "restore checkpoint"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+	J: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "next checkpoint"),
+	),
+	K: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "previous checkpoint"),
+	),
+}
+
+func (c *checkpointDialogCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (c *checkpointDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, checkpointKeys.Up) || key.Matches(msg, checkpointKeys.K):
+			if c.selectedIdx > 0 {
+				c.selectedIdx--
+			}
+			return c, nil
+		case key.Matches(msg, checkpointKeys.Down) || key.Matches(msg, checkpointKeys.J):
+			if c.selectedIdx < len(c.items)-1 {
+				c.selectedIdx++
+			}
+			return c, nil
+		case key.Matches(msg, checkpointKeys.Enter):
+			if len(c.items) > 0 {
+				return c, util.CmdHandler(RestoreCheckpointMsg{
+					CheckpointID: c.items[c.selectedIdx].checkpoint.ID,
+				})
+			}
+		case key.Matches(msg, checkpointKeys.Escape):
+			return c, util.CmdHandler(CloseCheckpointDialogMsg{})
+		}
+	case tea.WindowSizeMsg:
+		c.width = msg.Width
+		c.height = msg.Height
+	}
+	return c, nil
+}
+
+func (c *checkpointDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	if len(c.items) == 0 {
+		return baseStyle.Padding(1, 2).
+			Border(lipgloss.RoundedBorder()).
+			BorderBackground(t.Background()).
+			BorderForeground(t.TextMuted()).
+			Width(40).
+			Render("No checkpoints in this session")
+	}
+
+	maxWidth := max(40, min(80, c.width-15))
+	maxVisibleItems := min(10, len(c.items))
+
+	// Keep the selected checkpoint visible, centering it when possible
+	startIdx := 0
+	if len(c.items) > maxVisibleItems {
+		halfVisible := maxVisibleItems / 2
+		if c.selectedIdx >= halfVisible && c.selectedIdx < len(c.items)-halfVisible {
+			startIdx = c.selectedIdx - halfVisible
+		} else if c.selectedIdx >= len(c.items)-halfVisible {
+			startIdx = len(c.items) - maxVisibleItems
+		}
+	}
+	endIdx := min(startIdx+maxVisibleItems, len(c.items))
+
+	items := make([]string, 0, maxVisibleItems)
+	for i := startIdx; i < endIdx; i++ {
+		item := c.items[i]
+		number := fmt.Sprintf("%d. ", i+1)
+		files := ""
+		if n := len(item.checkpoint.Files); n > 0 {
+			files = fmt.Sprintf(" · %d file(s)", n)
+		}
+		width := maxWidth - 2 - len(number) - len(files)
+
+		itemStyle := baseStyle.Width(maxWidth)
+		filesStyle := baseStyle.Foreground(t.TextMuted())
+		if i == c.selectedIdx {
+			itemStyle = itemStyle.
+				Background(t.Primary()).
+				Foreground(t.Background()).
+				Bold(true)
+			filesStyle = filesStyle.
+				Background(t.Primary()).
+				Foreground(t.Background())
+		}
+		text := number + promptSummary(item.prompt, width) + filesStyle.Render(files)
+		items = append(items, itemStyle.Padding(0, 1).Render(text))
+	}
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Restore Checkpoint")
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, items...)),
+		baseStyle.Width(maxWidth).Render(""),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (c *checkpointDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(checkpointKeys)
+}
+
+// SetCheckpoints sets the checkpoints to choose from, numbered in the order
+// of the turns, and selects the one before the latest since restoring the
+// latest changes nothing
+func (c *checkpointDialogCmp) SetCheckpoints(checkpoints []snapshot.Checkpoint, messages []message.Message) {
+	prompts := make(map[string]message.Message, len(messages))
+	for _, msg := range messages {
+		if msg.Role == message.User {
+			prompts[msg.ID] = msg
+		}
+	}
+	c.items = c.items[:0]
+	for _, checkpoint := range checkpoints {
+		if prompt, ok := prompts[checkpoint.MessageID]; ok {
+			c.items = append(c.items, checkpointItem{checkpoint: checkpoint, prompt: prompt})
+		}
+	}
+	c.selectedIdx = max(0, len(c.items)-2)
+}
+
+// NewCheckpointDialogCmp creates a new checkpoint dialog
+func NewCheckpointDialogCmp() CheckpointDialog {
+	return &checkpointDialogCmp{
+		items: []checkpointItem{},
+	}
+}
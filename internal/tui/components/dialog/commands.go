@@ -1,9 +1,14 @@
 package dialog
 
 import (
+	"sort"
+	"strings"
+
 	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
+	"github.com/lithammer/fuzzysearch/fuzzy"
 	utilComponents "github.com/zhenbah/cryoncode/internal/tui/components/util"
 	"github.com/zhenbah/cryoncode/internal/tui/layout"
 	"github.com/zhenbah/cryoncode/internal/tui/styles"
@@ -65,6 +70,10 @@ type commandDialogCmp struct {
 	listView utilComponents.SimpleList[Command]
 	width    int
 	height   int
+	// commands are all the commands, the list only shows those matching
+	// the filter
+	commands []Command
+	filter   textinput.Model
 }
 
 type commandKeyMap struct {
@@ -101,6 +110,14 @@ func (c *commandDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			}
 		case key.Matches(msg, commandKeys.Escape):
 			return c, util.CmdHandler(CloseCommandDialogMsg{})
+		case msg.Type == tea.KeyRunes || msg.Type == tea.KeySpace || msg.Type == tea.KeyBackspace:
+			var cmd tea.Cmd
+			query := c.filter.Value()
+			c.filter, cmd = c.filter.Update(msg)
+			if c.filter.Value() != query {
+				c.listView.SetItems(FilterCommands(c.commands, c.filter.Value()))
+			}
+			return c, cmd
 		}
 	case tea.WindowSizeMsg:
 		c.width = msg.Width
@@ -120,15 +137,15 @@ func (c *commandDialogCmp) View() string {
 
 	maxWidth := 40
 
-	commands := c.listView.GetItems()
+	commands := c.commands
 
 	for _, cmd := range commands {
-		if len(cmd.Title) > maxWidth-4 {
-			maxWidth = len(cmd.Title) + 4
+		if lipgloss.Width(cmd.Title) > maxWidth-4 {
+			maxWidth = lipgloss.Width(cmd.Title) + 4
 		}
 		if cmd.Description != "" {
-			if len(cmd.Description) > maxWidth-4 {
-				maxWidth = len(cmd.Description) + 4
+			if lipgloss.Width(cmd.Description) > maxWidth-4 {
+				maxWidth = lipgloss.Width(cmd.Description) + 4
 			}
 		}
 	}
@@ -142,10 +159,18 @@ func (c *commandDialogCmp) View() string {
 		Padding(0, 1).
 		Render("Commands")
 
+	c.filter.Width = maxWidth - 4
+	filter := baseStyle.
+		Width(maxWidth).
+		Padding(0, 1).
+		Render(c.filter.View())
+
 	content := lipgloss.JoinVertical(
 		lipgloss.Left,
 		title,
 		baseStyle.Width(maxWidth).Render(""),
+		filter,
+		baseStyle.Width(maxWidth).Render(""),
 		baseStyle.Width(maxWidth).Render(c.listView.View()),
 		baseStyle.Width(maxWidth).Render(""),
 	)
@@ -163,18 +188,56 @@ func (c *commandDialogCmp) BindingKeys() []key.Binding {
 }
 
 func (c *commandDialogCmp) SetCommands(commands []Command) {
+	c.commands = commands
+	c.filter.Reset()
 	c.listView.SetItems(commands)
 }
 
+// FilterCommands returns the commands whose title fuzzy matches the query,
+// closest matches first. Commands that match equally well keep their order.
+func FilterCommands(commands []Command, query string) []Command {
+	query = strings.TrimSpace(query)
+	if query == "" {
+		return commands
+	}
+	titles := make([]string, len(commands))
+	for i, cmd := range commands {
+		titles[i] = cmd.Title
+	}
+	ranks := fuzzy.RankFindFold(query, titles)
+	sort.SliceStable(ranks, func(i, j int) bool {
+		if ranks[i].Distance != ranks[j].Distance {
+			return ranks[i].Distance < ranks[j].Distance
+		}
+		return ranks[i].OriginalIndex < ranks[j].OriginalIndex
+	})
+	filtered := make([]Command, 0, len(ranks))
+	for _, rank := range ranks {
+		filtered = append(filtered, commands[rank.OriginalIndex])
+	}
+	return filtered
+}
+
 // NewCommandDialogCmp creates a new command selection dialog
 func NewCommandDialogCmp() CommandDialog {
+	t := theme.CurrentTheme()
 	listView := utilComponents.NewSimpleList[Command](
 		[]Command{},
 		10,
-		"No commands available",
-		true,
+		"No matching commands",
+		false,
 	)
+
+	filter := textinput.New()
+	filter.Placeholder = "Type to filter..."
+	filter.Prompt = "> "
+	filter.PlaceholderStyle = filter.PlaceholderStyle.Background(t.Background()).Foreground(t.TextMuted())
+	filter.PromptStyle = filter.PromptStyle.Background(t.Background()).Foreground(t.Primary())
+	filter.TextStyle = filter.TextStyle.Background(t.Background()).Foreground(t.Text())
+	filter.Focus()
+
 	return &commandDialogCmp{
 		listView: listView,
+		filter:   filter,
 	}
 }
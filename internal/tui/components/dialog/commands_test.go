@@ -0,0 +1,43 @@
+package dialog
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestFilterCommands(t *testing.T) {
+	commands := []Command{
+		{ID: "new_session", Title: "New Session"},
+		{ID: "switch_session", Title: "Switch Session"},
+		{ID: "switch_model", Title: "Switch Model"},
+		{ID: "session", Title: "Session"},
+	}
+	ids := func(commands []Command) []string {
+		var ids []string
+		for _, cmd := range commands {
+			ids = append(ids, cmd.ID)
+		}
+		return ids
+	}
+
+	t.Run("returns all commands without a query", func(t *testing.T) {
+		assert.Equal(t, commands, FilterCommands(commands, "  "))
+	})
+
+	t.Run("matches the letters in order", func(t *testing.T) {
+		assert.Equal(t, []string{"new_session"}, ids(FilterCommands(commands, "nse")))
+	})
+
+	t.Run("ignores case", func(t *testing.T) {
+		assert.Equal(t, []string{"switch_model"}, ids(FilterCommands(commands, "MODEL")))
+	})
+
+	t.Run("puts the closest matches first", func(t *testing.T) {
+		assert.Equal(t, "session", FilterCommands(commands, "session")[0].ID)
+	})
+
+	t.Run("returns nothing when no command matches", func(t *testing.T) {
+		assert.Empty(t, FilterCommands(commands, "xyz"))
+	})
+}
@@ -214,8 +214,8 @@ func (c *completionDialogCmp) View() string {
 
 	for _, cmd := range completions {
 		title := cmd.DisplayValue()
-		if len(title) > maxWidth-4 {
-			maxWidth = len(title) + 4
+		if lipgloss.Width(title) > maxWidth-4 {
+			maxWidth = lipgloss.Width(title) + 4
 		}
 	}
 
@@ -0,0 +1,236 @@
+package dialog
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/charmbracelet/x/ansi"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// CopySelectedMsg is sent when the text to copy to the clipboard is selected
+type CopySelectedMsg struct {
+	Text string
+}
+
+// CloseCopyDialogMsg is sent when the copy dialog is closed
+type CloseCopyDialogMsg struct{}
+
+// CopyDialog interface for the dialog that picks what to copy out of an
+// assistant response
+type CopyDialog interface {
+	tea.Model
+	layout.Bindings
+	SetContent(content string)
+}
+
+// CodeBlock is a fenced code block of a markdown text
+type CodeBlock struct {
+	Language string
+	Code     string
+}
+
+type copyItem struct {
+	title string
+	text  string
+}
+
+type copyDialogCmp struct {
+	items       []copyItem
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type copyKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+	J      key.Binding
+	K      key.Binding
+}
+
+var copyKeys = copyKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous item"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next item"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "copy"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+	J: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "next item"),
+	),
+	K: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "previous item"),
+	),
+}
+
+func (c *copyDialogCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (c *copyDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, copyKeys.Up) || key.Matches(msg, copyKeys.K):
+			if c.selectedIdx > 0 {
+				c.selectedIdx--
+			}
+			return c, nil
+		case key.Matches(msg, copyKeys.Down) || key.Matches(msg, copyKeys.J):
+			if c.selectedIdx < len(c.items)-1 {
+				c.selectedIdx++
+			}
+			return c, nil
+		case key.Matches(msg, copyKeys.Enter):
+			if len(c.items) > 0 {
+				return c, util.CmdHandler(CopySelectedMsg{
+					Text: c.items[c.selectedIdx].text,
+				})
+			}
+		case key.Matches(msg, copyKeys.Escape):
+			return c, util.CmdHandler(CloseCopyDialogMsg{})
+		}
+	case tea.WindowSizeMsg:
+		c.width = msg.Width
+		c.height = msg.Height
+	}
+	return c, nil
+}
+
+func (c *copyDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	maxWidth := max(40, min(80, c.width-15))
+	maxVisibleItems := min(10, len(c.items))
+
+	// Keep the selected item visible, centering it when possible
+	startIdx := 0
+	if len(c.items) > maxVisibleItems {
+		halfVisible := maxVisibleItems / 2
+		if c.selectedIdx >= halfVisible && c.selectedIdx < len(c.items)-halfVisible {
+			startIdx = c.selectedIdx - halfVisible
+		} else if c.selectedIdx >= len(c.items)-halfVisible {
+			startIdx = len(c.items) - maxVisibleItems
+		}
+	}
+	endIdx := min(startIdx+maxVisibleItems, len(c.items))
+
+	items := make([]string, 0, maxVisibleItems)
+	for i := startIdx; i < endIdx; i++ {
+		itemStyle := baseStyle.Width(maxWidth)
+		if i == c.selectedIdx {
+			itemStyle = itemStyle.
+				Background(t.Primary()).
+				Foreground(t.Background()).
+				Bold(true)
+		}
+		title := c.items[i].title
+		title = ansi.Truncate(title, maxWidth-2, "…")
+		items = append(items, itemStyle.Padding(0, 1).Render(title))
+	}
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Copy to Clipboard")
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, items...)),
+		baseStyle.Width(maxWidth).Render(""),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (c *copyDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(copyKeys)
+}
+
+// SetContent sets the assistant response to copy from. The whole response is
+// offered first, followed by each of its code blocks.
+func (c *copyDialogCmp) SetContent(content string) {
+	c.items = []copyItem{{title: "Whole response", text: content}}
+	for i, block := range ExtractCodeBlocks(content) {
+		firstLine, _, _ := strings.Cut(strings.TrimSpace(block.Code), "\n")
+		language := block.Language
+		if language == "" {
+			language = "code"
+		}
+		c.items = append(c.items, copyItem{
+			title: fmt.Sprintf("%d. %s: %s", i+1, language, firstLine),
+			text:  block.Code,
+		})
+	}
+	c.selectedIdx = 0
+}
+
+// ExtractCodeBlocks returns the fenced code blocks of the markdown text. A
+// block that is not closed runs to the end of the text.
+func ExtractCodeBlocks(text string) []CodeBlock {
+	var blocks []CodeBlock
+	var current *CodeBlock
+	var fence string
+	var lines []string
+	for _, line := range strings.Split(text, "\n") {
+		trimmed := strings.TrimSpace(line)
+		if current == nil {
+			if strings.HasPrefix(trimmed, "```") || strings.HasPrefix(trimmed, "~~~") {
+				info := strings.TrimLeft(trimmed, trimmed[:1])
+				fence = trimmed[:len(trimmed)-len(info)]
+				current = &CodeBlock{Language: strings.TrimSpace(info)}
+				lines = nil
+			}
+			continue
+		}
+		// The closing fence is at least as long as the opening one
+		if strings.HasPrefix(trimmed, fence) && strings.Trim(trimmed, fence[:1]) == "" {
+			current.Code = strings.Join(lines, "\n")
+			blocks = append(blocks, *current)
+			current = nil
+			continue
+		}
+		lines = append(lines, line)
+	}
+	if current != nil && len(lines) > 0 {
+		current.Code = strings.Join(lines, "\n")
+		blocks = append(blocks, *current)
+	}
+	return blocks
+}
+
+// NewCopyDialogCmp creates a new copy dialog
+func NewCopyDialogCmp() CopyDialog {
+	return &copyDialogCmp{}
+}
@@ -0,0 +1,34 @@
+package dialog
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestExtractCodeBlocks(t *testing.T) {
+	t.Run("extracts fenced blocks with their language", func(t *testing.T) {
+		text := "Run this:\n\n```bash\ngo test ./...\n```\n\nThen:\n\n~~~\nfoo\nbar\n~~~\n"
+		assert.Equal(t, []CodeBlock{
+			{Language: "bash", Code: "go test ./..."},
+			{Language: "", Code: "foo\nbar"},
+		}, ExtractCodeBlocks(text))
+	})
+
+	t.Run("keeps other fences inside a block", func(t *testing.T) {
+		text := "````markdown\n```go\nx := 1\n```\n````"
+		assert.Equal(t, []CodeBlock{
+			{Language: "markdown", Code: "```go\nx := 1\n```"},
+		}, ExtractCodeBlocks(text))
+	})
+
+	t.Run("runs an unclosed block to the end", func(t *testing.T) {
+		assert.Equal(t, []CodeBlock{
+			{Language: "go", Code: "x := 1"},
+		}, ExtractCodeBlocks("```go\nx := 1"))
+	})
+
+	t.Run("returns nothing without blocks", func(t *testing.T) {
+		assert.Empty(t, ExtractCodeBlocks("just text"))
+	})
+}
@@ -0,0 +1,259 @@
+package dialog
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/zhenbah/cryoncode/internal/permission"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// UpdateGrantMsg is sent to change the path or command a grant applies to
+type UpdateGrantMsg struct {
+	GrantID string
+	Pattern string
+}
+
+// DeleteGrantMsg is sent to revoke a grant
+type DeleteGrantMsg struct {
+	GrantID string
+}
+
+// CloseGrantsDialogMsg is sent when the grants dialog is closed
+type CloseGrantsDialogMsg struct{}
+
+// GrantsDialog interface for the dialog that lists the permissions that were
+// always allowed
+type GrantsDialog interface {
+	tea.Model
+	layout.Bindings
+	SetGrants(grants []permission.Grant)
+}
+
+type grantsDialogCmp struct {
+	grants      []permission.Grant
+	selectedIdx int
+	width       int
+	height      int
+
+	// editing shows patternInput in place of the selected pattern
+	editing      bool
+	patternInput textinput.Model
+}
+
+type grantsKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Edit   key.Binding
+	Delete key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+	J      key.Binding
+	K      key.Binding
+}
+
+var grantsKeys = grantsKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous permission"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next permission"),
+	),
+	Edit: key.NewBinding(
+		key.WithKeys("e"),
+		key.WithHelp("e", "edit path or command"),
+	),
+	Delete: key.NewBinding(
+		key.WithKeys("d", "delete"),
+		key.WithHelp("d", "revoke permission"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "save"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+	J: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "next permission"),
+	),
+	K: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "previous permission"),
+	),
+}
+
+func (g *grantsDialogCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (g *grantsDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		if g.editing {
+			return g, g.updateEdit(msg)
+		}
+		switch {
+		case key.Matches(msg, grantsKeys.Up) || key.Matches(msg, grantsKeys.K):
+			if g.selectedIdx > 0 {
+				g.selectedIdx--
+			}
+			return g, nil
+		case key.Matches(msg, grantsKeys.Down) || key.Matches(msg, grantsKeys.J):
+			if g.selectedIdx < len(g.grants)-1 {
+				g.selectedIdx++
+			}
+			return g, nil
+		case key.Matches(msg, grantsKeys.Edit):
+			if len(g.grants) > 0 {
+				g.editing = true
+				g.patternInput = newTitleInput(g.grants[g.selectedIdx].Pattern)
+				g.patternInput.CharLimit = 1000
+				return g, textinput.Blink
+			}
+		case key.Matches(msg, grantsKeys.Delete):
+			if len(g.grants) > 0 {
+				return g, util.CmdHandler(DeleteGrantMsg{
+					GrantID: g.grants[g.selectedIdx].ID,
+				})
+			}
+		case key.Matches(msg, grantsKeys.Escape):
+			return g, util.CmdHandler(CloseGrantsDialogMsg{})
+		}
+	case tea.WindowSizeMsg:
+		g.width = msg.Width
+		g.height = msg.Height
+	}
+	return g, nil
+}
+
+func (g *grantsDialogCmp) updateEdit(msg tea.KeyMsg) tea.Cmd {
+	switch {
+	case key.Matches(msg, grantsKeys.Enter):
+		g.editing = false
+		pattern := strings.TrimSpace(g.patternInput.Value())
+		if pattern == "" || len(g.grants) == 0 {
+			return nil
+		}
+		return util.CmdHandler(UpdateGrantMsg{
+			GrantID: g.grants[g.selectedIdx].ID,
+			Pattern: pattern,
+		})
+	case key.Matches(msg, grantsKeys.Escape):
+		g.editing = false
+		return nil
+	}
+	var cmd tea.Cmd
+	g.patternInput, cmd = g.patternInput.Update(msg)
+	return cmd
+}
+
+func (g *grantsDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	if len(g.grants) == 0 {
+		return baseStyle.Padding(1, 2).
+			Border(lipgloss.RoundedBorder()).
+			BorderBackground(t.Background()).
+			BorderForeground(t.TextMuted()).
+			Width(50).
+			Render("No permissions are always allowed.\nChoose \"Always allow\" when a tool asks for permission.")
+	}
+
+	maxWidth := max(50, min(100, g.width-15))
+	maxVisibleGrants := min(10, len(g.grants))
+
+	// Keep the selected grant visible, centering it when possible
+	startIdx := 0
+	if len(g.grants) > maxVisibleGrants {
+		halfVisible := maxVisibleGrants / 2
+		if g.selectedIdx >= halfVisible && g.selectedIdx < len(g.grants)-halfVisible {
+			startIdx = g.selectedIdx - halfVisible
+		} else if g.selectedIdx >= len(g.grants)-halfVisible {
+			startIdx = len(g.grants) - maxVisibleGrants
+		}
+	}
+	endIdx := min(startIdx+maxVisibleGrants, len(g.grants))
+
+	grantItems := make([]string, 0, maxVisibleGrants)
+	for i := startIdx; i < endIdx; i++ {
+		grant := g.grants[i]
+		itemStyle := baseStyle.Width(maxWidth)
+		if i == g.selectedIdx {
+			itemStyle = itemStyle.
+				Background(t.Primary()).
+				Foreground(t.Background()).
+				Bold(true)
+		}
+
+		tool := fmt.Sprintf("%s (%s)", grant.ToolName, grant.Action)
+		if i == g.selectedIdx && g.editing {
+			g.patternInput.Width = maxWidth - lipgloss.Width(tool) - 5
+			grantItems = append(grantItems, itemStyle.Padding(0, 1).Render(tool+"  "+g.patternInput.View()))
+			continue
+		}
+
+		pattern := util.TruncateLeft(grant.Pattern, maxWidth-lipgloss.Width(tool)-4)
+		grantItems = append(grantItems, itemStyle.Padding(0, 1).Render(tool+"  "+pattern))
+	}
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Always Allowed")
+
+	footer := baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.TextMuted()).
+		Render("e edit · d revoke")
+	if g.editing {
+		footer = baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.TextMuted()).
+			Render("enter save · esc cancel")
+	}
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, grantItems...)),
+		baseStyle.Width(maxWidth).Render(""),
+		footer,
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (g *grantsDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(grantsKeys)
+}
+
+// SetGrants sets the grants to list, keeping the selection in range
+func (g *grantsDialogCmp) SetGrants(grants []permission.Grant) {
+	g.grants = grants
+	g.editing = false
+	g.selectedIdx = max(0, min(g.selectedIdx, len(grants)-1))
+}
+
+// NewGrantsDialogCmp creates a new dialog listing the permission grants
+func NewGrantsDialogCmp() GrantsDialog {
+	return &grantsDialogCmp{
+		grants: []permission.Grant{},
+	}
+}
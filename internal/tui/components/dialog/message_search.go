@@ -0,0 +1,216 @@
+package dialog
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// SearchMessagesMsg is sent when the query of the message search changes
+type SearchMessagesMsg struct {
+	Query string
+}
+
+// OpenMessageMsg is sent when a message is picked in the message search
+type OpenMessageMsg struct {
+	SessionID string
+	MessageID string
+}
+
+// CloseMessageSearchMsg is sent when the message search is closed
+type CloseMessageSearchMsg struct{}
+
+// MessageSearchDialog interface for the dialog that searches the text of the
+// messages of all sessions
+type MessageSearchDialog interface {
+	tea.Model
+	layout.Bindings
+	// Reset starts a new search for the query
+	Reset(query string) tea.Cmd
+	SetResults(query string, results []message.SearchResult)
+}
+
+type messageSearchDialogCmp struct {
+	input       textinput.Model
+	results     []message.SearchResult
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type messageSearchKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+}
+
+var messageSearchKeys = messageSearchKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up", "ctrl+p"),
+		key.WithHelp("↑", "previous message"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down", "ctrl+n"),
+		key.WithHelp("↓", "next message"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "open message"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+}
+
+func (m *messageSearchDialogCmp) Init() tea.Cmd {
+	return textinput.Blink
+}
+
+func (m *messageSearchDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, messageSearchKeys.Up):
+			if m.selectedIdx > 0 {
+				m.selectedIdx--
+			}
+			return m, nil
+		case key.Matches(msg, messageSearchKeys.Down):
+			if m.selectedIdx < len(m.results)-1 {
+				m.selectedIdx++
+			}
+			return m, nil
+		case key.Matches(msg, messageSearchKeys.Enter):
+			if len(m.results) > 0 {
+				result := m.results[m.selectedIdx]
+				return m, util.CmdHandler(OpenMessageMsg{
+					SessionID: result.SessionID,
+					MessageID: result.MessageID,
+				})
+			}
+			return m, nil
+		case key.Matches(msg, messageSearchKeys.Escape):
+			return m, util.CmdHandler(CloseMessageSearchMsg{})
+		}
+		query := m.input.Value()
+		var cmd tea.Cmd
+		m.input, cmd = m.input.Update(msg)
+		if m.input.Value() != query {
+			return m, tea.Batch(cmd, util.CmdHandler(SearchMessagesMsg{Query: m.input.Value()}))
+		}
+		return m, cmd
+	case tea.WindowSizeMsg:
+		m.width = msg.Width
+		m.height = msg.Height
+	}
+	return m, nil
+}
+
+func (m *messageSearchDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+	maxWidth := max(50, min(100, m.width-15))
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Search Messages")
+
+	m.input.Width = maxWidth - 5
+	input := baseStyle.Width(maxWidth).Padding(0, 1).Render(m.input.View())
+
+	// Keep the selected result visible
+	startIdx := max(0, m.selectedIdx-maxVisibleFinderResults+1)
+	endIdx := min(startIdx+maxVisibleFinderResults, len(m.results))
+
+	items := make([]string, 0, maxVisibleFinderResults)
+	for i := startIdx; i < endIdx; i++ {
+		result := m.results[i]
+		itemStyle := baseStyle.Width(maxWidth).Padding(0, 1)
+		detailStyle := baseStyle.Foreground(t.TextMuted())
+		if i == m.selectedIdx {
+			itemStyle = itemStyle.Background(t.Primary()).Foreground(t.Background()).Bold(true)
+			detailStyle = detailStyle.Background(t.Primary()).Foreground(t.Background())
+		}
+
+		sent := formatLastActive(result.CreatedAt)
+		room := maxWidth - lipgloss.Width(sent) - 4
+		sessionTitle := util.Truncate(result.SessionTitle, min(room, 30))
+		var snippet string
+		if rest := room - lipgloss.Width(sessionTitle) - 3; rest > 10 {
+			snippet = " · " + util.Truncate(strings.Join(strings.Fields(result.Snippet), " "), rest)
+		}
+		gap := max(maxWidth-lipgloss.Width(sessionTitle)-lipgloss.Width(snippet)-lipgloss.Width(sent)-2, 1)
+		items = append(items, itemStyle.Render(
+			sessionTitle+
+				detailStyle.Render(snippet+strings.Repeat(" ", gap))+
+				sent,
+		))
+	}
+	if len(items) == 0 {
+		empty := "No matching messages"
+		if strings.TrimSpace(m.input.Value()) == "" {
+			empty = "Type words to find in the messages"
+		}
+		items = append(items, baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.TextMuted()).
+			Render(empty))
+	}
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		input,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, items...)),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (m *messageSearchDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(messageSearchKeys)
+}
+
+func (m *messageSearchDialogCmp) Reset(query string) tea.Cmd {
+	m.input = newTitleInput(query)
+	m.input.Placeholder = "Search the messages of all sessions"
+	m.input.CharLimit = 200
+	m.results = nil
+	m.selectedIdx = 0
+	return tea.Batch(textinput.Blink, util.CmdHandler(SearchMessagesMsg{Query: query}))
+}
+
+// SetResults shows the messages found for the query, results of an older
+// query are ignored
+func (m *messageSearchDialogCmp) SetResults(query string, results []message.SearchResult) {
+	if query != m.input.Value() {
+		return
+	}
+	m.results = results
+	m.selectedIdx = 0
+}
+
+// NewMessageSearchDialogCmp creates a new dialog to search messages
+func NewMessageSearchDialogCmp() MessageSearchDialog {
+	return &messageSearchDialogCmp{
+		input: newTitleInput(""),
+	}
+}
@@ -18,7 +18,7 @@ import (
 
 const (
 	numVisibleModels = 10
-	maxDialogWidth   = 40
+	maxDialogWidth   = 60
 )
 
 // ModelSelectedMsg is sent when a model is selected
@@ -208,7 +208,10 @@ func (m *modelDialogCmp) View() string {
 			itemStyle = itemStyle.Background(t.Primary()).
 				Foreground(t.Background()).Bold(true)
 		}
-		modelItems = append(modelItems, itemStyle.Render(m.models[i].Name))
+		details := modelDetails(m.models[i])
+		name := util.Truncate(m.models[i].Name, maxDialogWidth-lipgloss.Width(details)-2)
+		gap := max(maxDialogWidth-lipgloss.Width(name)-lipgloss.Width(details), 1)
+		modelItems = append(modelItems, itemStyle.Render(name+strings.Repeat(" ", gap)+details))
 	}
 
 	scrollIndicator := m.getScrollIndicators(maxDialogWidth)
@@ -228,6 +231,33 @@ func (m *modelDialogCmp) View() string {
 		Render(content)
 }
 
+// modelDetails describes the context window and the price in dollars per
+// million input and output tokens of the model, e.g. "200K ctx  $3/$15"
+func modelDetails(model models.Model) string {
+	var details []string
+	if model.ContextWindow > 0 {
+		details = append(details, formatContextWindow(model.ContextWindow)+" ctx")
+	}
+	if model.CostPer1MIn > 0 || model.CostPer1MOut > 0 {
+		details = append(details, "$"+formatPrice(model.CostPer1MIn)+"/$"+formatPrice(model.CostPer1MOut))
+	}
+	return strings.Join(details, "  ")
+}
+
+func formatContextWindow(tokens int64) string {
+	switch {
+	case tokens >= 1_000_000:
+		return strings.TrimSuffix(fmt.Sprintf("%.1f", float64(tokens)/1_000_000), ".0") + "M"
+	case tokens >= 1_000:
+		return fmt.Sprintf("%dK", tokens/1_000)
+	}
+	return fmt.Sprintf("%d", tokens)
+}
+
+func formatPrice(price float64) string {
+	return strings.TrimSuffix(strings.TrimRight(fmt.Sprintf("%.2f", price), "0"), ".")
+}
+
 func (m *modelDialogCmp) getScrollIndicators(maxWidth int) string {
 	var indicator string
 
@@ -0,0 +1,29 @@
+package dialog
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+)
+
+func TestModelDetails(t *testing.T) {
+	assert.Equal(t, "200K ctx  $3/$15", modelDetails(models.Model{
+		ContextWindow: 200_000,
+		CostPer1MIn:   3,
+		CostPer1MOut:  15,
+	}))
+	assert.Equal(t, "1M ctx  $0.1/$0.4", modelDetails(models.Model{
+		ContextWindow: 1_000_000,
+		CostPer1MIn:   0.1,
+		CostPer1MOut:  0.4,
+	}))
+	assert.Equal(t, "1.5M ctx  $1.25/$10", modelDetails(models.Model{
+		ContextWindow: 1_500_000,
+		CostPer1MIn:   1.25,
+		CostPer1MOut:  10,
+	}))
+	// Local models have no price
+	assert.Equal(t, "32K ctx", modelDetails(models.Model{ContextWindow: 32_000}))
+	assert.Equal(t, "", modelDetails(models.Model{}))
+}
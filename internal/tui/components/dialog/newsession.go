@@ -0,0 +1,135 @@
+package dialog
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// ShowNewSessionDialogMsg is sent to ask for the title of a new session.
+type ShowNewSessionDialogMsg struct{}
+
+// CloseNewSessionDialogMsg is sent when the new session dialog is closed. An
+// empty title leaves the title to be generated from the first message.
+type CloseNewSessionDialogMsg struct {
+	Submit bool
+	Title  string
+}
+
+// NewSessionDialogCmp asks for the title of a new session.
+type NewSessionDialogCmp struct {
+	width, height int
+	input         textinput.Model
+	keys          argumentsDialogKeyMap
+}
+
+// NewNewSessionDialogCmp creates a new NewSessionDialogCmp.
+func NewNewSessionDialogCmp() NewSessionDialogCmp {
+	t := theme.CurrentTheme()
+
+	ti := textinput.New()
+	ti.Placeholder = "Leave empty to name it after the first message..."
+	ti.Width = 56
+	ti.Prompt = ""
+	ti.CharLimit = 100
+	ti.PlaceholderStyle = ti.PlaceholderStyle.Background(t.Background())
+	ti.PromptStyle = ti.PromptStyle.Background(t.Background()).Foreground(t.Primary())
+	ti.TextStyle = ti.TextStyle.Background(t.Background()).Foreground(t.Primary())
+	ti.Focus()
+
+	return NewSessionDialogCmp{
+		input: ti,
+		keys:  argumentsDialogKeyMap{},
+	}
+}
+
+// Init implements tea.Model.
+func (m NewSessionDialogCmp) Init() tea.Cmd {
+	return textinput.Blink
+}
+
+// Update implements tea.Model.
+func (m NewSessionDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, key.NewBinding(key.WithKeys("esc"))):
+			return m, util.CmdHandler(CloseNewSessionDialogMsg{})
+		case key.Matches(msg, key.NewBinding(key.WithKeys("enter"))):
+			return m, util.CmdHandler(CloseNewSessionDialogMsg{
+				Submit: true,
+				Title:  strings.TrimSpace(m.input.Value()),
+			})
+		}
+	case tea.WindowSizeMsg:
+		m.width = msg.Width
+		m.height = msg.Height
+	}
+
+	var cmd tea.Cmd
+	m.input, cmd = m.input.Update(msg)
+	return m, cmd
+}
+
+// View implements tea.Model.
+func (m NewSessionDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	maxWidth := 60
+
+	title := lipgloss.NewStyle().
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Background(t.Background()).
+		Render("New Session")
+
+	label := lipgloss.NewStyle().
+		Foreground(t.TextMuted()).
+		Width(maxWidth).
+		Padding(1, 1, 0, 1).
+		Background(t.Background()).
+		Render("Title:")
+
+	field := lipgloss.NewStyle().
+		Foreground(t.Text()).
+		Width(maxWidth).
+		Padding(0, 1).
+		Background(t.Background()).
+		Render(m.input.View())
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		label,
+		field,
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Background(t.Background()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+// SetSize sets the size of the component.
+func (m *NewSessionDialogCmp) SetSize(width, height int) {
+	m.width = width
+	m.height = height
+}
+
+// Bindings implements layout.Bindings.
+func (m NewSessionDialogCmp) Bindings() []key.Binding {
+	return m.keys.ShortHelp()
+}
@@ -23,6 +23,7 @@ type PermissionAction string
 const (
 	PermissionAllow           PermissionAction = "allow"
 	PermissionAllowForSession PermissionAction = "allow_session"
+	PermissionAllowAlways     PermissionAction = "allow_always"
 	PermissionDeny            PermissionAction = "deny"
 )
 
@@ -37,6 +38,8 @@ type PermissionDialogCmp interface {
 	tea.Model
 	layout.Bindings
 	SetPermissions(permission permission.PermissionRequest) tea.Cmd
+	// Permission returns the request the dialog is asking about
+	Permission() permission.PermissionRequest
 }
 
 type permissionsMapping struct {
@@ -45,6 +48,7 @@ type permissionsMapping struct {
 	EnterSpace   key.Binding
 	Allow        key.Binding
 	AllowSession key.Binding
+	AllowAlways  key.Binding
 	Deny         key.Binding
 	Tab          key.Binding
 }
@@ -70,6 +74,10 @@ var permissionsKeys = permissionsMapping{
 		key.WithKeys("s"),
 		key.WithHelp("s", "allow for session"),
 	),
+	AllowAlways: key.NewBinding(
+		key.WithKeys("p"),
+		key.WithHelp("p", "always allow"),
+	),
 	Deny: key.NewBinding(
 		key.WithKeys("d"),
 		key.WithHelp("d", "deny"),
@@ -87,7 +95,7 @@ type permissionDialogCmp struct {
 	permission      permission.PermissionRequest
 	windowSize      tea.WindowSizeMsg
 	contentViewPort viewport.Model
-	selectedOption  int // 0: Allow, 1: Allow for session, 2: Deny
+	selectedOption  int // 0: Allow, 1: Allow for session, 2: Always allow, 3: Deny
 
 	diffCache     map[string]string
 	markdownCache map[string]string
@@ -110,16 +118,18 @@ func (p *permissionDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	case tea.KeyMsg:
 		switch {
 		case key.Matches(msg, permissionsKeys.Right) || key.Matches(msg, permissionsKeys.Tab):
-			p.selectedOption = (p.selectedOption + 1) % 3
+			p.selectedOption = (p.selectedOption + 1) % len(permissionOptions)
 			return p, nil
 		case key.Matches(msg, permissionsKeys.Left):
-			p.selectedOption = (p.selectedOption + 2) % 3
+			p.selectedOption = (p.selectedOption + len(permissionOptions) - 1) % len(permissionOptions)
 		case key.Matches(msg, permissionsKeys.EnterSpace):
 			return p, p.selectCurrentOption()
 		case key.Matches(msg, permissionsKeys.Allow):
 			return p, util.CmdHandler(PermissionResponseMsg{Action: PermissionAllow, Permission: p.permission})
 		case key.Matches(msg, permissionsKeys.AllowSession):
 			return p, util.CmdHandler(PermissionResponseMsg{Action: PermissionAllowForSession, Permission: p.permission})
+		case key.Matches(msg, permissionsKeys.AllowAlways):
+			return p, util.CmdHandler(PermissionResponseMsg{Action: PermissionAllowAlways, Permission: p.permission})
 		case key.Matches(msg, permissionsKeys.Deny):
 			return p, util.CmdHandler(PermissionResponseMsg{Action: PermissionDeny, Permission: p.permission})
 		default:
@@ -133,59 +143,37 @@ func (p *permissionDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	return p, tea.Batch(cmds...)
 }
 
-func (p *permissionDialogCmp) selectCurrentOption() tea.Cmd {
-	var action PermissionAction
-
-	switch p.selectedOption {
-	case 0:
-		action = PermissionAllow
-	case 1:
-		action = PermissionAllowForSession
-	case 2:
-		action = PermissionDeny
-	}
+// permissionOptions are the buttons of the dialog, in order
+var permissionOptions = []struct {
+	action PermissionAction
+	label  string
+}{
+	{PermissionAllow, "Allow (a)"},
+	{PermissionAllowForSession, "Allow for session (s)"},
+	{PermissionAllowAlways, "Always allow (p)"},
+	{PermissionDeny, "Deny (d)"},
+}
 
+func (p *permissionDialogCmp) selectCurrentOption() tea.Cmd {
+	action := permissionOptions[p.selectedOption].action
 	return util.CmdHandler(PermissionResponseMsg{Action: action, Permission: p.permission})
 }
 
 func (p *permissionDialogCmp) renderButtons() string {
 	t := theme.CurrentTheme()
 	baseStyle := styles.BaseStyle()
-
-	allowStyle := baseStyle
-	allowSessionStyle := baseStyle
-	denyStyle := baseStyle
 	spacerStyle := baseStyle.Background(t.Background())
 
-	// Style the selected button
-	switch p.selectedOption {
-	case 0:
-		allowStyle = allowStyle.Background(t.Primary()).Foreground(t.Background())
-		allowSessionStyle = allowSessionStyle.Background(t.Background()).Foreground(t.Primary())
-		denyStyle = denyStyle.Background(t.Background()).Foreground(t.Primary())
-	case 1:
-		allowStyle = allowStyle.Background(t.Background()).Foreground(t.Primary())
-		allowSessionStyle = allowSessionStyle.Background(t.Primary()).Foreground(t.Background())
-		denyStyle = denyStyle.Background(t.Background()).Foreground(t.Primary())
-	case 2:
-		allowStyle = allowStyle.Background(t.Background()).Foreground(t.Primary())
-		allowSessionStyle = allowSessionStyle.Background(t.Background()).Foreground(t.Primary())
-		denyStyle = denyStyle.Background(t.Primary()).Foreground(t.Background())
+	buttons := make([]string, 0, len(permissionOptions)*2)
+	for i, option := range permissionOptions {
+		// Style the selected button
+		style := baseStyle.Background(t.Background()).Foreground(t.Primary())
+		if i == p.selectedOption {
+			style = baseStyle.Background(t.Primary()).Foreground(t.Background())
+		}
+		buttons = append(buttons, style.Padding(0, 1).Render(option.label), spacerStyle.Render("  "))
 	}
-
-	allowButton := allowStyle.Padding(0, 1).Render("Allow (a)")
-	allowSessionButton := allowSessionStyle.Padding(0, 1).Render("Allow for session (s)")
-	denyButton := denyStyle.Padding(0, 1).Render("Deny (d)")
-
-	content := lipgloss.JoinHorizontal(
-		lipgloss.Left,
-		allowButton,
-		spacerStyle.Render("  "),
-		allowSessionButton,
-		spacerStyle.Render("  "),
-		denyButton,
-		spacerStyle.Render("  "),
-	)
+	content := lipgloss.JoinHorizontal(lipgloss.Left, buttons...)
 
 	remainingWidth := p.width - lipgloss.Width(content)
 	if remainingWidth > 0 {
@@ -227,39 +215,14 @@ func (p *permissionDialogCmp) renderHeader() string {
 
 	// Add tool-specific header information
 	switch p.permission.ToolName {
-	case tools.BashToolName:
+	case tools.BashToolName, tools.ShellSessionToolName:
 		headerParts = append(headerParts, baseStyle.Foreground(t.TextMuted()).Width(p.width).Bold(true).Render("Command"))
-	case tools.EditToolName:
+	case tools.EditToolName, tools.PatchToolName, tools.MultiEditToolName, tools.NotebookEditToolName:
 		params := p.permission.Params.(tools.EditPermissionsParams)
-		fileKey := baseStyle.Foreground(t.TextMuted()).Bold(true).Render("File")
-		filePath := baseStyle.
-			Foreground(t.Text()).
-			Width(p.width - lipgloss.Width(fileKey)).
-			Render(fmt.Sprintf(": %s", params.FilePath))
-		headerParts = append(headerParts,
-			lipgloss.JoinHorizontal(
-				lipgloss.Left,
-				fileKey,
-				filePath,
-			),
-			baseStyle.Render(strings.Repeat(" ", p.width)),
-		)
-
+		headerParts = append(headerParts, p.renderFileChangeHeader(params.FilePath, params.Additions, params.Removals)...)
 	case tools.WriteToolName:
 		params := p.permission.Params.(tools.WritePermissionsParams)
-		fileKey := baseStyle.Foreground(t.TextMuted()).Bold(true).Render("File")
-		filePath := baseStyle.
-			Foreground(t.Text()).
-			Width(p.width - lipgloss.Width(fileKey)).
-			Render(fmt.Sprintf(": %s", params.FilePath))
-		headerParts = append(headerParts,
-			lipgloss.JoinHorizontal(
-				lipgloss.Left,
-				fileKey,
-				filePath,
-			),
-			baseStyle.Render(strings.Repeat(" ", p.width)),
-		)
+		headerParts = append(headerParts, p.renderFileChangeHeader(params.FilePath, params.Additions, params.Removals)...)
 	case tools.FetchToolName:
 		headerParts = append(headerParts, baseStyle.Foreground(t.TextMuted()).Width(p.width).Bold(true).Render("URL"))
 	}
@@ -267,6 +230,56 @@ func (p *permissionDialogCmp) renderHeader() string {
 	return lipgloss.NewStyle().Background(t.Background()).Render(lipgloss.JoinVertical(lipgloss.Left, headerParts...))
 }
 
+// renderFileChangeHeader shows the file being changed, whether it is inside
+// the workspace and a summary of the diff so the change can be judged before
+// reading it.
+func (p *permissionDialogCmp) renderFileChangeHeader(filePath string, additions, removals int) []string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	fileKey := baseStyle.Foreground(t.TextMuted()).Bold(true).Render("File")
+	fileValue := baseStyle.
+		Foreground(t.Text()).
+		Width(p.width - lipgloss.Width(fileKey)).
+		Render(fmt.Sprintf(": %s", filePath))
+
+	changesKey := baseStyle.Foreground(t.TextMuted()).Bold(true).Render("Changes")
+	changesValue := lipgloss.JoinHorizontal(
+		lipgloss.Left,
+		baseStyle.Foreground(t.Text()).Render(": "),
+		baseStyle.Foreground(t.Success()).Render(fmt.Sprintf("+%d", additions)),
+		baseStyle.Render(" "),
+		baseStyle.Foreground(t.Error()).Render(fmt.Sprintf("-%d", removals)),
+	)
+	changesValue = baseStyle.Width(p.width - lipgloss.Width(changesKey)).Render(changesValue)
+
+	resolved, inside := tools.ResolveWorkspacePath(filePath)
+	workspaceKey := baseStyle.Foreground(t.TextMuted()).Bold(true).Render("Workspace")
+	workspaceValue := baseStyle.Foreground(t.Success()).Render("inside")
+	if !inside {
+		workspaceValue = baseStyle.Foreground(t.Warning()).Bold(true).Render("outside")
+	}
+	workspaceValue = baseStyle.
+		Width(p.width - lipgloss.Width(workspaceKey)).
+		Render(baseStyle.Foreground(t.Text()).Render(": ") + workspaceValue)
+
+	parts := []string{lipgloss.JoinHorizontal(lipgloss.Left, fileKey, fileValue)}
+	// Show where a symlink actually points, that's the file being changed
+	if resolved != filePath {
+		resolvedKey := baseStyle.Foreground(t.TextMuted()).Bold(true).Render("Resolves to")
+		resolvedValue := baseStyle.
+			Foreground(t.Warning()).
+			Width(p.width - lipgloss.Width(resolvedKey)).
+			Render(fmt.Sprintf(": %s", resolved))
+		parts = append(parts, lipgloss.JoinHorizontal(lipgloss.Left, resolvedKey, resolvedValue))
+	}
+	return append(parts,
+		lipgloss.JoinHorizontal(lipgloss.Left, workspaceKey, workspaceValue),
+		lipgloss.JoinHorizontal(lipgloss.Left, changesKey, changesValue),
+		baseStyle.Render(strings.Repeat(" ", p.width)),
+	)
+}
+
 func (p *permissionDialogCmp) renderBashContent() string {
 	t := theme.CurrentTheme()
 	baseStyle := styles.BaseStyle()
@@ -404,9 +417,9 @@ func (p *permissionDialogCmp) render() string {
 	// Render content based on tool type
 	var contentFinal string
 	switch p.permission.ToolName {
-	case tools.BashToolName:
+	case tools.BashToolName, tools.ShellSessionToolName:
 		contentFinal = p.renderBashContent()
-	case tools.EditToolName:
+	case tools.EditToolName, tools.MultiEditToolName, tools.NotebookEditToolName:
 		contentFinal = p.renderEditContent()
 	case tools.PatchToolName:
 		contentFinal = p.renderPatchContent()
@@ -453,10 +466,10 @@ func (p *permissionDialogCmp) SetSize() tea.Cmd {
 		return nil
 	}
 	switch p.permission.ToolName {
-	case tools.BashToolName:
+	case tools.BashToolName, tools.ShellSessionToolName:
 		p.width = int(float64(p.windowSize.Width) * 0.4)
 		p.height = int(float64(p.windowSize.Height) * 0.3)
-	case tools.EditToolName:
+	case tools.EditToolName, tools.PatchToolName, tools.MultiEditToolName, tools.NotebookEditToolName:
 		p.width = int(float64(p.windowSize.Width) * 0.8)
 		p.height = int(float64(p.windowSize.Height) * 0.8)
 	case tools.WriteToolName:
@@ -469,6 +482,8 @@ func (p *permissionDialogCmp) SetSize() tea.Cmd {
 		p.width = int(float64(p.windowSize.Width) * 0.7)
 		p.height = int(float64(p.windowSize.Height) * 0.5)
 	}
+	// Leave room for all the buttons on one line
+	p.width = max(p.width, min(80, p.windowSize.Width))
 	return nil
 }
 
@@ -477,6 +492,10 @@ func (p *permissionDialogCmp) SetPermissions(permission permission.PermissionReq
 	return p.SetSize()
 }
 
+func (p *permissionDialogCmp) Permission() permission.PermissionRequest {
+	return p.permission
+}
+
 // Helper to get or set cached diff content
 func (c *permissionDialogCmp) GetOrSetDiff(key string, generator func() (string, error)) string {
 	if cached, ok := c.diffCache[key]; ok {
@@ -0,0 +1,238 @@
+package dialog
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// PromptAction is what is done with the prompt picked in the prompt dialog
+type PromptAction string
+
+const (
+	// ForkAtPrompt forks the session after the response to the prompt
+	ForkAtPrompt PromptAction = "fork"
+	// EditPrompt loads the prompt into the editor to send it again in place
+	// of the messages from it on
+	EditPrompt PromptAction = "edit"
+	// DeleteMessage deletes the message, the responses are listed too
+	DeleteMessage PromptAction = "delete"
+)
+
+// PromptSelectedMsg is sent when a prompt is picked in the prompt dialog
+type PromptSelectedMsg struct {
+	Action    PromptAction
+	MessageID string
+}
+
+// ClosePromptDialogMsg is sent when the prompt dialog is closed
+type ClosePromptDialogMsg struct{}
+
+// PromptDialog interface for the dialog that picks a prompt of the session
+// to fork the session at, to edit or to delete
+type PromptDialog interface {
+	tea.Model
+	layout.Bindings
+	SetMessages(action PromptAction, messages []message.Message)
+}
+
+type promptDialogCmp struct {
+	action      PromptAction
+	prompts     []message.Message
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type promptKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+	J      key.Binding
+	K      key.Binding
+}
+
+var promptKeys = promptKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous prompt"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next prompt"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "select prompt"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+	J: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "next prompt"),
+	),
+	K: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "previous prompt"),
+	),
+}
+
+func (p *promptDialogCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (p *promptDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, promptKeys.Up) || key.Matches(msg, promptKeys.K):
+			if p.selectedIdx > 0 {
+				p.selectedIdx--
+			}
+			return p, nil
+		case key.Matches(msg, promptKeys.Down) || key.Matches(msg, promptKeys.J):
+			if p.selectedIdx < len(p.prompts)-1 {
+				p.selectedIdx++
+			}
+			return p, nil
+		case key.Matches(msg, promptKeys.Enter):
+			if len(p.prompts) > 0 {
+				return p, util.CmdHandler(PromptSelectedMsg{
+					Action:    p.action,
+					MessageID: p.prompts[p.selectedIdx].ID,
+				})
+			}
+		case key.Matches(msg, promptKeys.Escape):
+			return p, util.CmdHandler(ClosePromptDialogMsg{})
+		}
+	case tea.WindowSizeMsg:
+		p.width = msg.Width
+		p.height = msg.Height
+	}
+	return p, nil
+}
+
+func (p *promptDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	if len(p.prompts) == 0 {
+		return baseStyle.Padding(1, 2).
+			Border(lipgloss.RoundedBorder()).
+			BorderBackground(t.Background()).
+			BorderForeground(t.TextMuted()).
+			Width(40).
+			Render("No prompts in this session")
+	}
+
+	maxWidth := max(40, min(80, p.width-15))
+	maxVisiblePrompts := min(10, len(p.prompts))
+
+	// Keep the selected prompt visible, centering it when possible
+	startIdx := 0
+	if len(p.prompts) > maxVisiblePrompts {
+		halfVisible := maxVisiblePrompts / 2
+		if p.selectedIdx >= halfVisible && p.selectedIdx < len(p.prompts)-halfVisible {
+			startIdx = p.selectedIdx - halfVisible
+		} else if p.selectedIdx >= len(p.prompts)-halfVisible {
+			startIdx = len(p.prompts) - maxVisiblePrompts
+		}
+	}
+	endIdx := min(startIdx+maxVisiblePrompts, len(p.prompts))
+
+	promptItems := make([]string, 0, maxVisiblePrompts)
+	for i := startIdx; i < endIdx; i++ {
+		itemStyle := baseStyle.Width(maxWidth)
+		if i == p.selectedIdx {
+			itemStyle = itemStyle.
+				Background(t.Primary()).
+				Foreground(t.Background()).
+				Bold(true)
+		}
+		promptItems = append(promptItems, itemStyle.Padding(0, 1).Render(p.summary(p.prompts[i], maxWidth-2)))
+	}
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render(p.title())
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, promptItems...)),
+		baseStyle.Width(maxWidth).Render(""),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (p *promptDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(promptKeys)
+}
+
+func (p *promptDialogCmp) title() string {
+	switch p.action {
+	case EditPrompt:
+		return "Edit Prompt"
+	case DeleteMessage:
+		return "Delete Message"
+	}
+	return "Fork Session After"
+}
+
+// summary returns the first line of the message, the responses are told
+// apart from the prompts when they're listed too
+func (p *promptDialogCmp) summary(msg message.Message, width int) string {
+	if msg.Role == message.User {
+		return promptSummary(msg, width)
+	}
+	if strings.TrimSpace(msg.Content().String()) == "" {
+		var names []string
+		for _, call := range msg.ToolCalls() {
+			names = append(names, call.Name)
+		}
+		msg.Parts = []message.ContentPart{message.TextContent{Text: "Tools: " + strings.Join(names, ", ")}}
+	}
+	return "↳ " + promptSummary(msg, width-2)
+}
+
+// SetMessages sets the prompts to choose from out of the session messages and
+// selects the latest one
+func (p *promptDialogCmp) SetMessages(action PromptAction, messages []message.Message) {
+	p.action = action
+	p.prompts = p.prompts[:0]
+	for _, msg := range messages {
+		response := msg.Role == message.Assistant &&
+			(strings.TrimSpace(msg.Content().String()) != "" || len(msg.ToolCalls()) > 0)
+		if msg.Role == message.User || (action == DeleteMessage && response) {
+			p.prompts = append(p.prompts, msg)
+		}
+	}
+	p.selectedIdx = max(0, len(p.prompts)-1)
+}
+
+// NewPromptDialogCmp creates a new prompt dialog
+func NewPromptDialogCmp() PromptDialog {
+	return &promptDialogCmp{
+		prompts: []message.Message{},
+	}
+}
@@ -12,23 +12,43 @@ import (
 	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
 
-const question = "Are you sure you want to quit?"
+const (
+	question        = "Are you sure you want to quit?"
+	pendingQuestion = "Quit anyway?"
+)
 
 type CloseQuitMsg struct{}
 
+// StopPendingWorkMsg is sent when the quit dialog is used to stop the
+// request in flight instead of quitting
+type StopPendingWorkMsg struct{}
+
 type QuitDialog interface {
 	tea.Model
 	layout.Bindings
+	// SetPendingWork describes the work lost by quitting, the dialog then
+	// offers to stop it instead. An empty description asks the plain question.
+	SetPendingWork(pending string)
 }
 
+type quitOption int
+
+const (
+	quitYes quitOption = iota
+	quitStop
+	quitNo
+)
+
 type quitDialogCmp struct {
-	selectedNo bool
+	pending  string
+	selected quitOption
 }
 
 type helpMapping struct {
 	LeftRight  key.Binding
 	EnterSpace key.Binding
 	Yes        key.Binding
+	Stop       key.Binding
 	No         key.Binding
 	Tab        key.Binding
 }
@@ -46,6 +66,10 @@ var helpKeys = helpMapping{
 		key.WithKeys("y", "Y"),
 		key.WithHelp("y/Y", "yes"),
 	),
+	Stop: key.NewBinding(
+		key.WithKeys("s", "S"),
+		key.WithHelp("s/S", "stop the pending work"),
+	),
 	No: key.NewBinding(
 		key.WithKeys("n", "N"),
 		key.WithHelp("n/N", "no"),
@@ -64,56 +88,101 @@ func (q *quitDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	switch msg := msg.(type) {
 	case tea.KeyMsg:
 		switch {
+		case msg.String() == "left":
+			q.move(-1)
+			return q, nil
 		case key.Matches(msg, helpKeys.LeftRight) || key.Matches(msg, helpKeys.Tab):
-			q.selectedNo = !q.selectedNo
+			q.move(1)
 			return q, nil
 		case key.Matches(msg, helpKeys.EnterSpace):
-			if !q.selectedNo {
-				return q, tea.Quit
-			}
-			return q, util.CmdHandler(CloseQuitMsg{})
+			return q, q.choose(q.selected)
 		case key.Matches(msg, helpKeys.Yes):
-			return q, tea.Quit
+			return q, q.choose(quitYes)
+		case key.Matches(msg, helpKeys.Stop) && q.pending != "":
+			return q, q.choose(quitStop)
 		case key.Matches(msg, helpKeys.No):
-			return q, util.CmdHandler(CloseQuitMsg{})
+			return q, q.choose(quitNo)
 		}
 	}
 	return q, nil
 }
 
+// options returns the choices of the dialog, stopping the pending work is
+// only offered when there is some
+func (q *quitDialogCmp) options() []quitOption {
+	if q.pending == "" {
+		return []quitOption{quitYes, quitNo}
+	}
+	return []quitOption{quitYes, quitStop, quitNo}
+}
+
+// move selects the option next to the selected one, wrapping around
+func (q *quitDialogCmp) move(step int) {
+	options := q.options()
+	for i, option := range options {
+		if option == q.selected {
+			q.selected = options[(i+step+len(options))%len(options)]
+			return
+		}
+	}
+}
+
+func (q *quitDialogCmp) choose(option quitOption) tea.Cmd {
+	switch option {
+	case quitYes:
+		return tea.Quit
+	case quitStop:
+		return util.CmdHandler(StopPendingWorkMsg{})
+	}
+	return util.CmdHandler(CloseQuitMsg{})
+}
+
+func (q *quitDialogCmp) SetPendingWork(pending string) {
+	q.pending = pending
+	q.selected = quitNo
+}
+
 func (q *quitDialogCmp) View() string {
 	t := theme.CurrentTheme()
 	baseStyle := styles.BaseStyle()
-	
-	yesStyle := baseStyle
-	noStyle := baseStyle
 	spacerStyle := baseStyle.Background(t.Background())
 
-	if q.selectedNo {
-		noStyle = noStyle.Background(t.Primary()).Foreground(t.Background())
-		yesStyle = yesStyle.Background(t.Background()).Foreground(t.Primary())
-	} else {
-		yesStyle = yesStyle.Background(t.Primary()).Foreground(t.Background())
-		noStyle = noStyle.Background(t.Background()).Foreground(t.Primary())
+	labels := map[quitOption]string{
+		quitYes:  "Yes",
+		quitStop: "Stop it",
+		quitNo:   "No",
 	}
+	var buttons []string
+	for i, option := range q.options() {
+		style := baseStyle.Background(t.Background()).Foreground(t.Primary())
+		if option == q.selected {
+			style = baseStyle.Background(t.Primary()).Foreground(t.Background())
+		}
+		if i > 0 {
+			buttons = append(buttons, spacerStyle.Render("  "))
+		}
+		buttons = append(buttons, style.Padding(0, 1).Render(labels[option]))
+	}
+	buttonRow := lipgloss.JoinHorizontal(lipgloss.Left, buttons...)
 
-	yesButton := yesStyle.Padding(0, 1).Render("Yes")
-	noButton := noStyle.Padding(0, 1).Render("No")
-
-	buttons := lipgloss.JoinHorizontal(lipgloss.Left, yesButton, spacerStyle.Render("  "), noButton)
+	lines := []string{question}
+	if q.pending != "" {
+		lines = []string{baseStyle.Foreground(t.Warning()).Render(q.pending), pendingQuestion}
+	}
+	text := lipgloss.JoinVertical(lipgloss.Center, lines...)
 
-	width := lipgloss.Width(question)
-	remainingWidth := width - lipgloss.Width(buttons)
+	width := lipgloss.Width(text)
+	remainingWidth := width - lipgloss.Width(buttonRow)
 	if remainingWidth > 0 {
-		buttons = spacerStyle.Render(strings.Repeat(" ", remainingWidth)) + buttons
+		buttonRow = spacerStyle.Render(strings.Repeat(" ", remainingWidth)) + buttonRow
 	}
 
 	content := baseStyle.Render(
 		lipgloss.JoinVertical(
 			lipgloss.Center,
-			question,
+			text,
 			"",
-			buttons,
+			buttonRow,
 		),
 	)
 
@@ -131,6 +200,6 @@ func (q *quitDialogCmp) BindingKeys() []key.Binding {
 
 func NewQuitCmp() QuitDialog {
 	return &quitDialogCmp{
-		selectedNo: true,
+		selected: quitNo,
 	}
 }
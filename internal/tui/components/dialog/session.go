@@ -1,9 +1,16 @@
 package dialog
 
 import (
+	"fmt"
+	"slices"
+	"strings"
+	"time"
+
 	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
+	zone "github.com/lrstanley/bubblezone"
 	"github.com/zhenbah/cryoncode/internal/session"
 	"github.com/zhenbah/cryoncode/internal/tui/layout"
 	"github.com/zhenbah/cryoncode/internal/tui/styles"
@@ -19,29 +26,76 @@ type SessionSelectedMsg struct {
 // CloseSessionDialogMsg is sent when the session dialog is closed
 type CloseSessionDialogMsg struct{}
 
+// RenameSessionMsg is sent to change the title of a session
+type RenameSessionMsg struct {
+	SessionID string
+	Title     string
+}
+
+// DeleteSessionMsg is sent to delete a session once the user confirmed it
+type DeleteSessionMsg struct {
+	SessionID string
+}
+
+// ArchiveSessionMsg is sent to archive a session, or to restore it when
+// Archived is false
+type ArchiveSessionMsg struct {
+	SessionID string
+	Archived  bool
+}
+
+// PinSessionMsg is sent to pin a session to the top of the list, or to unpin it
+type PinSessionMsg struct {
+	SessionID string
+	Pinned    bool
+}
+
 // SessionDialog interface for the session switching dialog
 type SessionDialog interface {
 	tea.Model
 	layout.Bindings
 	SetSessions(sessions []session.Session)
 	SetSelectedSession(sessionID string)
+	// SetTag lists only the sessions with the tag, all of them when it's empty
+	SetTag(tag string)
+	// ShowArchived lists the archived sessions instead of the others
+	ShowArchived(archived bool)
 }
 
+// sessionListZone marks the listed sessions so they can be clicked
+const sessionListZone = "session-list"
+
 type sessionDialogCmp struct {
-	sessions          []session.Session
+	all               []session.Session
+	sessions          []session.Session // the sessions listed, with the tag
+	tag               string
+	archived          bool // list the archived sessions
 	selectedIdx       int
 	width             int
 	height            int
 	selectedSessionID string
+
+	// renaming shows titleInput in place of the selected title
+	renaming   bool
+	titleInput textinput.Model
+	// confirmDelete asks before the selected session is deleted
+	confirmDelete bool
 }
 
 type sessionKeyMap struct {
-	Up     key.Binding
-	Down   key.Binding
-	Enter  key.Binding
-	Escape key.Binding
-	J      key.Binding
-	K      key.Binding
+	Up       key.Binding
+	Down     key.Binding
+	Enter    key.Binding
+	Escape   key.Binding
+	J        key.Binding
+	K        key.Binding
+	Rename   key.Binding
+	Delete   key.Binding
+	Tag      key.Binding
+	Pin      key.Binding
+	Archive  key.Binding
+	Archived key.Binding
+	Yes      key.Binding
 }
 
 var sessionKeys = sessionKeyMap{
@@ -69,6 +123,34 @@ var sessionKeys = sessionKeyMap{
 		key.WithKeys("k"),
 		key.WithHelp("k", "previous session"),
 	),
+	Rename: key.NewBinding(
+		key.WithKeys("r"),
+		key.WithHelp("r", "rename session"),
+	),
+	Delete: key.NewBinding(
+		key.WithKeys("d", "delete"),
+		key.WithHelp("d", "delete session"),
+	),
+	Tag: key.NewBinding(
+		key.WithKeys("t"),
+		key.WithHelp("t", "filter by tag"),
+	),
+	Pin: key.NewBinding(
+		key.WithKeys("p"),
+		key.WithHelp("p", "pin session"),
+	),
+	Archive: key.NewBinding(
+		key.WithKeys("a"),
+		key.WithHelp("a", "archive session"),
+	),
+	Archived: key.NewBinding(
+		key.WithKeys("A"),
+		key.WithHelp("A", "archived sessions"),
+	),
+	Yes: key.NewBinding(
+		key.WithKeys("y", "Y"),
+		key.WithHelp("y", "confirm delete"),
+	),
 }
 
 func (s *sessionDialogCmp) Init() tea.Cmd {
@@ -78,6 +160,18 @@ func (s *sessionDialogCmp) Init() tea.Cmd {
 func (s *sessionDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	switch msg := msg.(type) {
 	case tea.KeyMsg:
+		if s.renaming {
+			return s, s.updateRename(msg)
+		}
+		if s.confirmDelete {
+			s.confirmDelete = false
+			if key.Matches(msg, sessionKeys.Yes) && len(s.sessions) > 0 {
+				return s, util.CmdHandler(DeleteSessionMsg{
+					SessionID: s.sessions[s.selectedIdx].ID,
+				})
+			}
+			return s, nil
+		}
 		switch {
 		case key.Matches(msg, sessionKeys.Up) || key.Matches(msg, sessionKeys.K):
 			if s.selectedIdx > 0 {
@@ -95,9 +189,55 @@ func (s *sessionDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 					Session: s.sessions[s.selectedIdx],
 				})
 			}
+		case key.Matches(msg, sessionKeys.Rename):
+			if len(s.sessions) > 0 {
+				s.renaming = true
+				s.titleInput = newTitleInput(s.sessions[s.selectedIdx].Title)
+				return s, textinput.Blink
+			}
+		case key.Matches(msg, sessionKeys.Delete):
+			if len(s.sessions) > 0 {
+				s.confirmDelete = true
+			}
+			return s, nil
+		case key.Matches(msg, sessionKeys.Tag):
+			s.SetTag(nextTag(sessionTags(s.listed()), s.tag))
+			return s, nil
+		case key.Matches(msg, sessionKeys.Pin):
+			if len(s.sessions) > 0 {
+				sess := s.sessions[s.selectedIdx]
+				return s, util.CmdHandler(PinSessionMsg{SessionID: sess.ID, Pinned: !sess.Pinned})
+			}
+			return s, nil
+		case key.Matches(msg, sessionKeys.Archive):
+			if len(s.sessions) > 0 {
+				sess := s.sessions[s.selectedIdx]
+				return s, util.CmdHandler(ArchiveSessionMsg{SessionID: sess.ID, Archived: !sess.Archived})
+			}
+			return s, nil
+		case key.Matches(msg, sessionKeys.Archived):
+			s.ShowArchived(!s.archived)
+			return s, nil
 		case key.Matches(msg, sessionKeys.Escape):
 			return s, util.CmdHandler(CloseSessionDialogMsg{})
 		}
+	case tea.MouseMsg:
+		if msg.Action != tea.MouseActionRelease || msg.Button != tea.MouseButtonLeft || s.renaming {
+			return s, nil
+		}
+		z := zone.Get(sessionListZone)
+		if z == nil || !z.InBounds(msg) {
+			return s, nil
+		}
+		_, y := z.Pos(msg)
+		startIdx, endIdx := s.visibleRange()
+		if idx := startIdx + y; idx < endIdx {
+			s.confirmDelete = false
+			s.selectedIdx = idx
+			return s, util.CmdHandler(SessionSelectedMsg{
+				Session: s.sessions[idx],
+			})
+		}
 	case tea.WindowSizeMsg:
 		s.width = msg.Width
 		s.height = msg.Height
@@ -105,48 +245,77 @@ func (s *sessionDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	return s, nil
 }
 
+func (s *sessionDialogCmp) updateRename(msg tea.KeyMsg) tea.Cmd {
+	switch {
+	case key.Matches(msg, sessionKeys.Enter):
+		s.renaming = false
+		title := strings.TrimSpace(s.titleInput.Value())
+		if title == "" || len(s.sessions) == 0 {
+			return nil
+		}
+		return util.CmdHandler(RenameSessionMsg{
+			SessionID: s.sessions[s.selectedIdx].ID,
+			Title:     title,
+		})
+	case key.Matches(msg, sessionKeys.Escape):
+		s.renaming = false
+		return nil
+	}
+	var cmd tea.Cmd
+	s.titleInput, cmd = s.titleInput.Update(msg)
+	return cmd
+}
+
+func newTitleInput(title string) textinput.Model {
+	t := theme.CurrentTheme()
+	ti := textinput.New()
+	ti.Prompt = ""
+	ti.CharLimit = 100
+	ti.PromptStyle = ti.PromptStyle.Background(t.Background())
+	ti.TextStyle = ti.TextStyle.Background(t.Background()).Foreground(t.Text())
+	ti.SetValue(title)
+	ti.CursorEnd()
+	ti.Focus()
+	return ti
+}
+
 func (s *sessionDialogCmp) View() string {
 	t := theme.CurrentTheme()
 	baseStyle := styles.BaseStyle()
 	
 	if len(s.sessions) == 0 {
+		empty := "No sessions available"
+		switch {
+		case s.archived:
+			empty = "No archived sessions, A lists the others"
+		case len(s.all) > 0:
+			empty = "All sessions are archived, A lists them"
+		}
 		return baseStyle.Padding(1, 2).
 			Border(lipgloss.RoundedBorder()).
 			BorderBackground(t.Background()).
 			BorderForeground(t.TextMuted()).
-			Width(40).
-			Render("No sessions available")
+			Width(max(40, lipgloss.Width(empty)+6)).
+			Render(empty)
 	}
 
-	// Calculate max width needed for session titles
+	// Calculate max width needed for session titles and their last activity
 	maxWidth := 40 // Minimum width
 	for _, sess := range s.sessions {
-		if len(sess.Title) > maxWidth-4 { // Account for padding
-			maxWidth = len(sess.Title) + 4
+		width := len(sess.Title) + len(formatLastActive(sess.UpdatedAt)) + 6 // Account for padding
+		if len(sess.Tags) > 0 {
+			width += len(strings.Join(sess.Tags, " #")) + 2
+		}
+		if width > maxWidth {
+			maxWidth = width
 		}
 	}
 
 	maxWidth = max(30, min(maxWidth, s.width-15)) // Limit width to avoid overflow
 
-	// Limit height to avoid taking up too much screen space
-	maxVisibleSessions := min(10, len(s.sessions))
-
 	// Build the session list
-	sessionItems := make([]string, 0, maxVisibleSessions)
-	startIdx := 0
-
-	// If we have more sessions than can be displayed, adjust the start index
-	if len(s.sessions) > maxVisibleSessions {
-		// Center the selected item when possible
-		halfVisible := maxVisibleSessions / 2
-		if s.selectedIdx >= halfVisible && s.selectedIdx < len(s.sessions)-halfVisible {
-			startIdx = s.selectedIdx - halfVisible
-		} else if s.selectedIdx >= len(s.sessions)-halfVisible {
-			startIdx = len(s.sessions) - maxVisibleSessions
-		}
-	}
-
-	endIdx := min(startIdx+maxVisibleSessions, len(s.sessions))
+	startIdx, endIdx := s.visibleRange()
+	sessionItems := make([]string, 0, endIdx-startIdx)
 
 	for i := startIdx; i < endIdx; i++ {
 		sess := s.sessions[i]
@@ -159,22 +328,73 @@ func (s *sessionDialogCmp) View() string {
 				Bold(true)
 		}
 
-		sessionItems = append(sessionItems, itemStyle.Padding(0, 1).Render(sess.Title))
+		if i == s.selectedIdx && s.renaming {
+			s.titleInput.Width = maxWidth - 3
+			sessionItems = append(sessionItems, itemStyle.Padding(0, 1).Render(s.titleInput.View()))
+			continue
+		}
+
+		lastActive := formatLastActive(sess.UpdatedAt)
+		room := maxWidth - lipgloss.Width(lastActive) - 4
+		sessionTitle := sess.Title
+		if sess.Pinned {
+			sessionTitle = "★ " + sessionTitle
+		}
+		sessionTitle = util.Truncate(sessionTitle, room)
+		var tags string
+		if len(sess.Tags) > 0 {
+			if rest := room - lipgloss.Width(sessionTitle) - 1; rest > 5 {
+				tags = " " + util.Truncate("#"+strings.Join(sess.Tags, " #"), rest)
+			}
+		}
+		gap := max(maxWidth-lipgloss.Width(sessionTitle)-lipgloss.Width(tags)-lipgloss.Width(lastActive)-2, 1)
+		tagStyle := baseStyle.Foreground(t.TextMuted())
+		if i == s.selectedIdx {
+			tagStyle = tagStyle.Background(t.Primary()).Foreground(t.Background())
+		}
+		sessionItems = append(sessionItems, itemStyle.Padding(0, 1).Render(
+			sessionTitle+tagStyle.Render(tags+strings.Repeat(" ", gap))+lastActive,
+		))
 	}
 
+	heading := "Switch Session"
+	if s.archived {
+		heading = "Archived Sessions"
+	}
+	if s.tag != "" {
+		heading += " · #" + s.tag
+	}
 	title := baseStyle.
 		Foreground(t.Primary()).
 		Bold(true).
 		Width(maxWidth).
 		Padding(0, 1).
-		Render("Switch Session")
+		Render(heading)
+
+	var footer string
+	switch {
+	case s.confirmDelete:
+		footer = baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.Error()).
+			Render(fmt.Sprintf("Delete %q and its messages? (y/n)", s.sessions[s.selectedIdx].Title))
+	case s.renaming:
+		footer = baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.TextMuted()).
+			Render("enter save · esc cancel")
+	default:
+		archive := "a archive"
+		if s.archived {
+			archive = "a restore"
+		}
+		footer = baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.TextMuted()).
+			Render("r rename · d delete · p pin · " + archive + " · t tag · A archived")
+	}
 
 	content := lipgloss.JoinVertical(
 		lipgloss.Left,
 		title,
 		baseStyle.Width(maxWidth).Render(""),
-		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, sessionItems...)),
+		zone.Mark(sessionListZone, baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, sessionItems...))),
 		baseStyle.Width(maxWidth).Render(""),
+		footer,
 	)
 
 	return baseStyle.Padding(1, 2).
@@ -185,16 +405,39 @@ func (s *sessionDialogCmp) View() string {
 		Render(content)
 }
 
+// visibleRange returns the sessions that fit in the dialog, keeping the
+// selected one in the middle when possible
+func (s *sessionDialogCmp) visibleRange() (int, int) {
+	// Limit height to avoid taking up too much screen space
+	maxVisibleSessions := min(10, len(s.sessions))
+	startIdx := 0
+
+	// If we have more sessions than can be displayed, adjust the start index
+	if len(s.sessions) > maxVisibleSessions {
+		halfVisible := maxVisibleSessions / 2
+		if s.selectedIdx >= halfVisible && s.selectedIdx < len(s.sessions)-halfVisible {
+			startIdx = s.selectedIdx - halfVisible
+		} else if s.selectedIdx >= len(s.sessions)-halfVisible {
+			startIdx = len(s.sessions) - maxVisibleSessions
+		}
+	}
+
+	return startIdx, min(startIdx+maxVisibleSessions, len(s.sessions))
+}
+
 func (s *sessionDialogCmp) BindingKeys() []key.Binding {
 	return layout.KeyMapToSlice(sessionKeys)
 }
 
 func (s *sessionDialogCmp) SetSessions(sessions []session.Session) {
-	s.sessions = sessions
+	s.all = sessions
+	s.renaming = false
+	s.confirmDelete = false
+	s.filter()
 
 	// If we have a selected session ID, find its index
 	if s.selectedSessionID != "" {
-		for i, sess := range sessions {
+		for i, sess := range s.sessions {
 			if sess.ID == s.selectedSessionID {
 				s.selectedIdx = i
 				return
@@ -220,6 +463,95 @@ func (s *sessionDialogCmp) SetSelectedSession(sessionID string) {
 	}
 }
 
+func (s *sessionDialogCmp) ShowArchived(archived bool) {
+	s.archived = archived
+	s.SetTag(s.tag)
+}
+
+func (s *sessionDialogCmp) SetTag(tag string) {
+	s.tag = tag
+	s.confirmDelete = false
+	s.filter()
+	s.selectedIdx = 0
+	for i, sess := range s.sessions {
+		if sess.ID == s.selectedSessionID {
+			s.selectedIdx = i
+		}
+	}
+}
+
+// listed returns the archived sessions or the others, whichever are shown
+func (s *sessionDialogCmp) listed() []session.Session {
+	var listed []session.Session
+	for _, sess := range s.all {
+		if sess.Archived == s.archived {
+			listed = append(listed, sess)
+		}
+	}
+	return listed
+}
+
+// filter lists the sessions with the tag, the filter is dropped when no
+// listed session has the tag anymore
+func (s *sessionDialogCmp) filter() {
+	listed := s.listed()
+	if !slices.Contains(sessionTags(listed), s.tag) {
+		s.tag = ""
+	}
+	if s.tag == "" {
+		s.sessions = listed
+		return
+	}
+	s.sessions = nil
+	for _, sess := range listed {
+		if sess.HasTag(s.tag) {
+			s.sessions = append(s.sessions, sess)
+		}
+	}
+}
+
+// sessionTags returns the tags of the sessions, sorted and without duplicates
+func sessionTags(sessions []session.Session) []string {
+	var tags []string
+	for _, sess := range sessions {
+		tags = append(tags, sess.Tags...)
+	}
+	slices.Sort(tags)
+	return slices.Compact(tags)
+}
+
+// nextTag returns the tag after current, no tag after the last one so the
+// filter cycles through all the sessions too
+func nextTag(tags []string, current string) string {
+	if current == "" {
+		if len(tags) == 0 {
+			return ""
+		}
+		return tags[0]
+	}
+	i := slices.Index(tags, current)
+	if i < 0 || i == len(tags)-1 {
+		return ""
+	}
+	return tags[i+1]
+}
+
+// formatLastActive describes how long ago a session was last active
+func formatLastActive(updatedAt int64) string {
+	elapsed := time.Since(time.Unix(updatedAt, 0))
+	switch {
+	case elapsed < time.Minute:
+		return "just now"
+	case elapsed < time.Hour:
+		return fmt.Sprintf("%dm ago", int(elapsed.Minutes()))
+	case elapsed < 24*time.Hour:
+		return fmt.Sprintf("%dh ago", int(elapsed.Hours()))
+	case elapsed < 7*24*time.Hour:
+		return fmt.Sprintf("%dd ago", int(elapsed.Hours()/24))
+	}
+	return time.Unix(updatedAt, 0).Format("Jan 2, 2006")
+}
+
 // NewSessionDialogCmp creates a new session switching dialog
 func NewSessionDialogCmp() SessionDialog {
 	return &sessionDialogCmp{
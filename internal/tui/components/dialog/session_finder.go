@@ -0,0 +1,211 @@
+package dialog
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/zhenbah/cryoncode/internal/session"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+const maxVisibleFinderResults = 10
+
+// SearchSessionsMsg is sent when the query of the session finder changes
+type SearchSessionsMsg struct {
+	Query string
+}
+
+// OpenSessionMsg is sent when a session is picked in the session finder
+type OpenSessionMsg struct {
+	SessionID string
+}
+
+// CloseSessionFinderMsg is sent when the session finder is closed
+type CloseSessionFinderMsg struct{}
+
+// SessionFinderDialog interface for the dialog that finds sessions by their
+// title or first message
+type SessionFinderDialog interface {
+	tea.Model
+	layout.Bindings
+	// Reset clears the query, the results of the empty query are searched
+	Reset() tea.Cmd
+	SetResults(query string, results []session.SearchResult)
+}
+
+type sessionFinderDialogCmp struct {
+	input       textinput.Model
+	results     []session.SearchResult
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type sessionFinderKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+}
+
+var sessionFinderKeys = sessionFinderKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up", "ctrl+p"),
+		key.WithHelp("↑", "previous session"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down", "ctrl+n"),
+		key.WithHelp("↓", "next session"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "open session"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc", "ctrl+k"),
+		key.WithHelp("esc", "close"),
+	),
+}
+
+func (f *sessionFinderDialogCmp) Init() tea.Cmd {
+	return textinput.Blink
+}
+
+func (f *sessionFinderDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, sessionFinderKeys.Up):
+			if f.selectedIdx > 0 {
+				f.selectedIdx--
+			}
+			return f, nil
+		case key.Matches(msg, sessionFinderKeys.Down):
+			if f.selectedIdx < len(f.results)-1 {
+				f.selectedIdx++
+			}
+			return f, nil
+		case key.Matches(msg, sessionFinderKeys.Enter):
+			if len(f.results) > 0 {
+				return f, util.CmdHandler(OpenSessionMsg{
+					SessionID: f.results[f.selectedIdx].ID,
+				})
+			}
+			return f, nil
+		case key.Matches(msg, sessionFinderKeys.Escape):
+			return f, util.CmdHandler(CloseSessionFinderMsg{})
+		}
+		query := f.input.Value()
+		var cmd tea.Cmd
+		f.input, cmd = f.input.Update(msg)
+		if f.input.Value() != query {
+			return f, tea.Batch(cmd, util.CmdHandler(SearchSessionsMsg{Query: f.input.Value()}))
+		}
+		return f, cmd
+	case tea.WindowSizeMsg:
+		f.width = msg.Width
+		f.height = msg.Height
+	}
+	return f, nil
+}
+
+func (f *sessionFinderDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+	maxWidth := max(50, min(90, f.width-15))
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Find Session")
+
+	f.input.Width = maxWidth - 5
+	input := baseStyle.Width(maxWidth).Padding(0, 1).Render(f.input.View())
+
+	// Keep the selected result visible
+	startIdx := max(0, f.selectedIdx-maxVisibleFinderResults+1)
+	endIdx := min(startIdx+maxVisibleFinderResults, len(f.results))
+
+	items := make([]string, 0, maxVisibleFinderResults)
+	for i := startIdx; i < endIdx; i++ {
+		result := f.results[i]
+		itemStyle := baseStyle.Width(maxWidth).Padding(0, 1)
+		detailStyle := baseStyle.Foreground(t.TextMuted())
+		if i == f.selectedIdx {
+			itemStyle = itemStyle.Background(t.Primary()).Foreground(t.Background()).Bold(true)
+			detailStyle = detailStyle.Background(t.Primary()).Foreground(t.Background())
+		}
+
+		lastActive := formatLastActive(result.UpdatedAt)
+		room := maxWidth - lipgloss.Width(lastActive) - 4
+		sessionTitle := util.Truncate(result.Title, room)
+		// The first message follows the title when there is room for it
+		var firstMessage string
+		if rest := room - lipgloss.Width(sessionTitle) - 3; rest > 10 && result.FirstMessage != "" {
+			firstMessage = " · " + util.Truncate(strings.Join(strings.Fields(result.FirstMessage), " "), rest)
+		}
+		gap := max(maxWidth-lipgloss.Width(sessionTitle)-lipgloss.Width(firstMessage)-lipgloss.Width(lastActive)-2, 1)
+		items = append(items, itemStyle.Render(
+			sessionTitle+
+				detailStyle.Render(firstMessage+strings.Repeat(" ", gap))+
+				lastActive,
+		))
+	}
+	if len(items) == 0 {
+		items = append(items, baseStyle.Width(maxWidth).Padding(0, 1).Foreground(t.TextMuted()).
+			Render("No matching sessions"))
+	}
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		input,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, items...)),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+func (f *sessionFinderDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(sessionFinderKeys)
+}
+
+func (f *sessionFinderDialogCmp) Reset() tea.Cmd {
+	f.input = newTitleInput("")
+	f.input.Placeholder = "Search titles and first messages"
+	f.input.CharLimit = 200
+	f.selectedIdx = 0
+	return tea.Batch(textinput.Blink, util.CmdHandler(SearchSessionsMsg{}))
+}
+
+// SetResults shows the sessions found for the query, results of an older
+// query are ignored
+func (f *sessionFinderDialogCmp) SetResults(query string, results []session.SearchResult) {
+	if query != f.input.Value() {
+		return
+	}
+	f.results = results
+	f.selectedIdx = 0
+}
+
+// NewSessionFinderDialogCmp creates a new dialog to find sessions
+func NewSessionFinderDialogCmp() SessionFinderDialog {
+	return &sessionFinderDialogCmp{
+		input: newTitleInput(""),
+	}
+}
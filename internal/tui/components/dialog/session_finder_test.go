@@ -0,0 +1,20 @@
+package dialog
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+func TestSessionFinderIgnoresStaleResults(t *testing.T) {
+	f := NewSessionFinderDialogCmp().(*sessionFinderDialogCmp)
+	f.Reset()
+	f.input.SetValue("fix")
+
+	f.SetResults("fi", []session.SearchResult{{ID: "old"}})
+	assert.Empty(t, f.results)
+
+	f.SetResults("fix", []session.SearchResult{{ID: "new"}})
+	assert.Equal(t, []session.SearchResult{{ID: "new"}}, f.results)
+}
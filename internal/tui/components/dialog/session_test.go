@@ -0,0 +1,55 @@
+package dialog
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/zhenbah/cryoncode/internal/session"
+)
+
+func TestNextTag(t *testing.T) {
+	tags := []string{"api", "docs"}
+	assert.Equal(t, "api", nextTag(tags, ""))
+	assert.Equal(t, "docs", nextTag(tags, "api"))
+	assert.Equal(t, "", nextTag(tags, "docs"))
+	assert.Equal(t, "", nextTag(nil, ""))
+}
+
+func TestSessionDialogTagFilter(t *testing.T) {
+	d := NewSessionDialogCmp().(*sessionDialogCmp)
+	d.SetSessions([]session.Session{
+		{ID: "1", Tags: []string{"docs"}},
+		{ID: "2"},
+		{ID: "3", Tags: []string{"api", "docs"}},
+	})
+	assert.Len(t, d.sessions, 3)
+
+	d.SetTag("docs")
+	assert.Equal(t, "docs", d.tag)
+	assert.Len(t, d.sessions, 2)
+
+	// The filter is dropped once no session has the tag
+	d.SetSessions([]session.Session{{ID: "2"}})
+	assert.Equal(t, "", d.tag)
+	assert.Len(t, d.sessions, 1)
+}
+
+func TestSessionDialogArchived(t *testing.T) {
+	d := NewSessionDialogCmp().(*sessionDialogCmp)
+	d.SetSessions([]session.Session{
+		{ID: "1", Pinned: true},
+		{ID: "2", Archived: true, Tags: []string{"old"}},
+		{ID: "3"},
+	})
+	assert.Len(t, d.sessions, 2)
+
+	// Tags of archived sessions only filter the archived list
+	d.SetTag("old")
+	assert.Equal(t, "", d.tag)
+
+	d.ShowArchived(true)
+	d.SetTag("old")
+	assert.Equal(t, "old", d.tag)
+	assert.Equal(t, "2", d.sessions[0].ID)
+	assert.Len(t, d.sessions, 1)
+}
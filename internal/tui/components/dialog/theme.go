@@ -138,8 +138,8 @@ func (t *themeDialogCmp) View() string {
 	// Calculate max width needed for theme names
 	maxWidth := 40 // Minimum width
 	for _, themeName := range t.themes {
-		if len(themeName) > maxWidth-4 { // Account for padding
-			maxWidth = len(themeName) + 4
+		if lipgloss.Width(themeName) > maxWidth-4 { // Account for padding
+			maxWidth = lipgloss.Width(themeName) + 4
 		}
 	}
 
@@ -0,0 +1,207 @@
+package dialog
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/charmbracelet/x/ansi"
+	"github.com/zhenbah/cryoncode/internal/message"
+	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+	"github.com/zhenbah/cryoncode/internal/tui/util"
+)
+
+// UndoSelectedMsg is sent when the point to undo the file changes from is
+// selected
+type UndoSelectedMsg struct {
+	MessageID string
+}
+
+// CloseUndoDialogMsg is sent when the undo dialog is closed
+type CloseUndoDialogMsg struct{}
+
+// UndoDialog interface for the dialog that picks the prompt to undo the file
+// changes from
+type UndoDialog interface {
+	tea.Model
+	layout.Bindings
+	SetMessages(messages []message.Message)
+}
+
+type undoDialogCmp struct {
+	prompts     []message.Message
+	selectedIdx int
+	width       int
+	height      int
+}
+
+type undoKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Enter  key.Binding
+	Escape key.Binding
+	J      key.Binding
+	K      key.Binding
+}
+
+var undoKeys = undoKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up"),
+		key.WithHelp("↑", "previous prompt"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down"),
+		key.WithHelp("↓", "next prompt"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "undo changes since prompt"),
+	),
+	Escape: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "close"),
+	),
+	J: key.NewBinding(
+		key.WithKeys("j"),
+		key.WithHelp("j", "next prompt"),
+	),
+	K: key.NewBinding(
+		key.WithKeys("k"),
+		key.WithHelp("k", "previous prompt"),
+	),
+}
+
+func (u *undoDialogCmp) Init() tea.Cmd {
+	return nil
+}
+
+func (u *undoDialogCmp) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, undoKeys.Up) || key.Matches(msg, undoKeys.K):
+			if u.selectedIdx > 0 {
+				u.selectedIdx--
+			}
+			return u, nil
+		case key.Matches(msg, undoKeys.Down) || key.Matches(msg, undoKeys.J):
+			if u.selectedIdx < len(u.prompts)-1 {
+				u.selectedIdx++
+			}
+			return u, nil
+		case key.Matches(msg, undoKeys.Enter):
+			if len(u.prompts) > 0 {
+				return u, util.CmdHandler(UndoSelectedMsg{
+					MessageID: u.prompts[u.selectedIdx].ID,
+				})
+			}
+		case key.Matches(msg, undoKeys.Escape):
+			return u, util.CmdHandler(CloseUndoDialogMsg{})
+		}
+	case tea.WindowSizeMsg:
+		u.width = msg.Width
+		u.height = msg.Height
+	}
+	return u, nil
+}
+
+func (u *undoDialogCmp) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+
+	if len(u.prompts) == 0 {
+		return baseStyle.Padding(1, 2).
+			Border(lipgloss.RoundedBorder()).
+			BorderBackground(t.Background()).
+			BorderForeground(t.TextMuted()).
+			Width(40).
+			Render("No prompts to undo")
+	}
+
+	maxWidth := max(40, min(80, u.width-15))
+	maxVisiblePrompts := min(10, len(u.prompts))
+
+	// Keep the selected prompt visible, centering it when possible
+	startIdx := 0
+	if len(u.prompts) > maxVisiblePrompts {
+		halfVisible := maxVisiblePrompts / 2
+		if u.selectedIdx >= halfVisible && u.selectedIdx < len(u.prompts)-halfVisible {
+			startIdx = u.selectedIdx - halfVisible
+		} else if u.selectedIdx >= len(u.prompts)-halfVisible {
+			startIdx = len(u.prompts) - maxVisiblePrompts
+		}
+	}
+	endIdx := min(startIdx+maxVisiblePrompts, len(u.prompts))
+
+	promptItems := make([]string, 0, maxVisiblePrompts)
+	for i := startIdx; i < endIdx; i++ {
+		itemStyle := baseStyle.Width(maxWidth)
+		if i == u.selectedIdx {
+			itemStyle = itemStyle.
+				Background(t.Primary()).
+				Foreground(t.Background()).
+				Bold(true)
+		}
+		promptItems = append(promptItems, itemStyle.Padding(0, 1).Render(promptSummary(u.prompts[i], maxWidth-2)))
+	}
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(maxWidth).
+		Padding(0, 1).
+		Render("Undo File Changes Since")
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		baseStyle.Width(maxWidth).Render(""),
+		baseStyle.Width(maxWidth).Render(lipgloss.JoinVertical(lipgloss.Left, promptItems...)),
+		baseStyle.Width(maxWidth).Render(""),
+	)
+
+	return baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Width(lipgloss.Width(content) + 4).
+		Render(content)
+}
+
+// promptSummary returns the first line of the prompt, cut to the width
+func promptSummary(msg message.Message, width int) string {
+	text := strings.TrimSpace(msg.Content().String())
+	if line, _, found := strings.Cut(text, "\n"); found {
+		text = line + "…"
+	}
+	if width > 1 {
+		text = ansi.Truncate(text, width, "…")
+	}
+	return text
+}
+
+func (u *undoDialogCmp) BindingKeys() []key.Binding {
+	return layout.KeyMapToSlice(undoKeys)
+}
+
+// SetMessages sets the prompts to choose from out of the session messages and
+// selects the latest one
+func (u *undoDialogCmp) SetMessages(messages []message.Message) {
+	u.prompts = u.prompts[:0]
+	for _, msg := range messages {
+		if msg.Role == message.User {
+			u.prompts = append(u.prompts, msg)
+		}
+	}
+	u.selectedIdx = max(0, len(u.prompts)-1)
+}
+
+// NewUndoDialogCmp creates a new undo dialog
+func NewUndoDialogCmp() UndoDialog {
+	return &undoDialogCmp{
+		prompts: []message.Message{},
+	}
+}
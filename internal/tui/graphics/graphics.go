@@ -0,0 +1,253 @@
+// Package graphics draws images in terminals that support a graphics protocol.
+package graphics
+
+import (
+	"bytes"
+	"encoding/base64"
+	"fmt"
+	"hash/fnv"
+	"image"
+	"image/color/palette"
+	"image/draw"
+	_ "image/gif"  // register the GIF decoder
+	_ "image/jpeg" // register the JPEG decoder
+	"image/png"
+	"os"
+	"strings"
+)
+
+// Protocol is the escape sequence protocol used to draw images.
+type Protocol string
+
+const (
+	ProtocolAuto   Protocol = "auto"
+	ProtocolNone   Protocol = "none"
+	ProtocolKitty  Protocol = "kitty"
+	ProtocolITerm2 Protocol = "iterm2"
+	ProtocolSixel  Protocol = "sixel"
+)
+
+const (
+	// maxImageSize is the largest image drawn, larger ones are only named
+	maxImageSize = 10 * 1024 * 1024
+
+	// Terminals don't tell the size of a cell in pixels without a query, so
+	// a common one is assumed to keep the aspect ratio of images
+	cellWidth  = 10
+	cellHeight = 20
+
+	kittyChunkSize   = 4096
+	kittyPlaceholder = '\U0010EEEE'
+)
+
+// kittyDiacritics encode the row and column of a placeholder cell, from the
+// kitty graphics protocol specification.
+var kittyDiacritics = []rune{
+	0x0305, 0x030D, 0x030E, 0x0310, 0x0312, 0x033D, 0x033E, 0x033F,
+	0x0346, 0x034A, 0x034B, 0x034C, 0x0350, 0x0351, 0x0352, 0x0357,
+	0x035B, 0x0363, 0x0364, 0x0365, 0x0366, 0x0367, 0x0368, 0x0369,
+	0x036A, 0x036B, 0x036C, 0x036D, 0x036E, 0x036F,
+}
+
+// MaxRows is the most lines an image can take.
+const MaxRows = 15
+
+// Detect returns the protocol to use for the setting, guessing the one the
+// terminal supports from its environment for "auto" or an empty setting.
+func Detect(setting string) Protocol {
+	switch p := Protocol(strings.ToLower(setting)); p {
+	case ProtocolNone, ProtocolKitty, ProtocolITerm2, ProtocolSixel:
+		return p
+	}
+	return detectFromEnv(os.Getenv)
+}
+
+func detectFromEnv(getenv func(string) string) Protocol {
+	// Multiplexers need the sequences wrapped to pass them through, so images
+	// are only drawn there when the protocol is set explicitly
+	if getenv("TMUX") != "" || strings.HasPrefix(getenv("TERM"), "screen") {
+		return ProtocolNone
+	}
+	term := getenv("TERM")
+	program := getenv("TERM_PROGRAM")
+	switch {
+	case getenv("KITTY_WINDOW_ID") != "" || term == "xterm-kitty" || term == "xterm-ghostty" || program == "ghostty":
+		return ProtocolKitty
+	case program == "iTerm.app" || program == "WezTerm" || getenv("LC_TERMINAL") == "iTerm2":
+		return ProtocolITerm2
+	case strings.Contains(term, "sixel") || term == "foot" || strings.HasPrefix(term, "foot-") || term == "mlterm":
+		return ProtocolSixel
+	}
+	return ProtocolNone
+}
+
+// Render returns the image drawn in at most maxCols by maxRows cells, one
+// string per line. It returns false when the image can't be drawn, for
+// instance because the terminal has no graphics support or the format is
+// unknown, and a text placeholder should be shown instead.
+func Render(protocol Protocol, data []byte, maxCols, maxRows int) ([]string, bool) {
+	if protocol == ProtocolNone || protocol == ProtocolAuto || len(data) == 0 || len(data) > maxImageSize {
+		return nil, false
+	}
+	img, format, err := image.Decode(bytes.NewReader(data))
+	if err != nil {
+		return nil, false
+	}
+	cols, rows := fitCells(img.Bounds().Dx(), img.Bounds().Dy(), maxCols, maxRows)
+	if cols == 0 || rows == 0 {
+		return nil, false
+	}
+
+	switch protocol {
+	case ProtocolKitty:
+		if format != "png" {
+			var buf bytes.Buffer
+			if err := png.Encode(&buf, img); err != nil {
+				return nil, false
+			}
+			data = buf.Bytes()
+		}
+		return kittyLines(data, cols, rows), true
+	case ProtocolITerm2:
+		seq := fmt.Sprintf("\x1b]1337;File=inline=1;size=%d;width=%d;height=%d;preserveAspectRatio=1:%s\a",
+			len(data), cols, rows, base64.StdEncoding.EncodeToString(data))
+		return reservedLines(seq, cols, rows), true
+	case ProtocolSixel:
+		return reservedLines(encodeSixel(img, cols*cellWidth, rows*cellHeight), cols, rows), true
+	}
+	return nil, false
+}
+
+// fitCells returns the cells an image of the given size in pixels takes,
+// shrinking it to fit and keeping its aspect ratio.
+func fitCells(width, height, maxCols, maxRows int) (int, int) {
+	if width <= 0 || height <= 0 {
+		return 0, 0
+	}
+	maxRows = min(maxRows, len(kittyDiacritics))
+	cols := min(maxCols, (width+cellWidth-1)/cellWidth)
+	rows := (cols*cellWidth*height/width + cellHeight - 1) / cellHeight
+	if rows > maxRows {
+		rows = maxRows
+		cols = min(cols, max(1, rows*cellHeight*width/height/cellWidth))
+	}
+	return max(cols, 0), max(rows, 1)
+}
+
+// kittyLines transmits the image with a virtual placement and returns the
+// Unicode placeholders it is drawn in. Placeholders are ordinary text, so the
+// image moves with them when the messages scroll.
+func kittyLines(data []byte, cols, rows int) []string {
+	h := fnv.New32a()
+	h.Write(data)
+	id := h.Sum32() & 0xFFFFFF
+	if id == 0 {
+		id = 1
+	}
+
+	var transmit strings.Builder
+	encoded := base64.StdEncoding.EncodeToString(data)
+	for i := 0; i < len(encoded); i += kittyChunkSize {
+		chunk := encoded[i:min(i+kittyChunkSize, len(encoded))]
+		more := 0
+		if i+kittyChunkSize < len(encoded) {
+			more = 1
+		}
+		if i == 0 {
+			fmt.Fprintf(&transmit, "\x1b_Ga=T,U=1,f=100,q=2,i=%d,c=%d,r=%d,m=%d;%s\x1b\\", id, cols, rows, more, chunk)
+		} else {
+			fmt.Fprintf(&transmit, "\x1b_Gm=%d;%s\x1b\\", more, chunk)
+		}
+	}
+
+	// The image id is given by the foreground color of the placeholders
+	fg := fmt.Sprintf("\x1b[38;2;%d;%d;%dm", id>>16&0xFF, id>>8&0xFF, id&0xFF)
+	lines := make([]string, rows)
+	for row := range rows {
+		first := string([]rune{kittyPlaceholder, kittyDiacritics[row], kittyDiacritics[0]})
+		lines[row] = fg + first + strings.Repeat(string(kittyPlaceholder), cols-1) + "\x1b[39m"
+	}
+	lines[0] = transmit.String() + lines[0]
+	return lines
+}
+
+// reservedLines draws the image with the sequence and keeps the lines it
+// covers blank. The cursor is saved and restored around the image, so the
+// lines that follow are written where they belong.
+func reservedLines(seq string, cols, rows int) []string {
+	blank := strings.Repeat(" ", cols)
+	lines := make([]string, rows)
+	for i := range lines {
+		lines[i] = blank
+	}
+	lines[0] = "\x1b7" + seq + "\x1b8" + blank
+	return lines
+}
+
+// encodeSixel scales the image to the size in pixels and encodes it as sixels
+// with a 256 color palette.
+func encodeSixel(img image.Image, width, height int) string {
+	// Nearest neighbour scaling is enough for a preview
+	bounds := img.Bounds()
+	scaled := image.NewRGBA(image.Rect(0, 0, width, height))
+	for y := range height {
+		for x := range width {
+			scaled.Set(x, y, img.At(bounds.Min.X+x*bounds.Dx()/width, bounds.Min.Y+y*bounds.Dy()/height))
+		}
+	}
+	paletted := image.NewPaletted(scaled.Bounds(), palette.Plan9)
+	draw.FloydSteinberg.Draw(paletted, paletted.Bounds(), scaled, image.Point{})
+
+	var b strings.Builder
+	fmt.Fprintf(&b, "\x1bPq\"1;1;%d;%d", width, height)
+	for i, c := range paletted.Palette {
+		r, g, bl, _ := c.RGBA()
+		fmt.Fprintf(&b, "#%d;2;%d;%d;%d", i, r*100/0xFFFF, g*100/0xFFFF, bl*100/0xFFFF)
+	}
+	for top := 0; top < height; top += 6 {
+		// Each band of six rows is drawn once per color it uses
+		used := make(map[uint8]bool)
+		for y := top; y < min(top+6, height); y++ {
+			for x := range width {
+				used[paletted.ColorIndexAt(x, y)] = true
+			}
+		}
+		for idx := range len(paletted.Palette) {
+			if !used[uint8(idx)] {
+				continue
+			}
+			fmt.Fprintf(&b, "#%d", idx)
+			row := make([]byte, width)
+			for x := range width {
+				var bits byte
+				for dy := 0; dy < 6 && top+dy < height; dy++ {
+					if paletted.ColorIndexAt(x, top+dy) == uint8(idx) {
+						bits |= 1 << dy
+					}
+				}
+				row[x] = 63 + bits
+			}
+			writeSixelRun(&b, row)
+			b.WriteByte('$')
+		}
+		b.WriteByte('-')
+	}
+	b.WriteString("\x1b\\")
+	return b.String()
+}
+
+// writeSixelRun writes the sixels, repeating runs with the ! command.
+func writeSixelRun(b *strings.Builder, row []byte) {
+	for i := 0; i < len(row); {
+		j := i
+		for j < len(row) && row[j] == row[i] {
+			j++
+		}
+		if n := j - i; n > 3 {
+			fmt.Fprintf(b, "!%d%c", n, row[i])
+		} else {
+			b.Write(row[i:j])
+		}
+		i = j
+	}
+}
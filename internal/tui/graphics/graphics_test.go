@@ -0,0 +1,82 @@
+package graphics
+
+import (
+	"bytes"
+	"image"
+	"image/color"
+	"image/png"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestDetectFromEnv(t *testing.T) {
+	env := func(vars map[string]string) func(string) string {
+		return func(key string) string { return vars[key] }
+	}
+	assert.Equal(t, ProtocolKitty, detectFromEnv(env(map[string]string{"TERM": "xterm-kitty"})))
+	assert.Equal(t, ProtocolITerm2, detectFromEnv(env(map[string]string{"TERM_PROGRAM": "iTerm.app"})))
+	assert.Equal(t, ProtocolSixel, detectFromEnv(env(map[string]string{"TERM": "foot"})))
+	assert.Equal(t, ProtocolNone, detectFromEnv(env(map[string]string{"TERM": "xterm-256color"})))
+	assert.Equal(t, ProtocolNone, detectFromEnv(env(map[string]string{"TERM": "xterm-kitty", "TMUX": "/tmp/tmux"})))
+}
+
+func TestDetectPrefersSetting(t *testing.T) {
+	assert.Equal(t, ProtocolSixel, Detect("sixel"))
+	assert.Equal(t, ProtocolNone, Detect("none"))
+}
+
+func TestFitCells(t *testing.T) {
+	cols, rows := fitCells(200, 100, 80, 15)
+	assert.Equal(t, 20, cols)
+	assert.Equal(t, 5, rows)
+
+	// Tall images are shrunk to the maximum height
+	cols, rows = fitCells(100, 1000, 80, 15)
+	assert.Equal(t, 15, rows)
+	assert.Equal(t, 3, cols)
+
+	// Wide images are shrunk to the maximum width
+	cols, _ = fitCells(4000, 100, 80, 15)
+	assert.Equal(t, 80, cols)
+}
+
+func TestRender(t *testing.T) {
+	img := image.NewRGBA(image.Rect(0, 0, 40, 40))
+	for x := range 40 {
+		img.Set(x, x, color.RGBA{R: 255, A: 255})
+	}
+	var buf bytes.Buffer
+	require.NoError(t, png.Encode(&buf, img))
+
+	t.Run("kitty draws placeholders", func(t *testing.T) {
+		lines, ok := Render(ProtocolKitty, buf.Bytes(), 80, 15)
+		require.True(t, ok)
+		assert.Len(t, lines, 2)
+		assert.True(t, strings.HasPrefix(lines[0], "\x1b_Ga=T,U=1,f=100"))
+		assert.Equal(t, 4, strings.Count(lines[1], string(kittyPlaceholder)))
+	})
+
+	t.Run("sixel reserves the lines it covers", func(t *testing.T) {
+		lines, ok := Render(ProtocolSixel, buf.Bytes(), 80, 15)
+		require.True(t, ok)
+		assert.Len(t, lines, 2)
+		assert.Contains(t, lines[0], "\x1bPq")
+		assert.Equal(t, "    ", lines[1])
+	})
+
+	t.Run("falls back without a protocol or for unknown data", func(t *testing.T) {
+		_, ok := Render(ProtocolNone, buf.Bytes(), 80, 15)
+		assert.False(t, ok)
+		_, ok = Render(ProtocolKitty, []byte("not an image"), 80, 15)
+		assert.False(t, ok)
+	})
+}
+
+func TestWriteSixelRun(t *testing.T) {
+	var b strings.Builder
+	writeSixelRun(&b, []byte("??????AB"))
+	assert.Equal(t, "!6?AB", b.String())
+}
@@ -0,0 +1,60 @@
+package layout
+
+import (
+	"slices"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+)
+
+// actions are the key bindings that can be changed in the keybindings
+// config, by the name of their action
+var actions = map[string]*key.Binding{}
+
+// defaults are the bindings of the actions before the config changed them
+var defaults = map[string]key.Binding{}
+
+// RegisterAction lets the keys of the binding be set in the keybindings
+// config under the action name. It's called when the package holding the
+// binding is initialized, before the config is applied.
+func RegisterAction(name string, binding *key.Binding) {
+	actions[name] = binding
+	defaults[name] = *binding
+}
+
+// Actions returns the names of the actions that can be rebound, sorted
+func Actions() []string {
+	names := make([]string, 0, len(actions))
+	for name := range actions {
+		names = append(names, name)
+	}
+	slices.Sort(names)
+	return names
+}
+
+// ApplyKeybindings sets the keys of the actions to the configured ones, an
+// empty list of keys disables the action and the actions left out get their
+// default keys back. It returns the configured actions that don't exist,
+// sorted.
+func ApplyKeybindings(keybindings map[string][]string) []string {
+	for name, binding := range actions {
+		*binding = defaults[name]
+	}
+	var unknown []string
+	for name, keys := range keybindings {
+		binding, ok := actions[strings.ToLower(name)]
+		if !ok {
+			unknown = append(unknown, name)
+			continue
+		}
+		if len(keys) == 0 {
+			binding.SetEnabled(false)
+			continue
+		}
+		binding.SetKeys(keys...)
+		binding.SetHelp(strings.Join(keys, "/"), binding.Help().Desc)
+		binding.SetEnabled(true)
+	}
+	slices.Sort(unknown)
+	return unknown
+}
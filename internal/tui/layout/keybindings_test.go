@@ -0,0 +1,44 @@
+package layout
+
+import (
+	"testing"
+
+	"github.com/charmbracelet/bubbles/key"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestApplyKeybindings(t *testing.T) {
+	send := key.NewBinding(key.WithKeys("ctrl+s"), key.WithHelp("ctrl+s", "send"))
+	logs := key.NewBinding(key.WithKeys("ctrl+l"), key.WithHelp("ctrl+l", "logs"))
+	RegisterAction("test_send", &send)
+	RegisterAction("test_logs", &logs)
+	t.Cleanup(func() {
+		delete(actions, "test_send")
+		delete(actions, "test_logs")
+		delete(defaults, "test_send")
+		delete(defaults, "test_logs")
+	})
+
+	unknown := ApplyKeybindings(map[string][]string{
+		"test_send":  {"ctrl+enter", "alt+s"},
+		"test_logs":  {},
+		"not_action": {"x"},
+	})
+	assert.Equal(t, []string{"not_action"}, unknown)
+
+	assert.Equal(t, []string{"ctrl+enter", "alt+s"}, send.Keys())
+	assert.Equal(t, "ctrl+enter/alt+s", send.Help().Key)
+	assert.Equal(t, "send", send.Help().Desc)
+	assert.True(t, key.Matches(tea.KeyMsg{Type: tea.KeyRunes, Runes: []rune{'s'}, Alt: true}, send))
+
+	assert.False(t, logs.Enabled())
+	assert.False(t, key.Matches(tea.KeyMsg{Type: tea.KeyCtrlL}, logs))
+
+	// Applying the config again after a reload restores the actions left out
+	ApplyKeybindings(map[string][]string{"test_logs": {"ctrl+o"}})
+	assert.Equal(t, []string{"ctrl+s"}, send.Keys())
+	assert.Equal(t, "ctrl+s", send.Help().Key)
+	assert.True(t, logs.Enabled())
+	assert.Equal(t, []string{"ctrl+o"}, logs.Keys())
+}
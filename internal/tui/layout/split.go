@@ -14,17 +14,33 @@ type SplitPaneLayout interface {
 	SetLeftPanel(panel Container) tea.Cmd
 	SetRightPanel(panel Container) tea.Cmd
 	SetBottomPanel(panel Container) tea.Cmd
+	SetBottomPanelHeight(height int) tea.Cmd
+	SetVerticalRatio(ratio float64) tea.Cmd
 
 	ClearLeftPanel() tea.Cmd
 	ClearRightPanel() tea.Cmd
 	ClearBottomPanel() tea.Cmd
 }
 
+const (
+	// minSplitWidth is the narrowest width the right panel is shown at,
+	// narrower layouts give the whole width to the left panel
+	minSplitWidth = 100
+	minRightWidth = 30
+	maxRightWidth = 50
+	// minBottomHeight keeps a line to type in below the border of the bottom
+	// panel on short terminals
+	minBottomHeight = 2
+)
+
 type splitPaneLayout struct {
 	width         int
 	height        int
 	ratio         float64
 	verticalRatio float64
+	// bottomHeight is the height the bottom panel asks for, it grows past
+	// the vertical ratio up to half of the layout
+	bottomHeight int
 
 	rightPanel  Container
 	leftPanel   Container
@@ -58,7 +74,9 @@ func (s *splitPaneLayout) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		return s, s.SetSize(msg.Width, msg.Height)
 	}
 
-	if s.rightPanel != nil {
+	// The mouse can't point at a right panel hidden by a narrow layout
+	_, isMouse := msg.(tea.MouseMsg)
+	if s.rightPanel != nil && (!isMouse || s.rightVisible()) {
 		u, cmd := s.rightPanel.Update(msg)
 		s.rightPanel = u.(Container)
 		if cmd != nil {
@@ -88,13 +106,13 @@ func (s *splitPaneLayout) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 func (s *splitPaneLayout) View() string {
 	var topSection string
 
-	if s.leftPanel != nil && s.rightPanel != nil {
+	if s.leftPanel != nil && s.rightVisible() {
 		leftView := s.leftPanel.View()
 		rightView := s.rightPanel.View()
 		topSection = lipgloss.JoinHorizontal(lipgloss.Top, leftView, rightView)
 	} else if s.leftPanel != nil {
 		topSection = s.leftPanel.View()
-	} else if s.rightPanel != nil {
+	} else if s.rightVisible() {
 		topSection = s.rightPanel.View()
 	} else {
 		topSection = ""
@@ -129,26 +147,7 @@ func (s *splitPaneLayout) SetSize(width, height int) tea.Cmd {
 	s.width = width
 	s.height = height
 
-	var topHeight, bottomHeight int
-	if s.bottomPanel != nil {
-		topHeight = int(float64(height) * s.verticalRatio)
-		bottomHeight = height - topHeight
-	} else {
-		topHeight = height
-		bottomHeight = 0
-	}
-
-	var leftWidth, rightWidth int
-	if s.leftPanel != nil && s.rightPanel != nil {
-		leftWidth = int(float64(width) * s.ratio)
-		rightWidth = width - leftWidth
-	} else if s.leftPanel != nil {
-		leftWidth = width
-		rightWidth = 0
-	} else if s.rightPanel != nil {
-		leftWidth = 0
-		rightWidth = width
-	}
+	leftWidth, rightWidth, topHeight, bottomHeight := s.panelSizes()
 
 	var cmds []tea.Cmd
 	if s.leftPanel != nil {
@@ -156,7 +155,7 @@ func (s *splitPaneLayout) SetSize(width, height int) tea.Cmd {
 		cmds = append(cmds, cmd)
 	}
 
-	if s.rightPanel != nil {
+	if s.rightVisible() {
 		cmd := s.rightPanel.SetSize(rightWidth, topHeight)
 		cmds = append(cmds, cmd)
 	}
@@ -168,6 +167,37 @@ func (s *splitPaneLayout) SetSize(width, height int) tea.Cmd {
 	return tea.Batch(cmds...)
 }
 
+// panelSizes splits the layout between the panels. The right panel keeps a
+// readable width whatever the ratio, and the bottom panel keeps room to type.
+func (s *splitPaneLayout) panelSizes() (leftWidth, rightWidth, topHeight, bottomHeight int) {
+	topHeight = s.height
+	if s.bottomPanel != nil {
+		bottomHeight = s.height - int(float64(s.height)*s.verticalRatio)
+		if s.bottomHeight > bottomHeight {
+			bottomHeight = max(bottomHeight, min(s.bottomHeight, s.height/2))
+		}
+		bottomHeight = min(max(bottomHeight, minBottomHeight), s.height)
+		topHeight = s.height - bottomHeight
+	}
+
+	switch {
+	case s.leftPanel != nil && s.rightVisible():
+		rightWidth = min(max(s.width-int(float64(s.width)*s.ratio), minRightWidth), maxRightWidth)
+		leftWidth = s.width - rightWidth
+	case s.leftPanel != nil:
+		leftWidth = s.width
+	case s.rightPanel != nil:
+		rightWidth = s.width
+	}
+	return leftWidth, rightWidth, topHeight, bottomHeight
+}
+
+// rightVisible reports whether the right panel is shown, it's hidden when
+// the layout is too narrow to show it next to the left panel
+func (s *splitPaneLayout) rightVisible() bool {
+	return s.rightPanel != nil && (s.leftPanel == nil || s.width >= minSplitWidth)
+}
+
 func (s *splitPaneLayout) GetSize() (int, int) {
 	return s.width, s.height
 }
@@ -196,6 +226,28 @@ func (s *splitPaneLayout) SetBottomPanel(panel Container) tea.Cmd {
 	return nil
 }
 
+// SetBottomPanelHeight sets the height the bottom panel needs. The panel never
+// gets smaller than the vertical ratio makes it.
+func (s *splitPaneLayout) SetBottomPanelHeight(height int) tea.Cmd {
+	if height == s.bottomHeight {
+		return nil
+	}
+	s.bottomHeight = height
+	if s.width > 0 && s.height > 0 {
+		return s.SetSize(s.width, s.height)
+	}
+	return nil
+}
+
+// SetVerticalRatio sets the part of the height the top panels take
+func (s *splitPaneLayout) SetVerticalRatio(ratio float64) tea.Cmd {
+	s.verticalRatio = ratio
+	if s.width > 0 && s.height > 0 {
+		return s.SetSize(s.width, s.height)
+	}
+	return nil
+}
+
 func (s *splitPaneLayout) ClearLeftPanel() tea.Cmd {
 	s.leftPanel = nil
 	if s.width > 0 && s.height > 0 {
@@ -227,7 +279,7 @@ func (s *splitPaneLayout) BindingKeys() []key.Binding {
 			keys = append(keys, b.BindingKeys()...)
 		}
 	}
-	if s.rightPanel != nil {
+	if s.rightVisible() {
 		if b, ok := s.rightPanel.(Bindings); ok {
 			keys = append(keys, b.BindingKeys()...)
 		}
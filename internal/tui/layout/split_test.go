@@ -0,0 +1,74 @@
+package layout
+
+import (
+	"testing"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/stretchr/testify/assert"
+)
+
+type emptyModel struct{}
+
+func (emptyModel) Init() tea.Cmd { return nil }
+
+func (emptyModel) Update(tea.Msg) (tea.Model, tea.Cmd) { return emptyModel{}, nil }
+
+func (emptyModel) View() string { return "" }
+
+func newTestSplitPane(options ...SplitPaneOption) *splitPaneLayout {
+	options = append([]SplitPaneOption{
+		WithLeftPanel(NewContainer(emptyModel{})),
+		WithRightPanel(NewContainer(emptyModel{})),
+		WithBottomPanel(NewContainer(emptyModel{})),
+	}, options...)
+	return NewSplitPane(options...).(*splitPaneLayout)
+}
+
+func TestSplitPaneRightPanelWidth(t *testing.T) {
+	s := newTestSplitPane()
+
+	s.SetSize(120, 40)
+	left, right, _, _ := s.panelSizes()
+	assert.Equal(t, 84, left)
+	assert.Equal(t, 36, right)
+
+	// Wide terminals give the extra width to the left panel
+	s.SetSize(240, 40)
+	left, right, _, _ = s.panelSizes()
+	assert.Equal(t, 190, left)
+	assert.Equal(t, maxRightWidth, right)
+
+	// Narrow terminals hide the right panel
+	s.SetSize(80, 40)
+	left, right, _, _ = s.panelSizes()
+	assert.False(t, s.rightVisible())
+	assert.Equal(t, 80, left)
+	assert.Equal(t, 0, right)
+}
+
+func TestSplitPaneBottomPanelHeight(t *testing.T) {
+	s := newTestSplitPane(WithVerticalRatio(0.8))
+
+	s.SetSize(120, 50)
+	_, _, top, bottom := s.panelSizes()
+	assert.Equal(t, 40, top)
+	assert.Equal(t, 10, bottom)
+
+	s.SetVerticalRatio(0.9)
+	_, _, top, bottom = s.panelSizes()
+	assert.Equal(t, 45, top)
+	assert.Equal(t, 5, bottom)
+
+	// The bottom panel grows for its content up to half of the height
+	s.SetBottomPanelHeight(40)
+	_, _, top, bottom = s.panelSizes()
+	assert.Equal(t, 25, top)
+	assert.Equal(t, 25, bottom)
+
+	// Short terminals keep room to type
+	s.SetBottomPanelHeight(0)
+	s.SetSize(120, 10)
+	_, _, top, bottom = s.panelSizes()
+	assert.Equal(t, 8, top)
+	assert.Equal(t, minBottomHeight, bottom)
+}
@@ -2,6 +2,7 @@ package page
 
 import (
 	"context"
+	"math"
 	"strings"
 
 	"github.com/charmbracelet/bubbles/key"
@@ -9,16 +10,24 @@ import (
 	"github.com/charmbracelet/lipgloss"
 	"github.com/zhenbah/cryoncode/internal/app"
 	"github.com/zhenbah/cryoncode/internal/completions"
+	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/session"
 	"github.com/zhenbah/cryoncode/internal/tui/components/chat"
 	"github.com/zhenbah/cryoncode/internal/tui/components/dialog"
 	"github.com/zhenbah/cryoncode/internal/tui/layout"
+	"github.com/zhenbah/cryoncode/internal/tui/state"
 	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
 
 var ChatPage PageID = "chat"
 
+const (
+	inputRatioStep = 0.05
+	minInputRatio  = 0.05
+	maxInputRatio  = 0.5
+)
+
 type chatPage struct {
 	app                  *app.App
 	editor               layout.Container
@@ -27,12 +36,23 @@ type chatPage struct {
 	session              session.Session
 	completionDialog     dialog.CompletionDialog
 	showCompletionDialog bool
+	// editorFocused is false while keys go to the messages, e.g. to scroll
+	editorFocused bool
+	// editorInsert is false while the editor is in vim normal mode
+	editorInsert bool
+	// inputRatio is the part of the height kept for the editor
+	inputRatio float64
+	// sidebarHidden is set when the sidebar was toggled off
+	sidebarHidden bool
 }
 
 type ChatKeyMap struct {
 	ShowCompletionDialog key.Binding
 	NewSession           key.Binding
 	Cancel               key.Binding
+	FocusEditor          key.Binding
+	GrowEditor           key.Binding
+	ShrinkEditor         key.Binding
 }
 
 var keyMap = ChatKeyMap{
@@ -46,10 +66,30 @@ var keyMap = ChatKeyMap{
 	),
 	Cancel: key.NewBinding(
 		key.WithKeys("esc"),
-		key.WithHelp("esc", "cancel"),
+		key.WithHelp("esc", "cancel or focus messages"),
+	),
+	FocusEditor: key.NewBinding(
+		key.WithKeys("i"),
+		key.WithHelp("i", "focus editor"),
+	),
+	GrowEditor: key.NewBinding(
+		key.WithKeys("alt+up"),
+		key.WithHelp("alt+↑", "grow editor"),
+	),
+	ShrinkEditor: key.NewBinding(
+		key.WithKeys("alt+down"),
+		key.WithHelp("alt+↓", "shrink editor"),
 	),
 }
 
+func init() {
+	layout.RegisterAction("new_session", &keyMap.NewSession)
+	layout.RegisterAction("cancel", &keyMap.Cancel)
+	layout.RegisterAction("focus_editor", &keyMap.FocusEditor)
+	layout.RegisterAction("grow_editor", &keyMap.GrowEditor)
+	layout.RegisterAction("shrink_editor", &keyMap.ShrinkEditor)
+}
+
 func (p *chatPage) Init() tea.Cmd {
 	cmds := []tea.Cmd{
 		p.layout.Init(),
@@ -66,7 +106,18 @@ func (p *chatPage) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		cmds = append(cmds, cmd)
 	case dialog.CompletionDialogCloseMsg:
 		p.showCompletionDialog = false
+	case chat.EditorInsertModeMsg:
+		p.editorInsert = bool(msg)
+	case chat.EditorResizeMsg:
+		// One more line for the border above the editor
+		cmds = append(cmds, p.layout.SetBottomPanelHeight(msg.Height+1))
 	case chat.SendMsg:
+		if msg.Replaces != "" {
+			err := p.app.Messages.Truncate(context.Background(), p.session.ID, msg.Replaces)
+			if err != nil {
+				return p, util.ReportError(err)
+			}
+		}
 		cmd := p.sendMessage(msg.Text, msg.Attachments)
 		if cmd != nil {
 			return p, cmd
@@ -92,6 +143,21 @@ func (p *chatPage) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		if cmd != nil {
 			return p, cmd
 		}
+	case chat.SessionClearedMsg:
+		p.session = session.Session{}
+		cmds = append(cmds, p.clearSidebar())
+	case chat.ToggleSidebarMsg:
+		p.sidebarHidden = !p.sidebarHidden
+		state.Update(func(s *state.State) {
+			s.SidebarHidden = p.sidebarHidden
+		})
+		if p.session.ID == "" {
+			return p, nil
+		}
+		if p.sidebarHidden {
+			return p, p.clearSidebar()
+		}
+		return p, p.setSidebar()
 	case chat.SessionSelectedMsg:
 		if p.session.ID == "" {
 			cmd := p.setSidebar()
@@ -102,22 +168,34 @@ func (p *chatPage) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		p.session = msg
 	case tea.KeyMsg:
 		switch {
-		case key.Matches(msg, keyMap.ShowCompletionDialog):
+		case p.editorFocused && p.editorInsert && key.Matches(msg, keyMap.ShowCompletionDialog):
 			p.showCompletionDialog = true
 			// Continue sending keys to layout->chat
 		case key.Matches(msg, keyMap.NewSession):
-			p.session = session.Session{}
-			return p, tea.Batch(
-				p.clearSidebar(),
-				util.CmdHandler(chat.SessionClearedMsg{}),
-			)
+			return p, util.CmdHandler(dialog.ShowNewSessionDialogMsg{})
 		case key.Matches(msg, keyMap.Cancel):
-			if p.session.ID != "" {
+			if p.session.ID != "" && p.app.CoderAgent.IsSessionBusy(p.session.ID) {
 				// Cancel the current session's generation process
 				// This allows users to interrupt long-running operations
 				p.app.CoderAgent.Cancel(p.session.ID)
 				return p, nil
 			}
+			// With vim keybindings the first esc only leaves insert mode
+			if p.editorFocused && (!p.editorInsert || !config.Get().TUI.VimMode) {
+				p.editorFocused = false
+				return p, util.CmdHandler(chat.EditorFocusMsg(false))
+			}
+		case key.Matches(msg, keyMap.GrowEditor):
+			return p, p.resizeEditor(inputRatioStep)
+		case key.Matches(msg, keyMap.ShrinkEditor):
+			return p, p.resizeEditor(-inputRatioStep)
+		case !p.editorFocused && key.Matches(msg, keyMap.FocusEditor):
+			p.editorFocused = true
+			return p, util.CmdHandler(chat.EditorFocusMsg(true))
+		case !p.editorFocused && msg.Paste:
+			// Pasting while reading the messages goes to the editor
+			p.editorFocused = true
+			return p, tea.Sequence(util.CmdHandler(chat.EditorFocusMsg(true)), util.CmdHandler(msg))
 		}
 	}
 	if p.showCompletionDialog {
@@ -141,6 +219,9 @@ func (p *chatPage) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 }
 
 func (p *chatPage) setSidebar() tea.Cmd {
+	if p.sidebarHidden {
+		return nil
+	}
 	sidebarContainer := layout.NewContainer(
 		chat.NewSidebarCmp(p.session, p.app.History),
 		layout.WithPadding(1, 1, 1, 1),
@@ -155,7 +236,7 @@ func (p *chatPage) clearSidebar() tea.Cmd {
 func (p *chatPage) sendMessage(text string, attachments []message.Attachment) tea.Cmd {
 	var cmds []tea.Cmd
 	if p.session.ID == "" {
-		session, err := p.app.Sessions.Create(context.Background(), "New Session")
+		session, err := p.app.Sessions.Create(context.Background(), session.DefaultTitle)
 		if err != nil {
 			return util.ReportError(err)
 		}
@@ -175,6 +256,27 @@ func (p *chatPage) sendMessage(text string, attachments []message.Attachment) te
 	return tea.Batch(cmds...)
 }
 
+// resizeEditor moves the split between the messages and the editor. The size
+// is kept in the TUI state, written on exit, so resizing doesn't write a file
+// on every key press.
+func (p *chatPage) resizeEditor(step float64) tea.Cmd {
+	ratio := clampInputRatio(p.inputRatio + step)
+	if ratio == p.inputRatio {
+		return nil
+	}
+	p.inputRatio = ratio
+	state.Update(func(s *state.State) {
+		s.InputRatio = ratio
+	})
+	return p.layout.SetVerticalRatio(1 - ratio)
+}
+
+// clampInputRatio keeps the ratio between the smallest and largest editor,
+// rounded to hundredths so the saved value stays readable
+func clampInputRatio(ratio float64) float64 {
+	return min(max(math.Round(ratio*100)/100, minInputRatio), maxInputRatio)
+}
+
 func (p *chatPage) SetSize(width, height int) tea.Cmd {
 	return p.layout.SetSize(width, height)
 }
@@ -224,14 +326,24 @@ func NewChatPage(app *app.App) tea.Model {
 		chat.NewEditorCmp(app),
 		layout.WithBorder(true, false, false, false),
 	)
+	inputRatio := config.Get().TUI.InputRatio
+	if saved := state.Saved().InputRatio; saved > 0 {
+		inputRatio = saved
+	}
+	inputRatio = clampInputRatio(inputRatio)
 	return &chatPage{
 		app:              app,
 		editor:           editorContainer,
 		messages:         messagesContainer,
 		completionDialog: completionDialog,
+		editorFocused:    true,
+		editorInsert:     true,
+		inputRatio:       inputRatio,
+		sidebarHidden:    state.Saved().SidebarHidden,
 		layout: layout.NewSplitPane(
 			layout.WithLeftPanel(messagesContainer),
 			layout.WithBottomPanel(editorContainer),
+			layout.WithVerticalRatio(1-inputRatio),
 		),
 	}
 }
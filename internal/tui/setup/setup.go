@@ -0,0 +1,370 @@
+// Package setup is the wizard run on the first start, before there's a config
+// to start the TUI with.
+package setup
+
+import (
+	"fmt"
+	"slices"
+	"strings"
+
+	"github.com/charmbracelet/bubbles/key"
+	"github.com/charmbracelet/bubbles/textinput"
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+	"github.com/zhenbah/cryoncode/internal/tui/styles"
+	"github.com/zhenbah/cryoncode/internal/tui/theme"
+)
+
+const (
+	wizardWidth      = 64
+	numVisibleModels = 10
+)
+
+type step int
+
+const (
+	stepProvider step = iota
+	stepAPIKey
+	stepModel
+	stepDataDirectory
+)
+
+var stepTitles = []string{"Provider", "API key", "Model", "Data directory"}
+
+type wizardKeyMap struct {
+	Up     key.Binding
+	Down   key.Binding
+	Toggle key.Binding
+	Enter  key.Binding
+	Back   key.Binding
+	Quit   key.Binding
+}
+
+var wizardKeys = wizardKeyMap{
+	Up: key.NewBinding(
+		key.WithKeys("up", "k"),
+		key.WithHelp("↑", "previous"),
+	),
+	Down: key.NewBinding(
+		key.WithKeys("down", "j"),
+		key.WithHelp("↓", "next"),
+	),
+	Toggle: key.NewBinding(
+		key.WithKeys("tab"),
+		key.WithHelp("tab", "keyring/config file"),
+	),
+	Enter: key.NewBinding(
+		key.WithKeys("enter"),
+		key.WithHelp("enter", "continue"),
+	),
+	Back: key.NewBinding(
+		key.WithKeys("esc"),
+		key.WithHelp("esc", "back"),
+	),
+	Quit: key.NewBinding(
+		key.WithKeys("ctrl+c"),
+		key.WithHelp("ctrl+c", "quit"),
+	),
+}
+
+type wizard struct {
+	width, height int
+	step          step
+	providers     []models.ModelProvider
+	providerIdx   int
+	apiKey        textinput.Model
+	keyring       bool
+	models        []models.Model
+	modelIdx      int
+	dataDirectory textinput.Model
+	err           error
+	path          string // The config file written, empty until the setup is done
+}
+
+// Run asks for a provider, its API key, the model of the coder and the data
+// directory, then saves the key and writes the user config file. Quitting
+// leaves everything as it was.
+func Run() error {
+	result, err := tea.NewProgram(newWizard(), tea.WithAltScreen()).Run()
+	if err != nil {
+		return err
+	}
+	if w := result.(*wizard); w.path != "" {
+		fmt.Printf("Wrote the config to %s\n", w.path)
+	}
+	return nil
+}
+
+func newWizard() *wizard {
+	t := theme.CurrentTheme()
+	newInput := func() textinput.Model {
+		ti := textinput.New()
+		ti.Width = wizardWidth - 4
+		ti.Prompt = "> "
+		ti.PlaceholderStyle = ti.PlaceholderStyle.Background(t.Background())
+		ti.PromptStyle = ti.PromptStyle.Background(t.Background()).Foreground(t.Primary())
+		ti.TextStyle = ti.TextStyle.Background(t.Background())
+		return ti
+	}
+
+	apiKey := newInput()
+	apiKey.EchoMode = textinput.EchoPassword
+	apiKey.EchoCharacter = '•'
+	dataDirectory := newInput()
+	dataDirectory.SetValue(".cryoncode")
+
+	return &wizard{
+		providers:     config.KeyringProviders,
+		apiKey:        apiKey,
+		keyring:       true,
+		dataDirectory: dataDirectory,
+	}
+}
+
+func (w *wizard) Init() tea.Cmd {
+	return nil
+}
+
+func (w *wizard) provider() models.ModelProvider {
+	return w.providers[w.providerIdx]
+}
+
+func (w *wizard) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
+	switch msg := msg.(type) {
+	case tea.WindowSizeMsg:
+		w.width = msg.Width
+		w.height = msg.Height
+		return w, nil
+	case tea.KeyMsg:
+		switch {
+		case key.Matches(msg, wizardKeys.Quit):
+			return w, tea.Quit
+		case key.Matches(msg, wizardKeys.Back):
+			w.err = nil
+			if w.step == stepProvider {
+				return w, tea.Quit
+			}
+			w.step--
+			return w, w.focus()
+		case key.Matches(msg, wizardKeys.Enter):
+			return w, w.next()
+		}
+	}
+
+	var cmd tea.Cmd
+	switch w.step {
+	case stepProvider:
+		w.providerIdx = moveSelection(msg, w.providerIdx, len(w.providers))
+	case stepAPIKey:
+		if msg, ok := msg.(tea.KeyMsg); ok && key.Matches(msg, wizardKeys.Toggle) {
+			w.keyring = !w.keyring
+			return w, nil
+		}
+		w.apiKey, cmd = w.apiKey.Update(msg)
+	case stepModel:
+		w.modelIdx = moveSelection(msg, w.modelIdx, len(w.models))
+	case stepDataDirectory:
+		w.dataDirectory, cmd = w.dataDirectory.Update(msg)
+	}
+	return w, cmd
+}
+
+// next checks the answer of the current step and goes to the next one, the
+// config is written after the last one
+func (w *wizard) next() tea.Cmd {
+	w.err = nil
+	switch w.step {
+	case stepProvider:
+		w.models = providerModels(w.provider())
+		w.modelIdx = max(slices.IndexFunc(w.models, func(model models.Model) bool {
+			return model.ID == config.SetupModel(w.provider())
+		}), 0)
+	case stepAPIKey:
+		if strings.TrimSpace(w.apiKey.Value()) == "" {
+			w.err = fmt.Errorf("enter the API key of %s", providerName(w.provider()))
+			return nil
+		}
+	case stepModel:
+		if len(w.models) == 0 {
+			w.err = fmt.Errorf("no models available for %s", providerName(w.provider()))
+			return nil
+		}
+	case stepDataDirectory:
+		path, err := config.WriteSetup(config.Setup{
+			Provider:      w.provider(),
+			APIKey:        strings.TrimSpace(w.apiKey.Value()),
+			KeyInKeyring:  w.keyring,
+			Model:         w.models[w.modelIdx].ID,
+			DataDirectory: w.dataDirectory.Value(),
+		})
+		if err != nil {
+			w.err = err
+			return nil
+		}
+		w.path = path
+		return tea.Quit
+	}
+	w.step++
+	return w.focus()
+}
+
+// focus focuses the text input of the current step, if it has one
+func (w *wizard) focus() tea.Cmd {
+	w.apiKey.Blur()
+	w.dataDirectory.Blur()
+	switch w.step {
+	case stepAPIKey:
+		return w.apiKey.Focus()
+	case stepDataDirectory:
+		return w.dataDirectory.Focus()
+	}
+	return nil
+}
+
+func moveSelection(msg tea.Msg, selected, count int) int {
+	keyMsg, ok := msg.(tea.KeyMsg)
+	if !ok || count == 0 {
+		return selected
+	}
+	switch {
+	case key.Matches(keyMsg, wizardKeys.Up):
+		return (selected - 1 + count) % count
+	case key.Matches(keyMsg, wizardKeys.Down):
+		return (selected + 1) % count
+	}
+	return selected
+}
+
+// providerModels returns the models of a provider the way the model dialog
+// lists them, in reverse alphabetical order
+func providerModels(provider models.ModelProvider) []models.Model {
+	var providerModels []models.Model
+	for _, model := range models.SupportedModels {
+		if model.Provider == provider {
+			providerModels = append(providerModels, model)
+		}
+	}
+	slices.SortFunc(providerModels, func(a, b models.Model) int {
+		return strings.Compare(b.Name, a.Name)
+	})
+	return providerModels
+}
+
+func providerName(provider models.ModelProvider) string {
+	switch provider {
+	case models.ProviderOpenAI:
+		return "OpenAI"
+	case models.ProviderGROQ:
+		return "Groq"
+	case models.ProviderOpenRouter:
+		return "OpenRouter"
+	case models.ProviderXAI:
+		return "xAI"
+	case models.ProviderCopilot:
+		return "GitHub Copilot"
+	}
+	return strings.ToUpper(string(provider)[:1]) + string(provider)[1:]
+}
+
+func (w *wizard) View() string {
+	t := theme.CurrentTheme()
+	baseStyle := styles.BaseStyle()
+	textStyle := baseStyle.Width(wizardWidth)
+
+	title := baseStyle.
+		Foreground(t.Primary()).
+		Bold(true).
+		Width(wizardWidth).
+		Render("Welcome to Cryon code")
+	stepLine := baseStyle.
+		Foreground(t.TextMuted()).
+		Width(wizardWidth).
+		Padding(0, 0, 1).
+		Render(fmt.Sprintf("Step %d of %d: %s", w.step+1, len(stepTitles), stepTitles[w.step]))
+
+	var body []string
+	switch w.step {
+	case stepProvider:
+		body = append(body, textStyle.Padding(0, 0, 1).Render("No config was found. Which provider should the agents use?"))
+		names := make([]string, len(w.providers))
+		for i, provider := range w.providers {
+			names[i] = providerName(provider)
+		}
+		body = append(body, w.list(names, w.providerIdx)...)
+	case stepAPIKey:
+		what := "API key"
+		if w.provider() == models.ProviderCopilot {
+			what = "GitHub token"
+		}
+		body = append(body,
+			textStyle.Padding(0, 0, 1).Render(fmt.Sprintf("Paste your %s %s.", providerName(w.provider()), what)),
+			textStyle.Render(w.apiKey.View()),
+		)
+		where := "Saved in the OS keyring"
+		if !w.keyring {
+			where = "Written to the config file"
+		}
+		body = append(body, textStyle.Foreground(t.TextMuted()).Padding(1, 0, 0).Render(where+", press tab to change"))
+	case stepModel:
+		body = append(body, textStyle.Padding(0, 0, 1).Render("Which model should the coder use? The other agents get the defaults of the provider."))
+		offset := max(w.modelIdx-numVisibleModels+1, 0)
+		end := min(offset+numVisibleModels, len(w.models))
+		names := make([]string, 0, end-offset)
+		for _, model := range w.models[offset:end] {
+			names = append(names, model.Name)
+		}
+		body = append(body, w.list(names, w.modelIdx-offset)...)
+	case stepDataDirectory:
+		body = append(body,
+			textStyle.Padding(0, 0, 1).Render("Where should the sessions be kept? A relative directory is kept at the root of the git repository of each project, an absolute one is shared by all of them."),
+			textStyle.Render(w.dataDirectory.View()),
+		)
+	}
+
+	if w.err != nil {
+		body = append(body, textStyle.Foreground(t.Error()).Padding(1, 0, 0).Render(w.err.Error()))
+	}
+
+	var help []string
+	bindings := []key.Binding{wizardKeys.Enter, wizardKeys.Back, wizardKeys.Quit}
+	switch w.step {
+	case stepProvider, stepModel:
+		bindings = append([]key.Binding{wizardKeys.Up, wizardKeys.Down}, bindings...)
+	case stepAPIKey:
+		bindings = append([]key.Binding{wizardKeys.Toggle}, bindings...)
+	}
+	for _, binding := range bindings {
+		help = append(help, binding.Help().Key+" "+binding.Help().Desc)
+	}
+	helpLine := textStyle.Foreground(t.TextMuted()).Padding(1, 0, 0).Render(strings.Join(help, " • "))
+
+	content := lipgloss.JoinVertical(
+		lipgloss.Left,
+		append(append([]string{title, stepLine}, body...), helpLine)...,
+	)
+	box := baseStyle.Padding(1, 2).
+		Border(lipgloss.RoundedBorder()).
+		BorderBackground(t.Background()).
+		BorderForeground(t.TextMuted()).
+		Render(content)
+	return lipgloss.Place(w.width, w.height, lipgloss.Center, lipgloss.Center, box,
+		lipgloss.WithWhitespaceBackground(t.Background()))
+}
+
+// list renders items with the selected one highlighted
+func (w *wizard) list(items []string, selected int) []string {
+	t := theme.CurrentTheme()
+	lines := make([]string, len(items))
+	for i, item := range items {
+		itemStyle := styles.BaseStyle().Width(wizardWidth)
+		if i == selected {
+			itemStyle = itemStyle.Background(t.Primary()).
+				Foreground(t.Background()).Bold(true)
+		}
+		lines[i] = itemStyle.Render(item)
+	}
+	return lines
+}
@@ -0,0 +1,70 @@
+package setup
+
+import (
+	"path/filepath"
+	"testing"
+
+	tea "github.com/charmbracelet/bubbletea"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+	"github.com/zhenbah/cryoncode/internal/config"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
+)
+
+func TestWizard(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+	t.Setenv("XDG_CONFIG_HOME", "")
+
+	w := newWizard()
+	press := func(msg tea.KeyMsg) tea.Cmd {
+		_, cmd := w.Update(msg)
+		return cmd
+	}
+	enter := tea.KeyMsg{Type: tea.KeyEnter}
+
+	require.Equal(t, models.ProviderAnthropic, w.provider())
+	press(enter)
+	require.Equal(t, stepAPIKey, w.step)
+
+	// The key can't be left empty
+	press(enter)
+	assert.Error(t, w.err)
+	assert.Equal(t, stepAPIKey, w.step)
+
+	press(tea.KeyMsg{Type: tea.KeyRunes, Runes: []rune("sk-ant-test")})
+	press(tea.KeyMsg{Type: tea.KeyTab})
+	assert.False(t, w.keyring)
+	press(enter)
+	require.Equal(t, stepModel, w.step)
+	// The model the provider gives the coder comes first
+	assert.Equal(t, models.Claude4Sonnet, w.models[w.modelIdx].ID)
+
+	press(enter)
+	require.Equal(t, stepDataDirectory, w.step)
+	assert.NotNil(t, press(enter))
+
+	path := filepath.Join(home, ".cryoncode.json")
+	assert.Equal(t, path, w.path)
+	values, err := config.ReadConfigFile(path)
+	require.NoError(t, err)
+	key, _ := config.GetConfigValue(values, "providers.anthropic.apiKey")
+	assert.Equal(t, "sk-ant-test", key)
+	model, _ := config.GetConfigValue(values, "agents.coder.model")
+	assert.Equal(t, string(models.Claude4Sonnet), model)
+}
+
+func TestWizardBack(t *testing.T) {
+	w := newWizard()
+	w.Update(tea.KeyMsg{Type: tea.KeyDown})
+	w.Update(tea.KeyMsg{Type: tea.KeyEnter})
+	require.Equal(t, stepAPIKey, w.step)
+	assert.Equal(t, models.ProviderOpenAI, w.provider())
+
+	w.Update(tea.KeyMsg{Type: tea.KeyEsc})
+	assert.Equal(t, stepProvider, w.step)
+	// Going back from the first step quits without writing anything
+	_, cmd := w.Update(tea.KeyMsg{Type: tea.KeyEsc})
+	assert.NotNil(t, cmd)
+	assert.Empty(t, w.path)
+}
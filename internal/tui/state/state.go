@@ -0,0 +1,94 @@
+// Package state keeps where the TUI was left, so it reopens there after a
+// restart. The theme isn't part of it, the theme dialog saves it to the
+// config file.
+package state
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"sync"
+)
+
+const stateFilename = "tui_state.json"
+
+// State is what the TUI shows that is restored at startup
+type State struct {
+	// SessionID is the session open in the chat page
+	SessionID string `json:"sessionId,omitempty"`
+	// ScrollFromBottom is the number of lines the messages are scrolled up,
+	// counted from the bottom so it stays right when messages are added
+	ScrollFromBottom int `json:"scrollFromBottom,omitempty"`
+	// SidebarHidden is set when the session sidebar was toggled off
+	SidebarHidden bool `json:"sidebarHidden,omitempty"`
+	// InputRatio is the part of the chat page height kept for the editor once
+	// it was resized, tui.inputRatio of the config is used before that
+	InputRatio float64 `json:"inputRatio,omitempty"`
+}
+
+var (
+	mu sync.Mutex
+	// saved is the state read at startup
+	saved State
+	// scrollRestored is set once the saved scroll position was used
+	scrollRestored bool
+	// current is the state the TUI is in, it's written on exit
+	current State
+)
+
+// Load reads the state saved in the data directory, a missing or broken
+// file starts from an empty state
+func Load(dataDir string) State {
+	mu.Lock()
+	defer mu.Unlock()
+	saved = State{}
+	scrollRestored = false
+	if data, err := os.ReadFile(filepath.Join(dataDir, stateFilename)); err == nil {
+		if err := json.Unmarshal(data, &saved); err != nil {
+			saved = State{}
+		}
+	}
+	current = saved
+	return saved
+}
+
+// Save writes the current state to the data directory
+func Save(dataDir string) error {
+	mu.Lock()
+	data, err := json.MarshalIndent(current, "", "  ")
+	mu.Unlock()
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(dataDir, 0o755); err != nil {
+		return err
+	}
+	return os.WriteFile(filepath.Join(dataDir, stateFilename), data, 0o644)
+}
+
+// Saved returns the state read at startup
+func Saved() State {
+	mu.Lock()
+	defer mu.Unlock()
+	return saved
+}
+
+// TakeScroll returns the saved scroll position of the session, only the
+// first time the session is opened so switching back to it later starts at
+// the bottom as usual
+func TakeScroll(sessionID string) (int, bool) {
+	mu.Lock()
+	defer mu.Unlock()
+	if scrollRestored || sessionID == "" || saved.SessionID != sessionID {
+		return 0, false
+	}
+	scrollRestored = true
+	return saved.ScrollFromBottom, true
+}
+
+// Update changes the current state
+func Update(update func(s *State)) {
+	mu.Lock()
+	defer mu.Unlock()
+	update(&current)
+}
@@ -0,0 +1,54 @@
+package state
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSaveAndLoad(t *testing.T) {
+	dir := t.TempDir()
+	assert.Equal(t, State{}, Load(dir))
+
+	Update(func(s *State) {
+		s.SessionID = "session"
+		s.ScrollFromBottom = 12
+		s.SidebarHidden = true
+		s.InputRatio = 0.3
+	})
+	require.NoError(t, Save(dir))
+
+	loaded := Load(dir)
+	assert.Equal(t, State{SessionID: "session", ScrollFromBottom: 12, SidebarHidden: true, InputRatio: 0.3}, loaded)
+	assert.Equal(t, loaded, Saved())
+}
+
+func TestLoadBrokenFile(t *testing.T) {
+	dir := t.TempDir()
+	require.NoError(t, os.WriteFile(filepath.Join(dir, stateFilename), []byte("{"), 0o644))
+	assert.Equal(t, State{}, Load(dir))
+}
+
+func TestTakeScrollOnce(t *testing.T) {
+	dir := t.TempDir()
+	Load(dir)
+	Update(func(s *State) {
+		s.SessionID = "session"
+		s.ScrollFromBottom = 5
+	})
+	require.NoError(t, Save(dir))
+	Load(dir)
+
+	_, ok := TakeScroll("other")
+	assert.False(t, ok)
+
+	scroll, ok := TakeScroll("session")
+	assert.True(t, ok)
+	assert.Equal(t, 5, scroll)
+
+	_, ok = TakeScroll("session")
+	assert.False(t, ok)
+}
@@ -0,0 +1,66 @@
+package styles
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/alecthomas/chroma/v2/lexers"
+)
+
+var codeFenceRegex = regexp.MustCompile("^ {0,3}(`{3,}|~{3,})(.*)$")
+
+// TagCodeBlockLanguages adds the detected language to fenced code blocks that
+// don't name one, so they are highlighted like tagged blocks. Blocks whose
+// language can't be detected are left as they are.
+func TagCodeBlockLanguages(content string) string {
+	if !strings.Contains(content, "```") && !strings.Contains(content, "~~~") {
+		return content
+	}
+
+	lines := strings.Split(content, "\n")
+	open, fence := -1, ""
+	for i, line := range lines {
+		if open < 0 {
+			match := codeFenceRegex.FindStringSubmatch(line)
+			if match == nil {
+				continue
+			}
+			// A backtick fence can't have backticks in its info string
+			if match[1][0] == '`' && strings.Contains(match[2], "`") {
+				continue
+			}
+			fence = match[1]
+			if strings.TrimSpace(match[2]) == "" {
+				open = i
+			} else {
+				// Tagged blocks are skipped up to their closing fence
+				open = len(lines)
+			}
+			continue
+		}
+
+		trimmed := strings.TrimSpace(line)
+		if len(trimmed) < len(fence) || strings.Trim(trimmed, fence[:1]) != "" || len(line)-len(strings.TrimLeft(line, " ")) > 3 {
+			continue
+		}
+		if open < len(lines) {
+			if language := detectLanguage(strings.Join(lines[open+1:i], "\n")); language != "" {
+				lines[open] = strings.TrimRight(lines[open], " \t") + language
+			}
+		}
+		open, fence = -1, ""
+	}
+	return strings.Join(lines, "\n")
+}
+
+func detectLanguage(code string) string {
+	lexer := lexers.Analyse(code)
+	if lexer == nil {
+		return ""
+	}
+	config := lexer.Config()
+	if len(config.Aliases) > 0 {
+		return config.Aliases[0]
+	}
+	return strings.ToLower(config.Name)
+}
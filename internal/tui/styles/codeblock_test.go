@@ -0,0 +1,24 @@
+package styles
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestTagCodeBlockLanguages(t *testing.T) {
+	t.Run("tags blocks with a detected language", func(t *testing.T) {
+		content := "Run:\n\n```\n#!/bin/bash\necho hi\n```\n"
+		assert.Equal(t, "Run:\n\n```bash\n#!/bin/bash\necho hi\n```\n", TagCodeBlockLanguages(content))
+	})
+
+	t.Run("keeps tagged blocks and their inner fences", func(t *testing.T) {
+		content := "````markdown\n```\n#!/bin/bash\n```\n````"
+		assert.Equal(t, content, TagCodeBlockLanguages(content))
+	})
+
+	t.Run("keeps blocks without a detected language", func(t *testing.T) {
+		content := "```\nfoo\nbar\n```"
+		assert.Equal(t, content, TagCodeBlockLanguages(content))
+	})
+}
@@ -217,15 +217,27 @@ func generateMarkdownStyleConfig() ansi.StyleConfig {
 				NameDecorator: ansi.StylePrimitive{
 					Color: stringPtr(adaptiveColorToString(t.SyntaxFunction())),
 				},
+				NameException: ansi.StylePrimitive{
+					Color: stringPtr(adaptiveColorToString(t.SyntaxType())),
+				},
 				NameFunction: ansi.StylePrimitive{
 					Color: stringPtr(adaptiveColorToString(t.SyntaxFunction())),
 				},
+				NameOther: ansi.StylePrimitive{
+					Color: stringPtr(adaptiveColorToString(t.SyntaxVariable())),
+				},
+				Literal: ansi.StylePrimitive{
+					Color: stringPtr(adaptiveColorToString(t.SyntaxString())),
+				},
 				LiteralNumber: ansi.StylePrimitive{
 					Color: stringPtr(adaptiveColorToString(t.SyntaxNumber())),
 				},
 				LiteralString: ansi.StylePrimitive{
 					Color: stringPtr(adaptiveColorToString(t.SyntaxString())),
 				},
+				LiteralDate: ansi.StylePrimitive{
+					Color: stringPtr(adaptiveColorToString(t.SyntaxNumber())),
+				},
 				LiteralStringEscape: ansi.StylePrimitive{
 					Color: stringPtr(adaptiveColorToString(t.SyntaxKeyword())),
 				},
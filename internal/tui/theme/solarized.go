@@ -0,0 +1,264 @@
+package theme
+
+import (
+	"github.com/charmbracelet/lipgloss"
+)
+
+// Solarized color palette constants
+const (
+	solarizedBase03  = "#002b36"
+	solarizedBase02  = "#073642"
+	solarizedBase01  = "#586e75"
+	solarizedBase00  = "#657b83"
+	solarizedBase0   = "#839496"
+	solarizedBase1   = "#93a1a1"
+	solarizedBase2   = "#eee8d5"
+	solarizedBase3   = "#fdf6e3"
+	solarizedYellow  = "#b58900"
+	solarizedOrange  = "#cb4b16"
+	solarizedRed     = "#dc322f"
+	solarizedMagenta = "#d33682"
+	solarizedViolet  = "#6c71c4"
+	solarizedBlue    = "#268bd2"
+	solarizedCyan    = "#2aa198"
+	solarizedGreen   = "#859900"
+)
+
+// SolarizedTheme implements the Theme interface with Solarized colors.
+// The accent colors are shared between the dark and light variants, which
+// only swap the base tones.
+type SolarizedTheme struct {
+	BaseTheme
+}
+
+// NewSolarizedTheme creates a new instance of the Solarized theme.
+func NewSolarizedTheme() *SolarizedTheme {
+	theme := &SolarizedTheme{}
+
+	// Base colors
+	theme.PrimaryColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBlue,
+		Light: solarizedBlue,
+	}
+	theme.SecondaryColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedViolet,
+		Light: solarizedViolet,
+	}
+	theme.AccentColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedOrange,
+		Light: solarizedOrange,
+	}
+
+	// Status colors
+	theme.ErrorColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedRed,
+		Light: solarizedRed,
+	}
+	theme.WarningColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedYellow,
+		Light: solarizedYellow,
+	}
+	theme.SuccessColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedGreen,
+		Light: solarizedGreen,
+	}
+	theme.InfoColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedCyan,
+		Light: solarizedCyan,
+	}
+
+	// Text colors
+	theme.TextColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase0,
+		Light: solarizedBase00,
+	}
+	theme.TextMutedColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase01,
+		Light: solarizedBase1,
+	}
+	theme.TextEmphasizedColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase1,
+		Light: solarizedBase01,
+	}
+
+	// Background colors
+	theme.BackgroundColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase03,
+		Light: solarizedBase3,
+	}
+	theme.BackgroundSecondaryColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase02,
+		Light: solarizedBase2,
+	}
+	theme.BackgroundDarkerColor = lipgloss.AdaptiveColor{
+		Dark:  "#00212b", // Slightly darker than base03
+		Light: "#f4eed8", // Between base2 and base3
+	}
+
+	// Border colors
+	theme.BorderNormalColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase02,
+		Light: solarizedBase2,
+	}
+	theme.BorderFocusedColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBlue,
+		Light: solarizedBlue,
+	}
+	theme.BorderDimColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase02,
+		Light: solarizedBase2,
+	}
+
+	// Diff view colors
+	theme.DiffAddedColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedGreen,
+		Light: solarizedGreen,
+	}
+	theme.DiffRemovedColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedRed,
+		Light: solarizedRed,
+	}
+	theme.DiffContextColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase01,
+		Light: solarizedBase1,
+	}
+	theme.DiffHunkHeaderColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedViolet,
+		Light: solarizedViolet,
+	}
+	theme.DiffHighlightAddedColor = lipgloss.AdaptiveColor{
+		Dark:  "#a4b800",
+		Light: "#6b7a00",
+	}
+	theme.DiffHighlightRemovedColor = lipgloss.AdaptiveColor{
+		Dark:  "#ff5f5a",
+		Light: "#b8211e",
+	}
+	theme.DiffAddedBgColor = lipgloss.AdaptiveColor{
+		Dark:  "#0b3a2a", // Darker green background
+		Light: "#eef2d5", // Light green background
+	}
+	theme.DiffRemovedBgColor = lipgloss.AdaptiveColor{
+		Dark:  "#2f2a33", // Darker red background
+		Light: "#fbe6dc", // Light red background
+	}
+	theme.DiffContextBgColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase03,
+		Light: solarizedBase3,
+	}
+	theme.DiffLineNumberColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase01,
+		Light: solarizedBase1,
+	}
+	theme.DiffAddedLineNumberBgColor = lipgloss.AdaptiveColor{
+		Dark:  "#083325", // Slightly darker green
+		Light: "#e4eac4", // Light green
+	}
+	theme.DiffRemovedLineNumberBgColor = lipgloss.AdaptiveColor{
+		Dark:  "#29242d", // Slightly darker red
+		Light: "#f6d9cc", // Light red
+	}
+
+	// Markdown colors
+	theme.MarkdownTextColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase0,
+		Light: solarizedBase00,
+	}
+	theme.MarkdownHeadingColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedYellow,
+		Light: solarizedYellow,
+	}
+	theme.MarkdownLinkColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBlue,
+		Light: solarizedBlue,
+	}
+	theme.MarkdownLinkTextColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedCyan,
+		Light: solarizedCyan,
+	}
+	theme.MarkdownCodeColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedGreen,
+		Light: solarizedGreen,
+	}
+	theme.MarkdownBlockQuoteColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase01,
+		Light: solarizedBase1,
+	}
+	theme.MarkdownEmphColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedMagenta,
+		Light: solarizedMagenta,
+	}
+	theme.MarkdownStrongColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedOrange,
+		Light: solarizedOrange,
+	}
+	theme.MarkdownHorizontalRuleColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase01,
+		Light: solarizedBase1,
+	}
+	theme.MarkdownListItemColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBlue,
+		Light: solarizedBlue,
+	}
+	theme.MarkdownListEnumerationColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedCyan,
+		Light: solarizedCyan,
+	}
+	theme.MarkdownImageColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedViolet,
+		Light: solarizedViolet,
+	}
+	theme.MarkdownImageTextColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedCyan,
+		Light: solarizedCyan,
+	}
+	theme.MarkdownCodeBlockColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase0,
+		Light: solarizedBase00,
+	}
+
+	// Syntax highlighting colors
+	theme.SyntaxCommentColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase01,
+		Light: solarizedBase1,
+	}
+	theme.SyntaxKeywordColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedGreen,
+		Light: solarizedGreen,
+	}
+	theme.SyntaxFunctionColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBlue,
+		Light: solarizedBlue,
+	}
+	theme.SyntaxVariableColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase0,
+		Light: solarizedBase00,
+	}
+	theme.SyntaxStringColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedCyan,
+		Light: solarizedCyan,
+	}
+	theme.SyntaxNumberColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedMagenta,
+		Light: solarizedMagenta,
+	}
+	theme.SyntaxTypeColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedYellow,
+		Light: solarizedYellow,
+	}
+	theme.SyntaxOperatorColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedGreen,
+		Light: solarizedGreen,
+	}
+	theme.SyntaxPunctuationColor = lipgloss.AdaptiveColor{
+		Dark:  solarizedBase0,
+		Light: solarizedBase00,
+	}
+
+	return theme
+}
+
+func init() {
+	// Register the Solarized theme with the theme manager
+	RegisterTheme("solarized", NewSolarizedTheme())
+}
@@ -47,6 +47,19 @@ func TestThemeRegistration(t *testing.T) {
 		t.Errorf("Monokai theme is not registered")
 	}
 	
+	// Check if "solarized" theme is registered
+	solarizedFound := false
+	for _, themeName := range availableThemes {
+		if themeName == "solarized" {
+			solarizedFound = true
+			break
+		}
+	}
+	
+	if !solarizedFound {
+		t.Errorf("Solarized theme is not registered")
+	}
+	
 	// Try to get the themes and make sure they're not nil
 	catppuccin := GetTheme("catppuccin")
 	if catppuccin == nil {
@@ -63,6 +76,11 @@ func TestThemeRegistration(t *testing.T) {
 		t.Errorf("Monokai theme is nil")
 	}
 	
+	solarized := GetTheme("solarized")
+	if solarized == nil {
+		t.Errorf("Solarized theme is nil")
+	}
+	
 	// Test switching theme
 	originalTheme := CurrentThemeName()
 	
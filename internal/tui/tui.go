@@ -3,15 +3,23 @@ package tui
 import (
 	"context"
 	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
 	"strings"
+	"time"
 
+	"github.com/atotto/clipboard"
 	"github.com/charmbracelet/bubbles/key"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
+	zone "github.com/lrstanley/bubblezone"
 	"github.com/zhenbah/cryoncode/internal/app"
 	"github.com/zhenbah/cryoncode/internal/config"
 	"github.com/zhenbah/cryoncode/internal/llm/agent"
+	"github.com/zhenbah/cryoncode/internal/llm/models"
 	"github.com/zhenbah/cryoncode/internal/logging"
+	"github.com/zhenbah/cryoncode/internal/message"
 	"github.com/zhenbah/cryoncode/internal/permission"
 	"github.com/zhenbah/cryoncode/internal/pubsub"
 	"github.com/zhenbah/cryoncode/internal/session"
@@ -20,6 +28,7 @@ import (
 	"github.com/zhenbah/cryoncode/internal/tui/components/dialog"
 	"github.com/zhenbah/cryoncode/internal/tui/layout"
 	"github.com/zhenbah/cryoncode/internal/tui/page"
+	"github.com/zhenbah/cryoncode/internal/tui/state"
 	"github.com/zhenbah/cryoncode/internal/tui/theme"
 	"github.com/zhenbah/cryoncode/internal/tui/util"
 )
@@ -29,16 +38,89 @@ type keyMap struct {
 	Quit          key.Binding
 	Help          key.Binding
 	SwitchSession key.Binding
+	FindSession   key.Binding
 	Commands      key.Binding
 	Filepicker    key.Binding
 	Models        key.Binding
 	SwitchTheme   key.Binding
+	Undo          key.Binding
+	Copy          key.Binding
+	CopyCodeBlock key.Binding
 }
 
 type startCompactSessionMsg struct{}
 
+type showUndoDialogMsg struct{}
+
+type showCheckpointDialogMsg struct{}
+
+type retryResponseMsg struct{}
+
+type showPromptDialogMsg struct {
+	Action dialog.PromptAction
+}
+
+type showChangesDialogMsg struct{}
+
+type showGrantsDialogMsg struct{}
+
+type showCopyDialogMsg struct{}
+
+type copyNextCodeBlockMsg struct{}
+
+// exportSessionMsg writes the session as Markdown to the file at Path, or to
+// the clipboard when there is none
+type exportSessionMsg struct {
+	Path string
+}
+
+// showSessionDialogMsg opens the session switcher, listing the sessions with
+// Tag when it's set
+type showSessionDialogMsg struct {
+	Tag string
+}
+
+// switchAgentMsg switches to the agent Name, or lists the agents when it's
+// empty
+type switchAgentMsg struct {
+	Name string
+}
+
+// tagSessionMsg adds the tags in Args to the current session, tags prefixed
+// with - are removed
+type tagSessionMsg struct {
+	Args string
+}
+
+// pruneSessionsMsg archives the sessions last active before Age, or before
+// defaultPruneAge when it's empty
+type pruneSessionsMsg struct {
+	Age string
+}
+
+type showSessionFinderMsg struct{}
+
+type showMessageSearchMsg struct {
+	Query string
+}
+
+type showModelDialogMsg struct{}
+
+type showThemeDialogMsg struct{}
+
 const (
 	quitKey = "q"
+
+	// maxSessionSearchResults is the number of sessions the session finder
+	// loads for a query
+	maxSessionSearchResults = 50
+
+	// maxMessageSearchResults is the number of messages the message search
+	// loads for a query
+	maxMessageSearchResults = 50
+
+	// defaultPruneAge is the age of the sessions archived by /prune
+	defaultPruneAge = "30d"
 )
 
 var keys = keyMap{
@@ -61,9 +143,14 @@ var keys = keyMap{
 		key.WithHelp("ctrl+s", "switch session"),
 	),
 
-	Commands: key.NewBinding(
+	FindSession: key.NewBinding(
 		key.WithKeys("ctrl+k"),
-		key.WithHelp("ctrl+k", "commands"),
+		key.WithHelp("ctrl+k", "find session"),
+	),
+
+	Commands: key.NewBinding(
+		key.WithKeys("ctrl+p"),
+		key.WithHelp("ctrl+p", "command palette"),
 	),
 	Filepicker: key.NewBinding(
 		key.WithKeys("ctrl+f"),
@@ -78,6 +165,36 @@ var keys = keyMap{
 		key.WithKeys("ctrl+t"),
 		key.WithHelp("ctrl+t", "switch theme"),
 	),
+
+	Undo: key.NewBinding(
+		key.WithKeys("ctrl+g"),
+		key.WithHelp("ctrl+g", "undo file changes"),
+	),
+
+	Copy: key.NewBinding(
+		key.WithKeys("alt+y"),
+		key.WithHelp("alt+y", "copy last response"),
+	),
+
+	CopyCodeBlock: key.NewBinding(
+		key.WithKeys("alt+c"),
+		key.WithHelp("alt+c", "copy next code block"),
+	),
+}
+
+func init() {
+	layout.RegisterAction("logs", &keys.Logs)
+	layout.RegisterAction("quit", &keys.Quit)
+	layout.RegisterAction("help", &keys.Help)
+	layout.RegisterAction("switch_session", &keys.SwitchSession)
+	layout.RegisterAction("find_session", &keys.FindSession)
+	layout.RegisterAction("commands", &keys.Commands)
+	layout.RegisterAction("filepicker", &keys.Filepicker)
+	layout.RegisterAction("models", &keys.Models)
+	layout.RegisterAction("switch_theme", &keys.SwitchTheme)
+	layout.RegisterAction("undo_changes", &keys.Undo)
+	layout.RegisterAction("copy_response", &keys.Copy)
+	layout.RegisterAction("copy_code_block", &keys.CopyCodeBlock)
 }
 
 var helpEsc = key.NewBinding(
@@ -102,6 +219,7 @@ type appModel struct {
 	pages           map[page.PageID]tea.Model
 	loadedPages     map[page.PageID]bool
 	status          core.StatusCmp
+	toasts          core.ToastsCmp
 	app             *app.App
 	selectedSession session.Session
 
@@ -117,6 +235,34 @@ type appModel struct {
 	showSessionDialog bool
 	sessionDialog     dialog.SessionDialog
 
+	showSessionFinder   bool
+	sessionFinderDialog dialog.SessionFinderDialog
+
+	showMessageSearch   bool
+	messageSearchDialog dialog.MessageSearchDialog
+
+	showUndoDialog bool
+	undoDialog     dialog.UndoDialog
+
+	showCheckpointDialog bool
+	checkpointDialog     dialog.CheckpointDialog
+
+	showPromptDialog bool
+	promptDialog     dialog.PromptDialog
+
+	showChangesDialog bool
+	changesDialog     dialog.ChangesDialog
+
+	showGrantsDialog bool
+	grantsDialog     dialog.GrantsDialog
+
+	showCopyDialog bool
+	copyDialog     dialog.CopyDialog
+	// copiedBlock is the code block of the response copiedFrom that was
+	// copied last, so the next one is copied on the next press
+	copiedBlock int
+	copiedFrom  string
+
 	showCommandDialog bool
 	commandDialog     dialog.CommandDialog
 	commands          []dialog.Command
@@ -136,6 +282,9 @@ type appModel struct {
 	showMultiArgumentsDialog bool
 	multiArgumentsDialog     dialog.MultiArgumentsDialogCmp
 
+	showNewSessionDialog bool
+	newSessionDialog     dialog.NewSessionDialogCmp
+
 	isCompacting      bool
 	compactingMessage string
 }
@@ -176,6 +325,18 @@ func (a appModel) Init() tea.Cmd {
 		return dialog.ShowInitDialogMsg{Show: shouldShow}
 	})
 
+	// Reopen the session the TUI was left on
+	if sessionID := state.Saved().SessionID; sessionID != "" {
+		cmds = append(cmds, func() tea.Msg {
+			sess, err := a.app.Sessions.Get(context.Background(), sessionID)
+			if err != nil {
+				// The session was deleted since
+				return nil
+			}
+			return chat.SessionSelectedMsg(sess)
+		})
+	}
+
 	return tea.Batch(cmds...)
 }
 
@@ -189,6 +350,8 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 
 		s, _ := a.status.Update(msg)
 		a.status = s.(core.StatusCmp)
+		t, _ := a.toasts.Update(msg)
+		a.toasts = t.(core.ToastsCmp)
 		a.pages[a.currentPage], cmd = a.pages[a.currentPage].Update(msg)
 		cmds = append(cmds, cmd)
 
@@ -204,6 +367,38 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		a.sessionDialog = session.(dialog.SessionDialog)
 		cmds = append(cmds, sessionCmd)
 
+		finder, finderCmd := a.sessionFinderDialog.Update(msg)
+		a.sessionFinderDialog = finder.(dialog.SessionFinderDialog)
+		cmds = append(cmds, finderCmd)
+
+		search, searchCmd := a.messageSearchDialog.Update(msg)
+		a.messageSearchDialog = search.(dialog.MessageSearchDialog)
+		cmds = append(cmds, searchCmd)
+
+		undo, undoCmd := a.undoDialog.Update(msg)
+		a.undoDialog = undo.(dialog.UndoDialog)
+		cmds = append(cmds, undoCmd)
+
+		checkpoint, checkpointCmd := a.checkpointDialog.Update(msg)
+		a.checkpointDialog = checkpoint.(dialog.CheckpointDialog)
+		cmds = append(cmds, checkpointCmd)
+
+		prompt, promptCmd := a.promptDialog.Update(msg)
+		a.promptDialog = prompt.(dialog.PromptDialog)
+		cmds = append(cmds, promptCmd)
+
+		changes, changesCmd := a.changesDialog.Update(msg)
+		a.changesDialog = changes.(dialog.ChangesDialog)
+		cmds = append(cmds, changesCmd)
+
+		grants, grantsCmd := a.grantsDialog.Update(msg)
+		a.grantsDialog = grants.(dialog.GrantsDialog)
+		cmds = append(cmds, grantsCmd)
+
+		copyModel, copyCmd := a.copyDialog.Update(msg)
+		a.copyDialog = copyModel.(dialog.CopyDialog)
+		cmds = append(cmds, copyCmd)
+
 		command, commandCmd := a.commandDialog.Update(msg)
 		a.commandDialog = command.(dialog.CommandDialog)
 		cmds = append(cmds, commandCmd)
@@ -221,55 +416,37 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			cmds = append(cmds, argsCmd, a.multiArgumentsDialog.Init())
 		}
 
+		if a.showNewSessionDialog {
+			a.newSessionDialog.SetSize(msg.Width, msg.Height)
+		}
+
 		return a, tea.Batch(cmds...)
 	// Status
 	case util.InfoMsg:
-		s, cmd := a.status.Update(msg)
-		a.status = s.(core.StatusCmp)
+		t, cmd := a.toasts.Update(msg)
+		a.toasts = t.(core.ToastsCmp)
 		cmds = append(cmds, cmd)
 		return a, tea.Batch(cmds...)
 	case pubsub.Event[logging.LogMessage]:
 		if msg.Payload.Persist {
+			infoType := util.InfoTypeInfo
 			switch msg.Payload.Level {
 			case "error":
-				s, cmd := a.status.Update(util.InfoMsg{
-					Type: util.InfoTypeError,
-					Msg:  msg.Payload.Message,
-					TTL:  msg.Payload.PersistTime,
-				})
-				a.status = s.(core.StatusCmp)
-				cmds = append(cmds, cmd)
-			case "info":
-				s, cmd := a.status.Update(util.InfoMsg{
-					Type: util.InfoTypeInfo,
-					Msg:  msg.Payload.Message,
-					TTL:  msg.Payload.PersistTime,
-				})
-				a.status = s.(core.StatusCmp)
-				cmds = append(cmds, cmd)
-
+				infoType = util.InfoTypeError
 			case "warn":
-				s, cmd := a.status.Update(util.InfoMsg{
-					Type: util.InfoTypeWarn,
-					Msg:  msg.Payload.Message,
-					TTL:  msg.Payload.PersistTime,
-				})
-
-				a.status = s.(core.StatusCmp)
-				cmds = append(cmds, cmd)
-			default:
-				s, cmd := a.status.Update(util.InfoMsg{
-					Type: util.InfoTypeInfo,
-					Msg:  msg.Payload.Message,
-					TTL:  msg.Payload.PersistTime,
-				})
-				a.status = s.(core.StatusCmp)
-				cmds = append(cmds, cmd)
-			}
-		}
-	case util.ClearStatusMsg:
-		s, _ := a.status.Update(msg)
-		a.status = s.(core.StatusCmp)
+				infoType = util.InfoTypeWarn
+			}
+			t, cmd := a.toasts.Update(util.InfoMsg{
+				Type: infoType,
+				Msg:  msg.Payload.Message,
+				TTL:  msg.Payload.PersistTime,
+			})
+			a.toasts = t.(core.ToastsCmp)
+			cmds = append(cmds, cmd)
+		}
+
+	case pubsub.Event[config.Reloaded]:
+		return a, a.applyConfig(msg.Payload)
 
 	// Permission
 	case pubsub.Event[permission.PermissionRequest]:
@@ -282,6 +459,12 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			a.app.Permissions.Grant(msg.Permission)
 		case dialog.PermissionAllowForSession:
 			a.app.Permissions.GrantPersistant(msg.Permission)
+		case dialog.PermissionAllowAlways:
+			if err := a.app.Permissions.GrantAlways(context.Background(), msg.Permission); err != nil {
+				cmd = util.ReportError(err)
+			} else {
+				cmd = util.ReportInfo(fmt.Sprintf("%s is always allowed now, see Manage Permissions", msg.Permission.ToolName))
+			}
 		case dialog.PermissionDeny:
 			a.app.Permissions.Deny(msg.Permission)
 		}
@@ -295,6 +478,16 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		a.showQuit = false
 		return a, nil
 
+	case dialog.StopPendingWorkMsg:
+		a.showQuit = false
+		// The tool waiting for permission is denied so its request can end
+		if a.showPermissions {
+			a.app.Permissions.Deny(a.permissions.Permission())
+			a.showPermissions = false
+		}
+		a.app.CoderAgent.CancelAll()
+		return a, util.ReportInfo("Stopped the pending request")
+
 	case dialog.CloseSessionDialogMsg:
 		a.showSessionDialog = false
 		return a, nil
@@ -303,6 +496,499 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		a.showCommandDialog = false
 		return a, nil
 
+	case showSessionDialogMsg:
+		// Load sessions and show the dialog
+		sessions, err := a.app.Sessions.List(context.Background())
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if len(sessions) == 0 {
+			return a, util.ReportWarn("No sessions available")
+		}
+		tag := session.NormalizeTag(msg.Tag)
+		if tag != "" && !slices.ContainsFunc(sessions, func(s session.Session) bool { return s.HasTag(tag) }) {
+			return a, util.ReportWarn(fmt.Sprintf("No sessions tagged #%s", tag))
+		}
+		a.sessionDialog.SetSessions(sessions)
+		a.sessionDialog.ShowArchived(false)
+		a.sessionDialog.SetTag(tag)
+		a.showSessionDialog = true
+		return a, nil
+
+	case switchAgentMsg:
+		name := config.AgentName(strings.ToLower(strings.TrimSpace(msg.Name)))
+		if name == "" {
+			var agents []string
+			for _, agentName := range config.SessionAgents() {
+				if description := config.Get().Agents[agentName].Description; description != "" {
+					agents = append(agents, fmt.Sprintf("%s (%s)", agentName, description))
+				} else {
+					agents = append(agents, string(agentName))
+				}
+			}
+			return a, util.ReportInfo(fmt.Sprintf("Agent: %s. Agents: %s", a.app.CoderAgent.Name(), strings.Join(agents, ", ")))
+		}
+		model, err := a.app.CoderAgent.UseAgent(name)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if a.selectedSession.ID != "" {
+			if _, err := a.app.Sessions.SetSettings(context.Background(), a.selectedSession.ID, a.app.CoderAgent.Settings()); err != nil {
+				return a, util.ReportError(err)
+			}
+		}
+		return a, util.ReportInfo(fmt.Sprintf("Agent changed to %s with %s", name, model.Name))
+
+	case tagSessionMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session to tag")
+		}
+		sess := a.selectedSession
+		for _, tag := range strings.Fields(msg.Args) {
+			var err error
+			if name, ok := strings.CutPrefix(tag, "-"); ok {
+				sess, err = a.app.Sessions.RemoveTag(context.Background(), sess.ID, name)
+			} else {
+				sess, err = a.app.Sessions.AddTag(context.Background(), sess.ID, tag)
+			}
+			if err != nil {
+				return a, util.ReportError(err)
+			}
+		}
+		if len(sess.Tags) == 0 {
+			return a, util.ReportInfo("The session has no tags")
+		}
+		return a, util.ReportInfo("Tags: #" + strings.Join(sess.Tags, " #"))
+
+	case pruneSessionsMsg:
+		age := strings.TrimSpace(msg.Age)
+		if age == "" {
+			age = defaultPruneAge
+		}
+		d, err := session.ParseAge(age)
+		if err != nil {
+			return a, util.ReportWarn(err.Error())
+		}
+		pruned, err := a.app.Sessions.Prune(context.Background(), time.Now().Add(-d), true)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if len(pruned) == 0 {
+			return a, util.ReportInfo(fmt.Sprintf("No sessions were inactive for %s", age))
+		}
+		return a, util.ReportInfo(fmt.Sprintf("Archived %d sessions inactive for %s, press A in the session list to see them", len(pruned), age))
+
+	case showSessionFinderMsg:
+		a.showSessionFinder = true
+		return a, a.sessionFinderDialog.Reset()
+
+	case dialog.SearchSessionsMsg:
+		results, err := a.app.Sessions.Search(context.Background(), msg.Query, maxSessionSearchResults)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.sessionFinderDialog.SetResults(msg.Query, results)
+		return a, nil
+
+	case dialog.CloseSessionFinderMsg:
+		a.showSessionFinder = false
+		return a, nil
+
+	case dialog.OpenSessionMsg:
+		a.showSessionFinder = false
+		sess, err := a.app.Sessions.Get(context.Background(), msg.SessionID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if a.currentPage == page.ChatPage {
+			return a, util.CmdHandler(chat.SessionSelectedMsg(sess))
+		}
+		return a, nil
+
+	case showMessageSearchMsg:
+		a.showMessageSearch = true
+		return a, a.messageSearchDialog.Reset(msg.Query)
+
+	case dialog.SearchMessagesMsg:
+		results, err := a.app.Messages.Search(context.Background(), msg.Query, maxMessageSearchResults)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.messageSearchDialog.SetResults(msg.Query, results)
+		return a, nil
+
+	case dialog.CloseMessageSearchMsg:
+		a.showMessageSearch = false
+		return a, nil
+
+	case dialog.OpenMessageMsg:
+		a.showMessageSearch = false
+		sess, err := a.app.Sessions.Get(context.Background(), msg.SessionID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if a.currentPage == page.ChatPage {
+			// The message is scrolled to once the session is shown
+			return a, tea.Sequence(
+				util.CmdHandler(chat.SessionSelectedMsg(sess)),
+				util.CmdHandler(chat.ScrollToMessageMsg{ID: msg.MessageID}),
+			)
+		}
+		return a, nil
+
+	case showModelDialogMsg:
+		a.showModelDialog = true
+		return a, nil
+
+	case showThemeDialogMsg:
+		a.showThemeDialog = true
+		// Theme list is dynamically loaded by the dialog component
+		return a, a.themeDialog.Init()
+
+	case chat.SlashCommandMsg:
+		switch msg.Name {
+		case "new":
+			if msg.Args == "" {
+				return a, util.CmdHandler(dialog.ShowNewSessionDialogMsg{})
+			}
+			return a, util.CmdHandler(dialog.CloseNewSessionDialogMsg{Submit: true, Title: msg.Args})
+		case "clear":
+			// The session is created with the next message
+			return a, util.CmdHandler(chat.SessionClearedMsg{})
+		case "model":
+			if msg.Args == "" {
+				return a, util.CmdHandler(showModelDialogMsg{})
+			}
+			model, ok := config.ResolveModel(msg.Args)
+			if !ok {
+				return a, util.ReportWarn(fmt.Sprintf("Unknown model %q", msg.Args))
+			}
+			return a, util.CmdHandler(dialog.ModelSelectedMsg{Model: model})
+		case "agent":
+			return a, util.CmdHandler(switchAgentMsg{Name: msg.Args})
+		case "compact":
+			return a, util.CmdHandler(startCompactSessionMsg{})
+		case "sessions":
+			return a, util.CmdHandler(showSessionDialogMsg{Tag: msg.Args})
+		case "tag":
+			return a, util.CmdHandler(tagSessionMsg{Args: msg.Args})
+		case "prune":
+			return a, util.CmdHandler(pruneSessionsMsg{Age: msg.Args})
+		case "search":
+			return a, util.CmdHandler(showMessageSearchMsg{Query: msg.Args})
+		case "export":
+			return a, util.CmdHandler(exportSessionMsg{Path: msg.Args})
+		case "undo":
+			return a, util.CmdHandler(showUndoDialogMsg{})
+		case "checkpoints":
+			return a, util.CmdHandler(showCheckpointDialogMsg{})
+		case "fork":
+			return a, util.CmdHandler(showPromptDialogMsg{Action: dialog.ForkAtPrompt})
+		case "edit":
+			return a, util.CmdHandler(showPromptDialogMsg{Action: dialog.EditPrompt})
+		case "delete":
+			return a, util.CmdHandler(showPromptDialogMsg{Action: dialog.DeleteMessage})
+		case "retry":
+			if msg.Args == "" {
+				return a, util.CmdHandler(retryResponseMsg{})
+			}
+			model, ok := config.ResolveModel(msg.Args)
+			if !ok {
+				return a, util.ReportWarn(fmt.Sprintf("Unknown model %q", msg.Args))
+			}
+			return a, tea.Sequence(
+				util.CmdHandler(dialog.ModelSelectedMsg{Model: model}),
+				util.CmdHandler(retryResponseMsg{}),
+			)
+		case "init":
+			if cmd, ok := a.findCommand("init"); ok {
+				return a, cmd.Handler(cmd)
+			}
+		case "help":
+			a.showHelp = true
+			return a, nil
+		}
+		return a, nil
+
+	case showUndoDialogMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session to undo changes in")
+		}
+		if a.app.CoderAgent.IsSessionBusy(a.selectedSession.ID) {
+			return a, util.ReportWarn("Agent is busy, please wait...")
+		}
+		snapshots, err := a.app.Snapshots.ListBySession(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if len(snapshots) == 0 {
+			return a, util.ReportWarn("No file changes to undo")
+		}
+		messages, err := a.app.Messages.List(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.undoDialog.SetMessages(messages)
+		a.showUndoDialog = true
+		return a, nil
+
+	case dialog.CloseUndoDialogMsg:
+		a.showUndoDialog = false
+		return a, nil
+
+	case dialog.UndoSelectedMsg:
+		a.showUndoDialog = false
+		paths, err := a.app.Snapshots.RevertSince(context.Background(), a.selectedSession.ID, msg.MessageID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if len(paths) == 0 {
+			return a, util.ReportInfo("No file changes since the selected prompt")
+		}
+		return a, util.ReportInfo(fmt.Sprintf("Reverted changes to %d file(s)", len(paths)))
+
+	case showCheckpointDialogMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session")
+		}
+		if a.app.CoderAgent.IsSessionBusy(a.selectedSession.ID) {
+			return a, util.ReportWarn("Agent is busy, please wait...")
+		}
+		checkpoints, err := a.app.Snapshots.ListCheckpoints(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if len(checkpoints) == 0 {
+			return a, util.ReportWarn("No checkpoints in this session")
+		}
+		messages, err := a.app.Messages.List(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.checkpointDialog.SetCheckpoints(checkpoints, messages)
+		a.showCheckpointDialog = true
+		return a, nil
+
+	case dialog.CloseCheckpointDialogMsg:
+		a.showCheckpointDialog = false
+		return a, nil
+
+	case dialog.RestoreCheckpointMsg:
+		a.showCheckpointDialog = false
+		if a.app.CoderAgent.IsSessionBusy(a.selectedSession.ID) {
+			return a, util.ReportWarn("Agent is busy, please wait...")
+		}
+		paths, err := a.app.Snapshots.RestoreCheckpoint(context.Background(), a.selectedSession.ID, msg.CheckpointID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, util.ReportInfo(fmt.Sprintf("Restored the checkpoint, reverted changes to %d file(s)", len(paths)))
+
+	case retryResponseMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session to retry")
+		}
+		if a.app.CoderAgent.IsSessionBusy(a.selectedSession.ID) {
+			return a, util.ReportWarn("Agent is busy, please wait...")
+		}
+		prompt, err := a.lastPrompt()
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if prompt.ID == "" {
+			return a, util.ReportWarn("No prompt to retry")
+		}
+		// The prompt is sent again in place of itself and the responses to it
+		return a, util.CmdHandler(chat.SendMsg{
+			Text:        prompt.Content().String(),
+			Attachments: prompt.Attachments(),
+			Replaces:    prompt.ID,
+		})
+
+	case showPromptDialogMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session")
+		}
+		if a.app.CoderAgent.IsSessionBusy(a.selectedSession.ID) {
+			return a, util.ReportWarn("Agent is busy, please wait...")
+		}
+		messages, err := a.app.Messages.List(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.promptDialog.SetMessages(msg.Action, messages)
+		a.showPromptDialog = true
+		return a, nil
+
+	case dialog.ClosePromptDialogMsg:
+		a.showPromptDialog = false
+		return a, nil
+
+	case dialog.PromptSelectedMsg:
+		a.showPromptDialog = false
+		if msg.Action == dialog.DeleteMessage {
+			err := a.app.Messages.DeleteWithToolPair(context.Background(), a.selectedSession.ID, msg.MessageID)
+			if err != nil {
+				return a, util.ReportError(err)
+			}
+			return a, util.ReportInfo("Message deleted")
+		}
+		if msg.Action == dialog.EditPrompt {
+			prompt, err := a.app.Messages.Get(context.Background(), msg.MessageID)
+			if err != nil {
+				return a, util.ReportError(err)
+			}
+			return a, tea.Batch(
+				util.CmdHandler(chat.EditPromptMsg{Prompt: prompt}),
+				util.ReportInfo("Send the prompt to replace it and the messages after it, clear it to cancel"),
+			)
+		}
+		fork, err := a.app.Sessions.Fork(context.Background(), a.selectedSession.ID, msg.MessageID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, tea.Batch(
+			util.CmdHandler(chat.SessionSelectedMsg(fork)),
+			util.ReportInfo("Forked into "+fork.Title),
+		)
+
+	case showChangesDialogMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session")
+		}
+		files, err := a.app.Snapshots.ChangedFiles(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if len(files) == 0 {
+			return a, util.ReportWarn("No files changed in this session")
+		}
+		a.changesDialog.SetFiles(files)
+		a.showChangesDialog = true
+		return a, nil
+
+	case dialog.CloseChangesDialogMsg:
+		a.showChangesDialog = false
+		return a, nil
+
+	case dialog.RevertFileMsg:
+		if a.app.CoderAgent.IsSessionBusy(a.selectedSession.ID) {
+			return a, util.ReportWarn("Agent is busy, please wait...")
+		}
+		if err := a.app.Snapshots.RevertFile(context.Background(), a.selectedSession.ID, msg.Path); err != nil {
+			return a, util.ReportError(err)
+		}
+		files, err := a.app.Snapshots.ChangedFiles(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.changesDialog.SetFiles(files)
+		if len(files) == 0 {
+			a.showChangesDialog = false
+		}
+		return a, util.ReportInfo("Reverted " + msg.Path)
+
+	case showGrantsDialogMsg:
+		grants, err := a.app.Permissions.ListGrants(context.Background())
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		a.grantsDialog.SetGrants(grants)
+		a.showGrantsDialog = true
+		return a, nil
+
+	case dialog.CloseGrantsDialogMsg:
+		a.showGrantsDialog = false
+		return a, nil
+
+	case dialog.UpdateGrantMsg:
+		if err := a.app.Permissions.UpdateGrant(context.Background(), msg.GrantID, msg.Pattern); err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, util.CmdHandler(showGrantsDialogMsg{})
+
+	case dialog.DeleteGrantMsg:
+		if err := a.app.Permissions.DeleteGrant(context.Background(), msg.GrantID); err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, tea.Batch(util.CmdHandler(showGrantsDialogMsg{}), util.ReportInfo("Permission revoked"))
+
+	case showCopyDialogMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session")
+		}
+		response, err := a.lastResponse()
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		content := response.Content().String()
+		if content == "" {
+			return a, util.ReportWarn("No response to copy")
+		}
+		if len(dialog.ExtractCodeBlocks(content)) == 0 {
+			// Nothing to choose from, copy the response right away
+			return a, util.CmdHandler(dialog.CopySelectedMsg{Text: content})
+		}
+		a.copyDialog.SetContent(content)
+		a.showCopyDialog = true
+		return a, nil
+
+	case copyNextCodeBlockMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session")
+		}
+		response, err := a.lastResponse()
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		blocks := dialog.ExtractCodeBlocks(response.Content().String())
+		if len(blocks) == 0 {
+			return a, util.ReportWarn("No code blocks in the last response")
+		}
+		next := 0
+		if response.ID == a.copiedFrom {
+			next = (a.copiedBlock + 1) % len(blocks)
+		}
+		a.copiedFrom, a.copiedBlock = response.ID, next
+		if err := clipboard.WriteAll(blocks[next].Code); err != nil {
+			return a, util.ReportError(fmt.Errorf("failed to copy to the clipboard: %w", err))
+		}
+		return a, util.ReportInfo(fmt.Sprintf("Copied code block %d of %d", next+1, len(blocks)))
+
+	case exportSessionMsg:
+		if a.selectedSession.ID == "" {
+			return a, util.ReportWarn("No active session to export")
+		}
+		transcript, err := a.app.ExportMarkdown(context.Background(), a.selectedSession.ID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		if msg.Path == "" {
+			if err := clipboard.WriteAll(transcript); err != nil {
+				return a, util.ReportError(fmt.Errorf("failed to copy to the clipboard: %w", err))
+			}
+			return a, util.ReportInfo("Copied the session as Markdown")
+		}
+		path := msg.Path
+		if !filepath.IsAbs(path) {
+			path = filepath.Join(config.WorkingDirectory(), path)
+		}
+		if err := os.WriteFile(path, []byte(transcript), 0o644); err != nil {
+			return a, util.ReportError(fmt.Errorf("failed to export the session: %w", err))
+		}
+		return a, util.ReportInfo(fmt.Sprintf("Exported the session to %s", msg.Path))
+
+	case dialog.CloseCopyDialogMsg:
+		a.showCopyDialog = false
+		return a, nil
+
+	case dialog.CopySelectedMsg:
+		a.showCopyDialog = false
+		if err := clipboard.WriteAll(msg.Text); err != nil {
+			return a, util.ReportError(fmt.Errorf("failed to copy to the clipboard: %w", err))
+		}
+		return a, util.ReportInfo("Copied to the clipboard")
+
 	case startCompactSessionMsg:
 		// Start compacting the current session
 		a.isCompacting = true
@@ -336,7 +1022,7 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			model := a.app.CoderAgent.Model()
 			contextWindow := model.ContextWindow
 			tokens := a.selectedSession.CompletionTokens + a.selectedSession.PromptTokens
-			if (tokens >= int64(float64(contextWindow)*0.95)) && config.Get().AutoCompact {
+			if (tokens >= int64(float64(contextWindow)*config.Get().Compaction.Threshold)) && config.Get().AutoCompact {
 				return a, util.CmdHandler(startCompactSessionMsg{})
 			}
 		}
@@ -359,10 +1045,15 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	case dialog.ModelSelectedMsg:
 		a.showModelDialog = false
 
-		model, err := a.app.CoderAgent.Update(config.AgentCoder, msg.Model.ID)
+		model, err := a.app.CoderAgent.Update(a.app.CoderAgent.Name(), msg.Model.ID)
 		if err != nil {
 			return a, util.ReportError(err)
 		}
+		if a.selectedSession.ID != "" {
+			if _, err := a.app.Sessions.SetSettings(context.Background(), a.selectedSession.ID, a.app.CoderAgent.Settings()); err != nil {
+				return a, util.ReportError(err)
+			}
+		}
 
 		return a, util.ReportInfo(fmt.Sprintf("Model changed to %s", model.Name))
 
@@ -394,6 +1085,18 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	case chat.SessionSelectedMsg:
 		a.selectedSession = msg
 		a.sessionDialog.SetSelectedSession(msg.ID)
+		cmds = append(cmds, a.restoreSessionSettings(msg))
+
+	case chat.SessionClearedMsg:
+		a.selectedSession = session.Session{}
+		a.sessionDialog.SetSelectedSession("")
+		cmds = append(cmds, a.restoreSessionSettings(a.selectedSession))
+
+	case chat.ShowSessionsMsg:
+		if a.dialogShown() {
+			return a, nil
+		}
+		return a, util.CmdHandler(showSessionDialogMsg{})
 
 	case pubsub.Event[session.Session]:
 		if msg.Type == pubsub.UpdatedEvent && msg.Payload.ID == a.selectedSession.ID {
@@ -406,6 +1109,45 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 		return a, nil
 
+	case dialog.RenameSessionMsg:
+		sess, err := a.app.Sessions.Get(context.Background(), msg.SessionID)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		sess.Title = msg.Title
+		if _, err := a.app.Sessions.Save(context.Background(), sess); err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, a.reloadSessionDialog()
+
+	case dialog.ArchiveSessionMsg:
+		if _, err := a.app.Sessions.SetArchived(context.Background(), msg.SessionID, msg.Archived); err != nil {
+			return a, util.ReportError(err)
+		}
+		if msg.Archived {
+			return a, tea.Batch(a.reloadSessionDialog(), util.ReportInfo("Session archived"))
+		}
+		return a, tea.Batch(a.reloadSessionDialog(), util.ReportInfo("Session restored"))
+
+	case dialog.PinSessionMsg:
+		if _, err := a.app.Sessions.SetPinned(context.Background(), msg.SessionID, msg.Pinned); err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, a.reloadSessionDialog()
+
+	case dialog.DeleteSessionMsg:
+		if a.app.CoderAgent.IsSessionBusy(msg.SessionID) {
+			return a, util.ReportWarn("Agent is working on this session, please wait...")
+		}
+		if err := a.app.Sessions.Delete(context.Background(), msg.SessionID); err != nil {
+			return a, util.ReportError(err)
+		}
+		cmds = append(cmds, a.reloadSessionDialog(), util.ReportInfo("Session deleted"))
+		if msg.SessionID == a.selectedSession.ID {
+			cmds = append(cmds, util.CmdHandler(chat.SessionClearedMsg{}))
+		}
+		return a, tea.Batch(cmds...)
+
 	case dialog.CommandSelectedMsg:
 		a.showCommandDialog = false
 		// Execute the command handler if available
@@ -442,6 +1184,27 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 		return a, nil
 
+	case dialog.ShowNewSessionDialogMsg:
+		a.newSessionDialog = dialog.NewNewSessionDialogCmp()
+		a.newSessionDialog.SetSize(a.width, a.height)
+		a.showNewSessionDialog = true
+		return a, a.newSessionDialog.Init()
+
+	case dialog.CloseNewSessionDialogMsg:
+		a.showNewSessionDialog = false
+		if !msg.Submit {
+			return a, nil
+		}
+		if msg.Title == "" {
+			// The session is created with the first message
+			return a, util.CmdHandler(chat.SessionClearedMsg{})
+		}
+		newSession, err := a.app.Sessions.Create(context.Background(), msg.Title)
+		if err != nil {
+			return a, util.ReportError(err)
+		}
+		return a, util.CmdHandler(chat.SessionSelectedMsg(newSession))
+
 	case tea.KeyMsg:
 		// If multi-arguments dialog is open, let it handle the key press first
 		if a.showMultiArgumentsDialog {
@@ -449,17 +1212,55 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			a.multiArgumentsDialog = args.(dialog.MultiArgumentsDialogCmp)
 			return a, cmd
 		}
+		if a.showNewSessionDialog {
+			d, cmd := a.newSessionDialog.Update(msg)
+			a.newSessionDialog = d.(dialog.NewSessionDialogCmp)
+			return a, cmd
+		}
+		// Keys are typed into the query of the session finder
+		if a.showSessionFinder {
+			d, cmd := a.sessionFinderDialog.Update(msg)
+			a.sessionFinderDialog = d.(dialog.SessionFinderDialog)
+			return a, cmd
+		}
+		// and of the message search
+		if a.showMessageSearch {
+			d, cmd := a.messageSearchDialog.Update(msg)
+			a.messageSearchDialog = d.(dialog.MessageSearchDialog)
+			return a, cmd
+		}
 
 		switch {
 
 		case key.Matches(msg, keys.Quit):
 			a.showQuit = !a.showQuit
+			if a.showQuit {
+				a.quit.SetPendingWork(a.pendingWork())
+			}
 			if a.showHelp {
 				a.showHelp = false
 			}
 			if a.showSessionDialog {
 				a.showSessionDialog = false
 			}
+			if a.showUndoDialog {
+				a.showUndoDialog = false
+			}
+			if a.showCheckpointDialog {
+				a.showCheckpointDialog = false
+			}
+			if a.showPromptDialog {
+				a.showPromptDialog = false
+			}
+			if a.showChangesDialog {
+				a.showChangesDialog = false
+			}
+			if a.showGrantsDialog {
+				a.showGrantsDialog = false
+			}
+			if a.showCopyDialog {
+				a.showCopyDialog = false
+			}
 			if a.showCommandDialog {
 				a.showCommandDialog = false
 			}
@@ -473,23 +1274,40 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			if a.showMultiArgumentsDialog {
 				a.showMultiArgumentsDialog = false
 			}
+			if a.showNewSessionDialog {
+				a.showNewSessionDialog = false
+			}
 			return a, nil
 		case key.Matches(msg, keys.SwitchSession):
 			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showCommandDialog {
-				// Load sessions and show the dialog
-				sessions, err := a.app.Sessions.List(context.Background())
-				if err != nil {
-					return a, util.ReportError(err)
-				}
-				if len(sessions) == 0 {
-					return a, util.ReportWarn("No sessions available")
-				}
-				a.sessionDialog.SetSessions(sessions)
-				a.showSessionDialog = true
-				return a, nil
+				return a, util.CmdHandler(showSessionDialogMsg{})
+			}
+			return a, nil
+		case key.Matches(msg, keys.FindSession):
+			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showCommandDialog {
+				return a, util.CmdHandler(showSessionFinderMsg{})
+			}
+			return a, nil
+		case key.Matches(msg, keys.Undo):
+			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showCommandDialog {
+				return a, util.CmdHandler(showUndoDialogMsg{})
+			}
+			return a, nil
+		case key.Matches(msg, keys.Copy):
+			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showCommandDialog {
+				return a, util.CmdHandler(showCopyDialogMsg{})
+			}
+			return a, nil
+		case key.Matches(msg, keys.CopyCodeBlock):
+			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showCommandDialog {
+				return a, util.CmdHandler(copyNextCodeBlockMsg{})
 			}
 			return a, nil
 		case key.Matches(msg, keys.Commands):
+			if a.showCommandDialog {
+				a.showCommandDialog = false
+				return a, nil
+			}
 			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showThemeDialog && !a.showFilepicker {
 				// Show commands dialog
 				if len(a.commands) == 0 {
@@ -506,16 +1324,12 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				return a, nil
 			}
 			if a.currentPage == page.ChatPage && !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showCommandDialog {
-				a.showModelDialog = true
-				return a, nil
+				return a, util.CmdHandler(showModelDialogMsg{})
 			}
 			return a, nil
 		case key.Matches(msg, keys.SwitchTheme):
 			if !a.showQuit && !a.showPermissions && !a.showSessionDialog && !a.showCommandDialog {
-				// Show theme switcher dialog
-				a.showThemeDialog = true
-				// Theme list is dynamically loaded by the dialog component
-				return a, a.themeDialog.Init()
+				return a, util.CmdHandler(showThemeDialogMsg{})
 			}
 			return a, nil
 		case key.Matches(msg, returnKey) || key.Matches(msg):
@@ -616,6 +1430,66 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 	}
 
+	if a.showUndoDialog {
+		d, undoCmd := a.undoDialog.Update(msg)
+		a.undoDialog = d.(dialog.UndoDialog)
+		cmds = append(cmds, undoCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	if a.showCheckpointDialog {
+		d, checkpointCmd := a.checkpointDialog.Update(msg)
+		a.checkpointDialog = d.(dialog.CheckpointDialog)
+		cmds = append(cmds, checkpointCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	if a.showPromptDialog {
+		d, promptCmd := a.promptDialog.Update(msg)
+		a.promptDialog = d.(dialog.PromptDialog)
+		cmds = append(cmds, promptCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	if a.showChangesDialog {
+		d, changesCmd := a.changesDialog.Update(msg)
+		a.changesDialog = d.(dialog.ChangesDialog)
+		cmds = append(cmds, changesCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	if a.showGrantsDialog {
+		d, grantsCmd := a.grantsDialog.Update(msg)
+		a.grantsDialog = d.(dialog.GrantsDialog)
+		cmds = append(cmds, grantsCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	if a.showCopyDialog {
+		d, copyCmd := a.copyDialog.Update(msg)
+		a.copyDialog = d.(dialog.CopyDialog)
+		cmds = append(cmds, copyCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
 	if a.showCommandDialog {
 		d, commandCmd := a.commandDialog.Update(msg)
 		a.commandDialog = d.(dialog.CommandDialog)
@@ -636,31 +1510,155 @@ func (a appModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 	}
 
-	if a.showInitDialog {
-		d, initCmd := a.initDialog.Update(msg)
-		a.initDialog = d.(dialog.InitDialogCmp)
-		cmds = append(cmds, initCmd)
-		// Only block key messages send all other messages down
-		if _, ok := msg.(tea.KeyMsg); ok {
-			return a, tea.Batch(cmds...)
+	if a.showInitDialog {
+		d, initCmd := a.initDialog.Update(msg)
+		a.initDialog = d.(dialog.InitDialogCmp)
+		cmds = append(cmds, initCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	if a.showThemeDialog {
+		d, themeCmd := a.themeDialog.Update(msg)
+		a.themeDialog = d.(dialog.ThemeDialog)
+		cmds = append(cmds, themeCmd)
+		// Only block key messages send all other messages down
+		if _, ok := msg.(tea.KeyMsg); ok {
+			return a, tea.Batch(cmds...)
+		}
+	}
+
+	s, _ := a.status.Update(msg)
+	a.status = s.(core.StatusCmp)
+	t, _ := a.toasts.Update(msg)
+	a.toasts = t.(core.ToastsCmp)
+	// The mouse acts on the dialog, not on the page below it
+	if _, ok := msg.(tea.MouseMsg); ok && a.dialogShown() {
+		return a, tea.Batch(cmds...)
+	}
+	a.pages[a.currentPage], cmd = a.pages[a.currentPage].Update(msg)
+	cmds = append(cmds, cmd)
+	return a, tea.Batch(cmds...)
+}
+
+// restoreSessionSettings switches to the agent, model and settings the
+// session was run with. Sessions that weren't run yet keep the agent in use,
+// with its configured settings.
+func (a *appModel) restoreSessionSettings(sess session.Session) tea.Cmd {
+	if sess.Model != "" {
+		if _, ok := models.SupportedModels[sess.Model]; !ok {
+			return util.ReportWarn(fmt.Sprintf("The model of this session, %s, is not available", sess.Model))
+		}
+	}
+	current := a.app.CoderAgent.Model()
+	currentAgent := a.app.CoderAgent.Name()
+	model, err := a.app.CoderAgent.UseSettings(sess.Settings())
+	if err != nil {
+		return util.ReportWarn(fmt.Sprintf("Keeping the settings of %s: %s", current.Name, err))
+	}
+	if agentName := a.app.CoderAgent.Name(); agentName != currentAgent {
+		return util.ReportInfo(fmt.Sprintf("Agent changed to %s with %s", agentName, model.Name))
+	}
+	if model.ID == current.ID {
+		return nil
+	}
+	return util.ReportInfo(fmt.Sprintf("Model changed to %s", model.Name))
+}
+
+// applyConfig applies the changes of the reloaded config to what reads it
+// only when it's created: the theme, the keys, the tools and providers of the
+// agent, and the settings of the agent in use when its config changed.
+func (a *appModel) applyConfig(reloaded config.Reloaded) tea.Cmd {
+	if reloaded.Err != nil {
+		return util.ReportError(fmt.Errorf("config not reloaded: %w", reloaded.Err))
+	}
+	var cmds []tea.Cmd
+	if themeName := config.Get().TUI.Theme; themeName != theme.CurrentThemeName() {
+		if err := theme.SetTheme(themeName); err != nil {
+			return util.ReportError(err)
+		}
+		var cmd tea.Cmd
+		a.pages[a.currentPage], cmd = a.pages[a.currentPage].Update(dialog.ThemeChangedMsg{ThemeName: themeName})
+		cmds = append(cmds, cmd)
+	}
+	if unknown := layout.ApplyKeybindings(config.Get().Keybindings); len(unknown) > 0 {
+		return util.ReportWarn(fmt.Sprintf("Unknown actions in the keybindings config: %s", strings.Join(unknown, ", ")))
+	}
+	if slices.ContainsFunc(reloaded.Applied, func(section string) bool {
+		return section == "tools" || strings.HasPrefix(section, "agents.")
+	}) {
+		if err := a.app.CoderAgent.ReloadConfig(); err != nil {
+			return util.ReportError(err)
+		}
+	}
+	if agentName := a.app.CoderAgent.Name(); slices.Contains(reloaded.Applied, "agents."+string(agentName)) {
+		if _, err := a.app.CoderAgent.UseAgent(agentName); err != nil {
+			return util.ReportError(err)
+		}
+		if a.selectedSession.ID != "" {
+			if _, err := a.app.Sessions.SetSettings(context.Background(), a.selectedSession.ID, a.app.CoderAgent.Settings()); err != nil {
+				return util.ReportError(err)
+			}
+		}
+	}
+
+	switch {
+	case len(reloaded.Applied) == 0:
+		cmds = append(cmds, util.ReportWarn("Restart to apply the config changes to: "+strings.Join(reloaded.Restart, ", ")))
+	case len(reloaded.Restart) > 0:
+		cmds = append(cmds, util.ReportWarn(fmt.Sprintf("Config reloaded: %s. Restart to apply the changes to: %s",
+			strings.Join(reloaded.Applied, ", "), strings.Join(reloaded.Restart, ", "))))
+	default:
+		cmds = append(cmds, util.ReportInfo("Config reloaded: "+strings.Join(reloaded.Applied, ", ")))
+	}
+	return tea.Batch(cmds...)
+}
+
+// reloadSessionDialog refreshes the sessions of the session dialog after one
+// was renamed or deleted, and closes it when none are left.
+func (a *appModel) reloadSessionDialog() tea.Cmd {
+	sessions, err := a.app.Sessions.List(context.Background())
+	if err != nil {
+		return util.ReportError(err)
+	}
+	if len(sessions) == 0 {
+		a.showSessionDialog = false
+		return nil
+	}
+	a.sessionDialog.SetSessions(sessions)
+	return nil
+}
+
+// lastResponse returns the last assistant message of the current session that
+// has text, or an empty message when there is none.
+func (a *appModel) lastResponse() (message.Message, error) {
+	messages, err := a.app.Messages.List(context.Background(), a.selectedSession.ID)
+	if err != nil {
+		return message.Message{}, err
+	}
+	for i := len(messages) - 1; i >= 0; i-- {
+		if messages[i].Role == message.Assistant && strings.TrimSpace(messages[i].Content().String()) != "" {
+			return messages[i], nil
 		}
 	}
+	return message.Message{}, nil
+}
 
-	if a.showThemeDialog {
-		d, themeCmd := a.themeDialog.Update(msg)
-		a.themeDialog = d.(dialog.ThemeDialog)
-		cmds = append(cmds, themeCmd)
-		// Only block key messages send all other messages down
-		if _, ok := msg.(tea.KeyMsg); ok {
-			return a, tea.Batch(cmds...)
+// lastPrompt returns the last prompt of the current session, or an empty
+// message when there is none.
+func (a *appModel) lastPrompt() (message.Message, error) {
+	messages, err := a.app.Messages.List(context.Background(), a.selectedSession.ID)
+	if err != nil {
+		return message.Message{}, err
+	}
+	for i := len(messages) - 1; i >= 0; i-- {
+		if messages[i].Role == message.User {
+			return messages[i], nil
 		}
 	}
-
-	s, _ := a.status.Update(msg)
-	a.status = s.(core.StatusCmp)
-	a.pages[a.currentPage], cmd = a.pages[a.currentPage].Update(msg)
-	cmds = append(cmds, cmd)
-	return a, tea.Batch(cmds...)
+	return message.Message{}, nil
 }
 
 // RegisterCommand adds a command to the command dialog
@@ -699,6 +1697,27 @@ func (a *appModel) moveToPage(pageID page.PageID) tea.Cmd {
 	return tea.Batch(cmds...)
 }
 
+// pendingWork describes the work that quitting would lose, it's empty when
+// there is none
+func (a appModel) pendingWork() string {
+	switch {
+	case a.showPermissions:
+		return "A tool is waiting for your permission."
+	case a.app.CoderAgent.IsBusy():
+		return "A response is still being generated."
+	}
+	return ""
+}
+
+// dialogShown reports whether a dialog is shown over the page
+func (a appModel) dialogShown() bool {
+	return a.showPermissions || a.showHelp || a.showQuit || a.showSessionDialog ||
+		a.showSessionFinder || a.showMessageSearch || a.showUndoDialog || a.showCheckpointDialog ||
+		a.showPromptDialog || a.showChangesDialog || a.showGrantsDialog || a.showCopyDialog || a.showCommandDialog ||
+		a.showModelDialog || a.showInitDialog || a.showFilepicker || a.showThemeDialog ||
+		a.showMultiArgumentsDialog || a.showNewSessionDialog
+}
+
 func (a appModel) View() string {
 	components := []string{
 		a.pages[a.currentPage].View(),
@@ -809,6 +1828,36 @@ func (a appModel) View() string {
 		)
 	}
 
+	if a.showSessionFinder {
+		overlay := a.sessionFinderDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	if a.showMessageSearch {
+		overlay := a.messageSearchDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
 	if a.showSessionDialog {
 		overlay := a.sessionDialog.View()
 		row := lipgloss.Height(appView) / 2
@@ -824,6 +1873,96 @@ func (a appModel) View() string {
 		)
 	}
 
+	if a.showUndoDialog {
+		overlay := a.undoDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	if a.showCheckpointDialog {
+		overlay := a.checkpointDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	if a.showPromptDialog {
+		overlay := a.promptDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	if a.showChangesDialog {
+		overlay := a.changesDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	if a.showGrantsDialog {
+		overlay := a.grantsDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	if a.showCopyDialog {
+		overlay := a.copyDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
 	if a.showModelDialog {
 		overlay := a.modelDialog.View()
 		row := lipgloss.Height(appView) / 2
@@ -895,18 +2034,59 @@ func (a appModel) View() string {
 		)
 	}
 
-	return appView
+	if a.showNewSessionDialog {
+		overlay := a.newSessionDialog.View()
+		row := lipgloss.Height(appView) / 2
+		row -= lipgloss.Height(overlay) / 2
+		col := lipgloss.Width(appView) / 2
+		col -= lipgloss.Width(overlay) / 2
+		appView = layout.PlaceOverlay(
+			col,
+			row,
+			overlay,
+			appView,
+			true,
+		)
+	}
+
+	// Notifications stay on top of everything, in the top right corner
+	if !a.toasts.Empty() {
+		overlay := a.toasts.View()
+		appView = layout.PlaceOverlay(
+			max(0, lipgloss.Width(appView)-lipgloss.Width(overlay)-1),
+			1,
+			overlay,
+			appView,
+			false,
+		)
+	}
+
+	// Resolve the clickable zones and strip their markers
+	return zone.Scan(appView)
 }
 
 func New(app *app.App) tea.Model {
+	// Bindings are copied into components when they're created, so the
+	// configured keys are set first
+	if unknown := layout.ApplyKeybindings(config.Get().Keybindings); len(unknown) > 0 {
+		logging.WarnPersist(fmt.Sprintf("Unknown actions in the keybindings config: %s", strings.Join(unknown, ", ")))
+	}
+	// The pages read the saved state when they're created
+	state.Load(config.Get().Data.Directory)
 	startPage := page.ChatPage
 	model := &appModel{
 		currentPage:   startPage,
 		loadedPages:   make(map[page.PageID]bool),
-		status:        core.NewStatusCmp(app.LSPClients),
+		status:        core.NewStatusCmp(app.LSPClients, app.CoderAgent),
+		toasts:        core.NewToastsCmp(),
 		help:          dialog.NewHelpCmp(),
 		quit:          dialog.NewQuitCmp(),
 		sessionDialog: dialog.NewSessionDialogCmp(),
+		undoDialog:    dialog.NewUndoDialogCmp(),
+		promptDialog:  dialog.NewPromptDialogCmp(),
+		changesDialog: dialog.NewChangesDialogCmp(),
+		grantsDialog:  dialog.NewGrantsDialogCmp(),
+		copyDialog:    dialog.NewCopyDialogCmp(),
 		commandDialog: dialog.NewCommandDialogCmp(),
 		modelDialog:   dialog.NewModelDialogCmp(),
 		permissions:   dialog.NewPermissionDialogCmp(),
@@ -918,20 +2098,104 @@ func New(app *app.App) tea.Model {
 			page.ChatPage: page.NewChatPage(app),
 			page.LogsPage: page.NewLogsPage(),
 		},
-		filepicker: dialog.NewFilepickerCmp(app),
+		filepicker:          dialog.NewFilepickerCmp(app),
+		sessionFinderDialog: dialog.NewSessionFinderDialogCmp(),
+		messageSearchDialog: dialog.NewMessageSearchDialogCmp(),
+		checkpointDialog:    dialog.NewCheckpointDialogCmp(),
 	}
 
+	model.RegisterCommand(dialog.Command{
+		ID:          "new_session",
+		Title:       "New Session",
+		Description: "Start a new session, optionally naming it",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(dialog.ShowNewSessionDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "switch_session",
+		Title:       "Switch Session",
+		Description: "Switch to, rename or delete another session",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showSessionDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "prune_sessions",
+		Title:       "Prune Sessions",
+		Description: "Archive the sessions inactive for " + defaultPruneAge,
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(pruneSessionsMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "toggle_sidebar",
+		Title:       "Toggle Sidebar",
+		Description: "Show or hide the session sidebar",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(chat.ToggleSidebarMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "find_session",
+		Title:       "Find Session",
+		Description: "Search sessions by title and first message",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showSessionFinderMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "search_messages",
+		Title:       "Search Messages",
+		Description: "Find words in the messages of all sessions",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showMessageSearchMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "switch_model",
+		Title:       "Switch Model",
+		Description: "Choose the model of the coder agent",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showModelDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "switch_theme",
+		Title:       "Switch Theme",
+		Description: "Choose the colors of the interface",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showThemeDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "logs",
+		Title:       "View Logs",
+		Description: "Show the log messages of this run",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(page.PageChangeMsg{ID: page.LogsPage})
+		},
+	})
+
 	model.RegisterCommand(dialog.Command{
 		ID:          "init",
 		Title:       "Initialize Project",
-		Description: "Create/Update the Cryoncode.md memory file",
+		Description: "Create/Update the AGENTS.md memory file",
 		Handler: func(cmd dialog.Command) tea.Cmd {
-			prompt := `Please analyze this codebase and create a Cryoncode.md file containing:
+			prompt := `Please analyze this codebase and create an AGENTS.md file at the root of the project containing:
 1. Build/lint/test commands - especially for running a single test
 2. Code style guidelines including imports, formatting, types, naming conventions, error handling, etc.
 
 The file you create will be given to agentic coding agents (such as yourself) that operate in this repository. Make it about 20 lines long.
-If there's already a cryoncode.md, improve it.
+If there's already an AGENTS.md, OPENCODE.md or cryoncode.md, improve it.
 If there are Cursor rules (in .cursor/rules/ or .cursorrules) or Copilot rules (in .github/copilot-instructions.md), make sure to include them.`
 			return tea.Batch(
 				util.CmdHandler(chat.SendMsg{
@@ -951,6 +2215,105 @@ If there are Cursor rules (in .cursor/rules/ or .cursorrules) or Copilot rules (
 			}
 		},
 	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "undo",
+		Title:       "Undo File Changes",
+		Description: "Revert the file changes made since a chosen prompt",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showUndoDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "checkpoints",
+		Title:       "Restore Checkpoint",
+		Description: "Rewind the conversation and the files to the end of an earlier turn",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showCheckpointDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "fork",
+		Title:       "Fork Session",
+		Description: "Continue a copy of the session from the response to a chosen prompt",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showPromptDialogMsg{Action: dialog.ForkAtPrompt})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "retry",
+		Title:       "Retry Response",
+		Description: "Send the last prompt again in place of the responses to it",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(retryResponseMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "delete_message",
+		Title:       "Delete Message",
+		Description: "Delete a prompt or response of the session, with the results of its tool calls",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showPromptDialogMsg{Action: dialog.DeleteMessage})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "edit_prompt",
+		Title:       "Edit Prompt",
+		Description: "Edit a prompt of the session and send it again, removing the messages after it",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showPromptDialogMsg{Action: dialog.EditPrompt})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "changes",
+		Title:       "Files Changed",
+		Description: "List the files changed in the current session and revert them one by one",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showChangesDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "permissions",
+		Title:       "Manage Permissions",
+		Description: "Edit or revoke the permissions that are always allowed",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showGrantsDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "copy",
+		Title:       "Copy Last Response",
+		Description: "Copy the last response or one of its code blocks to the clipboard",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(showCopyDialogMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "copy_code_block",
+		Title:       "Copy Next Code Block",
+		Description: "Copy the next code block of the last response, starting over after the last one",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(copyNextCodeBlockMsg{})
+		},
+	})
+
+	model.RegisterCommand(dialog.Command{
+		ID:          "export_session",
+		Title:       "Export Session",
+		Description: "Copy the session as a Markdown transcript",
+		Handler: func(cmd dialog.Command) tea.Cmd {
+			return util.CmdHandler(exportSessionMsg{})
+		},
+	})
 	// Load custom commands
 	customCommands, err := dialog.LoadCustomCommands()
 	if err != nil {
@@ -0,0 +1,73 @@
+package util
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/x/ansi"
+)
+
+// TabWidth is the number of columns between tab stops, the same as lipgloss
+// uses when it renders tabs
+const TabWidth = 4
+
+// Truncate cuts the text to the number of terminal cells, ending it with an
+// ellipsis when it is cut. Wide characters like CJK and emoji take two cells
+// and are never split, neither are graphemes or escape sequences.
+func Truncate(text string, width int) string {
+	if width <= 0 {
+		return ""
+	}
+	if ansi.StringWidth(text) <= width {
+		return text
+	}
+	if width <= 3 {
+		return ansi.Truncate(text, width, "")
+	}
+	return ansi.Truncate(text, width, "...")
+}
+
+// TruncateLeft cuts the beginning of the text to the number of terminal
+// cells, starting it with an ellipsis when it is cut. It keeps the end of
+// paths, which is the part that tells them apart.
+func TruncateLeft(text string, width int) string {
+	if width <= 0 {
+		return ""
+	}
+	textWidth := ansi.StringWidth(text)
+	if textWidth <= width {
+		return text
+	}
+	if width <= 3 {
+		return ansi.TruncateLeft(text, textWidth-width, "")
+	}
+	return "..." + ansi.TruncateLeft(text, textWidth-width+3, "")
+}
+
+// ExpandTabs replaces tabs with the spaces up to the next tab stop. Columns
+// are counted in terminal cells, so text aligned with tabs stays aligned
+// after wide characters, and escape sequences take no room.
+func ExpandTabs(text string) string {
+	if !strings.Contains(text, "\t") {
+		return text
+	}
+	lines := strings.Split(text, "\n")
+	for i, line := range lines {
+		if !strings.Contains(line, "\t") {
+			continue
+		}
+		var b strings.Builder
+		column := 0
+		segments := strings.Split(line, "\t")
+		for j, segment := range segments {
+			b.WriteString(segment)
+			column += ansi.StringWidth(segment)
+			if j < len(segments)-1 {
+				spaces := TabWidth - column%TabWidth
+				b.WriteString(strings.Repeat(" ", spaces))
+				column += spaces
+			}
+		}
+		lines[i] = b.String()
+	}
+	return strings.Join(lines, "\n")
+}
@@ -0,0 +1,38 @@
+package util
+
+import (
+	"testing"
+
+	"github.com/charmbracelet/x/ansi"
+	"github.com/stretchr/testify/assert"
+)
+
+func TestTruncate(t *testing.T) {
+	assert.Equal(t, "short", Truncate("short", 10))
+	assert.Equal(t, "a long...", Truncate("a long title", 9))
+	assert.Equal(t, "ééé...", Truncate("éééééé", 6))
+	// Wide characters take two cells and aren't cut in half
+	assert.Equal(t, "日本...", Truncate("日本語のタイトル", 8))
+	assert.Equal(t, "日本...", Truncate("日本語のタイトル", 7))
+	// Emoji made of several code points stay whole
+	assert.Equal(t, "👩‍💻👩‍💻", Truncate("👩‍💻👩‍💻", 4))
+	assert.Equal(t, "ab", Truncate("abcdef", 2))
+	assert.Equal(t, "", Truncate("abc", 0))
+}
+
+func TestTruncateLeft(t *testing.T) {
+	assert.Equal(t, "src/main.go", TruncateLeft("src/main.go", 20))
+	assert.Equal(t, ".../main.go", TruncateLeft("/home/user/project/src/main.go", 11))
+	assert.Equal(t, "...ル.go", TruncateLeft("ファイル.go", 8))
+}
+
+func TestExpandTabs(t *testing.T) {
+	assert.Equal(t, "no tabs", ExpandTabs("no tabs"))
+	assert.Equal(t, "a   b", ExpandTabs("a\tb"))
+	assert.Equal(t, "abcd    e\n    f", ExpandTabs("abcd\te\n\tf"))
+	// Wide characters count for two columns
+	assert.Equal(t, "日本    x", ExpandTabs("日本\tx"))
+	// Escape sequences take no room
+	red := ansi.Style{}.ForegroundColor(ansi.Red).String()
+	assert.Equal(t, red+"ab"+ansi.ResetStyle+"  c", ExpandTabs(red+"ab"+ansi.ResetStyle+"\tc"))
+}
@@ -41,14 +41,13 @@ func ReportWarn(warn string) tea.Cmd {
 	})
 }
 
-type (
-	InfoMsg struct {
-		Type InfoType
-		Msg  string
-		TTL  time.Duration
-	}
-	ClearStatusMsg struct{}
-)
+// InfoMsg is shown to the user as a notification. TTL overrides how long it
+// stays on screen.
+type InfoMsg struct {
+	Type InfoType
+	Msg  string
+	TTL  time.Duration
+}
 
 func Clamp(v, low, high int) int {
 	if high < low {